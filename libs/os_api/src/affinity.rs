@@ -0,0 +1,189 @@
+use std::thread;
+use std::time::Duration;
+
+use crate::{OS, OsApiError, ProcessInfo};
+
+/// Tries made to apply affinity and priority to a freshly started process.
+pub(crate) const LAUNCH_SETTINGS_ATTEMPTS: u32 = 3;
+/// Pause between those tries; a loaded system may need a moment after process creation.
+pub(crate) const LAUNCH_SETTINGS_RETRY_DELAY: Duration = Duration::from_millis(50);
+
+impl OS {
+    /// Calls `apply` up to `attempts` times, sleeping `delay` between tries, and returns
+    /// the last result with the number of tries made. Permanent errors such as access
+    /// denied are returned at once.
+    pub(crate) fn retry_transient<T>(
+        attempts: u32,
+        delay: Duration,
+        mut apply: impl FnMut() -> Result<T, OsApiError>,
+    ) -> (Result<T, OsApiError>, u32) {
+        let mut tries = 1;
+        loop {
+            match apply() {
+                Err(err) if !err.is_permanent() && tries < attempts => {
+                    thread::sleep(delay);
+                    tries += 1;
+                }
+                result => return (result, tries),
+            }
+        }
+    }
+
+    /// Every running process whose affinity and priority can be read, sorted by name.
+    /// Processes that deny access or exit during the scan are left out.
+    pub fn list_processes_with_affinity() -> Vec<ProcessInfo> {
+        let mut processes: Vec<ProcessInfo> = Self::get_all_process_names()
+            .into_iter()
+            .filter_map(|(pid, name)| {
+                Some(ProcessInfo {
+                    pid,
+                    name,
+                    affinity_mask: Self::get_process_affinity(pid).ok()?,
+                    priority: Self::get_process_priority(pid).ok()?,
+                })
+            })
+            .collect();
+        processes.sort_by_cached_key(|process| (process.name.to_lowercase(), process.pid));
+        processes
+    }
+
+    /// Returns true when the affinity read back from a process is not the mask that was
+    /// requested, e.g. because the OS narrowed it to the CPUs that are actually available.
+    pub fn effective_affinity_differs(requested: usize, effective: usize) -> bool {
+        requested != 0 && effective != requested
+    }
+
+    /// Affinity mask with a bit for each of `cores`. Masks are `usize`, so on a 64-bit
+    /// build cores 32..64 get their bit even for a 32-bit target process. Cores beyond
+    /// the mask width are left out rather than overflowing the shift.
+    pub fn mask_from_cores(cores: impl IntoIterator<Item = usize>) -> usize {
+        cores.into_iter().fold(0usize, |mask, core| {
+            mask | u32::try_from(core)
+                .ok()
+                .and_then(|core| 1usize.checked_shl(core))
+                .unwrap_or(0)
+        })
+    }
+
+    /// Returns the cores from `cores` that are missing from `available_mask`, such as
+    /// threads disabled in firmware or offline. Cores beyond the mask width are not judged.
+    pub fn cores_outside_mask(cores: &[usize], available_mask: usize) -> Vec<usize> {
+        cores
+            .iter()
+            .copied()
+            .filter(|&core| core < usize::BITS as usize && available_mask & (1 << core) == 0)
+            .collect()
+    }
+
+    /// Busy fraction (0.0..=1.0) of every core between two `(idle, total)` time samples
+    /// indexed by core. Cores missing from either sample read as idle.
+    pub fn core_usage_between(before: &[(u64, u64)], after: &[(u64, u64)]) -> Vec<f32> {
+        after
+            .iter()
+            .enumerate()
+            .map(|(core, &(idle_after, total_after))| {
+                let Some(&(idle_before, total_before)) = before.get(core) else {
+                    return 0.0;
+                };
+                let total = total_after.saturating_sub(total_before);
+                let idle = idle_after.saturating_sub(idle_before);
+                if total == 0 {
+                    0.0
+                } else {
+                    (total.saturating_sub(idle) as f32 / total as f32).clamp(0.0, 1.0)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    use crate::{OS, OsApiError};
+
+    #[test]
+    fn test_retry_transient_retries_only_transient_errors() {
+        let calls = Cell::new(0);
+        let (result, tries) = OS::retry_transient(3, Duration::ZERO, || {
+            calls.set(calls.get() + 1);
+            if calls.get() < 2 {
+                Err(OsApiError::Io("busy".into()))
+            } else {
+                Ok(calls.get())
+            }
+        });
+        assert_eq!((result, tries), (Ok(2), 2));
+
+        let (result, tries) = OS::retry_transient(3, Duration::ZERO, || {
+            Err::<(), _>(OsApiError::Win {
+                code: 1,
+                message: "busy".into(),
+            })
+        });
+        assert!(result.is_err());
+        assert_eq!(tries, 3);
+
+        let (result, tries) = OS::retry_transient(3, Duration::ZERO, || {
+            Err::<(), _>(OsApiError::AccessDenied("denied".into()))
+        });
+        assert!(result.unwrap_err().is_access_denied());
+        assert_eq!(tries, 1);
+    }
+
+    #[test]
+    fn test_effective_affinity_differs_flags_narrowed_or_changed_masks() {
+        assert!(!OS::effective_affinity_differs(0b1111, 0b1111));
+        assert!(OS::effective_affinity_differs(0b1111, 0b0011));
+        assert!(OS::effective_affinity_differs(0b0011, 0b1100));
+        assert!(!OS::effective_affinity_differs(0, 0b1111));
+    }
+
+    #[test]
+    fn test_cores_outside_mask_lists_unavailable_cores() {
+        assert_eq!(OS::cores_outside_mask(&[0, 1, 2, 3], 0b0101), vec![1, 3]);
+        assert!(OS::cores_outside_mask(&[0, 2], 0b0101).is_empty());
+        assert!(OS::cores_outside_mask(&[usize::BITS as usize + 1], 0).is_empty());
+    }
+
+    #[test]
+    fn test_mask_from_cores_sets_high_bits_and_skips_out_of_range_cores() {
+        assert_eq!(OS::mask_from_cores([0, 2, 3]), 0b1101);
+        assert_eq!(OS::mask_from_cores([]), 0);
+        assert_eq!(OS::mask_from_cores([usize::BITS as usize, usize::MAX]), 0);
+        #[cfg(target_pointer_width = "64")]
+        {
+            assert_eq!(OS::mask_from_cores([31]), 0x8000_0000);
+            assert_eq!(OS::mask_from_cores([1, 33]), (1 << 33) | 0b10);
+            assert_eq!(OS::mask_from_cores([63]), 1 << 63);
+        }
+    }
+
+    #[test]
+    fn test_core_usage_between_reports_busy_fraction_per_core() {
+        let before = [(100, 200), (50, 100), (0, 0)];
+        let after = [(200, 400), (50, 200), (0, 0), (10, 20)];
+        assert_eq!(
+            OS::core_usage_between(&before, &after),
+            vec![0.5, 1.0, 0.0, 0.0]
+        );
+    }
+
+    #[test]
+    fn test_list_processes_with_affinity_includes_this_process() {
+        let own_pid = std::process::id();
+        let processes = OS::list_processes_with_affinity();
+        let own = processes
+            .iter()
+            .find(|process| process.pid == own_pid)
+            .expect("own process listed");
+
+        assert_eq!(
+            own.affinity_mask,
+            OS::get_process_affinity(own_pid).unwrap()
+        );
+        assert!(!own.name.is_empty());
+    }
+}
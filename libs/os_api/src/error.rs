@@ -0,0 +1,153 @@
+use std::fmt;
+
+/// Error returned by the process-control calls of `OS`.
+///
+/// Every variant carries the full message; `Display` prints it unchanged so logs read
+/// the same as when these calls returned plain strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OsApiError {
+    /// The caller lacks the rights for the target; running elevated may help
+    AccessDenied(String),
+    /// The process or thread has exited or never existed
+    ProcessNotFound(String),
+    /// The operation has no implementation on this platform
+    Unsupported(String),
+    /// Any other Windows failure, with its HRESULT
+    Win { code: i32, message: String },
+    /// Any other OS or I/O failure
+    Io(String),
+}
+
+/// HRESULTs of the `CreateProcessW` failures `launch_hint` explains.
+const HRESULT_FILE_NOT_FOUND: i32 = 0x8007_0002_u32 as i32;
+const HRESULT_PATH_NOT_FOUND: i32 = 0x8007_0003_u32 as i32;
+const HRESULT_BAD_EXE_FORMAT: i32 = 0x8007_00C1_u32 as i32;
+
+impl OsApiError {
+    pub fn message(&self) -> &str {
+        match self {
+            OsApiError::AccessDenied(message)
+            | OsApiError::ProcessNotFound(message)
+            | OsApiError::Unsupported(message)
+            | OsApiError::Win { message, .. }
+            | OsApiError::Io(message) => message,
+        }
+    }
+
+    pub fn is_access_denied(&self) -> bool {
+        matches!(self, OsApiError::AccessDenied(_))
+    }
+
+    pub fn is_process_not_found(&self) -> bool {
+        matches!(self, OsApiError::ProcessNotFound(_))
+    }
+
+    /// Whether trying again cannot help: missing rights, a vanished target or a
+    /// missing implementation.
+    pub fn is_permanent(&self) -> bool {
+        matches!(
+            self,
+            OsApiError::AccessDenied(_)
+                | OsApiError::ProcessNotFound(_)
+                | OsApiError::Unsupported(_)
+        )
+    }
+
+    /// What the user can do about a failed launch, for the common ways starting a
+    /// program fails. Elevation and access errors share `AccessDenied`.
+    pub fn launch_hint(&self) -> Option<&'static str> {
+        match self {
+            OsApiError::AccessDenied(_) => {
+                Some("it needs administrator rights; enable Run as administrator for this tool")
+            }
+            OsApiError::Win { code, .. }
+                if *code == HRESULT_FILE_NOT_FOUND || *code == HRESULT_PATH_NOT_FOUND =>
+            {
+                Some("the binary path is wrong; use Re-resolve in the program's settings")
+            }
+            OsApiError::Win {
+                code: HRESULT_BAD_EXE_FORMAT,
+                ..
+            } => Some("this is not a runnable .exe"),
+            _ => None,
+        }
+    }
+
+    /// The same kind of error with a different message, e.g. to add context.
+    #[cfg_attr(not(windows), allow(dead_code))]
+    pub(crate) fn with_message(self, message: String) -> Self {
+        match self {
+            OsApiError::AccessDenied(_) => OsApiError::AccessDenied(message),
+            OsApiError::ProcessNotFound(_) => OsApiError::ProcessNotFound(message),
+            OsApiError::Unsupported(_) => OsApiError::Unsupported(message),
+            OsApiError::Win { code, .. } => OsApiError::Win { code, message },
+            OsApiError::Io(_) => OsApiError::Io(message),
+        }
+    }
+}
+
+impl fmt::Display for OsApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for OsApiError {}
+
+impl From<OsApiError> for String {
+    fn from(err: OsApiError) -> Self {
+        err.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OsApiError;
+
+    #[test]
+    fn test_display_is_the_bare_message() {
+        let err = OsApiError::Win {
+            code: -2147024891,
+            message: "Failed to set affinity mask for process 42: Access is denied.".into(),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Failed to set affinity mask for process 42: Access is denied."
+        );
+        assert_eq!(String::from(err.clone()), err.to_string());
+        assert!(OsApiError::AccessDenied("denied".into()).is_access_denied());
+        assert!(!OsApiError::Io("denied".into()).is_access_denied());
+    }
+
+    #[test]
+    fn test_launch_hint_covers_common_create_process_failures() {
+        let win = |code: u32| OsApiError::Win {
+            code: code as i32,
+            message: "run failed".into(),
+        };
+        assert!(
+            win(0x8007_0002)
+                .launch_hint()
+                .unwrap()
+                .contains("Re-resolve")
+        );
+        assert!(
+            win(0x8007_0003)
+                .launch_hint()
+                .unwrap()
+                .contains("Re-resolve")
+        );
+        assert_eq!(
+            win(0x8007_00C1).launch_hint(),
+            Some("this is not a runnable .exe")
+        );
+        assert!(
+            OsApiError::AccessDenied("denied".into())
+                .launch_hint()
+                .unwrap()
+                .contains("Run as administrator")
+        );
+        assert_eq!(win(0x8000_4005).launch_hint(), None);
+        assert_eq!(OsApiError::Io("run failed".into()).launch_hint(), None);
+    }
+}
@@ -1,11 +1,17 @@
+mod affinity;
 mod catalog;
+mod error;
 mod process;
 mod shortcut;
 pub use catalog::{
     InstalledAppCatalogEntry, InstalledAppCatalogSource, InstalledAppCatalogTarget,
     InstalledPackageRuntimeInfo,
 };
-pub use process::PriorityClass;
+pub use error::OsApiError;
+pub use process::{
+    IoPriority, LaunchedProcess, PriorityClass, PriorityClassName, ProcessInfo, ProcessState,
+    ThreadInfo, WindowShow,
+};
 pub use shortcut::ShortcutSpec;
 
 #[cfg(target_os = "linux")]
@@ -15,9 +21,12 @@ mod windows;
 
 // Export the necessary implementation under a common interface
 #[cfg(target_os = "linux")]
-pub use linux::OS;
+pub use linux::{
+    LocalIpcClientError, LocalIpcEndpoint, LocalIpcGuard, LocalIpcRequest, LocalIpcServer,
+    LocalIpcWake, OS, ProcessWatch,
+};
 #[cfg(target_os = "windows")]
 pub use windows::{
     LocalIpcClientError, LocalIpcEndpoint, LocalIpcGuard, LocalIpcRequest, LocalIpcServer,
-    LocalIpcWake, OS,
+    LocalIpcWake, OS, ProcessWatch,
 };
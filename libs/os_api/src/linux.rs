@@ -1,7 +1,9 @@
-use std::collections::{HashMap, HashSet};
+use std::cell::Cell;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
 use std::fs;
 use std::io;
+use std::os::unix::ffi::OsStrExt;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 use std::process::{Child, Command, Stdio};
@@ -11,20 +13,36 @@ use std::thread;
 use std::time::Duration;
 
 use libc::{
-    PRIO_PROCESS, SCHED_FIFO, SCHED_RR, getpriority, pid_t, sched_getscheduler, sched_param,
-    sched_setscheduler, setpriority,
+    EACCES, ENOENT, EPERM, ESRCH, PRIO_PROCESS, SCHED_FIFO, SCHED_IDLE, SCHED_OTHER, SCHED_RR,
+    SIGKILL, getpriority, kill, pid_t, sched_getscheduler, sched_param, sched_setscheduler,
+    setpriority,
 };
 use nix::sched::{CpuSet, sched_getaffinity, sched_setaffinity};
 use nix::unistd::Pid;
 
-use crate::PriorityClass;
+use crate::affinity::{LAUNCH_SETTINGS_ATTEMPTS, LAUNCH_SETTINGS_RETRY_DELAY};
 use crate::{
     InstalledAppCatalogEntry, InstalledAppCatalogSource, InstalledAppCatalogTarget,
     InstalledPackageRuntimeInfo, ShortcutSpec,
 };
+use crate::{
+    IoPriority, LaunchedProcess, OsApiError, PriorityClass, PriorityClassName, ProcessState,
+    ThreadInfo, WindowShow,
+};
+
+mod ipc;
+
+pub use ipc::{
+    LocalIpcClientError, LocalIpcEndpoint, LocalIpcGuard, LocalIpcRequest, LocalIpcServer,
+    LocalIpcWake,
+};
 
 pub struct OS;
 
+/// cgroup v2 group under which per-process CPU limits are created.
+const CPU_LIMIT_CGROUP: &str = "cpu-affinity-tool";
+const CPU_MAX_PERIOD_US: u64 = 100_000;
+
 pub struct ProcessTree {
     pub parent_of: HashMap<u32, u32>,
     pub children_of: HashMap<u32, Vec<u32>>,
@@ -35,6 +53,7 @@ pub struct ProcessTree {
 struct DesktopEntry {
     name: String,
     exec: String,
+    icon: Option<String>,
     hidden: bool,
     no_display: bool,
     entry_type: Option<String>,
@@ -48,6 +67,46 @@ struct PostSpawnSettingsResult {
 
 static CHILD_REAPER: OnceLock<Sender<Child>> = OnceLock::new();
 
+/// Explains a refused `SCHED_FIFO` request in place of the bare errno.
+fn realtime_denied_message(fell_back_to_high: bool) -> String {
+    let outcome = if fell_back_to_high {
+        "High priority was set instead"
+    } else {
+        "High priority could not be set either"
+    };
+    format!(
+        "Realtime priority needs root or CAP_SYS_NICE (e.g. `sudo setcap cap_sys_nice+ep` on this tool); {outcome}"
+    )
+}
+
+/// `APPIMAGE` value of a NUL-separated `/proc/<pid>/environ` block.
+fn appimage_from_environ(environ: &[u8]) -> Option<PathBuf> {
+    environ
+        .split(|byte| *byte == 0)
+        .find_map(|var| var.strip_prefix(b"APPIMAGE="))
+        .filter(|value| !value.is_empty())
+        .map(|value| PathBuf::from(std::ffi::OsStr::from_bytes(value)))
+}
+
+/// Follows a launched process. The child reaper may collect it before the exit code
+/// is read, so the code is best effort here.
+pub struct ProcessWatch {
+    pid: u32,
+}
+
+impl ProcessWatch {
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn state(&self) -> ProcessState {
+        fs::read_to_string(OS::proc_path(self.pid, "stat"))
+            .ok()
+            .and_then(|stat| OS::parse_stat_state(&stat))
+            .unwrap_or(ProcessState::Exited(None))
+    }
+}
+
 impl OS {
     pub const fn supports_hide_to_tray() -> bool {
         false
@@ -57,6 +116,14 @@ impl OS {
         true
     }
 
+    pub const fn supports_window_lookup() -> bool {
+        false
+    }
+
+    pub const fn supports_job_objects() -> bool {
+        false
+    }
+
     pub fn create_shortcut(_spec: ShortcutSpec) -> Result<(), String> {
         Err("shortcut creation is only supported on Windows".to_string())
     }
@@ -113,41 +180,103 @@ impl OS {
         Pid::from_raw(pid as i32)
     }
 
-    fn set_priority_for_pid(pid: pid_t, priority: PriorityClass) -> Result<(), String> {
-        match priority {
-            PriorityClass::Realtime => {
+    /// Classifies an OS error so callers can tell a vanished process from missing rights.
+    fn api_error(err: &io::Error, message: String) -> OsApiError {
+        match err.raw_os_error() {
+            Some(EPERM | EACCES) => OsApiError::AccessDenied(message),
+            Some(ESRCH | ENOENT) => OsApiError::ProcessNotFound(message),
+            _ => OsApiError::Io(message),
+        }
+    }
+
+    fn last_api_error() -> OsApiError {
+        let err = io::Error::last_os_error();
+        Self::api_error(&err, err.to_string())
+    }
+
+    fn errno_api_error(errno: nix::errno::Errno) -> OsApiError {
+        Self::api_error(
+            &io::Error::from_raw_os_error(errno as i32),
+            errno.to_string(),
+        )
+    }
+
+    fn set_priority_for_pid(pid: pid_t, priority: PriorityClass) -> Result<(), OsApiError> {
+        // There is no EcoQoS on Linux; SCHED_IDLE is the closest "background work" policy.
+        // The nice value still follows the class so it reads back the same.
+        if priority.eco_qos() {
+            Self::set_policy(pid, SCHED_IDLE)?;
+            return Self::set_nice(pid, priority.class_name());
+        }
+
+        match priority.class_name() {
+            PriorityClassName::Realtime => {
                 let params = sched_param { sched_priority: 50 };
                 let ret = unsafe { sched_setscheduler(pid, SCHED_FIFO, &params) };
                 if ret == 0 {
-                    Ok(())
-                } else {
-                    Err(io::Error::last_os_error().to_string())
+                    return Ok(());
                 }
-            }
-            _ => {
-                let nice = Self::to_nice(priority);
-                let ret = unsafe { setpriority(PRIO_PROCESS, pid as u32, nice) };
-                if ret == 0 {
-                    Ok(())
-                } else {
-                    Err(io::Error::last_os_error().to_string())
+                let err = Self::last_api_error();
+                if !err.is_access_denied() {
+                    return Err(err);
                 }
+                // Without CAP_SYS_NICE the boost High gives may still be allowed by
+                // RLIMIT_NICE, so try it rather than leave the process untouched.
+                let fell_back =
+                    Self::set_nice_with_default_policy(pid, PriorityClassName::High).is_ok();
+                Err(OsApiError::AccessDenied(realtime_denied_message(fell_back)))
             }
+            class => Self::set_nice_with_default_policy(pid, class),
         }
     }
 
-    fn to_nice(priority: PriorityClass) -> i32 {
-        match priority {
-            PriorityClass::Idle => 19,
-            PriorityClass::BelowNormal => 10,
-            PriorityClass::Normal => 0,
-            PriorityClass::AboveNormal => -5,
-            PriorityClass::High => -10,
-            PriorityClass::Realtime => -20,
+    /// Leaves eco (`SCHED_IDLE`) or realtime scheduling before applying the nice value,
+    /// since nice alone does not undo either.
+    fn set_nice_with_default_policy(
+        pid: pid_t,
+        class: PriorityClassName,
+    ) -> Result<(), OsApiError> {
+        let policy = unsafe { sched_getscheduler(pid) };
+        if policy == -1 {
+            return Err(Self::last_api_error());
+        }
+        if policy != SCHED_OTHER {
+            Self::set_policy(pid, SCHED_OTHER)?;
+        }
+        Self::set_nice(pid, class)
+    }
+
+    fn set_policy(pid: pid_t, policy: i32) -> Result<(), OsApiError> {
+        let params = sched_param { sched_priority: 0 };
+        let ret = unsafe { sched_setscheduler(pid, policy, &params) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Self::last_api_error())
+        }
+    }
+
+    fn set_nice(pid: pid_t, class: PriorityClassName) -> Result<(), OsApiError> {
+        let ret = unsafe { setpriority(PRIO_PROCESS, pid as u32, Self::to_nice(class)) };
+        if ret == 0 {
+            Ok(())
+        } else {
+            Err(Self::last_api_error())
         }
     }
 
-    fn spawn(target: &Path, args: &[String]) -> Result<Child, String> {
+    fn to_nice(class: PriorityClassName) -> i32 {
+        match class {
+            PriorityClassName::Idle => 19,
+            PriorityClassName::BelowNormal => 10,
+            PriorityClassName::Normal => 0,
+            PriorityClassName::AboveNormal => -5,
+            PriorityClassName::High => -10,
+            PriorityClassName::Realtime => -20,
+        }
+    }
+
+    fn spawn(target: &Path, args: &[String]) -> Result<Child, OsApiError> {
         let mut cmd = Command::new(target);
         if !args.is_empty() {
             cmd.args(args);
@@ -156,12 +285,7 @@ impl OS {
         cmd.stdout(Stdio::inherit())
             .stderr(Stdio::inherit())
             .spawn()
-            .map_err(|e| format!("spawn {:?} failed: {e}", target))
-    }
-
-    fn set_affinity_for_pid(pid: u32, mask: usize) -> Result<(), String> {
-        let cpu_set = Self::cpuset_from_mask(mask)?;
-        sched_setaffinity(Self::pid(pid), &cpu_set).map_err(|e| e.to_string())
+            .map_err(|e| Self::api_error(&e, format!("spawn {:?} failed: {e}", target)))
     }
 
     fn apply_post_spawn_settings<F, G>(
@@ -298,8 +422,18 @@ impl OS {
         )
     }
 
-    fn strip_exec_field_codes(token: &str) -> Option<String> {
-        let mut chars = token.chars().peekable();
+    /// Expands the freedesktop field codes of one `Exec=` argument. File and URL codes
+    /// are dropped since the tool launches without documents, `%c` becomes the entry's
+    /// name and a standalone `%i` becomes `--icon <Icon>`.
+    fn expand_exec_field_codes(token: &str, entry: &DesktopEntry) -> Vec<String> {
+        if token == "%i" {
+            return match entry.icon.as_deref() {
+                Some(icon) if !icon.is_empty() => vec!["--icon".to_string(), icon.to_string()],
+                _ => Vec::new(),
+            };
+        }
+
+        let mut chars = token.chars();
         let mut out = String::new();
 
         while let Some(ch) = chars.next() {
@@ -310,9 +444,8 @@ impl OS {
 
             match chars.next() {
                 Some('%') => out.push('%'),
-                Some(
-                    'f' | 'F' | 'u' | 'U' | 'd' | 'D' | 'n' | 'N' | 'i' | 'c' | 'k' | 'v' | 'm',
-                ) => {}
+                Some('c') => out.push_str(&entry.name),
+                Some('f' | 'F' | 'u' | 'U' | 'd' | 'D' | 'n' | 'N' | 'i' | 'k' | 'v' | 'm') => {}
                 Some(code) => {
                     out.push('%');
                     out.push(code);
@@ -322,14 +455,45 @@ impl OS {
         }
 
         let cleaned = out.trim();
-        (!cleaned.is_empty()).then(|| cleaned.to_string())
+        if cleaned.is_empty() {
+            Vec::new()
+        } else {
+            vec![cleaned.to_string()]
+        }
+    }
+
+    /// Undoes the escapes desktop entry string values use (`\s`, `\n`, `\t`, `\r`,
+    /// `\\`); other backslashes are left for the `Exec=` quoting rules.
+    fn unescape_desktop_value(value: &str) -> String {
+        let mut chars = value.chars();
+        let mut out = String::with_capacity(value.len());
+        while let Some(ch) = chars.next() {
+            if ch != '\\' {
+                out.push(ch);
+                continue;
+            }
+            match chars.next() {
+                Some('s') => out.push(' '),
+                Some('n') => out.push('\n'),
+                Some('t') => out.push('\t'),
+                Some('r') => out.push('\r'),
+                Some('\\') => out.push('\\'),
+                Some(other) => {
+                    out.push('\\');
+                    out.push(other);
+                }
+                None => out.push('\\'),
+            }
+        }
+        out
     }
 
-    fn desktop_exec_tokens(exec: &str) -> Result<Vec<String>, String> {
+    fn desktop_exec_tokens(entry: &DesktopEntry) -> Result<Vec<String>, String> {
+        let exec = entry.exec.as_str();
         let raw = shlex::split(exec).unwrap_or_else(|| vec![exec.to_string()]);
         let tokens: Vec<String> = raw
             .into_iter()
-            .filter_map(|token| Self::strip_exec_field_codes(&token))
+            .flat_map(|token| Self::expand_exec_field_codes(&token, entry))
             .collect();
 
         if tokens.is_empty() {
@@ -339,8 +503,8 @@ impl OS {
         Ok(tokens)
     }
 
-    fn desktop_exec_to_target(exec: &str) -> Result<(PathBuf, Vec<String>), String> {
-        let tokens = Self::desktop_exec_tokens(exec)?;
+    fn desktop_exec_to_target(entry: &DesktopEntry) -> Result<(PathBuf, Vec<String>), String> {
+        let tokens = Self::desktop_exec_tokens(entry)?;
 
         let (command, args) = if tokens[0] == "env" {
             let mut index = 1usize;
@@ -368,7 +532,7 @@ impl OS {
         let content =
             fs::read_to_string(path).map_err(|e| format!("failed to read .desktop: {e}"))?;
         let entry = Self::parse_desktop_entry(&content)?;
-        Self::desktop_exec_to_target(&entry.exec)
+        Self::desktop_exec_to_target(&entry)
     }
 
     fn parse_desktop_entry(content: &str) -> Result<DesktopEntry, String> {
@@ -393,11 +557,12 @@ impl OS {
             let Some((key, value)) = line.split_once('=') else {
                 continue;
             };
-            let value = value.trim();
+            let value = Self::unescape_desktop_value(value.trim());
 
             match key.trim() {
-                "Name" if entry.name.is_empty() => entry.name = value.to_string(),
-                "Exec" if entry.exec.is_empty() => entry.exec = value.to_string(),
+                "Name" if entry.name.is_empty() => entry.name = value,
+                "Exec" if entry.exec.is_empty() => entry.exec = value,
+                "Icon" if entry.icon.is_none() => entry.icon = Some(value),
                 "Type" => entry.entry_type = Some(value.to_string()),
                 "Hidden" => entry.hidden = value.eq_ignore_ascii_case("true"),
                 "NoDisplay" => entry.no_display = value.eq_ignore_ascii_case("true"),
@@ -443,6 +608,24 @@ impl OS {
         Ok((parent_pid, comm))
     }
 
+    /// Reads the state field of a `/proc/<pid>/stat` line. A zombie still carries its
+    /// wait status (field 52); a process killed by a signal has no exit code.
+    fn parse_stat_state(stat: &str) -> Option<ProcessState> {
+        let close = stat.rfind(')')?;
+        let rest: Vec<&str> = stat[close + 1..].split_whitespace().collect();
+        match *rest.first()? {
+            "Z" | "X" => {
+                let code = rest
+                    .get(49)
+                    .and_then(|status| status.parse::<u32>().ok())
+                    .filter(|status| status & 0x7f == 0)
+                    .map(|status| (status >> 8) & 0xff);
+                Some(ProcessState::Exited(code))
+            }
+            _ => Some(ProcessState::Running),
+        }
+    }
+
     fn process_name_from_pid(pid: u32) -> String {
         Self::get_process_image_path(pid)
             .ok()
@@ -599,7 +782,7 @@ impl OS {
                 continue;
             }
 
-            let Ok((resolved_target, _args)) = Self::desktop_exec_to_target(&entry.exec) else {
+            let Ok((resolved_target, _args)) = Self::desktop_exec_to_target(&entry) else {
                 continue;
             };
 
@@ -695,12 +878,31 @@ impl OS {
             .find(|path| path.exists())
     }
 
-    pub fn get_process_affinity(pid: u32) -> Result<usize, String> {
-        let set = sched_getaffinity(Self::pid(pid)).map_err(|e| e.to_string())?;
+    /// Mask of the CPUs this process may run on; offline CPUs are absent.
+    /// Falls back to all bits set when the mask cannot be read.
+    pub fn available_core_mask() -> usize {
+        sched_getaffinity(Pid::from_raw(0))
+            .map(|set| Self::mask_from_cpuset(&set))
+            .ok()
+            .filter(|&mask| mask != 0)
+            .unwrap_or(usize::MAX)
+    }
+
+    /// Affinity mask of this process, e.g. as narrowed by the parent that launched it.
+    /// Falls back to all bits set when the mask cannot be read.
+    pub fn current_process_affinity() -> usize {
+        Self::get_process_affinity(std::process::id())
+            .ok()
+            .filter(|&mask| mask != 0)
+            .unwrap_or(usize::MAX)
+    }
+
+    pub fn get_process_affinity(pid: u32) -> Result<usize, OsApiError> {
+        let set = sched_getaffinity(Self::pid(pid)).map_err(Self::errno_api_error)?;
         Ok(Self::mask_from_cpuset(&set))
     }
 
-    pub fn get_process_priority(pid: u32) -> Result<PriorityClass, String> {
+    pub fn get_process_priority(pid: u32) -> Result<PriorityClass, OsApiError> {
         let pid = pid as pid_t;
         let policy = unsafe { sched_getscheduler(pid) };
         if policy == SCHED_FIFO || policy == SCHED_RR {
@@ -711,33 +913,170 @@ impl OS {
         let nice = unsafe { getpriority(PRIO_PROCESS, pid as u32) };
         let err = errno::errno().0;
         if nice == -1 && err != 0 {
-            return Err(io::Error::last_os_error().to_string());
+            return Err(Self::last_api_error());
         }
 
-        Ok(match nice {
-            n if n >= 15 => PriorityClass::Idle,
-            n if n >= 5 => PriorityClass::BelowNormal,
-            n if n >= -4 => PriorityClass::Normal,
-            n if n >= -9 => PriorityClass::AboveNormal,
-            _ => PriorityClass::High,
-        })
+        let class = match nice {
+            n if n >= 15 => PriorityClassName::Idle,
+            n if n >= 5 => PriorityClassName::BelowNormal,
+            n if n >= -4 => PriorityClassName::Normal,
+            n if n >= -9 => PriorityClassName::AboveNormal,
+            _ => PriorityClassName::High,
+        };
+        Ok(PriorityClass::from_parts(class, policy == SCHED_IDLE))
     }
 
-    pub fn set_process_affinity_by_pid(pid: u32, mask: usize) -> Result<(), String> {
-        let cpu_set = Self::cpuset_from_mask(mask)?;
-        sched_setaffinity(Self::pid(pid), &cpu_set).map_err(|e| e.to_string())
+    pub fn set_process_affinity_by_pid(pid: u32, mask: usize) -> Result<(), OsApiError> {
+        let cpu_set = Self::cpuset_from_mask(mask).map_err(OsApiError::Io)?;
+        sched_setaffinity(Self::pid(pid), &cpu_set).map_err(Self::errno_api_error)
     }
 
-    pub fn set_process_priority_by_pid(pid: u32, priority: PriorityClass) -> Result<(), String> {
+    pub fn set_process_priority_by_pid(
+        pid: u32,
+        priority: PriorityClass,
+    ) -> Result<(), OsApiError> {
         Self::set_priority_for_pid(pid as pid_t, priority)
     }
 
-    pub fn set_current_process_priority(priority: PriorityClass) -> Result<(), String> {
+    /// Widens every thread of a process to the system default affinity, undoing one
+    /// set on it. The default is taken from init (PID 1) since this process may itself
+    /// be pinned.
+    pub fn clear_process_affinity(pid: u32) -> Result<(), OsApiError> {
+        let mask = Self::default_affinity_mask();
+        Self::for_each_thread(pid, |tid| Self::set_process_affinity_by_pid(tid, mask))
+    }
+
+    fn default_affinity_mask() -> usize {
+        Self::get_process_affinity(1)
+            .ok()
+            .filter(|&mask| mask != 0)
+            .unwrap_or_else(Self::available_core_mask)
+    }
+
+    /// Runs `spawn` with the calling thread on the system default affinity, so a child
+    /// started while the tool is pinned to its own threads does not inherit them.
+    fn with_default_affinity<T>(spawn: impl FnOnce() -> T) -> T {
+        let previous = sched_getaffinity(Pid::from_raw(0)).ok();
+        let _ = Self::set_current_thread_affinity(Self::default_affinity_mask());
+        let spawned = spawn();
+        if let Some(previous) = previous {
+            let _ = sched_setaffinity(Pid::from_raw(0), &previous);
+        }
+        spawned
+    }
+
+    /// Runs `apply` on every thread of `pid`. Affinity and nice values are per thread
+    /// on Linux, so changing the PID alone only reaches the main thread.
+    fn for_each_thread(
+        pid: u32,
+        mut apply: impl FnMut(u32) -> Result<(), OsApiError>,
+    ) -> Result<(), OsApiError> {
+        for thread in Self::list_threads(pid)? {
+            match apply(thread.tid) {
+                // The thread exited after it was listed.
+                Err(err) if err.is_process_not_found() => {}
+                result => result?,
+            }
+        }
+        Ok(())
+    }
+
+    /// `ioprio_set` value for `priority`: the idle class, the lowest best-effort level,
+    /// or no class so the kernel derives it from the CPU nice value again.
+    fn ioprio_value(priority: IoPriority) -> libc::c_int {
+        const IOPRIO_CLASS_SHIFT: libc::c_int = 13;
+        const IOPRIO_CLASS_BE: libc::c_int = 2;
+        const IOPRIO_CLASS_IDLE: libc::c_int = 3;
+        match priority {
+            IoPriority::Normal => 0,
+            IoPriority::Low => (IOPRIO_CLASS_BE << IOPRIO_CLASS_SHIFT) | 7,
+            IoPriority::VeryLow => IOPRIO_CLASS_IDLE << IOPRIO_CLASS_SHIFT,
+        }
+    }
+
+    /// Sets the I/O priority of a process with `ioprio_set`.
+    pub fn set_process_io_priority(pid: u32, priority: IoPriority) -> Result<(), OsApiError> {
+        const IOPRIO_WHO_PROCESS: libc::c_int = 1;
+        let ret = unsafe {
+            libc::syscall(
+                libc::SYS_ioprio_set,
+                IOPRIO_WHO_PROCESS,
+                pid as pid_t,
+                Self::ioprio_value(priority),
+            )
+        };
+        if ret == 0 {
+            Ok(())
+        } else {
+            let err = io::Error::last_os_error();
+            Err(Self::api_error(
+                &err,
+                format!("Failed to set I/O priority for process {pid}: {err}"),
+            ))
+        }
+    }
+
+    /// Puts every thread of a process back at the `Normal` priority.
+    pub fn reset_priority(pid: u32) -> Result<(), OsApiError> {
+        Self::for_each_thread(pid, |tid| {
+            Self::set_process_priority_by_pid(tid, PriorityClass::Normal)
+        })
+    }
+
+    /// Lists the threads of a process from `/proc/<pid>/task`, ordered by thread id.
+    pub fn list_threads(pid: u32) -> Result<Vec<ThreadInfo>, OsApiError> {
+        let task_dir = PathBuf::from(format!("/proc/{pid}/task"));
+        let entries = fs::read_dir(&task_dir).map_err(|e| {
+            Self::api_error(&e, format!("Failed to list threads of process {pid}: {e}"))
+        })?;
+
+        let mut threads: Vec<ThreadInfo> = entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+            .map(|tid| ThreadInfo {
+                tid,
+                name: fs::read_to_string(task_dir.join(tid.to_string()).join("comm"))
+                    .map(|comm| comm.trim_end().to_string())
+                    .unwrap_or_default(),
+            })
+            .collect();
+        threads.sort_by_key(|thread| thread.tid);
+        Ok(threads)
+    }
+
+    /// Sets the affinity of one thread of `pid` and returns its previous mask.
+    pub fn set_thread_affinity(pid: u32, tid: u32, mask: usize) -> Result<usize, OsApiError> {
+        if !Path::new(&format!("/proc/{pid}/task/{tid}")).exists() {
+            return Err(OsApiError::ProcessNotFound(format!(
+                "Thread {tid} does not belong to process {pid}"
+            )));
+        }
+        let previous = Self::get_process_affinity(tid)?;
+        Self::set_process_affinity_by_pid(tid, mask)?;
+        Ok(previous)
+    }
+
+    /// Pins the calling thread to `mask`.
+    pub fn set_current_thread_affinity(mask: usize) -> Result<(), OsApiError> {
+        // sched_setaffinity with pid 0 targets the calling thread, not the whole process.
+        Self::set_process_affinity_by_pid(0, mask)
+    }
+
+    /// Sets the affinity of every thread of this process. Linux affinity is per thread,
+    /// so pinning the PID alone would leave the runtime's worker threads unpinned.
+    pub fn set_current_process_affinity(mask: usize) -> Result<(), OsApiError> {
+        Self::for_each_thread(std::process::id(), |tid| {
+            Self::set_process_affinity_by_pid(tid, mask)
+        })
+    }
+
+    pub fn set_current_process_priority(priority: PriorityClass) -> Result<(), OsApiError> {
         Self::set_priority_for_pid(0, priority)
     }
 
     pub fn parse_dropped_file(file_path: PathBuf) -> Result<(PathBuf, Vec<String>), String> {
-        let path = fs::read_link(&file_path).unwrap_or(file_path.clone());
+        // Follows the whole symlink chain, e.g. /usr/bin/app -> /etc/alternatives/app -> ...
+        let path = fs::canonicalize(&file_path).unwrap_or(file_path);
 
         if path
             .extension()
@@ -750,17 +1089,112 @@ impl OS {
         Ok((path, Vec::new()))
     }
 
+    /// Job Objects are Windows-only, so this is a plain `run`; children started here
+    /// still inherit the launch affinity.
+    pub fn run_in_job(
+        file_path: PathBuf,
+        args: Vec<String>,
+        cores: &[usize],
+        priority: PriorityClass,
+        window_show: WindowShow,
+    ) -> Result<LaunchedProcess, OsApiError> {
+        Self::run(file_path, args, cores, priority, window_show)
+    }
+
+    /// Like `run`, but the affinity is never set on the new process: the calling thread
+    /// is moved onto `cores` while it spawns the child, which inherits that mask, and is
+    /// moved back afterwards. Only the priority is set on the child.
+    pub fn run_inheriting_affinity(
+        file_path: PathBuf,
+        args: Vec<String>,
+        cores: &[usize],
+        priority: PriorityClass,
+        _window_show: WindowShow,
+    ) -> Result<LaunchedProcess, OsApiError> {
+        let mask = Self::compose_mask_from_cores(cores).map_err(OsApiError::Io)?;
+        let previous = sched_getaffinity(Pid::from_raw(0)).map_err(Self::errno_api_error)?;
+        Self::set_current_thread_affinity(mask)?;
+        let spawned = Self::spawn(&file_path, &args);
+        let _ = sched_setaffinity(Pid::from_raw(0), &previous);
+        let child = spawned?;
+        let pid = child.id();
+
+        let (_priority_result, settings_attempts) = Self::retry_transient(
+            LAUNCH_SETTINGS_ATTEMPTS,
+            LAUNCH_SETTINGS_RETRY_DELAY,
+            || Self::set_priority_for_pid(pid as pid_t, priority),
+        );
+        Self::reap_child(child);
+
+        Ok(LaunchedProcess {
+            pid,
+            settings_attempts,
+            in_job: false,
+        })
+    }
+
+    /// `cpu.max` line capping a cgroup at `percent` of `threads` logical threads together.
+    fn cpu_max_value(percent: u8, threads: u32) -> String {
+        let quota =
+            CPU_MAX_PERIOD_US * u64::from(threads.max(1)) * u64::from(percent.clamp(1, 100)) / 100;
+        // The kernel rejects quotas below 1ms.
+        format!("{} {CPU_MAX_PERIOD_US}", quota.max(1_000))
+    }
+
+    /// Removes limit groups whose processes have all exited; populated ones refuse `rmdir`.
+    fn prune_cpu_limit_groups(parent: &Path) {
+        for entry in fs::read_dir(parent).into_iter().flatten().flatten() {
+            if entry.file_type().is_ok_and(|kind| kind.is_dir()) {
+                let _ = fs::remove_dir(entry.path());
+            }
+        }
+    }
+
+    /// Caps `pid` and everything it starts at `percent` of the machine's total CPU time
+    /// by moving it into its own cgroup v2 group with a `cpu.max` limit. Needs write
+    /// access to `/sys/fs/cgroup`, which usually means running as root.
+    pub fn limit_cpu_rate(pid: u32, percent: u8) -> Result<(), OsApiError> {
+        let root = Path::new("/sys/fs/cgroup");
+        let controllers = fs::read_to_string(root.join("cgroup.controllers")).unwrap_or_default();
+        if !controllers.split_whitespace().any(|name| name == "cpu") {
+            return Err(OsApiError::Unsupported(format!(
+                "Failed to limit CPU usage of process {pid}: the cgroup v2 cpu controller is not available"
+            )));
+        }
+
+        let parent = root.join(CPU_LIMIT_CGROUP);
+        let group = parent.join(format!("pid-{pid}"));
+        let io_error = |err: io::Error| {
+            Self::api_error(
+                &err,
+                format!("Failed to limit CPU usage of process {pid}: {err}"),
+            )
+        };
+        Self::prune_cpu_limit_groups(&parent);
+        fs::create_dir_all(&parent).map_err(io_error)?;
+        fs::write(root.join("cgroup.subtree_control"), "+cpu").map_err(io_error)?;
+        fs::write(parent.join("cgroup.subtree_control"), "+cpu").map_err(io_error)?;
+        fs::create_dir_all(&group).map_err(io_error)?;
+        let threads = Self::available_core_mask().count_ones();
+        fs::write(group.join("cpu.max"), Self::cpu_max_value(percent, threads))
+            .map_err(io_error)?;
+        fs::write(group.join("cgroup.procs"), pid.to_string()).map_err(io_error)
+    }
+
+    /// Window placement has no portable equivalent on Linux, so `_window_show` is ignored.
     pub fn run(
         file_path: PathBuf,
         args: Vec<String>,
         cores: &[usize],
         priority: PriorityClass,
-    ) -> Result<u32, String> {
-        let mask = Self::compose_mask_from_cores(cores)?;
-        let _ = Self::cpuset_from_mask(mask)?;
+        _window_show: WindowShow,
+    ) -> Result<LaunchedProcess, OsApiError> {
+        let mask = Self::compose_mask_from_cores(cores).map_err(OsApiError::Io)?;
+        let _ = Self::cpuset_from_mask(mask).map_err(OsApiError::Io)?;
         let child = Self::spawn(&file_path, &args)?;
         let pid = child.id();
 
+        let settings_attempts = Cell::new(1);
         let PostSpawnSettingsResult {
             affinity_error: _affinity_error,
             priority_error: _priority_error,
@@ -768,12 +1202,32 @@ impl OS {
             pid,
             mask,
             priority,
-            Self::set_affinity_for_pid,
-            |pid, priority| Self::set_priority_for_pid(pid as pid_t, priority),
+            |pid, mask| {
+                let (result, tries) = Self::retry_transient(
+                    LAUNCH_SETTINGS_ATTEMPTS,
+                    LAUNCH_SETTINGS_RETRY_DELAY,
+                    || Self::set_process_affinity_by_pid(pid, mask),
+                );
+                settings_attempts.set(settings_attempts.get().max(tries));
+                result.map_err(String::from)
+            },
+            |pid, priority| {
+                let (result, tries) = Self::retry_transient(
+                    LAUNCH_SETTINGS_ATTEMPTS,
+                    LAUNCH_SETTINGS_RETRY_DELAY,
+                    || Self::set_priority_for_pid(pid as pid_t, priority),
+                );
+                settings_attempts.set(settings_attempts.get().max(tries));
+                result.map_err(String::from)
+            },
         );
         Self::reap_child(child);
 
-        Ok(pid)
+        Ok(LaunchedProcess {
+            pid,
+            settings_attempts: settings_attempts.get(),
+            in_job: false,
+        })
     }
 
     pub fn snapshot_process_tree() -> Result<ProcessTree, String> {
@@ -781,7 +1235,7 @@ impl OS {
         let mut children_of: HashMap<u32, Vec<u32>> = HashMap::new();
         let mut names = HashMap::new();
 
-        for pid in Self::get_all_pids() {
+        for pid in Self::read_all_pids()? {
             let Ok((parent_pid, _comm)) = Self::read_proc_stat(pid) else {
                 continue;
             };
@@ -805,21 +1259,18 @@ impl OS {
     }
 
     pub fn get_all_pids() -> Vec<u32> {
-        let mut pids = Vec::new();
-
-        let Ok(entries) = fs::read_dir("/proc") else {
-            return pids;
-        };
+        Self::read_all_pids().unwrap_or_default()
+    }
 
-        for entry in entries.flatten() {
-            let file_name = entry.file_name();
-            let file_name = file_name.to_string_lossy();
-            if let Ok(pid) = file_name.parse::<u32>() {
-                pids.push(pid);
-            }
-        }
+    /// Lists `/proc`, failing instead of reporting an empty process table so a
+    /// snapshot taken while `/proc` is unreadable is not mistaken for "everything exited".
+    fn read_all_pids() -> Result<Vec<u32>, String> {
+        let entries = fs::read_dir("/proc").map_err(|e| format!("failed to read /proc: {e}"))?;
 
-        pids
+        Ok(entries
+            .flatten()
+            .filter_map(|entry| entry.file_name().to_string_lossy().parse::<u32>().ok())
+            .collect())
     }
 
     pub fn find_pids_by_name(target_name: &str) -> Vec<u32> {
@@ -860,59 +1311,165 @@ impl OS {
             .unwrap_or_default()
     }
 
+    /// Finds all descendant processes of a given parent process from one `/proc` snapshot.
+    ///
+    /// Doesn't add duplicates if `descendants` already contains some PIDs.
     pub fn find_all_descendants(parent_pid: u32, descendants: &mut Vec<u32>) {
         if let Ok(tree) = Self::snapshot_process_tree() {
-            let mut visited: HashSet<u32> = descendants.iter().copied().collect();
-            let mut stack = vec![parent_pid];
+            Self::find_all_descendants_with_tree(parent_pid, descendants, &tree);
+        }
+    }
 
-            while let Some(parent) = stack.pop() {
-                if let Some(children) = tree.children_of.get(&parent) {
-                    for &child in children {
-                        if visited.insert(child) {
+    /// Finds all descendant processes using a pre-captured process tree snapshot.
+    pub fn find_all_descendants_with_tree(
+        parent_pid: u32,
+        descendants: &mut Vec<u32>,
+        tree: &ProcessTree,
+    ) {
+        let mut existing: HashSet<u32> = descendants.iter().copied().collect();
+        let mut processed: HashSet<u32> = HashSet::new();
+
+        let mut queue = VecDeque::new();
+        queue.push_back(parent_pid);
+        processed.insert(parent_pid);
+
+        while let Some(current) = queue.pop_front() {
+            if let Some(children) = tree.children_of.get(&current) {
+                for &child in children {
+                    if processed.insert(child) {
+                        if existing.insert(child) {
                             descendants.push(child);
-                            stack.push(child);
                         }
+                        queue.push_back(child);
                     }
                 }
             }
         }
     }
 
+    /// Kills `pid` and every descendant, returning the PIDs that were signalled.
+    ///
+    /// PIDs that exit before the signal lands are skipped rather than reported as errors.
+    pub fn terminate_tree(pid: u32) -> Result<Vec<u32>, OsApiError> {
+        let mut targets = vec![pid];
+        Self::find_all_descendants(pid, &mut targets);
+
+        let mut terminated = Vec::with_capacity(targets.len());
+        let mut first_error = None;
+        for target in targets {
+            if unsafe { kill(target as pid_t, SIGKILL) } == 0 {
+                terminated.push(target);
+                continue;
+            }
+            let err = io::Error::last_os_error();
+            if err.raw_os_error() != Some(ESRCH) && first_error.is_none() {
+                first_error = Some(Self::api_error(
+                    &err,
+                    format!("Failed to terminate process {target}: {err}"),
+                ));
+            }
+        }
+
+        match first_error {
+            Some(err) if terminated.is_empty() => Err(err),
+            _ => Ok(terminated),
+        }
+    }
+
     pub fn is_pid_live(pid: u32) -> bool {
         Self::proc_path(pid, "").is_dir()
     }
 
-    pub fn get_process_image_path(pid: u32) -> Result<PathBuf, String> {
+    pub fn get_process_image_path(pid: u32) -> Result<PathBuf, OsApiError> {
         fs::read_link(Self::proc_path(pid, "exe"))
-            .map_err(|e| format!("failed to read /proc/{pid}/exe: {e}"))
+            .map_err(|e| Self::api_error(&e, format!("failed to read /proc/{pid}/exe: {e}")))
     }
 
     pub fn focus_window_by_pid(_pid: u32) -> bool {
         false
     }
 
-    pub fn get_program_path_for_uri(uri_scheme: &str) -> Result<PathBuf, String> {
-        let output = Command::new("xdg-mime")
-            .args([
-                "query",
-                "default",
-                &format!("x-scheme-handler/{uri_scheme}"),
-            ])
-            .output()
-            .map_err(|e| format!("failed to execute xdg-mime: {e}"))?;
+    pub fn has_visible_window(_pid: u32) -> bool {
+        false
+    }
 
-        if !output.status.success() {
-            return Err(format!("xdg-mime failed with status {}", output.status));
+    pub fn watch_process(pid: u32) -> Result<ProcessWatch, OsApiError> {
+        if Self::is_pid_live(pid) {
+            Ok(ProcessWatch { pid })
+        } else {
+            Err(OsApiError::ProcessNotFound(format!(
+                "Failed to watch process {pid}: it has exited"
+            )))
         }
+    }
 
-        let desktop_file = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    pub fn get_program_path_for_uri(uri_scheme: &str) -> Result<PathBuf, String> {
+        let desktop_file =
+            Self::xdg_mime_query("default", &format!("x-scheme-handler/{uri_scheme}"))?;
         if desktop_file.is_empty() {
             return Err(format!(
                 "no default application found for URI scheme '{uri_scheme}'"
             ));
         }
+        Self::desktop_file_program(&desktop_file)
+    }
+
+    /// Whether `path` is a document rather than a program, so it has to be opened
+    /// through its file association. `.desktop` entries are resolved on drop instead.
+    pub fn needs_shell_open(path: &Path) -> bool {
+        let is_desktop_entry = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("desktop"));
+        !is_desktop_entry
+            && fs::metadata(path)
+                .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 == 0)
+    }
+
+    /// Program registered to open files like `path`, e.g. the PDF viewer for a `.pdf`.
+    pub fn association_handler(path: &Path) -> Result<PathBuf, String> {
+        let mime = Self::xdg_mime_query("filetype", &path.to_string_lossy())?;
+        if mime.is_empty() {
+            return Err(format!("could not tell the type of '{}'", path.display()));
+        }
+        let desktop_file = Self::xdg_mime_query("default", &mime)?;
+        if desktop_file.is_empty() {
+            return Err(format!("no default application found for '{mime}'"));
+        }
+        Self::desktop_file_program(&desktop_file)
+    }
 
-        let path = Self::find_desktop_file_by_name(&desktop_file)
+    /// Opens `path` with its associated program through `xdg-open`. That hands the
+    /// file over and exits, so there is never a handler PID to return, and `_args`
+    /// can't be passed on. The handler starts on every core, not on the tool's own.
+    pub fn shell_open(path: &Path, _args: &[String]) -> Result<Option<u32>, OsApiError> {
+        let mut command = Command::new("xdg-open");
+        command
+            .arg(path)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        let child = Self::with_default_affinity(|| command.spawn()).map_err(|e| {
+            Self::api_error(&e, format!("Failed to open '{}': {e}", path.display()))
+        })?;
+        Self::reap_child(child);
+        Ok(None)
+    }
+
+    /// Trimmed output of `xdg-mime query <query> <arg>`.
+    fn xdg_mime_query(query: &str, arg: &str) -> Result<String, String> {
+        let output = Command::new("xdg-mime")
+            .args(["query", query, arg])
+            .output()
+            .map_err(|e| format!("failed to execute xdg-mime: {e}"))?;
+        if !output.status.success() {
+            return Err(format!("xdg-mime failed with status {}", output.status));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn desktop_file_program(desktop_file: &str) -> Result<PathBuf, String> {
+        let path = Self::find_desktop_file_by_name(desktop_file)
             .ok_or_else(|| format!("desktop file '{desktop_file}' not found"))?;
         Self::parse_desktop_file(&path).map(|(path, _)| path)
     }
@@ -930,20 +1487,100 @@ impl OS {
             .unwrap_or_else(|| "Unknown CPU".to_string())
     }
 
+    /// Parses the per-core lines of `/proc/stat` into `(idle, total)` jiffies indexed by core.
+    fn parse_proc_stat_core_times(content: &str) -> Vec<(u64, u64)> {
+        let mut times = Vec::new();
+        for line in content.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(core) = fields
+                .next()
+                .and_then(|label| label.strip_prefix("cpu"))
+                .and_then(|index| index.parse::<usize>().ok())
+            else {
+                continue;
+            };
+            // user nice system idle iowait irq softirq steal; guest time is already in user.
+            let values: Vec<u64> = fields.take(8).filter_map(|v| v.parse().ok()).collect();
+            if values.len() < 4 {
+                continue;
+            }
+            if times.len() <= core {
+                times.resize(core + 1, (0, 0));
+            }
+            let idle = values[3] + values.get(4).copied().unwrap_or(0);
+            times[core] = (idle, values.iter().sum());
+        }
+        times
+    }
+
+    /// `(idle, total)` jiffies of every logical core, indexed by core; see
+    /// [`OS::core_usage_between`] for turning two readings into usage.
+    pub fn read_core_times() -> Result<Vec<(u64, u64)>, String> {
+        fs::read_to_string("/proc/stat")
+            .map(|content| Self::parse_proc_stat_core_times(&content))
+            .map_err(|e| format!("Failed to read /proc/stat: {e}"))
+    }
+
+    /// Samples how busy every logical core was over `window`, blocking for its duration.
+    /// Returns busy fractions (0.0..=1.0) indexed by core.
+    pub fn sample_per_core_usage(window: Duration) -> Result<Vec<f32>, String> {
+        let before = Self::read_core_times()?;
+        thread::sleep(window);
+        let after = Self::read_core_times()?;
+        Ok(Self::core_usage_between(&before, &after))
+    }
+
+    fn numa_node_from_entry_name(name: &str) -> Option<u16> {
+        name.strip_prefix("node")?.parse().ok()
+    }
+
+    pub fn get_numa_node_for_core(core: usize) -> Result<u16, String> {
+        let dir = PathBuf::from(format!("/sys/devices/system/cpu/cpu{core}"));
+        let entries = fs::read_dir(&dir)
+            .map_err(|e| format!("Failed to get NUMA node for core {core}: {e}"))?;
+
+        // Kernels built without NUMA support expose no nodeN link: everything is node 0.
+        Ok(entries
+            .flatten()
+            .find_map(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .and_then(Self::numa_node_from_entry_name)
+            })
+            .unwrap_or(0))
+    }
+
+    /// Memory policy can only be set by the process itself (`set_mempolicy`), so this
+    /// is a no-op; the affinity mask already keeps the threads on the selected node.
+    pub fn set_numa_preference(_pid: u32, _node: u16) -> Result<(), OsApiError> {
+        Ok(())
+    }
+
     pub fn list_supported_start_apps() -> Result<Vec<InstalledAppCatalogEntry>, String> {
         let desktop_entries = Self::list_desktop_catalog_entries();
         let path_entries = Self::list_path_catalog_entries();
         Ok(Self::merge_catalog_sources(desktop_entries, path_entries))
     }
 
-    pub fn activate_application(_aumid: &str) -> Result<u32, String> {
-        Err("Installed app activation is not supported on Linux".into())
+    pub fn activate_application(_aumid: &str) -> Result<u32, OsApiError> {
+        Err(OsApiError::Unsupported(
+            "Installed app activation is not supported on Linux".into(),
+        ))
     }
 
-    pub fn get_process_app_user_model_id(_pid: u32) -> Result<Option<String>, String> {
+    pub fn get_process_app_user_model_id(_pid: u32) -> Result<Option<String>, OsApiError> {
         Ok(None)
     }
 
+    /// AppImage file `pid` runs from. The AppImage runtime exports `APPIMAGE` to the
+    /// payload it mounts or extracts (`squashfs-root`), and its children inherit it.
+    pub fn get_process_appimage_path(pid: u32) -> Result<Option<PathBuf>, OsApiError> {
+        let environ = fs::read(Self::proc_path(pid, "environ"))
+            .map_err(|e| Self::api_error(&e, format!("failed to read /proc/{pid}/environ: {e}")))?;
+        Ok(appimage_from_environ(&environ))
+    }
+
     pub fn resolve_installed_package_runtime_info(
         _aumid: &str,
     ) -> Result<InstalledPackageRuntimeInfo, String> {
@@ -957,16 +1594,49 @@ impl OS {
             .map(|_| ())
             .map_err(|e| format!("Failed to open directory '{}': {e}", path.display()))
     }
+
+    /// Starts `command_line` through `sh -c` without waiting for it, on every core
+    /// rather than the tool's own. Returns the shell's PID.
+    pub fn spawn_shell_command(command_line: &str) -> Result<u32, String> {
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(command_line)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        let child = Self::with_default_affinity(|| command.spawn())
+            .map_err(|e| format!("Failed to run '{command_line}': {e}"))?;
+        let pid = child.id();
+        Self::reap_child(child);
+        Ok(pid)
+    }
+
+    /// Opens the folder holding `path`; file managers differ in how to select an item,
+    /// so only the folder is shown.
+    pub fn reveal_in_explorer(path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            return Err(format!("'{}' does not exist", path.display()));
+        }
+        let parent = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+            .ok_or_else(|| format!("'{}' has no parent folder", path.display()))?;
+        Self::open_directory(parent)
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::OS;
+    use super::{DesktopEntry, OS, ProcessTree, appimage_from_environ, realtime_denied_message};
     use crate::{
-        InstalledAppCatalogEntry, InstalledAppCatalogSource, InstalledAppCatalogTarget,
-        PriorityClass,
+        InstalledAppCatalogEntry, InstalledAppCatalogSource, InstalledAppCatalogTarget, IoPriority,
+        OsApiError, PriorityClass, ProcessState, WindowShow,
     };
+    use nix::sched::sched_getaffinity;
+    use nix::unistd::Pid;
     use std::cell::Cell;
+    use std::collections::HashMap;
     use std::fs;
     use std::os::unix::fs::PermissionsExt;
     use std::path::PathBuf;
@@ -998,34 +1668,156 @@ mod tests {
     }
 
     #[test]
-    fn test_strip_exec_field_codes_handles_desktop_placeholders() {
-        assert_eq!(OS::strip_exec_field_codes("%u"), None);
+    fn test_realtime_denied_message_names_the_capability_and_fallback() {
+        let message = realtime_denied_message(true);
+        assert!(message.contains("CAP_SYS_NICE"));
+        assert!(message.ends_with("High priority was set instead"));
+        assert!(realtime_denied_message(false).ends_with("could not be set either"));
+    }
+
+    #[test]
+    fn test_spawn_shell_command_runs_detached_command_line() {
+        let marker = std::env::temp_dir().join(format!("cpu-affinity-shell-{}", unique_suffix()));
+        let pid = OS::spawn_shell_command(&format!("echo started > '{}'", marker.display()))
+            .expect("sh should start");
+        assert!(pid > 0);
+
+        let mut written = false;
+        for _ in 0..50 {
+            if fs::read_to_string(&marker).is_ok_and(|content| content.trim() == "started") {
+                written = true;
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let _ = fs::remove_file(&marker);
+        assert!(written);
+    }
+
+    #[test]
+    fn test_ioprio_values_pick_class_and_level() {
+        assert_eq!(OS::ioprio_value(IoPriority::Normal), 0);
+        assert_eq!(OS::ioprio_value(IoPriority::Low), (2 << 13) | 7);
+        assert_eq!(OS::ioprio_value(IoPriority::VeryLow), 3 << 13);
+    }
+
+    #[test]
+    fn test_cpu_max_value_scales_quota_by_thread_count() {
+        assert_eq!(OS::cpu_max_value(30, 8), "240000 100000");
+        assert_eq!(OS::cpu_max_value(100, 1), "100000 100000");
+        assert_eq!(OS::cpu_max_value(0, 0), "1000 100000");
+        assert_eq!(OS::cpu_max_value(200, 2), "200000 100000");
+    }
+
+    #[test]
+    fn test_parse_stat_state_reads_zombie_exit_code() {
+        let stat = |state: &str, wait_status: &str| {
+            format!(
+                "42 (my app) {state} {} {wait_status}\n",
+                vec!["0"; 48].join(" ")
+            )
+        };
+
+        assert_eq!(
+            OS::parse_stat_state(&stat("S", "0")),
+            Some(ProcessState::Running)
+        );
+        assert_eq!(
+            OS::parse_stat_state(&stat("Z", "768")),
+            Some(ProcessState::Exited(Some(3)))
+        );
+        assert_eq!(
+            OS::parse_stat_state(&stat("Z", "9")),
+            Some(ProcessState::Exited(None))
+        );
+        assert_eq!(OS::parse_stat_state("garbage"), None);
+    }
+
+    fn desktop_entry(exec: &str) -> DesktopEntry {
+        DesktopEntry {
+            name: "Krita".to_string(),
+            exec: OS::unescape_desktop_value(exec),
+            icon: Some("krita".to_string()),
+            ..DesktopEntry::default()
+        }
+    }
+
+    fn strings(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn test_expand_exec_field_codes_handles_desktop_placeholders() {
+        let entry = desktop_entry("krita");
+        assert!(OS::expand_exec_field_codes("%u", &entry).is_empty());
+        assert_eq!(
+            OS::expand_exec_field_codes("--profile=%k", &entry),
+            strings(&["--profile="])
+        );
         assert_eq!(
-            OS::strip_exec_field_codes("--profile=%k"),
-            Some("--profile=".to_string())
+            OS::expand_exec_field_codes("100%%", &entry),
+            strings(&["100%"])
         );
         assert_eq!(
-            OS::strip_exec_field_codes("100%%"),
-            Some("100%".to_string())
+            OS::expand_exec_field_codes("%i", &entry),
+            strings(&["--icon", "krita"])
         );
+        let without_icon = DesktopEntry {
+            icon: None,
+            ..desktop_entry("krita")
+        };
+        assert!(OS::expand_exec_field_codes("%i", &without_icon).is_empty());
     }
 
     #[test]
-    fn test_desktop_exec_tokens_drop_field_codes() {
-        let tokens =
-            OS::desktop_exec_tokens(r#"flatpak run app.id --arg %u --title="Hello %c""#).unwrap();
+    fn test_desktop_exec_tokens_expand_field_codes() {
+        let tokens = OS::desktop_exec_tokens(&desktop_entry(
+            r#"flatpak run app.id --arg %u --title="Hello %c""#,
+        ))
+        .unwrap();
         assert_eq!(
             tokens,
-            vec![
-                "flatpak".to_string(),
-                "run".to_string(),
-                "app.id".to_string(),
-                "--arg".to_string(),
-                "--title=Hello".to_string(),
-            ]
+            strings(&["flatpak", "run", "app.id", "--arg", "--title=Hello Krita"])
         );
     }
 
+    #[test]
+    fn test_desktop_exec_tokens_handle_real_world_exec_lines() {
+        let cases: [(&str, &[&str]); 6] = [
+            (
+                r#""/opt/Visual Studio Code/code" --unity-launch %F"#,
+                &["/opt/Visual Studio Code/code", "--unity-launch"],
+            ),
+            ("/usr/bin/gimp-2.10 %U", &["/usr/bin/gimp-2.10"]),
+            (
+                r#"env GDK_BACKEND=x11 "/home/jörg/Spiele/Überlauf Édition/start.sh" --fullscreen %u"#,
+                &[
+                    "env",
+                    "GDK_BACKEND=x11",
+                    "/home/jörg/Spiele/Überlauf Édition/start.sh",
+                    "--fullscreen",
+                ],
+            ),
+            (
+                "krita %i -caption %c %U",
+                &["krita", "--icon", "krita", "-caption", "Krita"],
+            ),
+            (
+                r#"sh -c "echo \\"%%done\\" && exec /opt/游戏/run""#,
+                &["sh", "-c", r#"echo "%done" && exec /opt/游戏/run"#],
+            ),
+            (r#""/opt/My\sGame/start" %f"#, &["/opt/My Game/start"]),
+        ];
+
+        for (exec, expected) in cases {
+            assert_eq!(
+                OS::desktop_exec_tokens(&desktop_entry(exec)).unwrap(),
+                strings(expected),
+                "Exec={exec}"
+            );
+        }
+    }
+
     #[test]
     fn test_parse_desktop_file_handles_env_args_and_field_codes() {
         let unique = unique_suffix();
@@ -1055,6 +1847,57 @@ mod tests {
         assert_eq!(args, vec!["--profile".to_string(), "default".to_string()]);
     }
 
+    #[test]
+    fn test_parse_dropped_file_follows_symlink_chains() {
+        let unique = unique_suffix();
+        let temp_dir = std::env::temp_dir().join(format!("codex-linux-symlink-chain-{unique}"));
+        fs::create_dir_all(temp_dir.join("bin")).unwrap();
+        let _guard = TempDirGuard::new(temp_dir.clone());
+
+        let executable = temp_dir.join("bin").join("real-app");
+        fs::write(&executable, b"#!/bin/sh\n").unwrap();
+        std::os::unix::fs::symlink("bin/real-app", temp_dir.join("alternative")).unwrap();
+        std::os::unix::fs::symlink("alternative", temp_dir.join("app")).unwrap();
+
+        let (target, args) = OS::parse_dropped_file(temp_dir.join("app")).unwrap();
+
+        assert_eq!(target, fs::canonicalize(&executable).unwrap());
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn test_needs_shell_open_only_for_non_executable_files() {
+        let unique = unique_suffix();
+        let temp_dir = std::env::temp_dir().join(format!("codex-linux-shell-open-{unique}"));
+        fs::create_dir_all(&temp_dir).unwrap();
+        let _guard = TempDirGuard::new(temp_dir.clone());
+
+        let document = temp_dir.join("report.pdf");
+        fs::write(&document, b"%PDF-1.7\n").unwrap();
+        let program = temp_dir.join("game");
+        fs::write(&program, b"#!/bin/sh\n").unwrap();
+        fs::set_permissions(&program, fs::Permissions::from_mode(0o755)).unwrap();
+        let entry = temp_dir.join("game.desktop");
+        fs::write(&entry, b"[Desktop Entry]\n").unwrap();
+
+        assert!(OS::needs_shell_open(&document));
+        assert!(!OS::needs_shell_open(&program));
+        assert!(!OS::needs_shell_open(&entry));
+        assert!(!OS::needs_shell_open(&temp_dir));
+    }
+
+    #[test]
+    fn test_appimage_from_environ_reads_the_runtime_variable() {
+        assert_eq!(
+            appimage_from_environ(
+                b"HOME=/home/me\0APPDIR=/tmp/.mount_GameXy\0APPIMAGE=/home/me/Game.AppImage\0"
+            ),
+            Some(PathBuf::from("/home/me/Game.AppImage"))
+        );
+        assert_eq!(appimage_from_environ(b"HOME=/home/me\0APPIMAGE=\0"), None);
+        assert_eq!(appimage_from_environ(b""), None);
+    }
+
     #[test]
     fn test_list_desktop_catalog_entries_filters_and_preserves_valid_entry() {
         let unique = unique_suffix();
@@ -1136,6 +1979,27 @@ mod tests {
         assert_eq!(entries[0].detail, executable.display().to_string());
     }
 
+    #[test]
+    fn test_parse_proc_stat_core_times_reads_per_core_lines() {
+        let content = "cpu  10 0 10 80 0 0 0 0 0 0\n\
+                       cpu0 5 0 5 40 2 0 0 0 0 0\n\
+                       cpu2 1 1 1 7 0 0 0 0 0 0\n\
+                       intr 12345\n";
+        assert_eq!(
+            OS::parse_proc_stat_core_times(content),
+            vec![(42, 52), (0, 0), (7, 10)]
+        );
+    }
+
+    #[test]
+    fn test_numa_node_from_entry_name_parses_only_node_links() {
+        assert_eq!(OS::numa_node_from_entry_name("node0"), Some(0));
+        assert_eq!(OS::numa_node_from_entry_name("node3"), Some(3));
+        assert_eq!(OS::numa_node_from_entry_name("node"), None);
+        assert_eq!(OS::numa_node_from_entry_name("cpufreq"), None);
+        assert_eq!(OS::numa_node_from_entry_name("topology"), None);
+    }
+
     #[test]
     fn test_post_spawn_settings_attempt_priority_after_affinity_failure() {
         let priority_called = Cell::new(false);
@@ -1183,10 +2047,19 @@ mod tests {
             Vec::new(),
             &[],
             PriorityClass::Normal,
+            WindowShow::Normal,
         )
         .unwrap_err();
 
-        assert!(err.contains("affinity mask is empty"));
+        assert!(err.to_string().contains("affinity mask is empty"));
+    }
+
+    #[test]
+    fn test_compose_mask_from_cores_rejects_cores_beyond_the_mask_width() {
+        #[cfg(target_pointer_width = "64")]
+        assert_eq!(OS::compose_mask_from_cores(&[0, 33]), Ok((1 << 33) | 1));
+        let err = OS::compose_mask_from_cores(&[usize::BITS as usize]).unwrap_err();
+        assert!(err.contains("out of range"));
     }
 
     #[test]
@@ -1196,10 +2069,121 @@ mod tests {
             Vec::new(),
             &[0],
             PriorityClass::Normal,
+            WindowShow::Normal,
         )
         .unwrap_err();
 
-        assert!(err.contains("spawn"));
+        assert!(err.to_string().contains("spawn"));
+    }
+
+    /// Runs in a child process started by the release test: the main thread plus one
+    /// extra thread, both sleeping. Does nothing in a normal test run.
+    #[test]
+    fn test_helper_multi_threaded_sleeper() {
+        if std::env::var_os("OS_API_MULTI_THREADED_SLEEPER").is_none() {
+            return;
+        }
+        let worker = thread::spawn(|| thread::sleep(std::time::Duration::from_secs(10)));
+        thread::sleep(std::time::Duration::from_secs(10));
+        let _ = worker.join();
+    }
+
+    #[test]
+    fn test_clear_process_affinity_widens_every_thread() {
+        let available = OS::available_core_mask();
+        let core = available.trailing_zeros();
+        let mut child = Command::new(std::env::current_exe().unwrap())
+            .args([
+                "linux::tests::test_helper_multi_threaded_sleeper",
+                "--exact",
+                "--test-threads=1",
+            ])
+            .env("OS_API_MULTI_THREADED_SLEEPER", "1")
+            .stdout(Stdio::null())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+        let mut threads = Vec::new();
+        for _ in 0..100 {
+            threads = OS::list_threads(pid).unwrap();
+            if threads.len() > 1 {
+                break;
+            }
+            thread::sleep(std::time::Duration::from_millis(20));
+        }
+        let worker = threads.last().unwrap().tid;
+        assert_ne!(worker, pid);
+        for thread in &threads {
+            OS::set_process_affinity_by_pid(thread.tid, 1 << core).unwrap();
+        }
+
+        OS::clear_process_affinity(pid).unwrap();
+        OS::reset_priority(pid).unwrap();
+
+        let widened = OS::get_process_affinity(worker).unwrap();
+        let main = OS::get_process_affinity(pid).unwrap();
+        let _ = child.kill();
+        let _ = child.wait();
+        assert_eq!(widened, main);
+        if available.count_ones() > 1 {
+            assert_ne!(widened, 1 << core);
+        }
+    }
+
+    #[test]
+    fn test_run_inheriting_affinity_restores_the_calling_thread() {
+        let before = sched_getaffinity(Pid::from_raw(0)).unwrap();
+        let core = OS::available_core_mask().trailing_zeros() as usize;
+
+        let launched = OS::run_inheriting_affinity(
+            PathBuf::from("/bin/sleep"),
+            vec!["1".to_string()],
+            &[core],
+            PriorityClass::Normal,
+            WindowShow::Normal,
+        )
+        .unwrap();
+
+        assert_eq!(OS::get_process_affinity(launched.pid).unwrap(), 1 << core);
+        assert_eq!(sched_getaffinity(Pid::from_raw(0)).unwrap(), before);
+        let _ = OS::terminate_tree(launched.pid);
+    }
+
+    #[test]
+    fn test_find_all_descendants_with_tree_walks_deep_tree_without_duplicates() {
+        let tree = ProcessTree {
+            parent_of: HashMap::from([(2, 1), (3, 1), (4, 2), (5, 4), (6, 3)]),
+            children_of: HashMap::from([
+                (1, vec![2, 3]),
+                (2, vec![4]),
+                (3, vec![6, 4]),
+                (4, vec![5, 1]),
+            ]),
+            names: HashMap::new(),
+        };
+        let mut descendants = vec![6];
+
+        OS::find_all_descendants_with_tree(1, &mut descendants, &tree);
+
+        descendants.sort_unstable();
+        assert_eq!(descendants, vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_terminate_tree_kills_parent_and_reports_pid() {
+        let mut child = Command::new("/bin/sh")
+            .arg("-c")
+            .arg("sleep 30")
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            .unwrap();
+        let pid = child.id();
+
+        let terminated = OS::terminate_tree(pid).unwrap();
+
+        assert!(terminated.contains(&pid));
+        assert!(!child.wait().unwrap().success());
     }
 
     #[test]
@@ -1286,4 +2270,72 @@ mod tests {
         assert!(merged.contains(&desktop));
         assert!(merged.iter().any(|entry| entry.name == "steamcmd"));
     }
+
+    #[test]
+    fn test_list_threads_and_set_thread_affinity_for_current_thread() {
+        let pid = std::process::id();
+        let threads = OS::list_threads(pid).unwrap();
+        assert!(threads.iter().any(|thread| thread.tid == pid));
+
+        let tid = unsafe { libc::gettid() } as u32;
+        assert!(threads.iter().any(|thread| thread.tid == tid));
+        let mask = OS::get_process_affinity(tid).unwrap();
+        assert_eq!(OS::set_thread_affinity(pid, tid, mask), Ok(mask));
+        assert!(
+            OS::set_thread_affinity(pid, u32::MAX, mask)
+                .unwrap_err()
+                .is_process_not_found()
+        );
+    }
+
+    #[test]
+    fn test_set_current_process_affinity_reaches_other_threads() {
+        let mask = OS::get_process_affinity(std::process::id()).unwrap();
+        let (tid_tx, tid_rx) = std::sync::mpsc::channel();
+        let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+        let worker = std::thread::spawn(move || {
+            tid_tx.send(unsafe { libc::gettid() } as u32).unwrap();
+            let _ = done_rx.recv();
+        });
+        let tid = tid_rx.recv().unwrap();
+
+        assert_eq!(OS::set_current_process_affinity(mask), Ok(()));
+        assert_eq!(OS::get_process_affinity(tid), Ok(mask));
+
+        drop(done_tx);
+        worker.join().unwrap();
+    }
+
+    #[test]
+    fn test_shell_command_starts_on_the_default_affinity_from_a_pinned_thread() {
+        std::thread::spawn(|| {
+            let default = OS::default_affinity_mask();
+            let lowest = default & default.wrapping_neg();
+            OS::set_current_thread_affinity(lowest).unwrap();
+
+            let pid = OS::spawn_shell_command("sleep 1").unwrap();
+            assert_eq!(OS::get_process_affinity(pid), Ok(default));
+            assert_eq!(OS::get_process_affinity(0), Ok(lowest));
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_missing_process_errors_are_classified() {
+        // Above the kernel's pid_max, so it can never name a live process.
+        let pid = i32::MAX as u32;
+        let err = OS::get_process_affinity(pid).unwrap_err();
+        assert!(err.is_process_not_found(), "{err:?}");
+        assert!(OS::list_threads(pid).unwrap_err().is_process_not_found());
+        assert!(
+            OS::get_process_image_path(pid)
+                .unwrap_err()
+                .is_process_not_found()
+        );
+        assert!(matches!(
+            OS::activate_application("app"),
+            Err(OsApiError::Unsupported(_))
+        ));
+    }
 }
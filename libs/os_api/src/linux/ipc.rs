@@ -0,0 +1,451 @@
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::net::Shutdown;
+use std::os::fd::AsRawFd;
+use std::os::unix::fs::{DirBuilderExt, MetadataExt, OpenOptionsExt, PermissionsExt};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use super::OS;
+
+const MAX_FRAME_BYTES: usize = 4096;
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(25);
+const SERVER_READ_TIMEOUT: Duration = Duration::from_secs(1);
+const RESPONSE_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub type LocalIpcWake = Arc<dyn Fn() + Send + Sync + 'static>;
+
+#[derive(Debug, Clone)]
+pub struct LocalIpcEndpoint {
+    pub socket_path: PathBuf,
+    pub lock_path: PathBuf,
+}
+
+pub struct LocalIpcRequest {
+    pub request: Vec<u8>,
+    pub response_tx: Sender<Vec<u8>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LocalIpcClientError {
+    NoServer,
+    ServerNotReady,
+    Timeout,
+    SecurityRejected(String),
+    Io(String),
+}
+
+/// Holds an exclusive `flock` on the endpoint lock file; the kernel drops it
+/// when the primary exits, so a crash never leaves a stale guard behind.
+pub struct LocalIpcGuard {
+    _lock: File,
+}
+
+pub struct LocalIpcServer {
+    endpoint: LocalIpcEndpoint,
+    request_rx: Receiver<LocalIpcRequest>,
+    shutdown: Arc<AtomicBool>,
+    thread: Option<thread::JoinHandle<()>>,
+}
+
+impl LocalIpcServer {
+    pub fn try_recv(&self) -> Result<LocalIpcRequest, mpsc::TryRecvError> {
+        self.request_rx.try_recv()
+    }
+}
+
+impl Drop for LocalIpcServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+        let _ = fs::remove_file(&self.endpoint.socket_path);
+    }
+}
+
+/// A `0700` directory of `uid` under the shared temp dir, so other users can neither
+/// squat on the socket name nor reach the socket. Refuses a directory someone else
+/// created, or one that is a symlink or open to others.
+fn private_temp_dir(temp_dir: &Path, uid: u32) -> Result<PathBuf, String> {
+    let dir = temp_dir.join(format!("cpu-affinity-tool-{uid}"));
+    match fs::DirBuilder::new().mode(0o700).create(&dir) {
+        Ok(()) => {}
+        Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+        Err(err) => return Err(format!("failed to create {}: {err}", dir.display())),
+    }
+
+    let meta = fs::symlink_metadata(&dir)
+        .map_err(|err| format!("failed to inspect {}: {err}", dir.display()))?;
+    if !meta.is_dir() || meta.uid() != uid || meta.mode() & 0o077 != 0 {
+        return Err(format!(
+            "{} is not a private directory owned by this user",
+            dir.display()
+        ));
+    }
+    Ok(dir)
+}
+
+impl OS {
+    pub fn local_ipc_endpoint() -> Result<LocalIpcEndpoint, String> {
+        let uid = unsafe { libc::getuid() };
+        let exe = std::env::current_exe()
+            .map(|path| path.to_string_lossy().to_string())
+            .unwrap_or_else(|_| "cpu-affinity-tool".to_string());
+        let identity = stable_hash(&format!("{uid}:{exe}"));
+        let dir = match std::env::var_os("XDG_RUNTIME_DIR")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_dir())
+        {
+            Some(dir) => dir,
+            None => private_temp_dir(&std::env::temp_dir(), uid)?,
+        };
+
+        Ok(LocalIpcEndpoint {
+            socket_path: dir.join(format!("cpu-affinity-tool-{uid}-{identity}.sock")),
+            lock_path: dir.join(format!("cpu-affinity-tool-{uid}-{identity}.lock")),
+        })
+    }
+
+    pub fn try_claim_local_ipc_primary_guard(
+        endpoint: &LocalIpcEndpoint,
+    ) -> Result<Option<LocalIpcGuard>, String> {
+        let lock = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .mode(0o600)
+            .open(&endpoint.lock_path)
+            .map_err(|err| format!("failed to open primary guard lock: {err}"))?;
+
+        if unsafe { libc::flock(lock.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) } == 0 {
+            return Ok(Some(LocalIpcGuard { _lock: lock }));
+        }
+
+        let err = io::Error::last_os_error();
+        if err.kind() == io::ErrorKind::WouldBlock {
+            Ok(None)
+        } else {
+            Err(format!("failed to lock primary guard: {err}"))
+        }
+    }
+
+    pub fn local_ipc_primary_guard_exists(endpoint: &LocalIpcEndpoint) -> Result<bool, String> {
+        match Self::try_claim_local_ipc_primary_guard(endpoint)? {
+            Some(_guard) => Ok(false),
+            None => Ok(true),
+        }
+    }
+
+    pub fn start_local_ipc_server(endpoint: &LocalIpcEndpoint) -> Result<LocalIpcServer, String> {
+        Self::start_local_ipc_server_with_wake(endpoint, None)
+    }
+
+    /// Binds the endpoint socket; callers must hold the primary guard, since a
+    /// leftover socket file from a crashed primary is removed first.
+    pub fn start_local_ipc_server_with_wake(
+        endpoint: &LocalIpcEndpoint,
+        wake: Option<LocalIpcWake>,
+    ) -> Result<LocalIpcServer, String> {
+        let _ = fs::remove_file(&endpoint.socket_path);
+        let listener = UnixListener::bind(&endpoint.socket_path)
+            .map_err(|err| format!("failed to bind local IPC socket: {err}"))?;
+        fs::set_permissions(&endpoint.socket_path, fs::Permissions::from_mode(0o600))
+            .and_then(|()| listener.set_nonblocking(true))
+            .map_err(|err| {
+                let _ = fs::remove_file(&endpoint.socket_path);
+                format!("failed to prepare local IPC socket: {err}")
+            })?;
+
+        let (request_tx, request_rx) = mpsc::channel();
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let thread_shutdown = Arc::clone(&shutdown);
+        let thread = thread::Builder::new()
+            .name("cpu-affinity-tool-ipc".to_string())
+            .spawn(move || server_loop(listener, request_tx, thread_shutdown, wake))
+            .map_err(|err| {
+                let _ = fs::remove_file(&endpoint.socket_path);
+                format!("failed to spawn local IPC server: {err}")
+            })?;
+
+        Ok(LocalIpcServer {
+            endpoint: endpoint.clone(),
+            request_rx,
+            shutdown,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn send_local_ipc_request(
+        endpoint: &LocalIpcEndpoint,
+        request: &[u8],
+        timeout: Duration,
+    ) -> Result<Vec<u8>, LocalIpcClientError> {
+        if request.len() > MAX_FRAME_BYTES {
+            return Err(LocalIpcClientError::Io(
+                "request frame is too large".to_string(),
+            ));
+        }
+        if timeout.is_zero() {
+            return Err(LocalIpcClientError::Timeout);
+        }
+
+        let mut stream =
+            UnixStream::connect(&endpoint.socket_path).map_err(|err| match err.kind() {
+                io::ErrorKind::NotFound => LocalIpcClientError::NoServer,
+                io::ErrorKind::ConnectionRefused => LocalIpcClientError::ServerNotReady,
+                _ => LocalIpcClientError::Io(format!("failed to connect local IPC socket: {err}")),
+            })?;
+
+        if !same_user_peer(&stream) {
+            return Err(LocalIpcClientError::SecurityRejected(
+                "server user did not match current user".to_string(),
+            ));
+        }
+
+        let deadline = Instant::now() + timeout;
+        stream
+            .set_write_timeout(Some(timeout))
+            .and_then(|()| stream.write_all(request))
+            .and_then(|()| stream.shutdown(Shutdown::Write))
+            .map_err(client_error_from_io)?;
+
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(LocalIpcClientError::Timeout);
+        }
+        stream
+            .set_read_timeout(Some(remaining))
+            .map_err(client_error_from_io)?;
+        let response = read_frame(&mut stream).map_err(client_error_from_io)?;
+        if response.is_empty() {
+            return Err(LocalIpcClientError::SecurityRejected(
+                "local IPC server closed the socket before sending a response".to_string(),
+            ));
+        }
+
+        Ok(response)
+    }
+}
+
+fn server_loop(
+    listener: UnixListener,
+    request_tx: Sender<LocalIpcRequest>,
+    shutdown: Arc<AtomicBool>,
+    wake: Option<LocalIpcWake>,
+) {
+    while !shutdown.load(Ordering::SeqCst) {
+        let mut stream = match listener.accept() {
+            Ok((stream, _)) => stream,
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+                continue;
+            }
+            Err(_) => continue,
+        };
+
+        if !same_user_peer(&stream)
+            || stream.set_nonblocking(false).is_err()
+            || stream.set_read_timeout(Some(SERVER_READ_TIMEOUT)).is_err()
+        {
+            continue;
+        }
+
+        let request = match read_frame(&mut stream) {
+            Ok(request) if !request.is_empty() => request,
+            _ => continue,
+        };
+
+        let (response_tx, response_rx) = mpsc::channel();
+        if request_tx
+            .send(LocalIpcRequest {
+                request,
+                response_tx,
+            })
+            .is_err()
+        {
+            break;
+        }
+        if let Some(wake) = &wake {
+            wake();
+        }
+
+        let Some(response) = receive_response(&response_rx, &shutdown) else {
+            break;
+        };
+        let _ = stream.set_write_timeout(Some(RESPONSE_TIMEOUT));
+        let _ = stream.write_all(&response);
+    }
+}
+
+fn receive_response(response_rx: &Receiver<Vec<u8>>, shutdown: &AtomicBool) -> Option<Vec<u8>> {
+    let deadline = Instant::now() + RESPONSE_TIMEOUT;
+    loop {
+        if shutdown.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            return Some(b"{\"version\":1,\"code\":\"timeout\"}".to_vec());
+        }
+
+        let wait = std::cmp::min(ACCEPT_POLL_INTERVAL, deadline - now);
+        match response_rx.recv_timeout(wait) {
+            Ok(response) => return Some(response),
+            Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Some(b"{\"version\":1,\"code\":\"timeout\"}".to_vec());
+            }
+        }
+    }
+}
+
+/// Reads until the peer closes its write half, rejecting frames over the limit.
+fn read_frame(stream: &mut UnixStream) -> io::Result<Vec<u8>> {
+    let mut frame = Vec::new();
+    stream
+        .take(MAX_FRAME_BYTES as u64 + 1)
+        .read_to_end(&mut frame)?;
+    if frame.len() > MAX_FRAME_BYTES {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "local IPC frame exceeded read limit",
+        ));
+    }
+    Ok(frame)
+}
+
+fn client_error_from_io(err: io::Error) -> LocalIpcClientError {
+    match err.kind() {
+        io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut => LocalIpcClientError::Timeout,
+        io::ErrorKind::BrokenPipe | io::ErrorKind::ConnectionReset => {
+            LocalIpcClientError::ServerNotReady
+        }
+        _ => LocalIpcClientError::Io(format!("local IPC socket failed: {err}")),
+    }
+}
+
+fn same_user_peer(stream: &UnixStream) -> bool {
+    let mut credentials = libc::ucred {
+        pid: 0,
+        uid: 0,
+        gid: 0,
+    };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let result = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            (&mut credentials as *mut libc::ucred).cast(),
+            &mut len,
+        )
+    };
+
+    result == 0 && credentials.uid == unsafe { libc::getuid() }
+}
+
+fn stable_hash(value: &str) -> u64 {
+    let mut hash = 0xcbf29ce484222325u64;
+    for byte in value.as_bytes() {
+        hash ^= *byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+
+    static NEXT_TEST_ID: AtomicU64 = AtomicU64::new(1);
+
+    fn unique_endpoint(label: &str) -> LocalIpcEndpoint {
+        let id = NEXT_TEST_ID.fetch_add(1, AtomicOrdering::SeqCst);
+        let name = format!("cpu-affinity-tool-test-{label}-{}-{id}", std::process::id());
+        let dir = std::env::temp_dir();
+        LocalIpcEndpoint {
+            socket_path: dir.join(format!("{name}.sock")),
+            lock_path: dir.join(format!("{name}.lock")),
+        }
+    }
+
+    #[test]
+    fn test_private_temp_dir_is_created_0700_and_rejects_open_dirs() {
+        let base = unique_endpoint("private").lock_path.with_extension("d");
+        fs::create_dir_all(&base).unwrap();
+        let uid = unsafe { libc::getuid() };
+
+        let dir = private_temp_dir(&base, uid).unwrap();
+        assert_eq!(fs::metadata(&dir).unwrap().mode() & 0o777, 0o700);
+        assert_eq!(private_temp_dir(&base, uid), Ok(dir.clone()));
+
+        fs::set_permissions(&dir, fs::Permissions::from_mode(0o777)).unwrap();
+        assert!(private_temp_dir(&base, uid).is_err());
+        assert!(private_temp_dir(&base, uid.wrapping_add(1)).is_err());
+
+        let _ = fs::remove_dir_all(&base);
+    }
+
+    #[test]
+    fn test_primary_guard_is_exclusive_until_dropped() {
+        let endpoint = unique_endpoint("guard");
+
+        let guard = OS::try_claim_local_ipc_primary_guard(&endpoint)
+            .unwrap()
+            .expect("first claim should own the guard");
+        assert!(
+            OS::try_claim_local_ipc_primary_guard(&endpoint)
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(OS::local_ipc_primary_guard_exists(&endpoint), Ok(true));
+
+        drop(guard);
+        assert_eq!(OS::local_ipc_primary_guard_exists(&endpoint), Ok(false));
+        let _ = fs::remove_file(&endpoint.lock_path);
+    }
+
+    #[test]
+    fn test_request_roundtrips_through_server_and_missing_socket_reports_no_server() {
+        let endpoint = unique_endpoint("roundtrip");
+        assert_eq!(
+            OS::send_local_ipc_request(&endpoint, b"ping", Duration::from_millis(50)),
+            Err(LocalIpcClientError::NoServer)
+        );
+
+        let server = OS::start_local_ipc_server(&endpoint).unwrap();
+        let responder = thread::spawn(move || {
+            let deadline = Instant::now() + Duration::from_secs(2);
+            loop {
+                match server.try_recv() {
+                    Ok(request) => {
+                        let mut response = request.request.clone();
+                        response.reverse();
+                        request.response_tx.send(response).unwrap();
+                        return server;
+                    }
+                    Err(_) if Instant::now() < deadline => {
+                        thread::sleep(Duration::from_millis(10));
+                    }
+                    Err(err) => panic!("no IPC request received: {err:?}"),
+                }
+            }
+        });
+
+        assert_eq!(
+            OS::send_local_ipc_request(&endpoint, b"ping", Duration::from_secs(2)),
+            Ok(b"gnip".to_vec())
+        );
+        drop(responder.join().unwrap());
+        assert!(!endpoint.socket_path.exists());
+    }
+}
@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub enum PriorityClass {
     Idle,
     BelowNormal,
@@ -8,4 +8,151 @@ pub enum PriorityClass {
     AboveNormal,
     High,
     Realtime,
+    /// A named class combined with extra scheduling hints.
+    Custom {
+        class: PriorityClassName,
+        /// Windows "Efficiency mode" (EcoQoS); `SCHED_IDLE` on Linux
+        eco_qos: bool,
+    },
+}
+
+/// The six named priority classes, used as the base of a custom priority.
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum PriorityClassName {
+    Idle,
+    BelowNormal,
+    Normal,
+    AboveNormal,
+    High,
+    Realtime,
+}
+
+/// How the main window of a launched program is shown.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum WindowShow {
+    #[default]
+    Normal,
+    /// Minimized without taking focus
+    Minimized,
+    Hidden,
+}
+
+/// Disk and network I/O priority of a process, set beside its CPU priority.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum IoPriority {
+    #[default]
+    Normal,
+    Low,
+    /// Only served when nothing else is waiting for the disk
+    VeryLow,
+}
+
+/// A thread of a running process.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ThreadInfo {
+    pub tid: u32,
+    /// Thread description/name; empty when the thread has none
+    pub name: String,
+}
+
+/// A running process with its current scheduling settings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ProcessInfo {
+    pub pid: u32,
+    /// Executable name, e.g. `game.exe`
+    pub name: String,
+    pub affinity_mask: usize,
+    pub priority: PriorityClass,
+}
+
+/// What a `ProcessWatch` last saw of the process it follows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessState {
+    Running,
+    /// The exit code, when the system still had it
+    Exited(Option<u32>),
+}
+
+/// A process started by `OS::run`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LaunchedProcess {
+    pub pid: u32,
+    /// Tries it took to apply affinity and priority; 1 when the first one succeeded
+    pub settings_attempts: u32,
+    /// Whether it runs in a Job Object that holds its children to the same threads
+    pub in_job: bool,
+}
+
+impl PriorityClass {
+    /// Builds a priority, keeping the plain variant when no extra hints are set.
+    pub fn from_parts(class: PriorityClassName, eco_qos: bool) -> Self {
+        if eco_qos {
+            PriorityClass::Custom { class, eco_qos }
+        } else {
+            class.into()
+        }
+    }
+
+    pub fn class_name(self) -> PriorityClassName {
+        match self {
+            PriorityClass::Idle => PriorityClassName::Idle,
+            PriorityClass::BelowNormal => PriorityClassName::BelowNormal,
+            PriorityClass::Normal => PriorityClassName::Normal,
+            PriorityClass::AboveNormal => PriorityClassName::AboveNormal,
+            PriorityClass::High => PriorityClassName::High,
+            PriorityClass::Realtime => PriorityClassName::Realtime,
+            PriorityClass::Custom { class, .. } => class,
+        }
+    }
+
+    pub fn eco_qos(self) -> bool {
+        matches!(self, PriorityClass::Custom { eco_qos: true, .. })
+    }
+}
+
+impl From<PriorityClassName> for PriorityClass {
+    fn from(class: PriorityClassName) -> Self {
+        match class {
+            PriorityClassName::Idle => PriorityClass::Idle,
+            PriorityClassName::BelowNormal => PriorityClass::BelowNormal,
+            PriorityClassName::Normal => PriorityClass::Normal,
+            PriorityClassName::AboveNormal => PriorityClass::AboveNormal,
+            PriorityClassName::High => PriorityClass::High,
+            PriorityClassName::Realtime => PriorityClass::Realtime,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PriorityClass, PriorityClassName};
+
+    #[test]
+    fn test_plain_priorities_keep_their_serialized_form() {
+        assert_eq!(
+            serde_json::to_string(&PriorityClass::High).unwrap(),
+            r#""High""#
+        );
+        assert_eq!(
+            serde_json::from_str::<PriorityClass>(r#""BelowNormal""#).unwrap(),
+            PriorityClass::BelowNormal
+        );
+        assert_eq!(
+            PriorityClass::from_parts(PriorityClassName::High, false),
+            PriorityClass::High
+        );
+    }
+
+    #[test]
+    fn test_custom_priority_roundtrips_with_eco_qos() {
+        let priority = PriorityClass::from_parts(PriorityClassName::Idle, true);
+        let json = serde_json::to_string(&priority).unwrap();
+        assert_eq!(
+            serde_json::from_str::<PriorityClass>(&json).unwrap(),
+            priority
+        );
+        assert_eq!(priority.class_name(), PriorityClassName::Idle);
+        assert!(priority.eco_qos());
+        assert!(!PriorityClass::Idle.eco_qos());
+    }
 }
@@ -1,33 +1,76 @@
 use std::ffi::OsStr;
 use std::os::windows::ffi::OsStrExt;
 
-use windows::Win32::Foundation::{CloseHandle, HANDLE};
+use windows::Win32::Foundation::{
+    CloseHandle, ERROR_ACCESS_DENIED, ERROR_ELEVATION_REQUIRED, ERROR_INVALID_PARAMETER, HANDLE,
+};
 use windows::Win32::Globalization::{MULTI_BYTE_TO_WIDE_CHAR_FLAGS, MultiByteToWideChar};
 use windows::Win32::System::Environment::ExpandEnvironmentStringsW;
 use windows::Win32::System::Threading::{
     ABOVE_NORMAL_PRIORITY_CLASS, BELOW_NORMAL_PRIORITY_CLASS, HIGH_PRIORITY_CLASS,
-    IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, OpenProcess, PROCESS_ACCESS_RIGHTS,
-    PROCESS_CREATION_FLAGS, REALTIME_PRIORITY_CLASS,
+    IDLE_PRIORITY_CLASS, NORMAL_PRIORITY_CLASS, OpenProcess, OpenThread, PROCESS_ACCESS_RIGHTS,
+    PROCESS_CREATION_FLAGS, PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+    PROCESS_POWER_THROTTLING_EXECUTION_SPEED, PROCESS_POWER_THROTTLING_STATE,
+    ProcessPowerThrottling, REALTIME_PRIORITY_CLASS, SetPriorityClass, SetProcessInformation,
+    THREAD_ACCESS_RIGHTS,
 };
 use windows::core::PCWSTR;
 
-use crate::PriorityClass;
+use crate::{OsApiError, PriorityClass, PriorityClassName};
 
 #[derive(Debug)]
 pub(super) enum OsError {
     Win(windows::core::Error),
+    /// Opening a process or thread failed because the id names nothing live
+    Gone(windows::core::Error),
+    /// An error already classified by a nested call
+    Api(OsApiError),
     Msg(String),
 }
 
+impl OsError {
+    /// Converts into the public error; `message` becomes its full text.
+    pub(super) fn into_api(self, message: String) -> OsApiError {
+        match self {
+            OsError::Win(e) => win_api_error(&e, message),
+            OsError::Gone(_) => OsApiError::ProcessNotFound(message),
+            OsError::Api(e) => e.with_message(message),
+            OsError::Msg(_) => OsApiError::Io(message),
+        }
+    }
+}
+
 impl std::fmt::Display for OsError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            OsError::Win(e) => write!(f, "{e}"),
+            OsError::Win(e) | OsError::Gone(e) => write!(f, "{e}"),
+            OsError::Api(e) => write!(f, "{e}"),
             OsError::Msg(s) => write!(f, "{s}"),
         }
     }
 }
 
+pub(super) fn win_api_error(err: &windows::core::Error, message: String) -> OsApiError {
+    let code = err.code();
+    if code == ERROR_ACCESS_DENIED.to_hresult() || code == ERROR_ELEVATION_REQUIRED.to_hresult() {
+        OsApiError::AccessDenied(message)
+    } else {
+        OsApiError::Win {
+            code: code.0,
+            message,
+        }
+    }
+}
+
+/// `OpenProcess`/`OpenThread` report an id that names nothing as an invalid parameter.
+fn open_error(e: windows::core::Error) -> OsError {
+    if e.code() == ERROR_INVALID_PARAMETER.to_hresult() {
+        OsError::Gone(e)
+    } else {
+        OsError::Win(e)
+    }
+}
+
 impl From<windows::core::Error> for OsError {
     fn from(e: windows::core::Error) -> Self {
         Self::Win(e)
@@ -55,18 +98,57 @@ impl Drop for ComGuard {
 }
 
 pub(super) fn open_process(pid: u32, access: PROCESS_ACCESS_RIGHTS) -> Result<HANDLE, OsError> {
-    unsafe { Ok(OpenProcess(access, false, pid)?) }
+    unsafe { OpenProcess(access, false, pid).map_err(open_error) }
+}
+
+pub(super) fn open_thread(tid: u32, access: THREAD_ACCESS_RIGHTS) -> Result<HANDLE, OsError> {
+    unsafe { OpenThread(access, false, tid).map_err(open_error) }
 }
 
 pub(super) fn transform_to_win_priority(p: PriorityClass) -> PROCESS_CREATION_FLAGS {
-    match p {
-        PriorityClass::Idle => IDLE_PRIORITY_CLASS,
-        PriorityClass::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
-        PriorityClass::Normal => NORMAL_PRIORITY_CLASS,
-        PriorityClass::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
-        PriorityClass::High => HIGH_PRIORITY_CLASS,
-        PriorityClass::Realtime => REALTIME_PRIORITY_CLASS,
+    match p.class_name() {
+        PriorityClassName::Idle => IDLE_PRIORITY_CLASS,
+        PriorityClassName::BelowNormal => BELOW_NORMAL_PRIORITY_CLASS,
+        PriorityClassName::Normal => NORMAL_PRIORITY_CLASS,
+        PriorityClassName::AboveNormal => ABOVE_NORMAL_PRIORITY_CLASS,
+        PriorityClassName::High => HIGH_PRIORITY_CLASS,
+        PriorityClassName::Realtime => REALTIME_PRIORITY_CLASS,
+    }
+}
+
+/// Turns Windows "Efficiency mode" (EcoQoS) on, or hands throttling back to the system.
+fn set_eco_qos(handle: HANDLE, enabled: bool) -> Result<(), OsError> {
+    let mask = if enabled {
+        PROCESS_POWER_THROTTLING_EXECUTION_SPEED
+    } else {
+        0
+    };
+    let state = PROCESS_POWER_THROTTLING_STATE {
+        Version: PROCESS_POWER_THROTTLING_CURRENT_VERSION,
+        ControlMask: mask,
+        StateMask: mask,
+    };
+    unsafe {
+        SetProcessInformation(
+            handle,
+            ProcessPowerThrottling,
+            &state as *const PROCESS_POWER_THROTTLING_STATE as *const std::ffi::c_void,
+            std::mem::size_of::<PROCESS_POWER_THROTTLING_STATE>() as u32,
+        )?;
+    }
+    Ok(())
+}
+
+/// Sets the priority class and the EcoQoS state requested by `priority`.
+pub(super) fn apply_priority(handle: HANDLE, priority: PriorityClass) -> Result<(), OsError> {
+    unsafe { SetPriorityClass(handle, transform_to_win_priority(priority))? };
+    if priority.eco_qos() {
+        set_eco_qos(handle, true)?;
+    } else {
+        // Builds before Windows 10 1709 have no power throttling to reset.
+        let _ = set_eco_qos(handle, false);
     }
+    Ok(())
 }
 
 pub(super) fn from_win_priority(p: u32) -> PriorityClass {
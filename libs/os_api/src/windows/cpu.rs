@@ -1,9 +1,112 @@
+use ntapi::ntexapi::{
+    NtQuerySystemInformation, SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION,
+    SystemProcessorPerformanceInformation,
+};
+use std::time::Duration;
+use windows::Win32::System::Kernel::PROCESSOR_NUMBER;
+use windows::Win32::System::SystemInformation::{
+    CpuSetInformation, GetSystemCpuSetInformation, SYSTEM_CPU_SET_INFORMATION,
+};
+use windows::Win32::System::Threading::{
+    GetNumaProcessorNodeEx, PROCESS_SET_LIMITED_INFORMATION, SetProcessDefaultCpuSets,
+};
 use winreg::RegKey;
 use winreg::enums::HKEY_LOCAL_MACHINE;
 
+use crate::OsApiError;
+
 use super::OS;
+use super::common::{HandleGuard, OsError, open_process};
+
+/// Returns the CPU set IDs of every logical processor that belongs to `node`.
+fn cpu_set_ids_for_numa_node(node: u16) -> Result<Vec<u32>, OsError> {
+    unsafe {
+        let mut len = 0u32;
+        let _ = GetSystemCpuSetInformation(None, 0, &mut len, None, None);
+        if len == 0 {
+            return Err(OsError::Msg(
+                "GetSystemCpuSetInformation returned no data".into(),
+            ));
+        }
+
+        // u64 backing keeps the variable-length records suitably aligned.
+        let mut buf = vec![0u64; (len as usize).div_ceil(8)];
+        GetSystemCpuSetInformation(
+            Some(buf.as_mut_ptr() as *mut SYSTEM_CPU_SET_INFORMATION),
+            len,
+            &mut len,
+            None,
+            None,
+        )
+        .ok()?;
+
+        let bytes = buf.as_ptr() as *const u8;
+        let mut offset = 0usize;
+        let mut ids = Vec::new();
+        while offset + size_of::<SYSTEM_CPU_SET_INFORMATION>() <= len as usize {
+            let entry = &*(bytes.add(offset) as *const SYSTEM_CPU_SET_INFORMATION);
+            if entry.Size == 0 {
+                break;
+            }
+            if entry.Type == CpuSetInformation
+                && u16::from(entry.Anonymous.CpuSet.NumaNodeIndex) == node
+            {
+                ids.push(entry.Anonymous.CpuSet.Id);
+            }
+            offset += entry.Size as usize;
+        }
+        Ok(ids)
+    }
+}
+
+/// `(idle, total)` 100ns ticks of every logical processor in the current processor group.
+fn read_core_times() -> Result<Vec<(u64, u64)>, String> {
+    // One processor group holds at most 64 logical processors.
+    let mut info: Vec<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION> =
+        vec![unsafe { std::mem::zeroed() }; 64];
+    let mut returned = 0u32;
+    let status = unsafe {
+        NtQuerySystemInformation(
+            SystemProcessorPerformanceInformation,
+            info.as_mut_ptr().cast(),
+            (info.len() * size_of::<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION>()) as u32,
+            &mut returned,
+        )
+    };
+    if status < 0 {
+        return Err(format!(
+            "NtQuerySystemInformation failed with status {status:#x}"
+        ));
+    }
+    info.truncate(returned as usize / size_of::<SYSTEM_PROCESSOR_PERFORMANCE_INFORMATION>());
+
+    Ok(info
+        .iter()
+        .map(|core| unsafe {
+            let idle = *core.IdleTime.QuadPart() as u64;
+            // Kernel time already includes idle time.
+            let total = *core.KernelTime.QuadPart() as u64 + *core.UserTime.QuadPart() as u64;
+            (idle, total)
+        })
+        .collect())
+}
 
 impl OS {
+    /// `(idle, total)` ticks of every logical processor, indexed by core; see
+    /// [`OS::core_usage_between`] for turning two readings into usage.
+    pub fn read_core_times() -> Result<Vec<(u64, u64)>, String> {
+        read_core_times()
+    }
+
+    /// Samples how busy every logical core was over `window`, blocking for its duration.
+    /// Returns busy fractions (0.0..=1.0) indexed by core.
+    pub fn sample_per_core_usage(window: Duration) -> Result<Vec<f32>, String> {
+        let before = read_core_times()?;
+        std::thread::sleep(window);
+        let after = read_core_times()?;
+        Ok(Self::core_usage_between(&before, &after))
+    }
+
     pub fn get_cpu_model() -> String {
         let hklm = RegKey::predef(HKEY_LOCAL_MACHINE);
         hklm.open_subkey(r"HARDWARE\DESCRIPTION\System\CentralProcessor\0")
@@ -15,4 +118,51 @@ impl OS {
             })
             .unwrap_or_else(|_| "Unknown CPU".to_string())
     }
+
+    /// Returns the NUMA node that owns logical processor `core`.
+    ///
+    /// **Note:** Like the affinity helpers, only the first processor group is addressed.
+    pub fn get_numa_node_for_core(core: usize) -> Result<u16, String> {
+        (|| unsafe {
+            let number = u8::try_from(core)
+                .ok()
+                .filter(|&n| (n as u32) < usize::BITS)
+                .ok_or_else(|| OsError::Msg("core index out of range".into()))?;
+            let processor = PROCESSOR_NUMBER {
+                Group: 0,
+                Number: number,
+                Reserved: 0,
+            };
+            let mut node = 0u16;
+            GetNumaProcessorNodeEx(&processor, &mut node)?;
+            Ok(node)
+        })()
+        .map_err(|e: OsError| format!("Failed to get NUMA node for core {}: {}", core, e))
+    }
+
+    /// Prefers the logical processors of NUMA `node` for every thread of a process.
+    ///
+    /// Uses the process default CPU sets, a soft preference that the scheduler intersects
+    /// with the affinity mask, so threads stay on one node and allocate node-local memory.
+    pub fn set_numa_preference(pid: u32, node: u16) -> Result<(), OsApiError> {
+        (|| unsafe {
+            let ids = cpu_set_ids_for_numa_node(node)?;
+            if ids.is_empty() {
+                return Err(OsError::Msg(format!("NUMA node {node} has no processors")));
+            }
+
+            let handle = open_process(pid, PROCESS_SET_LIMITED_INFORMATION)?;
+            let _hg = HandleGuard(handle);
+
+            SetProcessDefaultCpuSets(handle, Some(&ids)).ok()?;
+            Ok(())
+        })()
+        .map_err(|e: OsError| {
+            let message = format!(
+                "Failed to set NUMA node {} preference for process {}: {}",
+                node, pid, e
+            );
+            e.into_api(message)
+        })
+    }
 }
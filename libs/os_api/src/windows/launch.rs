@@ -1,3 +1,5 @@
+use std::ffi::c_void;
+use std::mem::size_of;
 use std::os::windows::io::AsRawHandle;
 use std::path::PathBuf;
 use std::process::{Child, Command, Stdio};
@@ -7,17 +9,29 @@ use windows::Win32::Foundation::HANDLE;
 use windows::Win32::System::Com::{
     CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx,
 };
+use windows::Win32::System::JobObjects::{
+    AssignProcessToJobObject, CreateJobObjectW, JOB_OBJECT_CPU_RATE_CONTROL_ENABLE,
+    JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP, JOB_OBJECT_LIMIT_AFFINITY,
+    JOBOBJECT_BASIC_LIMIT_INFORMATION, JOBOBJECT_CPU_RATE_CONTROL_INFORMATION,
+    JOBOBJECT_CPU_RATE_CONTROL_INFORMATION_0, JobObjectBasicLimitInformation,
+    JobObjectCpuRateControlInformation, SetInformationJobObject,
+};
 use windows::Win32::System::Threading::{
-    CREATE_SUSPENDED, CreateProcessW, PROCESS_INFORMATION, ResumeThread, STARTUPINFOW,
-    SetPriorityClass, SetProcessAffinityMask,
+    CREATE_SUSPENDED, CreateProcessW, GetCurrentProcess, GetProcessAffinityMask,
+    PROCESS_INFORMATION, PROCESS_SET_QUOTA, PROCESS_TERMINATE, ResumeThread, STARTF_USESHOWWINDOW,
+    STARTUPINFOW, SetProcessAffinityMask, TerminateProcess,
 };
 use windows::Win32::UI::Shell::{ApplicationActivationManager, IApplicationActivationManager};
+use windows::Win32::UI::WindowsAndMessaging::{SHOW_WINDOW_CMD, SW_HIDE, SW_SHOWMINNOACTIVE};
 use windows::core::{PCWSTR, PWSTR};
 
-use crate::PriorityClass;
+use crate::affinity::{LAUNCH_SETTINGS_ATTEMPTS, LAUNCH_SETTINGS_RETRY_DELAY};
+use crate::{LaunchedProcess, OsApiError, PriorityClass, WindowShow};
 
 use super::OS;
-use super::common::{ComGuard, HandleGuard, OsError, to_wide_z_str, transform_to_win_priority};
+use super::common::{
+    ComGuard, HandleGuard, OsError, apply_priority, open_process, to_wide_z_str, win_api_error,
+};
 
 pub(super) fn quote_arg_windows(arg: &str) -> String {
     if arg.is_empty() {
@@ -66,6 +80,20 @@ pub(super) fn build_command_line(exe: &PathBuf, args: &[String]) -> String {
     parts.join(" ")
 }
 
+/// Longest command line `CreateProcessW` accepts, in UTF-16 units without the terminator.
+const MAX_COMMAND_LINE_LEN: usize = 32_766;
+
+/// Rejects command lines `CreateProcessW` would refuse with an unhelpful error.
+fn check_command_line_length(cmdline: &str) -> Result<(), OsApiError> {
+    let len = cmdline.encode_utf16().count();
+    if len > MAX_COMMAND_LINE_LEN {
+        return Err(OsApiError::Io(format!(
+            "command line is {len} characters long but Windows allows at most {MAX_COMMAND_LINE_LEN}; shorten or remove some arguments"
+        )));
+    }
+    Ok(())
+}
+
 #[allow(dead_code)]
 fn spawn(target: &PathBuf, args: &[String]) -> Result<Child, String> {
     let mut cmd = Command::new(target);
@@ -85,11 +113,110 @@ fn set_affinity(child: &Child, mask: usize) -> Result<(), String> {
         .map_err(|e| format!("SetProcessAffinityMask failed: {}", e))
 }
 
+/// Runs `spawn` with this process widened to the system affinity mask, so a child
+/// started while the tool is pinned to its own threads does not inherit them.
+pub(super) fn with_system_affinity<T>(spawn: impl FnOnce() -> T) -> T {
+    let own_process = unsafe { GetCurrentProcess() };
+    let mut own_mask: usize = 0;
+    let mut system_mask: usize = 0;
+    let widened = unsafe {
+        GetProcessAffinityMask(
+            own_process,
+            &mut own_mask as *mut _,
+            &mut system_mask as *mut _,
+        )
+    }
+    .is_ok()
+        && own_mask != system_mask
+        && unsafe { SetProcessAffinityMask(own_process, system_mask) }.is_ok();
+    let spawned = spawn();
+    if widened {
+        let _ = unsafe { SetProcessAffinityMask(own_process, own_mask) };
+    }
+    spawned
+}
+
+/// Lets the main thread of a process created suspended run. When it can't, the process
+/// would sit frozen forever, so it is terminated and the launch reported as failed.
+fn resume_main_thread(process: HANDLE, thread: HANDLE) -> Result<(), OsError> {
+    // The previous suspend count: 1 means the thread now runs, -1 that the call failed.
+    let previous = unsafe { ResumeThread(thread) };
+    let problem = match previous {
+        0 | 1 => return Ok(()),
+        u32::MAX => format!(
+            "its main thread could not be resumed (suspend count -1: {})",
+            windows::core::Error::from_thread()
+        ),
+        count => format!("its main thread is still suspended (suspend count was {count})"),
+    };
+    let _ = unsafe { TerminateProcess(process, 1) };
+    Err(OsError::Msg(format!(
+        "{problem}; the stuck process was terminated"
+    )))
+}
+
+/// Applies the group mask and priority to a process that was created suspended.
+fn apply_launch_settings(
+    process: HANDLE,
+    mask: usize,
+    priority: PriorityClass,
+) -> Result<(), OsApiError> {
+    unsafe { SetProcessAffinityMask(process, mask) }
+        .map_err(|e| win_api_error(&e, e.to_string()))?;
+    apply_priority(process, priority).map_err(|e| {
+        let message = e.to_string();
+        e.into_api(message)
+    })
+}
+
+/// How a launched process gets onto the group's cores.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ChildAffinity {
+    /// `SetProcessAffinityMask` on the suspended child
+    Set,
+    /// Like `Set`, plus a Job Object that holds its children to the mask
+    Job,
+    /// Inherited from this process, whose own mask is narrowed for the creation
+    Inherited,
+}
+
+/// `None` leaves the window to the program's own default.
+fn show_window_command(window_show: WindowShow) -> Option<SHOW_WINDOW_CMD> {
+    match window_show {
+        WindowShow::Normal => None,
+        WindowShow::Minimized => Some(SW_SHOWMINNOACTIVE),
+        WindowShow::Hidden => Some(SW_HIDE),
+    }
+}
+
+/// Puts a still-suspended process in a new Job Object whose affinity limit every
+/// process it starts inherits. The job lives on with its processes after the handle
+/// closes, and children cannot break away from it.
+fn assign_to_affinity_job(process: HANDLE, mask: usize) -> Result<(), OsError> {
+    unsafe {
+        let job = CreateJobObjectW(None, PCWSTR::null()).map_err(OsError::Win)?;
+        let _jg = HandleGuard(job);
+
+        let limits = JOBOBJECT_BASIC_LIMIT_INFORMATION {
+            LimitFlags: JOB_OBJECT_LIMIT_AFFINITY,
+            Affinity: mask,
+            ..Default::default()
+        };
+        SetInformationJobObject(
+            job,
+            JobObjectBasicLimitInformation,
+            &limits as *const JOBOBJECT_BASIC_LIMIT_INFORMATION as *const c_void,
+            size_of::<JOBOBJECT_BASIC_LIMIT_INFORMATION>() as u32,
+        )
+        .map_err(OsError::Win)?;
+        AssignProcessToJobObject(job, process).map_err(OsError::Win)
+    }
+}
+
 #[allow(dead_code)]
 fn set_priority(child: &Child, priority: PriorityClass) -> Result<(), String> {
     let handle = HANDLE(child.as_raw_handle());
-    unsafe { SetPriorityClass(handle, transform_to_win_priority(priority)) }
-        .map_err(|e| format!("SetPriorityClass failed: {}", e))
+    apply_priority(handle, priority).map_err(|e| format!("SetPriorityClass failed: {}", e))
 }
 
 impl OS {
@@ -98,29 +225,144 @@ impl OS {
         args: Vec<String>,
         cores: &[usize],
         priority: PriorityClass,
-    ) -> Result<u32, String> {
+        window_show: WindowShow,
+    ) -> Result<LaunchedProcess, OsApiError> {
+        Self::create_process(
+            file_path,
+            args,
+            cores,
+            priority,
+            window_show,
+            ChildAffinity::Set,
+        )
+    }
+
+    /// Like `run`, but the process starts inside a Job Object limited to `cores`, so
+    /// everything it spawns stays on them without being corrected afterwards.
+    pub fn run_in_job(
+        file_path: PathBuf,
+        args: Vec<String>,
+        cores: &[usize],
+        priority: PriorityClass,
+        window_show: WindowShow,
+    ) -> Result<LaunchedProcess, OsApiError> {
+        Self::create_process(
+            file_path,
+            args,
+            cores,
+            priority,
+            window_show,
+            ChildAffinity::Job,
+        )
+    }
+
+    /// Like `run`, but the affinity is never set on the new process: this process is
+    /// moved onto `cores` while it creates the child, which inherits that mask, and is
+    /// moved back afterwards. Only the priority is set on the child. For programs whose
+    /// anti-cheat objects to an outside tool changing their affinity.
+    pub fn run_inheriting_affinity(
+        file_path: PathBuf,
+        args: Vec<String>,
+        cores: &[usize],
+        priority: PriorityClass,
+        window_show: WindowShow,
+    ) -> Result<LaunchedProcess, OsApiError> {
+        Self::create_process(
+            file_path,
+            args,
+            cores,
+            priority,
+            window_show,
+            ChildAffinity::Inherited,
+        )
+    }
+
+    /// Caps `pid` and everything it starts at `percent` of the machine's total CPU time
+    /// with a hard-capped Job Object. Jobs nest, so this also works for a process
+    /// already started with `run_in_job`.
+    pub fn limit_cpu_rate(pid: u32, percent: u8) -> Result<(), OsApiError> {
+        (|| unsafe {
+            let process = open_process(pid, PROCESS_SET_QUOTA | PROCESS_TERMINATE)?;
+            let _hg = HandleGuard(process);
+            let job = CreateJobObjectW(None, PCWSTR::null())?;
+            let _jg = HandleGuard(job);
+
+            // CpuRate is in hundredths of a percent.
+            let rate = JOBOBJECT_CPU_RATE_CONTROL_INFORMATION {
+                ControlFlags: JOB_OBJECT_CPU_RATE_CONTROL_ENABLE
+                    | JOB_OBJECT_CPU_RATE_CONTROL_HARD_CAP,
+                Anonymous: JOBOBJECT_CPU_RATE_CONTROL_INFORMATION_0 {
+                    CpuRate: u32::from(percent.clamp(1, 100)) * 100,
+                },
+            };
+            SetInformationJobObject(
+                job,
+                JobObjectCpuRateControlInformation,
+                &rate as *const JOBOBJECT_CPU_RATE_CONTROL_INFORMATION as *const c_void,
+                size_of::<JOBOBJECT_CPU_RATE_CONTROL_INFORMATION>() as u32,
+            )?;
+            AssignProcessToJobObject(job, process)?;
+            Ok(())
+        })()
+        .map_err(|e: OsError| {
+            let message = format!("Failed to limit CPU usage of process {pid}: {e}");
+            e.into_api(message)
+        })
+    }
+
+    fn create_process(
+        file_path: PathBuf,
+        args: Vec<String>,
+        cores: &[usize],
+        priority: PriorityClass,
+        window_show: WindowShow,
+        affinity: ChildAffinity,
+    ) -> Result<LaunchedProcess, OsApiError> {
         let mut mask = 0usize;
         for &core in cores {
-            let bit = 1usize
-                .checked_shl(core as u32)
-                .ok_or_else(|| format!("core index {} out of range for affinity mask", core))?;
+            let bit = 1usize.checked_shl(core as u32).ok_or_else(|| {
+                OsApiError::Io(format!(
+                    "core index {} out of range for affinity mask",
+                    core
+                ))
+            })?;
             mask |= bit;
         }
 
         if mask == 0 {
-            return Err("affinity mask is empty".into());
+            return Err(OsApiError::Io("affinity mask is empty".into()));
         }
 
+        let cmdline = build_command_line(&file_path, &args);
+        check_command_line_length(&cmdline).map_err(|e| {
+            let message = format!("run {:?} failed: {}", file_path, e);
+            e.with_message(message)
+        })?;
+
         (|| unsafe {
-            let cmdline = build_command_line(&file_path, &args);
             let mut cmd_w = to_wide_z_str(&cmdline);
 
             let mut si: STARTUPINFOW = std::mem::zeroed();
             si.cb = std::mem::size_of::<STARTUPINFOW>() as u32;
+            if let Some(show) = show_window_command(window_show) {
+                si.dwFlags |= STARTF_USESHOWWINDOW;
+                si.wShowWindow = show.0 as u16;
+            }
 
             let mut pi: PROCESS_INFORMATION = std::mem::zeroed();
 
-            CreateProcessW(
+            let own_process = GetCurrentProcess();
+            let mut own_mask: usize = 0;
+            let mut system_mask: usize = 0;
+            if affinity == ChildAffinity::Inherited {
+                GetProcessAffinityMask(
+                    own_process,
+                    &mut own_mask as *mut _,
+                    &mut system_mask as *mut _,
+                )?;
+                SetProcessAffinityMask(own_process, mask)?;
+            }
+            let created = CreateProcessW(
                 PCWSTR(null_mut()),
                 Some(PWSTR(cmd_w.as_mut_ptr())),
                 None,
@@ -131,24 +373,51 @@ impl OS {
                 None,
                 &si,
                 &mut pi,
-            )?;
+            );
+            if affinity == ChildAffinity::Inherited {
+                let _ = SetProcessAffinityMask(own_process, own_mask);
+            }
+            created?;
 
             let process = pi.hProcess;
             let thread = pi.hThread;
             let _pg = HandleGuard(process);
             let _tg = HandleGuard(thread);
 
-            SetProcessAffinityMask(process, mask)?;
-            SetPriorityClass(process, transform_to_win_priority(priority))?;
-
-            let _ = ResumeThread(thread);
-
-            Ok(pi.dwProcessId)
+            // Right after creation a loaded system occasionally rejects the mask, so retry briefly.
+            let (settings, settings_attempts) = OS::retry_transient(
+                LAUNCH_SETTINGS_ATTEMPTS,
+                LAUNCH_SETTINGS_RETRY_DELAY,
+                || match affinity {
+                    ChildAffinity::Inherited => apply_priority(process, priority).map_err(|e| {
+                        let message = e.to_string();
+                        e.into_api(message)
+                    }),
+                    ChildAffinity::Set | ChildAffinity::Job => {
+                        apply_launch_settings(process, mask, priority)
+                    }
+                },
+            );
+            settings.map_err(OsError::Api)?;
+            // Without the job the launch still has its own affinity; the caller says so.
+            let in_job =
+                affinity == ChildAffinity::Job && assign_to_affinity_job(process, mask).is_ok();
+
+            resume_main_thread(process, thread)?;
+
+            Ok(LaunchedProcess {
+                pid: pi.dwProcessId,
+                settings_attempts,
+                in_job,
+            })
         })()
-        .map_err(|e: OsError| format!("run {:?} failed: {}", file_path, e))
+        .map_err(|e: OsError| {
+            let message = format!("run {:?} failed: {}", file_path, e);
+            e.into_api(message)
+        })
     }
 
-    pub fn activate_application(aumid: &str) -> Result<u32, String> {
+    pub fn activate_application(aumid: &str) -> Result<u32, OsApiError> {
         (|| unsafe {
             CoInitializeEx(None, COINIT_APARTMENTTHREADED)
                 .ok()
@@ -168,7 +437,10 @@ impl OS {
 
             Ok(process_id)
         })()
-        .map_err(|e: OsError| format!("activate_application {aumid:?} failed: {e}"))
+        .map_err(|e: OsError| {
+            let message = format!("activate_application {aumid:?} failed: {e}");
+            e.into_api(message)
+        })
     }
 }
 
@@ -176,7 +448,10 @@ impl OS {
 mod tests {
     use std::path::PathBuf;
 
-    use super::{build_command_line, quote_arg_windows};
+    use super::{
+        MAX_COMMAND_LINE_LEN, build_command_line, check_command_line_length, quote_arg_windows,
+    };
+    use crate::OsApiError;
     use crate::windows::shell::split_windows_args;
 
     #[test]
@@ -209,4 +484,21 @@ mod tests {
         expected.extend(args);
         assert_eq!(split, expected);
     }
+
+    #[test]
+    fn test_oversized_command_line_is_rejected_with_its_length() {
+        let exe = PathBuf::from(r"C:\Games\launcher.exe");
+        let args = vec!["--mod=x".repeat(1000); 5];
+        let cmdline = build_command_line(&exe, &args);
+
+        let err = check_command_line_length(&cmdline).unwrap_err();
+        assert_eq!(
+            err,
+            OsApiError::Io(format!(
+                "command line is {} characters long but Windows allows at most {MAX_COMMAND_LINE_LEN}; shorten or remove some arguments",
+                cmdline.len()
+            ))
+        );
+        assert!(check_command_line_length(&build_command_line(&exe, &args[..1])).is_ok());
+    }
 }
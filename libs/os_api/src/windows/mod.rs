@@ -5,12 +5,14 @@ mod launch;
 mod processes;
 mod scheduling;
 mod shell;
+mod threads;
 mod window;
 
 pub use ipc::{
     LocalIpcClientError, LocalIpcEndpoint, LocalIpcGuard, LocalIpcRequest, LocalIpcServer,
     LocalIpcWake,
 };
+pub use processes::ProcessWatch;
 
 pub struct OS;
 
@@ -22,4 +24,12 @@ impl OS {
     pub const fn supports_installed_app_picker() -> bool {
         true
     }
+
+    pub const fn supports_window_lookup() -> bool {
+        true
+    }
+
+    pub const fn supports_job_objects() -> bool {
+        true
+    }
 }
@@ -1,9 +1,10 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::c_void;
 use std::mem::size_of;
 use std::path::PathBuf;
 
 use windows::Win32::Foundation::{
-    APPMODEL_ERROR_NO_APPLICATION, ERROR_INSUFFICIENT_BUFFER, STILL_ACTIVE,
+    APPMODEL_ERROR_NO_APPLICATION, CloseHandle, ERROR_INSUFFICIENT_BUFFER, HANDLE, STILL_ACTIVE,
 };
 use windows::Win32::Storage::Packaging::Appx::GetApplicationUserModelId;
 use windows::Win32::System::Diagnostics::ToolHelp::{
@@ -12,12 +13,48 @@ use windows::Win32::System::Diagnostics::ToolHelp::{
 use windows::Win32::System::ProcessStatus::{K32EnumProcesses, K32GetModuleFileNameExW};
 use windows::Win32::System::Threading::{
     GetExitCodeProcess, PROCESS_QUERY_INFORMATION, PROCESS_QUERY_LIMITED_INFORMATION,
+    PROCESS_TERMINATE, TerminateProcess,
 };
 use windows::core::PWSTR;
 
+use crate::{OsApiError, ProcessState};
+
 use super::OS;
 use super::common::{HandleGuard, OsError, open_process};
 
+/// Open handle to a launched process, so its exit code stays readable after it exits.
+pub struct ProcessWatch {
+    pid: u32,
+    // Kept as an integer so the watch can move to a worker thread.
+    handle: isize,
+}
+
+impl ProcessWatch {
+    pub fn pid(&self) -> u32 {
+        self.pid
+    }
+
+    pub fn state(&self) -> ProcessState {
+        let mut exit_code: u32 = 0;
+        let read =
+            unsafe { GetExitCodeProcess(HANDLE(self.handle as *mut c_void), &mut exit_code) };
+        match read {
+            Ok(()) if exit_code == STILL_ACTIVE.0 as u32 => ProcessState::Running,
+            Ok(()) => ProcessState::Exited(Some(exit_code)),
+            Err(_) if OS::is_pid_live(self.pid) => ProcessState::Running,
+            Err(_) => ProcessState::Exited(None),
+        }
+    }
+}
+
+impl Drop for ProcessWatch {
+    fn drop(&mut self) {
+        unsafe {
+            let _ = CloseHandle(HANDLE(self.handle as *mut c_void));
+        }
+    }
+}
+
 /// One snapshot used for all process-tree operations.
 pub struct ProcessTree {
     pub parent_of: HashMap<u32, u32>,
@@ -81,8 +118,10 @@ fn get_parent_pid(pid: u32) -> Option<u32> {
     tree.parent_of.get(&pid).copied()
 }
 
+/// Enumerates process IDs; a failure (e.g. under handle pressure) is reported instead of
+/// panicking so callers can skip the current tick.
 #[allow(dead_code)]
-fn get_all_pids() -> Vec<u32> {
+fn get_all_pids() -> Result<Vec<u32>, String> {
     let mut cap = 1024usize;
 
     loop {
@@ -93,19 +132,22 @@ fn get_all_pids() -> Vec<u32> {
             .as_bool();
 
         if !ok {
-            return Vec::new();
+            return Err(format!(
+                "K32EnumProcesses failed: {}",
+                windows::core::Error::from_thread()
+            ));
         }
 
         let count = needed as usize / 4;
         if count < buf.len() {
             buf.truncate(count);
-            return buf;
+            return Ok(buf);
         }
 
         cap *= 2;
         if cap > 1_048_576 {
             buf.truncate(count);
-            return buf;
+            return Ok(buf);
         }
     }
 }
@@ -239,6 +281,40 @@ impl OS {
         }
     }
 
+    /// Terminates `pid` and every descendant, returning the PIDs that were terminated.
+    ///
+    /// PIDs that exit before they can be opened are skipped rather than reported as errors.
+    pub fn terminate_tree(pid: u32) -> Result<Vec<u32>, OsApiError> {
+        let mut targets = vec![pid];
+        Self::find_all_descendants(pid, &mut targets);
+
+        let mut terminated = Vec::with_capacity(targets.len());
+        let mut first_error = None;
+        for target in targets {
+            let result: Result<(), OsError> = (|| unsafe {
+                let handle = open_process(target, PROCESS_TERMINATE)?;
+                let _hg = HandleGuard(handle);
+                TerminateProcess(handle, 1)?;
+                Ok(())
+            })();
+            match result {
+                Ok(()) => terminated.push(target),
+                Err(OsError::Gone(_)) => {}
+                Err(err) => {
+                    if first_error.is_none() {
+                        let message = format!("Failed to terminate process {}: {}", target, err);
+                        first_error = Some(err.into_api(message));
+                    }
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) if terminated.is_empty() => Err(err),
+            _ => Ok(terminated),
+        }
+    }
+
     pub fn is_pid_live(pid: u32) -> bool {
         unsafe {
             let handle = match open_process(pid, PROCESS_QUERY_LIMITED_INFORMATION)
@@ -256,7 +332,7 @@ impl OS {
         }
     }
 
-    pub fn get_process_image_path(pid: u32) -> Result<PathBuf, String> {
+    pub fn get_process_image_path(pid: u32) -> Result<PathBuf, OsApiError> {
         (|| unsafe {
             let handle = open_process(pid, PROCESS_QUERY_LIMITED_INFORMATION)
                 .or_else(|_| open_process(pid, PROCESS_QUERY_INFORMATION))?;
@@ -271,10 +347,30 @@ impl OS {
             let path_str = String::from_utf16_lossy(&buffer[..len as usize]);
             Ok(PathBuf::from(path_str))
         })()
-        .map_err(|e: OsError| format!("Failed to get image path for process {}: {}", pid, e))
+        .map_err(|e: OsError| {
+            let message = format!("Failed to get image path for process {}: {}", pid, e);
+            e.into_api(message)
+        })
+    }
+
+    pub fn watch_process(pid: u32) -> Result<ProcessWatch, OsApiError> {
+        open_process(pid, PROCESS_QUERY_LIMITED_INFORMATION)
+            .map(|handle| ProcessWatch {
+                pid,
+                handle: handle.0 as isize,
+            })
+            .map_err(|e| {
+                let message = format!("Failed to watch process {}: {}", pid, e);
+                e.into_api(message)
+            })
     }
 
-    pub fn get_process_app_user_model_id(pid: u32) -> Result<Option<String>, String> {
+    /// AppImages are a Linux format.
+    pub fn get_process_appimage_path(_pid: u32) -> Result<Option<PathBuf>, OsApiError> {
+        Ok(None)
+    }
+
+    pub fn get_process_app_user_model_id(pid: u32) -> Result<Option<String>, OsApiError> {
         (|| unsafe {
             let handle = open_process(pid, PROCESS_QUERY_LIMITED_INFORMATION)
                 .or_else(|_| open_process(pid, PROCESS_QUERY_INFORMATION))?;
@@ -308,7 +404,10 @@ impl OS {
             let slice_len = len.saturating_sub(1) as usize;
             Ok(Some(String::from_utf16_lossy(&buffer[..slice_len])))
         })()
-        .map_err(|e: OsError| format!("Failed to get AppUserModelId for process {}: {}", pid, e))
+        .map_err(|e: OsError| {
+            let message = format!("Failed to get AppUserModelId for process {}: {}", pid, e);
+            e.into_api(message)
+        })
     }
 }
 
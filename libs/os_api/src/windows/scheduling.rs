@@ -1,22 +1,20 @@
+use ntapi::ntpsapi::{NtSetInformationProcess, ProcessIoPriority};
 use windows::Win32::System::Threading::{
     GetCurrentProcess, GetPriorityClass, GetProcessAffinityMask, PROCESS_QUERY_INFORMATION,
-    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION, SetPriorityClass,
-    SetProcessAffinityMask,
+    PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_SET_INFORMATION, SetProcessAffinityMask,
 };
 
-use crate::PriorityClass;
+use crate::{IoPriority, OsApiError, PriorityClass};
 
 use super::OS;
-use super::common::{
-    HandleGuard, OsError, from_win_priority, open_process, transform_to_win_priority,
-};
+use super::common::{HandleGuard, OsError, apply_priority, from_win_priority, open_process};
 
 impl OS {
     /// Gets the current CPU affinity mask for a process.
     ///
     /// **Note:** On systems with more than 64 logical CPUs (Processor Groups),
     /// this function only returns the affinity mask for the current processor group.
-    pub fn get_process_affinity(pid: u32) -> Result<usize, String> {
+    pub fn get_process_affinity(pid: u32) -> Result<usize, OsApiError> {
         (|| unsafe {
             let handle = open_process(pid, PROCESS_QUERY_LIMITED_INFORMATION)
                 .or_else(|_| open_process(pid, PROCESS_QUERY_INFORMATION))?;
@@ -32,11 +30,52 @@ impl OS {
             )?;
             Ok(process_mask)
         })()
-        .map_err(|e: OsError| format!("Failed to get affinity mask for process {}: {}", pid, e))
+        .map_err(|e: OsError| {
+            let message = format!("Failed to get affinity mask for process {}: {}", pid, e);
+            e.into_api(message)
+        })
+    }
+
+    /// System affinity mask of the current processor group; disabled or parked-off
+    /// cores are absent. Falls back to all bits set when the mask cannot be read.
+    pub fn available_core_mask() -> usize {
+        let mut process_mask: usize = 0;
+        let mut system_mask: usize = 0;
+        let result = unsafe {
+            GetProcessAffinityMask(
+                GetCurrentProcess(),
+                &mut process_mask as *mut _,
+                &mut system_mask as *mut _,
+            )
+        };
+        match result {
+            Ok(()) if system_mask != 0 => system_mask,
+            _ => usize::MAX,
+        }
+    }
+
+    /// Affinity mask of this process, e.g. as narrowed by the parent that launched it
+    /// or in Task Manager. Falls back to all bits set when the mask cannot be read.
+    ///
+    /// **Note:** Like the other affinity helpers, only the current processor group is covered.
+    pub fn current_process_affinity() -> usize {
+        let mut process_mask: usize = 0;
+        let mut system_mask: usize = 0;
+        let result = unsafe {
+            GetProcessAffinityMask(
+                GetCurrentProcess(),
+                &mut process_mask as *mut _,
+                &mut system_mask as *mut _,
+            )
+        };
+        match result {
+            Ok(()) if process_mask != 0 => process_mask,
+            _ => usize::MAX,
+        }
     }
 
     /// Gets the current priority class for a process.
-    pub fn get_process_priority(pid: u32) -> Result<PriorityClass, String> {
+    pub fn get_process_priority(pid: u32) -> Result<PriorityClass, OsApiError> {
         (|| unsafe {
             let handle = open_process(pid, PROCESS_QUERY_LIMITED_INFORMATION)
                 .or_else(|_| open_process(pid, PROCESS_QUERY_INFORMATION))?;
@@ -49,14 +88,17 @@ impl OS {
 
             Ok(from_win_priority(priority))
         })()
-        .map_err(|e: OsError| format!("Failed to get priority for process {}: {}", pid, e))
+        .map_err(|e: OsError| {
+            let message = format!("Failed to get priority for process {}: {}", pid, e);
+            e.into_api(message)
+        })
     }
 
     /// Sets the CPU affinity mask for a process by PID.
     ///
     /// **Note:** On systems with more than 64 logical CPUs (Processor Groups),
     /// this function only sets the affinity for the current processor group.
-    pub fn set_process_affinity_by_pid(pid: u32, mask: usize) -> Result<(), String> {
+    pub fn set_process_affinity_by_pid(pid: u32, mask: usize) -> Result<(), OsApiError> {
         (|| unsafe {
             let handle = open_process(pid, PROCESS_SET_INFORMATION)?;
             let _hg = HandleGuard(handle);
@@ -64,27 +106,85 @@ impl OS {
             SetProcessAffinityMask(handle, mask)?;
             Ok(())
         })()
-        .map_err(|e: OsError| format!("Failed to set affinity mask for process {}: {}", pid, e))
+        .map_err(|e: OsError| {
+            let message = format!("Failed to set affinity mask for process {}: {}", pid, e);
+            e.into_api(message)
+        })
+    }
+
+    /// Widens a process to every core of the current processor group, undoing an
+    /// affinity set on it.
+    pub fn clear_process_affinity(pid: u32) -> Result<(), OsApiError> {
+        Self::set_process_affinity_by_pid(pid, Self::available_core_mask())
+    }
+
+    /// Puts a process back at the `Normal` priority class.
+    pub fn reset_priority(pid: u32) -> Result<(), OsApiError> {
+        Self::set_process_priority_by_pid(pid, PriorityClass::Normal)
     }
 
     /// Sets the priority class for a process by PID.
-    pub fn set_process_priority_by_pid(pid: u32, priority: PriorityClass) -> Result<(), String> {
+    pub fn set_process_priority_by_pid(
+        pid: u32,
+        priority: PriorityClass,
+    ) -> Result<(), OsApiError> {
+        (|| unsafe {
+            let handle = open_process(pid, PROCESS_SET_INFORMATION)?;
+            let _hg = HandleGuard(handle);
+
+            apply_priority(handle, priority)
+        })()
+        .map_err(|e: OsError| {
+            let message = format!("Failed to set priority for process {}: {}", pid, e);
+            e.into_api(message)
+        })
+    }
+
+    /// Sets the I/O priority of a process through `NtSetInformationProcess`, the same
+    /// knob Task Manager-style tools use; there is no documented Win32 call for it.
+    pub fn set_process_io_priority(pid: u32, priority: IoPriority) -> Result<(), OsApiError> {
+        // IO_PRIORITY_HINT: IoPriorityVeryLow = 0, IoPriorityLow = 1, IoPriorityNormal = 2.
+        let mut hint: u32 = match priority {
+            IoPriority::VeryLow => 0,
+            IoPriority::Low => 1,
+            IoPriority::Normal => 2,
+        };
         (|| unsafe {
             let handle = open_process(pid, PROCESS_SET_INFORMATION)?;
             let _hg = HandleGuard(handle);
 
-            SetPriorityClass(handle, transform_to_win_priority(priority))?;
+            let status = NtSetInformationProcess(
+                handle.0.cast(),
+                ProcessIoPriority,
+                (&mut hint as *mut u32).cast(),
+                size_of::<u32>() as u32,
+            );
+            if status < 0 {
+                return Err(OsError::Msg(format!(
+                    "NtSetInformationProcess failed with status {status:#x}"
+                )));
+            }
             Ok(())
         })()
-        .map_err(|e: OsError| format!("Failed to set priority for process {}: {}", pid, e))
+        .map_err(|e: OsError| {
+            let message = format!("Failed to set I/O priority for process {}: {}", pid, e);
+            e.into_api(message)
+        })
+    }
+
+    /// Sets the CPU affinity mask for the current process and all of its threads.
+    pub fn set_current_process_affinity(mask: usize) -> Result<(), OsApiError> {
+        Self::set_process_affinity_by_pid(std::process::id(), mask)
     }
 
     /// Sets the priority class for the current process.
-    pub fn set_current_process_priority(priority: PriorityClass) -> Result<(), String> {
+    pub fn set_current_process_priority(priority: PriorityClass) -> Result<(), OsApiError> {
         unsafe {
             let handle = GetCurrentProcess();
-            SetPriorityClass(handle, transform_to_win_priority(priority))
-                .map_err(|e| format!("Failed to set current process priority: {}", e))
+            apply_priority(handle, priority).map_err(|e| {
+                let message = format!("Failed to set current process priority: {}", e);
+                e.into_api(message)
+            })
         }
     }
 }
@@ -1,10 +1,11 @@
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::fs;
+use std::mem::size_of;
 use std::os::windows::ffi::OsStrExt;
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
+use std::process::{Command, Stdio};
 use std::ptr::null_mut;
 
 use serde::Deserialize;
@@ -14,23 +15,32 @@ use windows::Win32::System::Com::{
     CLSCTX_INPROC_SERVER, COINIT_APARTMENTTHREADED, CoCreateInstance, CoInitializeEx,
     CoTaskMemFree, IPersistFile, STGM_READ,
 };
-use windows::Win32::System::Threading::CREATE_NO_WINDOW;
+use windows::Win32::System::Threading::{CREATE_NO_WINDOW, GetProcessId};
 use windows::Win32::UI::Shell::{
-    CommandLineToArgvW, FOLDERID_Desktop, IShellLinkW, KF_FLAG_DEFAULT, SHGetKnownFolderPath,
-    SLGP_UNCPRIORITY, SLR_NO_UI, ShellLink,
+    ASSOCF_NONE, ASSOCSTR_EXECUTABLE, AssocQueryStringW, CommandLineToArgvW, FOLDERID_Desktop,
+    IShellLinkW, KF_FLAG_DEFAULT, SEE_MASK_FLAG_NO_UI, SEE_MASK_NOCLOSEPROCESS, SHELLEXECUTEINFOW,
+    SHGetKnownFolderPath, SLGP_FLAGS, SLGP_RAWPATH, SLGP_UNCPRIORITY, SLR_NO_UI, ShellExecuteExW,
+    ShellLink,
 };
+use windows::Win32::UI::WindowsAndMessaging::SW_SHOWNORMAL;
 use windows::core::{Interface, PCWSTR, PWSTR};
 use winreg::RegKey;
 use winreg::enums::{HKEY_CLASSES_ROOT, HKEY_CURRENT_USER, HKEY_LOCAL_MACHINE};
 
 use crate::{
     InstalledAppCatalogEntry, InstalledAppCatalogSource, InstalledAppCatalogTarget,
-    InstalledPackageRuntimeInfo, ShortcutSpec,
+    InstalledPackageRuntimeInfo, OsApiError, ShortcutSpec,
 };
 
 use super::OS;
-use super::common::{ComGuard, OsError, decode_ansi, expand_env, to_wide_z};
-use super::launch::quote_arg_windows;
+use super::common::{
+    ComGuard, HandleGuard, OsError, decode_ansi, expand_env, to_wide_z, to_wide_z_str,
+    win_api_error,
+};
+use super::launch::{quote_arg_windows, with_system_affinity};
+
+/// Extensions `CreateProcessW` starts directly; anything else is a document.
+const DIRECT_LAUNCH_EXTENSIONS: [&str; 4] = ["exe", "com", "bat", "cmd"];
 
 #[derive(Deserialize)]
 struct AppsFolderRecord {
@@ -146,12 +156,20 @@ fn resolve_lnk(path: &Path) -> Result<(PathBuf, Vec<String>), String> {
 
         link.Resolve(HWND(null_mut()), SLR_NO_UI.0 as u32)?;
 
-        let mut wbuf = [0u16; 32768];
-        let mut find = WIN32_FIND_DATAW::default();
-        link.GetPath(&mut wbuf, &mut find as *mut _, SLGP_UNCPRIORITY.0 as u32)?;
-        let n = wbuf.iter().position(|&c| c == 0).unwrap_or(wbuf.len());
-        let target_raw = String::from_utf16_lossy(&wbuf[..n]);
-        let target = normalize_existing_windows_path(&PathBuf::from(expand_env(&target_raw)));
+        let resolved = read_link_path(&link, SLGP_UNCPRIORITY)?;
+        let raw = if resolved.trim().is_empty() {
+            read_link_path(&link, SLGP_RAWPATH)?
+        } else {
+            String::new()
+        };
+        let target_raw = select_lnk_target(&resolved, &raw).ok_or_else(|| {
+            OsError::Msg(
+                "shortcut has no file system target (it may point to a Store app or shell \
+                 folder); add the program's .exe directly instead"
+                    .into(),
+            )
+        })?;
+        let target = normalize_existing_windows_path(&PathBuf::from(expand_env(target_raw)));
 
         let mut abuf = [0u16; 32768];
         link.GetArguments(&mut abuf)?;
@@ -165,6 +183,23 @@ fn resolve_lnk(path: &Path) -> Result<(PathBuf, Vec<String>), String> {
     .map_err(|e: OsError| format!("resolve_lnk {:?} failed: {}", path, e))
 }
 
+fn read_link_path(link: &IShellLinkW, flags: SLGP_FLAGS) -> Result<String, OsError> {
+    let mut wbuf = [0u16; 32768];
+    let mut find = WIN32_FIND_DATAW::default();
+    unsafe { link.GetPath(&mut wbuf, &mut find as *mut _, flags.0 as u32)? };
+    let n = wbuf.iter().position(|&c| c == 0).unwrap_or(wbuf.len());
+    Ok(String::from_utf16_lossy(&wbuf[..n]))
+}
+
+/// Picks the first non-blank target reported by `GetPath`. Shortcuts whose target
+/// only lives in the LinkTargetIDList report empty for both flag variants.
+fn select_lnk_target<'a>(resolved: &'a str, raw: &'a str) -> Option<&'a str> {
+    [resolved, raw]
+        .into_iter()
+        .map(str::trim)
+        .find(|candidate| !candidate.is_empty())
+}
+
 fn strip_windows_verbatim_prefix(path: &Path) -> PathBuf {
     let text = path.to_string_lossy();
 
@@ -739,6 +774,89 @@ impl OS {
         Ok((file_path, Vec::new()))
     }
 
+    /// Whether `path` is a document rather than something `CreateProcessW` can start,
+    /// so it has to be opened through its file association.
+    pub fn needs_shell_open(path: &Path) -> bool {
+        !path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| {
+                DIRECT_LAUNCH_EXTENSIONS
+                    .iter()
+                    .any(|known| ext.eq_ignore_ascii_case(known))
+            })
+    }
+
+    /// Executable registered to open files like `path`, e.g. the PDF reader for a `.pdf`.
+    pub fn association_handler(path: &Path) -> Result<PathBuf, String> {
+        let ext = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .ok_or_else(|| format!("{} has no extension to look up", path.display()))?;
+        let ext_w = to_wide_z_str(&format!(".{ext}"));
+        let no_handler = || format!("No program is associated with .{ext} files");
+        let mut len = 0u32;
+        unsafe {
+            // Without a buffer the call only reports the length, terminator included.
+            let _ = AssocQueryStringW(
+                ASSOCF_NONE,
+                ASSOCSTR_EXECUTABLE,
+                PCWSTR(ext_w.as_ptr()),
+                PCWSTR::null(),
+                None,
+                &mut len,
+            );
+            if len == 0 {
+                return Err(no_handler());
+            }
+            let mut buf = vec![0u16; len as usize];
+            AssocQueryStringW(
+                ASSOCF_NONE,
+                ASSOCSTR_EXECUTABLE,
+                PCWSTR(ext_w.as_ptr()),
+                PCWSTR::null(),
+                Some(PWSTR(buf.as_mut_ptr())),
+                &mut len,
+            )
+            .ok()
+            .map_err(|e| format!("{}: {e}", no_handler()))?;
+            let end = buf.iter().position(|&c| c == 0).unwrap_or(buf.len());
+            Ok(PathBuf::from(String::from_utf16_lossy(&buf[..end])))
+        }
+    }
+
+    /// Opens `path` with its associated program. Returns the handler's PID when the
+    /// shell started a new process for it; an already running handler that took the
+    /// file over, e.g. through DDE, leaves none. A new handler starts on every core,
+    /// not on the tool's own.
+    pub fn shell_open(path: &Path, args: &[String]) -> Result<Option<u32>, OsApiError> {
+        let file_w = to_wide_z(path.as_os_str());
+        let params = args
+            .iter()
+            .map(|arg| quote_arg_windows(arg))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let params_w = to_wide_z_str(&params);
+        let verb_w = to_wide_z_str("open");
+        let mut info = SHELLEXECUTEINFOW {
+            cbSize: size_of::<SHELLEXECUTEINFOW>() as u32,
+            fMask: SEE_MASK_NOCLOSEPROCESS | SEE_MASK_FLAG_NO_UI,
+            lpVerb: PCWSTR(verb_w.as_ptr()),
+            lpFile: PCWSTR(file_w.as_ptr()),
+            lpParameters: PCWSTR(params_w.as_ptr()),
+            nShow: SW_SHOWNORMAL.0,
+            ..Default::default()
+        };
+        with_system_affinity(|| unsafe { ShellExecuteExW(&mut info) })
+            .map_err(|e| win_api_error(&e, format!("Failed to open {}: {e}", path.display())))?;
+        if info.hProcess.is_invalid() {
+            return Ok(None);
+        }
+        let _process = HandleGuard(info.hProcess);
+        let pid = unsafe { GetProcessId(info.hProcess) };
+        Ok((pid != 0).then_some(pid))
+    }
+
     pub fn get_program_path_for_uri(uri_scheme: &str) -> Result<PathBuf, String> {
         get_program_path_for_uri_registry(uri_scheme)
     }
@@ -760,6 +878,39 @@ impl OS {
             .map_err(|e| format!("Failed to open directory '{}': {}", target.display(), e))
     }
 
+    /// Starts `command_line` through `cmd.exe` without a console window and without
+    /// waiting for it, on every core rather than the tool's own. Returns the shell's PID.
+    pub fn spawn_shell_command(command_line: &str) -> Result<u32, String> {
+        // With /S cmd strips only the outer quotes and runs the rest as written.
+        let mut command = Command::new("cmd.exe");
+        command
+            .raw_arg(format!("/D /S /C \"{command_line}\""))
+            .creation_flags(CREATE_NO_WINDOW.0)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null());
+        with_system_affinity(|| command.spawn())
+            .map(|child| child.id())
+            .map_err(|e| format!("Failed to run '{command_line}': {e}"))
+    }
+
+    /// Opens the folder holding `path` in Explorer with the file selected.
+    pub fn reveal_in_explorer(path: &Path) -> Result<(), String> {
+        if !path.exists() {
+            return Err(format!("'{}' does not exist", path.display()));
+        }
+        let target = normalize_existing_windows_path(path);
+        // Explorer only accepts the quoted path after `/select,`, not a quoted whole argument.
+        Command::new("explorer.exe")
+            .raw_arg(format!(
+                "/select,{}",
+                quote_arg_windows(&target.to_string_lossy())
+            ))
+            .spawn()
+            .map(|_| ())
+            .map_err(|e| format!("Failed to reveal '{}': {}", target.display(), e))
+    }
+
     pub fn resolve_installed_package_runtime_info(
         aumid: &str,
     ) -> Result<InstalledPackageRuntimeInfo, String> {
@@ -798,8 +949,8 @@ mod tests {
         classify_start_menu_entry, collect_start_menu_entries_from_dir, dedup_catalog_entries,
         is_ignored_start_menu_entry_name, merge_catalog_sources, normalize_existing_windows_path,
         package_family_name_from_aumid, parse_apps_folder_json,
-        parse_appx_package_runtime_info_json, parse_url_file, strip_windows_verbatim_prefix,
-        target_identity,
+        parse_appx_package_runtime_info_json, parse_url_file, select_lnk_target,
+        strip_windows_verbatim_prefix, target_identity,
     };
     use crate::windows::common::{ComGuard, to_wide_z};
     use crate::{
@@ -1001,6 +1152,19 @@ mod tests {
         drop(target_guard);
     }
 
+    #[test]
+    fn test_select_lnk_target_falls_back_to_raw_path_and_rejects_blank() {
+        assert_eq!(
+            select_lnk_target(r"C:\Apps\game.exe", r"%ProgramFiles%\game.exe"),
+            Some(r"C:\Apps\game.exe")
+        );
+        assert_eq!(
+            select_lnk_target("", r"%ProgramFiles%\game.exe"),
+            Some(r"%ProgramFiles%\game.exe")
+        );
+        assert_eq!(select_lnk_target("  ", ""), None);
+    }
+
     #[test]
     fn test_strip_windows_verbatim_prefix_handles_local_and_unc_forms() {
         assert_eq!(
@@ -0,0 +1,111 @@
+use std::mem::size_of;
+
+use windows::Win32::Foundation::{HLOCAL, LocalFree};
+use windows::Win32::System::Diagnostics::ToolHelp::{
+    CreateToolhelp32Snapshot, TH32CS_SNAPTHREAD, THREADENTRY32, Thread32First, Thread32Next,
+};
+use windows::Win32::System::Threading::{
+    GetCurrentThread, GetThreadDescription, OpenThread, SetThreadAffinityMask,
+    THREAD_QUERY_INFORMATION, THREAD_QUERY_LIMITED_INFORMATION, THREAD_SET_INFORMATION,
+};
+
+use crate::{OsApiError, ThreadInfo};
+
+use super::OS;
+use super::common::{HandleGuard, OsError, open_thread, win_api_error};
+
+fn thread_ids_of(pid: u32) -> Result<Vec<u32>, OsError> {
+    unsafe {
+        let snap = CreateToolhelp32Snapshot(TH32CS_SNAPTHREAD, 0)?;
+        let _hg = HandleGuard(snap);
+
+        let mut te: THREADENTRY32 = std::mem::zeroed();
+        te.dwSize = size_of::<THREADENTRY32>() as u32;
+
+        if Thread32First(snap, &mut te).is_err() {
+            return Err(OsError::Msg("Thread32First failed".into()));
+        }
+
+        let mut tids = Vec::new();
+        loop {
+            if te.th32OwnerProcessID == pid {
+                tids.push(te.th32ThreadID);
+            }
+
+            te.dwSize = size_of::<THREADENTRY32>() as u32;
+            if Thread32Next(snap, &mut te).is_err() {
+                break;
+            }
+        }
+
+        Ok(tids)
+    }
+}
+
+/// Reads the description set with `SetThreadDescription`; empty when missing or unreadable.
+fn thread_description(tid: u32) -> String {
+    unsafe {
+        let Ok(handle) = OpenThread(THREAD_QUERY_LIMITED_INFORMATION, false, tid) else {
+            return String::new();
+        };
+        let _hg = HandleGuard(handle);
+
+        let Ok(description) = GetThreadDescription(handle) else {
+            return String::new();
+        };
+        let name = description.to_string().unwrap_or_default();
+        let _ = LocalFree(Some(HLOCAL(description.0 as *mut core::ffi::c_void)));
+        name
+    }
+}
+
+impl OS {
+    /// Lists the threads of a process in toolhelp snapshot order.
+    pub fn list_threads(pid: u32) -> Result<Vec<ThreadInfo>, OsApiError> {
+        let tids = thread_ids_of(pid).map_err(|e| {
+            let message = format!("Failed to list threads of process {}: {}", pid, e);
+            e.into_api(message)
+        })?;
+        Ok(tids
+            .into_iter()
+            .map(|tid| ThreadInfo {
+                tid,
+                name: thread_description(tid),
+            })
+            .collect())
+    }
+
+    /// Pins the calling thread to `mask`.
+    pub fn set_current_thread_affinity(mask: usize) -> Result<(), OsApiError> {
+        let previous = unsafe { SetThreadAffinityMask(GetCurrentThread(), mask) };
+        if previous == 0 {
+            let err = windows::core::Error::from_thread();
+            let message = format!("Failed to set current thread affinity: {}", err);
+            return Err(win_api_error(&err, message));
+        }
+        Ok(())
+    }
+
+    /// Sets the affinity of one thread of `pid` and returns its previous mask.
+    ///
+    /// The mask must be a subset of the process affinity mask.
+    pub fn set_thread_affinity(pid: u32, tid: u32, mask: usize) -> Result<usize, OsApiError> {
+        (|| unsafe {
+            let handle = open_thread(tid, THREAD_SET_INFORMATION | THREAD_QUERY_INFORMATION)?;
+            let _hg = HandleGuard(handle);
+
+            let previous = SetThreadAffinityMask(handle, mask);
+            if previous == 0 {
+                return Err(OsError::Win(windows::core::Error::from_thread()));
+            }
+            Ok(previous)
+        })()
+        .map_err(|e: OsError| {
+            let message = format!(
+                "Failed to set affinity for thread {} of process {}: {}",
+                tid, pid, e
+            );
+            e.into_api(message)
+        })
+    }
+}
@@ -1,6 +1,7 @@
 use std::ptr::null_mut;
 
 use windows::Win32::Foundation::{HWND, LPARAM, WPARAM};
+use windows::Win32::System::Console::{ATTACH_PARENT_PROCESS, AllocConsole, AttachConsole};
 use windows::Win32::UI::WindowsAndMessaging::{
     ASFW_ANY, AllowSetForegroundWindow, EnumWindows, GWL_EXSTYLE, GetForegroundWindow,
     GetWindowLongW, GetWindowThreadProcessId, IsWindowVisible, PostMessageW, SW_HIDE, SW_RESTORE,
@@ -11,48 +12,59 @@ use windows::core::BOOL;
 
 use super::OS;
 
-impl OS {
-    pub fn focus_window_by_pid(pid: u32) -> bool {
-        #[repr(C)]
-        struct Ctx {
-            target_pid: u32,
-            found: HWND,
+/// First visible top-level window owned by `pid`.
+fn find_visible_window(pid: u32) -> Option<HWND> {
+    #[repr(C)]
+    struct Ctx {
+        target_pid: u32,
+        found: HWND,
+    }
+
+    unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
+        let ctx = unsafe { &mut *(lparam.0 as *mut Ctx) };
+
+        let mut window_pid = 0u32;
+        unsafe {
+            GetWindowThreadProcessId(hwnd, Some(&mut window_pid));
         }
 
-        unsafe extern "system" fn enum_windows_proc(hwnd: HWND, lparam: LPARAM) -> BOOL {
-            let ctx = unsafe { &mut *(lparam.0 as *mut Ctx) };
+        if window_pid == ctx.target_pid && unsafe { IsWindowVisible(hwnd).as_bool() } {
+            ctx.found = hwnd;
+            return BOOL(0);
+        }
 
-            let mut window_pid = 0u32;
-            unsafe {
-                GetWindowThreadProcessId(hwnd, Some(&mut window_pid));
-            }
+        BOOL(1)
+    }
 
-            if window_pid == ctx.target_pid && unsafe { IsWindowVisible(hwnd).as_bool() } {
-                ctx.found = hwnd;
-                return BOOL(0);
-            }
+    unsafe {
+        let mut ctx = Box::new(Ctx {
+            target_pid: pid,
+            found: HWND(null_mut()),
+        });
 
-            BOOL(1)
-        }
+        let ctx_ptr = ctx.as_mut() as *mut Ctx;
+        let _ = EnumWindows(Some(enum_windows_proc), LPARAM(ctx_ptr as isize));
 
-        unsafe {
-            let mut ctx = Box::new(Ctx {
-                target_pid: pid,
-                found: HWND(null_mut()),
-            });
+        (ctx.found.0 != null_mut()).then_some(ctx.found)
+    }
+}
 
-            let ctx_ptr = ctx.as_mut() as *mut Ctx;
-            let _ = EnumWindows(Some(enum_windows_proc), LPARAM(ctx_ptr as isize));
+impl OS {
+    pub fn has_visible_window(pid: u32) -> bool {
+        find_visible_window(pid).is_some()
+    }
 
-            if ctx.found.0 == null_mut() {
-                return false;
-            }
+    pub fn focus_window_by_pid(pid: u32) -> bool {
+        let Some(hwnd) = find_visible_window(pid) else {
+            return false;
+        };
 
+        unsafe {
             let _ = AllowSetForegroundWindow(ASFW_ANY);
-            let _ = ShowWindowAsync(ctx.found, SW_RESTORE);
-            let _ = SetForegroundWindow(ctx.found);
+            let _ = ShowWindowAsync(hwnd, SW_RESTORE);
+            let _ = SetForegroundWindow(hwnd);
 
-            GetForegroundWindow() == ctx.found
+            GetForegroundWindow() == hwnd
         }
     }
 
@@ -103,4 +115,10 @@ impl OS {
             let _ = PostMessageW(None, WM_NULL, WPARAM(0), LPARAM(0));
         }
     }
+
+    /// Gives a GUI-subsystem process a console for its output: the one of the
+    /// terminal that started it, or a new one. Returns whether either worked.
+    pub fn attach_console() -> bool {
+        unsafe { AttachConsole(ATTACH_PARENT_PROCESS).is_ok() || AllocConsole().is_ok() }
+    }
 }
@@ -1,6 +1,7 @@
 use crate::app::models::AppToRun;
 use os_api::{InstalledAppCatalogEntry, InstalledAppCatalogTarget, PriorityClass, OS};
-use std::path::PathBuf;
+use std::fs;
+use std::path::{Path, PathBuf};
 
 pub struct DiscoveredApps {
     pub apps: Vec<AppToRun>,
@@ -16,13 +17,9 @@ pub fn apps_from_dropped_paths(dropped_paths: Vec<PathBuf>) -> DiscoveredApps {
     for path in dropped_paths {
         match OS::parse_dropped_file(path.clone()) {
             Ok((target, args)) => {
-                discovered.apps.push(AppToRun::new_path(
-                    path,
-                    args,
-                    target,
-                    PriorityClass::Normal,
-                    false,
-                ));
+                let mut app = AppToRun::new_path(path, args, target, PriorityClass::Normal, false);
+                detect_shell_open(&mut app);
+                discovered.apps.push(app);
             }
             Err(err) => {
                 discovered.first_error = Some(err);
@@ -34,6 +31,81 @@ pub fn apps_from_dropped_paths(dropped_paths: Vec<PathBuf>) -> DiscoveredApps {
     discovered
 }
 
+/// Marks a dropped document, e.g. a `.pdf`, to be opened by its default program. The
+/// program is not tracked by name: that would capture every instance of it, including
+/// ones this tool never started.
+fn detect_shell_open(app: &mut AppToRun) {
+    if app.bin_path().is_some_and(OS::needs_shell_open) {
+        app.use_shell_execute = true;
+    }
+}
+
+/// Deepest folder level scanned below a dropped folder.
+const FOLDER_SCAN_MAX_DEPTH: usize = 4;
+/// Scanning stops once this many launchable files were collected from folders.
+pub const FOLDER_SCAN_MAX_FILES: usize = 200;
+const FOLDER_SCAN_EXTENSIONS: [&str; 3] = ["exe", "lnk", "url"];
+
+/// Dropped paths with every folder replaced by the launchable files found inside it.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ExpandedDrop {
+    pub paths: Vec<PathBuf>,
+    pub folder_count: usize,
+    /// Files that came from scanning folders
+    pub found_in_folders: usize,
+    /// Whether scanning stopped at `FOLDER_SCAN_MAX_FILES`
+    pub truncated: bool,
+}
+
+pub fn expand_dropped_folders(dropped_paths: Vec<PathBuf>) -> ExpandedDrop {
+    let mut expanded = ExpandedDrop::default();
+    for path in dropped_paths {
+        if path.is_dir() {
+            expanded.folder_count += 1;
+            let mut found = Vec::new();
+            expanded.truncated |= collect_launchable_files(&path, 0, &mut found);
+            expanded.found_in_folders += found.len();
+            expanded.paths.extend(found);
+        } else {
+            expanded.paths.push(path);
+        }
+    }
+    expanded
+}
+
+/// Appends launchable files under `dir` in name order. Returns true when the file cap was hit.
+fn collect_launchable_files(dir: &Path, depth: usize, found: &mut Vec<PathBuf>) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    let mut entries: Vec<PathBuf> = entries.flatten().map(|entry| entry.path()).collect();
+    entries.sort();
+
+    for path in entries {
+        if path.is_dir() {
+            if depth < FOLDER_SCAN_MAX_DEPTH && collect_launchable_files(&path, depth + 1, found) {
+                return true;
+            }
+        } else if is_launchable_file(&path) {
+            if found.len() >= FOLDER_SCAN_MAX_FILES {
+                return true;
+            }
+            found.push(path);
+        }
+    }
+    false
+}
+
+fn is_launchable_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            FOLDER_SCAN_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        })
+}
+
 pub fn app_from_installed_entry(entry: InstalledAppCatalogEntry) -> Result<AppToRun, String> {
     match entry.target {
         InstalledAppCatalogTarget::Aumid(aumid) => Ok(AppToRun::new_installed(
@@ -51,6 +123,11 @@ pub fn app_from_installed_entry(entry: InstalledAppCatalogEntry) -> Result<AppTo
     }
 }
 
+/// Reads a dropped file again, e.g. after a game update moved the shortcut's target.
+pub fn resolve_dropped_file(path: &Path) -> Result<(PathBuf, Vec<String>), String> {
+    OS::parse_dropped_file(path.to_path_buf())
+}
+
 pub fn list_supported_start_apps() -> Result<Vec<InstalledAppCatalogEntry>, String> {
     OS::list_supported_start_apps()
 }
@@ -128,6 +205,23 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_apps_from_dropped_paths_opens_documents_through_their_association() {
+        let root =
+            std::env::temp_dir().join(format!("cpu-affinity-document-drop-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let document = root.join("notes.txt");
+        fs::write(&document, b"").unwrap();
+
+        let discovered = apps_from_dropped_paths(vec![document]);
+        let _ = fs::remove_dir_all(&root);
+
+        assert!(discovered.first_error.is_none());
+        assert!(discovered.apps[0].use_shell_execute);
+        // Only the document's own name; its handler program is not tracked.
+        assert_eq!(discovered.apps[0].additional_processes, vec!["notes.txt"]);
+    }
+
     #[test]
     fn test_catalog_source_contracts_match_picker_expectations() {
         assert_eq!(
@@ -171,4 +265,38 @@ mod tests {
             InstalledAppCatalogTarget::Path(PathBuf::from(r"C:\Tools\Tool.exe"))
         );
     }
+
+    #[test]
+    fn test_expand_dropped_folders_collects_launchable_files_recursively() {
+        let root =
+            std::env::temp_dir().join(format!("cpu-affinity-folder-drop-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("Games").join("Tools")).unwrap();
+        for file in [
+            "Games/Game.exe",
+            "Games/readme.txt",
+            "Games/Tools/Editor.LNK",
+            "Games/Site.url",
+        ] {
+            fs::write(root.join(file), b"").unwrap();
+        }
+        let single = root.join("Single.exe");
+        fs::write(&single, b"").unwrap();
+
+        let expanded = expand_dropped_folders(vec![root.join("Games"), single.clone()]);
+
+        assert_eq!(
+            expanded.paths,
+            vec![
+                root.join("Games").join("Game.exe"),
+                root.join("Games").join("Site.url"),
+                root.join("Games").join("Tools").join("Editor.LNK"),
+                single,
+            ]
+        );
+        assert_eq!(expanded.folder_count, 1);
+        assert_eq!(expanded.found_in_folders, 3);
+        assert!(!expanded.truncated);
+        let _ = fs::remove_dir_all(&root);
+    }
 }
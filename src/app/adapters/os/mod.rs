@@ -1,27 +1,76 @@
-use os_api::{PriorityClass, ShortcutSpec};
+use os_api::{PriorityClass, ProcessInfo, ShortcutSpec};
 use std::ffi::OsString;
 use std::fs;
 use std::io;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 const TEMP_SHORTCUT_ATTEMPTS: usize = 100;
 
 pub fn set_current_process_priority(priority: PriorityClass) -> Result<(), String> {
-    os_api::OS::set_current_process_priority(priority)
+    os_api::OS::set_current_process_priority(priority).map_err(String::from)
 }
 
 pub fn get_cpu_model() -> String {
     os_api::OS::get_cpu_model()
 }
 
+pub fn numa_node_for_core(core: usize) -> Option<u16> {
+    os_api::OS::get_numa_node_for_core(core).ok()
+}
+
+pub fn available_core_mask() -> usize {
+    os_api::OS::available_core_mask()
+}
+
+pub fn current_process_affinity() -> usize {
+    os_api::OS::current_process_affinity()
+}
+
+pub fn list_processes_with_affinity() -> Vec<ProcessInfo> {
+    os_api::OS::list_processes_with_affinity()
+}
+
+pub fn set_process_affinity(pid: u32, mask: usize) -> Result<(), String> {
+    os_api::OS::set_process_affinity_by_pid(pid, mask).map_err(String::from)
+}
+
+pub fn set_process_priority(pid: u32, priority: PriorityClass) -> Result<(), String> {
+    os_api::OS::set_process_priority_by_pid(pid, priority).map_err(String::from)
+}
+
+pub fn set_current_process_affinity(mask: usize) -> Result<(), String> {
+    os_api::OS::set_current_process_affinity(mask).map_err(String::from)
+}
+
+pub fn set_current_thread_affinity(mask: usize) -> Result<(), String> {
+    os_api::OS::set_current_thread_affinity(mask).map_err(String::from)
+}
+
+pub fn sample_per_core_usage(window: Duration) -> Result<Vec<f32>, String> {
+    os_api::OS::sample_per_core_usage(window)
+}
+
 pub fn supports_hide_to_tray() -> bool {
     os_api::OS::supports_hide_to_tray()
 }
 
+pub fn supports_window_lookup() -> bool {
+    os_api::OS::supports_window_lookup()
+}
+
+pub fn supports_job_objects() -> bool {
+    os_api::OS::supports_job_objects()
+}
+
 pub fn open_directory(path: &Path) -> Result<(), String> {
     os_api::OS::open_directory(path)
 }
 
+pub fn reveal_in_explorer(path: &Path) -> Result<(), String> {
+    os_api::OS::reveal_in_explorer(path)
+}
+
 pub fn current_exe_path() -> Result<PathBuf, String> {
     std::env::current_exe().map_err(|err| format!("failed to resolve current executable: {err}"))
 }
@@ -378,3 +427,8 @@ pub fn set_taskbar_visible(hwnd: windows::Win32::Foundation::HWND, visible: bool
 pub fn restore_and_focus_window(hwnd: windows::Win32::Foundation::HWND) {
     os_api::OS::restore_and_focus(hwnd);
 }
+
+#[cfg(target_os = "windows")]
+pub fn attach_console() -> bool {
+    os_api::OS::attach_console()
+}
@@ -0,0 +1,142 @@
+use crate::app::adapters::os;
+use os_api::OS;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Time for every spinner to get scheduled before the cores are sampled.
+const PROBE_WARMUP: Duration = Duration::from_millis(300);
+/// Window over which per-core usage is sampled while the spinners run.
+const PROBE_SAMPLE_WINDOW: Duration = Duration::from_millis(1700);
+/// Busy fraction above which a core counts as having spun.
+const SPIN_THRESHOLD: f32 = 0.5;
+
+/// Which cores actually saw load while busy loops ran pinned to a group's mask.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AffinityProbeReport {
+    pub requested: Vec<usize>,
+    pub spun: Vec<usize>,
+    /// Requested cores that stayed idle.
+    pub idle_requested: Vec<usize>,
+    /// Busy cores outside the mask; other programs can cause these too.
+    pub spun_outside: Vec<usize>,
+}
+
+impl AffinityProbeReport {
+    pub fn from_usage(requested: &[usize], usage: &[f32]) -> Self {
+        let spun: Vec<usize> = usage
+            .iter()
+            .enumerate()
+            .filter(|(_, &busy)| busy >= SPIN_THRESHOLD)
+            .map(|(core, _)| core)
+            .collect();
+        let mut requested = requested.to_vec();
+        requested.sort_unstable();
+        requested.dedup();
+
+        Self {
+            idle_requested: requested
+                .iter()
+                .copied()
+                .filter(|core| !spun.contains(core))
+                .collect(),
+            spun_outside: spun
+                .iter()
+                .copied()
+                .filter(|core| !requested.contains(core))
+                .collect(),
+            requested,
+            spun,
+        }
+    }
+
+    pub fn matches_mask(&self) -> bool {
+        self.idle_requested.is_empty() && self.spun_outside.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!("Cores that spun: {:?}", self.spun);
+        if !self.idle_requested.is_empty() {
+            summary.push_str(&format!("; requested but idle: {:?}", self.idle_requested));
+        }
+        if !self.spun_outside.is_empty() {
+            summary.push_str(&format!("; busy outside the mask: {:?}", self.spun_outside));
+        }
+        summary
+    }
+}
+
+/// Spins one busy loop per requested core, each pinned to the whole mask, and
+/// samples which cores carried the load. Blocks for about two seconds.
+pub fn run_affinity_probe(cores: &[usize]) -> Result<AffinityProbeReport, String> {
+    let mask = OS::mask_from_cores(cores.iter().copied());
+    if mask == 0 {
+        return Err("Select at least one CPU thread to test".into());
+    }
+
+    // Windows keeps a thread within its process mask, so while the tool is pinned
+    // away from these cores it is widened onto them for the length of the test.
+    let own = os::current_process_affinity();
+    let widen = cfg!(target_os = "windows") && mask & !own != 0;
+    if widen {
+        os::set_current_process_affinity(own | mask)
+            .map_err(|err| format!("Could not move the tool onto the cores to test: {err}"))?;
+    }
+    let report = spin_and_sample(cores, mask);
+    if widen {
+        let _ = os::set_current_process_affinity(own);
+    }
+    report
+}
+
+fn spin_and_sample(cores: &[usize], mask: usize) -> Result<AffinityProbeReport, String> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let spinners: Vec<_> = (0..mask.count_ones())
+        .map(|_| {
+            let stop = Arc::clone(&stop);
+            std::thread::spawn(move || -> Result<(), String> {
+                os::set_current_thread_affinity(mask)?;
+                while !stop.load(Ordering::Relaxed) {
+                    std::hint::spin_loop();
+                }
+                Ok(())
+            })
+        })
+        .collect();
+
+    std::thread::sleep(PROBE_WARMUP);
+    let usage = os::sample_per_core_usage(PROBE_SAMPLE_WINDOW);
+    stop.store(true, Ordering::Relaxed);
+
+    for spinner in spinners {
+        spinner
+            .join()
+            .map_err(|_| "Affinity test thread panicked".to_string())??;
+    }
+
+    Ok(AffinityProbeReport::from_usage(cores, &usage?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AffinityProbeReport;
+
+    #[test]
+    fn test_report_flags_idle_requested_and_busy_outside_cores() {
+        let report = AffinityProbeReport::from_usage(&[2, 0], &[0.97, 0.1, 0.2, 0.8]);
+
+        assert_eq!(report.requested, vec![0, 2]);
+        assert_eq!(report.spun, vec![0, 3]);
+        assert_eq!(report.idle_requested, vec![2]);
+        assert_eq!(report.spun_outside, vec![3]);
+        assert!(!report.matches_mask());
+        assert_eq!(
+            report.summary(),
+            "Cores that spun: [0, 3]; requested but idle: [2]; busy outside the mask: [3]"
+        );
+
+        let exact = AffinityProbeReport::from_usage(&[1], &[0.0, 1.0]);
+        assert!(exact.matches_mask());
+        assert_eq!(exact.summary(), "Cores that spun: [1]");
+    }
+}
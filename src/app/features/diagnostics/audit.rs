@@ -0,0 +1,272 @@
+use once_cell::sync::Lazy;
+use os_api::{PriorityClass, OS};
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// File beside the state that affinity and priority changes are appended to.
+pub const AUDIT_FILE_NAME: &str = "affinity-audit.csv";
+const AUDIT_HEADER: &str = "timestamp,pid,exe,action,old_mask,new_mask,priority";
+
+/// Open audit file while the audit is switched on.
+static AUDIT_WRITER: Lazy<Mutex<Option<BufWriter<File>>>> = Lazy::new(|| Mutex::new(None));
+
+/// What made the tool touch a process.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AuditAction {
+    Launch,
+    SetAffinity,
+    SetPriority,
+    MonitorAffinity,
+    MonitorPriority,
+}
+
+impl AuditAction {
+    fn as_str(self) -> &'static str {
+        match self {
+            AuditAction::Launch => "launch",
+            AuditAction::SetAffinity => "set_affinity",
+            AuditAction::SetPriority => "set_priority",
+            AuditAction::MonitorAffinity => "monitor_affinity",
+            AuditAction::MonitorPriority => "monitor_priority",
+        }
+    }
+}
+
+/// One row of the audit file; masks and priority are left empty when the change
+/// didn't involve them or the old value couldn't be read.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AuditRecord {
+    pub pid: u32,
+    pub exe: String,
+    pub action: AuditAction,
+    pub old_mask: Option<usize>,
+    pub new_mask: Option<usize>,
+    pub priority: Option<PriorityClass>,
+}
+
+impl AuditRecord {
+    /// CSV line for the record, timestamped in UTC.
+    pub fn to_csv_row(&self, at: SystemTime) -> String {
+        let mask = |mask: Option<usize>| mask.map(|mask| format!("{mask:#X}")).unwrap_or_default();
+        format!(
+            "{},{},{},{},{},{},{}",
+            utc_timestamp(at),
+            self.pid,
+            csv_field(&self.exe),
+            self.action.as_str(),
+            mask(self.old_mask),
+            mask(self.new_mask),
+            self.priority
+                .map(|priority| format!("{:?}", priority.class_name()))
+                .unwrap_or_default()
+        )
+    }
+}
+
+pub fn audit_path() -> PathBuf {
+    crate::app::models::data_file_path(AUDIT_FILE_NAME)
+}
+
+pub fn is_enabled() -> bool {
+    AUDIT_WRITER
+        .lock()
+        .map(|writer| writer.is_some())
+        .unwrap_or(false)
+}
+
+/// Opens or closes the audit file; does nothing when it is already in that state.
+pub fn set_enabled(enabled: bool) -> Result<(), String> {
+    let mut writer = AUDIT_WRITER.lock().map_err(|err| err.to_string())?;
+    match (enabled, writer.as_mut()) {
+        (true, None) => *writer = Some(open_audit_file(&audit_path())?),
+        (false, Some(open)) => {
+            let _ = open.flush();
+            *writer = None;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Writes out buffered rows so the file can be read while the tool runs.
+pub fn flush() {
+    if let Ok(mut writer) = AUDIT_WRITER.lock() {
+        if let Some(writer) = writer.as_mut() {
+            let _ = writer.flush();
+        }
+    }
+}
+
+pub fn record(record: AuditRecord) {
+    if let Ok(mut writer) = AUDIT_WRITER.lock() {
+        if let Some(writer) = writer.as_mut() {
+            let _ = writeln!(writer, "{}", record.to_csv_row(SystemTime::now()));
+        }
+    }
+}
+
+/// Runs `set` to change the affinity of `pid` and records the change when it worked.
+pub fn audited_affinity<E>(
+    pid: u32,
+    action: AuditAction,
+    mask: usize,
+    set: impl FnOnce() -> Result<(), E>,
+) -> Result<(), E> {
+    if !is_enabled() {
+        return set();
+    }
+    let old_mask = OS::get_process_affinity(pid).ok();
+    set()?;
+    record(AuditRecord {
+        pid,
+        exe: process_exe_name(pid),
+        action,
+        old_mask,
+        new_mask: Some(mask),
+        priority: None,
+    });
+    Ok(())
+}
+
+/// Runs `set` to change the priority of `pid` and records the change when it worked.
+pub fn audited_priority(
+    pid: u32,
+    action: AuditAction,
+    priority: PriorityClass,
+    set: impl FnOnce() -> Result<(), String>,
+) -> Result<(), String> {
+    set()?;
+    if is_enabled() {
+        record(AuditRecord {
+            pid,
+            exe: process_exe_name(pid),
+            action,
+            old_mask: None,
+            new_mask: None,
+            priority: Some(priority),
+        });
+    }
+    Ok(())
+}
+
+/// Records a program the tool started on `cores`.
+pub fn record_launch(pid: u32, bin_path: &Path, cores: &[usize], priority: PriorityClass) {
+    if !is_enabled() {
+        return;
+    }
+    record(AuditRecord {
+        pid,
+        exe: exe_name(bin_path),
+        action: AuditAction::Launch,
+        old_mask: None,
+        new_mask: Some(OS::mask_from_cores(cores.iter().copied())),
+        priority: Some(priority),
+    });
+}
+
+fn open_audit_file(path: &Path) -> Result<BufWriter<File>, String> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|err| format!("failed to open audit file '{}': {err}", path.display()))?;
+    let is_new = file.metadata().map(|meta| meta.len() == 0).unwrap_or(false);
+    let mut writer = BufWriter::new(file);
+    if is_new {
+        writeln!(writer, "{AUDIT_HEADER}")
+            .map_err(|err| format!("failed to write audit file '{}': {err}", path.display()))?;
+    }
+    Ok(writer)
+}
+
+fn process_exe_name(pid: u32) -> String {
+    OS::get_process_image_path(pid)
+        .map(|path| exe_name(&path))
+        .unwrap_or_default()
+}
+
+fn exe_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// `YYYY-MM-DDTHH:MM:SS.mmmZ` for `at`.
+fn utc_timestamp(at: SystemTime) -> String {
+    let since_epoch = at.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days((secs / 86_400) as i64);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}.{:03}Z",
+        (secs % 86_400) / 3600,
+        (secs % 3600) / 60,
+        secs % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// Gregorian date of a day count since 1970-01-01.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * shifted_month + 2) / 5 + 1) as u32;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AuditAction, AuditRecord};
+    use os_api::PriorityClass;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_audit_rows_are_timestamped_csv() {
+        let at = UNIX_EPOCH + Duration::from_millis(1_760_617_845_250);
+        let affinity = AuditRecord {
+            pid: 4321,
+            exe: "game, the sequel.exe".to_string(),
+            action: AuditAction::MonitorAffinity,
+            old_mask: Some(0xFFFF),
+            new_mask: Some(0x55),
+            priority: None,
+        };
+        assert_eq!(
+            affinity.to_csv_row(at),
+            "2025-10-16T12:30:45.250Z,4321,\"game, the sequel.exe\",monitor_affinity,0xFFFF,0x55,"
+        );
+
+        let launch = AuditRecord {
+            exe: "game.exe".to_string(),
+            action: AuditAction::Launch,
+            old_mask: None,
+            priority: Some(PriorityClass::High),
+            ..affinity
+        };
+        assert_eq!(
+            launch.to_csv_row(UNIX_EPOCH + Duration::from_secs(951_782_400)),
+            "2000-02-29T00:00:00.000Z,4321,game.exe,launch,,0x55,High"
+        );
+    }
+}
@@ -0,0 +1,110 @@
+use crate::app::models::cpu_presets::get_preset_for_model;
+use crate::app::models::{effective_cpu_model, effective_total_threads, CpuSchema};
+
+/// Detected CPU and the preset layout it matched, shown in the topology panel.
+#[derive(Clone, Debug)]
+pub struct CpuTopologyReport {
+    pub model: String,
+    pub total_threads: usize,
+    /// `None` when no embedded preset matched the model and thread count.
+    pub schema: Option<CpuSchema>,
+}
+
+impl CpuTopologyReport {
+    pub fn detect() -> Self {
+        let model = effective_cpu_model();
+        let total_threads = effective_total_threads();
+        Self {
+            schema: get_preset_for_model(&model, total_threads),
+            model,
+            total_threads,
+        }
+    }
+
+    /// Logical threads the matched preset does not place in any cluster.
+    pub fn unlisted_threads(&self) -> Vec<usize> {
+        let Some(schema) = &self.schema else {
+            return Vec::new();
+        };
+        let assigned = schema.get_assigned_cores();
+        (0..self.total_threads)
+            .filter(|thread| !assigned.contains(thread))
+            .collect()
+    }
+
+    /// Plain-text version of the panel, for pasting into a preset request.
+    pub fn summary(&self) -> String {
+        let mut lines = vec![
+            format!("CPU: {}", self.model),
+            format!("Logical threads: {}", self.total_threads),
+        ];
+        match &self.schema {
+            Some(schema) => {
+                for cluster in &schema.clusters {
+                    let cores: Vec<String> = cluster
+                        .cores
+                        .iter()
+                        .map(|core| format!("{}={} ({:?})", core.index, core.label, core.core_type))
+                        .collect();
+                    lines.push(format!("{}: {}", cluster.name, cores.join(", ")));
+                }
+                let unlisted = self.unlisted_threads();
+                if !unlisted.is_empty() {
+                    lines.push(format!("Not in preset: {unlisted:?}"));
+                }
+            }
+            None => lines.push("Preset: none matched".to_string()),
+        }
+        lines.join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CpuTopologyReport;
+    use crate::app::models::{CoreInfo, CoreType, CpuCluster, CpuSchema};
+
+    fn core(index: usize, label: &str, core_type: CoreType) -> CoreInfo {
+        CoreInfo {
+            index,
+            core_type,
+            label: label.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_summary_lists_clusters_and_threads_outside_the_preset() {
+        let report = CpuTopologyReport {
+            model: "Test CPU".to_string(),
+            total_threads: 4,
+            schema: Some(CpuSchema {
+                model: "Test CPU".to_string(),
+                clusters: vec![CpuCluster {
+                    name: "P-Cores".to_string(),
+                    cores: vec![
+                        core(0, "P0", CoreType::Performance),
+                        core(1, "P0-HT", CoreType::HyperThreading),
+                    ],
+                }],
+            }),
+        };
+
+        assert_eq!(report.unlisted_threads(), vec![2, 3]);
+        assert_eq!(
+            report.summary(),
+            "CPU: Test CPU\nLogical threads: 4\n\
+             P-Cores: 0=P0 (Performance), 1=P0-HT (HyperThreading)\n\
+             Not in preset: [2, 3]"
+        );
+
+        let unmatched = CpuTopologyReport {
+            schema: None,
+            ..report
+        };
+        assert!(unmatched.unlisted_threads().is_empty());
+        assert_eq!(
+            unmatched.summary(),
+            "CPU: Test CPU\nLogical threads: 4\nPreset: none matched"
+        );
+    }
+}
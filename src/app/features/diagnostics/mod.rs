@@ -1,3 +1,7 @@
+pub mod affinity_probe;
+pub mod audit;
+pub mod cpu_topology;
+
 use crate::app::models::{
     effective_cpu_model, effective_total_threads, AppStateStorage, LogManager,
 };
@@ -14,10 +18,17 @@ pub fn log_startup(log_manager: &mut LogManager, persistent_state: &Arc<RwLock<A
     log_manager.add_sticky_once(format!("Detected CPU: \"{}\" ({} threads)", model, threads));
 
     let presets_info = crate::app::models::cpu_presets::get_all_presets_info();
-    log_manager.add_sticky_once(format!(
-        "Loaded {} CPU presets from embedded JSON",
-        presets_info.len()
-    ));
+    log_manager.add_sticky_once(format!("Loaded {} CPU presets", presets_info.len()));
+    match crate::app::models::cpu_presets::user_presets_status() {
+        Ok(0) => {}
+        Ok(count) => log_manager.add_sticky_once(format!(
+            "Loaded {count} user CPU presets from {}",
+            crate::app::models::cpu_presets::user_presets_path().display()
+        )),
+        Err(err) => {
+            log_manager.add_sticky_once(format!("WARNING: Skipped user CPU presets: {err}"))
+        }
+    }
 
     let storage = persistent_state.read().unwrap();
     if storage.cpu_schema.clusters.is_empty() {
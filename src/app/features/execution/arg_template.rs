@@ -0,0 +1,233 @@
+/// Values a launch substitutes into an app's arguments.
+pub(super) struct ArgPlaceholders<'a> {
+    /// `{group}`: the group's name
+    pub group: &'a str,
+    /// `{cores}`: the launch threads, comma-separated
+    pub cores: &'a [usize],
+    /// `{mask}`: the affinity mask in hex, without a prefix
+    pub mask: usize,
+    /// `{pid}`: the started process, known only once it runs
+    pub pid: Option<u32>,
+}
+
+impl ArgPlaceholders<'_> {
+    fn value(&self, name: &str) -> Option<String> {
+        match name {
+            "group" => Some(self.group.to_string()),
+            "cores" => Some(
+                self.cores
+                    .iter()
+                    .map(usize::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            ),
+            "mask" => Some(format!("{:X}", self.mask)),
+            "pid" => self.pid.map(|pid| pid.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Expands placeholders in launch arguments; `{{` and `}}` stand for literal braces.
+///
+/// Unknown placeholders stay as written and are returned (once each) so the caller
+/// can report them.
+pub(super) fn expand_args(
+    args: &[String],
+    values: &ArgPlaceholders<'_>,
+) -> (Vec<String>, Vec<String>) {
+    let mut unknown = Vec::new();
+    let expanded = args
+        .iter()
+        .map(|arg| expand_arg(arg, values, &mut unknown, |value| value))
+        .collect();
+    (expanded, unknown)
+}
+
+/// Like [`expand_args`] for a command line handed to `sh -c` or `cmd /C`: each filled
+/// in value is quoted for that shell, so e.g. a group named `a & b` stays one word.
+pub(super) fn expand_shell_command(
+    command: &str,
+    values: &ArgPlaceholders<'_>,
+) -> (String, Vec<String>) {
+    let mut unknown = Vec::new();
+    let quote = if cfg!(target_os = "windows") {
+        quote_for_cmd
+    } else {
+        quote_for_sh
+    };
+    let expanded = expand_arg(command, values, &mut unknown, quote);
+    (expanded, unknown)
+}
+
+fn is_shell_safe(value: &str) -> bool {
+    !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || ",._-/:=+@".contains(c))
+}
+
+fn quote_for_sh(value: String) -> String {
+    if is_shell_safe(&value) {
+        return value;
+    }
+    format!("'{}'", value.replace('\'', r"'\''"))
+}
+
+/// Inside double quotes cmd treats `&`, `|`, `<`, `>` and `^` as text; a doubled
+/// quote keeps the quoting intact and reaches the program as one literal quote.
+/// `%` still expands variables there, so it steps out of the quotes as `^%`.
+fn quote_for_cmd(value: String) -> String {
+    if is_shell_safe(&value) {
+        return value;
+    }
+    format!("\"{}\"", value.replace('"', "\"\"").replace('%', "\"^%\""))
+}
+
+fn expand_arg(
+    arg: &str,
+    values: &ArgPlaceholders<'_>,
+    unknown: &mut Vec<String>,
+    quote: fn(String) -> String,
+) -> String {
+    let mut out = String::with_capacity(arg.len());
+    let mut rest = arg;
+    while let Some(pos) = rest.find(['{', '}']) {
+        out.push_str(&rest[..pos]);
+        let brace = &rest[pos..pos + 1];
+        let after = &rest[pos + 1..];
+        if after.starts_with(brace) {
+            out.push_str(brace);
+            rest = &after[1..];
+            continue;
+        }
+        let placeholder = (brace == "{")
+            .then(|| after.find(['{', '}']))
+            .flatten()
+            .filter(|&end| after[end..].starts_with('}'));
+        match placeholder {
+            Some(end) => {
+                let name = &after[..end];
+                match values.value(name) {
+                    Some(value) => out.push_str(&quote(value)),
+                    None => {
+                        let literal = format!("{{{name}}}");
+                        if !unknown.contains(&literal) {
+                            unknown.push(literal.clone());
+                        }
+                        out.push_str(&literal);
+                    }
+                }
+                rest = &after[end + 1..];
+            }
+            None => {
+                out.push_str(brace);
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{expand_args, expand_shell_command, quote_for_cmd, quote_for_sh, ArgPlaceholders};
+
+    fn expand(args: &[&str]) -> (Vec<String>, Vec<String>) {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        expand_args(
+            &args,
+            &ArgPlaceholders {
+                group: "Games",
+                cores: &[2, 3, 10],
+                mask: 0x40c,
+                pid: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_expand_args_substitutes_known_placeholders_and_keeps_unknown_ones() {
+        let (args, unknown) = expand(&[
+            "--title={group}",
+            "--cores={cores}",
+            "/affinity {mask}",
+            "{user}-{user}",
+            "{group",
+            "plain",
+        ]);
+
+        assert_eq!(
+            args,
+            vec![
+                "--title=Games",
+                "--cores=2,3,10",
+                "/affinity 40C",
+                "{user}-{user}",
+                "{group",
+                "plain",
+            ]
+        );
+        assert_eq!(unknown, vec!["{user}"]);
+    }
+
+    #[test]
+    fn test_expand_args_fills_pid_only_once_known() {
+        let (args, unknown) = expand(&["--attach={pid}"]);
+        assert_eq!(args, vec!["--attach={pid}"]);
+        assert_eq!(unknown, vec!["{pid}"]);
+
+        let (args, unknown) = expand_args(
+            &["fancontrol --pid {pid} --mask {mask}".to_string()],
+            &ArgPlaceholders {
+                group: "Games",
+                cores: &[2],
+                mask: 0x4,
+                pid: Some(4242),
+            },
+        );
+        assert_eq!(args, vec!["fancontrol --pid 4242 --mask 4"]);
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn test_expand_args_treats_doubled_braces_as_literal() {
+        let (args, unknown) = expand(&["{{group}}", "{{{group}}}", "a}b", "{{mask}"]);
+
+        assert_eq!(args, vec!["{group}", "{Games}", "a}b", "{mask}"]);
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn test_shell_command_quotes_filled_in_values_for_the_shell() {
+        let (command, unknown) = expand_shell_command(
+            "notify {group} --pid {pid} --cores={cores}",
+            &ArgPlaceholders {
+                group: "it's a & b",
+                cores: &[2, 3],
+                mask: 0xc,
+                pid: Some(4242),
+            },
+        );
+        assert!(unknown.is_empty());
+        let group = if cfg!(target_os = "windows") {
+            r#""it's a & b""#
+        } else {
+            r"'it'\''s a & b'"
+        };
+        assert_eq!(command, format!("notify {group} --pid 4242 --cores=2,3"));
+
+        assert_eq!(quote_for_sh("Games".to_string()), "Games");
+        assert_eq!(quote_for_sh(String::new()), "''");
+        assert_eq!(quote_for_sh("$(rm x)".to_string()), "'$(rm x)'");
+        assert_eq!(
+            quote_for_cmd("say \"hi\" | x".to_string()),
+            r#""say ""hi"" | x""#
+        );
+        assert_eq!(
+            quote_for_cmd("100% %PATH%".to_string()),
+            r#""100"^%" "^%"PATH"^%"""#
+        );
+    }
+}
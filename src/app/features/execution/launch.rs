@@ -1,18 +1,45 @@
+use crate::app::features::diagnostics::audit::{self, AuditAction};
+use crate::app::features::execution::arg_template::{
+    expand_args, expand_shell_command, ArgPlaceholders,
+};
 use crate::app::features::execution::store::{RunningAppPidsLookup, RunningAppSettingsUpdate};
 use crate::app::features::execution::{
-    ensure_package_owner_claim, is_excluded_installed_auto_process, InstalledPackageTrackingState,
-    RuntimeRegistry,
+    ensure_package_owner_claim, is_excluded_installed_auto_process, is_shell_host_process,
+    InstalledPackageTrackingState, RuntimeRegistry,
 };
 use crate::app::features::rules::RulesContext;
-use crate::app::models::{AppRuntimeKey, AppStateStorage, AppToRun, LaunchTarget, LogManager};
+use crate::app::features::topology;
+use crate::app::models::{
+    normalize_process_name, AppRuntimeKey, AppStateStorage, AppToRun, CoreSelector, LaunchTarget,
+    LogManager, ReadyCondition,
+};
 use crate::app::shared::ids::{GroupId, RuleId};
-use os_api::{InstalledPackageRuntimeInfo, PriorityClass, OS};
-use std::collections::{HashMap, HashSet};
+use crate::app::shell::events::ShellEvent;
+use os_api::{
+    InstalledPackageRuntimeInfo, IoPriority, LaunchedProcess, OsApiError, PriorityClass,
+    PriorityClassName, ProcessState, WindowShow, OS,
+};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
+use std::sync::mpsc::Sender;
 use std::sync::{Arc, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock as TokioRwLock;
 
+/// How often a launched program is checked while confirming its startup.
+const STARTUP_CONFIRMATION_POLL: Duration = Duration::from_millis(250);
+
+/// The group a launch runs under.
+struct LaunchGroup {
+    id: GroupId,
+    name: String,
+    cores: Vec<usize>,
+    /// Start the process in a Job Object holding its children to `cores`
+    use_job_object: bool,
+    /// Hard CPU usage cap for the launched process tree, in percent
+    cpu_rate_percent: Option<u8>,
+}
+
 #[derive(Debug, Clone, Default)]
 struct LaunchProcessSnapshot {
     children_of: HashMap<u32, Vec<u32>>,
@@ -42,6 +69,50 @@ struct PostLaunchCorrectionRequest {
     prelaunch_package_pids: HashSet<u32>,
 }
 
+/// Watch on a started program, set from the "Confirm launches" preference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct StartupConfirmation {
+    wait: Duration,
+    require_window: bool,
+}
+
+impl StartupConfirmation {
+    fn from_state(state: &AppStateStorage) -> Option<Self> {
+        (state.launch_confirm_secs > 0).then(|| Self {
+            wait: Duration::from_secs(u64::from(state.launch_confirm_secs)),
+            require_window: state.launch_confirm_window && OS::supports_window_lookup(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum StartupOutcome {
+    Confirmed,
+    /// Still running when the wait ran out, but no window appeared
+    NoWindow,
+    Exited(Option<u32>),
+}
+
+impl StartupOutcome {
+    fn event(self, display: &str, pid: u32, wait: Duration) -> ShellEvent {
+        match self {
+            Self::Confirmed => ShellEvent::Monitor(format!(
+                "Launch confirmed: {display} (PID {pid}) is running"
+            )),
+            Self::NoWindow => ShellEvent::Monitor(format!(
+                "{display} (PID {pid}) is running but showed no window within {}s",
+                wait.as_secs()
+            )),
+            Self::Exited(code) => {
+                let code = code.map_or_else(|| "unknown".to_string(), |code| code.to_string());
+                ShellEvent::Warning(format!(
+                    "WARNING: {display} (PID {pid}) process exited during startup (exit code {code})"
+                ))
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LaunchDispatchOutcome {
     Accepted,
@@ -53,6 +124,11 @@ pub enum AppRowAction {
     Run,
     Fix,
     Focus,
+    Stop,
+    /// Undo the group's affinity and priority and stop tracking, leaving the app running
+    Release,
+    /// Start the app on one CPU cluster instead of its group's cores, for this launch only
+    RunOnCluster(usize),
 }
 
 pub(crate) struct AppRowActionRequest {
@@ -62,17 +138,42 @@ pub(crate) struct AppRowActionRequest {
     pub action: AppRowAction,
 }
 
+/// How a launched program gets onto its group's cores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LaunchAffinity {
+    /// Set on the new process
+    Set,
+    /// Set on the new process, which also runs in a Job Object holding its children
+    Job,
+    /// Inherited from the tool, which is on the cores only while it starts the program
+    Inherited,
+}
+
 trait LaunchOs {
     fn set_process_affinity_by_pid(&self, pid: u32, mask: usize) -> Result<(), String>;
+    fn get_process_affinity(&self, pid: u32) -> Result<usize, String>;
+    fn available_core_mask(&self) -> usize;
     fn set_process_priority_by_pid(&self, pid: u32, priority: PriorityClass) -> Result<(), String>;
+    fn clear_process_affinity(&self, pid: u32) -> Result<(), String>;
+    fn reset_priority(&self, pid: u32) -> Result<(), String>;
     fn focus_window_by_pid(&self, pid: u32) -> bool;
+    fn has_visible_window(&self, pid: u32) -> bool;
+    fn terminate_tree(&self, pid: u32) -> Result<Vec<u32>, String>;
+    fn numa_node_for_core(&self, core: usize) -> Option<u16>;
+    fn set_numa_preference(&self, pid: u32, node: u16) -> Result<(), String>;
+    fn limit_cpu_rate(&self, pid: u32, percent: u8) -> Result<(), String>;
+    fn set_process_io_priority(&self, pid: u32, priority: IoPriority) -> Result<(), String>;
+    fn spawn_shell_command(&self, command_line: &str) -> Result<u32, String>;
+    fn shell_open(&self, path: &Path, args: Vec<String>) -> Result<Option<u32>, String>;
     fn run(
         &self,
         bin_path: PathBuf,
         args: Vec<String>,
         cores: &[usize],
         priority: PriorityClass,
-    ) -> Result<u32, String>;
+        window_show: WindowShow,
+        affinity: LaunchAffinity,
+    ) -> Result<LaunchedProcess, String>;
     fn activate_application(&self, aumid: &str) -> Result<u32, String>;
     fn snapshot_process_tree(&self) -> Result<LaunchProcessSnapshot, String>;
     fn get_process_image_path(&self, pid: u32) -> Result<PathBuf, String>;
@@ -87,29 +188,94 @@ struct RealLaunchOs;
 
 impl LaunchOs for RealLaunchOs {
     fn set_process_affinity_by_pid(&self, pid: u32, mask: usize) -> Result<(), String> {
-        OS::set_process_affinity_by_pid(pid, mask)
+        audit::audited_affinity(pid, AuditAction::SetAffinity, mask, || {
+            OS::set_process_affinity_by_pid(pid, mask).map_err(String::from)
+        })
+    }
+
+    fn get_process_affinity(&self, pid: u32) -> Result<usize, String> {
+        OS::get_process_affinity(pid).map_err(String::from)
+    }
+
+    fn available_core_mask(&self) -> usize {
+        OS::available_core_mask()
     }
 
     fn set_process_priority_by_pid(&self, pid: u32, priority: PriorityClass) -> Result<(), String> {
-        OS::set_process_priority_by_pid(pid, priority)
+        audit::audited_priority(pid, AuditAction::SetPriority, priority, || {
+            OS::set_process_priority_by_pid(pid, priority).map_err(String::from)
+        })
+    }
+
+    fn clear_process_affinity(&self, pid: u32) -> Result<(), String> {
+        OS::clear_process_affinity(pid).map_err(String::from)
+    }
+
+    fn reset_priority(&self, pid: u32) -> Result<(), String> {
+        OS::reset_priority(pid).map_err(String::from)
     }
 
     fn focus_window_by_pid(&self, pid: u32) -> bool {
         OS::focus_window_by_pid(pid)
     }
 
+    fn has_visible_window(&self, pid: u32) -> bool {
+        OS::has_visible_window(pid)
+    }
+
+    fn terminate_tree(&self, pid: u32) -> Result<Vec<u32>, String> {
+        OS::terminate_tree(pid).map_err(String::from)
+    }
+
+    fn numa_node_for_core(&self, core: usize) -> Option<u16> {
+        OS::get_numa_node_for_core(core).ok()
+    }
+
+    fn set_numa_preference(&self, pid: u32, node: u16) -> Result<(), String> {
+        OS::set_numa_preference(pid, node).map_err(String::from)
+    }
+
+    fn limit_cpu_rate(&self, pid: u32, percent: u8) -> Result<(), String> {
+        OS::limit_cpu_rate(pid, percent).map_err(String::from)
+    }
+
+    fn set_process_io_priority(&self, pid: u32, priority: IoPriority) -> Result<(), String> {
+        OS::set_process_io_priority(pid, priority).map_err(String::from)
+    }
+
+    fn spawn_shell_command(&self, command_line: &str) -> Result<u32, String> {
+        OS::spawn_shell_command(command_line)
+    }
+
+    fn shell_open(&self, path: &Path, args: Vec<String>) -> Result<Option<u32>, String> {
+        OS::shell_open(path, &args).map_err(String::from)
+    }
+
     fn run(
         &self,
         bin_path: PathBuf,
         args: Vec<String>,
         cores: &[usize],
         priority: PriorityClass,
-    ) -> Result<u32, String> {
-        OS::run(bin_path, args, cores, priority)
+        window_show: WindowShow,
+        affinity: LaunchAffinity,
+    ) -> Result<LaunchedProcess, String> {
+        let launched = match affinity {
+            LaunchAffinity::Set => OS::run(bin_path.clone(), args, cores, priority, window_show),
+            LaunchAffinity::Job => {
+                OS::run_in_job(bin_path.clone(), args, cores, priority, window_show)
+            }
+            LaunchAffinity::Inherited => {
+                OS::run_inheriting_affinity(bin_path.clone(), args, cores, priority, window_show)
+            }
+        }
+        .map_err(launch_error_text)?;
+        audit::record_launch(launched.pid, &bin_path, cores, priority);
+        Ok(launched)
     }
 
     fn activate_application(&self, aumid: &str) -> Result<u32, String> {
-        OS::activate_application(aumid)
+        OS::activate_application(aumid).map_err(launch_error_text)
     }
 
     fn snapshot_process_tree(&self) -> Result<LaunchProcessSnapshot, String> {
@@ -120,11 +286,11 @@ impl LaunchOs for RealLaunchOs {
     }
 
     fn get_process_image_path(&self, pid: u32) -> Result<PathBuf, String> {
-        OS::get_process_image_path(pid)
+        OS::get_process_image_path(pid).map_err(String::from)
     }
 
     fn get_process_app_user_model_id(&self, pid: u32) -> Result<Option<String>, String> {
-        OS::get_process_app_user_model_id(pid)
+        OS::get_process_app_user_model_id(pid).map_err(String::from)
     }
 
     fn resolve_installed_package_runtime_info(
@@ -135,14 +301,50 @@ impl LaunchOs for RealLaunchOs {
     }
 }
 
+/// A launch error with the suggested fix for it appended, when there is one.
+fn launch_error_text(err: OsApiError) -> String {
+    match err.launch_hint() {
+        Some(hint) => format!("{err} (Fix: {hint})"),
+        None => err.to_string(),
+    }
+}
+
+/// An autorun app waiting for its start time.
+#[derive(Debug, Clone)]
+pub(crate) struct ScheduledAutorun {
+    pub(crate) due: Instant,
+    pub(crate) group_index: usize,
+    pub(crate) program_index: usize,
+    pub(crate) app: AppToRun,
+}
+
+/// A launched app with a launch barrier that the queue waits on.
+#[derive(Debug, Clone)]
+pub(crate) struct BarrierWait {
+    pub(crate) app_key: AppRuntimeKey,
+    pub(crate) display: String,
+    pub(crate) ready_when: ReadyCondition,
+    /// Whether the app has been found running yet; launches without a tracked PID
+    /// are only found once the monitor detects them by name.
+    pub(crate) seen: bool,
+    pub(crate) started: Instant,
+    pub(crate) deadline: Instant,
+}
+
+/// How often a launch barrier's readiness check runs while the queue waits.
+const BARRIER_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Autorun apps in start order: ascending `autorun_order` across all groups, with
+/// ties keeping group order and then list order within the group.
 pub(crate) fn collect_autorun_items(
     persistent_state: &Arc<RwLock<AppStateStorage>>,
 ) -> Vec<(usize, usize, AppToRun)> {
     let state = persistent_state.read().unwrap();
-    state
+    let mut items: Vec<_> = state
         .groups
         .iter()
         .enumerate()
+        .filter(|(_, group)| group.enabled)
         .flat_map(|(g_i, group)| {
             group
                 .programs
@@ -156,26 +358,230 @@ pub(crate) fn collect_autorun_items(
                     }
                 })
         })
+        .collect();
+    items.sort_by_key(|(_, _, app)| app.autorun_order);
+    items
+}
+
+/// Start times for autorun items. An app's delay counts from the start of the one
+/// before it, so a delay also holds back every app ordered after it.
+pub(crate) fn schedule_autorun_items(
+    items: Vec<(usize, usize, AppToRun)>,
+    now: Instant,
+) -> VecDeque<ScheduledAutorun> {
+    let mut due = now;
+    items
+        .into_iter()
+        .map(|(group_index, program_index, app)| {
+            due += Duration::from_millis(app.autorun_delay_ms.unwrap_or(0).into());
+            ScheduledAutorun {
+                due,
+                group_index,
+                program_index,
+                app,
+            }
+        })
         .collect()
 }
 
+/// Queues the autorun apps and starts those without a delay; the rest are started
+/// by [`run_due_autoruns`] so the UI keeps running while they wait.
 pub fn start_app_with_autorun(
     persistent_state: &Arc<RwLock<AppStateStorage>>,
-    runtime: &RuntimeRegistry,
+    runtime: &mut RuntimeRegistry,
     log_manager: &mut LogManager,
 ) {
-    let os = RealLaunchOs;
+    runtime.queue_launches(schedule_autorun_items(
+        collect_autorun_items(persistent_state),
+        Instant::now(),
+    ));
+    run_due_autoruns(persistent_state, runtime, log_manager);
+}
 
-    for (g_i, p_i, app_to_run) in collect_autorun_items(persistent_state) {
-        run_app_with_affinity_sync_with_os(
+/// Queues the Run all launches of a group that has launch barriers, all due now, so
+/// each waits for the barrier of the app before it.
+pub fn queue_group_launches(
+    runtime: &mut RuntimeRegistry,
+    group_index: usize,
+    programs: Vec<(usize, AppToRun)>,
+) {
+    let now = Instant::now();
+    runtime.append_launches(
+        programs
+            .into_iter()
+            .map(|(program_index, app)| ScheduledAutorun {
+                due: now,
+                group_index,
+                program_index,
+                app,
+            })
+            .collect(),
+    );
+}
+
+/// Starts queued autorun apps whose time has come; returns how long until the next
+/// one is due or the pending launch barrier is checked again.
+pub fn run_due_autoruns(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    runtime: &mut RuntimeRegistry,
+    log_manager: &mut LogManager,
+) -> Option<Duration> {
+    run_due_autoruns_with_os(
+        persistent_state,
+        runtime,
+        log_manager,
+        &RealLaunchOs,
+        Instant::now(),
+    )
+}
+
+fn run_due_autoruns_with_os<O: LaunchOs>(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    runtime: &mut RuntimeRegistry,
+    log_manager: &mut LogManager,
+    os: &O,
+    now: Instant,
+) -> Option<Duration> {
+    if let Some(wait) = runtime.launch_barrier.take() {
+        if let Some(wait) = check_launch_barrier(runtime, log_manager, os, wait, now) {
+            let recheck = BARRIER_POLL_INTERVAL.min(wait.deadline - now);
+            runtime.launch_barrier = Some(wait);
+            return Some(recheck);
+        }
+    }
+    while let Some(item) = runtime.pending_autorun.pop_front() {
+        if item.due > now {
+            let wait = item.due - now;
+            runtime.pending_autorun.push_front(item);
+            return Some(wait);
+        }
+        let still_listed = persistent_state
+            .read()
+            .unwrap()
+            .groups
+            .get(item.group_index)
+            .and_then(|group| group.programs.get(item.program_index))
+            .is_some_and(|app| app.runtime_key() == item.app.runtime_key());
+        if !still_listed {
+            log_manager.add_entry(format!(
+                "Autorun skipped for {}: it was moved or removed while waiting",
+                item.app.display()
+            ));
+            runtime.record_queued_launch(item.group_index, false);
+            continue;
+        }
+        let barrier = item.app.launch_barrier.clone();
+        let app_key = item.app.get_key();
+        let display = item.app.display();
+        let outcome = run_app_with_affinity_sync_with_os(
             persistent_state,
             runtime,
             log_manager,
-            g_i,
-            p_i,
-            app_to_run,
-            &os,
+            item.group_index,
+            item.program_index,
+            item.app,
+            os,
         );
+        let rejected = matches!(outcome, LaunchDispatchOutcome::Rejected(_));
+        runtime.record_queued_launch(item.group_index, rejected);
+        if let Some(barrier) = barrier.filter(|_| !rejected && !runtime.pending_autorun.is_empty())
+        {
+            log_manager.add_entry(format!(
+                "Waiting up to {}s for {display} {} before the next launch",
+                barrier.timeout_secs,
+                describe_ready_condition(&barrier.ready_when)
+            ));
+            let seen = matches!(
+                runtime.lookup_running_app_pids(&app_key),
+                RunningAppPidsLookup::Found(_)
+            );
+            runtime.launch_barrier = Some(BarrierWait {
+                app_key,
+                display,
+                ready_when: barrier.ready_when,
+                seen,
+                started: now,
+                deadline: now + Duration::from_secs(barrier.timeout_secs.into()),
+            });
+            return Some(BARRIER_POLL_INTERVAL);
+        }
+    }
+    None
+}
+
+fn describe_ready_condition(ready_when: &ReadyCondition) -> String {
+    match ready_when {
+        ReadyCondition::Window => "to show a window".to_string(),
+        ReadyCondition::Child(name) => format!("to start {name}"),
+    }
+}
+
+/// Gives the wait back while its app is not ready, not yet detected or the monitor is
+/// busy, and time is left. Otherwise the queue moves on, with the launches behind it
+/// pushed back by the time spent waiting so their delays still count from a ready app.
+fn check_launch_barrier<O: LaunchOs>(
+    runtime: &mut RuntimeRegistry,
+    log_manager: &mut LogManager,
+    os: &O,
+    wait: BarrierWait,
+    now: Instant,
+) -> Option<BarrierWait> {
+    let timeout_secs = (wait.deadline - wait.started).as_secs();
+    match runtime.lookup_running_app_pids(&wait.app_key) {
+        RunningAppPidsLookup::Found(pids) if launch_barrier_ready(os, &wait.ready_when, &pids) => {
+            log_manager.add_entry(format!(
+                "{} is ready; continuing with the next launch",
+                wait.display
+            ));
+        }
+        RunningAppPidsLookup::Found(_) if now < wait.deadline => {
+            return Some(BarrierWait { seen: true, ..wait })
+        }
+        RunningAppPidsLookup::Busy if now < wait.deadline => return Some(wait),
+        RunningAppPidsLookup::NotFound if !wait.seen && now < wait.deadline => {
+            return Some(wait)
+        }
+        RunningAppPidsLookup::Found(_) | RunningAppPidsLookup::Busy => {
+            log_manager.add_important_entry(format!(
+                "WARNING: {} was not ready after {timeout_secs}s; continuing with the next launch",
+                wait.display
+            ))
+        }
+        RunningAppPidsLookup::NotFound if !wait.seen => {
+            log_manager.add_important_entry(format!(
+                "WARNING: {} was not detected running within {timeout_secs}s; continuing with the next launch",
+                wait.display
+            ))
+        }
+        RunningAppPidsLookup::NotFound => log_manager.add_important_entry(format!(
+            "WARNING: {} exited before it was ready; continuing with the next launch",
+            wait.display
+        )),
+    }
+    let waited = now.saturating_duration_since(wait.started);
+    for item in runtime.pending_autorun.iter_mut() {
+        item.due += waited;
+    }
+    None
+}
+
+fn launch_barrier_ready<O: LaunchOs>(os: &O, ready_when: &ReadyCondition, pids: &[u32]) -> bool {
+    match ready_when {
+        ReadyCondition::Window => pids.iter().any(|&pid| os.has_visible_window(pid)),
+        ReadyCondition::Child(name) => {
+            let Ok(snapshot) = os.snapshot_process_tree() else {
+                return false;
+            };
+            let wanted = normalize_process_name(name);
+            let mut tree = pids.to_vec();
+            extend_with_descendants(&snapshot, &mut tree);
+            tree.iter().any(|pid| {
+                snapshot
+                    .names
+                    .get(pid)
+                    .is_some_and(|name| normalize_process_name(name) == wanted)
+            })
+        }
     }
 }
 
@@ -226,21 +632,26 @@ fn run_app_row_action_with_os<O: LaunchOs>(
     } = request;
 
     match action {
-        AppRowAction::Run => run_app_with_affinity_sync_with_os(
+        AppRowAction::Run | AppRowAction::RunOnCluster(_) => launch_app_with_os(
             persistent_state,
             runtime,
             log_manager,
-            group_index,
-            program_index,
-            app_to_run,
+            AppRowActionRequest {
+                group_index,
+                program_index,
+                app: app_to_run,
+                action,
+            },
             os,
         ),
         AppRowAction::Focus => focus_existing_app(runtime, log_manager, app_to_run, os),
+        AppRowAction::Stop => stop_running_app(runtime, log_manager, app_to_run, os),
+        AppRowAction::Release => release_running_app(runtime, log_manager, app_to_run, os),
         AppRowAction::Fix => {
             let group_cores = {
                 let state = persistent_state.read().unwrap();
-                match state.groups.get(group_index) {
-                    Some(group) => group.cores.clone(),
+                match state.effective_group_cores(group_index) {
+                    Some(cores) => cores,
                     None => {
                         let message = format!("Error: Group index {group_index} not found");
                         log_manager.add_important_entry(message.clone());
@@ -280,7 +691,7 @@ fn reapply_existing_app_settings<O: LaunchOs>(
             return LaunchDispatchOutcome::Rejected(message);
         }
     };
-    let mask = group_cores.iter().fold(0usize, |acc, &i| acc | (1 << i));
+    let mask = OS::mask_from_cores(group_cores.iter().copied());
     let mut failures = Vec::new();
 
     for &pid in &pids {
@@ -333,7 +744,7 @@ fn focus_existing_app<O: LaunchOs>(
     app_to_run: AppToRun,
     os: &O,
 ) -> LaunchDispatchOutcome {
-    let pids = match runtime.lookup_running_app_pids(&app_to_run.get_key()) {
+    let pids = match runtime.lookup_running_app_pids_for_launch(&app_to_run.get_key()) {
         RunningAppPidsLookup::Found(pids) if !pids.is_empty() => pids,
         RunningAppPidsLookup::Found(_) | RunningAppPidsLookup::NotFound => {
             let message = format!(
@@ -366,6 +777,123 @@ fn focus_existing_app<O: LaunchOs>(
     }
 }
 
+/// PIDs tracked for a row action that needs a running app, or the rejection to return.
+fn tracked_pids_for_action(
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    app_to_run: &AppToRun,
+    verb: &str,
+) -> Result<Vec<u32>, LaunchDispatchOutcome> {
+    let reason = match runtime.lookup_running_app_pids(&app_to_run.get_key()) {
+        RunningAppPidsLookup::Found(pids) if !pids.is_empty() => return Ok(pids),
+        RunningAppPidsLookup::Found(_) | RunningAppPidsLookup::NotFound => "the app is not running",
+        RunningAppPidsLookup::Busy => "running app state is temporarily busy; try again",
+    };
+    let message = format!("Cannot {verb} {}: {reason}", app_to_run.display());
+    log_manager.add_important_entry(message.clone());
+    Err(LaunchDispatchOutcome::Rejected(message))
+}
+
+fn stop_running_app<O: LaunchOs>(
+    runtime: &mut RuntimeRegistry,
+    log_manager: &mut LogManager,
+    app_to_run: AppToRun,
+    os: &O,
+) -> LaunchDispatchOutcome {
+    let app_key = app_to_run.get_key();
+    let pids = match tracked_pids_for_action(runtime, log_manager, &app_to_run, "stop") {
+        Ok(pids) => pids,
+        Err(outcome) => return outcome,
+    };
+
+    let mut terminated = Vec::new();
+    let mut errors = Vec::new();
+    for pid in pids {
+        if terminated.contains(&pid) {
+            continue;
+        }
+        match os.terminate_tree(pid) {
+            Ok(pids) => {
+                for pid in pids {
+                    if !terminated.contains(&pid) {
+                        terminated.push(pid);
+                    }
+                }
+            }
+            Err(err) => errors.push(err),
+        }
+    }
+
+    if terminated.is_empty() && !errors.is_empty() {
+        let message = format!(
+            "Cannot stop {}: {}",
+            app_to_run.display(),
+            errors.join("; ")
+        );
+        log_manager.add_important_entry(message.clone());
+        return LaunchDispatchOutcome::Rejected(message);
+    }
+
+    let untracked = runtime.remove_running_app(&app_key);
+    log_manager.add_entry(format!(
+        "App stopped: {} (terminated PIDs: {:?})",
+        app_to_run.display(),
+        terminated
+    ));
+    if !untracked {
+        // The monitor drops the app once it sees the processes gone.
+        log_manager.add_entry(format!(
+            "WARNING: running app state was busy; {} stays listed until the monitor notices it exited",
+            app_to_run.display()
+        ));
+    }
+    for err in errors {
+        log_manager.add_entry(format!("WARNING: {err}"));
+    }
+    LaunchDispatchOutcome::Accepted
+}
+
+/// Resets the app's processes to the system default affinity and `Normal` priority,
+/// then drops it from tracking so monitoring stops re-applying the group. The app
+/// keeps running; a Job Object or CPU cap it was started in still applies.
+fn release_running_app<O: LaunchOs>(
+    runtime: &mut RuntimeRegistry,
+    log_manager: &mut LogManager,
+    app_to_run: AppToRun,
+    os: &O,
+) -> LaunchDispatchOutcome {
+    let pids = match tracked_pids_for_action(runtime, log_manager, &app_to_run, "release") {
+        Ok(pids) => pids,
+        Err(outcome) => return outcome,
+    };
+
+    // Released first so the monitors cannot re-pin the processes while they are reset.
+    if !runtime.release_running_app(&app_to_run.get_key()) {
+        let message = format!(
+            "Cannot release {}: running app state is temporarily busy; try again",
+            app_to_run.display()
+        );
+        log_manager.add_important_entry(message.clone());
+        return LaunchDispatchOutcome::Rejected(message);
+    }
+
+    let errors: Vec<String> = pids
+        .iter()
+        .flat_map(|&pid| [os.clear_process_affinity(pid), os.reset_priority(pid)])
+        .filter_map(Result::err)
+        .collect();
+
+    log_manager.add_entry(format!(
+        "App released: {} (PIDs: {:?})",
+        app_to_run.display(),
+        pids
+    ));
+    for err in errors {
+        log_manager.add_entry(format!("WARNING: {err}"));
+    }
+    LaunchDispatchOutcome::Accepted
+}
+
 fn run_app_with_affinity_sync_with_os<O: LaunchOs>(
     persistent_state: &Arc<RwLock<AppStateStorage>>,
     runtime: &RuntimeRegistry,
@@ -375,10 +903,52 @@ fn run_app_with_affinity_sync_with_os<O: LaunchOs>(
     app_to_run: AppToRun,
     os: &O,
 ) -> LaunchDispatchOutcome {
-    let group_cores = {
+    launch_app_with_os(
+        persistent_state,
+        runtime,
+        log_manager,
+        AppRowActionRequest {
+            group_index,
+            program_index: prog_index,
+            app: app_to_run,
+            action: AppRowAction::Run,
+        },
+        os,
+    )
+}
+
+/// Starts the app on its group's cores, or on one CPU cluster for `RunOnCluster`.
+fn launch_app_with_os<O: LaunchOs>(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    request: AppRowActionRequest,
+    os: &O,
+) -> LaunchDispatchOutcome {
+    let AppRowActionRequest {
+        group_index,
+        program_index: prog_index,
+        app: app_to_run,
+        action,
+    } = request;
+    let (
+        group_cores,
+        (group_name, enabled, single_numa_node, core_selector, use_job_object, cpu_rate_percent),
+    ) = {
         let state = persistent_state.read().unwrap();
-        match state.groups.get(group_index) {
-            Some(group) => group.cores.clone(),
+        match state
+            .effective_group_cores(group_index)
+            .zip(state.groups.get(group_index).map(|group| {
+                (
+                    group.name.clone(),
+                    group.enabled,
+                    group.single_numa_node,
+                    group.core_selector,
+                    group.use_job_object,
+                    group.cpu_rate_percent,
+                )
+            })) {
+            Some(group_settings) => group_settings,
             None => {
                 let message = format!("Error: Group index {group_index} not found");
                 log_manager.add_important_sticky_once(message.clone());
@@ -387,6 +957,15 @@ fn run_app_with_affinity_sync_with_os<O: LaunchOs>(
         }
     };
 
+    if !enabled {
+        let message = format!(
+            "Launch skipped for {}: group '{group_name}' is disabled",
+            app_to_run.display()
+        );
+        log_manager.add_important_entry(message.clone());
+        return LaunchDispatchOutcome::Rejected(message);
+    }
+
     let (group_id, rule_id) = {
         let state = persistent_state.read().unwrap();
         let rules = RulesContext::from_storage(&state);
@@ -405,36 +984,221 @@ fn run_app_with_affinity_sync_with_os<O: LaunchOs>(
         (group_id, rule_id)
     };
 
-    run_launch_decision(
+    let pinned_cores = match (action, core_selector) {
+        (AppRowAction::RunOnCluster(cluster), _) => {
+            cluster_launch_cores(persistent_state, log_manager, &app_to_run, cluster)
+        }
+        (_, CoreSelector::Fixed) => None,
+        (_, CoreSelector::LeastLoaded { cluster, count }) => {
+            pick_least_loaded_cores(persistent_state, runtime, log_manager, cluster, count, os)
+        }
+    };
+    let cpu_schema = persistent_state.read().unwrap().cpu_schema.clone();
+    if app_to_run.smt_off && cpu_schema.clusters.is_empty() {
+        log_manager.add_important_entry(format!(
+            "WARNING: no CPU preset is known for SMT off on {}; assuming odd threads are hyper-thread siblings",
+            app_to_run.display()
+        ));
+    }
+    let group_cores = topology::app_cores(
+        &pinned_cores.clone().unwrap_or(group_cores),
+        &app_to_run,
+        &cpu_schema,
+    );
+    // Monitoring re-derives the shaped mask from the group; least-loaded and cluster
+    // picks can't be.
+    let pinned_cores = pinned_cores.map(|_| group_cores.clone());
+
+    warn_if_group_cores_unavailable(log_manager, &group_name, &group_cores, os);
+    let reserved = persistent_state
+        .read()
+        .unwrap()
+        .reserved_cores_in(&group_cores);
+    if !reserved.is_empty() {
+        log_manager.add_important_entry(format!(
+            "WARNING: group \"{group_name}\" uses CPU threads {reserved:?} that are reserved for the OS"
+        ));
+    }
+
+    let app_key = app_to_run.get_key();
+    let display = app_to_run.display();
+    let numa_cores = single_numa_node.then(|| group_cores.clone());
+    let startup = StartupConfirmation::from_state(&persistent_state.read().unwrap());
+    let outcome = run_launch_decision(
         runtime,
         log_manager,
-        group_id,
+        LaunchGroup {
+            id: group_id,
+            name: group_name,
+            cores: group_cores,
+            use_job_object,
+            cpu_rate_percent,
+        },
         rule_id,
         app_to_run,
-        group_cores,
+        startup,
         os,
-    )
+    );
+
+    if let (LaunchDispatchOutcome::Accepted, Some(cores)) = (&outcome, pinned_cores) {
+        if runtime.set_running_app_cores(&app_key, cores) == RunningAppSettingsUpdate::Busy {
+            log_manager.add_important_entry(format!(
+                "WARNING: could not record the threads picked for {display}; monitoring may move it back to the group's cores"
+            ));
+        }
+    }
+    if let (LaunchDispatchOutcome::Accepted, Some(cores)) = (&outcome, numa_cores) {
+        apply_numa_preference(runtime, log_manager, &app_key, &cores, os);
+    }
+
+    outcome
 }
 
-fn run_launch_decision<O: LaunchOs>(
+/// All threads of CPU cluster `cluster`, for a one-off launch outside the group's cores.
+///
+/// Returns `None` (use the group's own cores) when the cluster no longer exists.
+fn cluster_launch_cores(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    log_manager: &mut LogManager,
+    app_to_run: &AppToRun,
+    cluster: usize,
+) -> Option<Vec<usize>> {
+    let state = persistent_state.read().unwrap();
+    let Some(found) = state.cpu_schema.clusters.get(cluster) else {
+        log_manager.add_important_entry(format!(
+            "WARNING: CPU cluster #{cluster} no longer exists; launching on the group's own threads"
+        ));
+        return None;
+    };
+    log_manager.add_entry(format!(
+        "Running {} on cluster '{}' for this launch",
+        app_to_run.display(),
+        found.name
+    ));
+    Some(found.cores.iter().map(|core| core.index).collect())
+}
+
+/// Whether a shell-open handler PID is a shell host such as `explorer.exe`, which must
+/// not be given the group's settings.
+fn is_shell_host_pid<O: LaunchOs>(os: &O, pid: u32) -> bool {
+    os.get_process_image_path(pid)
+        .is_ok_and(|path| is_shell_host_process(&path.to_string_lossy()))
+}
+
+/// Picks the `count` idlest available, non-reserved threads of a CPU cluster.
+///
+/// Uses the usage the running-app monitor last sampled and falls back to the first
+/// `count` threads until it has one; returns `None` (use the group's own cores) when
+/// the cluster no longer exists.
+fn pick_least_loaded_cores<O: LaunchOs>(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
     runtime: &RuntimeRegistry,
     log_manager: &mut LogManager,
-    group_id: GroupId,
-    rule_id: RuleId,
-    app_to_run: AppToRun,
-    group_cores: Vec<usize>,
+    cluster: usize,
+    count: usize,
     os: &O,
-) -> LaunchDispatchOutcome {
-    let app_key = app_to_run.get_key();
-    let mask = group_cores.iter().fold(0usize, |acc, &i| acc | (1 << i));
+) -> Option<Vec<usize>> {
+    let (cluster_name, cluster_cores, reserved) = {
+        let state = persistent_state.read().unwrap();
+        let Some(found) = state.cpu_schema.clusters.get(cluster) else {
+            log_manager.add_important_entry(format!(
+                "WARNING: CPU cluster #{cluster} no longer exists; launching on the group's own threads"
+            ));
+            return None;
+        };
+        let cores: Vec<usize> = found.cores.iter().map(|core| core.index).collect();
+        let reserved = state.reserved_cores_in(&cores);
+        (found.name.clone(), cores, reserved)
+    };
 
-    match runtime.lookup_running_app_pids(&app_key) {
-        RunningAppPidsLookup::Found(pids) => {
-            for &pid in &pids {
-                let _ = os.set_process_affinity_by_pid(pid, mask);
-                let _ = os.set_process_priority_by_pid(pid, app_to_run.priority);
-            }
+    let available = os.available_core_mask();
+    let usable: Vec<usize> = cluster_cores
+        .iter()
+        .copied()
+        .filter(|core| OS::cores_outside_mask(&[*core], available).is_empty())
+        .filter(|core| !reserved.contains(core))
+        .collect();
+    let candidates = if usable.is_empty() {
+        &cluster_cores
+    } else {
+        &usable
+    };
 
+    let usage = runtime.core_usage();
+    let picked = topology::least_loaded_cores(candidates, count, usage.as_deref());
+    match usage {
+        Some(_) => log_manager.add_entry(format!(
+            "Picked least-loaded threads {picked:?} of cluster \"{cluster_name}\""
+        )),
+        None => log_manager.add_entry(format!(
+            "CPU usage not sampled yet; using threads {picked:?} of cluster \"{cluster_name}\""
+        )),
+    }
+    Some(picked)
+}
+
+fn apply_numa_preference<O: LaunchOs>(
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    app_key: &AppRuntimeKey,
+    group_cores: &[usize],
+    os: &O,
+) {
+    let Some(node) = topology::preferred_numa_node(group_cores, |core| os.numa_node_for_core(core))
+    else {
+        log_manager.add_entry("NUMA preference skipped: node topology unavailable".into());
+        return;
+    };
+    let RunningAppPidsLookup::Found(pids) = runtime.lookup_running_app_pids(app_key) else {
+        return;
+    };
+
+    for pid in pids {
+        if let Err(error) = os.set_numa_preference(pid, node) {
+            log_manager.add_entry(format!("NUMA preference not applied: {error}"));
+        }
+    }
+}
+
+fn run_launch_decision<O: LaunchOs>(
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    group: LaunchGroup,
+    rule_id: RuleId,
+    app_to_run: AppToRun,
+    startup: Option<StartupConfirmation>,
+    os: &O,
+) -> LaunchDispatchOutcome {
+    let LaunchGroup {
+        id: group_id,
+        name: group_name,
+        cores: group_cores,
+        use_job_object,
+        cpu_rate_percent,
+    } = group;
+    let app_key = app_to_run.get_key();
+    let mask = OS::mask_from_cores(group_cores.iter().copied());
+
+    match runtime.lookup_running_app_pids_for_launch(&app_key) {
+        RunningAppPidsLookup::Found(pids) => {
+            for &pid in &pids {
+                // Inheriting apps were started on their cores and are never moved from outside.
+                if !app_to_run.inherit_affinity {
+                    let _ = os.set_process_affinity_by_pid(pid, mask);
+                }
+                let _ = os.set_process_priority_by_pid(pid, app_to_run.priority);
+                let _ = os.set_process_io_priority(pid, app_to_run.io_priority);
+            }
+
+            if !app_to_run.focus_on_relaunch {
+                log_manager.add_entry(format!(
+                    "App already running: {}, settings reapplied",
+                    app_to_run.display()
+                ));
+                return LaunchDispatchOutcome::Accepted;
+            }
+
+            // Stops at the first PID whose window could be raised.
             let was_focused = pids.iter().any(|&pid| os.focus_window_by_pid(pid));
             if was_focused {
                 log_manager.add_entry(format!(
@@ -490,29 +1254,113 @@ fn run_launch_decision<O: LaunchOs>(
         };
 
     log_manager.add_entry(format!("Starting '{}', app: {}", label, display));
+    let affinity = if app_to_run.inherit_affinity {
+        LaunchAffinity::Inherited
+    } else if use_job_object {
+        LaunchAffinity::Job
+    } else {
+        LaunchAffinity::Set
+    };
 
+    let shell_opened = app_to_run.use_shell_execute
+        && matches!(app_to_run.launch_target, LaunchTarget::Path { .. });
     let launch_result = match &app_to_run.launch_target {
-        LaunchTarget::Path { bin_path, .. } => os.run(
-            bin_path.clone(),
-            app_to_run.args.clone(),
-            &group_cores,
-            priority,
-        ),
+        LaunchTarget::Path { bin_path, .. } if shell_opened => {
+            match os.shell_open(
+                bin_path,
+                launch_args(log_manager, &app_to_run, &group_name, &group_cores, mask),
+            ) {
+                Ok(Some(pid)) if !is_shell_host_pid(os, pid) => Ok(pid),
+                Ok(_) => {
+                    runtime.set_launch_failure(&app_key, None);
+                    log_manager.add_entry(format!(
+                        "Opened {display} with its default program; no process of its own was reported, so add the program under Tracked Process Names to give it the group's threads"
+                    ));
+                    return LaunchDispatchOutcome::Accepted;
+                }
+                Err(err) => Err(err),
+            }
+        }
+        LaunchTarget::Path { bin_path, .. } => os
+            .run(
+                bin_path.clone(),
+                launch_args(log_manager, &app_to_run, &group_name, &group_cores, mask),
+                &group_cores,
+                priority,
+                app_to_run.window_show,
+                affinity,
+            )
+            .map(|launched| {
+                if launched.settings_attempts > 1 {
+                    log_manager.add_entry(format!(
+                        "Affinity and priority for PID {} took {} attempts",
+                        launched.pid, launched.settings_attempts
+                    ));
+                }
+                if launched.in_job {
+                    log_manager.add_entry(format!(
+                        "PID {} runs in a Job Object; its child processes stay on the group's threads",
+                        launched.pid
+                    ));
+                } else if affinity == LaunchAffinity::Inherited {
+                    log_manager.add_entry(format!(
+                        "PID {} inherited its affinity from the tool; it was not set from outside",
+                        launched.pid
+                    ));
+                } else if use_job_object {
+                    log_manager.add_important_entry(format!(
+                        "WARNING: {display} could not be put in a Job Object; child processes are corrected by monitoring instead"
+                    ));
+                }
+                launched.pid
+            }),
         LaunchTarget::Installed { aumid } => os.activate_application(aumid),
     };
 
+    runtime.set_launch_failure(&app_key, launch_result.as_ref().err().cloned());
+
     match launch_result {
         Ok(pid) => {
             let is_installed = matches!(app_to_run.launch_target, LaunchTarget::Installed { .. });
             let launch_pid_auto_managed =
                 !is_installed || installed_launch_pid_auto_managed(os, pid);
 
-            if is_installed && launch_pid_auto_managed {
+            // Neither started with the group's affinity, so it is set once they run.
+            if (is_installed || shell_opened) && launch_pid_auto_managed {
                 let _ = os.set_process_affinity_by_pid(pid, mask);
                 let _ = os.set_process_priority_by_pid(pid, priority);
             }
 
             if launch_pid_auto_managed {
+                warn_if_affinity_narrowed(log_manager, &display, pid, mask, os);
+                if app_to_run.io_priority != IoPriority::Normal {
+                    if let Err(err) = os.set_process_io_priority(pid, app_to_run.io_priority) {
+                        log_manager.add_important_entry(format!(
+                            "WARNING: I/O priority {:?} not applied to {display}: {err}",
+                            app_to_run.io_priority
+                        ));
+                    }
+                }
+                if let Some(percent) = cpu_rate_percent {
+                    match os.limit_cpu_rate(pid, percent) {
+                        Ok(()) => log_manager
+                            .add_entry(format!("CPU usage of PID {pid} capped at {percent}%")),
+                        Err(err) => log_manager.add_important_entry(format!(
+                            "WARNING: CPU limit of {percent}% not applied to {display}: {err}"
+                        )),
+                    }
+                }
+                run_post_launch_command(
+                    log_manager,
+                    &app_to_run,
+                    &ArgPlaceholders {
+                        group: &group_name,
+                        cores: &group_cores,
+                        mask,
+                        pid: Some(pid),
+                    },
+                    os,
+                );
                 record_started_pid(
                     runtime,
                     log_manager,
@@ -527,6 +1375,10 @@ fn run_launch_decision<O: LaunchOs>(
                 ));
             }
 
+            if let (false, Some(startup)) = (is_installed || shell_opened, startup) {
+                spawn_startup_confirmation(pid, display, startup, runtime.launch_event_sender());
+            }
+
             if let LaunchTarget::Installed { aumid } = &app_to_run.launch_target {
                 spawn_post_launch_correction(PostLaunchCorrectionRequest {
                     running_apps: runtime.running_apps_handle(),
@@ -551,6 +1403,287 @@ fn run_launch_decision<O: LaunchOs>(
     }
 }
 
+/// Starts the app's post-launch command, if any, without waiting for it. Failures
+/// are only logged: the app itself is already running.
+fn run_post_launch_command<O: LaunchOs>(
+    log_manager: &mut LogManager,
+    app_to_run: &AppToRun,
+    placeholders: &ArgPlaceholders<'_>,
+    os: &O,
+) {
+    let Some(command) = app_to_run
+        .post_launch_cmd
+        .as_deref()
+        .map(str::trim)
+        .filter(|command| !command.is_empty())
+    else {
+        return;
+    };
+    let (command, unknown) = expand_shell_command(command, placeholders);
+    for placeholder in unknown {
+        log_manager.add_sticky_once(format!(
+            "WARNING: unknown placeholder {placeholder} in the post-launch command of {}; passed as written",
+            app_to_run.display()
+        ));
+    }
+    match os.spawn_shell_command(&command) {
+        Ok(pid) => log_manager.add_entry(format!(
+            "Post-launch command for {} started (PID {pid}): {command}",
+            app_to_run.display()
+        )),
+        Err(err) => log_manager.add_important_entry(format!(
+            "WARNING: post-launch command for {} failed: {err}",
+            app_to_run.display()
+        )),
+    }
+}
+
+/// The app's arguments with `{group}`, `{cores}` and `{mask}` filled in.
+fn launch_args(
+    log_manager: &mut LogManager,
+    app_to_run: &AppToRun,
+    group_name: &str,
+    group_cores: &[usize],
+    mask: usize,
+) -> Vec<String> {
+    let (args, unknown) = expand_args(
+        &app_to_run.args,
+        &ArgPlaceholders {
+            group: group_name,
+            cores: group_cores,
+            mask,
+            pid: None,
+        },
+    );
+    for placeholder in unknown {
+        log_manager.add_sticky_once(format!(
+            "WARNING: unknown placeholder {placeholder} in arguments of {}; passed as written",
+            app_to_run.display()
+        ));
+    }
+    args
+}
+
+/// Cores disabled in firmware or taken offline since the group was saved are
+/// dropped or rejected by the OS, so say which ones before launching.
+fn warn_if_group_cores_unavailable<O: LaunchOs>(
+    log_manager: &mut LogManager,
+    group_name: &str,
+    group_cores: &[usize],
+    os: &O,
+) {
+    let unavailable = OS::cores_outside_mask(group_cores, os.available_core_mask());
+    if !unavailable.is_empty() {
+        log_manager.add_important_entry(format!(
+            "WARNING: group \"{group_name}\" uses CPU threads {unavailable:?} that are disabled, parked or offline; edit the group to pick available threads"
+        ));
+    }
+}
+
+/// Reads the mask back after launch; the OS can silently drop threads that are
+/// disabled or belong to another processor group.
+fn warn_if_affinity_narrowed<O: LaunchOs>(
+    log_manager: &mut LogManager,
+    display: &str,
+    pid: u32,
+    requested: usize,
+    os: &O,
+) {
+    let Ok(effective) = os.get_process_affinity(pid) else {
+        return;
+    };
+    if OS::effective_affinity_differs(requested, effective) {
+        log_manager.add_important_entry(format!(
+            "WARNING: {display} requested CPU mask {requested:#x} but runs with {effective:#x}; some selected threads may be disabled or outside this processor group"
+        ));
+    }
+}
+
+/// Applies the group's threads and each app's priority to matching processes that are
+/// already running, e.g. started outside the tool, and tracks them like launched ones.
+/// Returns how many processes were adjusted.
+pub fn apply_group_to_running(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    group_index: usize,
+) -> usize {
+    apply_group_to_running_with_os(
+        persistent_state,
+        runtime,
+        log_manager,
+        group_index,
+        &RealLaunchOs,
+    )
+}
+
+fn apply_group_to_running_with_os<O: LaunchOs>(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    group_index: usize,
+    os: &O,
+) -> usize {
+    let (group_name, cores, schema, programs, group_id, rule_ids) = {
+        let state = persistent_state.read().unwrap();
+        let rules = RulesContext::from_storage(&state);
+        let (Some(group), Some(cores), Some(group_id)) = (
+            state.groups.get(group_index),
+            state.effective_group_cores(group_index),
+            rules.group_id_for_index(group_index),
+        ) else {
+            log_manager.add_entry(format!("Group with index {group_index} not found"));
+            return 0;
+        };
+        let rule_ids: Vec<_> = (0..group.programs.len())
+            .map(|program_index| rules.rule_id_for_index(group_index, program_index))
+            .collect();
+        (
+            group.name.clone(),
+            cores,
+            state.cpu_schema.clone(),
+            group.programs.clone(),
+            group_id,
+            rule_ids,
+        )
+    };
+
+    // Only taken when some app has no tracked processes to reuse.
+    let mut snapshot = None;
+    let own_pid = std::process::id();
+    let mut adjusted = 0;
+
+    for (app, rule_id) in programs.iter().zip(rule_ids) {
+        let Some(rule_id) = rule_id else {
+            continue;
+        };
+        let app_key = app.get_key();
+        let pids = match runtime.lookup_running_app_pids(&app_key) {
+            RunningAppPidsLookup::Found(pids) if !pids.is_empty() => pids,
+            _ => {
+                let snapshot = match &mut snapshot {
+                    Some(snapshot) => snapshot,
+                    slot @ None => match os.snapshot_process_tree() {
+                        Ok(taken) => slot.insert(taken),
+                        Err(err) => {
+                            log_manager.add_important_entry(format!(
+                                "Couldn't apply group '{group_name}' to running apps: {err}"
+                            ));
+                            return adjusted;
+                        }
+                    },
+                };
+                find_running_pids(app, snapshot, os)
+            }
+        };
+        let mask = topology::app_mask(&cores, app, &schema);
+        for pid in pids {
+            if pid == own_pid {
+                continue;
+            }
+            let applied = os
+                .set_process_affinity_by_pid(pid, mask)
+                .and_then(|()| os.set_process_priority_by_pid(pid, app.priority));
+            if let Err(err) = applied {
+                log_manager.add_entry(format!(
+                    "WARNING: Couldn't apply group '{group_name}' to {} (PID {pid}): {err}",
+                    app.display()
+                ));
+                continue;
+            }
+            if !runtime.add_pid_to_existing_app(&app_key, pid) {
+                runtime.add_running_app(&app_key, pid, group_id.clone(), rule_id.clone());
+            }
+            adjusted += 1;
+        }
+    }
+
+    log_manager.add_entry(format!(
+        "Applied group '{group_name}' to {adjusted} running process(es)"
+    ));
+    adjusted
+}
+
+/// Gives the tracked processes of `programs` the priority `class` right away, each
+/// with its app's eco mode, e.g. after the priority of a whole group changed.
+/// Returns how many processes took it.
+pub fn apply_priority_to_running(
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    programs: &[AppToRun],
+    class: PriorityClassName,
+) -> usize {
+    apply_priority_to_running_with_os(runtime, log_manager, programs, class, &RealLaunchOs)
+}
+
+fn apply_priority_to_running_with_os<O: LaunchOs>(
+    runtime: &RuntimeRegistry,
+    log_manager: &mut LogManager,
+    programs: &[AppToRun],
+    class: PriorityClassName,
+    os: &O,
+) -> usize {
+    let mut adjusted = 0;
+    for app in programs {
+        let priority = PriorityClass::from_parts(class, app.priority.eco_qos());
+        let RunningAppPidsLookup::Found(pids) = runtime.lookup_running_app_pids(&app.get_key())
+        else {
+            continue;
+        };
+        for pid in pids {
+            match os.set_process_priority_by_pid(pid, priority) {
+                Ok(()) => adjusted += 1,
+                Err(err) => log_manager.add_important_entry(format!(
+                    "Failed to set priority of {} (PID {pid}): {err}",
+                    app.display()
+                )),
+            }
+        }
+    }
+    adjusted
+}
+
+/// Running processes of `app`: by executable name, extra tracked names or name
+/// pattern for a path target, and by app model ID for an installed app.
+fn find_running_pids<O: LaunchOs>(
+    app: &AppToRun,
+    snapshot: &LaunchProcessSnapshot,
+    os: &O,
+) -> Vec<u32> {
+    let names: Vec<String> = app
+        .primary_process_name_normalized()
+        .into_iter()
+        .chain(
+            app.additional_processes
+                .iter()
+                .map(|name| normalize_process_name(name)),
+        )
+        .filter(|name| !name.is_empty())
+        .collect();
+    let pattern = app.match_pattern_regex();
+    let aumid = app.installed_aumid().map(str::to_lowercase);
+
+    let mut pids: Vec<u32> = snapshot
+        .names
+        .iter()
+        .filter(|(&pid, name)| {
+            names.contains(&normalize_process_name(name))
+                || pattern
+                    .as_ref()
+                    .is_some_and(|pattern| pattern.is_match(name))
+                || aumid.as_ref().is_some_and(|aumid| {
+                    os.get_process_app_user_model_id(pid)
+                        .ok()
+                        .flatten()
+                        .is_some_and(|id| id.to_lowercase() == *aumid)
+                })
+        })
+        .map(|(&pid, _)| pid)
+        .collect();
+    pids.sort_unstable();
+    pids
+}
+
 fn record_started_pid(
     runtime: &RuntimeRegistry,
     log_manager: &mut LogManager,
@@ -578,6 +1711,58 @@ fn record_started_pid(
     }
 }
 
+/// Decides a startup watch from one check; `None` keeps waiting.
+fn startup_check(
+    state: ProcessState,
+    has_window: bool,
+    require_window: bool,
+    timed_out: bool,
+) -> Option<StartupOutcome> {
+    match state {
+        ProcessState::Exited(code) => Some(StartupOutcome::Exited(code)),
+        ProcessState::Running if require_window && has_window => Some(StartupOutcome::Confirmed),
+        ProcessState::Running if timed_out && require_window => Some(StartupOutcome::NoWindow),
+        ProcessState::Running if timed_out => Some(StartupOutcome::Confirmed),
+        ProcessState::Running => None,
+    }
+}
+
+/// Watches a started program in the background and reports whether it survived
+/// startup. The handle is opened right away so a quick crash keeps its exit code.
+fn spawn_startup_confirmation(
+    pid: u32,
+    display: String,
+    startup: StartupConfirmation,
+    events: Sender<ShellEvent>,
+) {
+    let Ok(handle) = tokio::runtime::Handle::try_current() else {
+        return;
+    };
+    let watch = match OS::watch_process(pid) {
+        Ok(watch) => watch,
+        Err(err) if err.is_process_not_found() => {
+            let _ = events.send(StartupOutcome::Exited(None).event(&display, pid, startup.wait));
+            return;
+        }
+        Err(_) => return,
+    };
+
+    handle.spawn(async move {
+        let started = Instant::now();
+        loop {
+            let has_window = startup.require_window && OS::has_visible_window(pid);
+            let timed_out = started.elapsed() >= startup.wait;
+            if let Some(outcome) =
+                startup_check(watch.state(), has_window, startup.require_window, timed_out)
+            {
+                let _ = events.send(outcome.event(&display, pid, startup.wait));
+                break;
+            }
+            tokio::time::sleep(STARTUP_CONFIRMATION_POLL).await;
+        }
+    });
+}
+
 fn spawn_post_launch_correction(request: PostLaunchCorrectionRequest) {
     let Ok(handle) = tokio::runtime::Handle::try_current() else {
         return;
@@ -652,10 +1837,9 @@ fn spawn_post_launch_correction(request: PostLaunchCorrectionRequest) {
                                         if !app.pids.contains(&pid) {
                                             app.pids.push(pid);
                                             attached_no_identity_pids.push(pid);
-                                            let mask = request
-                                                .group_cores
-                                                .iter()
-                                                .fold(0usize, |acc, &i| acc | (1 << i));
+                                            let mask = OS::mask_from_cores(
+                                                request.group_cores.iter().copied(),
+                                            );
                                             let _ = os.set_process_affinity_by_pid(pid, mask);
                                             let _ = os
                                                 .set_process_priority_by_pid(pid, request.priority);
@@ -719,7 +1903,7 @@ fn post_launch_correction_poll_with_os<O: LaunchOs>(
         retain_auto_managed_installed_pids(&snapshot, &mut no_identity_package_pids);
     }
 
-    let mask = group_cores.iter().fold(0usize, |acc, &i| acc | (1 << i));
+    let mask = OS::mask_from_cores(group_cores.iter().copied());
     let mut saw_identity_seed = false;
     let mut managed_pids = seed_pids.clone();
     retain_auto_managed_installed_pids(&snapshot, &mut managed_pids);
@@ -865,30 +2049,55 @@ fn path_is_under_root_case_insensitive(path: &Path, install_root: &Path) -> bool
 #[cfg(test)]
 mod tests {
     use super::{
-        collect_autorun_items, post_launch_correction_poll_with_os, record_started_pid,
-        run_app_row_action_with_os, run_app_with_affinity_sync_with_os, run_launch_decision,
-        AppRowAction, AppRowActionRequest, LaunchOs, LaunchProcessSnapshot,
+        apply_group_to_running_with_os, collect_autorun_items, post_launch_correction_poll_with_os,
+        record_started_pid, run_app_row_action_with_os, run_app_with_affinity_sync_with_os,
+        run_due_autoruns_with_os, run_launch_decision, schedule_autorun_items, startup_check,
+        AppRowAction, AppRowActionRequest, LaunchAffinity, LaunchGroup, LaunchOs,
+        LaunchProcessSnapshot, StartupOutcome, BARRIER_POLL_INTERVAL,
     };
-    use crate::app::features::execution::RuntimeRegistry;
+    use crate::app::features::execution::{LaunchProgress, RuntimeRegistry};
     use crate::app::models::{
-        AppStateStorage, AppStatus, AppToRun, CoreGroup, CpuSchema, LogManager,
+        AppStateStorage, AppStatus, AppToRun, CoreGroup, CoreInfo, CoreSelector, CoreType,
+        CpuCluster, CpuSchema, LaunchBarrier, LogManager, ReadyCondition, SelfAffinity,
     };
     use crate::app::shared::ids::{GroupId, RuleId};
-    use os_api::{InstalledPackageRuntimeInfo, PriorityClass};
+    use crate::app::shell::events::ShellEvent;
+    use os_api::{
+        InstalledPackageRuntimeInfo, IoPriority, LaunchedProcess, OsApiError, PriorityClass,
+        PriorityClassName, ProcessState, WindowShow,
+    };
     use std::cell::RefCell;
-    use std::collections::HashMap;
-    use std::path::PathBuf;
+    use std::collections::{HashMap, HashSet};
+    use std::path::{Path, PathBuf};
     use std::sync::{Arc, RwLock};
+    use std::time::{Duration, Instant};
 
     struct FakeLaunchOs {
         affinity_calls: RefCell<Vec<(u32, usize)>>,
         affinity_results: HashMap<u32, Result<(), String>>,
+        effective_affinities: HashMap<u32, usize>,
+        available_core_mask: usize,
         priority_calls: RefCell<Vec<(u32, PriorityClass)>>,
         priority_results: HashMap<u32, Result<(), String>>,
         focus_calls: RefCell<Vec<u32>>,
         focus_results: HashMap<u32, bool>,
+        windowed_pids: RefCell<HashSet<u32>>,
+        terminate_calls: RefCell<Vec<u32>>,
+        terminate_results: HashMap<u32, Result<Vec<u32>, String>>,
+        numa_nodes: HashMap<usize, u16>,
+        numa_calls: RefCell<Vec<(u32, u16)>>,
+        cpu_rate_calls: RefCell<Vec<(u32, u8)>>,
+        shell_calls: RefCell<Vec<String>>,
+        shell_open_calls: RefCell<Vec<PathBuf>>,
+        shell_open_result: RefCell<Result<Option<u32>, String>>,
+        cpu_rate_result: Result<(), String>,
+        io_priority_calls: RefCell<Vec<(u32, IoPriority)>>,
         run_calls: RefCell<Vec<(PathBuf, Vec<String>, Vec<usize>, PriorityClass)>>,
+        window_show_calls: RefCell<Vec<WindowShow>>,
+        launch_affinity_calls: RefCell<Vec<LaunchAffinity>>,
+        run_in_job_result: bool,
         run_result: RefCell<Result<u32, String>>,
+        run_settings_attempts: u32,
         activate_calls: RefCell<Vec<String>>,
         activate_result: RefCell<Result<u32, String>>,
         snapshot_result: RefCell<Result<LaunchProcessSnapshot, String>>,
@@ -902,12 +2111,29 @@ mod tests {
             Self {
                 affinity_calls: RefCell::new(Vec::new()),
                 affinity_results: HashMap::new(),
+                effective_affinities: HashMap::new(),
+                available_core_mask: usize::MAX,
                 priority_calls: RefCell::new(Vec::new()),
                 priority_results: HashMap::new(),
                 focus_calls: RefCell::new(Vec::new()),
                 focus_results: HashMap::new(),
+                windowed_pids: RefCell::new(HashSet::new()),
+                terminate_calls: RefCell::new(Vec::new()),
+                terminate_results: HashMap::new(),
+                numa_nodes: HashMap::new(),
+                numa_calls: RefCell::new(Vec::new()),
+                cpu_rate_calls: RefCell::new(Vec::new()),
+                shell_calls: RefCell::new(Vec::new()),
+                shell_open_calls: RefCell::new(Vec::new()),
+                shell_open_result: RefCell::new(Ok(None)),
+                cpu_rate_result: Ok(()),
+                io_priority_calls: RefCell::new(Vec::new()),
                 run_calls: RefCell::new(Vec::new()),
+                window_show_calls: RefCell::new(Vec::new()),
+                launch_affinity_calls: RefCell::new(Vec::new()),
+                run_in_job_result: true,
                 run_result: RefCell::new(Ok(0)),
+                run_settings_attempts: 1,
                 activate_calls: RefCell::new(Vec::new()),
                 activate_result: RefCell::new(Ok(0)),
                 snapshot_result: RefCell::new(Ok(LaunchProcessSnapshot::default())),
@@ -924,6 +2150,17 @@ mod tests {
             self.affinity_results.get(&pid).cloned().unwrap_or(Ok(()))
         }
 
+        fn get_process_affinity(&self, pid: u32) -> Result<usize, String> {
+            self.effective_affinities
+                .get(&pid)
+                .copied()
+                .ok_or_else(|| format!("missing affinity for pid {pid}"))
+        }
+
+        fn available_core_mask(&self) -> usize {
+            self.available_core_mask
+        }
+
         fn set_process_priority_by_pid(
             &self,
             pid: u32,
@@ -933,22 +2170,79 @@ mod tests {
             self.priority_results.get(&pid).cloned().unwrap_or(Ok(()))
         }
 
+        fn clear_process_affinity(&self, pid: u32) -> Result<(), String> {
+            self.set_process_affinity_by_pid(pid, self.available_core_mask)
+        }
+
+        fn reset_priority(&self, pid: u32) -> Result<(), String> {
+            self.set_process_priority_by_pid(pid, PriorityClass::Normal)
+        }
+
         fn focus_window_by_pid(&self, pid: u32) -> bool {
             self.focus_calls.borrow_mut().push(pid);
             self.focus_results.get(&pid).copied().unwrap_or(false)
         }
 
+        fn has_visible_window(&self, pid: u32) -> bool {
+            self.windowed_pids.borrow().contains(&pid)
+        }
+
+        fn terminate_tree(&self, pid: u32) -> Result<Vec<u32>, String> {
+            self.terminate_calls.borrow_mut().push(pid);
+            self.terminate_results
+                .get(&pid)
+                .cloned()
+                .unwrap_or_else(|| Ok(vec![pid]))
+        }
+
+        fn numa_node_for_core(&self, core: usize) -> Option<u16> {
+            self.numa_nodes.get(&core).copied()
+        }
+
+        fn set_numa_preference(&self, pid: u32, node: u16) -> Result<(), String> {
+            self.numa_calls.borrow_mut().push((pid, node));
+            Ok(())
+        }
+
+        fn limit_cpu_rate(&self, pid: u32, percent: u8) -> Result<(), String> {
+            self.cpu_rate_calls.borrow_mut().push((pid, percent));
+            self.cpu_rate_result.clone()
+        }
+
+        fn set_process_io_priority(&self, pid: u32, priority: IoPriority) -> Result<(), String> {
+            self.io_priority_calls.borrow_mut().push((pid, priority));
+            Ok(())
+        }
+
+        fn spawn_shell_command(&self, command_line: &str) -> Result<u32, String> {
+            self.shell_calls.borrow_mut().push(command_line.to_string());
+            Ok(7)
+        }
+
+        fn shell_open(&self, path: &Path, _args: Vec<String>) -> Result<Option<u32>, String> {
+            self.shell_open_calls.borrow_mut().push(path.to_path_buf());
+            self.shell_open_result.borrow().clone()
+        }
+
         fn run(
             &self,
             bin_path: PathBuf,
             args: Vec<String>,
             cores: &[usize],
             priority: PriorityClass,
-        ) -> Result<u32, String> {
+            window_show: WindowShow,
+            affinity: LaunchAffinity,
+        ) -> Result<LaunchedProcess, String> {
             self.run_calls
                 .borrow_mut()
                 .push((bin_path, args, cores.to_vec(), priority));
-            self.run_result.borrow().clone()
+            self.window_show_calls.borrow_mut().push(window_show);
+            self.launch_affinity_calls.borrow_mut().push(affinity);
+            self.run_result.borrow().clone().map(|pid| LaunchedProcess {
+                pid,
+                settings_attempts: self.run_settings_attempts,
+                in_job: affinity == LaunchAffinity::Job && self.run_in_job_result,
+            })
         }
 
         fn activate_application(&self, aumid: &str) -> Result<u32, String> {
@@ -1002,7 +2296,16 @@ mod tests {
                     ),
                 ],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".to_string(),
@@ -1010,7 +2313,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }))
@@ -1030,6 +2349,16 @@ mod tests {
         GroupId(format!("group-{value}"))
     }
 
+    fn launch_group(id: usize, cores: Vec<usize>) -> LaunchGroup {
+        LaunchGroup {
+            id: group_id(id),
+            name: "Games".to_string(),
+            cores,
+            use_job_object: false,
+            cpu_rate_percent: None,
+        }
+    }
+
     fn rule_id(value: usize) -> RuleId {
         RuleId(format!("rule-{value}"))
     }
@@ -1058,6 +2387,7 @@ mod tests {
         assert!(os.affinity_calls.borrow().is_empty());
         assert!(os.priority_calls.borrow().is_empty());
         assert!(os.focus_calls.borrow().is_empty());
+        assert!(os.terminate_calls.borrow().is_empty());
         assert!(os.run_calls.borrow().is_empty());
         assert!(os.activate_calls.borrow().is_empty());
     }
@@ -1076,26 +2406,245 @@ mod tests {
     }
 
     #[test]
-    fn test_already_running_with_focus_reapplies_settings_without_launch() {
-        let runtime = RuntimeRegistry::new();
-        let app = sample_app();
-        let app_key = app.get_key();
-        assert!(runtime.add_running_app(&app_key, 41, group_id(3), rule_id(4)));
-        assert!(runtime.add_pid_to_existing_app(&app_key, 42));
+    fn test_collect_autorun_items_sorts_by_order_across_groups() {
+        let state = sample_state();
+        {
+            let mut storage = state.write().unwrap();
+            let mut second = storage.groups[0].clone();
+            second.name = "Tools".to_string();
+            storage.groups.push(second);
+            let first = &mut storage.groups[0].programs;
+            first[0].autorun = true;
+            first[0].autorun_order = 2;
+            first[1].autorun_order = 1;
+            storage.groups[1].programs[1].autorun_order = 1;
+        }
+
+        let order: Vec<_> = collect_autorun_items(&state)
+            .iter()
+            .map(|(g_i, p_i, _)| (*g_i, *p_i))
+            .collect();
+
+        assert_eq!(order, vec![(0, 1), (1, 1), (0, 0)]);
+    }
+
+    #[test]
+    fn test_delayed_autorun_waits_without_blocking() {
+        let state = sample_state();
+        state.write().unwrap().groups[0].programs[1].autorun_delay_ms = Some(1_000);
+        let mut runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+        let now = Instant::now();
+        runtime.queue_launches(schedule_autorun_items(collect_autorun_items(&state), now));
+        assert_eq!(
+            runtime.launch_progress(0),
+            Some(LaunchProgress {
+                started: 0,
+                total: 1
+            })
+        );
+
+        let wait = run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, now);
+
+        assert_eq!(wait, Some(Duration::from_millis(1_000)));
+        assert!(os.run_calls.borrow().is_empty());
+
+        let later = now + Duration::from_millis(1_000);
+        let wait = run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, later);
+
+        assert_eq!(wait, None);
+        assert_eq!(os.run_calls.borrow().len(), 1);
+        assert!(runtime.pending_autorun.is_empty());
+        assert_eq!(runtime.launch_progress(0), None);
+    }
+
+    #[test]
+    fn test_launch_barrier_holds_the_queue_until_ready_or_timed_out() {
+        let state = sample_state();
+        {
+            let mut storage = state.write().unwrap();
+            let launcher = &mut storage.groups[0].programs[0];
+            launcher.autorun = true;
+            launcher.launch_barrier = Some(LaunchBarrier {
+                ready_when: ReadyCondition::Window,
+                timeout_secs: 30,
+            });
+            storage.groups[0].programs[1].autorun_delay_ms = Some(1_000);
+        }
+        let mut runtime = RuntimeRegistry::new();
         let mut log_manager = LogManager::default();
         let os = FakeLaunchOs {
-            focus_results: HashMap::from([(41, false), (42, true)]),
-            run_result: RefCell::new(Ok(999)),
+            run_result: RefCell::new(Ok(4242)),
             ..Default::default()
         };
+        let now = Instant::now();
+        runtime.queue_launches(schedule_autorun_items(collect_autorun_items(&state), now));
 
-        run_launch_decision(
-            &runtime,
+        let wait = run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, now);
+        assert_eq!(wait, Some(BARRIER_POLL_INTERVAL));
+        assert_eq!(os.run_calls.borrow().len(), 1);
+
+        // The delay would have passed, but the launcher has no window yet.
+        let later = now + Duration::from_secs(5);
+        run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, later);
+        assert_eq!(os.run_calls.borrow().len(), 1);
+
+        os.windowed_pids.borrow_mut().insert(4242);
+        let wait = run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, later);
+        assert_eq!(wait, Some(Duration::from_millis(1_000)));
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("is ready")));
+        run_due_autoruns_with_os(
+            &state,
+            &mut runtime,
             &mut log_manager,
-            group_id(3),
-            rule_id(4),
-            app,
-            vec![0, 2],
+            &os,
+            later + Duration::from_millis(1_000),
+        );
+        assert_eq!(os.run_calls.borrow().len(), 2);
+
+        state.write().unwrap().groups[0].programs[0].launch_barrier = Some(LaunchBarrier {
+            ready_when: ReadyCondition::Child("game.exe".to_string()),
+            timeout_secs: 30,
+        });
+        state.write().unwrap().groups[0].programs[1].autorun_delay_ms = None;
+        let mut runtime = RuntimeRegistry::new();
+        runtime.queue_launches(schedule_autorun_items(collect_autorun_items(&state), now));
+        run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, now);
+        run_due_autoruns_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            &os,
+            now + Duration::from_secs(30),
+        );
+        assert_eq!(os.run_calls.borrow().len(), 4);
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.starts_with("WARNING: ")
+                && entry.message.contains("was not ready after 30s")));
+    }
+
+    #[test]
+    fn test_launch_barrier_waits_for_an_untracked_launch_to_be_detected() {
+        let state = sample_state();
+        {
+            let mut storage = state.write().unwrap();
+            let launcher = &mut storage.groups[0].programs[0];
+            launcher.autorun = true;
+            launcher.use_shell_execute = true;
+            launcher.launch_barrier = Some(LaunchBarrier {
+                ready_when: ReadyCondition::Window,
+                timeout_secs: 30,
+            });
+        }
+        let app_key = state.read().unwrap().groups[0].programs[0].get_key();
+        let mut runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+        let now = Instant::now();
+        runtime.queue_launches(schedule_autorun_items(collect_autorun_items(&state), now));
+
+        run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, now);
+        assert_eq!(os.shell_open_calls.borrow().len(), 1);
+        let later = now + Duration::from_secs(5);
+        let wait = run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, later);
+        assert_eq!(wait, Some(BARRIER_POLL_INTERVAL));
+        assert!(os.run_calls.borrow().is_empty());
+
+        assert!(runtime.add_running_app(&app_key, 77, group_id(0), rule_id(0)));
+        os.windowed_pids.borrow_mut().insert(77);
+        run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, later);
+        assert_eq!(os.run_calls.borrow().len(), 1);
+
+        let mut runtime = RuntimeRegistry::new();
+        runtime.queue_launches(schedule_autorun_items(collect_autorun_items(&state), now));
+        run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, now);
+        run_due_autoruns_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            &os,
+            now + Duration::from_secs(30),
+        );
+        assert_eq!(os.run_calls.borrow().len(), 2);
+        assert!(log_manager.entries.iter().any(|entry| entry
+            .message
+            .contains("was not detected running within 30s")));
+    }
+
+    #[test]
+    fn test_delayed_autorun_skips_app_removed_while_waiting() {
+        let state = sample_state();
+        state.write().unwrap().groups[0].programs[1].autorun_delay_ms = Some(500);
+        let mut runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+        let now = Instant::now();
+        runtime.pending_autorun = schedule_autorun_items(collect_autorun_items(&state), now);
+        state.write().unwrap().groups[0].programs.remove(0);
+
+        let later = now + Duration::from_millis(500);
+        run_due_autoruns_with_os(&state, &mut runtime, &mut log_manager, &os, later);
+
+        assert!(os.run_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .back()
+            .unwrap()
+            .message
+            .starts_with("Autorun skipped"));
+    }
+
+    #[test]
+    fn test_disabled_group_skips_autorun_and_refuses_launches() {
+        let state = sample_state();
+        state.write().unwrap().groups[0].enabled = false;
+        assert!(collect_autorun_items(&state).is_empty());
+
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+        let app = state.read().unwrap().groups[0].programs[0].clone();
+
+        let outcome =
+            run_app_with_affinity_sync_with_os(&state, &runtime, &mut log_manager, 0, 0, app, &os);
+
+        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
+        assert!(os.run_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .back()
+            .unwrap()
+            .message
+            .contains("is disabled"));
+    }
+
+    #[test]
+    fn test_already_running_with_focus_reapplies_settings_without_launch() {
+        let runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 41, group_id(3), rule_id(4)));
+        assert!(runtime.add_pid_to_existing_app(&app_key, 42));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            focus_results: HashMap::from([(41, false), (42, true)]),
+            run_result: RefCell::new(Ok(999)),
+            ..Default::default()
+        };
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(3, vec![0, 2]),
+            rule_id(4),
+            app,
+            None,
             &os,
         );
 
@@ -1111,6 +2660,65 @@ mod tests {
             .any(|entry| entry.message.contains("window focused")));
     }
 
+    #[test]
+    fn test_already_running_focus_stops_at_first_focused_pid() {
+        let runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 41, group_id(3), rule_id(4)));
+        assert!(runtime.add_pid_to_existing_app(&app_key, 42));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            focus_results: HashMap::from([(41, true), (42, true)]),
+            ..Default::default()
+        };
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(3, vec![0, 2]),
+            rule_id(4),
+            app,
+            None,
+            &os,
+        );
+
+        assert_eq!(os.focus_calls.borrow().as_slice(), &[41]);
+    }
+
+    #[test]
+    fn test_already_running_without_focus_on_relaunch_skips_window_search() {
+        let runtime = RuntimeRegistry::new();
+        let mut app = sample_app();
+        app.focus_on_relaunch = false;
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 41, group_id(3), rule_id(4)));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            focus_results: HashMap::from([(41, true)]),
+            ..Default::default()
+        };
+
+        let outcome = run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(3, vec![0, 2]),
+            rule_id(4),
+            app,
+            None,
+            &os,
+        );
+
+        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
+        assert!(os.run_calls.borrow().is_empty());
+        assert!(os.focus_calls.borrow().is_empty());
+        assert_eq!(os.affinity_calls.borrow().as_slice(), &[(41, 5)]);
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.ends_with("settings reapplied")));
+    }
+
     #[test]
     fn test_already_running_without_focus_does_not_launch_duplicate() {
         let runtime = RuntimeRegistry::new();
@@ -1126,10 +2734,10 @@ mod tests {
         run_launch_decision(
             &runtime,
             &mut log_manager,
-            group_id(0),
+            launch_group(0, vec![1, 3]),
             rule_id(0),
             app,
-            vec![1, 3],
+            None,
             &os,
         );
 
@@ -1175,6 +2783,47 @@ mod tests {
         assert_eq!(runtime.get_app_status_sync(&app_key), AppStatus::Running);
     }
 
+    #[test]
+    fn test_apply_priority_to_running_sets_tracked_pids_only() {
+        let runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 41, group_id(0), rule_id(0)));
+        assert!(runtime.add_pid_to_existing_app(&app_key, 42));
+        let stopped = AppToRun::new_path(
+            PathBuf::from(r"C:\tool.exe"),
+            Vec::new(),
+            PathBuf::from(r"C:\tool.exe"),
+            PriorityClass::Normal,
+            false,
+        );
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            priority_results: HashMap::from([(42, Err("access denied".to_string()))]),
+            ..Default::default()
+        };
+
+        let adjusted = super::apply_priority_to_running_with_os(
+            &runtime,
+            &mut log_manager,
+            &[app, stopped],
+            PriorityClassName::AboveNormal,
+            &os,
+        );
+
+        assert_eq!(adjusted, 1);
+        assert_eq!(
+            os.priority_calls.borrow().as_slice(),
+            &[
+                (41, PriorityClass::AboveNormal),
+                (42, PriorityClass::AboveNormal)
+            ]
+        );
+        assert!(log_manager.entries.iter().any(
+            |entry| entry.message.contains("PID 42") && entry.message.contains("access denied")
+        ));
+    }
+
     #[test]
     fn test_row_fix_setter_failure_stays_mismatched_and_is_rejected() {
         let state = sample_state();
@@ -1256,245 +2905,1183 @@ mod tests {
             &state,
             &mut runtime,
             &mut log_manager,
-            row_request(app, AppRowAction::Fix),
+            row_request(app, AppRowAction::Fix),
+            &os,
+        );
+
+        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
+        assert_no_row_action_os_calls(&os);
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("not running")));
+    }
+
+    #[test]
+    fn test_row_focus_only_focuses_and_does_not_reapply_or_launch() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            focus_results: HashMap::from([(91, true)]),
+            ..Default::default()
+        };
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Focus),
+            &os,
+        );
+
+        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
+        assert_eq!(os.focus_calls.borrow().as_slice(), &[91]);
+        assert!(os.affinity_calls.borrow().is_empty());
+        assert!(os.priority_calls.borrow().is_empty());
+        assert!(os.run_calls.borrow().is_empty());
+        assert!(os.activate_calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_row_focus_busy_fails_closed_without_os_calls() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        assert!(runtime.add_running_app(&app.get_key(), 91, group_id(0), rule_id(0)));
+        let running_apps = runtime.running_apps_handle();
+        let _write_guard = running_apps.try_write().unwrap();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Focus),
+            &os,
+        );
+
+        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
+        assert_no_row_action_os_calls(&os);
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("temporarily busy")));
+    }
+
+    #[test]
+    fn test_row_stop_terminates_tracked_trees_and_untracks_app() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
+        assert!(runtime.add_pid_to_existing_app(&app_key, 92));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            terminate_results: HashMap::from([(91, Ok(vec![91, 92, 93]))]),
+            ..Default::default()
+        };
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Stop),
+            &os,
+        );
+
+        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
+        assert_eq!(os.terminate_calls.borrow().as_slice(), &[91]);
+        assert!(!runtime.contains_app(&app_key));
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("terminated PIDs: [91, 92, 93]")));
+    }
+
+    #[test]
+    fn test_row_stop_warns_when_tracking_cannot_be_cleared() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            terminate_results: HashMap::from([(91, Ok(vec![91]))]),
+            ..Default::default()
+        };
+        let running_apps = runtime.running_apps_handle();
+        let monitor_read = running_apps.try_read().unwrap();
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Stop),
+            &os,
+        );
+
+        drop(monitor_read);
+        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
+        assert!(runtime.contains_app(&app_key));
+        assert!(log_manager
+            .entries
+            .back()
+            .unwrap()
+            .message
+            .contains("stays listed until the monitor notices it exited"));
+    }
+
+    #[test]
+    fn test_row_release_resets_processes_and_stops_tracking_without_killing() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
+        assert!(runtime.add_pid_to_existing_app(&app_key, 92));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            available_core_mask: 0b1111,
+            ..Default::default()
+        };
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Release),
+            &os,
+        );
+
+        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
+        assert_eq!(
+            os.affinity_calls.borrow().as_slice(),
+            &[(91, 0b1111), (92, 0b1111)]
+        );
+        assert_eq!(
+            os.priority_calls.borrow().as_slice(),
+            &[(91, PriorityClass::Normal), (92, PriorityClass::Normal)]
+        );
+        assert!(os.terminate_calls.borrow().is_empty());
+        assert!(!runtime.contains_app(&app_key));
+        let released = runtime.running_apps_handle();
+        assert_eq!(
+            released.try_read().unwrap().released_pids,
+            HashSet::from([91, 92])
+        );
+        assert!(log_manager
+            .entries
+            .back()
+            .unwrap()
+            .message
+            .contains("PIDs: [91, 92]"));
+    }
+
+    #[test]
+    fn test_apply_group_to_running_adopts_matching_processes() {
+        let state = sample_state();
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            snapshot_result: RefCell::new(Ok(LaunchProcessSnapshot {
+                children_of: HashMap::new(),
+                names: HashMap::from([
+                    (500, "one.exe".to_string()),
+                    (501, "TWO.EXE".to_string()),
+                    (502, "other.exe".to_string()),
+                ]),
+            })),
+            ..Default::default()
+        };
+
+        let adjusted = apply_group_to_running_with_os(&state, &runtime, &mut log_manager, 0, &os);
+
+        assert_eq!(adjusted, 2);
+        assert_eq!(
+            os.affinity_calls.borrow().as_slice(),
+            &[(500, 0b11), (501, 0b11)]
+        );
+        assert_eq!(
+            os.priority_calls.borrow().as_slice(),
+            &[(500, PriorityClass::Normal), (501, PriorityClass::High)]
+        );
+        let programs = state.read().unwrap().groups[0].programs.clone();
+        assert!(programs
+            .iter()
+            .all(|program| runtime.contains_app(&program.get_key())));
+        assert_eq!(
+            log_manager.entries.back().unwrap().message,
+            "Applied group 'Games' to 2 running process(es)"
+        );
+    }
+
+    #[test]
+    fn test_apply_group_to_running_uses_each_app_mask_and_tracked_pids() {
+        let state = sample_state();
+        state.write().unwrap().groups[0].programs[1].smt_off = true;
+        let programs = state.read().unwrap().groups[0].programs.clone();
+        let runtime = RuntimeRegistry::new();
+        assert!(runtime.add_running_app(&programs[0].get_key(), 700, group_id(0), rule_id(0)));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            snapshot_result: RefCell::new(Ok(LaunchProcessSnapshot {
+                children_of: HashMap::new(),
+                names: HashMap::from([(500, "one.exe".to_string()), (501, "two.exe".to_string())]),
+            })),
+            ..Default::default()
+        };
+
+        let adjusted = apply_group_to_running_with_os(&state, &runtime, &mut log_manager, 0, &os);
+
+        assert_eq!(adjusted, 2);
+        assert_eq!(
+            os.affinity_calls.borrow().as_slice(),
+            &[(700, 0b11), (501, 0b1)]
+        );
+    }
+
+    #[test]
+    fn test_row_stop_keeps_tracking_when_every_terminate_fails() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            terminate_results: HashMap::from([(91, Err("access denied".to_string()))]),
+            ..Default::default()
+        };
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Stop),
+            &os,
+        );
+
+        assert!(matches!(
+            outcome,
+            super::LaunchDispatchOutcome::Rejected(message) if message.contains("access denied")
+        ));
+        assert!(runtime.contains_app(&app_key));
+    }
+
+    #[test]
+    fn test_row_focus_disappeared_entry_fails_closed_without_os_calls() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
+        runtime
+            .running_apps_handle()
+            .try_write()
+            .unwrap()
+            .remove_app(&app_key);
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Focus),
+            &os,
+        );
+
+        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
+        assert_no_row_action_os_calls(&os);
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("not running")));
+    }
+
+    #[test]
+    fn test_row_run_launches_not_running_app() {
+        let state = sample_state();
+        let mut runtime = RuntimeRegistry::new();
+        let mut app = sample_app();
+        app.window_show = WindowShow::Minimized;
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(5150)),
+            ..Default::default()
+        };
+
+        let outcome = run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::Run),
+            &os,
+        );
+
+        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
+        assert_eq!(os.run_calls.borrow().len(), 1);
+        assert_eq!(*os.window_show_calls.borrow(), vec![WindowShow::Minimized]);
+        assert!(os.focus_calls.borrow().is_empty());
+    }
+
+    #[test]
+    fn test_running_registry_contention_rejects_path_launch_without_duplicate() {
+        let runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 77, group_id(0), rule_id(0)));
+        let running_apps = runtime.running_apps_handle();
+        let _write_guard = running_apps.try_write().unwrap();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(555)),
+            ..Default::default()
+        };
+
+        let outcome = run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![1, 3]),
+            rule_id(0),
+            app,
+            None,
+            &os,
+        );
+
+        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
+        assert!(os.run_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("temporarily busy")));
+    }
+
+    #[test]
+    fn test_brief_registry_write_is_waited_out_instead_of_launching_duplicate() {
+        let runtime = RuntimeRegistry::new();
+        let app = sample_app();
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 77, group_id(0), rule_id(0)));
+        let write_guard = runtime.running_apps_handle().try_write_owned().unwrap();
+        let monitor_pass = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(15));
+            drop(write_guard);
+        });
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(555)),
+            ..Default::default()
+        };
+
+        let outcome = run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![1, 3]),
+            rule_id(0),
+            app,
+            None,
+            &os,
+        );
+        monitor_pass.join().unwrap();
+
+        assert!(matches!(outcome, super::LaunchDispatchOutcome::Accepted));
+        assert!(os.run_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.starts_with("App already running:")));
+    }
+
+    #[test]
+    fn test_running_registry_contention_rejects_installed_activation_without_duplicate() {
+        let runtime = RuntimeRegistry::new();
+        let app = AppToRun::new_installed(
+            "Sample Store App".to_string(),
+            "Sample.Package!App".to_string(),
+            PriorityClass::Normal,
+            false,
+        );
+        let app_key = app.get_key();
+        assert!(runtime.add_running_app(&app_key, 88, group_id(0), rule_id(0)));
+        let running_apps = runtime.running_apps_handle();
+        let _write_guard = running_apps.try_write().unwrap();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            activate_result: RefCell::new(Err("activation should not be attempted".to_string())),
+            ..Default::default()
+        };
+
+        let outcome = run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![0]),
+            rule_id(0),
+            app,
+            None,
+            &os,
+        );
+
+        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
+        assert!(os.activate_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("temporarily busy")));
+    }
+
+    #[test]
+    fn test_missing_group_logs_critical_and_stops() {
+        let state = sample_state();
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+
+        run_app_with_affinity_sync_with_os(
+            &state,
+            &runtime,
+            &mut log_manager,
+            99,
+            0,
+            sample_app(),
+            &os,
+        );
+
+        assert!(os.run_calls.borrow().is_empty());
+        assert_eq!(
+            log_manager
+                .entries
+                .iter()
+                .filter(|entry| entry.message.contains("Group index 99 not found"))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_fresh_launch_success_tracks_pid_in_runtime_registry() {
+        let state = sample_state();
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
+        let app = sample_app();
+        let app_key = app.get_key();
+
+        run_app_with_affinity_sync_with_os(&state, &runtime, &mut log_manager, 0, 0, app, &os);
+
+        assert_eq!(runtime.get_running_app_pids(&app_key), Some(vec![4242]));
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == "App started with PID: 4242"));
+    }
+
+    #[test]
+    fn test_run_on_cluster_launches_on_its_threads_and_pins_them() {
+        let state = sample_state();
+        state.write().unwrap().cpu_schema.clusters = ["P-cores", "E-cores"]
+            .iter()
+            .enumerate()
+            .map(|(cluster, name)| CpuCluster {
+                name: name.to_string(),
+                cores: (cluster * 4..cluster * 4 + 4)
+                    .map(|index| CoreInfo {
+                        index,
+                        core_type: CoreType::Performance,
+                        label: format!("C{index}"),
+                    })
+                    .collect(),
+            })
+            .collect();
+        let mut runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
+        let app = sample_app();
+        let app_key = app.get_key();
+
+        run_app_row_action_with_os(
+            &state,
+            &mut runtime,
+            &mut log_manager,
+            row_request(app, AppRowAction::RunOnCluster(1)),
+            &os,
+        );
+
+        assert_eq!(os.run_calls.borrow()[0].2, vec![4, 5, 6, 7]);
+        assert_eq!(
+            runtime.running_apps_handle().try_read().unwrap().apps[&app_key].pinned_cores,
+            Some(vec![4, 5, 6, 7])
+        );
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("on cluster 'E-cores'")));
+
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4343)),
+            ..Default::default()
+        };
+        run_app_row_action_with_os(
+            &state,
+            &mut RuntimeRegistry::new(),
+            &mut log_manager,
+            row_request(sample_app(), AppRowAction::RunOnCluster(5)),
+            &os,
+        );
+        let group_cores = state.read().unwrap().groups[0].cores.clone();
+        assert_eq!(os.run_calls.borrow()[0].2, group_cores);
+    }
+
+    #[test]
+    fn test_least_loaded_group_launches_on_idlest_cluster_threads_and_pins_them() {
+        let state = sample_state();
+        {
+            let mut state = state.write().unwrap();
+            state.groups[0].core_selector = CoreSelector::LeastLoaded {
+                cluster: 0,
+                count: 2,
+            };
+            state.cpu_schema.clusters = vec![CpuCluster {
+                name: "P-cores".to_string(),
+                cores: (0..4)
+                    .map(|index| CoreInfo {
+                        index,
+                        core_type: CoreType::Performance,
+                        label: format!("P{index}"),
+                    })
+                    .collect(),
+            }];
+            state.reserved_cores = vec![3];
+        }
+        let runtime = RuntimeRegistry::new();
+        runtime
+            .running_apps_handle()
+            .try_write()
+            .unwrap()
+            .core_usage = Some(vec![0.9, 0.2, 0.1, 0.0]);
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
+        let app = sample_app();
+        let app_key = app.get_key();
+
+        run_app_with_affinity_sync_with_os(&state, &runtime, &mut log_manager, 0, 0, app, &os);
+
+        assert_eq!(os.run_calls.borrow()[0].2, vec![1, 2]);
+        let running_apps = runtime.running_apps_handle();
+        assert_eq!(
+            running_apps.try_read().unwrap().apps[&app_key].pinned_cores,
+            Some(vec![1, 2])
+        );
+
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4343)),
+            ..Default::default()
+        };
+        run_app_with_affinity_sync_with_os(
+            &state,
+            &RuntimeRegistry::new(),
+            &mut log_manager,
+            0,
+            0,
+            sample_app(),
+            &os,
+        );
+        assert_eq!(os.run_calls.borrow()[0].2, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_fresh_launch_expands_argument_placeholders_for_os_run_only() {
+        let state = sample_state();
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
+        let mut app = sample_app();
+        app.args = vec!["--title={group}".into(), "{mask}:{cores}:{gpu}".into()];
+        let app_key = app.get_key();
+
+        run_app_with_affinity_sync_with_os(&state, &runtime, &mut log_manager, 0, 0, app, &os);
+
+        assert_eq!(
+            os.run_calls.borrow()[0].1,
+            vec!["--title=Games".to_string(), "3:0,1:{gpu}".to_string()]
+        );
+        assert_eq!(runtime.get_running_app_pids(&app_key), Some(vec![4242]));
+        assert!(log_manager.entries.iter().any(|entry| entry
+            .message
+            .starts_with("WARNING: unknown placeholder {gpu} in arguments of ")));
+    }
+
+    #[test]
+    fn test_fresh_launch_logs_settings_retries() {
+        let state = sample_state();
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            run_settings_attempts: 2,
+            ..Default::default()
+        };
+
+        run_app_with_affinity_sync_with_os(
+            &state,
+            &runtime,
+            &mut log_manager,
+            0,
+            0,
+            sample_app(),
+            &os,
+        );
+
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == "Affinity and priority for PID 4242 took 2 attempts"));
+    }
+
+    #[test]
+    fn test_job_object_group_launches_in_job_and_warns_when_it_falls_back() {
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
+        let job_group = || LaunchGroup {
+            use_job_object: true,
+            ..launch_group(0, vec![0])
+        };
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            job_group(),
+            rule_id(0),
+            sample_app(),
+            None,
+            &os,
+        );
+
+        assert_eq!(
+            *os.launch_affinity_calls.borrow(),
+            vec![LaunchAffinity::Job]
+        );
+        assert!(log_manager.entries.iter().any(|entry| entry.message
+            == "PID 4242 runs in a Job Object; its child processes stay on the group's threads"));
+
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            run_in_job_result: false,
+            ..Default::default()
+        };
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            job_group(),
+            rule_id(0),
+            sample_app(),
+            None,
+            &os,
+        );
+
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.starts_with("WARNING: ")
+                && entry.message.contains("could not be put in a Job Object")));
+    }
+
+    #[test]
+    fn test_inherit_affinity_app_is_never_given_its_mask_from_outside() {
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
+        let mut app = sample_app();
+        app.inherit_affinity = true;
+        let job_group = || LaunchGroup {
+            use_job_object: true,
+            ..launch_group(0, vec![0])
+        };
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            job_group(),
+            rule_id(0),
+            app.clone(),
+            None,
+            &os,
+        );
+
+        assert_eq!(
+            *os.launch_affinity_calls.borrow(),
+            vec![LaunchAffinity::Inherited]
+        );
+        assert!(os.affinity_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("inherited its affinity")));
+
+        let runtime = RuntimeRegistry::new();
+        assert!(runtime.add_running_app(&app.get_key(), 41, group_id(0), rule_id(0)));
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            job_group(),
+            rule_id(0),
+            app,
+            None,
+            &os,
+        );
+
+        assert!(os.affinity_calls.borrow().is_empty());
+        assert_eq!(
+            os.priority_calls.borrow().last(),
+            Some(&(41, PriorityClass::High))
+        );
+    }
+
+    #[test]
+    fn test_shell_execute_app_is_opened_by_association_and_constrained_after_start() {
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs::default();
+        let mut app = sample_app();
+        app.use_shell_execute = true;
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![0, 1]),
+            rule_id(0),
+            app.clone(),
+            None,
+            &os,
+        );
+
+        assert_eq!(os.shell_open_calls.borrow().len(), 1);
+        assert!(os.run_calls.borrow().is_empty());
+        assert!(os.affinity_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains("with its default program")));
+
+        *os.shell_open_result.borrow_mut() = Ok(Some(4242));
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![0, 1]),
+            rule_id(0),
+            app,
+            None,
+            &os,
+        );
+
+        assert_eq!(*os.affinity_calls.borrow(), vec![(4242, 0b11)]);
+        assert_eq!(
+            *os.priority_calls.borrow(),
+            vec![(4242, PriorityClass::High)]
+        );
+    }
+
+    #[test]
+    fn test_shell_execute_leaves_a_shell_host_handler_alone() {
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            shell_open_result: RefCell::new(Ok(Some(4242))),
+            image_paths: HashMap::from([(4242, PathBuf::from(r"C:\Windows\explorer.exe"))]),
+            ..Default::default()
+        };
+        let mut app = sample_app();
+        app.use_shell_execute = true;
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![0, 1]),
+            rule_id(0),
+            app.clone(),
+            None,
+            &os,
+        );
+
+        assert!(os.affinity_calls.borrow().is_empty());
+        assert!(os.priority_calls.borrow().is_empty());
+        assert!(!runtime.contains_app(&app.get_key()));
+    }
+
+    #[test]
+    fn test_failed_launch_is_kept_for_the_row_until_a_launch_succeeds() {
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let app = sample_app();
+        let error = super::launch_error_text(OsApiError::Win {
+            code: 0x8007_00C1_u32 as i32,
+            message: "run \"tool.exe\" failed: bad format".into(),
+        });
+        assert_eq!(
+            error,
+            "run \"tool.exe\" failed: bad format (Fix: this is not a runnable .exe)"
+        );
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Err(error.clone())),
+            ..Default::default()
+        };
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![0]),
+            rule_id(0),
+            app.clone(),
+            None,
+            &os,
+        );
+
+        assert_eq!(runtime.launch_failure(&app.get_key()), Some(error.clone()));
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == format!("ERROR: {error}")));
+
+        *os.run_result.borrow_mut() = Ok(4242);
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![0]),
+            rule_id(0),
+            app.clone(),
+            None,
+            &os,
+        );
+
+        assert_eq!(runtime.launch_failure(&app.get_key()), None);
+    }
+
+    #[test]
+    fn test_cpu_rate_limit_is_applied_after_launch_and_warns_on_failure() {
+        let limited_group = || LaunchGroup {
+            cpu_rate_percent: Some(30),
+            ..launch_group(0, vec![0])
+        };
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
+
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            limited_group(),
+            rule_id(0),
+            sample_app(),
+            None,
+            &os,
+        );
+
+        assert_eq!(os.cpu_rate_calls.borrow().as_slice(), &[(4242, 30)]);
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == "CPU usage of PID 4242 capped at 30%"));
+
+        let runtime = RuntimeRegistry::new();
+        let mut log_manager = LogManager::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            cpu_rate_result: Err("cgroup v2 cpu controller is not available".to_string()),
+            ..Default::default()
+        };
+
+        let outcome = run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            limited_group(),
+            rule_id(0),
+            sample_app(),
+            None,
             &os,
         );
 
-        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
-        assert_no_row_action_os_calls(&os);
-        assert!(log_manager
-            .entries
-            .iter()
-            .any(|entry| entry.message.contains("not running")));
+        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
+        assert!(log_manager.entries.iter().any(|entry| entry
+            .message
+            .starts_with("WARNING: CPU limit of 30% not applied to ")
+            && entry
+                .message
+                .ends_with(": cgroup v2 cpu controller is not available")));
     }
 
     #[test]
-    fn test_row_focus_only_focuses_and_does_not_reapply_or_launch() {
-        let state = sample_state();
-        let mut runtime = RuntimeRegistry::new();
-        let app = sample_app();
-        let app_key = app.get_key();
-        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
+    fn test_io_priority_is_set_after_launch_only_when_lowered() {
+        let runtime = RuntimeRegistry::new();
         let mut log_manager = LogManager::default();
         let os = FakeLaunchOs {
-            focus_results: HashMap::from([(91, true)]),
+            run_result: RefCell::new(Ok(4242)),
             ..Default::default()
         };
 
-        let outcome = run_app_row_action_with_os(
-            &state,
-            &mut runtime,
+        run_launch_decision(
+            &runtime,
             &mut log_manager,
-            row_request(app, AppRowAction::Focus),
+            launch_group(0, vec![0]),
+            rule_id(0),
+            sample_app(),
+            None,
             &os,
         );
+        assert!(os.io_priority_calls.borrow().is_empty());
 
-        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
-        assert_eq!(os.focus_calls.borrow().as_slice(), &[91]);
-        assert!(os.affinity_calls.borrow().is_empty());
-        assert!(os.priority_calls.borrow().is_empty());
-        assert!(os.run_calls.borrow().is_empty());
-        assert!(os.activate_calls.borrow().is_empty());
+        let mut app = sample_app();
+        app.io_priority = IoPriority::VeryLow;
+        let runtime = RuntimeRegistry::new();
+        run_launch_decision(
+            &runtime,
+            &mut log_manager,
+            launch_group(0, vec![0]),
+            rule_id(0),
+            app,
+            None,
+            &os,
+        );
+        assert_eq!(
+            os.io_priority_calls.borrow().as_slice(),
+            &[(4242, IoPriority::VeryLow)]
+        );
     }
 
     #[test]
-    fn test_row_focus_busy_fails_closed_without_os_calls() {
-        let state = sample_state();
-        let mut runtime = RuntimeRegistry::new();
-        let app = sample_app();
-        assert!(runtime.add_running_app(&app.get_key(), 91, group_id(0), rule_id(0)));
-        let running_apps = runtime.running_apps_handle();
-        let _write_guard = running_apps.try_write().unwrap();
+    fn test_post_launch_command_runs_with_pid_after_successful_launch_only() {
+        let mut app = sample_app();
+        app.post_launch_cmd = Some("fancurve --pid {pid} --cores {cores}".to_string());
+        let runtime = RuntimeRegistry::new();
         let mut log_manager = LogManager::default();
-        let os = FakeLaunchOs::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            ..Default::default()
+        };
 
-        let outcome = run_app_row_action_with_os(
-            &state,
-            &mut runtime,
+        run_launch_decision(
+            &runtime,
             &mut log_manager,
-            row_request(app, AppRowAction::Focus),
+            launch_group(0, vec![2, 3]),
+            rule_id(0),
+            app.clone(),
+            None,
             &os,
         );
 
-        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
-        assert_no_row_action_os_calls(&os);
-        assert!(log_manager
-            .entries
-            .iter()
-            .any(|entry| entry.message.contains("temporarily busy")));
+        assert_eq!(
+            os.shell_calls.borrow().as_slice(),
+            &["fancurve --pid 4242 --cores 2,3".to_string()]
+        );
+        assert!(log_manager.entries.iter().any(|entry| entry
+            .message
+            .ends_with("started (PID 7): fancurve --pid 4242 --cores 2,3")));
+
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Err("boom".to_string())),
+            ..Default::default()
+        };
+        run_launch_decision(
+            &RuntimeRegistry::new(),
+            &mut LogManager::default(),
+            launch_group(0, vec![2, 3]),
+            rule_id(0),
+            app,
+            None,
+            &os,
+        );
+        assert!(os.shell_calls.borrow().is_empty());
     }
 
     #[test]
-    fn test_row_focus_disappeared_entry_fails_closed_without_os_calls() {
+    fn test_fresh_launch_warns_when_effective_affinity_is_narrowed() {
         let state = sample_state();
-        let mut runtime = RuntimeRegistry::new();
-        let app = sample_app();
-        let app_key = app.get_key();
-        assert!(runtime.add_running_app(&app_key, 91, group_id(0), rule_id(0)));
-        runtime
-            .running_apps_handle()
-            .try_write()
-            .unwrap()
-            .remove_app(&app_key);
+        let runtime = RuntimeRegistry::new();
         let mut log_manager = LogManager::default();
-        let os = FakeLaunchOs::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            effective_affinities: HashMap::from([(4242, 0b01)]),
+            ..Default::default()
+        };
 
-        let outcome = run_app_row_action_with_os(
+        run_app_with_affinity_sync_with_os(
             &state,
-            &mut runtime,
+            &runtime,
             &mut log_manager,
-            row_request(app, AppRowAction::Focus),
+            0,
+            0,
+            sample_app(),
             &os,
         );
 
-        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
-        assert_no_row_action_os_calls(&os);
-        assert!(log_manager
-            .entries
-            .iter()
-            .any(|entry| entry.message.contains("not running")));
+        assert!(log_manager.entries.iter().any(|entry| entry
+            .message
+            .contains("requested CPU mask 0x3 but runs with 0x1")));
     }
 
     #[test]
-    fn test_row_run_launches_not_running_app() {
+    fn test_launch_warns_when_group_uses_unavailable_cores() {
         let state = sample_state();
-        let mut runtime = RuntimeRegistry::new();
-        let app = sample_app();
+        let group_name = state.read().unwrap().groups[0].name.clone();
+        let runtime = RuntimeRegistry::new();
         let mut log_manager = LogManager::default();
         let os = FakeLaunchOs {
-            run_result: RefCell::new(Ok(5150)),
+            run_result: RefCell::new(Ok(4242)),
+            effective_affinities: HashMap::from([(4242, 0b11)]),
+            available_core_mask: 0b01,
             ..Default::default()
         };
 
-        let outcome = run_app_row_action_with_os(
+        run_app_with_affinity_sync_with_os(
             &state,
-            &mut runtime,
+            &runtime,
             &mut log_manager,
-            row_request(app, AppRowAction::Run),
+            0,
+            0,
+            sample_app(),
             &os,
         );
 
-        assert_eq!(outcome, super::LaunchDispatchOutcome::Accepted);
-        assert_eq!(os.run_calls.borrow().len(), 1);
-        assert!(os.focus_calls.borrow().is_empty());
+        assert!(log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.contains(&format!(
+                "group \"{group_name}\" uses CPU threads [1] that are disabled"
+            ))));
     }
 
     #[test]
-    fn test_running_registry_contention_rejects_path_launch_without_duplicate() {
+    fn test_launch_warns_when_group_uses_reserved_cores() {
+        let state = sample_state();
+        state.write().unwrap().reserved_cores = vec![1, 7];
         let runtime = RuntimeRegistry::new();
-        let app = sample_app();
-        let app_key = app.get_key();
-        assert!(runtime.add_running_app(&app_key, 77, group_id(0), rule_id(0)));
-        let running_apps = runtime.running_apps_handle();
-        let _write_guard = running_apps.try_write().unwrap();
         let mut log_manager = LogManager::default();
         let os = FakeLaunchOs {
-            run_result: RefCell::new(Ok(555)),
+            run_result: RefCell::new(Ok(4242)),
+            effective_affinities: HashMap::from([(4242, 0b11)]),
             ..Default::default()
         };
 
-        let outcome = run_launch_decision(
+        run_app_with_affinity_sync_with_os(
+            &state,
             &runtime,
             &mut log_manager,
-            group_id(0),
-            rule_id(0),
-            app,
-            vec![1, 3],
+            0,
+            0,
+            sample_app(),
             &os,
         );
 
-        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
-        assert!(os.run_calls.borrow().is_empty());
-        assert!(log_manager
-            .entries
-            .iter()
-            .any(|entry| entry.message.contains("temporarily busy")));
+        assert!(log_manager.entries.iter().any(|entry| entry
+            .message
+            .contains("uses CPU threads [1] that are reserved")));
     }
 
     #[test]
-    fn test_running_registry_contention_rejects_installed_activation_without_duplicate() {
+    fn test_fresh_launch_stays_quiet_when_effective_affinity_matches() {
+        let state = sample_state();
         let runtime = RuntimeRegistry::new();
-        let app = AppToRun::new_installed(
-            "Sample Store App".to_string(),
-            "Sample.Package!App".to_string(),
-            PriorityClass::Normal,
-            false,
-        );
-        let app_key = app.get_key();
-        assert!(runtime.add_running_app(&app_key, 88, group_id(0), rule_id(0)));
-        let running_apps = runtime.running_apps_handle();
-        let _write_guard = running_apps.try_write().unwrap();
         let mut log_manager = LogManager::default();
         let os = FakeLaunchOs {
-            activate_result: RefCell::new(Err("activation should not be attempted".to_string())),
+            run_result: RefCell::new(Ok(4242)),
+            effective_affinities: HashMap::from([(4242, 0b11)]),
             ..Default::default()
         };
 
-        let outcome = run_launch_decision(
+        run_app_with_affinity_sync_with_os(
+            &state,
             &runtime,
             &mut log_manager,
-            group_id(0),
-            rule_id(0),
-            app,
-            vec![0],
+            0,
+            0,
+            sample_app(),
             &os,
         );
 
-        assert!(matches!(outcome, super::LaunchDispatchOutcome::Rejected(_)));
-        assert!(os.activate_calls.borrow().is_empty());
-        assert!(log_manager
-            .entries
-            .iter()
-            .any(|entry| entry.message.contains("temporarily busy")));
+        assert!(!log_manager.entries.iter().any(|entry| {
+            entry.message.contains("requested CPU mask")
+                || entry.message.contains("disabled, parked or offline")
+        }));
     }
 
     #[test]
-    fn test_missing_group_logs_critical_and_stops() {
+    fn test_single_numa_node_group_prefers_majority_node_after_launch() {
         let state = sample_state();
+        state.write().unwrap().groups[0].single_numa_node = true;
         let runtime = RuntimeRegistry::new();
         let mut log_manager = LogManager::default();
-        let os = FakeLaunchOs::default();
+        let os = FakeLaunchOs {
+            run_result: RefCell::new(Ok(4242)),
+            numa_nodes: HashMap::from([(0, 1), (1, 1)]),
+            ..Default::default()
+        };
 
         run_app_with_affinity_sync_with_os(
             &state,
             &runtime,
             &mut log_manager,
-            99,
+            0,
             0,
             sample_app(),
             &os,
         );
 
-        assert!(os.run_calls.borrow().is_empty());
-        assert_eq!(
-            log_manager
-                .entries
-                .iter()
-                .filter(|entry| entry.message.contains("Group index 99 not found"))
-                .count(),
-            2
-        );
+        assert_eq!(os.numa_calls.borrow().as_slice(), &[(4242, 1)]);
     }
 
     #[test]
-    fn test_fresh_launch_success_tracks_pid_in_runtime_registry() {
+    fn test_numa_preference_is_not_applied_when_group_option_is_off() {
         let state = sample_state();
         let runtime = RuntimeRegistry::new();
         let mut log_manager = LogManager::default();
         let os = FakeLaunchOs {
             run_result: RefCell::new(Ok(4242)),
+            numa_nodes: HashMap::from([(0, 0), (1, 0)]),
             ..Default::default()
         };
-        let app = sample_app();
-        let app_key = app.get_key();
 
-        run_app_with_affinity_sync_with_os(&state, &runtime, &mut log_manager, 0, 0, app, &os);
+        run_app_with_affinity_sync_with_os(
+            &state,
+            &runtime,
+            &mut log_manager,
+            0,
+            0,
+            sample_app(),
+            &os,
+        );
 
-        assert_eq!(runtime.get_running_app_pids(&app_key), Some(vec![4242]));
-        assert!(log_manager
-            .entries
-            .iter()
-            .any(|entry| entry.message == "App started with PID: 4242"));
+        assert!(os.numa_calls.borrow().is_empty());
     }
 
     #[test]
@@ -1542,10 +4129,10 @@ mod tests {
         run_launch_decision(
             &runtime,
             &mut log_manager,
-            group_id(0),
+            launch_group(0, vec![0]),
             rule_id(0),
             sample_app(),
-            vec![0],
+            None,
             &os,
         );
 
@@ -1578,10 +4165,10 @@ mod tests {
         run_launch_decision(
             &runtime,
             &mut log_manager,
-            group_id(0),
+            launch_group(0, vec![0, 2]),
             rule_id(0),
             app,
-            vec![0, 2],
+            None,
             &os,
         );
 
@@ -1621,10 +4208,10 @@ mod tests {
         run_launch_decision(
             &runtime,
             &mut log_manager,
-            group_id(0),
+            launch_group(0, vec![0, 2]),
             rule_id(0),
             app,
-            vec![0, 2],
+            None,
             &os,
         );
 
@@ -1833,10 +4420,10 @@ mod tests {
         run_launch_decision(
             &runtime,
             &mut log_manager,
-            group_id(0),
+            launch_group(0, vec![0, 2]),
             rule_id(0),
             app,
-            vec![0, 2],
+            None,
             &os,
         );
 
@@ -1855,4 +4442,43 @@ mod tests {
             .iter()
             .any(|entry| entry.message.contains("metadata unavailable")));
     }
+
+    #[test]
+    fn test_startup_check_waits_for_window_and_reports_early_exit() {
+        assert_eq!(
+            startup_check(ProcessState::Exited(Some(3)), false, false, false),
+            Some(StartupOutcome::Exited(Some(3)))
+        );
+        assert_eq!(
+            startup_check(ProcessState::Running, false, false, false),
+            None
+        );
+        assert_eq!(
+            startup_check(ProcessState::Running, false, false, true),
+            Some(StartupOutcome::Confirmed)
+        );
+        assert_eq!(
+            startup_check(ProcessState::Running, true, true, false),
+            Some(StartupOutcome::Confirmed)
+        );
+        assert_eq!(
+            startup_check(ProcessState::Running, false, true, true),
+            Some(StartupOutcome::NoWindow)
+        );
+
+        assert_eq!(
+            StartupOutcome::Exited(Some(3)).event("game.exe", 42, Duration::from_secs(5)),
+            ShellEvent::Warning(
+                "WARNING: game.exe (PID 42) process exited during startup (exit code 3)"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            StartupOutcome::Exited(None).event("game.exe", 42, Duration::from_secs(5)),
+            ShellEvent::Warning(
+                "WARNING: game.exe (PID 42) process exited during startup (exit code unknown)"
+                    .to_string()
+            )
+        );
+    }
 }
@@ -1,3 +1,4 @@
+mod arg_template;
 mod launch;
 mod reconcile;
 mod store;
@@ -7,18 +8,21 @@ use crate::app::features::diagnostics::DiagnosticEvent;
 use crate::app::models::{normalize_process_name, AppStateStorage, RunningApps};
 use std::sync::mpsc::Receiver;
 use std::sync::{Arc, RwLock};
-use tokio::sync::RwLock as TokioRwLock;
+use tokio::sync::{watch, RwLock as TokioRwLock};
 
-pub(crate) use launch::{run_app_row_action, AppRowActionRequest};
 pub use launch::{
-    run_app_with_affinity_sync, start_app_with_autorun, AppRowAction, LaunchDispatchOutcome,
+    apply_group_to_running, apply_priority_to_running, queue_group_launches,
+    run_app_with_affinity_sync, run_due_autoruns, start_app_with_autorun, AppRowAction,
+    LaunchDispatchOutcome,
 };
+pub(crate) use launch::{run_app_row_action, AppRowActionRequest};
 pub use reconcile::run_process_settings_monitor;
-pub use store::RuntimeRegistry;
 pub(crate) use store::{
     cleanup_orphaned_package_owners, ensure_package_owner_claim,
     resolve_installed_package_runtime_info_cached, InstalledPackageTrackingState,
+    RunningAppSettingsUpdate,
 };
+pub use store::{LaunchProgress, RuntimeRegistry};
 pub use tracking::run_running_app_monitor;
 
 pub(crate) fn is_excluded_installed_auto_process(process_name: &str) -> bool {
@@ -28,10 +32,21 @@ pub(crate) fn is_excluded_installed_auto_process(process_name: &str) -> bool {
     )
 }
 
+/// Programs that open files on another program's behalf, e.g. `rundll32` hosting a
+/// viewer DLL. A shell-opened app is never matched or constrained through them, since
+/// that would pin the desktop shell itself.
+pub(crate) fn is_shell_host_process(process_name: &str) -> bool {
+    matches!(
+        normalize_process_name(process_name).as_str(),
+        "explorer" | "rundll32" | "dllhost" | "xdg-open"
+    )
+}
+
 pub fn spawn_monitors(
     running_apps: Arc<TokioRwLock<RunningApps>>,
     installed_package_tracking: Arc<RwLock<InstalledPackageTrackingState>>,
     persistent_state: Arc<RwLock<AppStateStorage>>,
+    shutdown: watch::Receiver<bool>,
 ) -> Receiver<DiagnosticEvent> {
     let (monitor_tx, monitor_rx) = std::sync::mpsc::channel();
 
@@ -40,11 +55,13 @@ pub fn spawn_monitors(
         installed_package_tracking,
         persistent_state.clone(),
         monitor_tx.clone(),
+        shutdown.clone(),
     ));
     tokio::spawn(run_process_settings_monitor(
         running_apps,
         persistent_state,
         monitor_tx,
+        shutdown,
     ));
 
     monitor_rx
@@ -1,11 +1,13 @@
+use crate::app::features::diagnostics::audit::{self, AuditAction};
 use crate::app::features::rules::RulesContext;
-use crate::app::models::{AppRuntimeKey, AppStateStorage, RunningApps};
+use crate::app::features::topology;
+use crate::app::models::{AffinityDrift, AppRuntimeKey, AppStateStorage, RunningApps, ThreadRule};
 use crate::app::shared::ids::{GroupId, RuleId};
 use crate::app::shell::events::ShellEvent;
-use os_api::{PriorityClass, OS};
+use os_api::{OsApiError, PriorityClass, PriorityClassName, ThreadInfo, OS};
 use std::collections::HashMap;
 use std::sync::{Arc, RwLock};
-use tokio::sync::RwLock as TokioRwLock;
+use tokio::sync::{watch, RwLock as TokioRwLock};
 
 #[derive(Debug, Clone)]
 struct ProgramRuntimeSettings {
@@ -14,6 +16,9 @@ struct ProgramRuntimeSettings {
     rule_id: RuleId,
     expected_mask: usize,
     expected_priority: PriorityClass,
+    thread_rules: Vec<ThreadRule>,
+    /// Enforced like monitoring even while monitoring is off.
+    watch_autoapply: bool,
 }
 
 #[derive(Debug, Default, PartialEq, Eq)]
@@ -23,29 +28,33 @@ struct ProcessSettingsIterationOutcome {
 }
 
 trait ProcessSettingsOs {
-    fn get_process_affinity(&mut self, pid: u32) -> Result<usize, String>;
+    fn get_process_affinity(&mut self, pid: u32) -> Result<usize, OsApiError>;
     fn get_process_priority(&mut self, pid: u32) -> Result<PriorityClass, String>;
-    fn set_process_affinity_by_pid(&mut self, pid: u32, mask: usize) -> Result<(), String>;
+    fn set_process_affinity_by_pid(&mut self, pid: u32, mask: usize) -> Result<(), OsApiError>;
     fn set_process_priority_by_pid(
         &mut self,
         pid: u32,
         priority: PriorityClass,
     ) -> Result<(), String>;
+    fn list_threads(&mut self, pid: u32) -> Result<Vec<ThreadInfo>, String>;
+    fn set_thread_affinity(&mut self, pid: u32, tid: u32, mask: usize) -> Result<usize, String>;
 }
 
 struct RealProcessSettingsOs;
 
 impl ProcessSettingsOs for RealProcessSettingsOs {
-    fn get_process_affinity(&mut self, pid: u32) -> Result<usize, String> {
+    fn get_process_affinity(&mut self, pid: u32) -> Result<usize, OsApiError> {
         OS::get_process_affinity(pid)
     }
 
     fn get_process_priority(&mut self, pid: u32) -> Result<PriorityClass, String> {
-        OS::get_process_priority(pid)
+        OS::get_process_priority(pid).map_err(String::from)
     }
 
-    fn set_process_affinity_by_pid(&mut self, pid: u32, mask: usize) -> Result<(), String> {
-        OS::set_process_affinity_by_pid(pid, mask)
+    fn set_process_affinity_by_pid(&mut self, pid: u32, mask: usize) -> Result<(), OsApiError> {
+        audit::audited_affinity(pid, AuditAction::MonitorAffinity, mask, || {
+            OS::set_process_affinity_by_pid(pid, mask)
+        })
     }
 
     fn set_process_priority_by_pid(
@@ -53,7 +62,17 @@ impl ProcessSettingsOs for RealProcessSettingsOs {
         pid: u32,
         priority: PriorityClass,
     ) -> Result<(), String> {
-        OS::set_process_priority_by_pid(pid, priority)
+        audit::audited_priority(pid, AuditAction::MonitorPriority, priority, || {
+            OS::set_process_priority_by_pid(pid, priority).map_err(String::from)
+        })
+    }
+
+    fn list_threads(&mut self, pid: u32) -> Result<Vec<ThreadInfo>, String> {
+        OS::list_threads(pid).map_err(String::from)
+    }
+
+    fn set_thread_affinity(&mut self, pid: u32, tid: u32, mask: usize) -> Result<usize, String> {
+        OS::set_thread_affinity(pid, tid, mask).map_err(String::from)
     }
 }
 
@@ -61,12 +80,16 @@ pub async fn run_process_settings_monitor(
     running_apps: Arc<TokioRwLock<RunningApps>>,
     app_state: Arc<RwLock<AppStateStorage>>,
     monitor_tx: std::sync::mpsc::Sender<ShellEvent>,
+    mut shutdown: watch::Receiver<bool>,
 ) {
     let mut interval = tokio::time::interval(std::time::Duration::from_secs(3));
     let mut os = RealProcessSettingsOs;
 
     loop {
-        interval.tick().await;
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.wait_for(|&stop| stop) => break,
+        }
 
         let (state_snapshot, monitoring_enabled) = {
             let state = match app_state.read() {
@@ -93,8 +116,7 @@ pub async fn run_process_settings_monitor(
             if !outcome.notifications.is_empty() {
                 for message in outcome.notifications {
                     let _ = monitor_tx.send(ShellEvent::Monitor(format!("MONITOR: {}", message)));
-                    #[cfg(debug_assertions)]
-                    println!("MONITOR: {}", message);
+                    log::trace!("MONITOR: {}", message);
                 }
             }
 
@@ -113,14 +135,11 @@ fn collect_program_settings(
     let snapshot = rules.snapshot(state);
 
     for group in snapshot.groups {
-        let mut expected_mask = 0usize;
-        for &core_index in &group.cores {
-            if core_index < (std::mem::size_of::<usize>() * 8) {
-                expected_mask |= 1 << core_index;
-            }
-        }
-
+        let cores = state
+            .active_profile_cores(&group.id)
+            .unwrap_or(&group.cores);
         for program in group.rules {
+            let expected_mask = topology::app_mask(cores, &program.app, &state.cpu_schema);
             settings.insert(
                 program.app.get_key(),
                 ProgramRuntimeSettings {
@@ -129,6 +148,8 @@ fn collect_program_settings(
                     rule_id: program.id,
                     expected_mask,
                     expected_priority: program.app.priority,
+                    thread_rules: program.app.thread_affinity_rules.clone(),
+                    watch_autoapply: program.app.watch_autoapply,
                 },
             );
         }
@@ -137,6 +158,53 @@ fn collect_program_settings(
     settings
 }
 
+fn cores_to_mask(cores: &[usize]) -> usize {
+    OS::mask_from_cores(cores.iter().copied())
+}
+
+/// Pins the threads matched by the rules to their sub-mask of the group cores.
+/// Reports only threads whose affinity actually changed.
+fn apply_thread_rules<O: ProcessSettingsOs>(
+    settings: &ProgramRuntimeSettings,
+    expected_mask: usize,
+    pid: u32,
+    os: &mut O,
+    notifications: &mut Vec<String>,
+) {
+    let Ok(threads) = os.list_threads(pid) else {
+        return;
+    };
+
+    for rule in &settings.thread_rules {
+        let mask = cores_to_mask(&rule.cores) & expected_mask;
+        if mask == 0 {
+            continue;
+        }
+
+        // On Linux the main thread shares the PID, so pinning it would narrow what
+        // the process mask check reads back and the two would keep undoing each other.
+        for tid in rule
+            .matching_tids(&threads)
+            .into_iter()
+            .filter(|&tid| tid != pid)
+        {
+            match os.set_thread_affinity(pid, tid, mask) {
+                Ok(previous) if previous != mask => notifications.push(format!(
+                    "Pinned thread {} of {} (PID {}): {:X} -> {:X}",
+                    tid, settings.name, pid, previous, mask
+                )),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Whether retrying changes on `pid` cannot help. The main process is only
+/// given up on once it is gone, since access to it can be regained.
+fn is_unreachable(err: &OsApiError, index: usize) -> bool {
+    err.is_process_not_found() || (index > 0 && err.is_access_denied())
+}
+
 fn process_settings_iteration_with_os<O: ProcessSettingsOs>(
     apps: &mut RunningApps,
     state: &AppStateStorage,
@@ -146,44 +214,140 @@ fn process_settings_iteration_with_os<O: ProcessSettingsOs>(
     let key_to_settings = collect_program_settings(state);
     let mut outcome = ProcessSettingsIterationOutcome::default();
 
+    let released = &apps.released_pids;
     for (app_key, app) in apps.apps.iter_mut() {
         if let Some(settings) = key_to_settings.get(app_key) {
             app.group_id = settings.group_id.clone();
             app.rule_id = settings.rule_id.clone();
 
             let mut all_matched = true;
+            let mut drift = None;
+            let expected_mask = app
+                .pinned_cores
+                .as_deref()
+                .map_or(settings.expected_mask, cores_to_mask);
+            let enforce = monitoring_enabled || settings.watch_autoapply;
+            let expected = (expected_mask, settings.expected_priority);
+            app.settled_pids.retain(|pid, _| app.pids.contains(pid));
+            app.unreachable_pids.retain(|pid| app.pids.contains(pid));
+            app.priority_fallbacks.retain(|pid, (requested, _)| {
+                app.pids.contains(pid) && *requested == settings.expected_priority
+            });
+
+            for (index, &pid) in app.pids.iter().enumerate() {
+                // The main process is always checked; children only until they are settled.
+                if released.contains(&pid)
+                    || (index > 0 && app.settled_pids.get(&pid) == Some(&expected))
+                {
+                    continue;
+                }
+                // Unreachable PIDs are still checked for drift, just never changed.
+                let enforce = enforce && !app.unreachable_pids.contains(&pid);
+                let mut settled = true;
 
-            for &pid in &app.pids {
-                if let Ok(current_mask) = os.get_process_affinity(pid) {
-                    if current_mask != settings.expected_mask {
+                match os.get_process_affinity(pid) {
+                    Ok(current_mask) if current_mask != expected_mask => {
                         all_matched = false;
-                        if monitoring_enabled
-                            && os
-                                .set_process_affinity_by_pid(pid, settings.expected_mask)
-                                .is_ok()
-                        {
-                            outcome.notifications.push(format!(
+                        match enforce.then(|| os.set_process_affinity_by_pid(pid, expected_mask)) {
+                            Some(Ok(())) => outcome.notifications.push(format!(
                                 "Fixed affinity for {} (PID {}): {:X} -> {:X}",
-                                settings.name, pid, current_mask, settings.expected_mask
-                            ));
+                                settings.name, pid, current_mask, expected_mask
+                            )),
+                            result => {
+                                if matches!(result, Some(Err(ref err)) if is_unreachable(err, index))
+                                {
+                                    app.unreachable_pids.insert(pid);
+                                }
+                                settled = false;
+                                drift.get_or_insert(AffinityDrift {
+                                    pid,
+                                    actual_mask: current_mask,
+                                    expected_mask,
+                                });
+                            }
                         }
                     }
+                    Ok(_) => {}
+                    Err(err) => {
+                        settled = false;
+                        if is_unreachable(&err, index) {
+                            all_matched = false;
+                            app.unreachable_pids.insert(pid);
+                        }
+                    }
+                }
+
+                let enforce = enforce && !app.unreachable_pids.contains(&pid);
+                if enforce && !settings.thread_rules.is_empty() {
+                    apply_thread_rules(
+                        settings,
+                        expected_mask,
+                        pid,
+                        os,
+                        &mut outcome.notifications,
+                    );
                 }
 
-                if let Ok(current_priority) = os.get_process_priority(pid) {
-                    if current_priority != settings.expected_priority {
+                // A class the OS refused is expected at whatever it applied instead.
+                let expected_priority = app
+                    .priority_fallbacks
+                    .get(&pid)
+                    .map_or(settings.expected_priority, |&(_, applied)| applied);
+                match os.get_process_priority(pid) {
+                    // Windows does not report EcoQoS back, so the class is compared and an
+                    // eco mode (Linux `SCHED_IDLE`) left over from an earlier rule counts as drift.
+                    Ok(current_priority)
+                        if current_priority.class_name() != expected_priority.class_name()
+                            || (current_priority.eco_qos() && !expected_priority.eco_qos()) =>
+                    {
                         all_matched = false;
-                        if monitoring_enabled
-                            && os
-                                .set_process_priority_by_pid(pid, settings.expected_priority)
-                                .is_ok()
+                        match enforce
+                            .then(|| os.set_process_priority_by_pid(pid, expected_priority))
                         {
-                            outcome.notifications.push(format!(
-                                "Fixed priority for {} (PID {}): {:?} -> {:?}",
-                                settings.name, pid, current_priority, settings.expected_priority
-                            ));
+                            Some(Ok(())) => match os.get_process_priority(pid) {
+                                // Windows reports success for Realtime without administrator
+                                // rights but applies High; keep that rather than ask again.
+                                Ok(applied)
+                                    if applied.class_name() != expected_priority.class_name() =>
+                                {
+                                    app.priority_fallbacks
+                                        .insert(pid, (expected_priority, applied));
+                                    outcome.notifications.push(format!(
+                                        "Could not set {:?} priority for {} (PID {}), keeping {:?}: the OS applied a lower class",
+                                        expected_priority, settings.name, pid, applied
+                                    ));
+                                    settled = false;
+                                }
+                                _ => outcome.notifications.push(format!(
+                                    "Fixed priority for {} (PID {}): {:?} -> {:?}",
+                                    settings.name, pid, current_priority, expected_priority
+                                )),
+                            },
+                            Some(Err(err))
+                                if expected_priority.class_name()
+                                    == PriorityClassName::Realtime =>
+                            {
+                                // Realtime needs extra rights; keep what the OS settled on
+                                // rather than asking again on every pass.
+                                if let Ok(applied) = os.get_process_priority(pid) {
+                                    app.priority_fallbacks
+                                        .insert(pid, (expected_priority, applied));
+                                    outcome.notifications.push(format!(
+                                        "Could not set {:?} priority for {} (PID {}), keeping {:?}: {}",
+                                        expected_priority, settings.name, pid, applied, err
+                                    ));
+                                }
+                                settled = false;
+                            }
+                            _ => settled = false,
                         }
                     }
+                    Ok(_) => {}
+                    Err(_) => settled = false,
+                }
+
+                if settled {
+                    app.settled_pids.insert(pid, expected);
                 }
             }
 
@@ -191,6 +355,10 @@ fn process_settings_iteration_with_os<O: ProcessSettingsOs>(
                 app.settings_matched = all_matched;
                 outcome.changed = true;
             }
+            if app.affinity_drift != drift {
+                app.affinity_drift = drift;
+                outcome.changed = true;
+            }
         }
     }
 
@@ -199,18 +367,34 @@ fn process_settings_iteration_with_os<O: ProcessSettingsOs>(
 
 #[cfg(test)]
 mod tests {
-    use super::{process_settings_iteration_with_os, ProcessSettingsOs};
-    use crate::app::models::{AppStateStorage, AppToRun, CoreGroup, CpuSchema, RunningApps};
+    use super::{
+        process_settings_iteration_with_os, run_process_settings_monitor, ProcessSettingsOs,
+    };
+    use crate::app::models::{
+        AffinityDrift, AppStateStorage, AppToRun, CoreGroup, CoreSelector, CpuSchema, RunningApps,
+        SelfAffinity, SpreadStrategy, ThreadMatch, ThreadRule,
+    };
     use crate::app::shared::ids::{GroupId, RuleId};
-    use os_api::PriorityClass;
+    use os_api::{OsApiError, PriorityClass, PriorityClassName, ThreadInfo};
     use std::collections::HashMap;
     use std::path::PathBuf;
+    use std::sync::{Arc, RwLock};
+    use std::time::Duration;
+    use tokio::sync::RwLock as TokioRwLock;
 
     struct FakeProcessSettingsOs {
         affinity: HashMap<u32, usize>,
         priority: HashMap<u32, PriorityClass>,
         affinity_sets: Vec<(u32, usize)>,
         priority_sets: Vec<(u32, PriorityClass)>,
+        threads: HashMap<u32, Vec<ThreadInfo>>,
+        thread_affinity: HashMap<u32, usize>,
+        denied: Vec<u32>,
+        affinity_reads: Vec<u32>,
+        realtime_fallback: Option<PriorityClass>,
+        /// Applies `realtime_fallback` but reports success, like Windows without
+        /// administrator rights.
+        quiet_realtime_fallback: bool,
     }
 
     impl FakeProcessSettingsOs {
@@ -220,16 +404,22 @@ mod tests {
                 priority,
                 affinity_sets: Vec::new(),
                 priority_sets: Vec::new(),
+                threads: HashMap::new(),
+                thread_affinity: HashMap::new(),
+                denied: Vec::new(),
+                affinity_reads: Vec::new(),
+                realtime_fallback: None,
+                quiet_realtime_fallback: false,
             }
         }
     }
 
     impl ProcessSettingsOs for FakeProcessSettingsOs {
-        fn get_process_affinity(&mut self, pid: u32) -> Result<usize, String> {
-            self.affinity
-                .get(&pid)
-                .copied()
-                .ok_or_else(|| format!("missing affinity for pid {pid}"))
+        fn get_process_affinity(&mut self, pid: u32) -> Result<usize, OsApiError> {
+            self.affinity_reads.push(pid);
+            self.affinity.get(&pid).copied().ok_or_else(|| {
+                OsApiError::ProcessNotFound(format!("missing affinity for pid {pid}"))
+            })
         }
 
         fn get_process_priority(&mut self, pid: u32) -> Result<PriorityClass, String> {
@@ -239,7 +429,12 @@ mod tests {
                 .ok_or_else(|| format!("missing priority for pid {pid}"))
         }
 
-        fn set_process_affinity_by_pid(&mut self, pid: u32, mask: usize) -> Result<(), String> {
+        fn set_process_affinity_by_pid(&mut self, pid: u32, mask: usize) -> Result<(), OsApiError> {
+            if self.denied.contains(&pid) {
+                return Err(OsApiError::AccessDenied(format!(
+                    "access denied for pid {pid}"
+                )));
+            }
             self.affinity.insert(pid, mask);
             self.affinity_sets.push((pid, mask));
             Ok(())
@@ -250,10 +445,33 @@ mod tests {
             pid: u32,
             priority: PriorityClass,
         ) -> Result<(), String> {
-            self.priority.insert(pid, priority);
             self.priority_sets.push((pid, priority));
+            if let (PriorityClass::Realtime, Some(fallback)) = (priority, self.realtime_fallback) {
+                self.priority.insert(pid, fallback);
+                if self.quiet_realtime_fallback {
+                    return Ok(());
+                }
+                return Err("realtime needs CAP_SYS_NICE".to_string());
+            }
+            self.priority.insert(pid, priority);
             Ok(())
         }
+
+        fn list_threads(&mut self, pid: u32) -> Result<Vec<ThreadInfo>, String> {
+            self.threads
+                .get(&pid)
+                .cloned()
+                .ok_or_else(|| format!("missing threads for pid {pid}"))
+        }
+
+        fn set_thread_affinity(
+            &mut self,
+            _pid: u32,
+            tid: u32,
+            mask: usize,
+        ) -> Result<usize, String> {
+            Ok(self.thread_affinity.insert(tid, mask).unwrap_or(usize::MAX))
+        }
     }
 
     fn groups_with_programs() -> Vec<CoreGroup> {
@@ -269,7 +487,16 @@ mod tests {
                     false,
                 )],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             },
             CoreGroup {
                 name: "Games".to_string(),
@@ -282,7 +509,16 @@ mod tests {
                     false,
                 )],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             },
         ]
     }
@@ -297,7 +533,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }
@@ -356,6 +608,14 @@ mod tests {
         assert!(os.affinity_sets.is_empty());
         assert!(os.priority_sets.is_empty());
         assert!(!apps.apps.get(&key).unwrap().settings_matched);
+        assert_eq!(
+            apps.apps.get(&key).unwrap().affinity_drift,
+            Some(AffinityDrift {
+                pid: 88,
+                actual_mask: 0b001,
+                expected_mask: 0b110,
+            })
+        );
     }
 
     #[test]
@@ -376,6 +636,168 @@ mod tests {
         assert_eq!(os.priority_sets, vec![(89, PriorityClass::High)]);
         assert_eq!(outcome.notifications.len(), 2);
         assert!(!apps.apps.get(&key).unwrap().settings_matched);
+        assert_eq!(apps.apps.get(&key).unwrap().affinity_drift, None);
+    }
+
+    #[test]
+    fn test_watched_app_is_corrected_while_monitoring_is_off() {
+        let mut state = sample_state();
+        state.groups[1].programs[0].watch_autoapply = true;
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 91, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(91, 0b001)]),
+            HashMap::from([(91, PriorityClass::Normal)]),
+        );
+
+        let outcome = process_settings_iteration_with_os(&mut apps, &state, false, &mut os);
+
+        assert_eq!(os.affinity_sets, vec![(91, 0b110)]);
+        assert_eq!(os.priority_sets, vec![(91, PriorityClass::High)]);
+        assert_eq!(outcome.notifications.len(), 2);
+        assert_eq!(apps.apps.get(&key).unwrap().affinity_drift, None);
+    }
+
+    #[test]
+    fn test_pinned_cores_replace_group_cores_for_monitoring() {
+        let state = sample_state();
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 90, group_id(1), rule_id(0));
+        apps.apps.get_mut(&key).unwrap().pinned_cores = Some(vec![2]);
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(90, 0b110)]),
+            HashMap::from([(90, PriorityClass::High)]),
+        );
+
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+
+        assert_eq!(os.affinity_sets, vec![(90, 0b100)]);
+        assert!(!apps.apps.get(&key).unwrap().settings_matched);
+    }
+
+    #[test]
+    fn test_spread_strategy_shapes_expected_mask() {
+        let mut state = sample_state();
+        state.groups[1].programs[0].spread = SpreadStrategy::Pack { count: 1 };
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 91, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(91, 0b110)]),
+            HashMap::from([(91, PriorityClass::High)]),
+        );
+
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+
+        assert_eq!(os.affinity_sets, vec![(91, 0b010)]);
+    }
+
+    #[test]
+    fn test_eco_qos_priority_matches_its_plain_class() {
+        let mut state = sample_state();
+        state.groups[1].programs[0].priority =
+            PriorityClass::from_parts(PriorityClassName::High, true);
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 92, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(92, 0b110)]),
+            HashMap::from([(92, PriorityClass::High)]),
+        );
+
+        let outcome = process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+
+        assert!(outcome.notifications.is_empty());
+        assert!(os.priority_sets.is_empty());
+        assert!(apps.apps.get(&key).unwrap().settings_matched);
+    }
+
+    #[test]
+    fn test_leftover_eco_mode_is_reset_to_the_plain_class() {
+        let state = sample_state();
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 92, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(92, 0b110)]),
+            HashMap::from([(92, PriorityClass::from_parts(PriorityClassName::High, true))]),
+        );
+
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+
+        assert_eq!(os.priority_sets, vec![(92, PriorityClass::High)]);
+    }
+
+    #[test]
+    fn test_refused_realtime_priority_is_not_requested_again() {
+        let mut state = sample_state();
+        state.groups[1].programs[0].priority = PriorityClass::Realtime;
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 92, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(92, 0b110)]),
+            HashMap::from([(92, PriorityClass::Normal)]),
+        );
+        os.realtime_fallback = Some(PriorityClass::High);
+
+        let first = process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert_eq!(first.notifications.len(), 1);
+        assert!(first.notifications[0].contains("keeping High"));
+
+        let second = process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert!(second.notifications.is_empty());
+        assert_eq!(os.priority_sets, vec![(92, PriorityClass::Realtime)]);
+        assert!(apps.apps.get(&key).unwrap().settings_matched);
+    }
+
+    #[test]
+    fn test_silently_lowered_realtime_priority_is_not_requested_again() {
+        let mut state = sample_state();
+        state.groups[1].programs[0].priority = PriorityClass::Realtime;
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 92, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(92, 0b110)]),
+            HashMap::from([(92, PriorityClass::Normal)]),
+        );
+        os.realtime_fallback = Some(PriorityClass::High);
+        os.quiet_realtime_fallback = true;
+
+        let first = process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert_eq!(first.notifications.len(), 1);
+        assert!(first.notifications[0].contains("keeping High"));
+
+        let second = process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert!(second.notifications.is_empty());
+        assert_eq!(os.priority_sets, vec![(92, PriorityClass::Realtime)]);
+    }
+
+    #[test]
+    fn test_priority_fallback_is_dropped_once_the_rule_asks_for_another_class() {
+        let state = sample_state();
+        let key = state.groups[1].programs[0].get_key();
+        let requested = state.groups[1].programs[0].priority;
+        assert_ne!(requested, PriorityClass::Realtime);
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 92, group_id(1), rule_id(0));
+        apps.apps
+            .get_mut(&key)
+            .unwrap()
+            .priority_fallbacks
+            .insert(92, (PriorityClass::Realtime, PriorityClass::Idle));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(92, 0b110)]),
+            HashMap::from([(92, PriorityClass::Idle)]),
+        );
+
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+
+        assert_eq!(os.priority_sets, vec![(92, requested)]);
+        assert!(apps.apps[&key].priority_fallbacks.is_empty());
     }
 
     #[test]
@@ -399,6 +821,102 @@ mod tests {
         assert!(apps.apps.get(&key).unwrap().settings_matched);
     }
 
+    #[test]
+    fn test_children_are_constrained_once_and_unreachable_pids_are_left_alone() {
+        let state = sample_state();
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 93, group_id(1), rule_id(0));
+        apps.apps.get_mut(&key).unwrap().pids.extend([94, 95, 96]);
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(93, 0b110), (94, 0b001), (96, 0b001)]),
+            HashMap::from([
+                (93, PriorityClass::High),
+                (94, PriorityClass::High),
+                (96, PriorityClass::High),
+            ]),
+        );
+        os.denied.push(96);
+
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert_eq!(os.affinity_sets, vec![(94, 0b110)]);
+        let app = apps.apps.get(&key).unwrap();
+        assert_eq!(app.unreachable_pids.len(), 2);
+        assert!(app.unreachable_pids.contains(&95) && app.unreachable_pids.contains(&96));
+
+        os.affinity_reads.clear();
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert_eq!(os.affinity_reads, vec![93, 95, 96]);
+        assert_eq!(os.affinity_sets, vec![(94, 0b110)]);
+        let app = apps.apps.get(&key).unwrap();
+        assert!(!app.settings_matched);
+        assert_eq!(
+            app.affinity_drift,
+            Some(AffinityDrift {
+                pid: 96,
+                actual_mask: 0b001,
+                expected_mask: 0b110,
+            })
+        );
+
+        apps.apps.get_mut(&key).unwrap().pinned_cores = Some(vec![2]);
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert_eq!(
+            os.affinity_sets,
+            vec![(94, 0b110), (93, 0b100), (94, 0b100)]
+        );
+
+        apps.apps
+            .get_mut(&key)
+            .unwrap()
+            .pids
+            .retain(|&pid| pid != 95);
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert!(!apps.apps.get(&key).unwrap().unreachable_pids.contains(&95));
+    }
+
+    #[test]
+    fn test_denied_main_process_is_retried_on_every_pass() {
+        let state = sample_state();
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 97, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(97, 0b001)]),
+            HashMap::from([(97, PriorityClass::High)]),
+        );
+        os.denied.push(97);
+
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert!(apps.apps.get(&key).unwrap().unreachable_pids.is_empty());
+
+        os.denied.clear();
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert_eq!(os.affinity_sets, vec![(97, 0b110)]);
+    }
+
+    #[test]
+    fn test_monitor_task_exits_once_shutdown_is_signalled() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_time()
+            .build()
+            .unwrap();
+        let (shutdown_tx, shutdown_rx) = tokio::sync::watch::channel(false);
+        let (monitor_tx, _monitor_rx) = std::sync::mpsc::channel();
+        let task = runtime.spawn(run_process_settings_monitor(
+            Arc::new(TokioRwLock::new(RunningApps::default())),
+            Arc::new(RwLock::new(sample_state())),
+            monitor_tx,
+            shutdown_rx,
+        ));
+
+        shutdown_tx.send_replace(true);
+        let finished =
+            runtime.block_on(async { tokio::time::timeout(Duration::from_secs(5), task).await });
+
+        assert!(matches!(finished, Ok(Ok(()))));
+    }
+
     #[test]
     fn test_matched_settings_produce_no_notifications() {
         let state = sample_state();
@@ -416,4 +934,82 @@ mod tests {
         assert!(outcome.notifications.is_empty());
         assert!(apps.apps.get(&key).unwrap().settings_matched);
     }
+
+    #[test]
+    fn test_thread_rules_pin_matching_threads_within_group_cores() {
+        let mut state = sample_state();
+        state.groups[1].programs[0].thread_affinity_rules = vec![
+            ThreadRule {
+                matcher: ThreadMatch::Name("render".to_string()),
+                cores: vec![1, 5],
+            },
+            ThreadRule {
+                matcher: ThreadMatch::Index(7),
+                cores: vec![2],
+            },
+        ];
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 93, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(93, 0b110)]),
+            HashMap::from([(93, PriorityClass::High)]),
+        );
+        let thread = |tid: u32, name: &str| ThreadInfo {
+            tid,
+            name: name.to_string(),
+        };
+        os.threads.insert(
+            93,
+            vec![
+                thread(10, "Main"),
+                thread(11, "RenderThread"),
+                thread(12, "Worker"),
+            ],
+        );
+
+        let first = process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert_eq!(os.thread_affinity, HashMap::from([(11, 0b010)]));
+        assert_eq!(first.notifications.len(), 1);
+
+        let second = process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+        assert!(second.notifications.is_empty());
+
+        os.thread_affinity.clear();
+        process_settings_iteration_with_os(&mut apps, &state, false, &mut os);
+        assert!(os.thread_affinity.is_empty());
+    }
+
+    #[test]
+    fn test_thread_rules_leave_the_main_thread_to_the_process_mask() {
+        let mut state = sample_state();
+        state.groups[1].programs[0].thread_affinity_rules = vec![ThreadRule {
+            matcher: ThreadMatch::Name("game".to_string()),
+            cores: vec![1],
+        }];
+        let key = state.groups[1].programs[0].get_key();
+        let mut apps = RunningApps::default();
+        apps.add_app(&key, 93, group_id(1), rule_id(0));
+        let mut os = FakeProcessSettingsOs::new(
+            HashMap::from([(93, 0b110)]),
+            HashMap::from([(93, PriorityClass::High)]),
+        );
+        os.threads.insert(
+            93,
+            vec![
+                ThreadInfo {
+                    tid: 93,
+                    name: "game".to_string(),
+                },
+                ThreadInfo {
+                    tid: 94,
+                    name: "game".to_string(),
+                },
+            ],
+        );
+
+        process_settings_iteration_with_os(&mut apps, &state, true, &mut os);
+
+        assert_eq!(os.thread_affinity, HashMap::from([(94, 0b010)]));
+    }
 }
@@ -1,17 +1,26 @@
-use crate::app::models::{AppRuntimeKey, AppStatus, RunningApps};
+use super::launch::{BarrierWait, ScheduledAutorun};
+use crate::app::models::{AffinityDrift, AppRuntimeKey, AppStatus, RunningApp, RunningApps};
 use crate::app::shared::ids::{GroupId, RuleId};
 use crate::app::shell::events::ShellEvent;
 use os_api::InstalledPackageRuntimeInfo;
-use std::collections::HashMap;
-use std::sync::mpsc::Receiver;
+use std::collections::{HashMap, VecDeque};
+use std::sync::mpsc::{self, Receiver, Sender};
 use std::sync::{Arc, RwLock};
-use tokio::sync::RwLock as TokioRwLock;
+use std::time::{Duration, SystemTime};
+use tokio::sync::{watch, RwLock as TokioRwLock};
+
+/// Reads a launch makes before treating the running-app registry as busy; the
+/// monitors only hold its write lock for one short pass.
+const LAUNCH_LOOKUP_ATTEMPTS: u32 = 5;
+const LAUNCH_LOOKUP_RETRY_DELAY: Duration = Duration::from_millis(10);
 
 #[derive(Default)]
 pub struct ExecutionStore {
     running_apps: Arc<TokioRwLock<RunningApps>>,
     installed_package_tracking: Arc<RwLock<InstalledPackageTrackingState>>,
     running_apps_statuses: HashMap<AppRuntimeKey, AppStatus>,
+    /// Error of each app's last launch when it failed, cleared by a successful one.
+    launch_failures: RwLock<HashMap<AppRuntimeKey, String>>,
 }
 
 #[derive(Debug, Default)]
@@ -20,9 +29,29 @@ pub(crate) struct InstalledPackageTrackingState {
     package_owner_by_family: HashMap<String, AppRuntimeKey>,
 }
 
+/// How far a group's queued launches have got, shown on its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LaunchProgress {
+    pub started: usize,
+    pub total: usize,
+}
+
 pub struct RuntimeRegistry {
     pub(crate) store: ExecutionStore,
     pub(crate) monitor_rx: Option<Receiver<ShellEvent>>,
+    /// Results of background launch checks, drained with the monitor events.
+    pub(crate) launch_event_rx: Receiver<ShellEvent>,
+    launch_event_tx: Sender<ShellEvent>,
+    /// Queued launches still waiting out their delay or a launch barrier, soonest
+    /// first: autorun apps, and Run all of groups with launch barriers.
+    pub(crate) pending_autorun: VecDeque<ScheduledAutorun>,
+    /// Launched app the queue waits on before it starts the next one.
+    pub(crate) launch_barrier: Option<BarrierWait>,
+    /// Progress of the queued launches by group index; a group is dropped once its
+    /// last app started or one of its launches failed.
+    launch_progress: HashMap<usize, LaunchProgress>,
+    /// Set once on exit; the background monitors stop when they see it.
+    monitor_shutdown: watch::Sender<bool>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -61,6 +90,7 @@ impl ExecutionStore {
                 InstalledPackageTrackingState::default(),
             )),
             running_apps_statuses: HashMap::new(),
+            launch_failures: RwLock::new(HashMap::new()),
         }
     }
 
@@ -103,6 +133,68 @@ impl ExecutionStore {
         }
     }
 
+    pub(crate) fn move_running_app_to_group(
+        &self,
+        app_key: &AppRuntimeKey,
+        group_id: GroupId,
+    ) -> RunningAppSettingsUpdate {
+        self.update_running_app(app_key, |app| {
+            app.group_id = group_id.clone();
+            app.pinned_cores = None;
+        })
+    }
+
+    pub(crate) fn set_running_app_cores(
+        &self,
+        app_key: &AppRuntimeKey,
+        cores: Vec<usize>,
+    ) -> RunningAppSettingsUpdate {
+        self.update_running_app(app_key, |app| app.pinned_cores = Some(cores.clone()))
+    }
+
+    /// Applies `update` to a tracked app, waiting out a brief monitor write before
+    /// reporting `Busy` so the change is not silently lost.
+    fn update_running_app(
+        &self,
+        app_key: &AppRuntimeKey,
+        mut update: impl FnMut(&mut RunningApp),
+    ) -> RunningAppSettingsUpdate {
+        for attempt in 1..=LAUNCH_LOOKUP_ATTEMPTS {
+            if let Ok(mut apps) = self.running_apps.try_write() {
+                return match apps.apps.get_mut(app_key) {
+                    Some(app) => {
+                        update(app);
+                        RunningAppSettingsUpdate::Updated
+                    }
+                    None => RunningAppSettingsUpdate::NotFound,
+                };
+            }
+            if attempt < LAUNCH_LOOKUP_ATTEMPTS {
+                std::thread::sleep(LAUNCH_LOOKUP_RETRY_DELAY);
+            }
+        }
+        RunningAppSettingsUpdate::Busy
+    }
+
+    /// Carries a group's tracked instance and its status over to the app's new key.
+    pub fn rekey_running_app(
+        &mut self,
+        from: &AppRuntimeKey,
+        to: &AppRuntimeKey,
+        group_id: &GroupId,
+    ) -> bool {
+        let moved = match self.running_apps.try_write() {
+            Ok(mut apps) => apps.rekey_app(from, to, group_id),
+            Err(_) => false,
+        };
+        if moved {
+            if let Some(status) = self.running_apps_statuses.remove(from) {
+                self.running_apps_statuses.insert(to.clone(), status);
+            }
+        }
+        moved
+    }
+
     pub fn contains_app(&self, app_key: &AppRuntimeKey) -> bool {
         self.running_apps
             .try_read()
@@ -126,6 +218,30 @@ impl ExecutionStore {
         }
     }
 
+    pub fn release_running_app(&mut self, app_key: &AppRuntimeKey) -> bool {
+        match self.running_apps.try_write() {
+            Ok(mut apps) => {
+                apps.release_app(app_key);
+                self.running_apps_statuses
+                    .insert(app_key.clone(), AppStatus::NotRunning);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    pub fn remove_running_app(&mut self, app_key: &AppRuntimeKey) -> bool {
+        match self.running_apps.try_write() {
+            Ok(mut apps) => {
+                apps.remove_app(app_key);
+                self.running_apps_statuses
+                    .insert(app_key.clone(), AppStatus::NotRunning);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
     pub fn get_app_status_sync(&mut self, app_key: &AppRuntimeKey) -> AppStatus {
         if let Ok(apps) = self.running_apps.try_read() {
             let status = if let Some(app) = apps.apps.get(app_key) {
@@ -147,6 +263,21 @@ impl ExecutionStore {
         }
     }
 
+    pub fn affinity_drift(&self, app_key: &AppRuntimeKey) -> Option<AffinityDrift> {
+        self.running_apps
+            .try_read()
+            .ok()
+            .and_then(|apps| apps.apps.get(app_key).and_then(|app| app.affinity_drift))
+    }
+
+    /// Per-core usage last sampled by the running-app monitor, if it is available.
+    pub(crate) fn core_usage(&self) -> Option<Vec<f32>> {
+        self.running_apps
+            .try_read()
+            .ok()
+            .and_then(|apps| apps.core_usage.clone())
+    }
+
     pub(crate) fn lookup_running_app_pids(&self, app_key: &AppRuntimeKey) -> RunningAppPidsLookup {
         match self.running_apps.try_read() {
             Ok(apps) => apps
@@ -159,6 +290,21 @@ impl ExecutionStore {
         }
     }
 
+    /// Like `lookup_running_app_pids`, but waits out a brief monitor write before
+    /// reporting `Busy`, so a launch neither duplicates a running app nor fails spuriously.
+    pub(crate) fn lookup_running_app_pids_for_launch(
+        &self,
+        app_key: &AppRuntimeKey,
+    ) -> RunningAppPidsLookup {
+        for _ in 1..LAUNCH_LOOKUP_ATTEMPTS {
+            match self.lookup_running_app_pids(app_key) {
+                RunningAppPidsLookup::Busy => std::thread::sleep(LAUNCH_LOOKUP_RETRY_DELAY),
+                settled => return settled,
+            }
+        }
+        self.lookup_running_app_pids(app_key)
+    }
+
     pub fn get_running_app_pids(&self, app_key: &AppRuntimeKey) -> Option<Vec<u32>> {
         match self.lookup_running_app_pids(app_key) {
             RunningAppPidsLookup::Found(pids) => Some(pids),
@@ -166,6 +312,25 @@ impl ExecutionStore {
         }
     }
 
+    fn set_launch_failure(&self, app_key: &AppRuntimeKey, error: Option<String>) {
+        let mut failures = self.launch_failures.write().unwrap();
+        match error {
+            Some(error) => failures.insert(app_key.clone(), error),
+            None => failures.remove(app_key),
+        };
+    }
+
+    fn launch_failure(&self, app_key: &AppRuntimeKey) -> Option<String> {
+        self.launch_failures.read().unwrap().get(app_key).cloned()
+    }
+
+    pub fn get_running_app_started_at(&self, app_key: &AppRuntimeKey) -> Option<SystemTime> {
+        self.running_apps
+            .try_read()
+            .ok()
+            .and_then(|apps| apps.apps.get(app_key).map(|app| app.created_at))
+    }
+
     pub(crate) fn mark_running_app_settings_matched(
         &mut self,
         app_key: &AppRuntimeKey,
@@ -193,6 +358,9 @@ impl ExecutionStore {
             Ok(mut apps) => match apps.apps.get_mut(app_key) {
                 Some(app) => {
                     app.settings_matched = settings_matched;
+                    if settings_matched {
+                        app.affinity_drift = None;
+                    }
                     RunningAppSettingsUpdate::Updated
                 }
                 None => RunningAppSettingsUpdate::NotFound,
@@ -212,10 +380,72 @@ impl ExecutionStore {
 
 impl RuntimeRegistry {
     pub fn new() -> Self {
+        let (launch_event_tx, launch_event_rx) = mpsc::channel();
         Self {
             store: ExecutionStore::new(),
             monitor_rx: None,
+            launch_event_rx,
+            launch_event_tx,
+            pending_autorun: VecDeque::new(),
+            launch_barrier: None,
+            launch_progress: HashMap::new(),
+            monitor_shutdown: watch::channel(false).0,
+        }
+    }
+
+    pub fn monitor_shutdown_handle(&self) -> watch::Receiver<bool> {
+        self.monitor_shutdown.subscribe()
+    }
+
+    /// Asks the background monitors to stop; they finish the pass they are in first.
+    pub fn stop_monitors(&self) {
+        self.monitor_shutdown.send_replace(true);
+    }
+
+    /// Replaces the launch queue and starts counting each group's launches in it.
+    pub(crate) fn queue_launches(&mut self, queue: VecDeque<ScheduledAutorun>) {
+        self.launch_progress.clear();
+        self.pending_autorun.clear();
+        self.launch_barrier = None;
+        self.append_launches(queue);
+    }
+
+    /// Adds launches behind those already queued and counts them in their groups'
+    /// progress.
+    pub(crate) fn append_launches(&mut self, queue: VecDeque<ScheduledAutorun>) {
+        for item in &queue {
+            self.launch_progress
+                .entry(item.group_index)
+                .or_insert(LaunchProgress {
+                    started: 0,
+                    total: 0,
+                })
+                .total += 1;
         }
+        self.pending_autorun.extend(queue);
+    }
+
+    /// Counts a queued launch of the group as done; a failed one ends its progress.
+    pub(crate) fn record_queued_launch(&mut self, group_index: usize, failed: bool) {
+        let finished = match self.launch_progress.get_mut(&group_index) {
+            Some(_) if failed => true,
+            Some(progress) => {
+                progress.started += 1;
+                progress.started >= progress.total
+            }
+            None => false,
+        };
+        if finished {
+            self.launch_progress.remove(&group_index);
+        }
+    }
+
+    pub fn launch_progress(&self, group_index: usize) -> Option<LaunchProgress> {
+        self.launch_progress.get(&group_index).copied()
+    }
+
+    pub(crate) fn launch_event_sender(&self) -> Sender<ShellEvent> {
+        self.launch_event_tx.clone()
     }
 
     pub fn running_apps_handle(&self) -> Arc<TokioRwLock<RunningApps>> {
@@ -248,6 +478,34 @@ impl RuntimeRegistry {
         self.store.add_running_app(app_key, pid, group_id, rule_id)
     }
 
+    pub fn rekey_running_app(
+        &mut self,
+        from: &AppRuntimeKey,
+        to: &AppRuntimeKey,
+        group_id: &GroupId,
+    ) -> bool {
+        self.store.rekey_running_app(from, to, group_id)
+    }
+
+    /// Keeps the app on `cores` chosen at launch instead of its group's cores.
+    pub(crate) fn set_running_app_cores(
+        &self,
+        app_key: &AppRuntimeKey,
+        cores: Vec<usize>,
+    ) -> RunningAppSettingsUpdate {
+        self.store.set_running_app_cores(app_key, cores)
+    }
+
+    /// Points a tracked app at the group its rule was moved to. Cores picked at launch
+    /// from the old group are dropped, so the new group's cores apply.
+    pub(crate) fn move_running_app_to_group(
+        &self,
+        app_key: &AppRuntimeKey,
+        group_id: GroupId,
+    ) -> RunningAppSettingsUpdate {
+        self.store.move_running_app_to_group(app_key, group_id)
+    }
+
     pub fn contains_app(&self, app_key: &AppRuntimeKey) -> bool {
         self.store.contains_app(app_key)
     }
@@ -256,6 +514,15 @@ impl RuntimeRegistry {
         self.store.add_pid_to_existing_app(app_key, pid)
     }
 
+    pub fn remove_running_app(&mut self, app_key: &AppRuntimeKey) -> bool {
+        self.store.remove_running_app(app_key)
+    }
+
+    /// Stops tracking the app; monitors skip its processes until they exit.
+    pub fn release_running_app(&mut self, app_key: &AppRuntimeKey) -> bool {
+        self.store.release_running_app(app_key)
+    }
+
     pub fn get_app_status_sync(&mut self, app_key: &AppRuntimeKey) -> AppStatus {
         self.store.get_app_status_sync(app_key)
     }
@@ -264,10 +531,40 @@ impl RuntimeRegistry {
         self.store.get_running_app_pids(app_key)
     }
 
+    /// Live affinity the monitor last saw differ from the app's intended mask.
+    pub fn affinity_drift(&self, app_key: &AppRuntimeKey) -> Option<AffinityDrift> {
+        self.store.affinity_drift(app_key)
+    }
+
+    pub fn get_running_app_started_at(&self, app_key: &AppRuntimeKey) -> Option<SystemTime> {
+        self.store.get_running_app_started_at(app_key)
+    }
+
+    /// Records the outcome of a launch: the error when it failed, `None` when it started.
+    pub(crate) fn set_launch_failure(&self, app_key: &AppRuntimeKey, error: Option<String>) {
+        self.store.set_launch_failure(app_key, error);
+    }
+
+    /// Why the app's last launch failed, until it starts successfully.
+    pub fn launch_failure(&self, app_key: &AppRuntimeKey) -> Option<String> {
+        self.store.launch_failure(app_key)
+    }
+
+    pub(crate) fn core_usage(&self) -> Option<Vec<f32>> {
+        self.store.core_usage()
+    }
+
     pub(crate) fn lookup_running_app_pids(&self, app_key: &AppRuntimeKey) -> RunningAppPidsLookup {
         self.store.lookup_running_app_pids(app_key)
     }
 
+    pub(crate) fn lookup_running_app_pids_for_launch(
+        &self,
+        app_key: &AppRuntimeKey,
+    ) -> RunningAppPidsLookup {
+        self.store.lookup_running_app_pids_for_launch(app_key)
+    }
+
     pub(crate) fn mark_running_app_settings_matched(
         &mut self,
         app_key: &AppRuntimeKey,
@@ -491,4 +788,28 @@ mod tests {
         );
         assert_eq!(store.get_app_status_sync(&key), AppStatus::SettingsMismatch);
     }
+
+    #[test]
+    fn test_pinned_cores_report_a_busy_registry_instead_of_dropping_silently() {
+        let store = ExecutionStore::new();
+        let key = installed_app("Sample", "Pkg!App", PriorityClass::Normal).get_key();
+        assert!(store.add_running_app(&key, 42, group_id(0), rule_id(0)));
+
+        let running_apps = store.running_apps_handle();
+        let write_guard = running_apps.try_write().unwrap();
+        assert_eq!(
+            store.set_running_app_cores(&key, vec![2]),
+            RunningAppSettingsUpdate::Busy
+        );
+        drop(write_guard);
+
+        assert_eq!(
+            store.set_running_app_cores(&key, vec![2]),
+            RunningAppSettingsUpdate::Updated
+        );
+        assert_eq!(
+            running_apps.try_read().unwrap().apps[&key].pinned_cores,
+            Some(vec![2])
+        );
+    }
 }
@@ -1,7 +1,7 @@
 use crate::app::features::execution::{
     cleanup_orphaned_package_owners, ensure_package_owner_claim,
-    is_excluded_installed_auto_process, resolve_installed_package_runtime_info_cached,
-    InstalledPackageTrackingState,
+    is_excluded_installed_auto_process, is_shell_host_process,
+    resolve_installed_package_runtime_info_cached, InstalledPackageTrackingState,
 };
 use crate::app::features::rules::RulesContext;
 use crate::app::models::{
@@ -10,10 +10,11 @@ use crate::app::models::{
 use crate::app::shared::ids::{GroupId, RuleId};
 use crate::app::shell::events::ShellEvent;
 use os_api::{InstalledPackageRuntimeInfo, OS};
+use regex::Regex;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
-use tokio::sync::RwLock as TokioRwLock;
+use tokio::sync::{watch, RwLock as TokioRwLock};
 
 #[derive(Debug, Clone, Default)]
 struct ProcessSnapshot {
@@ -27,6 +28,9 @@ enum ConfiguredProgramMatcher {
         primary_name: Option<String>,
         fallback_names: Vec<String>,
         bin_path: PathBuf,
+        /// Resolved file of an AppImage rule; its payload runs from a temporary mount
+        /// under another name, so it is matched by the `APPIMAGE` variable instead.
+        appimage: Option<PathBuf>,
     },
     Installed {
         aumid: String,
@@ -44,15 +48,39 @@ struct ConfiguredProgramSnapshot {
     key: AppRuntimeKey,
     display_name: String,
     additional_processes_normalized: Vec<String>,
+    /// Processes whose whole name matches are tracked like explicit names.
+    name_pattern: Option<Regex>,
     matcher: ConfiguredProgramMatcher,
+    /// Normalized names of child processes the group leaves at the default affinity.
+    excluded_names: Vec<String>,
     group_id: GroupId,
     rule_id: RuleId,
 }
 
+/// A child process kept out of tracking by its group's exclusion list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ExcludedProcess {
+    pid: u32,
+    name: String,
+    app: String,
+}
+
+/// Lookups built once per snapshot and shared by every configured program.
+#[derive(Debug, Default)]
+struct ProcessLookups {
+    name_to_pids: HashMap<String, Vec<u32>>,
+    aumid_to_seed_pids: HashMap<String, Vec<u32>>,
+    appimage_to_pids: HashMap<PathBuf, Vec<u32>>,
+}
+
 #[derive(Debug, Default, PartialEq, Eq)]
 struct RunningAppsIterationOutcome {
     changed: bool,
     notifications: Vec<String>,
+    /// Tracked apps whose previous PIDs all exited while new ones took over.
+    restarted: Vec<AppRuntimeKey>,
+    /// Processes left out because their group excludes them, on every pass they are seen.
+    excluded: Vec<ExcludedProcess>,
 }
 
 trait RunningAppsOs {
@@ -60,6 +88,7 @@ trait RunningAppsOs {
     fn get_process_image_path(&self, pid: u32) -> Result<PathBuf, String>;
     fn is_pid_live(&self, pid: u32) -> bool;
     fn get_process_app_user_model_id(&self, pid: u32) -> Result<Option<String>, String>;
+    fn get_process_appimage_path(&self, pid: u32) -> Result<Option<PathBuf>, String>;
     fn resolve_installed_package_runtime_info(
         &self,
         aumid: &str,
@@ -77,7 +106,7 @@ impl RunningAppsOs for RealRunningAppsOs {
     }
 
     fn get_process_image_path(&self, pid: u32) -> Result<PathBuf, String> {
-        OS::get_process_image_path(pid)
+        OS::get_process_image_path(pid).map_err(String::from)
     }
 
     fn is_pid_live(&self, pid: u32) -> bool {
@@ -85,7 +114,11 @@ impl RunningAppsOs for RealRunningAppsOs {
     }
 
     fn get_process_app_user_model_id(&self, pid: u32) -> Result<Option<String>, String> {
-        OS::get_process_app_user_model_id(pid)
+        OS::get_process_app_user_model_id(pid).map_err(String::from)
+    }
+
+    fn get_process_appimage_path(&self, pid: u32) -> Result<Option<PathBuf>, String> {
+        OS::get_process_appimage_path(pid).map_err(String::from)
     }
 
     fn resolve_installed_package_runtime_info(
@@ -101,14 +134,34 @@ pub async fn run_running_app_monitor(
     installed_package_tracking: Arc<RwLock<InstalledPackageTrackingState>>,
     app_state: Arc<RwLock<AppStateStorage>>,
     monitor_tx: std::sync::mpsc::Sender<ShellEvent>,
+    mut shutdown: watch::Receiver<bool>,
 ) {
     let mut interval = tokio::time::interval(std::time::Duration::from_secs(2));
     let os = RealRunningAppsOs;
+    // Excluded processes inherit the app's affinity when spawned, so each one is handed
+    // the default mask once.
+    let mut released_pids: HashSet<u32> = HashSet::new();
+    // Core times of the previous tick; least-loaded launches read the usage between
+    // ticks instead of sampling on the UI thread.
+    let mut previous_core_times: Option<Vec<(u64, u64)>> = None;
+    // Resolved files of AppImage rules by configured path, so the filesystem is only
+    // asked again when the configuration names a new one.
+    let mut appimage_paths: HashMap<PathBuf, PathBuf> = HashMap::new();
 
     loop {
-        interval.tick().await;
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown.wait_for(|&stop| stop) => break,
+        }
+
+        let core_times = OS::read_core_times().ok();
+        let core_usage = previous_core_times
+            .as_deref()
+            .zip(core_times.as_deref())
+            .map(|(before, after)| OS::core_usage_between(before, after));
+        previous_core_times = core_times;
 
-        let configured_programs = {
+        let (configured_programs, reset_runtime_on_restart) = {
             let state = match app_state.read() {
                 Ok(guard) => guard,
                 Err(_) => {
@@ -120,40 +173,62 @@ pub async fn run_running_app_monitor(
                 }
             };
 
-            collect_configured_programs(&state)
+            (
+                collect_configured_programs(&state, &mut appimage_paths),
+                state.reset_runtime_on_restart,
+            )
         };
 
+        // A failed process enumeration is non-fatal: skip this tick and keep the current
+        // tracking rather than treating every app as stopped.
         let snapshot = match os.snapshot_process_tree() {
             Ok(snapshot) => snapshot,
             Err(_) => continue,
         };
-        let name_to_pids = build_name_to_pids(&snapshot);
-        let aumid_to_seed_pids = if configured_programs.iter().any(|configured| {
-            matches!(
-                configured.matcher,
-                ConfiguredProgramMatcher::Installed { .. }
-            )
-        }) {
-            build_aumid_to_seed_pids(&snapshot, &os)
-        } else {
-            HashMap::new()
-        };
+        let lookups = build_process_lookups(&snapshot, &configured_programs, &os);
 
         if let Ok(mut apps) = running_apps.try_write() {
+            apps.core_usage = core_usage;
             let outcome = process_running_apps_iteration_with_os(
                 &mut apps,
                 configured_programs,
                 &snapshot,
-                &name_to_pids,
-                &aumid_to_seed_pids,
+                &lookups,
                 &installed_package_tracking,
                 &os,
             );
 
+            if reset_runtime_on_restart {
+                for key in &outcome.restarted {
+                    apps.restart_clock(key);
+                }
+            }
+
             for message in outcome.notifications {
                 let _ = monitor_tx.send(ShellEvent::Monitor(message));
             }
 
+            released_pids.retain(|pid| snapshot.names.contains_key(pid));
+            for excluded in outcome.excluded {
+                if !released_pids.insert(excluded.pid) {
+                    continue;
+                }
+                let message = match OS::set_process_affinity_by_pid(
+                    excluded.pid,
+                    OS::available_core_mask(),
+                ) {
+                    Ok(()) => format!(
+                        "Left {} (PID {}) of {} at the default affinity",
+                        excluded.name, excluded.pid, excluded.app
+                    ),
+                    Err(err) => format!(
+                        "Couldn't reset the affinity of {} (PID {}) of {}: {err}",
+                        excluded.name, excluded.pid, excluded.app
+                    ),
+                };
+                let _ = monitor_tx.send(ShellEvent::Monitor(message));
+            }
+
             if outcome.changed {
                 let _ = monitor_tx.send(ShellEvent::RuntimeStateChanged);
             }
@@ -161,20 +236,44 @@ pub async fn run_running_app_monitor(
     }
 }
 
-fn collect_configured_programs(state: &AppStateStorage) -> Vec<ConfiguredProgramSnapshot> {
+/// Matchers for every rule the monitor tracks. `appimage_paths` carries resolved
+/// AppImage files between calls and is left holding only the ones still configured.
+fn collect_configured_programs(
+    state: &AppStateStorage,
+    appimage_paths: &mut HashMap<PathBuf, PathBuf>,
+) -> Vec<ConfiguredProgramSnapshot> {
     let mut programs = Vec::new();
+    let mut resolved_appimages = HashMap::new();
     let rules = RulesContext::from_storage(state);
     let snapshot = rules.snapshot(state);
 
     for group in snapshot.groups {
         for program in group.rules {
+            let name_pattern = program.app.match_pattern_regex();
             let matcher = match &program.app.launch_target {
                 LaunchTarget::Path { bin_path, .. } => {
                     let tracked_names = collect_tracked_process_names(&program.app);
-                    if tracked_names.is_empty() {
+                    let appimage = is_appimage(bin_path).then(|| {
+                        resolved_appimages
+                            .entry(bin_path.clone())
+                            .or_insert_with(|| {
+                                appimage_paths.remove(bin_path).unwrap_or_else(|| {
+                                    std::fs::canonicalize(bin_path).unwrap_or(bin_path.clone())
+                                })
+                            })
+                            .clone()
+                    });
+                    let primary_name = program.app.primary_process_name_normalized();
+                    // Watched apps are found by their own executable even when its name
+                    // was removed from the tracked processes.
+                    let watch_primary = program.app.watch_autoapply && primary_name.is_some();
+                    if tracked_names.is_empty()
+                        && name_pattern.is_none()
+                        && appimage.is_none()
+                        && !watch_primary
+                    {
                         continue;
                     }
-                    let primary_name = program.app.primary_process_name_normalized();
                     let fallback_names = tracked_names
                         .iter()
                         .filter(|name| Some(*name) != primary_name.as_ref())
@@ -182,10 +281,12 @@ fn collect_configured_programs(state: &AppStateStorage) -> Vec<ConfiguredProgram
                         .collect();
 
                     ConfiguredProgramMatcher::Path {
-                        primary_name: primary_name
-                            .filter(|primary| tracked_names.iter().any(|name| name == primary)),
+                        primary_name: primary_name.filter(|primary| {
+                            watch_primary || tracked_names.iter().any(|name| name == primary)
+                        }),
                         fallback_names,
                         bin_path: bin_path.clone(),
+                        appimage,
                     }
                 }
                 LaunchTarget::Installed { aumid } => ConfiguredProgramMatcher::Installed {
@@ -197,20 +298,38 @@ fn collect_configured_programs(state: &AppStateStorage) -> Vec<ConfiguredProgram
                 key: program.app.get_key(),
                 display_name: program.app.name.clone(),
                 additional_processes_normalized: collect_tracked_process_names(&program.app),
+                name_pattern,
                 matcher,
+                excluded_names: group
+                    .exclude_names
+                    .iter()
+                    .map(|name| normalize_process_name(name))
+                    .filter(|name| !name.is_empty())
+                    .collect(),
                 group_id: group.id.clone(),
                 rule_id: program.id,
             });
         }
     }
 
+    *appimage_paths = resolved_appimages;
     programs
 }
 
+fn is_appimage(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("appimage"))
+}
+
 fn collect_tracked_process_names(program: &AppToRun) -> Vec<String> {
     let mut names = Vec::new();
 
     for process_name in &program.additional_processes {
+        // Shell hosts would capture every file the desktop opens, not just this one.
+        if program.use_shell_execute && is_shell_host_process(process_name) {
+            continue;
+        }
         push_process_name(&mut names, process_name);
     }
 
@@ -237,6 +356,56 @@ fn build_name_to_pids(snapshot: &ProcessSnapshot) -> HashMap<String, Vec<u32>> {
     name_to_pids
 }
 
+/// Builds the lookups; the per-process identity sweeps only run when a configured
+/// program needs them.
+fn build_process_lookups<O: RunningAppsOs>(
+    snapshot: &ProcessSnapshot,
+    configured_programs: &[ConfiguredProgramSnapshot],
+    os: &O,
+) -> ProcessLookups {
+    let needs_aumids = configured_programs
+        .iter()
+        .any(|program| matches!(program.matcher, ConfiguredProgramMatcher::Installed { .. }));
+    let needs_appimages = configured_programs.iter().any(|program| {
+        matches!(
+            program.matcher,
+            ConfiguredProgramMatcher::Path {
+                appimage: Some(_),
+                ..
+            }
+        )
+    });
+
+    ProcessLookups {
+        name_to_pids: build_name_to_pids(snapshot),
+        aumid_to_seed_pids: if needs_aumids {
+            build_aumid_to_seed_pids(snapshot, os)
+        } else {
+            HashMap::new()
+        },
+        appimage_to_pids: if needs_appimages {
+            build_appimage_to_pids(snapshot, os)
+        } else {
+            HashMap::new()
+        },
+    }
+}
+
+fn build_appimage_to_pids<O: RunningAppsOs>(
+    snapshot: &ProcessSnapshot,
+    os: &O,
+) -> HashMap<PathBuf, Vec<u32>> {
+    let mut appimage_to_pids: HashMap<PathBuf, Vec<u32>> = HashMap::new();
+
+    for &pid in snapshot.names.keys() {
+        if let Ok(Some(appimage)) = os.get_process_appimage_path(pid) {
+            appimage_to_pids.entry(appimage).or_default().push(pid);
+        }
+    }
+
+    appimage_to_pids
+}
+
 fn build_aumid_to_seed_pids<O: RunningAppsOs>(
     snapshot: &ProcessSnapshot,
     os: &O,
@@ -395,6 +564,34 @@ fn collect_named_process_pids(
     tracked_pids
 }
 
+fn collect_pattern_pids(pattern: Option<&Regex>, snapshot: &ProcessSnapshot) -> Vec<u32> {
+    let Some(pattern) = pattern else {
+        return Vec::new();
+    };
+    let mut pids: Vec<u32> = snapshot
+        .names
+        .iter()
+        .filter(|(_, name)| pattern.is_match(name))
+        .map(|(&pid, _)| pid)
+        .collect();
+    pids.sort_unstable();
+    pids
+}
+
+/// Tracked names plus pattern matches; these bypass installed-app auto filtering.
+fn collect_explicit_pids(
+    configured: &ConfiguredProgramSnapshot,
+    name_to_pids: &HashMap<String, Vec<u32>>,
+    snapshot: &ProcessSnapshot,
+) -> Vec<u32> {
+    let mut pids =
+        collect_named_process_pids(&configured.additional_processes_normalized, name_to_pids);
+    for pid in collect_pattern_pids(configured.name_pattern.as_ref(), snapshot) {
+        push_unique_pid(&mut pids, pid);
+    }
+    pids
+}
+
 fn push_unique_pid(tracked_pids: &mut Vec<u32>, pid: u32) {
     if !tracked_pids.contains(&pid) {
         tracked_pids.push(pid);
@@ -441,6 +638,40 @@ fn extend_with_descendants(snapshot: &ProcessSnapshot, tracked_pids: &mut Vec<u3
     }
 }
 
+/// Removes processes whose name the group excludes and returns them.
+fn take_excluded_pids(
+    snapshot: &ProcessSnapshot,
+    excluded_names: &[String],
+    tracked_pids: &mut Vec<u32>,
+) -> Vec<u32> {
+    if excluded_names.is_empty() {
+        return Vec::new();
+    }
+    let is_excluded = |pid: &u32| {
+        snapshot.names.get(pid).is_some_and(|name| {
+            let name = normalize_process_name(name);
+            excluded_names.contains(&name)
+        })
+    };
+    let excluded: Vec<u32> = tracked_pids.iter().copied().filter(is_excluded).collect();
+    tracked_pids.retain(|pid| !excluded.contains(pid));
+    excluded
+}
+
+fn excluded_processes(
+    snapshot: &ProcessSnapshot,
+    pids: Vec<u32>,
+    app: &str,
+) -> Vec<ExcludedProcess> {
+    pids.into_iter()
+        .map(|pid| ExcludedProcess {
+            pid,
+            name: snapshot.names.get(&pid).cloned().unwrap_or_default(),
+            app: app.to_string(),
+        })
+        .collect()
+}
+
 fn retain_live_pids<O: RunningAppsOs>(tracked_pids: &mut Vec<u32>, os: &O) {
     tracked_pids.retain(|&pid| os.is_pid_live(pid));
 }
@@ -449,29 +680,57 @@ fn process_running_apps_iteration_with_os<O: RunningAppsOs>(
     apps: &mut RunningApps,
     configured_programs: Vec<ConfiguredProgramSnapshot>,
     snapshot: &ProcessSnapshot,
-    name_to_pids: &HashMap<String, Vec<u32>>,
-    aumid_to_seed_pids: &HashMap<String, Vec<u32>>,
+    lookups: &ProcessLookups,
     installed_package_tracking: &Arc<RwLock<InstalledPackageTrackingState>>,
     os: &O,
 ) -> RunningAppsIterationOutcome {
+    let name_to_pids = &lookups.name_to_pids;
     let mut processed_keys = HashSet::new();
     let mut outcome = RunningAppsIterationOutcome::default();
+    apps.released_pids.retain(|&pid| os.is_pid_live(pid));
+    let released = apps.released_pids.clone();
 
     for configured in configured_programs {
         let key = configured.key.clone();
         processed_keys.insert(key.clone());
         let was_tracked = apps.apps.contains_key(&key);
+        // A process belongs to one entry: rules sharing process names, such as copies of
+        // one rule in several groups, leave PIDs another entry already tracks alone.
+        let claimed_elsewhere: HashSet<u32> = apps
+            .apps
+            .iter()
+            .filter(|(other_key, _)| **other_key != key)
+            .flat_map(|(_, app)| app.pids.iter().copied())
+            .collect();
 
         let mut detected_pids = match &configured.matcher {
-            ConfiguredProgramMatcher::Path { fallback_names, .. } => {
+            ConfiguredProgramMatcher::Path {
+                fallback_names,
+                appimage,
+                ..
+            } => {
                 let mut pids = collect_path_verified_pids(&configured.matcher, name_to_pids, os);
                 extend_with_named_processes(&mut pids, fallback_names, name_to_pids);
+                if let Some(appimage_pids) = appimage
+                    .as_ref()
+                    .and_then(|appimage| lookups.appimage_to_pids.get(appimage))
+                {
+                    for &pid in appimage_pids {
+                        push_unique_pid(&mut pids, pid);
+                    }
+                }
+                for pid in collect_pattern_pids(configured.name_pattern.as_ref(), snapshot) {
+                    push_unique_pid(&mut pids, pid);
+                }
                 pids
             }
-            ConfiguredProgramMatcher::Installed { aumid } => {
-                aumid_to_seed_pids.get(aumid).cloned().unwrap_or_default()
-            }
+            ConfiguredProgramMatcher::Installed { aumid } => lookups
+                .aumid_to_seed_pids
+                .get(aumid)
+                .cloned()
+                .unwrap_or_default(),
         };
+        detected_pids.retain(|pid| !claimed_elsewhere.contains(pid) && !released.contains(pid));
 
         let installed_package_info = match &configured.matcher {
             ConfiguredProgramMatcher::Installed { aumid }
@@ -523,10 +782,7 @@ fn process_running_apps_iteration_with_os<O: RunningAppsOs>(
                     extend_with_descendants(snapshot, &mut app.pids);
                 }
                 ConfiguredProgramMatcher::Installed { aumid } => {
-                    let explicit_pids = collect_named_process_pids(
-                        &configured.additional_processes_normalized,
-                        name_to_pids,
-                    );
+                    let explicit_pids = collect_explicit_pids(&configured, name_to_pids, snapshot);
                     let explicit_pid_set: HashSet<u32> = explicit_pids.iter().copied().collect();
                     let mut managed_pids = app.pids.clone();
 
@@ -572,10 +828,26 @@ fn process_running_apps_iteration_with_os<O: RunningAppsOs>(
                 }
             }
 
+            app.pids.retain(|pid| {
+                (old_pids.contains(pid) || !claimed_elsewhere.contains(pid))
+                    && !released.contains(pid)
+            });
             retain_live_pids(&mut app.pids, os);
+            let excluded = take_excluded_pids(snapshot, &configured.excluded_names, &mut app.pids);
+            outcome.excluded.extend(excluded_processes(
+                snapshot,
+                excluded,
+                &configured.display_name,
+            ));
 
             if app.pids != old_pids {
                 outcome.changed = true;
+                if !app.pids.is_empty()
+                    && !old_pids.is_empty()
+                    && !app.pids.iter().any(|pid| old_pids.contains(pid))
+                {
+                    outcome.restarted.push(key.clone());
+                }
             }
 
             if app.pids.is_empty() {
@@ -603,10 +875,7 @@ fn process_running_apps_iteration_with_os<O: RunningAppsOs>(
                 extend_with_descendants(snapshot, &mut detected_pids);
             }
             ConfiguredProgramMatcher::Installed { aumid } => {
-                let explicit_pids = collect_named_process_pids(
-                    &configured.additional_processes_normalized,
-                    name_to_pids,
-                );
+                let explicit_pids = collect_explicit_pids(&configured, name_to_pids, snapshot);
                 let explicit_pid_set: HashSet<u32> = explicit_pids.iter().copied().collect();
 
                 extend_with_descendants(snapshot, &mut detected_pids);
@@ -637,7 +906,14 @@ fn process_running_apps_iteration_with_os<O: RunningAppsOs>(
                 retain_auto_managed_installed_pids(snapshot, &mut detected_pids, &explicit_pid_set);
             }
         }
+        detected_pids.retain(|pid| !released.contains(pid));
         retain_live_pids(&mut detected_pids, os);
+        let excluded = take_excluded_pids(snapshot, &configured.excluded_names, &mut detected_pids);
+        outcome.excluded.extend(excluded_processes(
+            snapshot,
+            excluded,
+            &configured.display_name,
+        ));
 
         if detected_pids.is_empty() {
             continue;
@@ -691,12 +967,14 @@ fn process_running_apps_iteration_with_os<O: RunningAppsOs>(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_aumid_to_seed_pids, build_name_to_pids, collect_configured_programs,
+        build_name_to_pids, build_process_lookups, collect_configured_programs,
         extend_with_descendants, process_running_apps_iteration_with_os, ConfiguredProgramMatcher,
-        ProcessSnapshot, RunningAppsOs,
+        ExcludedProcess, ProcessSnapshot, RunningAppsOs,
     };
     use crate::app::features::execution::InstalledPackageTrackingState;
-    use crate::app::models::{AppStateStorage, AppToRun, CoreGroup, CpuSchema, RunningApps};
+    use crate::app::models::{
+        AppStateStorage, AppToRun, CoreGroup, CoreSelector, CpuSchema, RunningApps, SelfAffinity,
+    };
     use crate::app::shared::ids::{GroupId, RuleId};
     use os_api::{InstalledPackageRuntimeInfo, PriorityClass};
     use std::cell::Cell;
@@ -710,6 +988,7 @@ mod tests {
         live_pids: HashSet<u32>,
         aumids: HashMap<u32, String>,
         aumid_lookup_count: Cell<usize>,
+        appimages: HashMap<u32, PathBuf>,
         installed_package_infos: HashMap<String, Result<InstalledPackageRuntimeInfo, String>>,
         metadata_lookup_count: Cell<usize>,
     }
@@ -722,6 +1001,7 @@ mod tests {
                 live_pids: HashSet::new(),
                 aumids: HashMap::new(),
                 aumid_lookup_count: Cell::new(0),
+                appimages: HashMap::new(),
                 installed_package_infos: HashMap::new(),
                 metadata_lookup_count: Cell::new(0),
             }
@@ -750,6 +1030,10 @@ mod tests {
             Ok(self.aumids.get(&pid).cloned())
         }
 
+        fn get_process_appimage_path(&self, pid: u32) -> Result<Option<PathBuf>, String> {
+            Ok(self.appimages.get(&pid).cloned())
+        }
+
         fn resolve_installed_package_runtime_info(
             &self,
             aumid: &str,
@@ -780,7 +1064,16 @@ mod tests {
                 cores: vec![0, 1],
                 programs: vec![app],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".to_string(),
@@ -788,7 +1081,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }
@@ -810,7 +1119,16 @@ mod tests {
                 cores: vec![2, 3],
                 programs: vec![app],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".to_string(),
@@ -818,7 +1136,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }
@@ -845,7 +1179,16 @@ mod tests {
                     ),
                 ],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".to_string(),
@@ -853,7 +1196,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }
@@ -867,21 +1226,12 @@ mod tests {
         let installed_package_tracking =
             Arc::new(RwLock::new(InstalledPackageTrackingState::default()));
         let snapshot = os.snapshot.clone().unwrap();
-        let name_to_pids = build_name_to_pids(&snapshot);
-        let aumid_to_seed_pids = if configured
-            .iter()
-            .any(|program| matches!(program.matcher, ConfiguredProgramMatcher::Installed { .. }))
-        {
-            build_aumid_to_seed_pids(&snapshot, os)
-        } else {
-            HashMap::new()
-        };
+        let lookups = build_process_lookups(&snapshot, &configured, os);
         process_running_apps_iteration_with_os(
             apps,
             configured,
             &snapshot,
-            &name_to_pids,
-            &aumid_to_seed_pids,
+            &lookups,
             &installed_package_tracking,
             os,
         )
@@ -895,10 +1245,25 @@ mod tests {
         RuleId(format!("rule-{value}"))
     }
 
+    #[test]
+    fn test_shell_opened_programs_never_track_shell_hosts() {
+        let mut state = sample_path_program_state();
+        let app = &mut state.groups[0].programs[0];
+        app.additional_processes = vec!["explorer.exe".to_string(), "viewer.exe".to_string()];
+        app.use_shell_execute = true;
+
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
+
+        assert_eq!(
+            configured[0].additional_processes_normalized,
+            vec!["viewer".to_string()]
+        );
+    }
+
     #[test]
     fn test_collect_configured_programs_preserves_ids_and_names() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
 
         assert_eq!(configured.len(), 1);
         assert_eq!(configured[0].group_id, group_id(0));
@@ -927,7 +1292,7 @@ mod tests {
         state.groups[0].programs[0].name = "Friendly Name".to_string();
         state.groups[0].programs[0].additional_processes.clear();
 
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
 
         assert!(configured.is_empty());
     }
@@ -953,7 +1318,7 @@ mod tests {
     #[test]
     fn test_collect_configured_programs_normalizes_installed_matcher_inputs() {
         let state = sample_installed_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
 
         assert_eq!(configured.len(), 1);
         assert_eq!(configured[0].display_name, "Spotify");
@@ -971,7 +1336,7 @@ mod tests {
     #[test]
     fn test_newly_detected_path_app_creates_tracking_entry_and_notification() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -994,10 +1359,126 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_released_pids_are_not_detected_again_until_they_exit() {
+        let state = sample_path_program_state();
+        let mut apps = RunningApps::default();
+        apps.released_pids.insert(10);
+        let mut os = FakeRunningAppsOs {
+            snapshot: Ok(ProcessSnapshot {
+                children_of: HashMap::new(),
+                names: HashMap::from([(10, "game.exe".to_string())]),
+            }),
+            image_paths: HashMap::from([(10, PathBuf::from(r"C:\game.exe"))]),
+            live_pids: HashSet::from([10]),
+            ..Default::default()
+        };
+
+        let outcome = run_iteration(
+            &mut apps,
+            collect_configured_programs(&state, &mut HashMap::new()),
+            &os,
+        );
+        assert!(!outcome.changed);
+        assert!(apps.apps.is_empty());
+        assert!(apps.released_pids.contains(&10));
+
+        os.live_pids.clear();
+        run_iteration(
+            &mut apps,
+            collect_configured_programs(&state, &mut HashMap::new()),
+            &os,
+        );
+        assert!(apps.released_pids.is_empty());
+    }
+
+    #[test]
+    fn test_appimage_rule_tracks_processes_started_from_the_image() {
+        let mut state = sample_path_program_state();
+        let appimage = PathBuf::from("/opt/apps/Game.AppImage");
+        state.groups[0].programs[0] = AppToRun::new_path(
+            appimage.clone(),
+            vec![],
+            appimage.clone(),
+            PriorityClass::High,
+            false,
+        );
+        let mut appimage_paths = HashMap::new();
+        let configured = collect_configured_programs(&state, &mut appimage_paths);
+        assert_eq!(appimage_paths.keys().collect::<Vec<_>>(), vec![&appimage]);
+        let mut apps = RunningApps::default();
+        // The runtime (10) has exited and the payload (20) was re-parented away from it.
+        let os = FakeRunningAppsOs {
+            snapshot: Ok(ProcessSnapshot {
+                children_of: HashMap::from([(20, vec![21])]),
+                names: HashMap::from([
+                    (20, "game".to_string()),
+                    (21, "game-helper".to_string()),
+                    (30, "game".to_string()),
+                ]),
+            }),
+            image_paths: HashMap::from([
+                (20, PathBuf::from("/tmp/.mount_GameXy/usr/bin/game")),
+                (30, PathBuf::from("/usr/bin/game")),
+            ]),
+            live_pids: HashSet::from([20, 21, 30]),
+            appimages: HashMap::from([(20, appimage.clone()), (21, appimage)]),
+            ..Default::default()
+        };
+
+        let outcome = run_iteration(&mut apps, configured, &os);
+
+        let key = state.groups[0].programs[0].get_key();
+        assert!(outcome.changed);
+        let mut pids = apps.apps.get(&key).map(|app| app.pids.clone()).unwrap();
+        pids.sort_unstable();
+        assert_eq!(pids, vec![20, 21]);
+
+        // A later pass reuses the resolved file and forgets rules that are gone.
+        let mounted = PathBuf::from("/mnt/apps/Game.AppImage");
+        appimage_paths.insert(PathBuf::from("/opt/apps/Game.AppImage"), mounted.clone());
+        let configured = collect_configured_programs(&state, &mut appimage_paths);
+        assert!(matches!(
+            &configured[0].matcher,
+            ConfiguredProgramMatcher::Path { appimage: Some(path), .. } if *path == mounted
+        ));
+        state.groups[0].programs.clear();
+        collect_configured_programs(&state, &mut appimage_paths);
+        assert!(appimage_paths.is_empty());
+    }
+
+    #[test]
+    fn test_watched_app_is_found_by_its_executable_without_tracked_names() {
+        let mut state = sample_path_program_state();
+        state.groups[0].programs[0].additional_processes.clear();
+        assert!(collect_configured_programs(&state, &mut HashMap::new()).is_empty());
+
+        state.groups[0].programs[0].watch_autoapply = true;
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
+        let mut apps = RunningApps::default();
+        let os = FakeRunningAppsOs {
+            snapshot: Ok(ProcessSnapshot {
+                children_of: HashMap::new(),
+                names: HashMap::from([(10, "game.exe".to_string())]),
+            }),
+            image_paths: HashMap::from([(10, PathBuf::from(r"C:\game.exe"))]),
+            live_pids: HashSet::from([10]),
+            ..Default::default()
+        };
+
+        run_iteration(&mut apps, configured, &os);
+
+        let key = state.groups[0].programs[0].get_key();
+        assert_eq!(
+            apps.apps.get(&key).map(|app| app.pids.clone()),
+            Some(vec![10])
+        );
+    }
+
     #[test]
     fn test_exact_name_match_requires_matching_image_path() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1019,7 +1500,7 @@ mod tests {
     #[test]
     fn test_primary_process_name_does_not_prefix_match_helper_processes() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1040,7 +1521,7 @@ mod tests {
     #[test]
     fn test_additional_processes_attach_extra_pids_to_same_path_app() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1069,7 +1550,7 @@ mod tests {
     #[test]
     fn test_explicit_fallback_process_name_can_cold_detect_path_app() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1094,7 +1575,7 @@ mod tests {
     #[test]
     fn test_explicit_fallback_process_names_match_exactly() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1112,10 +1593,40 @@ mod tests {
         assert!(apps.apps.is_empty());
     }
 
+    #[test]
+    fn test_match_pattern_attaches_matching_process_names() {
+        let mut state = sample_path_program_state();
+        state.groups[0].programs[0].additional_processes.clear();
+        state.groups[0].programs[0].match_pattern = Some(r"game_\d+\.exe".into());
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
+        let mut apps = RunningApps::default();
+        let os = FakeRunningAppsOs {
+            snapshot: Ok(ProcessSnapshot {
+                children_of: HashMap::new(),
+                names: HashMap::from([
+                    (11, "game_1234.exe".to_string()),
+                    (12, "game_b1234.exe".to_string()),
+                ]),
+            }),
+            image_paths: HashMap::new(),
+            live_pids: HashSet::from([11, 12]),
+            ..Default::default()
+        };
+
+        let outcome = run_iteration(&mut apps, configured, &os);
+
+        let key = state.groups[0].programs[0].get_key();
+        assert!(outcome.changed);
+        assert_eq!(
+            apps.apps.get(&key).map(|app| app.pids.clone()),
+            Some(vec![11])
+        );
+    }
+
     #[test]
     fn test_installed_seed_discovery_creates_tracking_entry() {
         let state = sample_installed_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1148,7 +1659,7 @@ mod tests {
     #[test]
     fn test_installed_background_host_seed_does_not_create_tracking_entry() {
         let state = sample_installed_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1173,7 +1684,7 @@ mod tests {
     #[test]
     fn test_installed_target_attaches_additional_processes_after_seed() {
         let state = sample_installed_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1206,7 +1717,7 @@ mod tests {
     fn test_installed_background_host_does_not_keep_app_running_after_main_exit() {
         let mut state = sample_installed_program_state();
         state.groups[0].programs[0].additional_processes.clear();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let key = state.groups[0].programs[0].get_key();
         apps.add_app(&key, 20, group_id(0), rule_id(0));
@@ -1236,7 +1747,7 @@ mod tests {
         let mut state = sample_installed_program_state();
         state.groups[0].programs[0].additional_processes =
             vec!["backgroundTaskHost.exe".to_string()];
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1272,7 +1783,7 @@ mod tests {
     fn test_tracked_installed_target_attaches_package_local_pid_after_seed() {
         let mut state = sample_installed_program_state();
         state.groups[0].programs[0].additional_processes.clear();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let key = state.groups[0].programs[0].get_key();
         apps.add_app(&key, 20, group_id(0), rule_id(0));
@@ -1322,7 +1833,7 @@ mod tests {
     #[test]
     fn test_installed_target_does_not_blindly_cold_discover_helper_only_processes() {
         let state = sample_installed_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1333,6 +1844,7 @@ mod tests {
             live_pids: HashSet::from([21]),
             aumids: HashMap::new(),
             aumid_lookup_count: Cell::new(0),
+            appimages: HashMap::new(),
             metadata_lookup_count: Cell::new(0),
             installed_package_infos: HashMap::from([(
                 "spotifyab.spotifymusic_zpdnekdrzrea0!spotify".to_string(),
@@ -1355,7 +1867,7 @@ mod tests {
     #[test]
     fn test_path_only_iteration_skips_aumid_lookup_sweep() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let os = FakeRunningAppsOs {
             snapshot: Ok(ProcessSnapshot {
@@ -1381,7 +1893,7 @@ mod tests {
     #[test]
     fn test_shared_package_no_identity_pids_attach_only_to_first_active_target() {
         let state = sample_shared_package_installed_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let first_key = state.groups[0].programs[0].get_key();
         let second_key = state.groups[0].programs[1].get_key();
@@ -1457,7 +1969,7 @@ mod tests {
     #[test]
     fn test_stale_tracked_app_is_removed_when_process_stops() {
         let state = sample_path_program_state();
-        let configured = collect_configured_programs(&state);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
         let mut apps = RunningApps::default();
         let key = state.groups[0].programs[0].get_key();
         apps.add_app(&key, 10, group_id(0), rule_id(0));
@@ -1478,6 +1990,101 @@ mod tests {
         assert!(!apps.apps.contains_key(&key));
     }
 
+    #[test]
+    fn test_replaced_pid_set_reports_restart_and_keeps_start_time() {
+        let state = sample_path_program_state();
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
+        let mut apps = RunningApps::default();
+        let key = state.groups[0].programs[0].get_key();
+        apps.add_app(&key, 10, group_id(0), rule_id(0));
+        let started_at = apps.apps[&key].created_at;
+        let os = FakeRunningAppsOs {
+            snapshot: Ok(ProcessSnapshot {
+                children_of: HashMap::new(),
+                names: HashMap::from([(20, "game.exe".to_string())]),
+            }),
+            image_paths: HashMap::from([(20, PathBuf::from(r"C:\game.exe"))]),
+            live_pids: HashSet::from([20]),
+            ..Default::default()
+        };
+
+        let outcome = run_iteration(&mut apps, configured, &os);
+
+        assert_eq!(outcome.restarted, vec![key.clone()]);
+        assert_eq!(apps.apps[&key].pids, vec![20]);
+        assert_eq!(apps.apps[&key].created_at, started_at);
+
+        apps.restart_clock(&key);
+        assert!(apps.apps[&key].created_at >= started_at);
+    }
+
+    #[test]
+    fn test_group_copy_of_rule_keeps_its_own_pids() {
+        let mut state = sample_path_program_state();
+        let mut copy_group = state.groups[0].clone();
+        copy_group.name = "Work".to_string();
+        copy_group.programs[0].copy_index = 1;
+        state.groups.push(copy_group);
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
+        let mut apps = RunningApps::default();
+        let original = state.groups[0].programs[0].get_key();
+        let copy = state.groups[1].programs[0].get_key();
+        apps.add_app(&copy, 11, group_id(1), rule_id(0));
+        let os = FakeRunningAppsOs {
+            snapshot: Ok(ProcessSnapshot {
+                children_of: HashMap::new(),
+                names: HashMap::from([(10, "game.exe".to_string()), (11, "game.exe".to_string())]),
+            }),
+            image_paths: HashMap::from([
+                (10, PathBuf::from(r"C:\game.exe")),
+                (11, PathBuf::from(r"C:\game.exe")),
+            ]),
+            live_pids: HashSet::from([10, 11]),
+            ..Default::default()
+        };
+
+        run_iteration(&mut apps, configured.clone(), &os);
+        run_iteration(&mut apps, configured, &os);
+
+        assert_eq!(apps.apps[&original].pids, vec![10]);
+        assert_eq!(apps.apps[&copy].pids, vec![11]);
+    }
+
+    #[test]
+    fn test_group_excluded_child_is_not_tracked_and_is_reported() {
+        let mut state = sample_path_program_state();
+        state.groups[0].exclude_names = vec!["EasyAntiCheat.exe".to_string()];
+        let configured = collect_configured_programs(&state, &mut HashMap::new());
+        let mut apps = RunningApps::default();
+        let key = state.groups[0].programs[0].get_key();
+        let os = FakeRunningAppsOs {
+            snapshot: Ok(ProcessSnapshot {
+                children_of: HashMap::from([(10, vec![11, 12])]),
+                names: HashMap::from([
+                    (10, "game.exe".to_string()),
+                    (11, "easyanticheat.exe".to_string()),
+                    (12, "crashpad.exe".to_string()),
+                ]),
+            }),
+            image_paths: HashMap::from([(10, PathBuf::from(r"C:\game.exe"))]),
+            live_pids: HashSet::from([10, 11, 12]),
+            ..Default::default()
+        };
+
+        let first = run_iteration(&mut apps, configured.clone(), &os);
+        let second = run_iteration(&mut apps, configured, &os);
+
+        assert_eq!(apps.apps[&key].pids, vec![10, 12]);
+        let excluded = ExcludedProcess {
+            pid: 11,
+            name: "easyanticheat.exe".to_string(),
+            app: "game".to_string(),
+        };
+        assert_eq!(first.excluded, vec![excluded.clone()]);
+        assert_eq!(second.excluded, vec![excluded]);
+        assert!(!second.changed);
+    }
+
     #[test]
     fn test_stale_tracked_app_is_removed_when_configuration_disappears() {
         let state = sample_path_program_state();
@@ -1,6 +1,7 @@
 pub mod diagnostics;
 pub mod execution;
 pub mod preferences;
+pub mod profiles;
 pub mod rules;
 pub mod shortcut;
 pub mod topology;
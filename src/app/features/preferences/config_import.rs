@@ -0,0 +1,273 @@
+use crate::app::models::{AppRuntimeKey, AppStateStorage, AppToRun, CoreGroup};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// What importing a config would change, shown for confirmation before it
+/// replaces the current one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConfigDiff {
+    pub groups_added: Vec<String>,
+    pub groups_removed: Vec<String>,
+    /// Groups in both configs whose settings or apps differ
+    pub groups_changed: Vec<String>,
+    /// `(group, app)` names, including the apps of added groups
+    pub programs_added: Vec<(String, String)>,
+    /// `(group, app)` names, including the apps of removed groups
+    pub programs_removed: Vec<(String, String)>,
+    /// Theme index before and after
+    pub theme_change: Option<(usize, usize)>,
+    /// `(group, app, command)` for post-launch commands that are new or changed
+    pub post_launch_commands: Vec<(String, String, String)>,
+    /// `(group, app)` for apps that would start with the tool and do not today
+    pub autorun_added: Vec<(String, String)>,
+}
+
+impl ConfigDiff {
+    pub fn is_empty(&self) -> bool {
+        *self == ConfigDiff::default()
+    }
+}
+
+/// Compares two configs group by group. Groups are matched by name, apps within a
+/// matched group by their runtime key.
+pub fn diff(old: &AppStateStorage, new: &AppStateStorage) -> ConfigDiff {
+    let mut result = ConfigDiff::default();
+    let mut matched = vec![false; new.groups.len()];
+
+    for old_group in &old.groups {
+        let new_index = new
+            .groups
+            .iter()
+            .enumerate()
+            .position(|(index, group)| !matched[index] && group.name == old_group.name);
+        let Some(new_index) = new_index else {
+            result.groups_removed.push(old_group.name.clone());
+            result
+                .programs_removed
+                .extend(program_names(old_group, |_| true));
+            continue;
+        };
+        matched[new_index] = true;
+        let new_group = &new.groups[new_index];
+
+        if group_json(old_group) != group_json(new_group) {
+            result.groups_changed.push(old_group.name.clone());
+        }
+        let old_keys = program_keys(old_group);
+        let new_keys = program_keys(new_group);
+        result
+            .programs_added
+            .extend(program_names(new_group, |key| !old_keys.contains(key)));
+        result
+            .programs_removed
+            .extend(program_names(old_group, |key| !new_keys.contains(key)));
+    }
+
+    for (group, _) in new
+        .groups
+        .iter()
+        .zip(&matched)
+        .filter(|(_, &matched)| !matched)
+    {
+        result.groups_added.push(group.name.clone());
+        result.programs_added.extend(program_names(group, |_| true));
+    }
+
+    if old.theme_index != new.theme_index {
+        result.theme_change = Some((old.theme_index, new.theme_index));
+    }
+
+    // Both run programs without a click, so they are listed even inside unchanged groups.
+    let old_programs: HashMap<(&str, AppRuntimeKey), &AppToRun> = old
+        .groups
+        .iter()
+        .flat_map(|group| {
+            group
+                .programs
+                .iter()
+                .map(move |program| ((group.name.as_str(), program.get_key()), program))
+        })
+        .collect();
+    for group in &new.groups {
+        for program in &group.programs {
+            let previous = old_programs.get(&(group.name.as_str(), program.get_key()));
+            let command = program
+                .post_launch_cmd
+                .as_deref()
+                .map(str::trim)
+                .filter(|command| !command.is_empty());
+            if let Some(command) = command {
+                if previous.and_then(|previous| previous.post_launch_cmd.as_deref().map(str::trim))
+                    != Some(command)
+                {
+                    result.post_launch_commands.push((
+                        group.name.clone(),
+                        program.name.clone(),
+                        command.to_string(),
+                    ));
+                }
+            }
+            if program.autorun && !previous.is_some_and(|previous| previous.autorun) {
+                result
+                    .autorun_added
+                    .push((group.name.clone(), program.name.clone()));
+            }
+        }
+    }
+    result
+}
+
+/// Keeps the settings that describe this machine rather than the groups, e.g. its
+/// CPU layout and whether the tool audits or monitors, when a config from
+/// elsewhere replaces the current one.
+pub fn keep_machine_local(current: &AppStateStorage, imported: &mut AppStateStorage) {
+    imported.cpu_schema = current.cpu_schema.clone();
+    imported.reserved_cores = current.reserved_cores.clone();
+    imported.self_affinity = current.self_affinity;
+    imported.affinity_audit = current.affinity_audit;
+    imported.process_monitoring_enabled = current.process_monitoring_enabled;
+}
+
+/// Reads a state file of this tool, e.g. one copied from another machine, migrating
+/// older schema versions the same way startup does.
+pub fn read_config_file(path: &Path) -> Result<AppStateStorage, String> {
+    let data = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read '{}': {err}", path.display()))?;
+    AppStateStorage::from_json(&data)
+        .ok_or_else(|| format!("'{}' is not a config of this tool", path.display()))
+}
+
+fn group_json(group: &CoreGroup) -> serde_json::Value {
+    serde_json::to_value(group).unwrap_or_default()
+}
+
+fn program_keys(group: &CoreGroup) -> HashSet<AppRuntimeKey> {
+    group
+        .programs
+        .iter()
+        .map(|program| program.get_key())
+        .collect()
+}
+
+fn program_names(
+    group: &CoreGroup,
+    include: impl Fn(&AppRuntimeKey) -> bool,
+) -> Vec<(String, String)> {
+    group
+        .programs
+        .iter()
+        .filter(|program| include(&program.get_key()))
+        .map(|program| (group.name.clone(), program.name.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, ConfigDiff};
+    use crate::app::models::AppStateStorage;
+    use serde_json::json;
+
+    fn config(theme_index: usize, groups: serde_json::Value) -> AppStateStorage {
+        serde_json::from_value(json!({
+            "version": 7,
+            "groups": groups,
+            "cpu_schema": { "model": "Test CPU", "clusters": [] },
+            "theme_index": theme_index,
+        }))
+        .unwrap()
+    }
+
+    fn group(name: &str, cores: &[usize], apps: &[&str]) -> serde_json::Value {
+        let programs: Vec<_> = apps
+            .iter()
+            .map(|app| {
+                json!({
+                    "name": app,
+                    "launch_target": { "Path": {
+                        "dropped_path": format!("C:\\{app}.lnk"),
+                        "bin_path": format!("C:\\{app}.exe"),
+                    } },
+                    "autorun": false,
+                    "priority": "Normal",
+                })
+            })
+            .collect();
+        json!({
+            "name": name,
+            "cores": cores,
+            "programs": programs,
+            "is_hidden": false,
+            "run_all_button": true,
+        })
+    }
+
+    #[test]
+    fn test_diff_reports_groups_apps_and_theme() {
+        let old = config(
+            0,
+            json!([
+                group("Games", &[0, 1], &["Game", "Launcher"]),
+                group("Streaming", &[2], &["Obs"]),
+                group("Work", &[3], &["Editor"]),
+            ]),
+        );
+        let new = config(
+            2,
+            json!([
+                group("Games", &[0, 1], &["Game", "Overlay"]),
+                group("Streaming", &[2, 3], &["Obs"]),
+                group("Music", &[4], &["Player"]),
+            ]),
+        );
+
+        let changes = diff(&old, &new);
+
+        assert_eq!(changes.groups_added, vec!["Music".to_string()]);
+        assert_eq!(changes.groups_removed, vec!["Work".to_string()]);
+        assert_eq!(
+            changes.groups_changed,
+            vec!["Games".to_string(), "Streaming".to_string()]
+        );
+        let pairs = |items: &[(&str, &str)]| {
+            items
+                .iter()
+                .map(|(group, app)| (group.to_string(), app.to_string()))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            changes.programs_added,
+            pairs(&[("Games", "Overlay"), ("Music", "Player")])
+        );
+        assert_eq!(
+            changes.programs_removed,
+            pairs(&[("Games", "Launcher"), ("Work", "Editor")])
+        );
+        assert_eq!(changes.theme_change, Some((0, 2)));
+
+        assert_eq!(diff(&old, &old), ConfigDiff::default());
+        assert!(diff(&new, &new).is_empty());
+    }
+
+    #[test]
+    fn test_diff_lists_new_post_launch_commands_and_autorun_apps() {
+        let old = config(0, json!([group("Games", &[0], &["Game", "Tool"])]));
+        let mut new = old.clone();
+        new.groups[0].programs[0].post_launch_cmd = Some("fans.sh {pid}".to_string());
+        new.groups[0].programs[1].autorun = true;
+
+        let changes = diff(&old, &new);
+        assert_eq!(
+            changes.post_launch_commands,
+            vec![(
+                "Games".to_string(),
+                "Game".to_string(),
+                "fans.sh {pid}".to_string()
+            )]
+        );
+        assert_eq!(
+            changes.autorun_added,
+            vec![("Games".to_string(), "Tool".to_string())]
+        );
+        assert!(diff(&new, &new).is_empty());
+    }
+}
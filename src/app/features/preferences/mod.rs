@@ -1,6 +1,15 @@
-use crate::app::models::AppStateStorage;
+pub mod config_import;
+
+use crate::app::models::{AppStateStorage, SelfAffinity};
 use std::sync::{Arc, RwLock};
 
+/// Seconds a launch is watched when the check is first switched on.
+pub const DEFAULT_LAUNCH_CONFIRM_SECS: u32 = 5;
+pub const MAX_LAUNCH_CONFIRM_SECS: u32 = 60;
+/// Apps a "Run all" starts without asking when the confirmation is switched back on.
+pub const DEFAULT_RUN_ALL_CONFIRM_THRESHOLD: usize = 5;
+pub const MAX_RUN_ALL_CONFIRM_THRESHOLD: usize = 100;
+
 pub fn toggle_theme(persistent_state: &Arc<RwLock<AppStateStorage>>) {
     let mut state = persistent_state.write().unwrap();
     state.theme_index = (state.theme_index + 1) % 3;
@@ -11,10 +20,79 @@ pub fn toggle_process_monitoring(persistent_state: &Arc<RwLock<AppStateStorage>>
     state.process_monitoring_enabled = !state.process_monitoring_enabled;
 }
 
+pub fn toggle_runtime_reset_on_restart(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.reset_runtime_on_restart = !state.reset_runtime_on_restart;
+}
+
+pub fn toggle_group_overlap_warning(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.hide_group_overlap_warning = !state.hide_group_overlap_warning;
+}
+
+pub fn toggle_hide_taskbar_when_tray(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.hide_taskbar_when_tray = !state.hide_taskbar_when_tray;
+}
+
+pub fn toggle_separate_apps_per_group(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.separate_apps_per_group = !state.separate_apps_per_group;
+}
+
+pub fn set_launch_confirm_secs(persistent_state: &Arc<RwLock<AppStateStorage>>, secs: u32) {
+    let mut state = persistent_state.write().unwrap();
+    state.launch_confirm_secs = secs.min(MAX_LAUNCH_CONFIRM_SECS);
+}
+
+pub fn toggle_launch_confirm_window(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.launch_confirm_window = !state.launch_confirm_window;
+}
+
+pub fn set_run_all_confirm_threshold(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    threshold: usize,
+) {
+    let mut state = persistent_state.write().unwrap();
+    state.run_all_confirm_threshold = threshold.min(MAX_RUN_ALL_CONFIRM_THRESHOLD);
+}
+
+pub fn toggle_affinity_audit(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.affinity_audit = !state.affinity_audit;
+}
+
+pub fn toggle_click_to_launch(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.click_to_launch = !state.click_to_launch;
+}
+
+pub fn toggle_always_on_top(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.always_on_top = !state.always_on_top;
+}
+
+pub fn toggle_hide_tips(persistent_state: &Arc<RwLock<AppStateStorage>>) {
+    let mut state = persistent_state.write().unwrap();
+    state.hide_tips = !state.hide_tips;
+}
+
+pub fn set_self_affinity(persistent_state: &Arc<RwLock<AppStateStorage>>, value: SelfAffinity) {
+    let mut state = persistent_state.write().unwrap();
+    state.self_affinity = value;
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{toggle_process_monitoring, toggle_theme};
-    use crate::app::models::{AppStateStorage, CpuSchema};
+    use super::{
+        set_launch_confirm_secs, set_self_affinity, toggle_affinity_audit, toggle_always_on_top,
+        toggle_click_to_launch, toggle_group_overlap_warning, toggle_hide_taskbar_when_tray,
+        toggle_hide_tips, toggle_launch_confirm_window, toggle_process_monitoring,
+        toggle_runtime_reset_on_restart, toggle_separate_apps_per_group, toggle_theme,
+        MAX_LAUNCH_CONFIRM_SECS,
+    };
+    use crate::app::models::{AppStateStorage, CpuSchema, SelfAffinity};
     use std::sync::{Arc, RwLock};
 
     fn sample_state() -> Arc<RwLock<AppStateStorage>> {
@@ -27,7 +105,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }))
@@ -49,4 +143,85 @@ mod tests {
         toggle_process_monitoring(&state);
         assert!(state.read().unwrap().process_monitoring_enabled);
     }
+
+    #[test]
+    fn test_toggle_runtime_reset_on_restart_flips_flag() {
+        let state = sample_state();
+        toggle_runtime_reset_on_restart(&state);
+        assert!(state.read().unwrap().reset_runtime_on_restart);
+        toggle_runtime_reset_on_restart(&state);
+        assert!(!state.read().unwrap().reset_runtime_on_restart);
+    }
+
+    #[test]
+    fn test_toggle_group_overlap_warning_flips_flag() {
+        let state = sample_state();
+        toggle_group_overlap_warning(&state);
+        assert!(state.read().unwrap().hide_group_overlap_warning);
+    }
+
+    #[test]
+    fn test_toggle_hide_taskbar_when_tray_flips_flag() {
+        let state = sample_state();
+        toggle_hide_taskbar_when_tray(&state);
+        assert!(!state.read().unwrap().hide_taskbar_when_tray);
+    }
+
+    #[test]
+    fn test_toggle_separate_apps_per_group_flips_flag() {
+        let state = sample_state();
+        toggle_separate_apps_per_group(&state);
+        assert!(state.read().unwrap().separate_apps_per_group);
+    }
+
+    #[test]
+    fn test_launch_confirm_settings_clamp_and_flip() {
+        let state = sample_state();
+        set_launch_confirm_secs(&state, 600);
+        assert_eq!(
+            state.read().unwrap().launch_confirm_secs,
+            MAX_LAUNCH_CONFIRM_SECS
+        );
+        set_launch_confirm_secs(&state, 0);
+        assert_eq!(state.read().unwrap().launch_confirm_secs, 0);
+        toggle_launch_confirm_window(&state);
+        assert!(state.read().unwrap().launch_confirm_window);
+    }
+
+    #[test]
+    fn test_toggle_affinity_audit_flips_flag() {
+        let state = sample_state();
+        toggle_affinity_audit(&state);
+        assert!(state.read().unwrap().affinity_audit);
+    }
+
+    #[test]
+    fn test_toggle_click_to_launch_flips_flag() {
+        let state = sample_state();
+        toggle_click_to_launch(&state);
+        assert!(state.read().unwrap().click_to_launch);
+    }
+
+    #[test]
+    fn test_toggle_always_on_top_flips_flag() {
+        let state = sample_state();
+        toggle_always_on_top(&state);
+        assert!(state.read().unwrap().always_on_top);
+        toggle_always_on_top(&state);
+        assert!(!state.read().unwrap().always_on_top);
+    }
+
+    #[test]
+    fn test_toggle_hide_tips_flips_flag() {
+        let state = sample_state();
+        toggle_hide_tips(&state);
+        assert!(state.read().unwrap().hide_tips);
+    }
+
+    #[test]
+    fn test_set_self_affinity_stores_choice() {
+        let state = sample_state();
+        set_self_affinity(&state, SelfAffinity::Core(3));
+        assert_eq!(state.read().unwrap().self_affinity, SelfAffinity::Core(3));
+    }
 }
@@ -0,0 +1,365 @@
+use crate::app::models::{AffinityFavorite, AppStateStorage, Profile};
+use crate::app::shared::ids::GroupId;
+use std::sync::{Arc, RwLock};
+
+const MAX_PROFILE_NAME_LEN: usize = 64;
+
+pub fn profile_names(persistent_state: &Arc<RwLock<AppStateStorage>>) -> Vec<String> {
+    let state = persistent_state.read().unwrap();
+    state
+        .profiles
+        .iter()
+        .map(|profile| profile.name.clone())
+        .collect()
+}
+
+pub fn active_profile_name(persistent_state: &Arc<RwLock<AppStateStorage>>) -> Option<String> {
+    let state = persistent_state.read().unwrap();
+    state.active_profile().map(|profile| profile.name.clone())
+}
+
+/// Adds an empty profile and makes it active so the next group edit can fill it.
+pub fn create_profile(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    name: &str,
+) -> Result<(), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_LEN {
+        return Err(format!(
+            "Profile name cannot be longer than {MAX_PROFILE_NAME_LEN} characters"
+        ));
+    }
+
+    let mut state = persistent_state.write().unwrap();
+    if state
+        .profiles
+        .iter()
+        .any(|profile| profile.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("Profile \"{name}\" already exists"));
+    }
+
+    state.profiles.push(Profile::new(name));
+    state.active_profile = Some(name.to_string());
+    Ok(())
+}
+
+/// Switches the layered profile; `None` returns every group to its base cores.
+pub fn set_active_profile(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    name: Option<&str>,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    if let Some(name) = name {
+        if !state.profiles.iter().any(|profile| profile.name == name) {
+            return false;
+        }
+    }
+    if state.active_profile.as_deref() == name {
+        return false;
+    }
+
+    state.active_profile = name.map(str::to_string);
+    true
+}
+
+pub fn delete_active_profile(persistent_state: &Arc<RwLock<AppStateStorage>>) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    let Some(name) = state.active_profile.take() else {
+        return false;
+    };
+    state.profiles.retain(|profile| profile.name != name);
+    true
+}
+
+/// Sets or clears the active profile's override for one group.
+pub fn set_group_override(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    group_id: &GroupId,
+    cores: Option<Vec<usize>>,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    state
+        .active_profile_mut()
+        .is_some_and(|profile| profile.set_cores_for(group_id, cores))
+}
+
+pub fn favorites(persistent_state: &Arc<RwLock<AppStateStorage>>) -> Vec<AffinityFavorite> {
+    persistent_state.read().unwrap().favorites.clone()
+}
+
+/// Adds a favorite mask for `threads`, written like `0-3, 8`.
+pub fn add_favorite(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    name: &str,
+    threads: &str,
+    total_threads: usize,
+) -> Result<AffinityFavorite, String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("Favorite name cannot be empty".to_string());
+    }
+    if name.chars().count() > MAX_PROFILE_NAME_LEN {
+        return Err(format!(
+            "Favorite name cannot be longer than {MAX_PROFILE_NAME_LEN} characters"
+        ));
+    }
+    let cores = parse_thread_list(threads, total_threads)?;
+
+    let mut state = persistent_state.write().unwrap();
+    if state
+        .favorites
+        .iter()
+        .any(|favorite| favorite.name.eq_ignore_ascii_case(name))
+    {
+        return Err(format!("Favorite \"{name}\" already exists"));
+    }
+    let favorite = AffinityFavorite {
+        name: name.to_string(),
+        cores,
+    };
+    state.favorites.push(favorite.clone());
+    Ok(favorite)
+}
+
+pub fn remove_favorite(persistent_state: &Arc<RwLock<AppStateStorage>>, name: &str) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    let before = state.favorites.len();
+    state.favorites.retain(|favorite| favorite.name != name);
+    state.favorites.len() != before
+}
+
+/// Thread indices of a list like `0-3, 8, 10-11`, sorted and without duplicates.
+pub fn parse_thread_list(text: &str, total_threads: usize) -> Result<Vec<usize>, String> {
+    let parse = |value: &str| {
+        value
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("\"{}\" is not a thread number", value.trim()))
+    };
+    let mut threads = Vec::new();
+    for part in text.split(',').filter(|part| !part.trim().is_empty()) {
+        let (first, last) = match part.split_once('-') {
+            Some((first, last)) => (parse(first)?, parse(last)?),
+            None => {
+                let thread = parse(part)?;
+                (thread, thread)
+            }
+        };
+        if first > last {
+            return Err(format!("\"{}\" runs backwards", part.trim()));
+        }
+        if last >= total_threads {
+            return Err(format!(
+                "Thread {last} does not exist; this CPU has threads 0-{}",
+                total_threads.saturating_sub(1)
+            ));
+        }
+        threads.extend(first..=last);
+    }
+    threads.sort_unstable();
+    threads.dedup();
+    if threads.is_empty() {
+        return Err("List at least one thread, e.g. 0-3".to_string());
+    }
+    Ok(threads)
+}
+
+/// Drops a deleted group's overrides from every profile.
+/// Returns the removed overrides keyed by profile name so they can be restored.
+pub fn forget_group(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    group_id: &GroupId,
+) -> Vec<(String, Vec<usize>)> {
+    let mut state = persistent_state.write().unwrap();
+    let mut removed = Vec::new();
+    for profile in &mut state.profiles {
+        if let Some(cores) = profile.cores_for(group_id).map(<[usize]>::to_vec) {
+            profile.set_cores_for(group_id, None);
+            removed.push((profile.name.clone(), cores));
+        }
+    }
+    removed
+}
+
+/// Puts overrides returned by `forget_group` back into the profiles that still exist.
+pub fn restore_group_overrides(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    group_id: &GroupId,
+    overrides: Vec<(String, Vec<usize>)>,
+) {
+    let mut state = persistent_state.write().unwrap();
+    for (profile_name, cores) in overrides {
+        if let Some(profile) = state
+            .profiles
+            .iter_mut()
+            .find(|profile| profile.name == profile_name)
+        {
+            profile.set_cores_for(group_id, Some(cores));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        add_favorite, create_profile, delete_active_profile, forget_group, parse_thread_list,
+        remove_favorite, restore_group_overrides, set_active_profile, set_group_override,
+    };
+    use crate::app::features::rules::{PersistedGroupIdentity, PersistedRuleIdentities};
+    use crate::app::models::{AppStateStorage, CoreGroup, CoreSelector, CpuSchema, SelfAffinity};
+    use crate::app::shared::ids::GroupId;
+    use os_api::PriorityClass;
+    use std::sync::{Arc, RwLock};
+
+    fn sample_state() -> Arc<RwLock<AppStateStorage>> {
+        Arc::new(RwLock::new(AppStateStorage {
+            version: 7,
+            groups: vec![CoreGroup {
+                name: "Games".to_string(),
+                cores: vec![0, 1],
+                programs: vec![],
+                is_hidden: false,
+                is_collapsed: false,
+                run_all_button: false,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
+            }],
+            cpu_schema: CpuSchema {
+                model: "Test CPU".to_string(),
+                clusters: Vec::new(),
+            },
+            theme_index: 0,
+            process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
+            rule_identities: Some(PersistedRuleIdentities {
+                groups: vec![PersistedGroupIdentity {
+                    id: GroupId("group-1".to_string()),
+                    rule_ids: vec![],
+                }],
+                next_group_id: 2,
+                next_rule_id: 1,
+            }),
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
+            loaded_version: 7,
+            pending_pre_v6_backup: false,
+        }))
+    }
+
+    #[test]
+    fn test_parse_thread_list_accepts_ranges_and_rejects_bad_threads() {
+        assert_eq!(parse_thread_list("0-3, 8,2", 16), Ok(vec![0, 1, 2, 3, 8]));
+        assert_eq!(parse_thread_list(" 5 ", 16), Ok(vec![5]));
+        assert!(parse_thread_list("", 16).is_err());
+        assert!(parse_thread_list("3-1", 16).is_err());
+        assert!(parse_thread_list("0-16", 16)
+            .unwrap_err()
+            .contains("threads 0-15"));
+        assert!(parse_thread_list("P-cores", 16).is_err());
+    }
+
+    #[test]
+    fn test_favorites_are_added_once_and_removed_by_name() {
+        let state = sample_state();
+
+        let favorite = add_favorite(&state, " First four ", "0-3", 8).unwrap();
+        assert_eq!(favorite.name, "First four");
+        assert_eq!(favorite.cores, vec![0, 1, 2, 3]);
+        assert!(add_favorite(&state, "first FOUR", "4", 8).is_err());
+        assert!(add_favorite(&state, "Too far", "8", 8).is_err());
+        assert_eq!(state.read().unwrap().favorites, vec![favorite]);
+
+        assert!(remove_favorite(&state, "First four"));
+        assert!(!remove_favorite(&state, "First four"));
+        assert!(state.read().unwrap().favorites.is_empty());
+    }
+
+    #[test]
+    fn test_create_profile_trims_rejects_duplicates_and_activates() {
+        let state = sample_state();
+
+        assert_eq!(create_profile(&state, "  Gaming "), Ok(()));
+        assert_eq!(
+            state.read().unwrap().active_profile.as_deref(),
+            Some("Gaming")
+        );
+        assert!(create_profile(&state, "gaming").is_err());
+        assert!(create_profile(&state, "   ").is_err());
+        assert_eq!(state.read().unwrap().profiles.len(), 1);
+    }
+
+    #[test]
+    fn test_active_profile_override_layers_over_base_cores() {
+        let state = sample_state();
+        let group_id = GroupId("group-1".to_string());
+        create_profile(&state, "Streaming").unwrap();
+
+        assert!(set_group_override(&state, &group_id, Some(vec![4, 5])));
+        assert_eq!(
+            state.read().unwrap().effective_group_cores(0),
+            Some(vec![4, 5])
+        );
+        assert_eq!(state.read().unwrap().groups[0].cores, vec![0, 1]);
+
+        assert!(set_active_profile(&state, None));
+        assert_eq!(
+            state.read().unwrap().effective_group_cores(0),
+            Some(vec![0, 1])
+        );
+        assert!(!set_active_profile(&state, Some("Missing")));
+        assert!(set_active_profile(&state, Some("Streaming")));
+        assert_eq!(
+            state.read().unwrap().effective_group_cores(0),
+            Some(vec![4, 5])
+        );
+    }
+
+    #[test]
+    fn test_deleting_profiles_and_groups_clears_overrides() {
+        let state = sample_state();
+        let group_id = GroupId("group-1".to_string());
+        create_profile(&state, "Gaming").unwrap();
+        set_group_override(&state, &group_id, Some(vec![2]));
+
+        let removed = forget_group(&state, &group_id);
+        assert_eq!(removed, vec![("Gaming".to_string(), vec![2])]);
+        assert_eq!(
+            state.read().unwrap().effective_group_cores(0),
+            Some(vec![0, 1])
+        );
+
+        restore_group_overrides(&state, &group_id, removed);
+        assert_eq!(
+            state.read().unwrap().effective_group_cores(0),
+            Some(vec![2])
+        );
+        forget_group(&state, &group_id);
+
+        assert!(delete_active_profile(&state));
+        assert!(state.read().unwrap().profiles.is_empty());
+        assert_eq!(state.read().unwrap().active_profile, None);
+    }
+}
@@ -5,9 +5,13 @@ use crate::app::shared::ids::{GroupId, RuleId};
 use serde::{Deserialize, Serialize};
 
 pub use service::{
-    add_apps_to_group, add_installed_app_to_group, create_group, load_group_for_edit, load_rule,
-    move_group_to_index, move_rule_between_groups_at, remove_group, remove_rule_from_group,
-    set_group_is_hidden, update_group_properties, update_rule,
+    add_apps_to_group, add_installed_app_to_group, clone_rule_to_group, create_group,
+    group_name_conflict, insert_group, load_group_for_edit, load_rule, merge_separated_apps,
+    move_group_to_index, move_rule_between_groups_at, parse_exclude_names, remove_group,
+    remove_rule_from_group, selected_cores, separate_shared_apps, set_all_groups_collapsed,
+    set_group_color, set_group_core_selector, set_group_cpu_rate_percent, set_group_enabled,
+    set_group_exclude_names, set_group_is_collapsed, set_group_is_hidden, set_group_use_job_object,
+    set_rules_priority, update_group_properties, update_rule, SeparatedApp,
 };
 
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
@@ -37,7 +41,12 @@ pub struct GroupConfig {
     pub name: String,
     pub cores: Vec<usize>,
     pub is_hidden: bool,
+    pub is_collapsed: bool,
+    pub enabled: bool,
+    /// Accent color, picked or derived from the name.
+    pub color: [u8; 3],
     pub run_all_enabled: bool,
+    pub exclude_names: Vec<String>,
     pub rules: Vec<RuleConfig>,
 }
 
@@ -152,7 +161,11 @@ impl RulesContext {
                 name: group.name.clone(),
                 cores: group.cores.clone(),
                 is_hidden: group.is_hidden,
+                is_collapsed: group.is_collapsed,
+                enabled: group.enabled,
+                color: group.accent_color(),
                 run_all_enabled: group.run_all_button,
+                exclude_names: group.exclude_names.clone(),
                 rules: group
                     .programs
                     .iter()
@@ -202,11 +215,21 @@ impl RulesContext {
         id
     }
 
-    pub fn remove_group(&mut self, group_index: usize) {
+    pub fn remove_group(&mut self, group_index: usize) -> Option<PersistedGroupIdentity> {
         if group_index < self.group_ids.len() {
-            self.group_ids.remove(group_index);
-            self.rule_ids.remove(group_index);
+            return Some(PersistedGroupIdentity {
+                id: self.group_ids.remove(group_index),
+                rule_ids: self.rule_ids.remove(group_index),
+            });
         }
+        None
+    }
+
+    /// Re-inserts identities taken by `remove_group`, clamping the index to the group count.
+    pub fn insert_group(&mut self, group_index: usize, identity: PersistedGroupIdentity) {
+        let group_index = group_index.min(self.group_ids.len());
+        self.group_ids.insert(group_index, identity.id);
+        self.rule_ids.insert(group_index, identity.rule_ids);
     }
 
     pub fn append_rules_to_group(&mut self, group_index: usize, count: usize) {
@@ -342,7 +365,9 @@ impl RulesContext {
 #[cfg(test)]
 mod tests {
     use super::RulesContext;
-    use crate::app::models::{AppStateStorage, AppToRun, CoreGroup, CpuSchema};
+    use crate::app::models::{
+        AppStateStorage, AppToRun, CoreGroup, CoreSelector, CpuSchema, SelfAffinity,
+    };
     use crate::app::shared::ids::{GroupId, RuleId};
     use os_api::PriorityClass;
     use std::path::PathBuf;
@@ -361,7 +386,16 @@ mod tests {
                     false,
                 )],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".into(),
@@ -369,7 +403,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }
@@ -381,7 +431,16 @@ mod tests {
             cores: vec![2, 3],
             programs: vec![],
             is_hidden: false,
+            is_collapsed: false,
             run_all_button: false,
+            single_numa_node: false,
+            default_priority: PriorityClass::Normal,
+            core_selector: CoreSelector::Fixed,
+            exclude_names: Vec::new(),
+            use_job_object: false,
+            cpu_rate_percent: None,
+            enabled: true,
+            color: None,
         }
     }
 
@@ -1,6 +1,8 @@
 use crate::app::adapters::discovery;
-use crate::app::models::{AddAppsOutcome, AppStateStorage, AppToRun, CoreGroup};
-use os_api::InstalledAppCatalogEntry;
+use crate::app::models::{
+    AddAppsOutcome, AppRuntimeKey, AppStateStorage, AppToRun, CoreGroup, CoreSelector,
+};
+use os_api::{InstalledAppCatalogEntry, PriorityClass, PriorityClassName};
 use std::sync::{Arc, RwLock};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -8,6 +10,13 @@ pub struct EditableGroup {
     pub name: String,
     pub selected_cores: Vec<usize>,
     pub run_all_enabled: bool,
+    pub single_numa_node: bool,
+    pub default_priority: PriorityClass,
+    pub core_selector: CoreSelector,
+    pub exclude_names: Vec<String>,
+    pub use_job_object: bool,
+    pub cpu_rate_percent: Option<u8>,
+    pub color: Option<[u8; 3]>,
 }
 
 pub fn set_group_is_hidden(
@@ -25,6 +34,54 @@ pub fn set_group_is_hidden(
     false
 }
 
+pub fn set_group_is_collapsed(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    is_collapsed: bool,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    match state.groups.get_mut(index) {
+        Some(group) if group.is_collapsed != is_collapsed => {
+            group.is_collapsed = is_collapsed;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Returns whether any group changed.
+pub fn set_all_groups_collapsed(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    is_collapsed: bool,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    let mut changed = false;
+    for group in state
+        .groups
+        .iter_mut()
+        .filter(|group| group.is_collapsed != is_collapsed)
+    {
+        group.is_collapsed = is_collapsed;
+        changed = true;
+    }
+    changed
+}
+
+pub fn set_group_enabled(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    enabled: bool,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    match state.groups.get_mut(index) {
+        Some(group) if group.enabled != enabled => {
+            group.enabled = enabled;
+            true
+        }
+        _ => false,
+    }
+}
+
 pub fn move_group_to_index(
     persistent_state: &Arc<RwLock<AppStateStorage>>,
     source_index: usize,
@@ -43,11 +100,34 @@ pub fn move_group_to_index(
     true
 }
 
+/// Name of a group other than `except_index` that `name` collides with. Names are
+/// compared ignoring ASCII case, like `--run-group` resolves them.
+pub fn group_name_conflict(
+    groups: &[CoreGroup],
+    name: &str,
+    except_index: Option<usize>,
+) -> Option<String> {
+    let name = name.trim();
+    groups
+        .iter()
+        .enumerate()
+        .find(|(index, group)| {
+            Some(*index) != except_index && group.name.eq_ignore_ascii_case(name)
+        })
+        .map(|(_, group)| group.name.clone())
+}
+
+fn duplicate_group_name_error(existing: &str) -> String {
+    format!("A group named \"{existing}\" already exists; pick another name")
+}
+
 pub fn create_group(
     persistent_state: &Arc<RwLock<AppStateStorage>>,
     group_name: &str,
     core_selection: &[bool],
     run_all_enabled: bool,
+    single_numa_node: bool,
+    default_priority: PriorityClass,
 ) -> Result<(), String> {
     let group_name_trimmed = group_name.trim();
     if group_name_trimmed.is_empty() {
@@ -60,12 +140,24 @@ pub fn create_group(
     }
 
     let mut state = persistent_state.write().unwrap();
+    if let Some(existing) = group_name_conflict(&state.groups, group_name_trimmed, None) {
+        return Err(duplicate_group_name_error(&existing));
+    }
     state.groups.push(CoreGroup {
         name: group_name_trimmed.to_string(),
         cores: selected_cores,
         programs: vec![],
         is_hidden: false,
+        is_collapsed: false,
         run_all_button: run_all_enabled,
+        single_numa_node,
+        default_priority,
+        core_selector: CoreSelector::Fixed,
+        exclude_names: Vec::new(),
+        use_job_object: false,
+        cpu_rate_percent: None,
+        enabled: true,
+        color: None,
     });
     Ok(())
 }
@@ -76,6 +168,8 @@ pub fn update_group_properties(
     name: String,
     core_selection: &[bool],
     run_all_button: bool,
+    single_numa_node: bool,
+    default_priority: PriorityClass,
 ) -> Result<bool, String> {
     let group_name_trimmed = name.trim();
     if group_name_trimmed.is_empty() {
@@ -89,22 +183,134 @@ pub fn update_group_properties(
 
     let mut state = persistent_state.write().unwrap();
     if index < state.groups.len() {
+        if let Some(existing) = group_name_conflict(&state.groups, group_name_trimmed, Some(index))
+        {
+            return Err(duplicate_group_name_error(&existing));
+        }
         state.groups[index].name = group_name_trimmed.to_string();
         state.groups[index].cores = selected_cores;
         state.groups[index].run_all_button = run_all_button;
+        state.groups[index].single_numa_node = single_numa_node;
+        state.groups[index].default_priority = default_priority;
         Ok(true)
     } else {
         Ok(false)
     }
 }
 
-pub fn remove_group(persistent_state: &Arc<RwLock<AppStateStorage>>, index: usize) -> bool {
+/// Sets how launches of the group pick their cores. Returns whether it changed.
+pub fn set_group_core_selector(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    core_selector: CoreSelector,
+) -> bool {
     let mut state = persistent_state.write().unwrap();
-    if index < state.groups.len() {
-        state.groups.remove(index);
-        return true;
+    match state.groups.get_mut(index) {
+        Some(group) if group.core_selector != core_selector => {
+            group.core_selector = core_selector;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Splits the group editor's exclusion text on commas and new lines, dropping blanks
+/// and repeats.
+pub fn parse_exclude_names(text: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for name in text.split([',', '\n']).map(str::trim) {
+        if !name.is_empty() && !names.iter().any(|known| known.eq_ignore_ascii_case(name)) {
+            names.push(name.to_string());
+        }
+    }
+    names
+}
+
+/// Sets the child processes the group leaves at the default affinity. Returns whether
+/// they changed.
+pub fn set_group_exclude_names(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    exclude_names: Vec<String>,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    match state.groups.get_mut(index) {
+        Some(group) if group.exclude_names != exclude_names => {
+            group.exclude_names = exclude_names;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Sets whether the group's launches run in a Job Object. Returns whether it changed.
+pub fn set_group_use_job_object(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    use_job_object: bool,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    match state.groups.get_mut(index) {
+        Some(group) if group.use_job_object != use_job_object => {
+            group.use_job_object = use_job_object;
+            true
+        }
+        _ => false,
     }
-    false
+}
+
+/// Sets the group's CPU usage cap, clamped to 1..=100 percent. Returns whether it changed.
+pub fn set_group_cpu_rate_percent(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    cpu_rate_percent: Option<u8>,
+) -> bool {
+    let cpu_rate_percent = cpu_rate_percent.map(|percent| percent.clamp(1, 100));
+    let mut state = persistent_state.write().unwrap();
+    match state.groups.get_mut(index) {
+        Some(group) if group.cpu_rate_percent != cpu_rate_percent => {
+            group.cpu_rate_percent = cpu_rate_percent;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Sets the group's accent color, `None` for the name-derived one. Returns whether it changed.
+pub fn set_group_color(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    color: Option<[u8; 3]>,
+) -> bool {
+    let mut state = persistent_state.write().unwrap();
+    match state.groups.get_mut(index) {
+        Some(group) if group.color != color => {
+            group.color = color;
+            true
+        }
+        _ => false,
+    }
+}
+
+pub fn remove_group(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+) -> Option<CoreGroup> {
+    let mut state = persistent_state.write().unwrap();
+    (index < state.groups.len()).then(|| state.groups.remove(index))
+}
+
+/// Puts a removed group back at `index`, or at the end if fewer groups remain.
+/// Returns the index it was inserted at.
+pub fn insert_group(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    index: usize,
+    group: CoreGroup,
+) -> usize {
+    let mut state = persistent_state.write().unwrap();
+    let index = index.min(state.groups.len());
+    state.groups.insert(index, group);
+    index
 }
 
 pub fn load_group_for_edit(
@@ -116,6 +322,13 @@ pub fn load_group_for_edit(
         name: group.name.clone(),
         selected_cores: group.cores.clone(),
         run_all_enabled: group.run_all_button,
+        single_numa_node: group.single_numa_node,
+        default_priority: group.default_priority,
+        core_selector: group.core_selector,
+        exclude_names: group.exclude_names.clone(),
+        use_job_object: group.use_job_object,
+        cpu_rate_percent: group.cpu_rate_percent,
+        color: group.color,
     })
 }
 
@@ -125,22 +338,19 @@ pub fn add_apps_to_group(
     paths: Vec<std::path::PathBuf>,
 ) -> AddAppsOutcome {
     let discovered = discovery::apps_from_dropped_paths(paths);
-    let added_count = discovered.apps.len();
-    let first_error = discovered.first_error.clone();
-
     let mut state = persistent_state.write().unwrap();
-    if let Some(group) = state.groups.get_mut(group_index) {
-        group.programs.extend(discovered.apps);
-        AddAppsOutcome {
-            added_count,
-            first_error,
-        }
-    } else {
-        AddAppsOutcome {
-            added_count: 0,
-            first_error: Some(format!("Group with index {group_index} not found")),
-        }
+    let Some(group) = state.groups.get_mut(group_index) else {
+        return group_not_found(group_index);
+    };
+
+    let mut outcome = AddAppsOutcome {
+        first_error: discovered.first_error,
+        ..AddAppsOutcome::default()
+    };
+    for app in discovered.apps {
+        push_unless_duplicate(group, app, &mut outcome);
     }
+    outcome
 }
 
 pub fn add_installed_app_to_group(
@@ -151,26 +361,44 @@ pub fn add_installed_app_to_group(
     match discovery::app_from_installed_entry(entry) {
         Ok(app) => {
             let mut state = persistent_state.write().unwrap();
-            if let Some(group) = state.groups.get_mut(group_index) {
-                group.programs.push(app);
-                AddAppsOutcome {
-                    added_count: 1,
-                    first_error: None,
-                }
-            } else {
-                AddAppsOutcome {
-                    added_count: 0,
-                    first_error: Some(format!("Group with index {group_index} not found")),
-                }
-            }
+            let Some(group) = state.groups.get_mut(group_index) else {
+                return group_not_found(group_index);
+            };
+            let mut outcome = AddAppsOutcome::default();
+            push_unless_duplicate(group, app, &mut outcome);
+            outcome
         }
         Err(err) => AddAppsOutcome {
-            added_count: 0,
             first_error: Some(err),
+            ..AddAppsOutcome::default()
         },
     }
 }
 
+/// Adds `app` with the group's default priority unless the group already launches the
+/// same target with the same arguments.
+fn push_unless_duplicate(group: &mut CoreGroup, mut app: AppToRun, outcome: &mut AddAppsOutcome) {
+    if group
+        .programs
+        .iter()
+        .any(|existing| existing.launches_same_target(&app))
+    {
+        outcome.skipped_names.push(app.name);
+    } else {
+        outcome.added_count += 1;
+        outcome.added_names.push(app.name.clone());
+        app.priority = group.default_priority;
+        group.programs.push(app);
+    }
+}
+
+fn group_not_found(group_index: usize) -> AddAppsOutcome {
+    AddAppsOutcome {
+        first_error: Some(format!("Group with index {group_index} not found")),
+        ..AddAppsOutcome::default()
+    }
+}
+
 pub fn load_rule(
     persistent_state: &Arc<RwLock<AppStateStorage>>,
     group_idx: usize,
@@ -201,6 +429,28 @@ pub fn update_rule(
     false
 }
 
+/// Sets the priority of the rules at `(group, program)` indices; returns how many exist.
+pub fn set_rules_priority(
+    persistent_state: &Arc<RwLock<AppStateStorage>>,
+    rule_indices: &[(usize, usize)],
+    class: PriorityClassName,
+) -> usize {
+    let mut state = persistent_state.write().unwrap();
+    let mut updated = 0;
+    for &(group_index, program_index) in rule_indices {
+        if let Some(program) = state
+            .groups
+            .get_mut(group_index)
+            .and_then(|group| group.programs.get_mut(program_index))
+        {
+            // Each app keeps its own eco (efficiency) mode; only the class changes.
+            program.priority = PriorityClass::from_parts(class, program.priority.eco_qos());
+            updated += 1;
+        }
+    }
+    updated
+}
+
 pub fn remove_rule_from_group(
     persistent_state: &Arc<RwLock<AppStateStorage>>,
     group_index: usize,
@@ -252,7 +502,134 @@ pub fn move_rule_between_groups_at(
     Some(moved)
 }
 
-fn selected_cores(core_selection: &[bool]) -> Vec<usize> {
+/// Copies a rule into another group and returns the app and target group names.
+///
+/// The copy gets the lowest free `copy_index` so it is tracked apart from the original.
+pub fn clone_rule_to_group(
+    state: &mut AppStateStorage,
+    source_group_index: usize,
+    rule_index: usize,
+    target_group_index: usize,
+) -> Result<(String, String), String> {
+    let mut copy = state
+        .groups
+        .get(source_group_index)
+        .and_then(|group| group.programs.get(rule_index))
+        .cloned()
+        .ok_or_else(|| format!("Rule {rule_index} in group {source_group_index} not found"))?;
+    if source_group_index == target_group_index {
+        return Err("Cannot copy an app into its own group".to_string());
+    }
+    let target = state
+        .groups
+        .get(target_group_index)
+        .ok_or_else(|| format!("Group with index {target_group_index} not found"))?;
+    if target
+        .programs
+        .iter()
+        .any(|existing| existing.launches_same_target(&copy))
+    {
+        return Err(format!(
+            "Group '{}' already launches '{}' with the same arguments",
+            target.name, copy.name
+        ));
+    }
+
+    let taken: Vec<_> = state
+        .groups
+        .iter()
+        .flat_map(|group| group.programs.iter().map(AppToRun::get_key))
+        .collect();
+    next_free_copy_index(&mut copy, &taken);
+
+    let names = (copy.name.clone(), target.name.clone());
+    state.groups[target_group_index].programs.push(copy);
+    Ok(names)
+}
+
+/// Lowest `copy_index` of at least 1 that gives `app` a key not in `taken`.
+fn next_free_copy_index(app: &mut AppToRun, taken: &[AppRuntimeKey]) {
+    app.copy_index = 1;
+    while taken.contains(&app.get_key()) {
+        app.copy_index += 1;
+    }
+}
+
+/// An app whose runtime key changed so it is tracked apart from the same app elsewhere.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeparatedApp {
+    pub group_index: usize,
+    pub old_key: AppRuntimeKey,
+    pub new_key: AppRuntimeKey,
+}
+
+/// Gives every app that shares its runtime key with an app in an earlier group a free
+/// `copy_index`, so each group launches and tracks its own instance.
+pub fn separate_shared_apps(state: &mut AppStateStorage) -> Vec<SeparatedApp> {
+    let mut taken: Vec<AppRuntimeKey> = state
+        .groups
+        .iter()
+        .flat_map(|group| group.programs.iter().map(AppToRun::get_key))
+        .collect();
+    let mut seen = Vec::new();
+    let mut separated = Vec::new();
+    for (group_index, group) in state.groups.iter_mut().enumerate() {
+        let mut seen_in_group = Vec::new();
+        for app in &mut group.programs {
+            let old_key = app.get_key();
+            if seen.contains(&old_key) {
+                next_free_copy_index(app, &taken);
+                let new_key = app.get_key();
+                taken.push(new_key.clone());
+                seen_in_group.push(new_key.clone());
+                separated.push(SeparatedApp {
+                    group_index,
+                    old_key,
+                    new_key,
+                });
+            } else {
+                seen_in_group.push(old_key);
+            }
+        }
+        seen.extend(seen_in_group);
+    }
+    separated
+}
+
+/// Undoes [`separate_shared_apps`]: every app with a `copy_index` goes back to the
+/// shared key, so the groups launch and track one instance again. Copies made with
+/// Copy to group are merged as well.
+pub fn merge_separated_apps(state: &mut AppStateStorage) -> Vec<SeparatedApp> {
+    let mut merged = Vec::new();
+    for (group_index, group) in state.groups.iter_mut().enumerate() {
+        for app_index in 0..group.programs.len() {
+            let app = &group.programs[app_index];
+            if app.copy_index == 0 {
+                continue;
+            }
+            let old_key = app.get_key();
+            let mut shared = app.clone();
+            shared.copy_index = 0;
+            let new_key = shared.get_key();
+            if group
+                .programs
+                .iter()
+                .any(|other| other.get_key() == new_key)
+            {
+                continue;
+            }
+            group.programs[app_index].copy_index = 0;
+            merged.push(SeparatedApp {
+                group_index,
+                old_key,
+                new_key,
+            });
+        }
+    }
+    merged
+}
+
+pub fn selected_cores(core_selection: &[bool]) -> Vec<usize> {
     core_selection
         .iter()
         .enumerate()
@@ -263,8 +640,8 @@ fn selected_cores(core_selection: &[bool]) -> Vec<usize> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::app::models::{CpuSchema, LaunchTarget};
-    use os_api::PriorityClass;
+    use crate::app::models::{CpuSchema, LaunchTarget, SelfAffinity, SpreadStrategy};
+    use os_api::{IoPriority, PriorityClass, WindowShow};
     use std::path::PathBuf;
 
     fn sample_persistent_state() -> Arc<RwLock<AppStateStorage>> {
@@ -283,9 +660,33 @@ mod tests {
                     additional_processes: vec![],
                     autorun: false,
                     priority: PriorityClass::Normal,
+                    window_show: WindowShow::Normal,
+                    thread_affinity_rules: Vec::new(),
+                    match_pattern: None,
+                    copy_index: 0,
+                    spread: SpreadStrategy::All,
+                    post_launch_cmd: None,
+                    autorun_order: 0,
+                    autorun_delay_ms: None,
+                    launch_barrier: None,
+                    smt_off: false,
+                    watch_autoapply: false,
+                    focus_on_relaunch: true,
+                    io_priority: IoPriority::Normal,
+                    inherit_affinity: false,
+                    use_shell_execute: false,
                 }],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".to_string(),
@@ -293,7 +694,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }))
@@ -302,7 +719,15 @@ mod tests {
     #[test]
     fn test_create_and_edit_group() {
         let persistent_state = sample_persistent_state();
-        create_group(&persistent_state, "Work", &[true, false, true, false], true).unwrap();
+        create_group(
+            &persistent_state,
+            "Work",
+            &[true, false, true, false],
+            true,
+            true,
+            PriorityClass::High,
+        )
+        .unwrap();
         assert_eq!(persistent_state.read().unwrap().groups.len(), 2);
 
         let editable = load_group_for_edit(&persistent_state, 0).unwrap();
@@ -310,15 +735,90 @@ mod tests {
         assert_eq!(editable.selected_cores, vec![1, 3]);
         assert!(editable.run_all_enabled);
 
+        assert!(!editable.single_numa_node);
+        assert!(persistent_state.read().unwrap().groups[1].single_numa_node);
+        assert_eq!(
+            persistent_state.read().unwrap().groups[1].default_priority,
+            PriorityClass::High
+        );
+
         assert!(update_group_properties(
             &persistent_state,
             0,
             "Edited".to_string(),
             &[false, true, false, true],
             false,
+            true,
+            PriorityClass::AboveNormal,
         )
         .unwrap());
         assert_eq!(persistent_state.read().unwrap().groups[0].name, "Edited");
+        assert!(persistent_state.read().unwrap().groups[0].single_numa_node);
+        assert_eq!(
+            persistent_state.read().unwrap().groups[0].default_priority,
+            PriorityClass::AboveNormal
+        );
+    }
+
+    #[test]
+    fn test_group_names_must_be_unique_ignoring_case() {
+        let persistent_state = sample_persistent_state();
+        let duplicate = "A group named \"Games\" already exists; pick another name".to_string();
+
+        assert_eq!(
+            create_group(
+                &persistent_state,
+                " games ",
+                &[true, false],
+                true,
+                false,
+                PriorityClass::Normal
+            ),
+            Err(duplicate.clone())
+        );
+        create_group(
+            &persistent_state,
+            "Work",
+            &[true, false],
+            true,
+            false,
+            PriorityClass::Normal,
+        )
+        .unwrap();
+        assert_eq!(
+            update_group_properties(
+                &persistent_state,
+                1,
+                "GAMES".to_string(),
+                &[true, false],
+                true,
+                false,
+                PriorityClass::Normal,
+            ),
+            Err(duplicate.clone())
+        );
+        // Renaming a group to a different case of its own name is allowed.
+        assert_eq!(
+            update_group_properties(
+                &persistent_state,
+                0,
+                "GAMES".to_string(),
+                &[true, false],
+                true,
+                false,
+                PriorityClass::Normal,
+            ),
+            Ok(true)
+        );
+
+        let state = persistent_state.read().unwrap();
+        assert_eq!(state.groups.len(), 2);
+        assert_eq!(state.groups[1].name, "Work");
+        assert_eq!(
+            group_name_conflict(&state.groups, "work", Some(0)),
+            Some("Work".to_string())
+        );
+        assert_eq!(group_name_conflict(&state.groups, "work", Some(1)), None);
     }
 
     #[test]
@@ -326,11 +826,25 @@ mod tests {
         let persistent_state = sample_persistent_state();
 
         assert_eq!(
-            create_group(&persistent_state, "   ", &[true, false], true),
+            create_group(
+                &persistent_state,
+                "   ",
+                &[true, false],
+                true,
+                false,
+                PriorityClass::Normal
+            ),
             Err("Group name cannot be empty".to_string())
         );
         assert_eq!(
-            create_group(&persistent_state, "No Cores", &[false, false], true),
+            create_group(
+                &persistent_state,
+                "No Cores",
+                &[false, false],
+                true,
+                false,
+                PriorityClass::Normal
+            ),
             Err("At least one core must be selected".to_string())
         );
 
@@ -344,15 +858,39 @@ mod tests {
         let persistent_state = sample_persistent_state();
 
         assert_eq!(
-            update_group_properties(&persistent_state, 0, " ".to_string(), &[true], false),
+            update_group_properties(
+                &persistent_state,
+                0,
+                " ".to_string(),
+                &[true],
+                false,
+                false,
+                PriorityClass::Normal
+            ),
             Err("Group name cannot be empty".to_string())
         );
         assert_eq!(
-            update_group_properties(&persistent_state, 0, "Edited".to_string(), &[false], false),
+            update_group_properties(
+                &persistent_state,
+                0,
+                "Edited".to_string(),
+                &[false],
+                false,
+                false,
+                PriorityClass::Normal
+            ),
             Err("At least one core must be selected".to_string())
         );
         assert_eq!(
-            update_group_properties(&persistent_state, 99, "Missing".to_string(), &[true], false),
+            update_group_properties(
+                &persistent_state,
+                99,
+                "Missing".to_string(),
+                &[true],
+                false,
+                false,
+                PriorityClass::Normal
+            ),
             Ok(false)
         );
 
@@ -366,7 +904,15 @@ mod tests {
     #[test]
     fn test_group_visibility_and_move_report_only_real_changes() {
         let persistent_state = sample_persistent_state();
-        create_group(&persistent_state, "Work", &[true, false], false).unwrap();
+        create_group(
+            &persistent_state,
+            "Work",
+            &[true, false],
+            false,
+            false,
+            PriorityClass::Normal,
+        )
+        .unwrap();
 
         assert!(set_group_is_hidden(&persistent_state, 0, true));
         assert!(!set_group_is_hidden(&persistent_state, 0, true));
@@ -381,10 +927,91 @@ mod tests {
         assert!(state.groups[1].is_hidden);
     }
 
+    #[test]
+    fn test_collapsing_groups_leaves_the_hidden_app_list_alone() {
+        let persistent_state = sample_persistent_state();
+        create_group(
+            &persistent_state,
+            "Work",
+            &[true, false],
+            false,
+            false,
+            PriorityClass::Normal,
+        )
+        .unwrap();
+        assert!(set_group_is_hidden(&persistent_state, 0, true));
+
+        assert!(set_group_is_collapsed(&persistent_state, 1, true));
+        assert!(!set_group_is_collapsed(&persistent_state, 1, true));
+        assert!(set_all_groups_collapsed(&persistent_state, true));
+        assert!(!set_all_groups_collapsed(&persistent_state, true));
+        {
+            let state = persistent_state.read().unwrap();
+            assert!(state.groups.iter().all(|group| group.is_collapsed));
+            assert!(state.groups[0].is_hidden);
+            assert!(!state.groups[1].is_hidden);
+        }
+
+        assert!(set_all_groups_collapsed(&persistent_state, false));
+        let state = persistent_state.read().unwrap();
+        assert!(state.groups.iter().all(|group| !group.is_collapsed));
+        assert!(state.groups[0].is_hidden);
+    }
+
+    #[test]
+    fn test_group_exclude_names_are_parsed_and_report_changes() {
+        let persistent_state = sample_persistent_state();
+        let names = parse_exclude_names(" EasyAntiCheat.exe, ,BEService.exe\neasyanticheat.exe ");
+        assert_eq!(names, vec!["EasyAntiCheat.exe", "BEService.exe"]);
+
+        assert!(set_group_exclude_names(&persistent_state, 0, names.clone()));
+        assert!(!set_group_exclude_names(
+            &persistent_state,
+            0,
+            names.clone()
+        ));
+        assert!(!set_group_exclude_names(
+            &persistent_state,
+            99,
+            names.clone()
+        ));
+        assert_eq!(
+            persistent_state.read().unwrap().groups[0].exclude_names,
+            names
+        );
+    }
+
+    #[test]
+    fn test_group_cpu_rate_percent_is_clamped_and_reports_changes() {
+        let persistent_state = sample_persistent_state();
+
+        assert!(set_group_cpu_rate_percent(&persistent_state, 0, Some(0)));
+        assert_eq!(
+            persistent_state.read().unwrap().groups[0].cpu_rate_percent,
+            Some(1)
+        );
+        assert!(set_group_cpu_rate_percent(&persistent_state, 0, Some(250)));
+        assert!(!set_group_cpu_rate_percent(&persistent_state, 0, Some(100)));
+        assert!(set_group_cpu_rate_percent(&persistent_state, 0, None));
+        assert!(!set_group_cpu_rate_percent(&persistent_state, 99, Some(30)));
+        assert_eq!(
+            persistent_state.read().unwrap().groups[0].cpu_rate_percent,
+            None
+        );
+    }
+
     #[test]
     fn test_move_rule_between_groups_rejects_invalid_indices_without_mutation() {
         let persistent_state = sample_persistent_state();
-        create_group(&persistent_state, "Work", &[true, false], false).unwrap();
+        create_group(
+            &persistent_state,
+            "Work",
+            &[true, false],
+            false,
+            false,
+            PriorityClass::Normal,
+        )
+        .unwrap();
         let mut state = persistent_state.write().unwrap();
         let before = state
             .groups
@@ -434,13 +1061,163 @@ mod tests {
             additional_processes: vec!["helper.exe".to_string()],
             autorun: true,
             priority: PriorityClass::High,
+            window_show: WindowShow::Hidden,
+            thread_affinity_rules: Vec::new(),
+            match_pattern: None,
+            copy_index: 0,
+            spread: SpreadStrategy::All,
+            post_launch_cmd: None,
+            autorun_order: 0,
+            autorun_delay_ms: None,
+            launch_barrier: None,
+            smt_off: false,
+            watch_autoapply: false,
+            focus_on_relaunch: true,
+            io_priority: IoPriority::Normal,
+            inherit_affinity: false,
+            use_shell_execute: false,
         };
 
         assert!(update_rule(&persistent_state, 0, 0, updated));
+        assert_eq!(
+            set_rules_priority(
+                &persistent_state,
+                &[(0, 0), (0, 5)],
+                PriorityClassName::Idle
+            ),
+            1
+        );
+        assert_eq!(
+            persistent_state.read().unwrap().groups[0].programs[0].priority,
+            PriorityClass::Idle
+        );
         let removed = remove_rule_from_group(&persistent_state, 0, 0);
         assert_eq!(removed.as_deref(), Some(r"C:\Updated.exe"));
         assert!(persistent_state.read().unwrap().groups[0]
             .programs
             .is_empty());
     }
+
+    #[test]
+    fn test_clone_rule_to_group_gives_each_copy_its_own_runtime_key() {
+        let persistent_state = sample_persistent_state();
+        for name in ["Work", "Stream"] {
+            create_group(
+                &persistent_state,
+                name,
+                &[true, false],
+                false,
+                false,
+                PriorityClass::Normal,
+            )
+            .unwrap();
+        }
+        let mut state = persistent_state.write().unwrap();
+
+        assert_eq!(
+            clone_rule_to_group(&mut state, 0, 0, 1),
+            Ok(("Sample".to_string(), "Work".to_string()))
+        );
+        assert!(clone_rule_to_group(&mut state, 0, 0, 1).is_err());
+        assert!(clone_rule_to_group(&mut state, 0, 0, 0).is_err());
+        assert!(clone_rule_to_group(&mut state, 0, 9, 1).is_err());
+        clone_rule_to_group(&mut state, 1, 0, 2).unwrap();
+
+        let copies = [
+            &state.groups[0].programs[0],
+            &state.groups[1].programs[0],
+            &state.groups[2].programs[0],
+        ];
+        assert_eq!(
+            copies.map(|app| app.copy_index),
+            [0, 1, 2],
+            "each copy takes the next free index"
+        );
+        assert_ne!(copies[0].get_key(), copies[1].get_key());
+        assert_ne!(copies[1].get_key(), copies[2].get_key());
+        assert_eq!(state.groups[1].programs.len(), 1);
+    }
+
+    #[test]
+    fn test_separate_shared_apps_rekeys_later_groups_only() {
+        let persistent_state = sample_persistent_state();
+        for name in ["Work", "Stream"] {
+            create_group(
+                &persistent_state,
+                name,
+                &[true, false],
+                false,
+                false,
+                PriorityClass::Normal,
+            )
+            .unwrap();
+        }
+        let mut state = persistent_state.write().unwrap();
+        let shared = state.groups[0].programs[0].clone();
+        state.groups[1].programs.push(shared.clone());
+        state.groups[2].programs.push(shared.clone());
+
+        let separated = separate_shared_apps(&mut state);
+
+        let shared_key = shared.get_key();
+        assert_eq!(
+            separated
+                .iter()
+                .map(|app| (app.group_index, &app.old_key))
+                .collect::<Vec<_>>(),
+            vec![(1, &shared_key), (2, &shared_key)]
+        );
+        assert_eq!(state.groups[0].programs[0].get_key(), shared_key);
+        assert_eq!(state.groups[1].programs[0].copy_index, 1);
+        assert_eq!(state.groups[2].programs[0].copy_index, 2);
+        assert!(separate_shared_apps(&mut state).is_empty());
+
+        let merged = merge_separated_apps(&mut state);
+        assert_eq!(
+            merged
+                .iter()
+                .map(|app| (app.group_index, &app.new_key))
+                .collect::<Vec<_>>(),
+            vec![(1, &shared_key), (2, &shared_key)]
+        );
+        assert!(state
+            .groups
+            .iter()
+            .all(|group| group.programs[0].get_key() == shared_key));
+        assert!(merge_separated_apps(&mut state).is_empty());
+    }
+
+    #[test]
+    fn test_push_unless_duplicate_skips_same_target_and_args() {
+        let persistent_state = sample_persistent_state();
+        let mut state = persistent_state.write().unwrap();
+        let group = &mut state.groups[0];
+        let existing = group.programs[0].clone();
+        let mut with_args = existing.clone();
+        with_args.args = vec!["--safe".to_string()];
+        let mut outcome = AddAppsOutcome::default();
+
+        push_unless_duplicate(group, existing, &mut outcome);
+        push_unless_duplicate(group, with_args, &mut outcome);
+
+        assert_eq!(outcome.added_count, 1);
+        assert_eq!(outcome.skipped_names, vec!["Sample".to_string()]);
+        assert_eq!(group.programs.len(), 2);
+    }
+
+    #[test]
+    fn test_new_apps_inherit_group_default_priority() {
+        let persistent_state = sample_persistent_state();
+        let mut state = persistent_state.write().unwrap();
+        let group = &mut state.groups[0];
+        group.default_priority = PriorityClass::High;
+        let mut added = group.programs[0].clone();
+        added.args = vec!["--safe".to_string()];
+        let mut outcome = AddAppsOutcome::default();
+
+        push_unless_duplicate(group, added, &mut outcome);
+
+        assert_eq!(group.programs[0].priority, PriorityClass::Normal);
+        assert_eq!(group.programs[1].priority, PriorityClass::High);
+    }
 }
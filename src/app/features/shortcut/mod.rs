@@ -200,7 +200,9 @@ fn shortcut_filename(display_name: &str, attempt: usize) -> String {
 mod tests {
     use super::*;
     use crate::app::features::rules::RulesContext;
-    use crate::app::models::{AppStateStorage, AppToRun, CoreGroup, CpuSchema};
+    use crate::app::models::{
+        AppStateStorage, AppToRun, CoreGroup, CoreSelector, CpuSchema, SelfAffinity,
+    };
     use os_api::PriorityClass;
     use std::collections::HashSet;
 
@@ -324,7 +326,16 @@ mod tests {
                 cores: vec![0, 1],
                 programs,
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".to_string(),
@@ -332,7 +343,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 7,
             pending_pre_v6_backup: false,
         };
@@ -400,7 +427,16 @@ mod tests {
             cores: vec![2, 3],
             programs: Vec::new(),
             is_hidden: false,
+            is_collapsed: false,
             run_all_button: true,
+            single_numa_node: false,
+            default_priority: PriorityClass::Normal,
+            core_selector: CoreSelector::Fixed,
+            exclude_names: Vec::new(),
+            use_job_object: false,
+            cpu_rate_percent: None,
+            enabled: true,
+            color: None,
         });
         let old_group_id = rules.group_id_for_index(0).unwrap();
         let moved_rule_id = rules.rule_id_for_index(0, 0).unwrap();
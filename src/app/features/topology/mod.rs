@@ -1,3 +1,7 @@
+use crate::app::models::{AppToRun, CoreType, CpuSchema, SpreadStrategy};
+use os_api::OS;
+use std::collections::BTreeMap;
+
 pub fn detect_cpu_model(test_override: &str) -> String {
     #[allow(clippy::const_is_empty)]
     if !test_override.is_empty() {
@@ -15,3 +19,396 @@ pub fn detect_total_threads(test_override: usize) -> usize {
         num_cpus::get()
     }
 }
+
+/// Groups `cores` by NUMA node, skipping cores whose node cannot be determined.
+pub fn numa_nodes_for_cores(
+    cores: &[usize],
+    node_of: impl Fn(usize) -> Option<u16>,
+) -> BTreeMap<u16, Vec<usize>> {
+    let mut nodes: BTreeMap<u16, Vec<usize>> = BTreeMap::new();
+    for &core in cores {
+        if let Some(node) = node_of(core) {
+            nodes.entry(node).or_default().push(core);
+        }
+    }
+    nodes
+}
+
+/// Picks the node holding most of `cores`; ties go to the lowest node index.
+pub fn preferred_numa_node(cores: &[usize], node_of: impl Fn(usize) -> Option<u16>) -> Option<u16> {
+    numa_nodes_for_cores(cores, node_of)
+        .into_iter()
+        .max_by(|(left_node, left), (right_node, right)| {
+            left.len().cmp(&right.len()).then(right_node.cmp(left_node))
+        })
+        .map(|(node, _)| node)
+}
+
+/// Editor warning shown when a single-node group selects threads from several nodes.
+pub fn numa_span_warning(
+    cores: &[usize],
+    node_of: impl Fn(usize) -> Option<u16> + Copy,
+) -> Option<String> {
+    let nodes = numa_nodes_for_cores(cores, node_of);
+    if nodes.len() < 2 {
+        return None;
+    }
+
+    let preferred = preferred_numa_node(cores, node_of)?;
+    let listed = nodes
+        .keys()
+        .map(|node| node.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    Some(format!(
+        "Selected threads span NUMA nodes {listed}; apps will prefer node {preferred}"
+    ))
+}
+
+/// Cores shared by two groups.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupCoreOverlap {
+    pub first: String,
+    pub second: String,
+    pub cores: Vec<usize>,
+}
+
+/// Lists every pair of groups that share cores, in group order.
+pub fn group_core_overlaps(groups: &[(String, Vec<usize>)]) -> Vec<GroupCoreOverlap> {
+    let mut overlaps = Vec::new();
+    for (index, (first, first_cores)) in groups.iter().enumerate() {
+        for (second, second_cores) in &groups[index + 1..] {
+            let mut cores: Vec<usize> = first_cores
+                .iter()
+                .copied()
+                .filter(|core| second_cores.contains(core))
+                .collect();
+            if cores.is_empty() {
+                continue;
+            }
+            cores.sort_unstable();
+            cores.dedup();
+            overlaps.push(GroupCoreOverlap {
+                first: first.clone(),
+                second: second.clone(),
+                cores,
+            });
+        }
+    }
+    overlaps
+}
+
+/// The `count` idlest of `candidates` by per-core busy fraction, sorted by index.
+///
+/// Cores missing from `usage` count as fully busy and ties keep candidate order;
+/// without `usage` the first `count` candidates are taken.
+pub fn least_loaded_cores(candidates: &[usize], count: usize, usage: Option<&[f32]>) -> Vec<usize> {
+    let mut ranked = candidates.to_vec();
+    if let Some(usage) = usage {
+        let load = |core: &usize| usage.get(*core).copied().unwrap_or(1.0);
+        ranked.sort_by(|a, b| load(a).total_cmp(&load(b)));
+    }
+    ranked.truncate(count);
+    ranked.sort_unstable();
+    ranked
+}
+
+/// The cores of `cores` an app with `strategy` runs on, sorted by index.
+///
+/// Threads that share a label within a schema cluster are one physical core; threads
+/// the schema doesn't know are their own core. Never returns fewer than one core when
+/// `cores` is non-empty.
+pub fn spread_cores(cores: &[usize], strategy: SpreadStrategy, schema: &CpuSchema) -> Vec<usize> {
+    let mut sorted = cores.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    match strategy {
+        SpreadStrategy::All => sorted,
+        SpreadStrategy::Pack { count } => {
+            sorted.truncate(count.max(1));
+            sorted
+        }
+        SpreadStrategy::Spread => {
+            let physical_of = |core: usize| {
+                schema
+                    .clusters
+                    .iter()
+                    .enumerate()
+                    .find_map(|(cluster_index, cluster)| {
+                        cluster
+                            .cores
+                            .iter()
+                            .find(|info| info.index == core)
+                            .map(|info| (cluster_index, info.label.clone()))
+                    })
+                    .unwrap_or((usize::MAX, core.to_string()))
+            };
+            let mut physical_order = Vec::new();
+            for &core in &sorted {
+                let physical = physical_of(core);
+                if !physical_order.contains(&physical) {
+                    physical_order.push(physical);
+                }
+            }
+            let kept: Vec<_> = physical_order.into_iter().step_by(2).collect();
+            sorted.retain(|&core| kept.contains(&physical_of(core)));
+            sorted
+        }
+    }
+}
+
+/// Affinity mask for `cores` shaped by `strategy`; see [`spread_cores`].
+pub fn compute_mask(cores: &[usize], strategy: SpreadStrategy, schema: &CpuSchema) -> usize {
+    OS::mask_from_cores(spread_cores(cores, strategy, schema))
+}
+
+/// The primary thread of each physical core in `cores`, sorted by index.
+///
+/// Siblings are the threads the schema marks as hyper-threads; without a schema, odd
+/// thread indices are assumed to be the siblings. Never returns fewer than one core
+/// when `cores` is non-empty.
+pub fn smt_off_cores(cores: &[usize], schema: &CpuSchema) -> Vec<usize> {
+    let mut sorted = cores.to_vec();
+    sorted.sort_unstable();
+    sorted.dedup();
+
+    let is_sibling = |core: usize| {
+        if schema.clusters.is_empty() {
+            return core % 2 == 1;
+        }
+        schema
+            .clusters
+            .iter()
+            .flat_map(|cluster| &cluster.cores)
+            .any(|info| info.index == core && info.core_type == CoreType::HyperThreading)
+    };
+    let primary: Vec<usize> = sorted
+        .iter()
+        .copied()
+        .filter(|&core| !is_sibling(core))
+        .collect();
+    if primary.is_empty() {
+        sorted.truncate(1);
+        return sorted;
+    }
+    primary
+}
+
+/// The cores an app runs on: SMT siblings are dropped from the group's `cores` when
+/// the app has SMT off, then its spread strategy shapes what is left.
+pub fn app_cores(cores: &[usize], app: &AppToRun, schema: &CpuSchema) -> Vec<usize> {
+    spread_cores(&smt_candidates(cores, app, schema), app.spread, schema)
+}
+
+/// Affinity mask for the cores an app runs on; see [`app_cores`].
+pub fn app_mask(cores: &[usize], app: &AppToRun, schema: &CpuSchema) -> usize {
+    compute_mask(&smt_candidates(cores, app, schema), app.spread, schema)
+}
+
+fn smt_candidates(cores: &[usize], app: &AppToRun, schema: &CpuSchema) -> Vec<usize> {
+    if app.smt_off {
+        smt_off_cores(cores, schema)
+    } else {
+        cores.to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        app_mask, compute_mask, group_core_overlaps, least_loaded_cores, numa_nodes_for_cores,
+        numa_span_warning, preferred_numa_node, smt_off_cores, spread_cores, GroupCoreOverlap,
+    };
+    use crate::app::models::cpu_presets::get_preset_for_model;
+    use crate::app::models::{AppToRun, CoreInfo, CoreType, CpuCluster, CpuSchema, SpreadStrategy};
+    use os_api::PriorityClass;
+
+    /// Four hyper-threaded P-cores (threads 0-7) and two E-cores (8-9).
+    fn hybrid_schema() -> CpuSchema {
+        let p_cores = (0..8)
+            .map(|index| CoreInfo {
+                index,
+                core_type: if index % 2 == 0 {
+                    CoreType::Performance
+                } else {
+                    CoreType::HyperThreading
+                },
+                label: format!("P{}", index / 2),
+            })
+            .collect();
+        let e_cores = (8..10)
+            .map(|index| CoreInfo {
+                index,
+                core_type: CoreType::Efficient,
+                label: format!("E{}", index - 8),
+            })
+            .collect();
+        CpuSchema {
+            model: "Test CPU".to_string(),
+            clusters: vec![
+                CpuCluster {
+                    name: "Performance".to_string(),
+                    cores: p_cores,
+                },
+                CpuCluster {
+                    name: "Efficient".to_string(),
+                    cores: e_cores,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_compute_mask_shapes_group_cores_by_strategy() {
+        let schema = hybrid_schema();
+        let cores = [7, 6, 5, 4, 3, 2, 1, 0];
+
+        assert_eq!(compute_mask(&cores, SpreadStrategy::All, &schema), 0xFF);
+        assert_eq!(
+            compute_mask(&cores, SpreadStrategy::Pack { count: 4 }, &schema),
+            0x0F
+        );
+        assert_eq!(
+            compute_mask(&cores, SpreadStrategy::Pack { count: 0 }, &schema),
+            0x01
+        );
+        // P0 and P2 with both of their threads
+        assert_eq!(compute_mask(&cores, SpreadStrategy::Spread, &schema), 0x33);
+        // E-cores are one thread each; a lone core is kept
+        assert_eq!(
+            spread_cores(&[8, 9, 2, 3], SpreadStrategy::Spread, &schema),
+            vec![2, 3, 9]
+        );
+        assert_eq!(spread_cores(&[5], SpreadStrategy::Spread, &schema), vec![5]);
+    }
+
+    #[test]
+    fn test_spread_without_schema_treats_each_thread_as_a_core() {
+        let schema = CpuSchema {
+            model: "Generic CPU".to_string(),
+            clusters: Vec::new(),
+        };
+
+        assert_eq!(
+            spread_cores(&[0, 1, 2, 3, 4], SpreadStrategy::Spread, &schema),
+            vec![0, 2, 4]
+        );
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_compute_mask_keeps_threads_above_31() {
+        let schema = CpuSchema {
+            model: "Generic CPU".to_string(),
+            clusters: Vec::new(),
+        };
+
+        assert_eq!(
+            compute_mask(&[1, 33, 63, 64], SpreadStrategy::All, &schema),
+            (1 << 63) | (1 << 33) | 0b10
+        );
+    }
+
+    #[test]
+    fn test_smt_off_drops_hyper_thread_siblings_of_13900k() {
+        let schema = get_preset_for_model("13th Gen Intel(R) Core(TM) i9-13900K", 32).unwrap();
+        let cores: Vec<usize> = (0..32).collect();
+
+        let primary = smt_off_cores(&cores, &schema);
+        let expected: Vec<usize> = (0..16).step_by(2).chain(16..32).collect();
+        assert_eq!(primary, expected);
+
+        let mut app = AppToRun::new_installed(
+            "Game".to_string(),
+            "Game!App".to_string(),
+            PriorityClass::Normal,
+            false,
+        );
+        assert_eq!(app_mask(&cores[..8], &app, &schema), 0xFF);
+        app.smt_off = true;
+        assert_eq!(app_mask(&cores[..8], &app, &schema), 0x55);
+        // A lone sibling is still a core to run on
+        assert_eq!(smt_off_cores(&[3], &schema), vec![3]);
+    }
+
+    #[test]
+    fn test_smt_off_without_schema_drops_odd_threads() {
+        let schema = CpuSchema {
+            model: "Generic CPU".to_string(),
+            clusters: Vec::new(),
+        };
+
+        assert_eq!(smt_off_cores(&[5, 4, 3, 2], &schema), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_least_loaded_cores_prefers_idle_known_cores() {
+        let usage = [0.9, 0.1, 0.5, 0.1, 0.0];
+        assert_eq!(
+            least_loaded_cores(&[0, 1, 2, 3], 2, Some(&usage)),
+            vec![1, 3]
+        );
+        assert_eq!(least_loaded_cores(&[8, 0, 2], 2, Some(&usage)), vec![0, 2]);
+        assert_eq!(least_loaded_cores(&[2, 0, 3], 2, None), vec![0, 2]);
+        assert_eq!(least_loaded_cores(&[2, 0], 5, Some(&usage)), vec![0, 2]);
+    }
+
+    fn two_node_layout(core: usize) -> Option<u16> {
+        match core {
+            0..=3 => Some(0),
+            4..=7 => Some(1),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn test_numa_nodes_for_cores_groups_known_cores() {
+        let nodes = numa_nodes_for_cores(&[0, 5, 1, 9], two_node_layout);
+        assert_eq!(nodes.len(), 2);
+        assert_eq!(nodes[&0], vec![0, 1]);
+        assert_eq!(nodes[&1], vec![5]);
+    }
+
+    #[test]
+    fn test_preferred_numa_node_picks_majority_then_lowest() {
+        assert_eq!(preferred_numa_node(&[0, 4, 5], two_node_layout), Some(1));
+        assert_eq!(preferred_numa_node(&[0, 4], two_node_layout), Some(0));
+        assert_eq!(preferred_numa_node(&[9], two_node_layout), None);
+    }
+
+    #[test]
+    fn test_numa_span_warning_only_for_multiple_nodes() {
+        assert_eq!(numa_span_warning(&[0, 1, 2], two_node_layout), None);
+        assert_eq!(
+            numa_span_warning(&[0, 4, 5], two_node_layout).as_deref(),
+            Some("Selected threads span NUMA nodes 0, 1; apps will prefer node 1")
+        );
+    }
+
+    #[test]
+    fn test_group_core_overlaps_lists_each_sharing_pair() {
+        let groups = vec![
+            ("Games".to_string(), vec![4, 0, 1, 2]),
+            ("Work".to_string(), vec![5, 6]),
+            ("Stream".to_string(), vec![2, 4, 6]),
+        ];
+
+        assert_eq!(
+            group_core_overlaps(&groups),
+            vec![
+                GroupCoreOverlap {
+                    first: "Games".into(),
+                    second: "Stream".into(),
+                    cores: vec![2, 4],
+                },
+                GroupCoreOverlap {
+                    first: "Work".into(),
+                    second: "Stream".into(),
+                    cores: vec![6],
+                },
+            ]
+        );
+        assert!(group_core_overlaps(&groups[..2]).is_empty());
+    }
+}
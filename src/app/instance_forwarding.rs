@@ -1,6 +1,6 @@
 use crate::app::runtime::RunRuleOutcome;
 use crate::app::shared::ids::{GroupId, RuleId};
-use crate::app::startup::{is_cli_safe_id, StartupIntent};
+use crate::app::startup::{is_cli_safe_id, is_cli_safe_name, StartupIntent};
 use serde::{Deserialize, Serialize};
 #[cfg(test)]
 use std::sync::mpsc::Sender;
@@ -19,9 +19,20 @@ pub(crate) const EXIT_TIMEOUT: i32 = 24;
 pub(crate) const EXIT_PROTOCOL_ERROR: i32 = 25;
 pub(crate) const EXIT_AUTH_FAILED: i32 = 26;
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum IpcCommand {
-    RunRule { group_id: GroupId, rule_id: RuleId },
+    RunRule {
+        group_id: GroupId,
+        rule_id: RuleId,
+    },
+    RunGroup {
+        group_name: String,
+    },
+    RunApp {
+        group_name: String,
+        app_name: String,
+    },
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
@@ -56,6 +67,7 @@ pub(crate) enum IpcProtocolError {
     InvalidJson,
     UnsupportedVersion,
     InvalidId,
+    InvalidName,
     SerializeFailed,
 }
 
@@ -74,13 +86,14 @@ pub(crate) enum ForwardingClientError {
     ServerNotReady,
     Timeout,
     SecurityRejected(String),
-    #[cfg_attr(not(feature = "windows"), allow(dead_code))]
+    #[cfg_attr(not(any(feature = "windows", feature = "linux")), allow(dead_code))]
     Io(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) enum EntryAction {
     RunGui(StartupIntent),
+    RunHeadless(StartupIntent),
     Exit(i32),
 }
 
@@ -124,10 +137,21 @@ struct WireCommandFrame {
     command: WireCommand,
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
 enum WireCommand {
-    RunRule { group_id: String, rule_id: String },
+    RunRule {
+        group_id: String,
+        rule_id: String,
+    },
+    RunGroup {
+        group_name: String,
+    },
+    RunApp {
+        group_name: String,
+        app_name: String,
+    },
 }
 
 #[derive(Debug, Serialize)]
@@ -137,10 +161,21 @@ struct WireCommandFrameOut {
     command: WireCommandOut,
 }
 
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum WireCommandOut {
-    RunRule { group_id: String, rule_id: String },
+    RunRule {
+        group_id: String,
+        rule_id: String,
+    },
+    RunGroup {
+        group_name: String,
+    },
+    RunApp {
+        group_name: String,
+        app_name: String,
+    },
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -176,6 +211,26 @@ pub(crate) fn parse_ipc_command_frame(frame: &[u8]) -> Result<IpcCommand, IpcPro
                 rule_id: RuleId(rule_id),
             })
         }
+        WireCommand::RunGroup { group_name } => {
+            if !is_cli_safe_name(&group_name) {
+                return Err(IpcProtocolError::InvalidName);
+            }
+
+            Ok(IpcCommand::RunGroup { group_name })
+        }
+        WireCommand::RunApp {
+            group_name,
+            app_name,
+        } => {
+            if !is_cli_safe_name(&group_name) || !is_cli_safe_name(&app_name) {
+                return Err(IpcProtocolError::InvalidName);
+            }
+
+            Ok(IpcCommand::RunApp {
+                group_name,
+                app_name,
+            })
+        }
     }
 }
 
@@ -187,6 +242,16 @@ pub(crate) fn serialize_ipc_command_frame(
             group_id: group_id.0.clone(),
             rule_id: rule_id.0.clone(),
         },
+        IpcCommand::RunGroup { group_name } => WireCommandOut::RunGroup {
+            group_name: group_name.clone(),
+        },
+        IpcCommand::RunApp {
+            group_name,
+            app_name,
+        } => WireCommandOut::RunApp {
+            group_name: group_name.clone(),
+            app_name: app_name.clone(),
+        },
     };
     let frame = WireCommandFrameOut {
         version: IPC_PROTOCOL_VERSION,
@@ -273,6 +338,9 @@ pub(crate) fn decide_entry_action(
         ForwardingProbeResult::Forwarded(response) => {
             EntryAction::Exit(forwarding_exit_code(&response))
         }
+        ForwardingProbeResult::NoActiveInstance if startup_intent.runs_headless() => {
+            EntryAction::RunHeadless(startup_intent)
+        }
         ForwardingProbeResult::NoActiveInstance => EntryAction::RunGui(startup_intent),
         ForwardingProbeResult::ServerNotReady => EntryAction::Exit(EXIT_SERVER_NOT_READY),
         ForwardingProbeResult::ProtocolError => EntryAction::Exit(EXIT_PROTOCOL_ERROR),
@@ -297,7 +365,7 @@ where
                 forwarding_runtime: None,
                 forwarding_warning: Some(err),
             },
-            intent @ StartupIntent::RunRule { .. } => PreparedStartupForwarding {
+            intent => PreparedStartupForwarding {
                 action: decide_entry_action(intent, ForwardingProbeResult::AuthFailed),
                 forwarding_runtime: None,
                 forwarding_warning: None,
@@ -305,8 +373,8 @@ where
         };
     }
 
-    match startup_intent.clone() {
-        StartupIntent::NormalGui => {
+    match startup_ipc_command(&startup_intent) {
+        None => {
             let (forwarding_runtime, forwarding_warning) =
                 prepare_normal_gui_forwarding_with(platform);
             PreparedStartupForwarding {
@@ -315,14 +383,26 @@ where
                 forwarding_warning,
             }
         }
-        StartupIntent::RunRule { group_id, rule_id } => prepare_run_rule_forwarding_with(
-            startup_intent,
-            group_id,
-            rule_id,
-            platform,
-            clock,
-            retry_policy,
-        ),
+        Some(command) => {
+            prepare_command_forwarding_with(startup_intent, command, platform, clock, retry_policy)
+        }
+    }
+}
+
+fn startup_ipc_command(startup_intent: &StartupIntent) -> Option<IpcCommand> {
+    match startup_intent.clone() {
+        StartupIntent::NormalGui | StartupIntent::Service => None,
+        StartupIntent::RunRule { group_id, rule_id } => {
+            Some(IpcCommand::RunRule { group_id, rule_id })
+        }
+        StartupIntent::RunGroup { group_name } => Some(IpcCommand::RunGroup { group_name }),
+        StartupIntent::RunApp {
+            group_name,
+            app_name,
+        } => Some(IpcCommand::RunApp {
+            group_name,
+            app_name,
+        }),
     }
 }
 
@@ -343,10 +423,9 @@ where
     }
 }
 
-fn prepare_run_rule_forwarding_with<P, C>(
+fn prepare_command_forwarding_with<P, C>(
     startup_intent: StartupIntent,
-    group_id: GroupId,
-    rule_id: RuleId,
+    command: IpcCommand,
     platform: &mut P,
     clock: &mut C,
     retry_policy: ForwardingRetryPolicy,
@@ -356,6 +435,13 @@ where
     C: StartupForwardingClock,
 {
     match platform.try_claim_primary_guard() {
+        // A headless run finishes before anyone could forward to it, so it
+        // releases the guard instead of starting a server.
+        Ok(Some(_guard)) if startup_intent.runs_headless() => PreparedStartupForwarding {
+            action: decide_entry_action(startup_intent, ForwardingProbeResult::NoActiveInstance),
+            forwarding_runtime: None,
+            forwarding_warning: None,
+        },
         Ok(Some(guard)) => match platform.start_forwarding_runtime(guard) {
             Ok(runtime) => PreparedStartupForwarding {
                 action: decide_entry_action(
@@ -373,7 +459,7 @@ where
         },
         Ok(None) => {
             let probe_result =
-                forward_run_rule_to_primary_with(platform, clock, retry_policy, group_id, rule_id);
+                forward_command_to_primary_with(platform, clock, retry_policy, command);
             PreparedStartupForwarding {
                 action: decide_entry_action(startup_intent, probe_result),
                 forwarding_runtime: None,
@@ -388,18 +474,17 @@ where
     }
 }
 
-fn forward_run_rule_to_primary_with<P, C>(
+fn forward_command_to_primary_with<P, C>(
     platform: &mut P,
     clock: &mut C,
     retry_policy: ForwardingRetryPolicy,
-    group_id: GroupId,
-    rule_id: RuleId,
+    command: IpcCommand,
 ) -> ForwardingProbeResult
 where
     P: StartupForwardingPlatform,
     C: StartupForwardingClock,
 {
-    let request = match serialize_ipc_command_frame(&IpcCommand::RunRule { group_id, rule_id }) {
+    let request = match serialize_ipc_command_frame(&command) {
         Ok(request) => request,
         Err(_) => return ForwardingProbeResult::ProtocolError,
     };
@@ -918,6 +1003,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_ipc_command_roundtrips_name_based_frames_and_rejects_blank_names() {
+        let commands = [
+            IpcCommand::RunGroup {
+                group_name: "Game Group".to_string(),
+            },
+            IpcCommand::RunApp {
+                group_name: "Game Group".to_string(),
+                app_name: "Game Launcher".to_string(),
+            },
+        ];
+        for command in commands {
+            let frame = serialize_ipc_command_frame(&command).unwrap();
+            assert_eq!(parse_ipc_command_frame(&frame), Ok(command));
+        }
+
+        assert_eq!(
+            parse_ipc_command_frame(
+                br#"{"version":1,"command":{"type":"run_app","group_name":"Games","app_name":" "}}"#
+            ),
+            Err(IpcProtocolError::InvalidName)
+        );
+    }
+
+    #[test]
+    fn test_startup_boundary_headless_intent_runs_locally_or_forwards() {
+        let intent = StartupIntent::RunGroup {
+            group_name: "Games".to_string(),
+        };
+        let mut primary = FakeStartupPlatform::primary();
+
+        let prepared = prepare_with_fake(intent.clone(), &mut primary);
+
+        assert_eq!(prepared.action, EntryAction::RunHeadless(intent.clone()));
+        assert_eq!(prepared.forwarding_runtime, None);
+        assert!(!primary
+            .calls
+            .borrow()
+            .contains(&StartupCall::StartForwardingRuntime));
+
+        let mut secondary = FakeStartupPlatform::secondary(vec![FakeSendOutcome::Response(
+            response_bytes(IpcResponseCode::MissingGroup),
+        )]);
+
+        let prepared = prepare_with_fake(intent, &mut secondary);
+
+        assert_eq!(prepared.action, EntryAction::Exit(EXIT_MISSING_GROUP));
+        assert_eq!(
+            *secondary.sent_commands.borrow(),
+            vec![IpcCommand::RunGroup {
+                group_name: "Games".to_string()
+            }]
+        );
+    }
+
     #[test]
     fn test_startup_boundary_parse_and_gui_error_exit_codes_stay_distinct() {
         assert_eq!(EXIT_CLI_PARSE_ERROR, 2);
@@ -0,0 +1,143 @@
+use crate::app::instance_forwarding::{
+    forwarding_exit_code, prepare_startup_forwarding_with, run_rule_outcome_to_response,
+    ForwardingClientError, ForwardingRetryPolicy, PreparedStartupForwarding,
+    StartupForwardingClock, StartupForwardingPlatform, EXIT_GUI_STARTUP_ERROR,
+};
+use crate::app::shell::{App, AppForwardingRuntime};
+use crate::app::startup::StartupIntent;
+use os_api::{LocalIpcClientError, LocalIpcEndpoint, LocalIpcGuard, OS};
+use std::time::{Duration, Instant};
+
+/// Claims the local forwarding endpoint or hands the intent to the running
+/// primary (named pipe on Windows, unix socket on Linux).
+pub fn prepare_startup_forwarding(
+    startup_intent: StartupIntent,
+) -> PreparedStartupForwarding<AppForwardingRuntime> {
+    let mut platform = LocalStartupForwardingPlatform::default();
+    let mut clock = RealStartupForwardingClock::new();
+    prepare_startup_forwarding_with(
+        startup_intent,
+        &mut platform,
+        &mut clock,
+        local_forwarding_retry_policy(),
+    )
+}
+
+/// Runs a headless intent and maps its outcome to the forwarding exit codes.
+pub fn run_headless_exit_code(startup_intent: StartupIntent) -> i32 {
+    let response = run_rule_outcome_to_response(App::run_headless(startup_intent));
+    if let Some(detail) = &response.detail {
+        eprintln!("Launch failed: {detail}");
+    }
+    forwarding_exit_code(&response)
+}
+
+/// Runs `--service` mode while holding the single-instance guard, so it never
+/// competes with a GUI or another service over the same processes, and serves the
+/// launches forwarded to that instance.
+pub fn run_service_exit_code() -> i32 {
+    let forwarding = match claim_service_instance() {
+        Ok(forwarding) => forwarding,
+        Err(err) => {
+            eprintln!("Service mode not started: {err}");
+            return EXIT_GUI_STARTUP_ERROR;
+        }
+    };
+    App::run_service(forwarding)
+}
+
+fn claim_service_instance() -> Result<AppForwardingRuntime, String> {
+    let endpoint = OS::local_ipc_endpoint()?;
+    let guard = OS::try_claim_local_ipc_primary_guard(&endpoint)?
+        .ok_or_else(|| "another instance of the tool is already running".to_string())?;
+    Ok(AppForwardingRuntime::pending(guard, endpoint))
+}
+
+fn local_forwarding_retry_policy() -> ForwardingRetryPolicy {
+    ForwardingRetryPolicy {
+        total_timeout: Duration::from_secs(5),
+        request_timeout: Duration::from_secs(2),
+        retry_sleep: Duration::from_millis(25),
+    }
+}
+
+struct RealStartupForwardingClock {
+    started: Instant,
+}
+
+impl RealStartupForwardingClock {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+        }
+    }
+}
+
+impl StartupForwardingClock for RealStartupForwardingClock {
+    fn now(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    fn sleep(&mut self, duration: Duration) {
+        std::thread::sleep(duration);
+    }
+}
+
+#[derive(Default)]
+struct LocalStartupForwardingPlatform {
+    endpoint: Option<LocalIpcEndpoint>,
+}
+
+impl LocalStartupForwardingPlatform {
+    fn endpoint(&self) -> Result<&LocalIpcEndpoint, String> {
+        self.endpoint
+            .as_ref()
+            .ok_or_else(|| "local shortcut endpoint was not initialized".to_string())
+    }
+}
+
+impl StartupForwardingPlatform for LocalStartupForwardingPlatform {
+    type Guard = LocalIpcGuard;
+    type Runtime = AppForwardingRuntime;
+
+    fn resolve_endpoint(&mut self) -> Result<(), String> {
+        self.endpoint = Some(OS::local_ipc_endpoint()?);
+        Ok(())
+    }
+
+    fn try_claim_primary_guard(&mut self) -> Result<Option<Self::Guard>, String> {
+        let result = OS::try_claim_local_ipc_primary_guard(self.endpoint()?);
+        if let Err(err) = &result {
+            eprintln!("Shortcut forwarding guard check failed: {err}");
+        }
+        result
+    }
+
+    fn start_forwarding_runtime(&mut self, guard: Self::Guard) -> Result<Self::Runtime, String> {
+        Ok(AppForwardingRuntime::pending(
+            guard,
+            self.endpoint()?.clone(),
+        ))
+    }
+
+    fn send_request(
+        &mut self,
+        request: &[u8],
+        timeout: Duration,
+    ) -> Result<Vec<u8>, ForwardingClientError> {
+        let endpoint = self.endpoint().map_err(ForwardingClientError::Io)?;
+        OS::send_local_ipc_request(endpoint, request, timeout).map_err(|err| match err {
+            LocalIpcClientError::NoServer => ForwardingClientError::NoServer,
+            LocalIpcClientError::ServerNotReady => ForwardingClientError::ServerNotReady,
+            LocalIpcClientError::Timeout => ForwardingClientError::Timeout,
+            LocalIpcClientError::SecurityRejected(err) => {
+                eprintln!("Shortcut forwarding security rejected: {err}");
+                ForwardingClientError::SecurityRejected(err)
+            }
+            LocalIpcClientError::Io(err) => {
+                eprintln!("Shortcut forwarding failed: {err}");
+                ForwardingClientError::Io(err)
+            }
+        })
+    }
+}
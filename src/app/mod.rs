@@ -1,7 +1,9 @@
 pub mod adapters;
 pub mod features;
-#[cfg(any(test, all(target_os = "windows", feature = "windows")))]
+#[cfg(any(test, feature = "windows", feature = "linux"))]
 pub mod instance_forwarding;
+#[cfg(any(feature = "windows", feature = "linux"))]
+pub mod local_forwarding;
 pub mod models;
 pub mod runtime;
 pub mod shared;
@@ -9,16 +9,23 @@ mod tests;
 use crate::app::features::rules::PersistedRuleIdentities;
 use crate::app::models::core_group::CoreGroup;
 use crate::app::models::cpu_schema::CpuSchema;
+use crate::app::models::profile::{AffinityFavorite, Profile};
+use crate::app::shared::ids::GroupId;
+use os_api::OS;
 use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 
 /// Current version of the application state schema.
 pub const CURRENT_APP_STATE_VERSION: u32 = 7;
 
+pub use state_path::{data_file_path, set_state_dir_override};
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum StateStorageMode {
     LegacySidecar,
     PlatformData,
+    /// Directory given by `--state-dir` or the state directory environment variable
+    CustomDir,
 }
 
 impl StateStorageMode {
@@ -26,6 +33,7 @@ impl StateStorageMode {
         match self {
             StateStorageMode::LegacySidecar => "Legacy sidecar",
             StateStorageMode::PlatformData => "Platform data",
+            StateStorageMode::CustomDir => "Custom directory",
         }
     }
 }
@@ -46,15 +54,83 @@ pub struct AppStateStorage {
     /// Flag indicating whether process monitoring is enabled
     #[serde(default)]
     pub process_monitoring_enabled: bool,
+    /// Whether a tracked app's runtime restarts when its whole PID set is replaced
+    #[serde(default)]
+    pub reset_runtime_on_restart: bool,
+    /// Whether the banner about groups sharing cores is suppressed
+    #[serde(default)]
+    pub hide_group_overlap_warning: bool,
+    /// Whether hiding to the tray also drops the taskbar button (Windows)
+    #[serde(default = "default_hide_taskbar_when_tray")]
+    pub hide_taskbar_when_tray: bool,
+    /// Whether the same app in several groups is launched and tracked once per group
+    #[serde(default)]
+    pub separate_apps_per_group: bool,
+    /// Seconds a launched program is watched for an early exit; 0 skips the check
+    #[serde(default)]
+    pub launch_confirm_secs: u32,
+    /// Whether the launch check also waits for the program to show a window (Windows)
+    #[serde(default)]
+    pub launch_confirm_window: bool,
     /// Persisted logical identities for groups and rules in schema v6.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub rule_identities: Option<PersistedRuleIdentities>,
+    /// Named core layouts that override group cores while active
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub profiles: Vec<Profile>,
+    /// Name of the profile currently layered over group cores
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_profile: Option<String>,
+    /// Threads kept free for the OS; groups only use them with an explicit override
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reserved_cores: Vec<usize>,
+    /// Threads the tool's own process is pinned to
+    #[serde(default)]
+    pub self_affinity: SelfAffinity,
+    /// Whether affinity and priority changes are appended to the CSV audit file
+    #[serde(default)]
+    pub affinity_audit: bool,
+    /// Whether double-clicking an app's name runs it; a single click only selects the row
+    #[serde(default)]
+    pub click_to_launch: bool,
+    /// Whether the window stays above other windows, e.g. over a windowed game
+    #[serde(default)]
+    pub always_on_top: bool,
+    /// Whether the rotating usage tips in the footer are dismissed
+    #[serde(default)]
+    pub hide_tips: bool,
+    /// Apps a "Run all" may start before asking for confirmation; 0 never asks
+    #[serde(default = "default_run_all_confirm_threshold")]
+    pub run_all_confirm_threshold: usize,
+    /// One-click masks in the header, applied to a process picked from the Processes view
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub favorites: Vec<AffinityFavorite>,
     #[serde(skip)]
     pub(crate) loaded_version: u32,
     #[serde(skip)]
     pub(crate) pending_pre_v6_backup: bool,
 }
 
+/// Where the tool keeps its own process, out of the way of the programs it manages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SelfAffinity {
+    /// Left on every thread the system allows
+    #[default]
+    Off,
+    /// Every thread no group uses, recomputed as groups change
+    OutsideGroups,
+    /// A single house-keeping thread
+    Core(usize),
+}
+
+fn default_hide_taskbar_when_tray() -> bool {
+    true
+}
+
+fn default_run_all_confirm_threshold() -> usize {
+    5
+}
+
 impl AppStateStorage {
     /// Loads the application state from the default JSON file.
     pub fn load_state() -> AppStateStorage {
@@ -75,6 +151,11 @@ impl AppStateStorage {
             })
     }
 
+    /// Parses state JSON of any schema version, e.g. a config picked for import.
+    pub fn from_json(data: &str) -> Option<AppStateStorage> {
+        migrations::load_from_data(data, Path::new(""))
+    }
+
     fn save_to_path(&self, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
         storage_io::save_to_path(self, path)
     }
@@ -111,6 +192,63 @@ impl AppStateStorage {
         self.rule_identities = Some(rule_identities);
     }
 
+    pub fn active_profile(&self) -> Option<&Profile> {
+        let name = self.active_profile.as_deref()?;
+        self.profiles.iter().find(|profile| profile.name == name)
+    }
+
+    pub fn active_profile_mut(&mut self) -> Option<&mut Profile> {
+        let name = self.active_profile.clone()?;
+        self.profiles
+            .iter_mut()
+            .find(|profile| profile.name == name)
+    }
+
+    /// Override from the active profile for the group with this identity, if any.
+    pub fn active_profile_cores(&self, group_id: &GroupId) -> Option<&[usize]> {
+        self.active_profile()?.cores_for(group_id)
+    }
+
+    /// The cores in `cores` that the user has reserved, in input order.
+    pub fn reserved_cores_in(&self, cores: &[usize]) -> Vec<usize> {
+        cores
+            .iter()
+            .copied()
+            .filter(|core| self.reserved_cores.contains(core))
+            .collect()
+    }
+
+    /// Cores a launch should use for the group: the active profile's override when
+    /// present, otherwise the group's own cores.
+    pub fn effective_group_cores(&self, group_index: usize) -> Option<Vec<usize>> {
+        let group = self.groups.get(group_index)?;
+        let override_cores = self
+            .rule_identities
+            .as_ref()
+            .and_then(|identities| identities.groups.get(group_index))
+            .and_then(|identity| self.active_profile_cores(&identity.id));
+        Some(override_cores.unwrap_or(&group.cores).to_vec())
+    }
+
+    /// Mask the tool's own process should run on under `self_affinity`, limited to
+    /// the `available` threads; `None` when it should stay unpinned or no thread is left.
+    pub fn self_affinity_mask(&self, available: usize) -> Option<usize> {
+        let mask = match self.self_affinity {
+            SelfAffinity::Off => return None,
+            SelfAffinity::OutsideGroups => {
+                let used = OS::mask_from_cores(
+                    (0..self.groups.len())
+                        .filter_map(|index| self.effective_group_cores(index))
+                        .flatten(),
+                );
+                available & !used
+            }
+            SelfAffinity::Core(core) if core < usize::BITS as usize => available & (1 << core),
+            SelfAffinity::Core(_) => 0,
+        };
+        (mask != 0).then_some(mask)
+    }
+
     pub(crate) fn backfill_tracked_process_names(&mut self) -> bool {
         let mut changed = false;
         for group in &mut self.groups {
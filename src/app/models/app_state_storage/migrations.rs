@@ -1,7 +1,7 @@
 use super::{schema_refresh, AppStateStorage};
-use crate::app::models::core_group::CoreGroup;
 use crate::app::models::cpu_schema::{CoreInfo, CoreType, CpuCluster, CpuSchema};
 use serde::Deserialize;
+use serde_json::{json, Value};
 use std::path::Path;
 
 #[derive(Deserialize)]
@@ -9,20 +9,53 @@ struct VersionCheck {
     version: Option<u32>,
 }
 
-#[derive(Deserialize)]
-struct V2AppStateStorage {
-    _version: u32,
-    groups: Vec<CoreGroup>,
-    clusters: Vec<Vec<usize>>,
-    theme_index: usize,
-    process_monitoring_enabled: bool,
+/// Schema version a file without a `version` field is treated as.
+const LEGACY_VERSION: u32 = 1;
+
+/// First schema whose JSON deserializes directly into `AppStateStorage`; newer
+/// versions only add defaulted fields or are upgraded after typed loading.
+const FIRST_TYPED_VERSION: u32 = 3;
+
+/// Upgrades raw state JSON from `from` one schema step at a time.
+pub(super) fn migrate(from: u32, mut value: Value) -> Value {
+    for version in from..FIRST_TYPED_VERSION {
+        value = match version {
+            1 => migrate_v1_to_v2(value),
+            2 => migrate_v2_to_v3(value),
+            _ => value,
+        };
+    }
+    value
 }
 
-#[derive(Deserialize)]
-struct LegacyAppStateStorage {
-    groups: Vec<CoreGroup>,
-    clusters: Vec<Vec<usize>>,
-    theme_index: usize,
+/// v1 had no monitoring flag; monitoring starts off.
+fn migrate_v1_to_v2(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        object.insert("version".to_string(), json!(2));
+        object.insert("process_monitoring_enabled".to_string(), json!(false));
+    }
+    value
+}
+
+/// v2 stored clusters as bare index lists; v3 keeps them in a generic CPU schema.
+fn migrate_v2_to_v3(mut value: Value) -> Value {
+    if let Some(object) = value.as_object_mut() {
+        let clusters = object
+            .remove("clusters")
+            .and_then(|clusters| serde_json::from_value(clusters).ok())
+            .unwrap_or_default();
+        let cpu_schema = CpuSchema {
+            model: "Generic CPU".to_string(),
+            clusters: build_generic_clusters(clusters),
+        };
+        object.remove("_version");
+        object.insert("version".to_string(), json!(3));
+        object.insert(
+            "cpu_schema".to_string(),
+            serde_json::to_value(cpu_schema).unwrap_or(Value::Null),
+        );
+    }
+    value
 }
 
 pub(super) fn load_from_data(data: &str, path: &Path) -> Option<AppStateStorage> {
@@ -77,47 +110,22 @@ fn load_v3(data: &str, _path: &Path) -> Option<AppStateStorage> {
 }
 
 fn load_v2(data: &str, _path: &Path) -> Option<AppStateStorage> {
-    let v2: V2AppStateStorage = serde_json::from_str(data).ok()?;
-
-    let mut migrated = AppStateStorage {
-        version: 5,
-        groups: v2.groups,
-        cpu_schema: CpuSchema {
-            model: "Generic CPU".to_string(),
-            clusters: build_generic_clusters(v2.clusters),
-        },
-        theme_index: v2.theme_index,
-        process_monitoring_enabled: v2.process_monitoring_enabled,
-        rule_identities: None,
-        loaded_version: 0,
-        pending_pre_v6_backup: false,
-    };
-
-    schema_refresh::refresh_migrated_schema(&mut migrated);
-    migrated.backfill_tracked_process_names();
-    Some(migrated.finalize_load(2, true))
+    load_migrated(data, 2, 2)
 }
 
 fn load_legacy(data: &str, _path: &Path) -> Option<AppStateStorage> {
-    let legacy: LegacyAppStateStorage = serde_json::from_str(data).ok()?;
+    load_migrated(data, LEGACY_VERSION, 0)
+}
 
-    let mut migrated = AppStateStorage {
-        version: 5,
-        groups: legacy.groups,
-        cpu_schema: CpuSchema {
-            model: "Generic CPU".to_string(),
-            clusters: build_generic_clusters(legacy.clusters),
-        },
-        theme_index: legacy.theme_index,
-        process_monitoring_enabled: false,
-        rule_identities: None,
-        loaded_version: 0,
-        pending_pre_v6_backup: false,
-    };
+fn load_migrated(data: &str, from: u32, loaded_version: u32) -> Option<AppStateStorage> {
+    let value = migrate(from, serde_json::from_str(data).ok()?);
+    let mut migrated: AppStateStorage = serde_json::from_value(value).ok()?;
+    migrated.version = 5;
+    migrated.rule_identities = None;
 
     schema_refresh::refresh_migrated_schema(&mut migrated);
     migrated.backfill_tracked_process_names();
-    Some(migrated.finalize_load(0, true))
+    Some(migrated.finalize_load(loaded_version, true))
 }
 
 pub(super) fn build_generic_clusters(clusters: Vec<Vec<usize>>) -> Vec<CpuCluster> {
@@ -1,4 +1,4 @@
-use super::{AppStateStorage, CURRENT_APP_STATE_VERSION};
+use super::{AppStateStorage, SelfAffinity, CURRENT_APP_STATE_VERSION};
 use crate::app::models::cpu_presets::get_preset_for_model;
 use crate::app::models::cpu_schema::{CoreType, CpuSchema};
 use crate::app::models::meta::{effective_cpu_model, effective_total_threads, TEST_CPU_MODEL};
@@ -17,7 +17,23 @@ pub(super) fn build_default_state() -> AppStateStorage {
         cpu_schema,
         theme_index: 0,
         process_monitoring_enabled: false,
+        reset_runtime_on_restart: false,
+        hide_group_overlap_warning: false,
+        hide_taskbar_when_tray: true,
+        separate_apps_per_group: false,
+        launch_confirm_secs: 0,
+        launch_confirm_window: false,
         rule_identities: None,
+        profiles: Vec::new(),
+        active_profile: None,
+        reserved_cores: Vec::new(),
+        self_affinity: SelfAffinity::Off,
+        affinity_audit: false,
+        click_to_launch: false,
+        always_on_top: false,
+        hide_tips: false,
+        run_all_confirm_threshold: 5,
+        favorites: Vec::new(),
         loaded_version: CURRENT_APP_STATE_VERSION,
         pending_pre_v6_backup: false,
     }
@@ -1,8 +1,13 @@
 use super::StateStorageMode;
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
 
 pub(super) const STATE_FILE_NAME: &str = "state.json";
+/// Environment variable naming the directory for the state file and the files kept
+/// next to it, e.g. for a portable copy on a USB stick. Relative paths are taken from
+/// the executable's directory.
+pub const STATE_DIR_ENV: &str = "CPU_AFFINITY_STATE_DIR";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(super) struct ResolvedStateLocation {
@@ -20,6 +25,56 @@ impl ResolvedStateLocation {
 }
 
 static RESOLVED_STATE_LOCATION: OnceLock<ResolvedStateLocation> = OnceLock::new();
+static STATE_DIR_FLAG: OnceLock<PathBuf> = OnceLock::new();
+
+/// Applies `--state-dir`, which wins over [`STATE_DIR_ENV`]; only takes effect before
+/// the state is first loaded.
+pub fn set_state_dir_override(dir: PathBuf) {
+    let _ = STATE_DIR_FLAG.set(dir);
+}
+
+/// Where a file kept beside the state lives, such as user presets: the same
+/// directory as `state.json`, wherever that was resolved to. A copy left next to the
+/// executable by older versions is moved there on first use.
+pub fn data_file_path(file_name: &str) -> PathBuf {
+    let path = get_state_dir().join(file_name);
+    migrate_legacy_data_file(&exe_sibling_path(file_name), &path);
+    path
+}
+
+/// Moves `legacy` to `path` unless `path` already exists. Copies instead when the two
+/// are on different volumes; a failure leaves the legacy file where it was.
+fn migrate_legacy_data_file(legacy: &Path, path: &Path) {
+    if legacy == path || path.exists() || !legacy.is_file() {
+        return;
+    }
+    if std::fs::rename(legacy, path).is_err() {
+        let _ = std::fs::copy(legacy, path);
+    }
+}
+
+fn current_state_dir_override() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(Path::to_path_buf));
+    state_dir_override_from(
+        STATE_DIR_FLAG.get().cloned(),
+        std::env::var_os(STATE_DIR_ENV),
+        exe_dir.as_deref(),
+    )
+}
+
+fn state_dir_override_from(
+    flag: Option<PathBuf>,
+    env: Option<OsString>,
+    exe_dir: Option<&Path>,
+) -> Option<PathBuf> {
+    let dir = flag.or_else(|| env.filter(|dir| !dir.is_empty()).map(PathBuf::from))?;
+    match exe_dir {
+        Some(exe_dir) if dir.is_relative() => Some(exe_dir.join(dir)),
+        _ => Some(dir),
+    }
+}
 
 pub(super) fn get_state_path() -> PathBuf {
     get_resolved_state_location().state_path.clone()
@@ -38,6 +93,15 @@ pub(super) fn get_resolved_state_location() -> &'static ResolvedStateLocation {
 }
 
 fn resolve_state_location() -> ResolvedStateLocation {
+    if let Some(dir) = current_state_dir_override() {
+        if std::fs::create_dir_all(&dir).is_ok() {
+            return ResolvedStateLocation {
+                mode: StateStorageMode::CustomDir,
+                state_path: dir.join(STATE_FILE_NAME),
+            };
+        }
+    }
+
     let sidecar_state_path = current_sidecar_state_path();
     let platform_state_path = current_platform_state_path();
 
@@ -80,12 +144,16 @@ fn resolve_state_location_with(
 }
 
 fn current_sidecar_state_path() -> PathBuf {
+    exe_sibling_path(STATE_FILE_NAME)
+}
+
+fn exe_sibling_path(file_name: &str) -> PathBuf {
     std::env::current_exe()
         .map(|mut path| {
-            path.set_file_name(STATE_FILE_NAME);
+            path.set_file_name(file_name);
             path
         })
-        .unwrap_or_else(|_| STATE_FILE_NAME.into())
+        .unwrap_or_else(|_| file_name.into())
 }
 
 fn current_platform_state_path() -> Option<PathBuf> {
@@ -140,11 +208,11 @@ fn platform_state_path_for(
 mod tests {
     use super::super::StateStorageMode;
     use super::{
-        platform_state_path_for, resolve_state_location_with, ResolvedStateLocation,
-        STATE_FILE_NAME,
+        migrate_legacy_data_file, platform_state_path_for, resolve_state_location_with,
+        state_dir_override_from, ResolvedStateLocation, STATE_FILE_NAME,
     };
     use std::fs;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::time::{SystemTime, UNIX_EPOCH};
 
     struct TempDirGuard {
@@ -254,6 +322,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_legacy_data_file_moves_to_the_state_dir_once() {
+        let exe_dir = TempDirGuard::new("cpu_affinity_tool_legacy_data");
+        let legacy = exe_dir.path.join("user_presets.json");
+        let data_dir = exe_dir.path.join("data");
+        fs::create_dir_all(&data_dir).unwrap();
+        let path = data_dir.join("user_presets.json");
+        fs::write(&legacy, "old").unwrap();
+
+        migrate_legacy_data_file(&legacy, &path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert!(!legacy.exists());
+
+        fs::write(&legacy, "stale").unwrap();
+        migrate_legacy_data_file(&legacy, &path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+        assert!(legacy.exists());
+    }
+
+    #[test]
+    fn test_state_dir_flag_wins_over_env_and_empty_env_is_ignored() {
+        let exe_dir = Path::new("/media/usb/tool");
+        assert_eq!(
+            state_dir_override_from(
+                Some(PathBuf::from("/media/usb/state")),
+                Some("/tmp/env-state".into()),
+                Some(exe_dir)
+            ),
+            Some(PathBuf::from("/media/usb/state"))
+        );
+        assert_eq!(
+            state_dir_override_from(None, Some("/tmp/env-state".into()), Some(exe_dir)),
+            Some(PathBuf::from("/tmp/env-state"))
+        );
+        assert_eq!(
+            state_dir_override_from(None, Some("data".into()), Some(exe_dir)),
+            Some(exe_dir.join("data"))
+        );
+        assert_eq!(
+            state_dir_override_from(None, Some("".into()), Some(exe_dir)),
+            None
+        );
+        assert_eq!(state_dir_override_from(None, None, Some(exe_dir)), None);
+    }
+
     #[test]
     fn test_platform_state_path_for_windows_uses_local_app_data() {
         let path = platform_state_path_for(
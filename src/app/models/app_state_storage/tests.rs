@@ -1,7 +1,7 @@
-use super::{state_path, storage_io, AppStateStorage, CURRENT_APP_STATE_VERSION};
+use super::{state_path, storage_io, AppStateStorage, SelfAffinity, CURRENT_APP_STATE_VERSION};
 use crate::app::features::rules::RulesContext;
 use crate::app::models::app_to_run::{AppToRun, LaunchTarget};
-use crate::app::models::core_group::CoreGroup;
+use crate::app::models::core_group::{CoreGroup, CoreSelector};
 use crate::app::models::cpu_presets::get_preset_for_model;
 use crate::app::models::cpu_schema::{CoreInfo, CoreType, CpuCluster, CpuSchema};
 use crate::app::models::meta::{effective_cpu_model, effective_total_threads};
@@ -49,7 +49,16 @@ fn sample_state() -> AppStateStorage {
                 true,
             )],
             is_hidden: false,
+            is_collapsed: false,
             run_all_button: true,
+            single_numa_node: false,
+            default_priority: PriorityClass::Normal,
+            core_selector: CoreSelector::Fixed,
+            exclude_names: Vec::new(),
+            use_job_object: false,
+            cpu_rate_percent: None,
+            enabled: true,
+            color: None,
         }],
         cpu_schema: CpuSchema {
             model: "Sample CPU".to_string(),
@@ -57,7 +66,23 @@ fn sample_state() -> AppStateStorage {
         },
         theme_index: 2,
         process_monitoring_enabled: true,
+        reset_runtime_on_restart: false,
+        hide_group_overlap_warning: false,
+        hide_taskbar_when_tray: true,
+        separate_apps_per_group: false,
+        launch_confirm_secs: 0,
+        launch_confirm_window: false,
         rule_identities: None,
+        profiles: Vec::new(),
+        active_profile: None,
+        reserved_cores: Vec::new(),
+        self_affinity: SelfAffinity::Off,
+        affinity_audit: false,
+        click_to_launch: false,
+        always_on_top: false,
+        hide_tips: false,
+        run_all_confirm_threshold: 5,
+        favorites: Vec::new(),
         loaded_version: CURRENT_APP_STATE_VERSION,
         pending_pre_v6_backup: false,
     }
@@ -87,7 +112,23 @@ fn current_schema_state() -> AppStateStorage {
         },
         theme_index: 1,
         process_monitoring_enabled: false,
+        reset_runtime_on_restart: false,
+        hide_group_overlap_warning: false,
+        hide_taskbar_when_tray: true,
+        separate_apps_per_group: false,
+        launch_confirm_secs: 0,
+        launch_confirm_window: false,
         rule_identities: None,
+        profiles: Vec::new(),
+        active_profile: None,
+        reserved_cores: Vec::new(),
+        self_affinity: SelfAffinity::Off,
+        affinity_audit: false,
+        click_to_launch: false,
+        always_on_top: false,
+        hide_tips: false,
+        run_all_confirm_threshold: 5,
+        favorites: Vec::new(),
         loaded_version: CURRENT_APP_STATE_VERSION,
         pending_pre_v6_backup: false,
     }
@@ -309,7 +350,23 @@ fn test_load_v5_generic_state_refreshes_when_cpu_model_is_known_without_rewrite(
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         };
@@ -545,6 +602,7 @@ fn test_load_legacy_state_defaults_monitor_flag_until_explicit_current_schema_sa
         assert!(loaded.pending_pre_v6_backup);
         assert_eq!(loaded.theme_index, 2);
         assert!(!loaded.process_monitoring_enabled);
+        assert!(loaded.hide_taskbar_when_tray);
         assert_eq!(
             serde_json::to_value(&loaded.cpu_schema).unwrap(),
             serde_json::to_value(&expected_cpu_schema).unwrap()
@@ -691,3 +749,69 @@ fn test_invalid_state_file_is_backed_up_and_replaced_with_default() {
         assert!(persisted.groups.is_empty());
     });
 }
+
+#[test]
+fn test_migrate_from_v1_adds_disabled_monitoring_flag() {
+    let v1 = json!({
+        "groups": [],
+        "clusters": [[0, 1]],
+        "theme_index": 0
+    });
+
+    let migrated = super::migrations::migrate(1, v1);
+
+    assert_eq!(migrated["version"], json!(3));
+    assert_eq!(migrated["process_monitoring_enabled"], json!(false));
+}
+
+#[test]
+fn test_migrate_v2_to_v3_moves_clusters_into_generic_cpu_schema() {
+    let v2 = json!({
+        "version": 2,
+        "_version": 2,
+        "groups": [],
+        "clusters": [[0, 2], [1]],
+        "theme_index": 1,
+        "process_monitoring_enabled": true
+    });
+
+    let v3 = super::migrations::migrate(2, v2);
+
+    assert_eq!(v3["version"], json!(3));
+    assert!(v3.get("clusters").is_none());
+    assert!(v3.get("_version").is_none());
+    assert_eq!(v3["process_monitoring_enabled"], json!(true));
+    assert_eq!(
+        v3["cpu_schema"],
+        serde_json::to_value(CpuSchema {
+            model: "Generic CPU".to_string(),
+            clusters: super::migrations::build_generic_clusters(vec![vec![0, 2], vec![1]]),
+        })
+        .unwrap()
+    );
+    assert!(serde_json::from_value::<AppStateStorage>(v3).is_ok());
+}
+
+#[test]
+fn test_migrate_leaves_typed_versions_untouched() {
+    let v3 = serde_json::to_value(sample_state_with_version(3)).unwrap();
+
+    assert_eq!(super::migrations::migrate(3, v3.clone()), v3);
+}
+
+#[test]
+fn test_self_affinity_mask_follows_setting_and_group_cores() {
+    let mut state = sample_state();
+    assert_eq!(state.self_affinity_mask(0b1111), None);
+
+    state.self_affinity = SelfAffinity::OutsideGroups;
+    assert_eq!(state.self_affinity_mask(0b1111), Some(0b1100));
+    state.groups[0].cores = vec![0, 1, 2, 3];
+    assert_eq!(state.self_affinity_mask(0b1111), None);
+
+    state.self_affinity = SelfAffinity::Core(3);
+    assert_eq!(state.self_affinity_mask(0b1111), Some(0b1000));
+    assert_eq!(state.self_affinity_mask(0b0111), None);
+    state.self_affinity = SelfAffinity::Core(usize::BITS as usize);
+    assert_eq!(state.self_affinity_mask(usize::MAX), None);
+}
@@ -2,7 +2,8 @@ use std::borrow::Borrow;
 use std::fmt;
 use std::path::{Path, PathBuf};
 
-use os_api::PriorityClass;
+use os_api::{IoPriority, PriorityClass, ThreadInfo, WindowShow};
+use regex::{Regex, RegexBuilder};
 use serde::{Deserialize, Deserializer, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -22,6 +23,16 @@ struct AppRuntimeKeyPayload {
     target_id: String,
     args: Vec<String>,
     priority: PriorityClass,
+    #[serde(skip_serializing_if = "is_zero")]
+    copy_index: u32,
+}
+
+fn is_zero(value: &u32) -> bool {
+    *value == 0
+}
+
+fn default_focus_on_relaunch() -> bool {
+    true
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -37,12 +48,14 @@ impl AppRuntimeKey {
         target_id: String,
         args: &[String],
         priority: PriorityClass,
+        copy_index: u32,
     ) -> Self {
         let payload = AppRuntimeKeyPayload {
             target_kind,
             target_id,
             args: args.to_vec(),
             priority,
+            copy_index,
         };
 
         let encoded = serde_json::to_string(&payload).unwrap_or_else(|_| {
@@ -68,6 +81,92 @@ impl fmt::Display for AppRuntimeKey {
     }
 }
 
+/// How a thread rule picks threads of a running program.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ThreadMatch {
+    /// Case-insensitive substring of the thread description/name
+    Name(String),
+    /// Position in the OS thread listing; 0 is usually the main thread
+    Index(usize),
+}
+
+/// What a launched app has to show before the next queued launch starts.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub enum ReadyCondition {
+    /// One of its processes has a visible window
+    Window,
+    /// A process with this executable name runs below it, e.g. the game a launcher starts
+    Child(String),
+}
+
+/// Holds back the apps queued after this one until it is ready.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LaunchBarrier {
+    pub ready_when: ReadyCondition,
+    /// The queue goes on with a warning once this many seconds passed
+    pub timeout_secs: u32,
+}
+
+impl Default for LaunchBarrier {
+    fn default() -> Self {
+        Self {
+            ready_when: ReadyCondition::Window,
+            timeout_secs: 60,
+        }
+    }
+}
+
+/// How an app narrows its group's cores into the mask it actually runs on.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SpreadStrategy {
+    /// Every core of the group
+    #[default]
+    All,
+    /// The `count` lowest-numbered group cores
+    Pack { count: usize },
+    /// Every other physical core of the group, with all of its threads, to spread heat
+    Spread,
+}
+
+impl SpreadStrategy {
+    pub fn is_all(&self) -> bool {
+        *self == Self::All
+    }
+}
+
+/// Pins matching threads to a subset of the group cores; other threads keep the
+/// process affinity.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ThreadRule {
+    pub matcher: ThreadMatch,
+    /// Cores for the matched threads, intersected with the group cores on apply
+    pub cores: Vec<usize>,
+}
+
+impl ThreadRule {
+    /// Thread ids in `threads` this rule applies to.
+    pub fn matching_tids(&self, threads: &[ThreadInfo]) -> Vec<u32> {
+        match &self.matcher {
+            ThreadMatch::Name(pattern) => {
+                let pattern = pattern.trim().to_lowercase();
+                if pattern.is_empty() {
+                    return Vec::new();
+                }
+                threads
+                    .iter()
+                    .filter(|thread| thread.name.to_lowercase().contains(&pattern))
+                    .map(|thread| thread.tid)
+                    .collect()
+            }
+            ThreadMatch::Index(index) => threads
+                .get(*index)
+                .map(|thread| thread.tid)
+                .into_iter()
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Serialize, Clone, PartialEq)]
 pub struct AppToRun {
     /// Display name of the application
@@ -83,6 +182,58 @@ pub struct AppToRun {
     pub autorun: bool,
     /// Process priority class to assign to the application
     pub priority: PriorityClass,
+    /// How the application's window is shown when it starts
+    #[serde(default)]
+    pub window_show: WindowShow,
+    /// Opt-in per-thread affinity, enforced by process monitoring
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub thread_affinity_rules: Vec<ThreadRule>,
+    /// Regex over whole process names (e.g. `game_\d+\.exe`) for executables that
+    /// get renamed between patches
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub match_pattern: Option<String>,
+    /// Tells copies of one launch rule in other groups apart so each gets its own
+    /// runtime tracking; 0 for the original
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub copy_index: u32,
+    /// Shapes the group's cores into the launch mask; the group still sets the candidates
+    #[serde(default, skip_serializing_if = "SpreadStrategy::is_all")]
+    pub spread: SpreadStrategy,
+    /// Shell command run once the app has started; `{pid}` and the launch argument
+    /// placeholders are filled in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub post_launch_cmd: Option<String>,
+    /// Position among all autorun apps; lower starts first, ties keep group order
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub autorun_order: u32,
+    /// Wait after the previous autorun app before this one starts, in milliseconds
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub autorun_delay_ms: Option<u32>,
+    /// Readiness check the apps started after this one by autorun or Run all wait for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_barrier: Option<LaunchBarrier>,
+    /// Runs on one thread per physical core, leaving out hyper-thread siblings
+    #[serde(default)]
+    pub smt_off: bool,
+    /// Applies the group's affinity and priority whenever the app is found running,
+    /// even when it was started outside the tool and monitoring is off
+    #[serde(default)]
+    pub watch_autoapply: bool,
+    /// Raises the app's window when it is launched while already running; off for
+    /// background apps that have none
+    #[serde(default = "default_focus_on_relaunch")]
+    pub focus_on_relaunch: bool,
+    /// Disk I/O priority set beside the CPU priority once the app has started
+    #[serde(default)]
+    pub io_priority: IoPriority,
+    /// Starts the app with the group's affinity inherited from the tool instead of
+    /// set on it, for anti-cheats that flag outside affinity changes
+    #[serde(default)]
+    pub inherit_affinity: bool,
+    /// Opens the target through its file association, e.g. a document in its
+    /// default program, instead of executing it
+    #[serde(default)]
+    pub use_shell_execute: bool,
 }
 
 #[derive(Deserialize)]
@@ -95,6 +246,36 @@ struct AppToRunV5 {
     additional_processes: Vec<String>,
     autorun: bool,
     priority: PriorityClass,
+    #[serde(default)]
+    window_show: WindowShow,
+    #[serde(default)]
+    thread_affinity_rules: Vec<ThreadRule>,
+    #[serde(default)]
+    match_pattern: Option<String>,
+    #[serde(default)]
+    copy_index: u32,
+    #[serde(default)]
+    spread: SpreadStrategy,
+    #[serde(default)]
+    post_launch_cmd: Option<String>,
+    #[serde(default)]
+    autorun_order: u32,
+    #[serde(default)]
+    autorun_delay_ms: Option<u32>,
+    #[serde(default)]
+    launch_barrier: Option<LaunchBarrier>,
+    #[serde(default)]
+    smt_off: bool,
+    #[serde(default)]
+    watch_autoapply: bool,
+    #[serde(default = "default_focus_on_relaunch")]
+    focus_on_relaunch: bool,
+    #[serde(default)]
+    io_priority: IoPriority,
+    #[serde(default)]
+    inherit_affinity: bool,
+    #[serde(default)]
+    use_shell_execute: bool,
 }
 
 #[derive(Deserialize)]
@@ -130,6 +311,21 @@ impl<'de> Deserialize<'de> for AppToRun {
                 additional_processes: v5.additional_processes,
                 autorun: v5.autorun,
                 priority: v5.priority,
+                window_show: v5.window_show,
+                thread_affinity_rules: v5.thread_affinity_rules,
+                match_pattern: v5.match_pattern,
+                copy_index: v5.copy_index,
+                spread: v5.spread,
+                post_launch_cmd: v5.post_launch_cmd,
+                autorun_order: v5.autorun_order,
+                autorun_delay_ms: v5.autorun_delay_ms,
+                launch_barrier: v5.launch_barrier,
+                smt_off: v5.smt_off,
+                watch_autoapply: v5.watch_autoapply,
+                focus_on_relaunch: v5.focus_on_relaunch,
+                io_priority: v5.io_priority,
+                inherit_affinity: v5.inherit_affinity,
+                use_shell_execute: v5.use_shell_execute,
             }),
             AppToRunSerde::V4(v4) => Ok(Self {
                 name: v4.name,
@@ -141,6 +337,21 @@ impl<'de> Deserialize<'de> for AppToRun {
                 additional_processes: v4.additional_processes,
                 autorun: v4.autorun,
                 priority: v4.priority,
+                window_show: WindowShow::Normal,
+                thread_affinity_rules: Vec::new(),
+                match_pattern: None,
+                copy_index: 0,
+                spread: SpreadStrategy::All,
+                post_launch_cmd: None,
+                autorun_order: 0,
+                autorun_delay_ms: None,
+                launch_barrier: None,
+                smt_off: false,
+                watch_autoapply: false,
+                focus_on_relaunch: true,
+                io_priority: IoPriority::Normal,
+                inherit_affinity: false,
+                use_shell_execute: false,
             }),
         }
     }
@@ -154,13 +365,13 @@ impl AppToRun {
         priority: PriorityClass,
         autorun: bool,
     ) -> Self {
-        let name = path_file_name_lossy(&dropped_path)
-            .as_deref()
-            .unwrap_or("Unknown")
-            .rsplit_once('.')
-            .map(|(stem, _)| stem)
-            .unwrap_or("Unknown")
-            .to_string();
+        let file_name =
+            path_file_name_lossy(&dropped_path).unwrap_or_else(|| "Unknown".to_string());
+        // Like `Path::file_stem`, which would not split Windows paths on other platforms.
+        let name = match file_name.rsplit_once('.') {
+            Some((stem, _)) if !stem.is_empty() => stem.to_string(),
+            _ => file_name,
+        };
 
         let mut app = Self {
             name,
@@ -172,6 +383,21 @@ impl AppToRun {
             additional_processes: Vec::new(),
             autorun,
             priority,
+            window_show: WindowShow::Normal,
+            thread_affinity_rules: Vec::new(),
+            match_pattern: None,
+            copy_index: 0,
+            spread: SpreadStrategy::All,
+            post_launch_cmd: None,
+            autorun_order: 0,
+            autorun_delay_ms: None,
+            launch_barrier: None,
+            smt_off: false,
+            watch_autoapply: false,
+            focus_on_relaunch: true,
+            io_priority: IoPriority::Normal,
+            inherit_affinity: false,
+            use_shell_execute: false,
         };
         app.ensure_primary_process_name_tracked();
         app
@@ -190,6 +416,21 @@ impl AppToRun {
             additional_processes: Vec::new(),
             autorun,
             priority,
+            window_show: WindowShow::Normal,
+            thread_affinity_rules: Vec::new(),
+            match_pattern: None,
+            copy_index: 0,
+            spread: SpreadStrategy::All,
+            post_launch_cmd: None,
+            autorun_order: 0,
+            autorun_delay_ms: None,
+            launch_barrier: None,
+            smt_off: false,
+            watch_autoapply: false,
+            focus_on_relaunch: true,
+            io_priority: IoPriority::Normal,
+            inherit_affinity: false,
+            use_shell_execute: false,
         }
     }
 
@@ -348,6 +589,37 @@ impl AppToRun {
         }
     }
 
+    /// Whether both entries start the same target with the same arguments,
+    /// regardless of name, priority or other per-rule settings.
+    /// Compiled `match_pattern`, anchored to the whole name and case-insensitive.
+    /// An invalid pattern falls back to matching its text literally.
+    pub fn match_pattern_regex(&self) -> Option<Regex> {
+        let pattern = self.match_pattern.as_deref()?.trim();
+        if pattern.is_empty() {
+            return None;
+        }
+        compile_process_pattern(pattern)
+            .or_else(|_| compile_process_pattern(&regex::escape(pattern)))
+            .ok()
+    }
+
+    /// Why `match_pattern` is not a valid regex, if it isn't.
+    pub fn match_pattern_error(&self) -> Option<String> {
+        let pattern = self.match_pattern.as_deref()?.trim();
+        if pattern.is_empty() {
+            return None;
+        }
+        compile_process_pattern(pattern)
+            .err()
+            .map(|err| err.to_string())
+    }
+
+    pub fn launches_same_target(&self, other: &AppToRun) -> bool {
+        self.is_installed_target() == other.is_installed_target()
+            && self.args == other.args
+            && self.target_id() == other.target_id()
+    }
+
     fn target_id(&self) -> String {
         match &self.launch_target {
             LaunchTarget::Path { bin_path, .. } => normalized_path_identity(bin_path),
@@ -356,6 +628,12 @@ impl AppToRun {
     }
 }
 
+fn compile_process_pattern(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(&format!("^(?:{pattern})$"))
+        .case_insensitive(true)
+        .build()
+}
+
 pub fn normalize_process_name(candidate: &str) -> String {
     let file_name = candidate
         .rsplit(['/', '\\'])
@@ -414,7 +692,13 @@ impl From<&AppToRun> for AppRuntimeKey {
             "path"
         };
 
-        Self::from_parts(target_kind, app.target_id(), &app.args, app.priority)
+        Self::from_parts(
+            target_kind,
+            app.target_id(),
+            &app.args,
+            app.priority,
+            app.copy_index,
+        )
     }
 }
 
@@ -451,7 +735,7 @@ fn normalized_path_identity(path: &Path) -> String {
 #[cfg(test)]
 mod tests {
     use super::{normalize_process_name, AppToRun, LaunchTarget};
-    use os_api::PriorityClass;
+    use os_api::{PriorityClass, WindowShow};
     use serde_json::json;
     use std::path::PathBuf;
 
@@ -495,6 +779,25 @@ mod tests {
         assert_eq!(path.additional_processes, vec!["foo.bar.exe".to_string()]);
     }
 
+    #[test]
+    fn test_new_path_names_app_after_file_stem() {
+        let name = |dropped: &str| {
+            AppToRun::new_path(
+                PathBuf::from(dropped),
+                vec![],
+                PathBuf::from(dropped),
+                PriorityClass::Normal,
+                false,
+            )
+            .name
+        };
+
+        assert_eq!(name(r"C:\Games\game.exe"), "game");
+        assert_eq!(name(r"C:\Games\my.app.v2.exe"), "my.app.v2");
+        assert_eq!(name("/usr/bin/game"), "game");
+        assert_eq!(name("/opt/tools/.launcher"), ".launcher");
+    }
+
     #[test]
     fn test_process_name_normalization_strips_only_final_extension() {
         assert_eq!(normalize_process_name(r"C:\Games\foo.bar.exe"), "foo.bar");
@@ -622,5 +925,49 @@ mod tests {
             Some("SpotifyAB.SpotifyMusic_zpdnekdrzrea0!Spotify")
         );
         assert!(app.is_installed_target());
+        assert_eq!(app.window_show, WindowShow::Normal);
+    }
+
+    #[test]
+    fn test_window_show_roundtrips_and_defaults_to_normal() {
+        let mut app = AppToRun::new_path(
+            PathBuf::from(r"C:\Helper.lnk"),
+            vec![],
+            PathBuf::from(r"C:\Helper.exe"),
+            PriorityClass::Normal,
+            false,
+        );
+        assert_eq!(app.window_show, WindowShow::Normal);
+
+        app.window_show = WindowShow::Minimized;
+        let encoded = serde_json::to_value(&app).unwrap();
+        let decoded: AppToRun = serde_json::from_value(encoded).unwrap();
+        assert_eq!(decoded.window_show, WindowShow::Minimized);
+    }
+
+    #[test]
+    fn test_match_pattern_matches_whole_process_name() {
+        let mut app = AppToRun::new_path(
+            PathBuf::from(r"C:\Launcher.exe"),
+            vec![],
+            PathBuf::from(r"C:\Launcher.exe"),
+            PriorityClass::Normal,
+            false,
+        );
+        assert!(app.match_pattern_regex().is_none());
+
+        app.match_pattern = Some(r"game_\d+\.exe".into());
+        let pattern = app.match_pattern_regex().unwrap();
+        assert!(app.match_pattern_error().is_none());
+        assert!(pattern.is_match("game_1234.exe"));
+        assert!(pattern.is_match("GAME_7.EXE"));
+        assert!(!pattern.is_match("game_b1234.exe"));
+        assert!(!pattern.is_match("my_game_1234.exe"));
+
+        app.match_pattern = Some("game(1.exe".into());
+        let literal = app.match_pattern_regex().unwrap();
+        assert!(app.match_pattern_error().is_some());
+        assert!(literal.is_match("game(1.exe"));
+        assert!(!literal.is_match("game11.exe"));
     }
 }
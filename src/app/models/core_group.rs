@@ -1,10 +1,15 @@
 use crate::app::models::AppToRun;
+use os_api::PriorityClass;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub struct AddAppsOutcome {
     pub added_count: usize,
     pub first_error: Option<String>,
+    /// Names of the apps that were added
+    pub added_names: Vec<String>,
+    /// Names of the apps skipped because the group already launches the same target
+    pub skipped_names: Vec<String>,
 }
 
 /// Represents a group of CPU cores and associated programs.
@@ -17,8 +22,101 @@ pub struct CoreGroup {
     pub cores: Vec<usize>,
     /// List of applications associated with this group
     pub programs: Vec<AppToRun>,
-    /// Whether the group is hidden in the UI
+    /// Whether the group's app list is hidden in the UI
     pub is_hidden: bool,
+    /// Whether the whole group is folded to its header line in the group list
+    #[serde(default)]
+    pub is_collapsed: bool,
     /// Whether the "run all" button is enabled for this group
     pub run_all_button: bool,
+    /// Whether launched apps should prefer the NUMA node holding most of the group's cores
+    #[serde(default)]
+    pub single_numa_node: bool,
+    /// Priority given to apps newly added to the group; existing apps keep their own
+    #[serde(default = "default_priority")]
+    pub default_priority: PriorityClass,
+    /// How launches pick their cores; `Fixed` uses `cores`
+    #[serde(default)]
+    pub core_selector: CoreSelector,
+    /// Executable names of child processes left at the system's default affinity,
+    /// e.g. an anti-cheat service started by a game
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_names: Vec<String>,
+    /// Whether launches start in a Job Object that keeps every child on the group's
+    /// threads (Windows)
+    #[serde(default)]
+    pub use_job_object: bool,
+    /// Hard cap on the machine's total CPU time the group's launches may use, in percent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cpu_rate_percent: Option<u8>,
+    /// Whether the group's apps may be launched; a disabled group keeps its settings,
+    /// skips autorun and refuses launches, while its running apps stay monitored
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Accent color picked in the editor; `None` uses one derived from the name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<[u8; 3]>,
+}
+
+impl CoreGroup {
+    /// Color the group is marked with in the group list.
+    pub fn accent_color(&self) -> [u8; 3] {
+        self.color.unwrap_or_else(|| name_accent_color(&self.name))
+    }
+}
+
+/// Accent for a group without a picked color. The hue comes from an FNV-1a hash of
+/// the name, so a group keeps the same color across runs and versions.
+pub fn name_accent_color(name: &str) -> [u8; 3] {
+    let hash = name.bytes().fold(0x811c_9dc5_u32, |hash, byte| {
+        (hash ^ u32::from(byte)).wrapping_mul(0x0100_0193)
+    });
+    hsv_to_rgb((hash % 360) as f32, 0.55, 0.85)
+}
+
+fn hsv_to_rgb(hue: f32, saturation: f32, value: f32) -> [u8; 3] {
+    let chroma = value * saturation;
+    let x = chroma * (1.0 - ((hue / 60.0) % 2.0 - 1.0).abs());
+    let (r, g, b) = match (hue / 60.0) as u32 {
+        0 => (chroma, x, 0.0),
+        1 => (x, chroma, 0.0),
+        2 => (0.0, chroma, x),
+        3 => (0.0, x, chroma),
+        4 => (x, 0.0, chroma),
+        _ => (chroma, 0.0, x),
+    };
+    let offset = value - chroma;
+    [r, g, b].map(|channel| ((channel + offset) * 255.0).round() as u8)
+}
+
+/// How a group picks the cores a launch runs on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CoreSelector {
+    /// The group's cores, or the active profile's override
+    #[default]
+    Fixed,
+    /// The `count` least-loaded threads of CPU cluster `cluster`, sampled at launch,
+    /// for machines whose usable cores shift with the power mode
+    LeastLoaded { cluster: usize, count: usize },
+}
+
+fn default_priority() -> PriorityClass {
+    PriorityClass::Normal
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hsv_to_rgb, name_accent_color};
+
+    #[test]
+    fn test_name_accent_color_is_stable_and_varies_by_name() {
+        assert_eq!(name_accent_color("Games"), name_accent_color("Games"));
+        assert_ne!(name_accent_color("Games"), name_accent_color("Tools"));
+        assert_eq!(hsv_to_rgb(0.0, 1.0, 1.0), [255, 0, 0]);
+        assert_eq!(hsv_to_rgb(240.0, 1.0, 1.0), [0, 0, 255]);
+    }
 }
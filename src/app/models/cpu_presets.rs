@@ -1,14 +1,16 @@
 use crate::app::models::{CoreInfo, CoreType, CpuCluster, CpuSchema};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
 
-#[derive(Deserialize)]
+#[derive(Default, Serialize, Deserialize)]
 struct SchemesRoot {
     schemes: Vec<SchemeConfig>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 struct SchemeConfig {
     name: String,
     #[serde(rename = "rules")]
@@ -16,14 +18,14 @@ struct SchemeConfig {
     layout: Vec<LayoutEntry>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 struct MatchRule {
     #[serde(default)]
     regexes: Vec<String>,
     total_threads: Option<usize>,
 }
 
-#[derive(Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 struct LayoutEntry {
     #[serde(rename = "type")]
     entry_type: String,
@@ -32,14 +34,19 @@ struct LayoutEntry {
     threads_per_core: usize,
 
     // For standard group
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     group_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     label_prefix: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     cores: Option<usize>,
 
     // For repeat group
     #[serde(default = "default_repeat")]
     repeat: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     group_name_pattern: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     cores_per_group: Option<usize>,
 }
 
@@ -52,25 +59,89 @@ fn default_repeat() -> usize {
 
 const PRESETS_JSON: &str = include_str!("../../../assets/cpu_presets.json");
 
+/// File beside the state with presets users added themselves.
+pub const USER_PRESETS_FILE_NAME: &str = "user_presets.json";
+
 static PRESETS: Lazy<SchemesRoot> = Lazy::new(|| {
     serde_json::from_str(PRESETS_JSON).expect("Failed to parse embedded cpu_presets.json")
 });
 
-pub fn get_all_presets_info() -> Vec<(String, Vec<String>, Option<usize>)> {
-    let root = &*PRESETS;
-    let mut info = Vec::new();
-    for s in &root.schemes {
-        for rule in &s.match_rules {
-            info.push((s.name.clone(), rule.regexes.clone(), rule.total_threads));
+/// User presets as last read from or written to disk; `Err` when the file is unreadable.
+static USER_PRESETS: Lazy<RwLock<Result<SchemesRoot, String>>> =
+    Lazy::new(|| RwLock::new(read_user_presets()));
+
+pub fn user_presets_path() -> PathBuf {
+    crate::app::models::data_file_path(USER_PRESETS_FILE_NAME)
+}
+
+fn read_user_presets() -> Result<SchemesRoot, String> {
+    let path = user_presets_path();
+    match std::fs::read_to_string(&path) {
+        Ok(json) => parse_user_presets(&json, &path),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(SchemesRoot::default()),
+        Err(err) => Err(format!("cannot read {}: {err}", path.display())),
+    }
+}
+
+/// Parses a user presets file; a malformed file is reported instead of panicking like
+/// the embedded presets would.
+fn parse_user_presets(json: &str, path: &Path) -> Result<SchemesRoot, String> {
+    let root: SchemesRoot = serde_json::from_str(json)
+        .map_err(|err| format!("{} is not valid: {err}", path.display()))?;
+    for scheme in &root.schemes {
+        for pattern in scheme.match_rules.iter().flat_map(|rule| &rule.regexes) {
+            Regex::new(pattern).map_err(|err| {
+                format!(
+                    "{}: preset '{}' has an invalid regex: {err}",
+                    path.display(),
+                    scheme.name
+                )
+            })?;
         }
     }
-    info
+    Ok(root)
+}
+
+/// Number of user presets loaded, or why the user preset file was skipped.
+pub fn user_presets_status() -> Result<usize, String> {
+    match &*USER_PRESETS.read().unwrap() {
+        Ok(root) => Ok(root.schemes.len()),
+        Err(err) => Err(err.clone()),
+    }
+}
+
+/// Runs `f` over user presets first, then the embedded ones, so user entries win.
+fn with_all_schemes<T>(f: impl FnOnce(&mut dyn Iterator<Item = &SchemeConfig>) -> T) -> T {
+    let user = USER_PRESETS.read().unwrap();
+    let user_schemes = user
+        .as_ref()
+        .map(|root| root.schemes.as_slice())
+        .unwrap_or(&[]);
+    f(&mut user_schemes.iter().chain(PRESETS.schemes.iter()))
+}
+
+pub fn get_all_presets_info() -> Vec<(String, Vec<String>, Option<usize>)> {
+    with_all_schemes(|schemes| {
+        let mut info = Vec::new();
+        for s in schemes {
+            for rule in &s.match_rules {
+                info.push((s.name.clone(), rule.regexes.clone(), rule.total_threads));
+            }
+        }
+        info
+    })
 }
 
 pub fn get_preset_for_model(model: &str, total_threads: usize) -> Option<CpuSchema> {
-    let root = &*PRESETS;
+    with_all_schemes(|schemes| find_preset(schemes, model, total_threads))
+}
 
-    for scheme in &root.schemes {
+fn find_preset<'a>(
+    schemes: impl Iterator<Item = &'a SchemeConfig>,
+    model: &str,
+    total_threads: usize,
+) -> Option<CpuSchema> {
+    for scheme in schemes {
         let mut matched = false;
 
         for rule in &scheme.match_rules {
@@ -170,6 +241,134 @@ pub fn get_preset_for_model(model: &str, total_threads: usize) -> Option<CpuSche
     None
 }
 
+/// Core layout a user describes for a CPU no embedded preset recognizes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UserPresetLayout {
+    pub name: String,
+    /// Model string the preset matches exactly (as reported by the OS).
+    pub model: String,
+    pub performance_cores: usize,
+    /// Whether each performance core runs two threads.
+    pub performance_ht: bool,
+    pub efficient_cores: usize,
+    /// Chiplets the performance cores are split across evenly; 0 for a single die.
+    pub ccds: usize,
+}
+
+impl UserPresetLayout {
+    pub fn total_threads(&self) -> usize {
+        self.performance_cores * if self.performance_ht { 2 } else { 1 } + self.efficient_cores
+    }
+
+    fn to_scheme(&self) -> Result<SchemeConfig, String> {
+        let name = self.name.trim();
+        let model = self.model.trim();
+        if name.is_empty() {
+            return Err("Preset name cannot be empty".to_string());
+        }
+        if model.is_empty() {
+            return Err("CPU model cannot be empty".to_string());
+        }
+        if self.total_threads() == 0 {
+            return Err("Preset needs at least one core".to_string());
+        }
+        let threads_per_core = if self.performance_ht { 2 } else { 1 };
+
+        let mut layout = Vec::new();
+        if self.ccds > 0 {
+            if self.efficient_cores > 0 {
+                return Err("Chiplet layouts cannot have efficient cores".to_string());
+            }
+            if !self.performance_cores.is_multiple_of(self.ccds) {
+                return Err(format!(
+                    "{} cores do not split evenly across {} CCDs",
+                    self.performance_cores, self.ccds
+                ));
+            }
+            layout.push(LayoutEntry {
+                entry_type: "ccd".to_string(),
+                threads_per_core,
+                group_name: None,
+                label_prefix: None,
+                cores: None,
+                repeat: self.ccds,
+                group_name_pattern: Some("CCD {i}".to_string()),
+                cores_per_group: Some(self.performance_cores / self.ccds),
+            });
+        } else {
+            if self.performance_cores > 0 {
+                layout.push(LayoutEntry {
+                    entry_type: if self.performance_ht {
+                        "performance"
+                    } else {
+                        "p_core_no_ht"
+                    }
+                    .to_string(),
+                    threads_per_core,
+                    group_name: Some("Performance Cores".to_string()),
+                    label_prefix: None,
+                    cores: Some(self.performance_cores),
+                    repeat: 1,
+                    group_name_pattern: None,
+                    cores_per_group: None,
+                });
+            }
+            if self.efficient_cores > 0 {
+                layout.push(LayoutEntry {
+                    entry_type: "efficient".to_string(),
+                    threads_per_core: 1,
+                    group_name: Some("Efficient Cores".to_string()),
+                    label_prefix: None,
+                    cores: Some(self.efficient_cores),
+                    repeat: 1,
+                    group_name_pattern: None,
+                    cores_per_group: None,
+                });
+            }
+        }
+
+        Ok(SchemeConfig {
+            name: name.to_string(),
+            match_rules: vec![MatchRule {
+                regexes: vec![format!("^{}$", regex::escape(model))],
+                total_threads: Some(self.total_threads()),
+            }],
+            layout,
+        })
+    }
+
+    /// The preset as it would appear in the `schemes` list of a presets file.
+    pub fn to_json(&self) -> Result<String, String> {
+        let scheme = self.to_scheme()?;
+        serde_json::to_string_pretty(&scheme).map_err(|err| err.to_string())
+    }
+}
+
+/// Adds `scheme` ahead of the other user presets, replacing one with the same name.
+fn merge_user_scheme(root: &mut SchemesRoot, scheme: SchemeConfig) {
+    root.schemes.retain(|existing| existing.name != scheme.name);
+    root.schemes.insert(0, scheme);
+}
+
+/// Writes the layout into the user presets file and makes it available to matching.
+pub fn save_user_preset(layout: &UserPresetLayout) -> Result<PathBuf, String> {
+    let scheme = layout.to_scheme()?;
+    let mut user = USER_PRESETS.write().unwrap();
+    let root = user
+        .as_mut()
+        .map_err(|err| format!("{err}; fix or remove the file before saving new presets"))?;
+    let mut updated = SchemesRoot {
+        schemes: root.schemes.clone(),
+    };
+    merge_user_scheme(&mut updated, scheme);
+
+    let path = user_presets_path();
+    let json = serde_json::to_string_pretty(&updated).map_err(|err| err.to_string())?;
+    std::fs::write(&path, json).map_err(|err| format!("cannot write {}: {err}", path.display()))?;
+    *root = updated;
+    Ok(path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +380,125 @@ mod tests {
         println!("Loaded {} presets", info.len());
     }
 
+    fn sample_layout() -> UserPresetLayout {
+        UserPresetLayout {
+            name: "My CPU".to_string(),
+            model: "Contoso CPU 9000 (R)".to_string(),
+            performance_cores: 4,
+            performance_ht: true,
+            efficient_cores: 2,
+            ccds: 0,
+        }
+    }
+
+    #[test]
+    fn test_user_layout_builds_matching_scheme() {
+        let layout = sample_layout();
+        let scheme = layout.to_scheme().unwrap();
+
+        assert_eq!(layout.total_threads(), 10);
+        let schema = find_preset(std::iter::once(&scheme), "Contoso CPU 9000 (R)", 10).unwrap();
+        assert_eq!(schema.clusters.len(), 2);
+        assert_eq!(schema.clusters[0].cores.len(), 8);
+        assert_eq!(
+            schema.clusters[0].cores[1].core_type,
+            crate::app::models::CoreType::HyperThreading
+        );
+        assert_eq!(schema.clusters[1].cores[0].index, 8);
+        assert!(find_preset(std::iter::once(&scheme), "Contoso CPU 9000 (R)", 12).is_none());
+        assert!(find_preset(std::iter::once(&scheme), "Contoso CPU 9000 (R) v2", 10).is_none());
+
+        let json = layout.to_json().unwrap();
+        assert!(json.contains("\"type\": \"performance\""));
+        assert!(!json.contains("group_name_pattern"));
+
+        let chiplets = UserPresetLayout {
+            efficient_cores: 0,
+            ccds: 2,
+            ..sample_layout()
+        };
+        let schema = find_preset(
+            std::iter::once(&chiplets.to_scheme().unwrap()),
+            "Contoso CPU 9000 (R)",
+            8,
+        )
+        .unwrap();
+        assert_eq!(schema.clusters[1].name, "CCD 1");
+    }
+
+    #[test]
+    fn test_user_layout_rejects_inconsistent_input() {
+        let uneven = UserPresetLayout {
+            performance_cores: 5,
+            efficient_cores: 0,
+            ccds: 2,
+            ..sample_layout()
+        };
+        assert!(uneven.to_scheme().is_err());
+        let hybrid_chiplets = UserPresetLayout {
+            ccds: 2,
+            ..sample_layout()
+        };
+        assert!(hybrid_chiplets.to_scheme().is_err());
+        let unnamed = UserPresetLayout {
+            name: " ".to_string(),
+            ..sample_layout()
+        };
+        assert!(unnamed.to_scheme().is_err());
+    }
+
+    #[test]
+    fn test_user_presets_take_precedence_over_embedded() {
+        let model = "13th Gen Intel(R) Core(TM) i9-13900K";
+        let mut user = SchemesRoot::default();
+        let layout = UserPresetLayout {
+            model: model.to_string(),
+            performance_cores: 32,
+            performance_ht: false,
+            efficient_cores: 0,
+            ..sample_layout()
+        };
+        merge_user_scheme(&mut user, layout.to_scheme().unwrap());
+        merge_user_scheme(&mut user, layout.to_scheme().unwrap());
+
+        assert_eq!(user.schemes.len(), 1);
+        let schema =
+            find_preset(user.schemes.iter().chain(PRESETS.schemes.iter()), model, 32).unwrap();
+        assert_eq!(schema.clusters.len(), 1);
+        assert_eq!(schema.clusters[0].cores.len(), 32);
+    }
+
+    #[test]
+    fn test_user_scheme_with_same_regex_overrides_embedded_one() {
+        let user_json = r#"{
+            "schemes": [{
+                "name": "Custom i9",
+                "rules": [{ "regexes": ["(?i)intel.*i9.*1[234]900"], "total_threads": 32 }],
+                "layout": [{ "group_name": "All Cores", "type": "p_core_no_ht", "cores": 32 }]
+            }]
+        }"#;
+        let user = parse_user_presets(user_json, Path::new("user_presets.json")).unwrap();
+        let model = "13th Gen Intel(R) Core(TM) i9-13900K";
+
+        let schema =
+            find_preset(user.schemes.iter().chain(PRESETS.schemes.iter()), model, 32).unwrap();
+        assert_eq!(schema.clusters.len(), 1);
+        assert_eq!(schema.clusters[0].name, "All Cores");
+
+        let embedded = find_preset(PRESETS.schemes.iter(), model, 32).unwrap();
+        assert_eq!(embedded.clusters.len(), 2);
+    }
+
+    #[test]
+    fn test_malformed_user_presets_are_reported_not_loaded() {
+        let path = Path::new("user_presets.json");
+
+        assert!(parse_user_presets("{ not json", path).is_err());
+        let bad_regex = r#"{"schemes": [{"name": "Broken", "rules": [{"regexes": ["(unclosed"]}], "layout": []}]}"#;
+        let err = parse_user_presets(bad_regex, path).err().unwrap();
+        assert!(err.contains("'Broken'"));
+    }
+
     #[test]
     fn test_intel_i9_matching() {
         let model = "13th Gen Intel(R) Core(TM) i9-13900K";
@@ -17,23 +17,28 @@ pub struct LogEntry {
     pub retention: LogRetention,
 }
 
+/// Formats a point in time as `HH:MM:SS`, the clock used for log timestamps.
+pub fn format_clock_time(time: std::time::SystemTime) -> String {
+    let secs = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    format!(
+        "{:02}:{:02}:{:02}",
+        (secs % 86400) / 3600, // hours
+        (secs % 3600) / 60,    // minutes
+        secs % 60              // seconds
+    )
+}
+
 impl LogEntry {
     /// Formats the log entry as a string: "[HH:MM:SS] :: message"
     pub fn format(&self) -> String {
-        let duration = self
-            .timestamp
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default();
-
-        let secs = duration.as_secs();
-        let ts = format!(
-            "[{:02}:{:02}:{:02}]",
-            (secs % 86400) / 3600, // hours
-            (secs % 3600) / 60,    // minutes
-            secs % 60              // seconds
-        );
-
-        format!("{ts} :: {}", self.message)
+        format!(
+            "[{}] :: {}",
+            format_clock_time(self.timestamp),
+            self.message
+        )
     }
 }
 
@@ -54,8 +59,10 @@ impl LogManager {
             retention,
         };
 
-        #[cfg(debug_assertions)]
-        println!("{}", entry.format());
+        match retention {
+            LogRetention::Regular => log::info!("{}", entry.message),
+            LogRetention::Important | LogRetention::Sticky => log::warn!("{}", entry.message),
+        }
 
         self.entries.push_back(entry);
         self.enforce_retention(retention);
@@ -133,7 +140,23 @@ impl LogManager {
 
 #[cfg(test)]
 mod tests {
-    use super::{LogManager, LogRetention, IMPORTANT_LOG_CAP, REGULAR_LOG_CAP};
+    use super::{
+        format_clock_time, LogEntry, LogManager, LogRetention, IMPORTANT_LOG_CAP, REGULAR_LOG_CAP,
+    };
+    use std::time::{Duration, UNIX_EPOCH};
+
+    #[test]
+    fn test_entries_and_clock_times_share_the_timestamp_format() {
+        let timestamp = UNIX_EPOCH + Duration::from_secs(86_400 + 3_600 * 13 + 60 * 5 + 9);
+        let entry = LogEntry {
+            message: "hello".to_string(),
+            timestamp,
+            retention: LogRetention::Regular,
+        };
+
+        assert_eq!(format_clock_time(timestamp), "13:05:09");
+        assert_eq!(entry.format(), "[13:05:09] :: hello");
+    }
 
     #[test]
     fn test_regular_retention_is_capped() {
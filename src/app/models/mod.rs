@@ -14,14 +14,22 @@ pub mod cpu_schema;
 /// Log management functionality
 mod log_manager;
 mod meta;
+/// Named per-group core layouts
+mod profile;
 /// Running application tracking
 mod running_app;
 
 // Public re-exports of key structures for use in other modules
-pub use app_state_storage::{AppStateStorage, StateStorageMode};
-pub use app_to_run::{normalize_process_name, AppRuntimeKey, AppToRun, LaunchTarget};
-pub use core_group::{AddAppsOutcome, CoreGroup};
+pub use app_state_storage::{
+    data_file_path, set_state_dir_override, AppStateStorage, SelfAffinity, StateStorageMode,
+};
+pub use app_to_run::{
+    normalize_process_name, AppRuntimeKey, AppToRun, LaunchBarrier, LaunchTarget, ReadyCondition,
+    SpreadStrategy, ThreadMatch, ThreadRule,
+};
+pub use core_group::{name_accent_color, AddAppsOutcome, CoreGroup, CoreSelector};
 pub use cpu_schema::{CoreInfo, CoreType, CpuCluster, CpuSchema};
-pub use log_manager::LogManager;
+pub use log_manager::{format_clock_time, LogManager};
 pub use meta::{effective_cpu_model, effective_total_threads, APP_VERSION};
-pub use running_app::{AppStatus, RunningApps};
+pub use profile::{AffinityFavorite, Profile};
+pub use running_app::{AffinityDrift, AppStatus, RunningApp, RunningApps};
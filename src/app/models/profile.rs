@@ -0,0 +1,75 @@
+use crate::app::shared::ids::GroupId;
+use serde::{Deserialize, Serialize};
+
+/// Cores that replace a group's saved cores while the owning profile is active.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GroupCoreOverride {
+    /// Logical identity of the overridden group
+    pub group_id: GroupId,
+    /// Indices of the CPU cores used instead of the group's base cores
+    pub cores: Vec<usize>,
+}
+
+/// Named set of threads applied once to a picked process, outside any group.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AffinityFavorite {
+    /// Button label, unique ignoring case
+    pub name: String,
+    /// Indices of the CPU threads the picked process is limited to
+    pub cores: Vec<usize>,
+}
+
+/// Named core layout layered on top of the groups' base cores.
+/// Groups without an override keep using their own cores.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    /// Display name of the profile, unique ignoring case
+    pub name: String,
+    /// Per-group core overrides
+    #[serde(default)]
+    pub group_cores: Vec<GroupCoreOverride>,
+}
+
+impl Profile {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            group_cores: Vec::new(),
+        }
+    }
+
+    pub fn cores_for(&self, group_id: &GroupId) -> Option<&[usize]> {
+        self.group_cores
+            .iter()
+            .find(|entry| &entry.group_id == group_id)
+            .map(|entry| entry.cores.as_slice())
+    }
+
+    /// Sets or clears the override for a group. Returns whether anything changed.
+    pub fn set_cores_for(&mut self, group_id: &GroupId, cores: Option<Vec<usize>>) -> bool {
+        let existing = self
+            .group_cores
+            .iter()
+            .position(|entry| &entry.group_id == group_id);
+
+        match (existing, cores) {
+            (Some(index), Some(cores)) => {
+                let changed = self.group_cores[index].cores != cores;
+                self.group_cores[index].cores = cores;
+                changed
+            }
+            (Some(index), None) => {
+                self.group_cores.remove(index);
+                true
+            }
+            (None, Some(cores)) => {
+                self.group_cores.push(GroupCoreOverride {
+                    group_id: group_id.clone(),
+                    cores,
+                });
+                true
+            }
+            (None, None) => false,
+        }
+    }
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
-use std::collections::HashMap;
+use os_api::PriorityClass;
+use std::collections::{HashMap, HashSet};
 
 use crate::app::models::AppRuntimeKey;
 use crate::app::shared::ids::{GroupId, RuleId};
@@ -11,6 +12,14 @@ pub enum AppStatus {
     SettingsMismatch,
 }
 
+/// A live process whose affinity differs from the mask its group intends.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AffinityDrift {
+    pub pid: u32,
+    pub actual_mask: usize,
+    pub expected_mask: usize,
+}
+
 /// Represents a single running application instance.
 /// This structure tracks information about a running application,
 /// including its process IDs, logical group/rule identities, and creation time.
@@ -25,6 +34,20 @@ pub struct RunningApp {
     pub created_at: std::time::SystemTime,
     /// Whether the CPU affinity and priority settings match the desired values
     pub settings_matched: bool,
+    /// Cores picked at launch by a least-loaded group; monitoring keeps the app on them
+    pub pinned_cores: Option<Vec<usize>>,
+    /// First process the last monitor pass found on the wrong cores and left there
+    pub affinity_drift: Option<AffinityDrift>,
+    /// Child processes already on the mask and priority they were checked against;
+    /// monitoring skips them until either expectation changes
+    pub settled_pids: HashMap<u32, (usize, PriorityClass)>,
+    /// Processes whose affinity could not be read or set because access was denied
+    /// or they were gone; monitoring still reports their drift but stops changing them
+    pub unreachable_pids: HashSet<u32>,
+    /// Requested and applied priority of processes the OS gave a lower class than asked
+    /// (e.g. High instead of Realtime without the rights); monitoring expects the applied
+    /// class instead of asking again, until the rule requests another class
+    pub priority_fallbacks: HashMap<u32, (PriorityClass, PriorityClass)>,
 }
 
 /// Manages a collection of running applications.
@@ -34,6 +57,11 @@ pub struct RunningApp {
 pub struct RunningApps {
     /// Map of application keys to RunningApp instances
     pub apps: HashMap<AppRuntimeKey, RunningApp>,
+    /// Processes released from tracking; monitors leave them alone until they exit
+    pub released_pids: HashSet<u32>,
+    /// Busy fraction of every core over the monitor's last interval, `None` until it
+    /// has taken two readings
+    pub core_usage: Option<Vec<f32>>,
 }
 
 impl RunningApps {
@@ -63,10 +91,23 @@ impl RunningApps {
                 rule_id,
                 created_at: std::time::SystemTime::now(),
                 settings_matched: true, // Default to true until checked by monitor
+                pinned_cores: None,
+                affinity_drift: None,
+                settled_pids: HashMap::new(),
+                unreachable_pids: HashSet::new(),
+                priority_fallbacks: HashMap::new(),
             },
         );
     }
 
+    /// Stops tracking an application and remembers its processes as released, so
+    /// detection does not pick them up again. Returns the released PIDs.
+    pub fn release_app(&mut self, app_key: &AppRuntimeKey) -> Option<Vec<u32>> {
+        let app = self.apps.remove(app_key)?;
+        self.released_pids.extend(app.pids.iter().copied());
+        Some(app.pids)
+    }
+
     /// Removes an application from the collection.
     ///
     /// # Parameters
@@ -75,6 +116,39 @@ impl RunningApps {
     pub fn remove_app(&mut self, app_key: &AppRuntimeKey) {
         self.apps.remove(app_key);
     }
+
+    /// Moves the entry tracked as `from` to `to` when it belongs to `group_id`.
+    ///
+    /// Returns whether the entry moved; an existing entry under `to` is kept.
+    pub fn rekey_app(
+        &mut self,
+        from: &AppRuntimeKey,
+        to: &AppRuntimeKey,
+        group_id: &GroupId,
+    ) -> bool {
+        if self.apps.contains_key(to)
+            || self
+                .apps
+                .get(from)
+                .is_none_or(|app| &app.group_id != group_id)
+        {
+            return false;
+        }
+        let app = self.apps.remove(from).expect("entry checked above");
+        self.apps.insert(to.clone(), app);
+        true
+    }
+
+    /// Restarts the runtime clock of a tracked application.
+    ///
+    /// # Parameters
+    ///
+    /// * `app_key` - The key of the application that restarted
+    pub fn restart_clock(&mut self, app_key: &AppRuntimeKey) {
+        if let Some(app) = self.apps.get_mut(app_key) {
+            app.created_at = std::time::SystemTime::now();
+        }
+    }
 }
 
 #[cfg(test)]
@@ -111,6 +185,19 @@ mod tests {
         assert!(app.settings_matched);
     }
 
+    #[test]
+    fn test_rekey_app_moves_only_entries_of_the_given_group() {
+        let mut apps = RunningApps::default();
+        let (shared, separate) = (key("Shared"), key("Separate"));
+        let games = GroupId("group-a".to_string());
+        apps.add_app(&shared, 42, games.clone(), RuleId("rule-a".to_string()));
+
+        assert!(!apps.rekey_app(&shared, &separate, &GroupId("group-b".to_string())));
+        assert!(apps.rekey_app(&shared, &separate, &games));
+        assert_eq!(apps.apps[&separate].pids, vec![42]);
+        assert!(!apps.apps.contains_key(&shared));
+    }
+
     #[test]
     fn test_add_app_replaces_existing_entry_for_same_runtime_key() {
         let mut apps = RunningApps::default();
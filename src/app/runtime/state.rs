@@ -1,31 +1,53 @@
 use crate::app::adapters::storage::StorageAdapter;
-use crate::app::features::execution::{self, RuntimeRegistry};
-use crate::app::features::preferences;
+use crate::app::adapters::{discovery, os};
+use crate::app::features::diagnostics::affinity_probe;
+use crate::app::features::diagnostics::audit;
+use crate::app::features::diagnostics::cpu_topology::CpuTopologyReport;
+use crate::app::features::execution::{
+    self, LaunchProgress, RunningAppSettingsUpdate, RuntimeRegistry,
+};
+use crate::app::features::preferences::{self, config_import};
+use crate::app::features::profiles;
 use crate::app::features::rules::{self, RulesContext};
 use crate::app::features::shortcut::{
     create_saved_rule_shortcut, CreateRuleShortcutError, RuleShortcutPlatform,
     SystemRuleShortcutPlatform,
 };
+use crate::app::features::topology::{self, GroupCoreOverlap};
+use crate::app::models::cpu_presets;
 use crate::app::models::cpu_schema::CpuSchema;
 use crate::app::models::{
-    effective_total_threads, AddAppsOutcome, AppRuntimeKey, AppStateStorage, AppStatus, AppToRun,
-    LogManager, StateStorageMode,
+    effective_total_threads, AddAppsOutcome, AffinityDrift, AffinityFavorite, AppRuntimeKey,
+    AppStateStorage, AppStatus, AppToRun, LogManager, SelfAffinity, StateStorageMode,
 };
 use crate::app::shared::ids::{GroupId, RuleId};
-use crate::app::shell::sessions::{RuleShortcutResult, ShortcutCreationRole};
+use crate::app::shell::sessions::{
+    DeletedGroup, PendingConfigImport, PendingFolderAdd, PendingFolderScan, PendingGroupPriority,
+    PendingRunGroup, RuleSelectMode, RuleShortcutResult, ShortcutCreationRole,
+};
 use crate::app::shell::UiSession;
 use crate::app::shell::{GroupRoute, WindowRoute};
-use os_api::InstalledAppCatalogEntry;
-use std::path::PathBuf;
+use os_api::{InstalledAppCatalogEntry, PriorityClass, PriorityClassName, OS};
+use std::path::{Path, PathBuf};
 use std::sync::mpsc::{self, TryRecvError};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime};
+
+/// Folder drops yielding more files than this ask before adding them.
+const FOLDER_ADD_CONFIRM_THRESHOLD: usize = 20;
+/// Logged when the tool's own affinity setting leaves it no thread to run on.
+const NO_SELF_AFFINITY_THREADS_WARNING: &str =
+    "WARNING: No thread matches the tool's affinity setting; it runs on all threads";
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct CentralProgramSnapshot {
     pub rule_id: RuleId,
     pub name: String,
     pub launch_target_detail: String,
+    /// Executable to reveal in the file manager; `None` for installed apps
+    pub bin_path: Option<PathBuf>,
     pub app_key: AppRuntimeKey,
+    pub priority: PriorityClass,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -34,13 +56,20 @@ pub(crate) struct CentralGroupSnapshot {
     pub name: String,
     pub cores: Vec<usize>,
     pub is_hidden: bool,
+    pub is_collapsed: bool,
+    pub enabled: bool,
+    pub color: [u8; 3],
     pub run_all_button: bool,
+    /// Queued launches still waiting out their delays, e.g. during autorun.
+    pub launch_progress: Option<LaunchProgress>,
     pub programs: Vec<CentralProgramSnapshot>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub(crate) struct CentralPanelSnapshot {
     pub groups: Vec<CentralGroupSnapshot>,
+    /// Group pairs sharing cores; empty when the warning is suppressed.
+    pub core_overlaps: Vec<GroupCoreOverlap>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -73,6 +102,18 @@ fn shortcut_disabled(
     }
 }
 
+/// Prefers an exact name match and falls back to a case-insensitive one.
+fn find_by_name<T>(items: &[T], name: &str, item_name: impl Fn(&T) -> &str) -> Option<usize> {
+    items
+        .iter()
+        .position(|item| item_name(item) == name)
+        .or_else(|| {
+            items
+                .iter()
+                .position(|item| item_name(item).eq_ignore_ascii_case(name))
+        })
+}
+
 fn default_shortcut_creation_role() -> ShortcutCreationRole {
     if cfg!(all(target_os = "windows", feature = "windows")) {
         ShortcutCreationRole::Primary
@@ -124,6 +165,12 @@ pub struct AppState {
     pub(crate) runtime: RuntimeRegistry,
     pub(crate) log_manager: LogManager,
     shortcut_creation_role: ShortcutCreationRole,
+    /// Affinity the tool's process had before it pinned itself; read once so the
+    /// pinned mask does not narrow later recomputations.
+    self_affinity_base: Option<usize>,
+    /// Mask last applied to the tool's own process (`Some(None)` while unpinned);
+    /// `None` until the first sync.
+    applied_self_affinity: Option<Option<usize>>,
     #[cfg(test)]
     save_count: usize,
 }
@@ -148,6 +195,8 @@ impl AppState {
             runtime: RuntimeRegistry::new(),
             log_manager: LogManager::default(),
             shortcut_creation_role: default_shortcut_creation_role(),
+            self_affinity_base: None,
+            applied_self_affinity: None,
             #[cfg(test)]
             save_count: 0,
         }
@@ -174,6 +223,8 @@ impl AppState {
             runtime: RuntimeRegistry::new(),
             log_manager: LogManager::default(),
             shortcut_creation_role: default_shortcut_creation_role(),
+            self_affinity_base: None,
+            applied_self_affinity: None,
             save_count: 0,
         }
     }
@@ -195,6 +246,8 @@ impl AppState {
     }
 
     fn persist_state(&mut self) -> bool {
+        // Any later change invalidates the position a deleted group would be restored to.
+        self.ui.recently_deleted_group = None;
         self.reconcile_rules();
 
         if let Ok(mut state) = self.persistent_state.write() {
@@ -264,11 +317,16 @@ impl AppState {
                     .snapshot(&state)
                     .groups
                     .into_iter()
-                    .map(|group| CentralGroupSnapshot {
+                    .enumerate()
+                    .map(|(group_index, group)| CentralGroupSnapshot {
+                        launch_progress: self.runtime.launch_progress(group_index),
                         group_id: group.id,
                         name: group.name,
                         cores: group.cores,
                         is_hidden: group.is_hidden,
+                        is_collapsed: group.is_collapsed,
+                        enabled: group.enabled,
+                        color: group.color,
                         run_all_button: group.run_all_enabled,
                         programs: group
                             .rules
@@ -277,11 +335,27 @@ impl AppState {
                                 rule_id: program.id.clone(),
                                 name: program.app.name.clone(),
                                 launch_target_detail: program.app.launch_target_detail(),
+                                bin_path: program.app.bin_path().map(PathBuf::from),
+                                priority: program.app.priority,
                                 app_key: program.app.get_key(),
                             })
                             .collect(),
                     })
                     .collect(),
+                core_overlaps: if state.hide_group_overlap_warning {
+                    Vec::new()
+                } else {
+                    let groups: Vec<(String, Vec<usize>)> = state
+                        .groups
+                        .iter()
+                        .enumerate()
+                        .map(|(index, group)| {
+                            let cores = state.effective_group_cores(index).unwrap_or_default();
+                            (group.name.clone(), cores)
+                        })
+                        .collect();
+                    topology::group_core_overlaps(&groups)
+                },
             },
             Err(_) => CentralPanelSnapshot::default(),
         }
@@ -294,6 +368,29 @@ impl AppState {
         }
     }
 
+    /// Cores of the group owning the rule being edited, with the active profile applied.
+    pub fn current_app_edit_group_cores(&mut self) -> Vec<usize> {
+        let Some(target) = self.ui.app_edit_state.target.clone() else {
+            return Vec::new();
+        };
+        let Some(group_index) = self.group_index_for_id(&target.group_id) else {
+            return Vec::new();
+        };
+        let Ok(state) = self.persistent_state.read() else {
+            return Vec::new();
+        };
+        state
+            .groups
+            .get(group_index)
+            .map(|group| {
+                state
+                    .active_profile_cores(&target.group_id)
+                    .unwrap_or(&group.cores)
+                    .to_vec()
+            })
+            .unwrap_or_default()
+    }
+
     pub fn set_group_is_hidden(&mut self, group_id: GroupId, is_hidden: bool) {
         let Some(group_index) = self.group_index_for_id(&group_id) else {
             return;
@@ -304,6 +401,56 @@ impl AppState {
         }
     }
 
+    pub fn set_group_is_collapsed(&mut self, group_id: GroupId, is_collapsed: bool) {
+        let Some(group_index) = self.group_index_for_id(&group_id) else {
+            return;
+        };
+
+        if rules::set_group_is_collapsed(&self.persistent_state, group_index, is_collapsed) {
+            let _ = self.persist_state();
+        }
+    }
+
+    /// Folds every group to its header line, or unfolds them all.
+    pub fn set_all_groups_collapsed(&mut self, is_collapsed: bool) {
+        if rules::set_all_groups_collapsed(&self.persistent_state, is_collapsed) {
+            let _ = self.persist_state();
+        }
+    }
+
+    /// Turns launching the group's apps on or off; apps already running stay monitored.
+    pub fn set_group_enabled(&mut self, group_id: GroupId, enabled: bool) {
+        let Some(group_index) = self.group_index_for_id(&group_id) else {
+            return;
+        };
+
+        if rules::set_group_enabled(&self.persistent_state, group_index, enabled) {
+            let group_name = self.get_group_name(group_index).unwrap_or_default();
+            let state = if enabled { "enabled" } else { "disabled" };
+            self.log_manager
+                .add_entry(format!("Group '{group_name}' {state}"));
+            let _ = self.persist_state();
+        }
+    }
+
+    /// Logs and returns `false` when the group at `group_index` is disabled.
+    fn ensure_group_enabled(&mut self, group_index: usize) -> bool {
+        let enabled = self
+            .persistent_state
+            .read()
+            .unwrap()
+            .groups
+            .get(group_index)
+            .is_none_or(|group| group.enabled);
+        if !enabled {
+            let group_name = self.get_group_name(group_index).unwrap_or_default();
+            self.log_manager.add_important_entry(format!(
+                "Group '{group_name}' is disabled; enable it to run its apps"
+            ));
+        }
+        enabled
+    }
+
     pub fn get_group_programs(&self, index: usize) -> Option<Vec<AppToRun>> {
         self.persistent_state
             .read()
@@ -326,6 +473,100 @@ impl AppState {
         self.persistent_state.read().unwrap().cpu_schema.clone()
     }
 
+    /// Saves the layout drafted in the topology panel as a user preset and re-detects
+    /// the CPU with it.
+    pub fn save_user_cpu_preset(&mut self) {
+        let layout = self.ui.preset_draft.clone();
+        let path = match cpu_presets::save_user_preset(&layout) {
+            Ok(path) => path,
+            Err(err) => {
+                self.log_manager
+                    .add_entry(format!("Cannot save CPU preset: {err}"));
+                return;
+            }
+        };
+        self.log_manager.add_entry(format!(
+            "Saved CPU preset '{}' to {}",
+            layout.name.trim(),
+            path.display()
+        ));
+
+        let report = CpuTopologyReport::detect();
+        match &report.schema {
+            Some(schema) => {
+                if let Ok(mut state) = self.persistent_state.write() {
+                    state.cpu_schema = schema.clone();
+                }
+                let _ = self.persist_state();
+                self.log_manager.add_entry(format!(
+                    "CPU layout: {} ({} clusters)",
+                    schema.model,
+                    schema.clusters.len()
+                ));
+            }
+            None => self.log_manager.add_entry(format!(
+                "WARNING: preset '{}' does not match this CPU (\"{}\", {} threads)",
+                layout.name.trim(),
+                report.model,
+                report.total_threads
+            )),
+        }
+        self.ui.cpu_topology = Some(report);
+    }
+
+    /// Re-reads the thread count, the CPUs the OS offers and the matching preset,
+    /// e.g. after a power-mode switch parked or woke cores, and fits the group editor
+    /// to them.
+    pub fn refresh_cpu_topology(&mut self) {
+        self.refresh_cpu_topology_with(CpuTopologyReport::detect(), os::available_core_mask());
+        self.refresh_numa_nodes();
+    }
+
+    fn refresh_cpu_topology_with(&mut self, report: CpuTopologyReport, available_core_mask: usize) {
+        let previous_threads = self.ui.group_form.core_selection.len();
+        self.ui.group_form.available_core_mask = available_core_mask;
+        let lost = self
+            .ui
+            .group_form
+            .resize_core_selection(report.total_threads);
+
+        let layout = {
+            let mut state = self.persistent_state.write().unwrap();
+            let stale = state
+                .cpu_schema
+                .get_assigned_cores()
+                .iter()
+                .any(|&core| core >= report.total_threads);
+            match &report.schema {
+                Some(schema) => state.cpu_schema = schema.clone(),
+                None if stale => {
+                    state.cpu_schema = CpuSchema {
+                        model: report.model.clone(),
+                        clusters: Vec::new(),
+                    }
+                }
+                None => {}
+            }
+            if state.cpu_schema.clusters.is_empty() {
+                "no preset matched".to_string()
+            } else {
+                format!("{} clusters", state.cpu_schema.clusters.len())
+            }
+        };
+        let _ = self.persist_state();
+
+        self.log_manager.add_entry(format!(
+            "CPU refreshed: {} threads (was {previous_threads}), available mask {available_core_mask:#X}, layout: {layout}",
+            report.total_threads
+        ));
+        if !lost.is_empty() {
+            self.log_manager.add_important_entry(format!(
+                "WARNING: selected threads {lost:?} are no longer available and were removed from the group being edited"
+            ));
+        }
+        self.ui.cpu_topology = Some(report);
+    }
+
     pub fn move_group_to_index(&mut self, group_id: GroupId, target_index: usize) -> bool {
         let Some(source_index) = self.group_index_for_id(&group_id) else {
             return false;
@@ -466,6 +707,16 @@ impl AppState {
             return MoveRuleToGroupOutcome::LockFailed;
         }
 
+        if source_group_index != target_group_index
+            && self
+                .runtime
+                .move_running_app_to_group(&moving_key, target_group_id)
+                == RunningAppSettingsUpdate::Busy
+        {
+            self.log_manager.add_important_entry(format!(
+                "WARNING: the running '{moving_name}' keeps its old cores until it is started again; the running-app list was busy"
+            ));
+        }
         let _ = self.persist_state();
         let message = if source_group_index == target_group_index {
             format!("Reordered app '{moving_name}' in group '{target_group_name}'")
@@ -476,23 +727,320 @@ impl AppState {
         MoveRuleToGroupOutcome::Moved
     }
 
-    pub fn add_selected_files_to_group(&mut self, group_id: GroupId, paths: Vec<PathBuf>) {
-        if paths.is_empty() {
-            return;
+    /// Group and rule index of a rule, whichever group it is in.
+    fn rule_location(&self, rule_id: &RuleId) -> Option<(usize, usize)> {
+        let group_count = self.persistent_state.read().ok()?.groups.len();
+        (0..group_count).find_map(|group_index| {
+            self.rules
+                .rule_index_for_id(group_index, rule_id)
+                .map(|rule_index| (group_index, rule_index))
+        })
+    }
+
+    /// Updates the highlighted app rows for a click on the name of `rule_id`.
+    pub fn select_rule(&mut self, rule_id: RuleId, mode: RuleSelectMode) {
+        let range = match (mode, &self.ui.selection_anchor) {
+            (RuleSelectMode::Range, Some(anchor)) => self
+                .rule_location(anchor)
+                .zip(self.rule_location(&rule_id))
+                .filter(|((anchor_group, _), (group, _))| anchor_group == group),
+            _ => None,
+        };
+        let selection = &mut self.ui.selected_rules;
+        match (mode, range) {
+            (RuleSelectMode::Range, Some(((group_index, from), (_, to)))) => {
+                *selection = (from.min(to)..=from.max(to))
+                    .filter_map(|rule_index| self.rules.rule_id_for_index(group_index, rule_index))
+                    .collect();
+                return;
+            }
+            (RuleSelectMode::Toggle, _) => {
+                if let Some(position) = selection.iter().position(|id| *id == rule_id) {
+                    selection.remove(position);
+                } else {
+                    selection.push(rule_id.clone());
+                }
+            }
+            _ => *selection = vec![rule_id.clone()],
+        }
+        self.ui.selection_anchor = Some(rule_id);
+    }
+
+    pub fn clear_rule_selection(&mut self) {
+        self.ui.selected_rules.clear();
+        self.ui.selection_anchor = None;
+    }
+
+    fn selected_rule_locations(&mut self) -> Vec<(usize, usize)> {
+        self.reconcile_rules();
+        self.ui
+            .selected_rules
+            .iter()
+            .filter_map(|rule_id| self.rule_location(rule_id))
+            .collect()
+    }
+
+    /// Removes the selected apps from their groups; returns how many were removed.
+    pub fn remove_selected_rules(&mut self) -> usize {
+        let mut locations = self.selected_rule_locations();
+        // Highest indices first so the remaining ones stay valid.
+        locations.sort_unstable_by(|a, b| b.cmp(a));
+        let mut removed = 0;
+        for (group_index, rule_index) in locations {
+            if let Some(path) =
+                rules::remove_rule_from_group(&self.persistent_state, group_index, rule_index)
+            {
+                self.rules.remove_rule(group_index, rule_index);
+                self.log_manager
+                    .add_entry(format!("Removing app: {}", path));
+                removed += 1;
+            }
+        }
+        if removed > 0 {
+            let _ = self.persist_state();
+        }
+        self.clear_rule_selection();
+        removed
+    }
+
+    /// Gives the selected apps the priority `class` from their next launch or monitor
+    /// pass on; each keeps its eco mode.
+    pub fn set_selected_rules_priority(&mut self, class: PriorityClassName) -> usize {
+        let locations = self.selected_rule_locations();
+        let updated = rules::set_rules_priority(&self.persistent_state, &locations, class);
+        if updated > 0 {
+            let _ = self.persist_state();
+            self.log_manager
+                .add_entry(format!("Set priority of {updated} app(s) to {:?}", class));
         }
+        updated
+    }
 
+    /// Asks to confirm giving every app of the group the priority `class`; the change
+    /// waits in `pending_group_priority`.
+    pub fn request_group_priority(&mut self, group_id: GroupId, priority: PriorityClassName) {
         let Some(group_index) = self.group_index_for_id(&group_id) else {
             return;
         };
+        let app_count = self
+            .get_group_programs(group_index)
+            .map_or(0, |programs| programs.len());
+        if app_count == 0 {
+            return;
+        }
+        self.ui.pending_group_priority = Some(PendingGroupPriority {
+            group_id,
+            group_name: self.get_group_name(group_index).unwrap_or_default(),
+            priority,
+            app_count,
+        });
+    }
+
+    /// Applies the group priority awaiting confirmation, if any.
+    pub fn confirm_pending_group_priority(&mut self) -> usize {
+        let Some(pending) = self.ui.pending_group_priority.take() else {
+            return 0;
+        };
+        self.set_group_priority(pending.group_id, pending.priority)
+    }
+
+    /// Gives every app of the group the priority `class`, keeping each app's eco mode,
+    /// saves it, and applies it to the apps' running processes at once. Returns how
+    /// many apps were updated.
+    pub fn set_group_priority(&mut self, group_id: GroupId, class: PriorityClassName) -> usize {
+        let Some(group_index) = self.group_index_for_id(&group_id) else {
+            return 0;
+        };
+        // Read before the change: running apps are tracked under the old rule keys.
+        let programs = self.get_group_programs(group_index).unwrap_or_default();
+        let locations: Vec<_> = (0..programs.len())
+            .map(|program_index| (group_index, program_index))
+            .collect();
+        let updated = rules::set_rules_priority(&self.persistent_state, &locations, class);
+        if updated == 0 {
+            return 0;
+        }
+        let _ = self.persist_state();
+
+        let adjusted = execution::apply_priority_to_running(
+            &self.runtime,
+            &mut self.log_manager,
+            &programs,
+            class,
+        );
+        self.log_manager.add_entry(format!(
+            "Set priority of {updated} app(s) in group '{}' to {:?}; {adjusted} running process(es) updated",
+            self.get_group_name(group_index).unwrap_or_default(),
+            class
+        ));
+        updated
+    }
+
+    /// Moves the selected apps to the end of the target group; returns how many moved.
+    pub fn move_selected_rules_to_group(&mut self, target_group_id: GroupId) -> usize {
+        let Some(target_group_index) = self.group_index_for_id(&target_group_id) else {
+            return 0;
+        };
+        let moving: Vec<(GroupId, RuleId)> = self
+            .ui
+            .selected_rules
+            .clone()
+            .into_iter()
+            .filter_map(|rule_id| {
+                let (group_index, _) = self.rule_location(&rule_id)?;
+                (group_index != target_group_index)
+                    .then(|| self.rules.group_id_for_index(group_index))
+                    .flatten()
+                    .map(|group_id| (group_id, rule_id))
+            })
+            .collect();
+
+        let mut moved = 0;
+        for (source_group_id, rule_id) in moving {
+            let end = self
+                .get_group_programs(target_group_index)
+                .map_or(0, |programs| programs.len());
+            if self.move_rule_to_group_at(source_group_id, rule_id, target_group_id.clone(), end)
+                == MoveRuleToGroupOutcome::Moved
+            {
+                moved += 1;
+            }
+        }
+        moved
+    }
+
+    /// Adds the files as rules of the group and returns how many were added. Folders
+    /// are scanned in the background and their files added later, see
+    /// [`Self::poll_folder_scan`].
+    pub fn add_selected_files_to_group(&mut self, group_id: GroupId, paths: Vec<PathBuf>) -> usize {
+        if paths.is_empty() {
+            return 0;
+        }
+
+        let Some(group_index) = self.group_index_for_id(&group_id) else {
+            return 0;
+        };
 
-        let attempted_count = paths.len();
         let group_name = self.get_group_name(group_index).unwrap_or_default();
         self.log_manager.add_entry(format!(
             "Adding app targets to group: {group_name}, paths: {paths:?}"
         ));
 
-        let outcome = rules::add_apps_to_group(&self.persistent_state, group_index, paths);
-        self.handle_add_apps_outcome(group_index, &group_name, attempted_count, outcome);
+        self.add_paths_expanding_folders(group_id, group_index, &group_name, paths)
+    }
+
+    /// Adds plain files right away; paths with folders among them are handed to a
+    /// background scan that replaces each folder with the launchable files inside it.
+    fn add_paths_expanding_folders(
+        &mut self,
+        group_id: GroupId,
+        group_index: usize,
+        group_name: &str,
+        paths: Vec<PathBuf>,
+    ) -> usize {
+        if !paths.iter().any(|path| path.is_dir()) {
+            let attempted_count = paths.len();
+            let outcome = rules::add_apps_to_group(&self.persistent_state, group_index, paths);
+            return self.handle_add_apps_outcome(group_index, group_name, attempted_count, outcome);
+        }
+
+        if self.ui.pending_folder_scan.is_some() {
+            self.log_manager.add_entry(format!(
+                "Dropped folders were not added to group {group_name}: another folder scan is still running"
+            ));
+            return 0;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.ui.pending_folder_scan = Some(PendingFolderScan { group_id, rx });
+        std::thread::spawn(move || {
+            let _ = tx.send(discovery::expand_dropped_folders(paths));
+        });
+        0
+    }
+
+    /// Adds the files found by a finished folder scan. Large scans wait in
+    /// `pending_folder_add` for confirmation and add nothing yet.
+    pub fn poll_folder_scan(&mut self) {
+        let Some(scan) = self.ui.pending_folder_scan.take() else {
+            return;
+        };
+
+        let expanded = match scan.rx.try_recv() {
+            Ok(expanded) => expanded,
+            Err(TryRecvError::Empty) => {
+                self.ui.pending_folder_scan = Some(scan);
+                return;
+            }
+            Err(TryRecvError::Disconnected) => {
+                self.log_manager
+                    .add_entry("Folder scan channel disconnected".to_string());
+                return;
+            }
+        };
+
+        let group_id = scan.group_id;
+        let Some(group_index) = self.group_index_for_id(&group_id) else {
+            self.log_manager.add_entry(
+                "Scanned folders were not added: the group no longer exists".to_string(),
+            );
+            return;
+        };
+        let group_name = self.get_group_name(group_index).unwrap_or_default();
+
+        let pending = PendingFolderAdd {
+            group_id,
+            paths: expanded.paths,
+            folder_count: expanded.folder_count,
+            found_in_folders: expanded.found_in_folders,
+            truncated: expanded.truncated,
+        };
+        if pending.found_in_folders > FOLDER_ADD_CONFIRM_THRESHOLD {
+            self.ui.pending_folder_add = Some(pending);
+            return;
+        }
+        self.add_folder_scan(group_index, &group_name, pending);
+    }
+
+    fn add_folder_scan(
+        &mut self,
+        group_index: usize,
+        group_name: &str,
+        pending: PendingFolderAdd,
+    ) -> usize {
+        let attempted_count = pending.paths.len();
+        let outcome = rules::add_apps_to_group(&self.persistent_state, group_index, pending.paths);
+        let added = outcome.added_count;
+        let limit_note = if pending.truncated {
+            format!(
+                " (scan stopped at {} files)",
+                discovery::FOLDER_SCAN_MAX_FILES
+            )
+        } else {
+            String::new()
+        };
+        self.log_manager.add_entry(format!(
+            "Scanned {} folder(s) for group {}: found {} launchable files{}, added {} of {} targets",
+            pending.folder_count,
+            group_name,
+            pending.found_in_folders,
+            limit_note,
+            added,
+            attempted_count
+        ));
+        self.handle_add_apps_outcome(group_index, group_name, attempted_count, outcome)
+    }
+
+    /// Adds the files of a confirmed large folder drop.
+    pub fn confirm_pending_folder_add(&mut self) -> usize {
+        let Some(pending) = self.ui.pending_folder_add.take() else {
+            return 0;
+        };
+        let Some(group_index) = self.group_index_for_id(&pending.group_id) else {
+            return 0;
+        };
+        let group_name = self.get_group_name(group_index).unwrap_or_default();
+        self.add_folder_scan(group_index, &group_name, pending)
     }
 
     pub fn add_installed_app_to_group(
@@ -511,11 +1059,17 @@ impl AppState {
         if outcome.added_count > 0 {
             self.rules
                 .append_rules_to_group(group_index, outcome.added_count);
+            self.separate_shared_apps_if_enabled();
             let _ = self.persist_state();
             self.log_manager.add_entry(format!(
                 "Added installed app '{app_name}' to group: {group_name}"
             ));
         }
+        if !outcome.skipped_names.is_empty() {
+            self.log_manager.add_entry(format!(
+                "Skipped installed app '{app_name}': group {group_name} already has it"
+            ));
+        }
 
         if let Some(err) = outcome.first_error {
             self.log_manager
@@ -523,27 +1077,101 @@ impl AppState {
         }
     }
 
-    pub fn consume_dropped_files_into_group(&mut self, group_id: GroupId) -> bool {
-        self.ui.file_drop_hover_target = None;
-        let Some(files) = self.ui.dropped_files.take() else {
+    /// Copies a rule into another group; the copy is launched and tracked on its own.
+    pub fn clone_rule_to_group(
+        &mut self,
+        source_group_id: GroupId,
+        rule_id: RuleId,
+        target_group_id: GroupId,
+    ) -> bool {
+        self.reconcile_rules();
+
+        let (Some(source_group_index), Some(target_group_index)) = (
+            self.group_index_for_id(&source_group_id),
+            self.group_index_for_id(&target_group_id),
+        ) else {
+            return false;
+        };
+        let Some(rule_index) = self.rules.rule_index_for_id(source_group_index, &rule_id) else {
             return false;
         };
 
+        let result = match self.persistent_state.write() {
+            Ok(mut state) => rules::clone_rule_to_group(
+                &mut state,
+                source_group_index,
+                rule_index,
+                target_group_index,
+            ),
+            Err(_) => Err("state lock failed".to_string()),
+        };
+        match result {
+            Ok((app_name, group_name)) => {
+                self.rules.append_rules_to_group(target_group_index, 1);
+                let _ = self.persist_state();
+                self.log_manager
+                    .add_entry(format!("Copied app '{app_name}' to group: {group_name}"));
+                true
+            }
+            Err(err) => {
+                self.log_manager
+                    .add_entry(format!("Cannot copy app to group: {err}"));
+                false
+            }
+        }
+    }
+
+    /// Adds dropped files to the pending set, skipping paths already in it, so drops
+    /// reported over several frames are handled once.
+    pub fn queue_dropped_files(&mut self, files: Vec<PathBuf>) {
         if files.is_empty() {
-            return false;
+            return;
+        }
+        let pending = self.ui.dropped_files.get_or_insert_with(Vec::new);
+        for file in files {
+            if !pending.contains(&file) {
+                pending.push(file);
+            }
+        }
+    }
+
+    /// Asks which group should take files dropped outside every group.
+    pub fn open_drop_group_picker(&mut self) {
+        if self.ui.dropped_files.is_some() {
+            self.ui.drop_group_picker_open = true;
+        }
+    }
+
+    pub fn discard_dropped_files(&mut self) {
+        self.ui.drop_group_picker_open = false;
+        self.ui.file_drop_hover_target = None;
+        if let Some(files) = self.ui.dropped_files.take() {
+            self.log_manager
+                .add_entry(format!("Discarded {} dropped file(s)", files.len()));
+        }
+    }
+
+    /// Adds pending dropped files to the group. Returns `None` when there was nothing
+    /// to consume or the group is gone, which is logged since the files are dropped,
+    /// otherwise how many rules were added.
+    pub fn consume_dropped_files_into_group(&mut self, group_id: GroupId) -> Option<usize> {
+        self.ui.file_drop_hover_target = None;
+        self.ui.drop_group_picker_open = false;
+        let files = self.ui.dropped_files.take()?;
+
+        if files.is_empty() {
+            return None;
         }
 
         let Some(group_index) = self.group_index_for_id(&group_id) else {
-            return false;
+            self.log_manager
+                .add_entry("Dropped files were not added: the group no longer exists".to_string());
+            return None;
         };
 
-        let files_count = files.len();
         let group_name = self.get_group_name(group_index).unwrap_or_default();
 
-        let outcome = rules::add_apps_to_group(&self.persistent_state, group_index, files);
-        self.handle_add_apps_outcome(group_index, &group_name, files_count, outcome);
-
-        true
+        Some(self.add_paths_expanding_folders(group_id, group_index, &group_name, files))
     }
 
     fn handle_add_apps_outcome(
@@ -552,10 +1180,11 @@ impl AppState {
         group_name: &str,
         attempted_count: usize,
         outcome: AddAppsOutcome,
-    ) {
+    ) -> usize {
         if outcome.added_count > 0 {
             self.rules
                 .append_rules_to_group(group_index, outcome.added_count);
+            self.separate_shared_apps_if_enabled();
             let _ = self.persist_state();
 
             if outcome.added_count == attempted_count {
@@ -563,16 +1192,29 @@ impl AppState {
                     .add_entry(format!("Added app targets to group: {group_name}"));
             } else {
                 self.log_manager.add_entry(format!(
-                    "Added {} app targets to group: {}",
-                    outcome.added_count, group_name
+                    "Added {} app targets to group {}: {}",
+                    outcome.added_count,
+                    group_name,
+                    outcome.added_names.join(", ")
                 ));
             }
         }
 
+        if !outcome.skipped_names.is_empty() {
+            self.log_manager.add_entry(format!(
+                "Skipped {} duplicate app targets already in group {}: {}",
+                outcome.skipped_names.len(),
+                group_name,
+                outcome.skipped_names.join(", ")
+            ));
+        }
+
         if let Some(err) = outcome.first_error {
             self.log_manager
                 .add_entry(format!("Error adding app targets: {err}"));
         }
+
+        outcome.added_count
     }
 
     pub fn get_theme_index(&self) -> usize {
@@ -582,19 +1224,35 @@ impl AppState {
     pub fn start_app_with_autorun(&mut self) {
         execution::start_app_with_autorun(
             &self.persistent_state,
-            &self.runtime,
+            &mut self.runtime,
             &mut self.log_manager,
         );
     }
 
+    /// Starts delayed autorun apps that are due; returns how long until the next one.
+    pub fn poll_autorun(&mut self) -> Option<Duration> {
+        execution::run_due_autoruns(
+            &self.persistent_state,
+            &mut self.runtime,
+            &mut self.log_manager,
+        )
+    }
+
     pub fn toggle_theme(&mut self) {
         preferences::toggle_theme(&self.persistent_state);
         let _ = self.persist_state();
     }
 
+    /// Flips monitoring; the monitor picks the new flag up on its next tick.
     pub fn toggle_process_monitoring(&mut self) {
         preferences::toggle_process_monitoring(&self.persistent_state);
         let _ = self.persist_state();
+        self.log_manager
+            .add_entry(if self.is_process_monitoring_enabled() {
+                "Process monitoring resumed".to_string()
+            } else {
+                "Process monitoring paused".to_string()
+            });
     }
 
     pub fn is_process_monitoring_enabled(&self) -> bool {
@@ -604,7 +1262,142 @@ impl AppState {
             .process_monitoring_enabled
     }
 
+    pub fn profile_names(&self) -> Vec<String> {
+        profiles::profile_names(&self.persistent_state)
+    }
+
+    pub fn active_profile_name(&self) -> Option<String> {
+        profiles::active_profile_name(&self.persistent_state)
+    }
+
+    pub fn favorites(&self) -> Vec<AffinityFavorite> {
+        profiles::favorites(&self.persistent_state)
+    }
+
+    /// Saves the header's new-favorite form; returns whether a favorite was added.
+    pub fn add_favorite_from_form(&mut self) -> bool {
+        match profiles::add_favorite(
+            &self.persistent_state,
+            &self.ui.new_favorite_name,
+            &self.ui.new_favorite_threads,
+            effective_total_threads(),
+        ) {
+            Ok(favorite) => {
+                let _ = self.persist_state();
+                self.log_manager.add_entry(format!(
+                    "Added favorite \"{}\" for threads {:?}",
+                    favorite.name, favorite.cores
+                ));
+                self.ui.new_favorite_name.clear();
+                self.ui.new_favorite_threads.clear();
+                true
+            }
+            Err(err) => {
+                self.log_manager.add_entry(err);
+                false
+            }
+        }
+    }
+
+    pub fn remove_favorite(&mut self, name: &str) {
+        if profiles::remove_favorite(&self.persistent_state, name) {
+            let _ = self.persist_state();
+        }
+    }
+
+    /// Opens the Processes view so the next process clicked there gets the favorite's mask.
+    pub fn start_favorite_pick(&mut self, favorite: AffinityFavorite) {
+        self.ui.process_list.pick_favorite = Some(favorite);
+        self.set_current_window(WindowRoute::Processes);
+    }
+
+    /// Limits `pid` to the threads of the favorite being picked for and ends the pick.
+    pub fn apply_favorite_to_process(&mut self, pid: u32) {
+        let Some(favorite) = self.ui.process_list.pick_favorite.take() else {
+            return;
+        };
+        let process_name = self
+            .ui
+            .process_list
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)
+            .map_or_else(|| format!("PID {pid}"), |process| process.name.clone());
+        let mask = OS::mask_from_cores(favorite.cores.iter().copied());
+        match os::set_process_affinity(pid, mask) {
+            Ok(()) => {
+                self.log_manager.add_entry(format!(
+                    "Applied favorite \"{}\" to {process_name} (PID {pid})",
+                    favorite.name
+                ));
+                if let Some(process) = self
+                    .ui
+                    .process_list
+                    .processes
+                    .iter_mut()
+                    .find(|process| process.pid == pid)
+                {
+                    process.affinity_mask = mask;
+                }
+            }
+            Err(err) => self.log_manager.add_important_entry(format!(
+                "ERROR: could not apply favorite \"{}\" to {process_name} (PID {pid}): {err}",
+                favorite.name
+            )),
+        }
+    }
+
+    pub fn set_active_profile(&mut self, name: Option<&str>) {
+        if profiles::set_active_profile(&self.persistent_state, name) {
+            let _ = self.persist_state();
+            self.log_manager.add_entry(match name {
+                Some(name) => format!("Switched to core profile: {name}"),
+                None => "Switched to base group cores".to_string(),
+            });
+        }
+    }
+
+    pub fn create_profile(&mut self, name: &str) -> bool {
+        match profiles::create_profile(&self.persistent_state, name) {
+            Ok(()) => {
+                let _ = self.persist_state();
+                self.log_manager
+                    .add_entry(format!("Created core profile: {}", name.trim()));
+                true
+            }
+            Err(err) => {
+                self.log_manager.add_entry(err);
+                false
+            }
+        }
+    }
+
+    pub fn delete_active_profile(&mut self) {
+        if profiles::delete_active_profile(&self.persistent_state) {
+            let _ = self.persist_state();
+        }
+    }
+
+    /// Existing group whose name the group form's name collides with.
+    pub fn group_form_name_conflict(&mut self) -> Option<String> {
+        let except_index = self
+            .ui
+            .group_form
+            .editing_group_id
+            .clone()
+            .and_then(|group_id| self.group_index_for_id(&group_id));
+        let state = self.persistent_state.read().ok()?;
+        rules::group_name_conflict(&state.groups, &self.ui.group_form.group_name, except_index)
+    }
+
     pub fn commit_group_form_session(&mut self) {
+        // The form stays open so the name can be changed instead of losing the edits.
+        if let Some(existing) = self.group_form_name_conflict() {
+            self.log_manager.add_important_entry(format!(
+                "A group named \"{existing}\" already exists; pick another name"
+            ));
+            return;
+        }
         let should_save = if let Some(group_id) = self.ui.group_form.editing_group_id.clone() {
             let Some(index) = self.group_index_for_id(&group_id) else {
                 self.ui.reset_group_form();
@@ -613,14 +1406,69 @@ impl AppState {
                 return;
             };
 
+            let save_to_profile =
+                self.ui.group_form.save_to_profile && self.ui.group_form.active_profile.is_some();
+            let base_selection = if save_to_profile {
+                let mut selection = vec![false; self.ui.group_form.core_selection.len()];
+                for &core in &self.ui.group_form.base_cores {
+                    if let Some(selected) = selection.get_mut(core) {
+                        *selected = true;
+                    }
+                }
+                selection
+            } else {
+                self.ui.group_form.core_selection.clone()
+            };
+
             match rules::update_group_properties(
                 &self.persistent_state,
                 index,
                 self.ui.group_form.group_name.clone(),
-                &self.ui.group_form.core_selection,
+                &base_selection,
                 self.ui.group_form.run_all_enabled,
+                self.ui.group_form.single_numa_node,
+                self.ui.group_form.default_priority,
             ) {
-                Ok(updated) => updated,
+                Ok(updated) => {
+                    let updated = rules::set_group_core_selector(
+                        &self.persistent_state,
+                        index,
+                        self.ui.group_form.core_selector,
+                    ) || updated;
+                    let updated = rules::set_group_exclude_names(
+                        &self.persistent_state,
+                        index,
+                        rules::parse_exclude_names(&self.ui.group_form.exclude_names),
+                    ) || updated;
+                    let updated = rules::set_group_use_job_object(
+                        &self.persistent_state,
+                        index,
+                        self.ui.group_form.use_job_object,
+                    ) || updated;
+                    let updated = rules::set_group_cpu_rate_percent(
+                        &self.persistent_state,
+                        index,
+                        self.ui.group_form.cpu_rate_percent,
+                    ) || updated;
+                    let updated = rules::set_group_color(
+                        &self.persistent_state,
+                        index,
+                        self.ui.group_form.color,
+                    ) || updated;
+                    let override_cores = save_to_profile
+                        .then(|| rules::selected_cores(&self.ui.group_form.core_selection));
+                    if override_cores.as_ref().is_some_and(Vec::is_empty) {
+                        self.log_manager
+                            .add_entry("At least one core must be selected".to_string());
+                        false
+                    } else {
+                        profiles::set_group_override(
+                            &self.persistent_state,
+                            &group_id,
+                            override_cores,
+                        ) || updated
+                    }
+                }
                 Err(err) => {
                     self.log_manager.add_entry(err);
                     false
@@ -632,8 +1480,34 @@ impl AppState {
                 &self.ui.group_form.group_name,
                 &self.ui.group_form.core_selection,
                 self.ui.group_form.run_all_enabled,
+                self.ui.group_form.single_numa_node,
+                self.ui.group_form.default_priority,
             ) {
-                Ok(()) => true,
+                Ok(()) => {
+                    let index = self.persistent_state.read().unwrap().groups.len() - 1;
+                    rules::set_group_core_selector(
+                        &self.persistent_state,
+                        index,
+                        self.ui.group_form.core_selector,
+                    );
+                    rules::set_group_exclude_names(
+                        &self.persistent_state,
+                        index,
+                        rules::parse_exclude_names(&self.ui.group_form.exclude_names),
+                    );
+                    rules::set_group_use_job_object(
+                        &self.persistent_state,
+                        index,
+                        self.ui.group_form.use_job_object,
+                    );
+                    rules::set_group_cpu_rate_percent(
+                        &self.persistent_state,
+                        index,
+                        self.ui.group_form.cpu_rate_percent,
+                    );
+                    rules::set_group_color(&self.persistent_state, index, self.ui.group_form.color);
+                    true
+                }
                 Err(err) => {
                     self.log_manager.add_entry(err);
                     false
@@ -656,9 +1530,17 @@ impl AppState {
     pub fn delete_current_group_form_target(&mut self) {
         if let Some(group_id) = self.ui.group_form.editing_group_id.clone() {
             if let Some(index) = self.group_index_for_id(&group_id) {
-                if rules::remove_group(&self.persistent_state, index) {
-                    self.rules.remove_group(index);
+                if let Some(group) = rules::remove_group(&self.persistent_state, index) {
+                    let profile_cores = profiles::forget_group(&self.persistent_state, &group_id);
+                    let identity = self.rules.remove_group(index);
                     let _ = self.persist_state();
+                    self.ui.recently_deleted_group = identity.map(|identity| DeletedGroup {
+                        index,
+                        group,
+                        identity,
+                        profile_cores,
+                        deleted_at: Instant::now(),
+                    });
                 }
             }
         }
@@ -668,6 +1550,53 @@ impl AppState {
             .set_current_window(WindowRoute::Groups(GroupRoute::List));
     }
 
+    /// Name and program count of the group the editor would delete.
+    pub fn group_form_delete_summary(&mut self) -> Option<(String, usize)> {
+        let group_id = self.ui.group_form.editing_group_id.clone()?;
+        let index = self.group_index_for_id(&group_id)?;
+        let state = self.persistent_state.read().ok()?;
+        state
+            .groups
+            .get(index)
+            .map(|group| (group.name.clone(), group.programs.len()))
+    }
+
+    /// Name of the group whose deletion can still be undone.
+    pub fn undoable_deleted_group_name(&mut self) -> Option<String> {
+        if self
+            .ui
+            .recently_deleted_group
+            .as_ref()
+            .is_some_and(DeletedGroup::is_expired)
+        {
+            self.ui.recently_deleted_group = None;
+        }
+        self.ui
+            .recently_deleted_group
+            .as_ref()
+            .map(|deleted| deleted.group.name.clone())
+    }
+
+    /// Restores the last deleted group at its original position when still possible.
+    pub fn undo_group_delete(&mut self) -> bool {
+        let Some(deleted) = self.ui.recently_deleted_group.take() else {
+            return false;
+        };
+        if deleted.is_expired() {
+            return false;
+        }
+
+        let name = deleted.group.name.clone();
+        let group_id = deleted.identity.id.clone();
+        let index = rules::insert_group(&self.persistent_state, deleted.index, deleted.group);
+        self.rules.insert_group(index, deleted.identity);
+        profiles::restore_group_overrides(&self.persistent_state, &group_id, deleted.profile_cores);
+        let _ = self.persist_state();
+        self.log_manager
+            .add_entry(format!("Group restored: {name}"));
+        true
+    }
+
     pub fn cancel_group_form_session(&mut self) {
         self.ui.reset_group_form();
         self.ui
@@ -694,26 +1623,236 @@ impl AppState {
 
     pub fn start_creating_group(&mut self) {
         self.ui.reset_group_form();
+        self.refresh_available_cores();
         self.set_current_window(WindowRoute::Groups(GroupRoute::Create));
     }
 
+    /// Re-reads which CPUs the OS offers and which the user reserved so the editor
+    /// can lock both out.
+    fn refresh_available_cores(&mut self) {
+        self.ui.group_form.available_core_mask = crate::app::adapters::os::available_core_mask();
+        self.ui.group_form.reserved_cores = self
+            .persistent_state
+            .read()
+            .map(|state| state.reserved_cores.clone())
+            .unwrap_or_default();
+        self.refresh_numa_nodes();
+    }
+
+    /// Looks up each thread's NUMA node once, so the editor's span warning doesn't
+    /// query the OS every frame.
+    fn refresh_numa_nodes(&mut self) {
+        let threads = self.ui.group_form.core_selection.len();
+        self.ui.group_form.numa_nodes = (0..threads).map(os::numa_node_for_core).collect();
+    }
+
+    /// Saves reservations toggled in the group editor; they apply to all groups at once.
+    pub fn sync_reserved_cores_from_form(&mut self) {
+        let reserved = self.ui.group_form.reserved_cores.clone();
+        {
+            let mut state = self.persistent_state.write().unwrap();
+            if state.reserved_cores == reserved {
+                return;
+            }
+            state.reserved_cores = reserved.clone();
+        }
+        let _ = self.persist_state();
+        self.log_manager
+            .add_entry(format!("Reserved CPU threads: {reserved:?}"));
+    }
+
+    /// Starts the "Test affinity" diagnostic for the threads selected in the group form.
+    pub fn request_affinity_probe(&mut self) {
+        if self.ui.group_form.affinity_probe_rx.is_some() {
+            return;
+        }
+
+        let cores: Vec<usize> = self
+            .ui
+            .group_form
+            .core_selection
+            .iter()
+            .enumerate()
+            .filter_map(|(index, selected)| selected.then_some(index))
+            .collect();
+        let (tx, rx) = mpsc::channel();
+        self.ui.group_form.affinity_probe_result = None;
+        self.ui.group_form.affinity_probe_rx = Some(rx);
+
+        std::thread::spawn(move || {
+            let _ = tx.send(affinity_probe::run_affinity_probe(&cores));
+        });
+    }
+
+    /// Re-reads running processes with their affinity and priority in the background.
+    pub fn request_process_list_refresh(&mut self) {
+        if self.ui.process_list.is_refreshing() {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel();
+        self.ui.process_list.refresh_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(os::list_processes_with_affinity());
+        });
+    }
+
+    pub fn poll_process_list(&mut self) {
+        let Some(rx) = self.ui.process_list.refresh_rx.take() else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(processes) => self.ui.process_list.set_processes(processes),
+            Err(TryRecvError::Empty) => self.ui.process_list.refresh_rx = Some(rx),
+            Err(TryRecvError::Disconnected) => {
+                self.log_manager
+                    .add_entry("Process list refresh channel disconnected".to_string());
+                self.ui.process_list.set_processes(Vec::new());
+            }
+        }
+    }
+
+    /// Groups in display order, for pickers that refer to a group by id.
+    pub fn group_choices(&mut self) -> Vec<(GroupId, String)> {
+        self.reconcile_rules();
+        let Ok(state) = self.persistent_state.read() else {
+            return Vec::new();
+        };
+        state
+            .groups
+            .iter()
+            .enumerate()
+            .filter_map(|(index, group)| {
+                self.rules
+                    .group_id_for_index(index)
+                    .map(|id| (id, group.name.clone()))
+            })
+            .collect()
+    }
+
+    /// Applies a group's threads and default priority to one running process, once;
+    /// the process is not tracked afterwards.
+    pub fn apply_group_to_process(&mut self, group_id: &GroupId, pid: u32) {
+        let Some(index) = self.group_index_for_id(group_id) else {
+            return;
+        };
+        let Some((cores, (group_name, priority))) = ({
+            let state = self.persistent_state.read().unwrap();
+            state.effective_group_cores(index).zip(
+                state
+                    .groups
+                    .get(index)
+                    .map(|group| (group.name.clone(), group.default_priority)),
+            )
+        }) else {
+            return;
+        };
+        let process_name = self
+            .ui
+            .process_list
+            .processes
+            .iter()
+            .find(|process| process.pid == pid)
+            .map_or_else(|| format!("PID {pid}"), |process| process.name.clone());
+
+        let mask = OS::mask_from_cores(cores.iter().copied());
+        let result = os::set_process_affinity(pid, mask)
+            .and_then(|()| os::set_process_priority(pid, priority));
+        match result {
+            Ok(()) => {
+                self.log_manager.add_entry(format!(
+                    "Applied group \"{group_name}\" to {process_name} (PID {pid})"
+                ));
+                if let Some(process) = self
+                    .ui
+                    .process_list
+                    .processes
+                    .iter_mut()
+                    .find(|process| process.pid == pid)
+                {
+                    process.affinity_mask = mask;
+                    process.priority = priority;
+                }
+            }
+            Err(err) => self.log_manager.add_important_entry(format!(
+                "ERROR: could not apply group \"{group_name}\" to {process_name} (PID {pid}): {err}"
+            )),
+        }
+    }
+
+    pub fn poll_affinity_probe(&mut self) {
+        let Some(rx) = self.ui.group_form.affinity_probe_rx.take() else {
+            return;
+        };
+
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(TryRecvError::Empty) => {
+                self.ui.group_form.affinity_probe_rx = Some(rx);
+                return;
+            }
+            Err(TryRecvError::Disconnected) => {
+                Err("Affinity test channel disconnected".to_string())
+            }
+        };
+
+        let message = match &result {
+            Ok(report) if report.matches_mask() => {
+                format!(
+                    "Affinity test for {:?}: {}",
+                    report.requested,
+                    report.summary()
+                )
+            }
+            Ok(report) => format!(
+                "WARNING: Affinity test for {:?} did not match the mask. {}",
+                report.requested,
+                report.summary()
+            ),
+            Err(err) => format!("Affinity test failed: {err}"),
+        };
+        self.log_manager.add_entry(message);
+        self.ui.group_form.affinity_probe_result = Some(result);
+    }
+
     pub fn start_editing_group(&mut self, group_id: GroupId) {
         let Some(group_index) = self.group_index_for_id(&group_id) else {
             return;
         };
 
         if let Some(group) = rules::load_group_for_edit(&self.persistent_state, group_index) {
+            let profile_cores = self.persistent_state.read().ok().and_then(|state| {
+                let profile = state.active_profile()?;
+                Some((
+                    profile.name.clone(),
+                    profile.cores_for(&group_id).map(<[usize]>::to_vec),
+                ))
+            });
+            let (active_profile, override_cores) = profile_cores.unzip();
+            let override_cores = override_cores.flatten();
             let total_cores = self.ui.group_form.core_selection.len();
             let mut selection = vec![false; total_cores];
-            for core in group.selected_cores {
+            for &core in override_cores.as_ref().unwrap_or(&group.selected_cores) {
                 if core < total_cores {
                     selection[core] = true;
                 }
             }
             self.ui.group_form.core_selection = selection;
+            self.ui.group_form.save_to_profile = override_cores.is_some();
+            self.ui.group_form.active_profile = active_profile;
+            self.ui.group_form.base_cores = group.selected_cores;
             self.ui.group_form.group_name = group.name;
             self.ui.group_form.run_all_enabled = group.run_all_enabled;
+            self.ui.group_form.single_numa_node = group.single_numa_node;
+            self.ui.group_form.default_priority = group.default_priority;
+            self.ui.group_form.core_selector = group.core_selector;
+            self.ui.group_form.exclude_names = group.exclude_names.join(", ");
+            self.ui.group_form.use_job_object = group.use_job_object;
+            self.ui.group_form.cpu_rate_percent = group.cpu_rate_percent;
+            self.ui.group_form.color = group.color;
             self.ui.group_form.last_clicked_core = None;
+            self.refresh_available_cores();
             self.ui.current_window = WindowRoute::Groups(GroupRoute::Edit);
         } else {
             self.log_manager
@@ -796,6 +1935,16 @@ impl AppState {
         }
     }
 
+    /// Stops the app awaiting confirmation, if any.
+    pub fn confirm_pending_app_stop(&mut self) -> Option<RunRuleOutcome> {
+        let pending = self.ui.pending_app_stop.take()?;
+        Some(self.run_group_program_action(
+            pending.group_id,
+            pending.rule_id,
+            execution::AppRowAction::Stop,
+        ))
+    }
+
     pub fn create_shortcut_for_rule_with_platform(
         &mut self,
         group_id: GroupId,
@@ -956,70 +2105,513 @@ impl AppState {
                 Some(RuleShortcutDisabledReason::MissingRule) => {
                     CreateRuleShortcutError::MissingRule
                 }
-                None => CreateRuleShortcutError::NoTarget,
-            };
-            self.ui.app_edit_state.shortcut_result = Some(RuleShortcutResult::Failed {
-                message: if matches!(
-                    status.disabled_reason,
-                    Some(RuleShortcutDisabledReason::NonPrimary)
-                ) {
-                    status
-                        .message
-                        .unwrap_or_else(|| err.to_user_message().to_string())
-                } else {
-                    err.to_user_message().to_string()
-                },
-            });
-            return Err(err);
+                None => CreateRuleShortcutError::NoTarget,
+            };
+            self.ui.app_edit_state.shortcut_result = Some(RuleShortcutResult::Failed {
+                message: if matches!(
+                    status.disabled_reason,
+                    Some(RuleShortcutDisabledReason::NonPrimary)
+                ) {
+                    status
+                        .message
+                        .unwrap_or_else(|| err.to_user_message().to_string())
+                } else {
+                    err.to_user_message().to_string()
+                },
+            });
+            return Err(err);
+        }
+
+        let Some(target) = self.ui.app_edit_state.target.clone() else {
+            return Err(CreateRuleShortcutError::NoTarget);
+        };
+        let result =
+            self.create_shortcut_for_rule_with_platform(target.group_id, target.rule_id, platform);
+        self.ui.app_edit_state.shortcut_result = Some(match &result {
+            Ok(path) => RuleShortcutResult::Created {
+                filename: path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string()),
+            },
+            Err(err) => RuleShortcutResult::Failed {
+                message: err.to_user_message().to_string(),
+            },
+        });
+
+        result
+    }
+
+    /// Runs the group's apps like [`Self::run_group`], unless that would start more apps
+    /// than the confirmation threshold; then the run waits in `pending_run_group`.
+    pub fn request_run_group(&mut self, group_id: GroupId, force: bool) {
+        let threshold = self.run_all_confirm_threshold();
+        if threshold > 0 {
+            if let Some(group_index) = self.group_index_for_id(&group_id) {
+                let app_count = self
+                    .get_group_programs(group_index)
+                    .unwrap_or_default()
+                    .iter()
+                    .filter(|program| force || !self.runtime.contains_app(&program.get_key()))
+                    .count();
+                if app_count > threshold {
+                    self.ui.pending_run_group = Some(PendingRunGroup {
+                        group_id,
+                        group_name: self.get_group_name(group_index).unwrap_or_default(),
+                        force,
+                        app_count,
+                        dont_ask_again: false,
+                    });
+                    return;
+                }
+            }
+        }
+        self.run_group(group_id, force);
+    }
+
+    /// Runs the group awaiting confirmation, if any.
+    pub fn confirm_pending_run_group(&mut self) {
+        let Some(pending) = self.ui.pending_run_group.take() else {
+            return;
+        };
+        if pending.dont_ask_again {
+            self.set_run_all_confirm_threshold(0);
+        }
+        self.run_group(pending.group_id, pending.force);
+    }
+
+    /// Runs the group's apps. Apps that are already running only get their settings
+    /// reapplied unless `force` is set, in which case every app goes through the
+    /// regular launch path. When an app has a launch barrier the launches are queued
+    /// so each one waits for the app before it to be ready.
+    pub fn run_group(&mut self, group_id: GroupId, force: bool) {
+        let Some(group_index) = self.group_index_for_id(&group_id) else {
+            return;
+        };
+
+        let Some(programs) = self.get_group_programs(group_index) else {
+            return;
+        };
+
+        if !self.ensure_group_enabled(group_index) {
+            return;
+        }
+
+        if programs.is_empty() {
+            let group_name = self.get_group_name(group_index).unwrap_or_default();
+            self.log_manager
+                .add_entry(format!("No app targets to run in group: {group_name}"));
+            return;
+        }
+
+        let wait_for_barriers = programs
+            .iter()
+            .any(|program| program.launch_barrier.is_some());
+        let mut queued = Vec::new();
+        for (program_index, program) in programs.into_iter().enumerate() {
+            if !force && self.runtime.contains_app(&program.get_key()) {
+                self.log_manager.add_entry(format!(
+                    "Run all: {} is already running, reapplying settings",
+                    program.display()
+                ));
+                execution::run_app_row_action(
+                    &self.persistent_state,
+                    &mut self.runtime,
+                    &mut self.log_manager,
+                    execution::AppRowActionRequest {
+                        group_index,
+                        program_index,
+                        app: program,
+                        action: execution::AppRowAction::Fix,
+                    },
+                );
+                continue;
+            }
+
+            if wait_for_barriers {
+                self.log_manager
+                    .add_entry(format!("Run all: queued {}", program.display()));
+                queued.push((program_index, program));
+            } else {
+                self.log_manager
+                    .add_entry(format!("Run all: launching {}", program.display()));
+                self.run_app_with_affinity_sync(group_index, program_index, program);
+            }
+        }
+        if !queued.is_empty() {
+            execution::queue_group_launches(&mut self.runtime, group_index, queued);
+            self.poll_autorun();
+        }
+    }
+
+    /// Applies the group's settings to its apps' already running processes and starts
+    /// tracking them; returns how many processes were adjusted.
+    pub fn apply_group_to_running(&mut self, group_id: GroupId) -> usize {
+        let Some(group_index) = self.group_index_for_id(&group_id) else {
+            return 0;
+        };
+        execution::apply_group_to_running(
+            &self.persistent_state,
+            &self.runtime,
+            &mut self.log_manager,
+            group_index,
+        )
+    }
+
+    /// Runs every app in the group with the given name and reports the first
+    /// failure, so scripted launches can turn the result into an exit code.
+    pub fn run_group_by_name(&mut self, group_name: &str) -> RunRuleOutcome {
+        let Some(group_index) = self.group_index_for_name(group_name) else {
+            return RunRuleOutcome::MissingGroup;
+        };
+        let programs = self.get_group_programs(group_index).unwrap_or_default();
+        if programs.is_empty() {
+            self.log_manager
+                .add_entry(format!("No app targets to run in group: {group_name}"));
+            return RunRuleOutcome::MissingRule;
+        }
+        if !self.ensure_group_enabled(group_index) {
+            return RunRuleOutcome::LaunchRejected(format!("Group '{group_name}' is disabled"));
+        }
+
+        let mut rejections = Vec::new();
+        for (program_index, program) in programs.into_iter().enumerate() {
+            if let execution::LaunchDispatchOutcome::Rejected(message) =
+                self.run_app_with_affinity_sync(group_index, program_index, program)
+            {
+                rejections.push(message);
+            }
+        }
+
+        if rejections.is_empty() {
+            RunRuleOutcome::Accepted
+        } else {
+            RunRuleOutcome::LaunchRejected(rejections.join("; "))
+        }
+    }
+
+    pub fn run_group_program_by_name(
+        &mut self,
+        group_name: &str,
+        app_name: &str,
+    ) -> RunRuleOutcome {
+        let Some(group_index) = self.group_index_for_name(group_name) else {
+            return RunRuleOutcome::MissingGroup;
+        };
+        let programs = self.get_group_programs(group_index).unwrap_or_default();
+        let Some(program_index) =
+            find_by_name(&programs, app_name, |program| program.name.as_str())
+        else {
+            return RunRuleOutcome::MissingRule;
+        };
+        let (Some(group_id), Some(rule_id)) = (
+            self.rules.group_id_for_index(group_index),
+            self.rules.rule_id_for_index(group_index, program_index),
+        ) else {
+            return RunRuleOutcome::MissingRule;
+        };
+
+        self.run_group_program(group_id, rule_id)
+    }
+
+    /// The first matching group; shared names are reported since the choice is
+    /// then only decided by the group order.
+    fn group_index_for_name(&mut self, group_name: &str) -> Option<usize> {
+        self.reconcile_rules();
+        let state = self.persistent_state.read().ok()?;
+        let index = find_by_name(&state.groups, group_name, |group| group.name.as_str())?;
+        let same_name = state
+            .groups
+            .iter()
+            .filter(|group| group.name.eq_ignore_ascii_case(group_name))
+            .count();
+        if same_name > 1 {
+            self.log_manager.add_important_entry(format!(
+                "{same_name} groups are named \"{group_name}\"; using group {}. Rename the others to target them by name",
+                index + 1
+            ));
+        }
+        Some(index)
+    }
+
+    pub fn get_app_status_sync(&mut self, app_key: &AppRuntimeKey) -> AppStatus {
+        self.runtime.get_app_status_sync(app_key)
+    }
+
+    pub fn get_running_app_pids(&self, app_key: &AppRuntimeKey) -> Option<Vec<u32>> {
+        self.runtime.get_running_app_pids(app_key)
+    }
+
+    pub fn get_running_app_started_at(&self, app_key: &AppRuntimeKey) -> Option<SystemTime> {
+        self.runtime.get_running_app_started_at(app_key)
+    }
+
+    /// Per-thread load last sampled by the running-app monitor, 0.0 to 1.0.
+    pub fn core_usage(&self) -> Option<Vec<f32>> {
+        self.runtime.core_usage()
+    }
+
+    pub fn get_launch_failure(&self, app_key: &AppRuntimeKey) -> Option<String> {
+        self.runtime.launch_failure(app_key)
+    }
+
+    pub fn get_affinity_drift(&self, app_key: &AppRuntimeKey) -> Option<AffinityDrift> {
+        self.runtime.affinity_drift(app_key)
+    }
+
+    pub fn is_runtime_reset_on_restart(&self) -> bool {
+        self.persistent_state
+            .read()
+            .unwrap()
+            .reset_runtime_on_restart
+    }
+
+    pub fn toggle_runtime_reset_on_restart(&mut self) {
+        preferences::toggle_runtime_reset_on_restart(&self.persistent_state);
+        let _ = self.persist_state();
+    }
+
+    pub fn is_taskbar_hidden_when_tray(&self) -> bool {
+        self.persistent_state.read().unwrap().hide_taskbar_when_tray
+    }
+
+    pub fn toggle_hide_taskbar_when_tray(&mut self) {
+        preferences::toggle_hide_taskbar_when_tray(&self.persistent_state);
+        let _ = self.persist_state();
+    }
+
+    pub fn is_separate_apps_per_group(&self) -> bool {
+        self.persistent_state
+            .read()
+            .unwrap()
+            .separate_apps_per_group
+    }
+
+    pub fn toggle_separate_apps_per_group(&mut self) {
+        preferences::toggle_separate_apps_per_group(&self.persistent_state);
+        if self.is_separate_apps_per_group() {
+            self.separate_shared_apps_if_enabled();
+        } else {
+            self.merge_separated_apps();
+        }
+        let _ = self.persist_state();
+    }
+
+    /// Names of the detected CPU clusters, in schema order.
+    pub fn cluster_names(&self) -> Vec<String> {
+        self.persistent_state
+            .read()
+            .unwrap()
+            .cpu_schema
+            .clusters
+            .iter()
+            .map(|cluster| cluster.name.clone())
+            .collect()
+    }
+
+    pub fn is_click_to_launch(&self) -> bool {
+        self.persistent_state.read().unwrap().click_to_launch
+    }
+
+    pub fn toggle_click_to_launch(&mut self) {
+        preferences::toggle_click_to_launch(&self.persistent_state);
+        let _ = self.persist_state();
+    }
+
+    pub fn is_always_on_top(&self) -> bool {
+        self.persistent_state.read().unwrap().always_on_top
+    }
+
+    pub fn toggle_always_on_top(&mut self) {
+        preferences::toggle_always_on_top(&self.persistent_state);
+        let _ = self.persist_state();
+    }
+
+    pub fn are_tips_hidden(&self) -> bool {
+        self.persistent_state.read().unwrap().hide_tips
+    }
+
+    pub fn toggle_hide_tips(&mut self) {
+        preferences::toggle_hide_tips(&self.persistent_state);
+        let _ = self.persist_state();
+    }
+
+    pub fn is_affinity_audit_enabled(&self) -> bool {
+        self.persistent_state.read().unwrap().affinity_audit
+    }
+
+    pub fn toggle_affinity_audit(&mut self) {
+        preferences::toggle_affinity_audit(&self.persistent_state);
+        let _ = self.persist_state();
+        self.sync_affinity_audit();
+    }
+
+    /// Stops the background monitors and writes out the audit rows still buffered,
+    /// so nothing is cut off mid-write when the process exits.
+    pub fn shutdown_background_tasks(&mut self) {
+        self.runtime.stop_monitors();
+        audit::flush();
+    }
+
+    /// Opens or closes the audit file to match the setting and writes out buffered
+    /// rows; the setting is switched back off when the file can't be opened.
+    pub fn sync_affinity_audit(&mut self) {
+        let wanted = self.is_affinity_audit_enabled();
+        if wanted != audit::is_enabled() {
+            match audit::set_enabled(wanted) {
+                Ok(()) if wanted => self.log_manager.add_entry(format!(
+                    "Recording affinity changes to {}",
+                    audit::audit_path().display()
+                )),
+                Ok(()) => self
+                    .log_manager
+                    .add_entry("Stopped recording affinity changes".to_string()),
+                Err(err) => {
+                    self.log_manager
+                        .add_important_entry(format!("ERROR: Affinity audit is off: {err}"));
+                    preferences::toggle_affinity_audit(&self.persistent_state);
+                    let _ = self.persist_state();
+                }
+            }
         }
+        audit::flush();
+    }
 
-        let Some(target) = self.ui.app_edit_state.target.clone() else {
-            return Err(CreateRuleShortcutError::NoTarget);
-        };
-        let result =
-            self.create_shortcut_for_rule_with_platform(target.group_id, target.rule_id, platform);
-        self.ui.app_edit_state.shortcut_result = Some(match &result {
-            Ok(path) => RuleShortcutResult::Created {
-                filename: path
-                    .file_name()
-                    .map(|name| name.to_string_lossy().to_string())
-                    .unwrap_or_else(|| path.display().to_string()),
-            },
-            Err(err) => RuleShortcutResult::Failed {
-                message: err.to_user_message().to_string(),
-            },
-        });
+    pub fn launch_confirm_secs(&self) -> u32 {
+        self.persistent_state.read().unwrap().launch_confirm_secs
+    }
 
-        result
+    pub fn set_launch_confirm_secs(&mut self, secs: u32) {
+        preferences::set_launch_confirm_secs(&self.persistent_state, secs);
+        let _ = self.persist_state();
     }
 
-    pub fn run_group(&mut self, group_id: GroupId) {
-        let Some(group_index) = self.group_index_for_id(&group_id) else {
+    pub fn is_launch_confirm_window(&self) -> bool {
+        self.persistent_state.read().unwrap().launch_confirm_window
+    }
+
+    pub fn toggle_launch_confirm_window(&mut self) {
+        preferences::toggle_launch_confirm_window(&self.persistent_state);
+        let _ = self.persist_state();
+    }
+
+    pub fn run_all_confirm_threshold(&self) -> usize {
+        self.persistent_state
+            .read()
+            .unwrap()
+            .run_all_confirm_threshold
+    }
+
+    pub fn set_run_all_confirm_threshold(&mut self, threshold: usize) {
+        preferences::set_run_all_confirm_threshold(&self.persistent_state, threshold);
+        let _ = self.persist_state();
+    }
+
+    pub fn self_affinity(&self) -> SelfAffinity {
+        self.persistent_state.read().unwrap().self_affinity
+    }
+
+    pub fn set_self_affinity(&mut self, value: SelfAffinity) {
+        preferences::set_self_affinity(&self.persistent_state, value);
+        let _ = self.persist_state();
+        self.sync_self_affinity();
+    }
+
+    /// Pins the tool's own process to the threads its setting asks for, whenever
+    /// that mask changes, e.g. after a group edit under "outside groups".
+    pub fn sync_self_affinity(&mut self) {
+        let available = *self
+            .self_affinity_base
+            .get_or_insert_with(os::current_process_affinity);
+        self.sync_self_affinity_with(available, os::set_current_process_affinity);
+    }
+
+    fn sync_self_affinity_with(
+        &mut self,
+        available: usize,
+        set_affinity: impl FnOnce(usize) -> Result<(), String>,
+    ) {
+        let (setting, wanted) = {
+            let state = self.persistent_state.read().unwrap();
+            (state.self_affinity, state.self_affinity_mask(available))
+        };
+        if self.applied_self_affinity == Some(wanted) {
             return;
+        }
+        let previous = self.applied_self_affinity.replace(wanted).flatten();
+        let message = match (wanted, previous) {
+            (Some(mask), _) => match set_affinity(mask) {
+                Ok(()) => format!("Pinned the tool to affinity mask {mask:#X}"),
+                Err(err) => format!("ERROR: Failed to pin the tool to its threads: {err}"),
+            },
+            (None, Some(_)) => match set_affinity(available) {
+                Ok(()) if setting == SelfAffinity::Off => {
+                    "The tool runs on all threads again".to_string()
+                }
+                Ok(()) => NO_SELF_AFFINITY_THREADS_WARNING.to_string(),
+                Err(err) => format!("ERROR: Failed to unpin the tool: {err}"),
+            },
+            (None, None) if setting != SelfAffinity::Off => {
+                NO_SELF_AFFINITY_THREADS_WARNING.to_string()
+            }
+            (None, None) => return,
         };
+        self.log_manager.add_entry(message);
+    }
 
-        let Some(programs) = self.get_group_programs(group_index) else {
-            return;
+    /// With per-group separation on, re-keys apps shared with an earlier group and
+    /// carries a running instance launched from that group over to the new key.
+    fn separate_shared_apps_if_enabled(&mut self) {
+        let separated = match self.persistent_state.write() {
+            Ok(mut state) if state.separate_apps_per_group => {
+                rules::separate_shared_apps(&mut state)
+            }
+            _ => return,
         };
+        self.rekey_running_apps(&separated);
+        if !separated.is_empty() {
+            self.log_manager.add_entry(format!(
+                "Tracking {} app(s) shared with another group separately",
+                separated.len()
+            ));
+        }
+    }
 
-        if programs.is_empty() {
-            let group_name = self.get_group_name(group_index).unwrap_or_default();
-            self.log_manager
-                .add_entry(format!("No app targets to run in group: {group_name}"));
-            return;
+    /// With per-group separation turned off, puts separated apps back on their shared
+    /// key and carries running instances over to it.
+    fn merge_separated_apps(&mut self) {
+        let merged = match self.persistent_state.write() {
+            Ok(mut state) => rules::merge_separated_apps(&mut state),
+            Err(_) => return,
+        };
+        self.rekey_running_apps(&merged);
+        if !merged.is_empty() {
+            self.log_manager.add_entry(format!(
+                "Tracking {} app(s) shared with another group together again",
+                merged.len()
+            ));
         }
+    }
 
-        for (program_index, program) in programs.into_iter().enumerate() {
-            self.run_app_with_affinity_sync(group_index, program_index, program);
+    fn rekey_running_apps(&mut self, rekeyed: &[rules::SeparatedApp]) {
+        for app in rekeyed {
+            if let Some(group_id) = self.rules.group_id_for_index(app.group_index) {
+                self.runtime
+                    .rekey_running_app(&app.old_key, &app.new_key, &group_id);
+            }
         }
     }
 
-    pub fn get_app_status_sync(&mut self, app_key: &AppRuntimeKey) -> AppStatus {
-        self.runtime.get_app_status_sync(app_key)
+    pub fn is_group_overlap_warning_hidden(&self) -> bool {
+        self.persistent_state
+            .read()
+            .unwrap()
+            .hide_group_overlap_warning
     }
 
-    pub fn get_running_app_pids(&self, app_key: &AppRuntimeKey) -> Option<Vec<u32>> {
-        self.runtime.get_running_app_pids(app_key)
+    pub fn toggle_group_overlap_warning(&mut self) {
+        preferences::toggle_group_overlap_warning(&self.persistent_state);
+        let _ = self.persist_state();
     }
 
     pub fn open_installed_app_picker(&mut self, group_id: GroupId) {
@@ -1219,6 +2811,8 @@ impl AppState {
         self.ui.app_edit_state.target =
             Some(crate::app::shell::sessions::RuleEditorTarget { group_id, rule_id });
         self.ui.app_edit_state.shortcut_result = None;
+        self.ui.app_edit_state.resolved_args = None;
+        self.ui.app_edit_state.confirm_realtime = false;
         self.ui.current_window = WindowRoute::AppRunSettings;
     }
 
@@ -1266,8 +2860,16 @@ impl AppState {
                 {
                     updated_app.sync_primary_process_name_after_path_edit(&original);
                 }
+                if let Some(err) = updated_app.match_pattern_error() {
+                    self.log_manager.add_entry(format!(
+                        "Invalid process name pattern for {}: {}; matching it as a literal name",
+                        updated_app.display(),
+                        err.lines().last().unwrap_or_default().trim()
+                    ));
+                }
 
                 if rules::update_rule(&self.persistent_state, group_idx, prog_idx, updated_app) {
+                    self.separate_shared_apps_if_enabled();
                     let _ = self.persist_state();
                 }
             }
@@ -1307,6 +2909,51 @@ impl AppState {
         StorageAdapter::active_storage_mode()
     }
 
+    /// Reads the config at `path` and holds it, with what it would change, until the
+    /// user confirms or cancels the import.
+    pub fn request_config_import(&mut self, path: PathBuf) {
+        match config_import::read_config_file(&path) {
+            Ok(config) => self.stage_config_import(path, config),
+            Err(err) => self
+                .log_manager
+                .add_important_entry(format!("ERROR: Config not imported: {err}")),
+        }
+    }
+
+    fn stage_config_import(&mut self, path: PathBuf, mut config: AppStateStorage) {
+        let current = self.persistent_state.read().unwrap();
+        config_import::keep_machine_local(&current, &mut config);
+        let diff = config_import::diff(&current, &config);
+        drop(current);
+        self.ui.pending_config_import = Some(PendingConfigImport { path, config, diff });
+    }
+
+    /// Replaces the current config with the one awaiting confirmation and saves it.
+    pub fn confirm_config_import(&mut self) -> bool {
+        let Some(pending) = self.ui.pending_config_import.take() else {
+            return false;
+        };
+        match self.persistent_state.write() {
+            Ok(mut state) => {
+                *state = pending.config;
+                self.rules = RulesContext::from_storage(&state);
+            }
+            Err(_) => {
+                self.log_manager.add_sticky_once(
+                    "WARNING: persistent_state lock poisoned during import".into(),
+                );
+                return false;
+            }
+        }
+        // Selections and open editors point at the replaced groups.
+        self.clear_rule_selection();
+        self.ui.reset_group_form();
+        self.close_app_run_settings();
+        self.log_manager
+            .add_important_entry(format!("Imported config from {}", pending.path.display()));
+        self.persist_state()
+    }
+
     pub fn open_active_data_dir(&mut self) {
         let data_dir = self.active_data_dir();
         if let Err(err) = crate::app::adapters::os::open_directory(&data_dir) {
@@ -1317,6 +2964,69 @@ impl AppState {
         }
     }
 
+    /// Shows a program's executable in the file manager. Shortcuts and URL files were
+    /// resolved when added, so this is the real target rather than the dropped file.
+    pub fn reveal_program_location(&mut self, bin_path: &Path) {
+        if let Err(err) = crate::app::adapters::os::reveal_in_explorer(bin_path) {
+            self.log_manager.add_important_entry(format!(
+                "ERROR: Failed to open the location of '{}': {err}",
+                bin_path.display()
+            ));
+        }
+    }
+
+    /// Points the draft at the current target of the file it was added from. Changed
+    /// shortcut arguments are only offered, so manual edits survive until accepted.
+    pub fn reresolve_current_edit_target(&mut self) {
+        self.reresolve_current_edit_target_with(
+            crate::app::adapters::discovery::resolve_dropped_file,
+        );
+    }
+
+    fn reresolve_current_edit_target_with(
+        &mut self,
+        resolve: impl FnOnce(&Path) -> Result<(PathBuf, Vec<String>), String>,
+    ) {
+        let Some(app) = self.ui.app_edit_state.current_edit.as_mut() else {
+            return;
+        };
+        let Some(dropped_path) = app.dropped_path().map(Path::to_path_buf) else {
+            return;
+        };
+
+        match resolve(&dropped_path) {
+            Ok((bin_path, args)) => {
+                if let Some(current) = app.bin_path_mut() {
+                    *current = bin_path.clone();
+                }
+                self.ui.app_edit_state.resolved_args = (args != app.args).then_some(args);
+                self.log_manager.add_entry(format!(
+                    "Re-resolved '{}' to '{}'",
+                    dropped_path.display(),
+                    bin_path.display()
+                ));
+            }
+            Err(err) => self.log_manager.add_important_entry(format!(
+                "ERROR: Failed to re-resolve '{}': {err}",
+                dropped_path.display()
+            )),
+        }
+    }
+
+    /// Replaces the draft's arguments with the pending re-resolved ones.
+    pub fn accept_resolved_args(&mut self) {
+        if let (Some(args), Some(app)) = (
+            self.ui.app_edit_state.resolved_args.take(),
+            self.ui.app_edit_state.current_edit.as_mut(),
+        ) {
+            app.args = args;
+        }
+    }
+
+    pub fn discard_resolved_args(&mut self) {
+        self.ui.app_edit_state.resolved_args = None;
+    }
+
     fn filtered_installed_app_entry_indices(&self) -> Vec<usize> {
         let query = self.ui.installed_app_picker.query.trim().to_lowercase();
         let mut matches: Vec<(usize, (usize, usize, String, String))> = self
@@ -1400,6 +3110,8 @@ impl AppState {
         self.ui.app_edit_state.current_edit = None;
         self.ui.app_edit_state.target = None;
         self.ui.app_edit_state.shortcut_result = None;
+        self.ui.app_edit_state.resolved_args = None;
+        self.ui.app_edit_state.confirm_realtime = false;
     }
 }
 
@@ -1407,7 +3119,8 @@ impl AppState {
 mod tests {
     #[cfg(all(target_os = "windows", feature = "windows"))]
     use super::RuleShortcutDisabledReason;
-    use super::{AppState, MoveRuleToGroupOutcome, RunRuleOutcome};
+    use super::{AppState, MoveRuleToGroupOutcome, RunRuleOutcome, FOLDER_ADD_CONFIRM_THRESHOLD};
+    use crate::app::features::diagnostics::cpu_topology::CpuTopologyReport;
     use crate::app::features::execution::RuntimeRegistry;
     use crate::app::features::rules::RulesContext;
     #[cfg(all(target_os = "windows", feature = "windows"))]
@@ -1415,21 +3128,22 @@ mod tests {
         CreateRuleShortcutError, RuleShortcutPlatform, ShortcutWriteError,
     };
     use crate::app::models::{
-        AppStateStorage, AppToRun, CoreGroup, CpuSchema, LaunchTarget, LogManager,
+        AppStateStorage, AppToRun, CoreGroup, CoreSelector, CpuSchema, LaunchTarget, LogManager,
+        SelfAffinity,
     };
     use crate::app::shared::ids::{GroupId, RuleId};
-    use crate::app::shell::sessions::{RuleEditorTarget, RuleShortcutResult, ShortcutCreationRole};
+    use crate::app::shell::sessions::{
+        RuleEditorTarget, RuleSelectMode, RuleShortcutResult, ShortcutCreationRole,
+    };
     use crate::app::shell::UiSession;
     use crate::app::shell::{GroupRoute, WindowRoute};
     use os_api::PriorityClass;
     #[cfg(all(target_os = "windows", feature = "windows"))]
     use os_api::ShortcutSpec;
-    use os_api::{InstalledAppCatalogEntry, InstalledAppCatalogSource};
+    use os_api::{InstalledAppCatalogEntry, InstalledAppCatalogSource, PriorityClassName};
     #[cfg(all(target_os = "windows", feature = "windows"))]
     use std::collections::HashSet;
-    #[cfg(all(target_os = "windows", feature = "windows"))]
-    use std::path::Path;
-    use std::path::PathBuf;
+    use std::path::{Path, PathBuf};
     use std::sync::{Arc, RwLock};
 
     #[cfg(all(target_os = "windows", feature = "windows"))]
@@ -1496,7 +3210,16 @@ mod tests {
                     false,
                 )],
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: true,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             }],
             cpu_schema: CpuSchema {
                 model: "Test CPU".to_string(),
@@ -1504,7 +3227,23 @@ mod tests {
             },
             theme_index: 0,
             process_monitoring_enabled: false,
+            reset_runtime_on_restart: false,
+            hide_group_overlap_warning: false,
+            hide_taskbar_when_tray: true,
+            separate_apps_per_group: false,
+            launch_confirm_secs: 0,
+            launch_confirm_window: false,
             rule_identities: None,
+            profiles: Vec::new(),
+            active_profile: None,
+            reserved_cores: Vec::new(),
+            self_affinity: SelfAffinity::Off,
+            affinity_audit: false,
+            click_to_launch: false,
+            always_on_top: false,
+            hide_tips: false,
+            run_all_confirm_threshold: 5,
+            favorites: Vec::new(),
             loaded_version: 5,
             pending_pre_v6_backup: false,
         }));
@@ -1521,6 +3260,8 @@ mod tests {
             runtime: RuntimeRegistry::new(),
             log_manager: LogManager::default(),
             shortcut_creation_role: ShortcutCreationRole::Primary,
+            self_affinity_base: None,
+            applied_self_affinity: None,
             save_count: 0,
         }
     }
@@ -1545,7 +3286,16 @@ mod tests {
                 cores: vec![2, 3],
                 programs: Vec::new(),
                 is_hidden: false,
+                is_collapsed: false,
                 run_all_button: false,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             });
         app.reconcile_rules();
     }
@@ -1812,20 +3562,276 @@ mod tests {
     }
 
     #[test]
-    fn test_run_group_program_reports_launch_rejection() {
+    fn test_run_group_skips_running_apps_unless_forced() {
+        let mut app = sample_state();
+        let group_id = group_id(&app, 0);
+        let program = app.get_group_program(0, 0).unwrap();
+        assert!(app.runtime.add_running_app(
+            &program.get_key(),
+            12345,
+            group_id.clone(),
+            rule_id(&app, 0, 0)
+        ));
+        let already_running = format!(
+            "Run all: {} is already running, reapplying settings",
+            program.display()
+        );
+        let launching = format!("Run all: launching {}", program.display());
+        let logged = |app: &AppState, message: &str| {
+            app.log_manager
+                .entries
+                .iter()
+                .any(|entry| entry.message == message)
+        };
+
+        app.run_group(group_id.clone(), false);
+        assert!(logged(&app, &already_running));
+        assert!(!logged(&app, &launching));
+
+        app.run_group(group_id, true);
+        assert!(logged(&app, &launching));
+    }
+
+    #[test]
+    fn test_run_group_asks_before_starting_more_apps_than_threshold() {
+        let mut app = sample_state();
+        let group_id = group_id(&app, 0);
+        app.persistent_state.write().unwrap().groups[0]
+            .programs
+            .push(sample_app("Other"));
+        app.reconcile_rules();
+        app.set_run_all_confirm_threshold(1);
+        let launching = |app: &AppState| {
+            app.log_manager
+                .entries
+                .iter()
+                .any(|entry| entry.message.starts_with("Run all: launching"))
+        };
+
+        app.request_run_group(group_id.clone(), false);
+        assert_eq!(app.ui.pending_run_group.as_ref().unwrap().app_count, 2);
+        assert!(!launching(&app));
+        app.ui.pending_run_group = None;
+
+        let program = app.get_group_program(0, 0).unwrap();
+        assert!(app.runtime.add_running_app(
+            &program.get_key(),
+            12345,
+            group_id.clone(),
+            rule_id(&app, 0, 0)
+        ));
+        app.request_run_group(group_id.clone(), false);
+        assert!(app.ui.pending_run_group.is_none());
+        assert!(launching(&app));
+
+        app.request_run_group(group_id, true);
+        let pending = app.ui.pending_run_group.as_mut().unwrap();
+        assert_eq!(pending.app_count, 2);
+        pending.dont_ask_again = true;
+        app.confirm_pending_run_group();
+        assert!(app.ui.pending_run_group.is_none());
+        assert_eq!(app.run_all_confirm_threshold(), 0);
+    }
+
+    #[test]
+    fn test_config_import_replaces_state_only_after_confirmation() {
+        let mut app = sample_state();
+        let mut imported = app.persistent_state.read().unwrap().clone();
+        imported.groups[0].name = "Streaming".to_string();
+        imported.theme_index = 2;
+        imported.affinity_audit = true;
+        imported.self_affinity = SelfAffinity::Core(0);
+        imported.cpu_schema.model = "Other CPU".to_string();
+        let selected = rule_id(&app, 0, 0);
+        app.select_rule(selected, RuleSelectMode::Only);
+
+        app.stage_config_import(PathBuf::from("shared.json"), imported.clone());
+        let diff = &app.ui.pending_config_import.as_ref().unwrap().diff;
+        assert_eq!(diff.groups_added, vec!["Streaming".to_string()]);
+        assert_eq!(diff.groups_removed, vec!["Games".to_string()]);
+        assert_eq!(diff.theme_change, Some((0, 2)));
+        assert_eq!(app.get_group_name(0).as_deref(), Some("Games"));
+        assert_eq!(app.save_count(), 0);
+
+        app.ui.pending_config_import = None;
+        assert!(!app.confirm_config_import());
+        assert_eq!(app.get_group_name(0).as_deref(), Some("Games"));
+
+        app.stage_config_import(PathBuf::from("shared.json"), imported);
+        assert!(app.confirm_config_import());
+        assert!(app.ui.pending_config_import.is_none());
+        assert_eq!(app.get_group_name(0).as_deref(), Some("Streaming"));
+        assert_eq!(app.get_theme_index(), 2);
+        {
+            let state = app.persistent_state.read().unwrap();
+            assert!(!state.affinity_audit);
+            assert_eq!(state.self_affinity, SelfAffinity::Off);
+            assert_ne!(state.cpu_schema.model, "Other CPU");
+        }
+        assert!(app.ui.selected_rules.is_empty());
+        assert_eq!(app.save_count(), 1);
+        assert!(app
+            .log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == "Imported config from shared.json"));
+    }
+
+    #[test]
+    fn test_selected_rules_take_bulk_priority_move_and_remove() {
+        let mut app = sample_state();
+        {
+            let mut state = app.persistent_state.write().unwrap();
+            state.groups[0].programs.push(sample_app("Second"));
+            state.groups[0].programs.push(sample_app("Third"));
+        }
+        app.reconcile_rules();
+        add_empty_group(&mut app, "Background");
+        let first_name = app.get_group_program(0, 0).unwrap().name;
+        let (first, second, third) = (
+            rule_id(&app, 0, 0),
+            rule_id(&app, 0, 1),
+            rule_id(&app, 0, 2),
+        );
+        let names = |app: &AppState, group_index: usize| -> Vec<String> {
+            app.get_group_programs(group_index)
+                .unwrap()
+                .into_iter()
+                .map(|program| program.name)
+                .collect()
+        };
+
+        app.select_rule(third.clone(), RuleSelectMode::Only);
+        app.select_rule(first.clone(), RuleSelectMode::Range);
+        assert_eq!(
+            app.ui.selected_rules,
+            vec![first.clone(), second.clone(), third.clone()]
+        );
+        app.select_rule(second, RuleSelectMode::Toggle);
+        assert_eq!(app.ui.selected_rules, vec![first, third]);
+
+        assert_eq!(app.set_selected_rules_priority(PriorityClassName::High), 2);
+        let priorities: Vec<PriorityClass> = app
+            .get_group_programs(0)
+            .unwrap()
+            .iter()
+            .map(|program| program.priority)
+            .collect();
+        assert_eq!(
+            priorities,
+            vec![
+                PriorityClass::High,
+                PriorityClass::Normal,
+                PriorityClass::High
+            ]
+        );
+
+        assert_eq!(app.move_selected_rules_to_group(group_id(&app, 1)), 2);
+        assert_eq!(names(&app, 0), vec!["Second".to_string()]);
+        assert_eq!(names(&app, 1), vec![first_name, "Third".to_string()]);
+
+        assert_eq!(app.remove_selected_rules(), 2);
+        assert!(names(&app, 1).is_empty());
+        assert!(app.ui.selected_rules.is_empty());
+    }
+
+    #[test]
+    fn test_group_priority_applies_to_every_app_after_confirmation() {
+        let mut app = sample_state();
+        let mut eco_app = sample_app("Second");
+        eco_app.priority = PriorityClass::from_parts(PriorityClassName::Normal, true);
+        app.persistent_state.write().unwrap().groups[0]
+            .programs
+            .push(eco_app);
+        app.reconcile_rules();
+        let games = group_id(&app, 0);
+        let priorities = |app: &AppState| -> Vec<PriorityClass> {
+            app.get_group_programs(0)
+                .unwrap()
+                .iter()
+                .map(|program| program.priority)
+                .collect()
+        };
+        let before = priorities(&app);
+
+        app.request_group_priority(games.clone(), PriorityClassName::AboveNormal);
+        let pending = app.ui.pending_group_priority.as_ref().unwrap();
+        assert_eq!(
+            (pending.group_name.as_str(), pending.app_count),
+            ("Games", 2)
+        );
+        assert_eq!(priorities(&app), before);
+        assert_eq!(app.save_count(), 0);
+
+        assert_eq!(app.confirm_pending_group_priority(), 2);
+        assert!(app.ui.pending_group_priority.is_none());
+        assert_eq!(
+            priorities(&app),
+            vec![
+                PriorityClass::AboveNormal,
+                PriorityClass::from_parts(PriorityClassName::AboveNormal, true)
+            ]
+        );
+        assert_eq!(app.save_count(), 1);
+        assert_eq!(app.confirm_pending_group_priority(), 0);
+
+        add_empty_group(&mut app, "Empty");
+        app.request_group_priority(group_id(&app, 1), PriorityClassName::High);
+        assert!(app.ui.pending_group_priority.is_none());
+    }
+
+    #[test]
+    fn test_run_group_program_reports_launch_rejection() {
+        let mut app = sample_state();
+        let existing_group_id = group_id(&app, 0);
+        let existing_rule_id = rule_id(&app, 0, 0);
+
+        let outcome = app.run_group_program(existing_group_id, existing_rule_id);
+
+        assert!(matches!(
+            outcome,
+            RunRuleOutcome::LaunchRejected(message)
+                if message.contains("Failed to start process")
+                    || message.contains("No such file")
+                    || message.contains("The system cannot find")
+        ));
+    }
+
+    #[test]
+    fn test_run_by_name_reports_missing_targets_case_insensitively() {
+        let mut app = sample_state();
+        add_empty_group(&mut app, "Background");
+
+        assert_eq!(
+            app.run_group_by_name("Missing"),
+            RunRuleOutcome::MissingGroup
+        );
+        assert_eq!(
+            app.run_group_by_name("background"),
+            RunRuleOutcome::MissingRule
+        );
+        assert_eq!(
+            app.run_group_program_by_name("games", "Missing"),
+            RunRuleOutcome::MissingRule
+        );
+    }
+
+    #[test]
+    fn test_run_group_program_by_name_resolves_saved_rule() {
         let mut app = sample_state();
-        let existing_group_id = group_id(&app, 0);
-        let existing_rule_id = rule_id(&app, 0, 0);
-
-        let outcome = app.run_group_program(existing_group_id, existing_rule_id);
+        let program = app.get_group_program(0, 0).unwrap();
 
-        assert!(matches!(
-            outcome,
-            RunRuleOutcome::LaunchRejected(message)
-                if message.contains("Failed to start process")
-                    || message.contains("No such file")
-                    || message.contains("The system cannot find")
+        assert!(app.runtime.add_running_app(
+            &program.get_key(),
+            12345,
+            group_id(&app, 0),
+            rule_id(&app, 0, 0)
         ));
+
+        assert_eq!(
+            app.run_group_program_by_name("GAMES", &program.name.to_uppercase()),
+            RunRuleOutcome::Accepted
+        );
     }
 
     #[test]
@@ -1855,6 +3861,46 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_commit_group_form_session_saves_threads_to_active_profile_only() {
+        let mut app = sample_state();
+        let existing_group_id = group_id(&app, 0);
+        assert!(app.create_profile("Streaming"));
+        app.start_editing_group(existing_group_id.clone());
+
+        assert_eq!(
+            app.ui.group_form.active_profile.as_deref(),
+            Some("Streaming")
+        );
+        assert!(!app.ui.group_form.save_to_profile);
+
+        app.ui.group_form.core_selection = vec![false, false, true, true];
+        app.ui.group_form.save_to_profile = true;
+        app.commit_group_form_session();
+
+        {
+            let state = app.persistent_state.read().unwrap();
+            assert_eq!(state.groups[0].cores, vec![0, 1]);
+            assert_eq!(state.effective_group_cores(0), Some(vec![2, 3]));
+        }
+
+        app.start_editing_group(existing_group_id);
+        assert!(app.ui.group_form.save_to_profile);
+        assert_eq!(
+            app.ui.group_form.core_selection,
+            vec![false, false, true, true]
+        );
+
+        app.set_active_profile(None);
+        assert_eq!(
+            app.persistent_state
+                .read()
+                .unwrap()
+                .effective_group_cores(0),
+            Some(vec![0, 1])
+        );
+    }
+
     #[test]
     fn test_start_creating_group_clears_previous_edit_session() {
         let mut app = sample_state();
@@ -1986,18 +4032,71 @@ mod tests {
         let target_group_id = group_id(&app, 1);
         let moved_rule_id = rule_id(&app, 0, 0);
         let original_app = app.persistent_state.read().unwrap().groups[0].programs[0].clone();
+        let app_key = original_app.get_key();
+        assert!(app.runtime.add_running_app(
+            &app_key,
+            77,
+            source_group_id.clone(),
+            moved_rule_id.clone()
+        ));
+        app.runtime.set_running_app_cores(&app_key, vec![0]);
+        assert_eq!(
+            app.runtime.running_apps_handle().try_read().unwrap().apps[&app_key].pinned_cores,
+            Some(vec![0])
+        );
 
-        let outcome =
-            app.move_rule_to_group_at(source_group_id, moved_rule_id.clone(), target_group_id, 0);
+        let outcome = app.move_rule_to_group_at(
+            source_group_id,
+            moved_rule_id.clone(),
+            target_group_id.clone(),
+            0,
+        );
 
         assert_eq!(outcome, MoveRuleToGroupOutcome::Moved);
         let state = app.persistent_state.read().unwrap();
         assert!(state.groups[0].programs.is_empty());
         assert_eq!(state.groups[1].programs, vec![original_app]);
         drop(state);
-        assert_eq!(app.rules.rule_id_for_index(1, 0), Some(moved_rule_id));
+        assert_eq!(
+            app.rules.rule_id_for_index(1, 0),
+            Some(moved_rule_id.clone())
+        );
         assert!(app.rules.rule_id_for_index(0, 0).is_none());
         assert_eq!(app.save_count(), 1);
+        let running_apps = app.runtime.running_apps_handle();
+        let running_apps = running_apps.try_read().unwrap();
+        let running = running_apps.apps.get(&app_key).unwrap();
+        assert_eq!(running.group_id, target_group_id);
+        assert_eq!(running.rule_id, moved_rule_id);
+        assert!(running.pinned_cores.is_none());
+    }
+
+    #[test]
+    fn test_move_rule_to_group_warns_when_the_running_app_cannot_follow() {
+        let mut app = sample_state();
+        add_empty_group(&mut app, "Background");
+        let source_group_id = group_id(&app, 0);
+        let moved_rule_id = rule_id(&app, 0, 0);
+        let app_key = app.persistent_state.read().unwrap().groups[0].programs[0].get_key();
+        assert!(app.runtime.add_running_app(
+            &app_key,
+            77,
+            source_group_id.clone(),
+            moved_rule_id.clone()
+        ));
+        let running_apps = app.runtime.running_apps_handle();
+        let write_guard = running_apps.try_write().unwrap();
+
+        let outcome =
+            app.move_rule_to_group_at(source_group_id, moved_rule_id, group_id(&app, 1), 0);
+
+        assert_eq!(outcome, MoveRuleToGroupOutcome::Moved);
+        assert!(app
+            .log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message.starts_with("WARNING: the running ")));
+        drop(write_guard);
     }
 
     #[test]
@@ -2093,6 +4192,181 @@ mod tests {
         assert_eq!(app.save_count(), 0);
     }
 
+    #[test]
+    fn test_reveal_program_location_logs_missing_executable() {
+        let mut app = sample_state();
+        let missing = PathBuf::from("/definitely/missing/cpu-affinity-tool-test.exe");
+
+        app.reveal_program_location(&missing);
+
+        assert!(app.log_manager.entries.iter().any(|entry| {
+            entry
+                .message
+                .starts_with("ERROR: Failed to open the location of")
+                && entry.message.ends_with("does not exist")
+        }));
+    }
+
+    #[test]
+    fn test_reresolve_updates_target_and_offers_changed_args() {
+        let mut app = sample_state();
+        app.open_app_run_settings(group_id(&app, 0), rule_id(&app, 0, 0));
+        assert!(app.ensure_current_edit_loaded());
+        app.ui.app_edit_state.current_edit.as_mut().unwrap().args = vec!["--mine".to_string()];
+
+        app.reresolve_current_edit_target_with(|dropped| {
+            assert_eq!(dropped, Path::new(r"C:\Sample.lnk"));
+            Ok((
+                PathBuf::from(r"D:\Games\Sample.exe"),
+                vec!["--from-shortcut".to_string()],
+            ))
+        });
+
+        let draft = app.ui.app_edit_state.current_edit.as_ref().unwrap();
+        assert_eq!(draft.bin_path(), Some(Path::new(r"D:\Games\Sample.exe")));
+        assert_eq!(draft.args, vec!["--mine"]);
+        assert_eq!(
+            app.ui.app_edit_state.resolved_args,
+            Some(vec!["--from-shortcut".to_string()])
+        );
+        assert!(app
+            .log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == r"Re-resolved 'C:\Sample.lnk' to 'D:\Games\Sample.exe'"));
+
+        app.accept_resolved_args();
+        let draft = app.ui.app_edit_state.current_edit.as_ref().unwrap();
+        assert_eq!(draft.args, vec!["--from-shortcut"]);
+        assert_eq!(app.ui.app_edit_state.resolved_args, None);
+
+        app.reresolve_current_edit_target_with(|_| Err("target not found".to_string()));
+        assert!(app.log_manager.entries.iter().any(|entry| entry.message
+            == r"ERROR: Failed to re-resolve 'C:\Sample.lnk': target not found"));
+    }
+
+    #[test]
+    fn test_clone_rule_to_group_keeps_source_and_logs_copy() {
+        let mut app = sample_state();
+        add_empty_group(&mut app, "Background");
+        let source_group_id = group_id(&app, 0);
+        let target_group_id = group_id(&app, 1);
+        let source_rule_id = rule_id(&app, 0, 0);
+
+        assert!(app.clone_rule_to_group(
+            source_group_id.clone(),
+            source_rule_id.clone(),
+            target_group_id.clone(),
+        ));
+        assert!(!app.clone_rule_to_group(source_group_id, source_rule_id.clone(), target_group_id));
+
+        let state = app.persistent_state.read().unwrap();
+        assert_eq!(state.groups[0].programs.len(), 1);
+        assert_eq!(state.groups[1].programs.len(), 1);
+        assert_ne!(
+            state.groups[0].programs[0].get_key(),
+            state.groups[1].programs[0].get_key()
+        );
+        drop(state);
+        assert_eq!(app.rules.rule_id_for_index(0, 0), Some(source_rule_id));
+        assert!(app.rules.rule_id_for_index(1, 0).is_some());
+        assert_eq!(app.save_count(), 1);
+        assert!(app
+            .log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == "Copied app 'Sample' to group: Background"));
+    }
+
+    #[test]
+    fn test_separating_shared_apps_moves_running_instance_of_later_group() {
+        let mut app = sample_state();
+        add_empty_group(&mut app, "Background");
+        let shared = app.persistent_state.read().unwrap().groups[0].programs[0].clone();
+        app.persistent_state.write().unwrap().groups[1]
+            .programs
+            .push(shared.clone());
+        app.reconcile_rules();
+        let shared_key = shared.get_key();
+        assert!(app.runtime.add_running_app(
+            &shared_key,
+            42,
+            group_id(&app, 1),
+            rule_id(&app, 1, 0)
+        ));
+
+        app.toggle_separate_apps_per_group();
+
+        let separated_key = app.persistent_state.read().unwrap().groups[1].programs[0].get_key();
+        assert_ne!(separated_key, shared_key);
+        assert_eq!(app.get_running_app_pids(&separated_key), Some(vec![42]));
+        assert_eq!(app.get_running_app_pids(&shared_key), None);
+        assert_eq!(app.save_count(), 1);
+
+        app.toggle_separate_apps_per_group();
+
+        assert_eq!(
+            app.persistent_state.read().unwrap().groups[1].programs[0].get_key(),
+            shared_key
+        );
+        assert_eq!(app.get_running_app_pids(&shared_key), Some(vec![42]));
+        assert_eq!(app.get_running_app_pids(&separated_key), None);
+        assert_eq!(app.save_count(), 2);
+    }
+
+    #[test]
+    fn test_reserved_cores_toggled_in_editor_persist_once() {
+        let mut app = sample_state();
+        app.start_creating_group();
+        app.ui.group_form.toggle_reserved_core(0);
+
+        app.sync_reserved_cores_from_form();
+        app.sync_reserved_cores_from_form();
+
+        assert_eq!(app.persistent_state.read().unwrap().reserved_cores, vec![0]);
+        assert_eq!(app.save_count(), 1);
+        assert!(!app.ui.group_form.is_core_selectable(0));
+        app.ui.group_form.allow_reserved_cores = true;
+        assert!(app.ui.group_form.is_core_selectable(0));
+    }
+
+    #[test]
+    fn test_self_affinity_sync_pins_once_and_unpins_when_switched_off() {
+        let mut app = sample_state();
+        let applied = std::cell::RefCell::new(Vec::new());
+        let record = |mask| {
+            applied.borrow_mut().push(mask);
+            Ok(())
+        };
+
+        app.sync_self_affinity_with(0b1111, record);
+        assert!(applied.borrow().is_empty());
+
+        app.persistent_state.write().unwrap().self_affinity = SelfAffinity::OutsideGroups;
+        app.sync_self_affinity_with(0b1111, record);
+        app.sync_self_affinity_with(0b1111, record);
+        assert_eq!(*applied.borrow(), vec![0b1100]);
+
+        app.persistent_state.write().unwrap().groups[0].cores = vec![0, 1, 2, 3];
+        app.sync_self_affinity_with(0b1111, record);
+        assert_eq!(*applied.borrow(), vec![0b1100, 0b1111]);
+        assert!(app
+            .log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == super::NO_SELF_AFFINITY_THREADS_WARNING));
+
+        app.persistent_state.write().unwrap().self_affinity = SelfAffinity::Core(2);
+        app.sync_self_affinity_with(0b1111, record);
+        app.persistent_state.write().unwrap().self_affinity = SelfAffinity::Off;
+        app.sync_self_affinity_with(0b1111, record);
+        assert_eq!(*applied.borrow(), vec![0b1100, 0b1111, 0b0100, 0b1111]);
+        assert_eq!(
+            app.log_manager.entries.back().unwrap().message,
+            "The tool runs on all threads again"
+        );
+    }
+
     #[test]
     fn test_toggle_theme_and_monitoring_save_once() {
         let mut app = sample_state();
@@ -2104,6 +4378,58 @@ mod tests {
         app.toggle_process_monitoring();
         assert!(app.is_process_monitoring_enabled());
         assert_eq!(app.save_count(), 2);
+        assert_eq!(
+            app.log_manager.entries.back().unwrap().message,
+            "Process monitoring resumed"
+        );
+    }
+
+    #[test]
+    fn test_disabled_group_run_all_logs_once_and_persists() {
+        let mut app = sample_state();
+        let group_id = group_id(&app, 0);
+
+        app.set_group_enabled(group_id.clone(), false);
+        app.set_group_enabled(group_id.clone(), false);
+        assert!(!app.persistent_state.read().unwrap().groups[0].enabled);
+        assert_eq!(app.save_count(), 1);
+
+        let entries_before = app.log_manager.entries.len();
+        app.run_group(group_id, false);
+        assert_eq!(app.log_manager.entries.len(), entries_before + 1);
+        assert!(app
+            .log_manager
+            .entries
+            .back()
+            .unwrap()
+            .message
+            .ends_with("is disabled; enable it to run its apps"));
+    }
+
+    #[test]
+    fn test_refresh_cpu_topology_fits_selection_and_warns_about_lost_threads() {
+        let mut app = sample_state();
+        app.ui.group_form.core_selection = vec![false; 8];
+        app.ui.group_form.core_selection[1] = true;
+        app.ui.group_form.core_selection[2] = true;
+        app.ui.group_form.core_selection[6] = true;
+
+        app.refresh_cpu_topology_with(
+            CpuTopologyReport {
+                model: "Test CPU".to_string(),
+                total_threads: 4,
+                schema: None,
+            },
+            !(1 << 2),
+        );
+
+        assert_eq!(
+            app.ui.group_form.core_selection,
+            vec![false, true, false, false]
+        );
+        assert!(app.log_manager.entries.iter().any(|entry| entry.message
+            == "WARNING: selected threads [2, 6] are no longer available and were removed from the group being edited"));
+        assert!(app.ui.cpu_topology.is_some());
     }
 
     #[test]
@@ -2147,14 +4473,32 @@ mod tests {
             cores: vec![2],
             programs: Vec::new(),
             is_hidden: false,
+            is_collapsed: false,
             run_all_button: false,
+            single_numa_node: false,
+            default_priority: PriorityClass::Normal,
+            core_selector: CoreSelector::Fixed,
+            exclude_names: Vec::new(),
+            use_job_object: false,
+            cpu_rate_percent: None,
+            enabled: true,
+            color: None,
         };
         let third = CoreGroup {
             name: "Third".to_string(),
             cores: vec![3],
             programs: Vec::new(),
             is_hidden: false,
+            is_collapsed: false,
             run_all_button: false,
+            single_numa_node: false,
+            default_priority: PriorityClass::Normal,
+            core_selector: CoreSelector::Fixed,
+            exclude_names: Vec::new(),
+            use_job_object: false,
+            cpu_rate_percent: None,
+            enabled: true,
+            color: None,
         };
         app.persistent_state
             .write()
@@ -2208,6 +4552,76 @@ mod tests {
         assert_eq!(app.save_count(), 2);
     }
 
+    #[test]
+    fn test_duplicate_group_name_keeps_the_form_open_and_name_lookup_warns() {
+        let mut app = sample_state();
+        app.ui.group_form.group_name = "games".to_string();
+        app.ui.group_form.core_selection[2] = true;
+
+        assert_eq!(app.group_form_name_conflict(), Some("Games".to_string()));
+        app.commit_group_form_session();
+        assert_eq!(app.persistent_state.read().unwrap().groups.len(), 1);
+        assert_eq!(app.ui.group_form.group_name, "games");
+        assert_eq!(app.save_count(), 0);
+
+        // States saved before names had to be unique may still share one.
+        add_empty_group(&mut app, "Background");
+        add_empty_group(&mut app, "background");
+        assert_eq!(app.group_index_for_name("BACKGROUND"), Some(1));
+        assert!(app.log_manager.entries.iter().any(|entry| entry
+            .message
+            .starts_with("2 groups are named \"BACKGROUND\"; using group 2")));
+    }
+
+    #[test]
+    fn test_undo_group_delete_restores_group_identity_and_profile_cores() {
+        let mut app = sample_state();
+        app.ui.group_form.group_name = "Work".to_string();
+        app.ui.group_form.core_selection[2] = true;
+        app.commit_group_form_session();
+        let games_id = group_id(&app, 0);
+        let games_rule_id = rule_id(&app, 0, 0);
+        assert!(app.create_profile("Gaming"));
+        crate::app::features::profiles::set_group_override(
+            &app.persistent_state,
+            &games_id,
+            Some(vec![3]),
+        );
+
+        app.ui.group_form.editing_group_id = Some(games_id.clone());
+        assert_eq!(
+            app.group_form_delete_summary(),
+            Some(("Games".to_string(), 1))
+        );
+        app.delete_current_group_form_target();
+        assert_eq!(app.undoable_deleted_group_name(), Some("Games".to_string()));
+        assert_eq!(app.persistent_state.read().unwrap().groups.len(), 1);
+
+        assert!(app.undo_group_delete());
+        let state = app.persistent_state.read().unwrap();
+        assert_eq!(state.groups[0].name, "Games");
+        assert_eq!(state.groups[0].programs.len(), 1);
+        assert_eq!(state.active_profile_cores(&games_id), Some(&[3][..]));
+        drop(state);
+        assert_eq!(group_id(&app, 0), games_id);
+        assert_eq!(rule_id(&app, 0, 0), games_rule_id);
+        assert!(app.undoable_deleted_group_name().is_none());
+    }
+
+    #[test]
+    fn test_later_mutation_discards_group_delete_undo() {
+        let mut app = sample_state();
+        app.ui.group_form.editing_group_id = Some(group_id(&app, 0));
+        app.delete_current_group_form_target();
+        assert!(app.undoable_deleted_group_name().is_some());
+
+        app.toggle_theme();
+
+        assert!(app.undoable_deleted_group_name().is_none());
+        assert!(!app.undo_group_delete());
+        assert!(app.persistent_state.read().unwrap().groups.is_empty());
+    }
+
     #[test]
     fn test_delete_current_app_edit_target_saves_once() {
         let mut app = sample_state();
@@ -2233,17 +4647,49 @@ mod tests {
         assert_eq!(app.save_count(), 0);
     }
 
+    #[test]
+    fn test_queue_dropped_files_coalesces_drops_until_consumed() {
+        let mut app = sample_state();
+        app.queue_dropped_files(vec![PathBuf::from(r"C:\A.exe"), PathBuf::from(r"C:\B.exe")]);
+        app.queue_dropped_files(vec![PathBuf::from(r"C:\B.exe"), PathBuf::from(r"C:\C.exe")]);
+        app.queue_dropped_files(Vec::new());
+
+        assert_eq!(
+            app.ui.dropped_files,
+            Some(vec![
+                PathBuf::from(r"C:\A.exe"),
+                PathBuf::from(r"C:\B.exe"),
+                PathBuf::from(r"C:\C.exe"),
+            ])
+        );
+
+        app.open_drop_group_picker();
+        assert!(app.ui.drop_group_picker_open);
+        app.discard_dropped_files();
+        assert!(!app.ui.drop_group_picker_open);
+        assert!(app.ui.dropped_files.is_none());
+        assert!(app
+            .log_manager
+            .entries
+            .iter()
+            .any(|entry| entry.message == "Discarded 3 dropped file(s)"));
+
+        app.open_drop_group_picker();
+        assert!(!app.ui.drop_group_picker_open);
+    }
+
     #[test]
     fn test_consume_dropped_files_without_pending_files_clears_cached_target() {
         let mut app = sample_state();
         let target_group_id = group_id(&app, 0);
         app.ui.file_drop_hover_target = Some(target_group_id.clone());
 
-        assert!(!app.consume_dropped_files_into_group(target_group_id));
+        assert_eq!(app.consume_dropped_files_into_group(target_group_id), None);
 
         assert!(app.ui.file_drop_hover_target.is_none());
         assert!(app.ui.dropped_files.is_none());
         assert_eq!(app.save_count(), 0);
+        assert!(app.log_manager.entries.is_empty());
     }
 
     #[test]
@@ -2252,7 +4698,14 @@ mod tests {
         app.ui.dropped_files = Some(vec![PathBuf::from(r"C:\Dropped.exe")]);
         app.ui.file_drop_hover_target = Some(GroupId("stale-group".to_string()));
 
-        assert!(!app.consume_dropped_files_into_group(GroupId("stale-group".to_string())));
+        assert_eq!(
+            app.consume_dropped_files_into_group(GroupId("stale-group".to_string())),
+            None
+        );
+        assert_eq!(
+            app.log_manager.entries.back().unwrap().message,
+            "Dropped files were not added: the group no longer exists"
+        );
 
         assert!(app.ui.file_drop_hover_target.is_none());
         assert!(app.ui.dropped_files.is_none());
@@ -2274,7 +4727,10 @@ mod tests {
         app.ui.dropped_files = Some(vec![PathBuf::from(r"C:\Dropped.exe")]);
         app.ui.file_drop_hover_target = Some(target_group_id.clone());
 
-        assert!(app.consume_dropped_files_into_group(target_group_id));
+        assert_eq!(
+            app.consume_dropped_files_into_group(target_group_id),
+            Some(1)
+        );
 
         let state = app.persistent_state.read().unwrap();
         assert_eq!(state.groups[0].programs.len(), 1);
@@ -2338,7 +4794,16 @@ mod tests {
                 cores: vec![2, 3],
                 programs: vec![],
                 is_hidden: true,
+                is_collapsed: false,
                 run_all_button: false,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: Vec::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                enabled: true,
+                color: None,
             });
 
         let snapshot = app.build_central_panel_snapshot();
@@ -2350,6 +4815,67 @@ mod tests {
         assert!(snapshot.groups[1].is_hidden);
     }
 
+    #[test]
+    fn test_large_folder_drop_waits_for_confirmation() {
+        let mut app = sample_state();
+        let folder =
+            std::env::temp_dir().join(format!("cpu-affinity-large-drop-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&folder);
+        std::fs::create_dir_all(&folder).unwrap();
+        for index in 0..=FOLDER_ADD_CONFIRM_THRESHOLD {
+            std::fs::write(folder.join(format!("Tool{index:02}.exe")), b"").unwrap();
+        }
+        let group = group_id(&app, 0);
+
+        assert_eq!(
+            app.add_selected_files_to_group(group.clone(), vec![folder.clone()]),
+            0
+        );
+        while app.ui.pending_folder_scan.is_some() {
+            std::thread::sleep(std::time::Duration::from_millis(5));
+            app.poll_folder_scan();
+        }
+        let pending = app.ui.pending_folder_add.as_ref().unwrap();
+        assert_eq!(pending.found_in_folders, FOLDER_ADD_CONFIRM_THRESHOLD + 1);
+        assert_eq!(pending.group_id, group);
+
+        assert_eq!(
+            app.confirm_pending_folder_add(),
+            FOLDER_ADD_CONFIRM_THRESHOLD + 1
+        );
+        assert!(app.ui.pending_folder_add.is_none());
+        assert_eq!(
+            app.persistent_state.read().unwrap().groups[0]
+                .programs
+                .len(),
+            FOLDER_ADD_CONFIRM_THRESHOLD + 2
+        );
+        assert!(app.log_manager.entries.iter().any(|entry| entry
+            .message
+            .starts_with("Scanned 1 folder(s) for group Games: found 21 launchable files")));
+        let _ = std::fs::remove_dir_all(&folder);
+    }
+
+    #[test]
+    fn test_central_snapshot_lists_core_overlaps_unless_hidden() {
+        let mut app = sample_state();
+        add_empty_group(&mut app, "Work");
+        add_empty_group(&mut app, "Stream");
+        app.persistent_state.write().unwrap().groups[2].cores = vec![1, 3];
+
+        let snapshot = app.build_central_panel_snapshot();
+        assert_eq!(snapshot.core_overlaps.len(), 2);
+        assert_eq!(snapshot.core_overlaps[0].first, "Games");
+        assert_eq!(snapshot.core_overlaps[0].second, "Stream");
+        assert_eq!(snapshot.core_overlaps[0].cores, vec![1]);
+        assert_eq!(snapshot.core_overlaps[1].first, "Work");
+        assert_eq!(snapshot.core_overlaps[1].cores, vec![3]);
+
+        app.toggle_group_overlap_warning();
+        assert!(app.is_group_overlap_warning_hidden());
+        assert!(app.build_central_panel_snapshot().core_overlaps.is_empty());
+    }
+
     #[test]
     fn test_installed_app_picker_open_query_navigation_and_close() {
         let mut app = sample_state();
@@ -0,0 +1,67 @@
+//! Diagnostic output through the `log` facade. Nothing is printed unless a level is
+//! requested with `--verbose` or the `CPU_AFFINITY_LOG` environment variable; the
+//! in-app log is kept separately by `LogManager`.
+
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Environment variable holding the level filter, e.g. `debug` or `trace`.
+pub const LOG_LEVEL_ENV: &str = "CPU_AFFINITY_LOG";
+
+/// Writes records to stderr, which can be redirected to a file to collect them.
+struct StderrLogger;
+
+impl Log for StderrLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            eprintln!(
+                "{:<5} {}: {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+/// Level taken from the environment value; `--verbose` raises it to at least `Debug`.
+/// Unset or unparsable values turn logging off.
+pub fn level_filter(env_value: Option<&str>, verbose: bool) -> LevelFilter {
+    let from_env = env_value
+        .and_then(|value| value.trim().parse::<LevelFilter>().ok())
+        .unwrap_or(LevelFilter::Off);
+    if verbose {
+        from_env.max(LevelFilter::Debug)
+    } else {
+        from_env
+    }
+}
+
+/// Installs the stderr logger when a level is requested.
+pub fn init(verbose: bool) {
+    let level = level_filter(std::env::var(LOG_LEVEL_ENV).ok().as_deref(), verbose);
+    if level != LevelFilter::Off && log::set_logger(&StderrLogger).is_ok() {
+        log::set_max_level(level);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::level_filter;
+    use log::LevelFilter;
+
+    #[test]
+    fn test_level_filter_combines_env_and_verbose_flag() {
+        assert_eq!(level_filter(None, false), LevelFilter::Off);
+        assert_eq!(level_filter(Some("bogus"), false), LevelFilter::Off);
+        assert_eq!(level_filter(Some(" warn "), false), LevelFilter::Warn);
+        assert_eq!(level_filter(None, true), LevelFilter::Debug);
+        assert_eq!(level_filter(Some("trace"), true), LevelFilter::Trace);
+        assert_eq!(level_filter(Some("error"), true), LevelFilter::Debug);
+    }
+}
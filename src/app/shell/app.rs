@@ -3,7 +3,7 @@ use crate::app::features::execution;
 use crate::app::features::execution::InstalledPackageTrackingState;
 #[cfg(test)]
 use crate::app::instance_forwarding::ForwardedIpcCommand;
-#[cfg(any(test, all(target_os = "windows", feature = "windows")))]
+#[cfg(any(test, feature = "windows", feature = "linux"))]
 use crate::app::instance_forwarding::{
     parse_ipc_command_frame, run_rule_outcome_to_response, serialize_ipc_response_frame,
     IpcCommand, IpcResponse, IpcResponseCode,
@@ -12,9 +12,10 @@ use crate::app::models::RunningApps;
 use crate::app::runtime::{AppState, RunRuleOutcome};
 use crate::app::shell::events::ShellEvent;
 use crate::app::shell::presenters::{
-    central, footer, group_editor, header, installed_app_picker, logs, run_settings,
+    central, cpu_topology, footer, group_editor, header, installed_app_picker, logs, processes,
+    run_settings,
 };
-#[cfg(all(target_os = "windows", feature = "windows"))]
+#[cfg(any(feature = "windows", feature = "linux"))]
 use crate::app::shell::sessions::ShortcutCreationRole;
 use crate::app::shell::{GroupRoute, WindowRoute};
 use crate::app::startup::StartupIntent;
@@ -24,20 +25,72 @@ use std::path::PathBuf;
 use std::sync::mpsc::Receiver;
 use std::sync::{Arc, RwLock};
 use std::time::Duration;
-use tokio::sync::RwLock as TokioRwLock;
+use tokio::sync::{watch, RwLock as TokioRwLock};
+
+/// How often `--service` mode moves monitor events into the printed log.
+#[cfg(any(feature = "windows", feature = "linux"))]
+const SERVICE_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Width of the main window in points; the layout is a single fixed-width column.
+const WINDOW_WIDTH: f32 = 470.0;
+
+/// Shortest the main window may get, in points, on screens tall enough for it.
+const MIN_WINDOW_HEIGHT: f32 = 600.0;
+
+/// Height cap used until the monitor size is known.
+const DEFAULT_MAX_WINDOW_HEIGHT: f32 = 1000.0;
+
+/// Viewport settings shared by the native entry points. Sizes are logical points, so
+/// they scale with the display; the limits are refitted once the monitor is known.
+pub fn main_viewport() -> egui::ViewportBuilder {
+    egui::ViewportBuilder::default()
+        .with_min_inner_size([WINDOW_WIDTH, MIN_WINDOW_HEIGHT])
+        .with_max_inner_size([WINDOW_WIDTH, DEFAULT_MAX_WINDOW_HEIGHT])
+        .with_maximize_button(false)
+}
+
+/// Min and max inner size for a monitor of `monitor_size` points: the minimum never
+/// exceeds the screen, which at 150-200% scaling can be shorter than 600 points, and
+/// the height may grow up to the full screen.
+fn window_size_limits(monitor_size: egui::Vec2) -> (egui::Vec2, egui::Vec2) {
+    let min = egui::vec2(
+        WINDOW_WIDTH.min(monitor_size.x),
+        MIN_WINDOW_HEIGHT.min(monitor_size.y),
+    );
+    let max = egui::vec2(WINDOW_WIDTH, monitor_size.y.max(min.y));
+    (min, max)
+}
 
 pub struct App {
     pub state: AppState,
     tray_rx: Option<Receiver<TrayCmd>>,
     #[cfg(test)]
     forwarded_command_rx: Option<Receiver<ForwardedIpcCommand>>,
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(any(feature = "windows", feature = "linux"))]
     forwarding_runtime: Option<AppForwardingRuntime>,
     #[cfg(target_os = "windows")]
-    _tray_icon_guard: Option<tray_icon::TrayIcon>,
+    tray_icon: Option<tray_icon::TrayIcon>,
+    #[cfg(target_os = "windows")]
+    tray_monitoring_item: Option<tray_icon::menu::MenuItem>,
+    /// Tracked-app count the tray tooltip was last set for.
+    #[cfg(target_os = "windows")]
+    tray_tooltip_count: Option<usize>,
     #[cfg(target_os = "windows")]
     hwnd: Option<windows::Win32::Foundation::HWND>,
     is_hidden: bool,
+    /// Window level last sent to the viewport.
+    window_level: Option<egui::WindowLevel>,
+    /// Monitor size, in points, the window size limits were last fitted to.
+    window_limits_monitor: Option<egui::Vec2>,
+}
+
+/// Level for the main window; a window hidden in the tray never stays on top.
+fn window_level_for(always_on_top: bool, is_hidden: bool) -> egui::WindowLevel {
+    if always_on_top && !is_hidden {
+        egui::WindowLevel::AlwaysOnTop
+    } else {
+        egui::WindowLevel::Normal
+    }
 }
 
 fn theme_preference_for_index(theme_index: usize) -> egui::ThemePreference {
@@ -48,14 +101,14 @@ fn theme_preference_for_index(theme_index: usize) -> egui::ThemePreference {
     }
 }
 
-#[cfg(all(target_os = "windows", feature = "windows"))]
+#[cfg(any(feature = "windows", feature = "linux"))]
 pub struct AppForwardingRuntime {
     _guard: os_api::LocalIpcGuard,
     endpoint: os_api::LocalIpcEndpoint,
     server: Option<os_api::LocalIpcServer>,
 }
 
-#[cfg(all(target_os = "windows", feature = "windows"))]
+#[cfg(any(feature = "windows", feature = "linux"))]
 impl AppForwardingRuntime {
     pub fn pending(guard: os_api::LocalIpcGuard, endpoint: os_api::LocalIpcEndpoint) -> Self {
         Self {
@@ -66,56 +119,88 @@ impl AppForwardingRuntime {
     }
 
     fn start_server(&mut self, ctx: &egui::Context) -> Result<(), String> {
+        let repaint_ctx = ctx.clone();
+        self.start_server_with_wake(Arc::new(move || {
+            repaint_ctx.request_repaint();
+        }))
+    }
+
+    fn start_server_with_wake(&mut self, wake: os_api::LocalIpcWake) -> Result<(), String> {
         if self.server.is_some() {
             return Ok(());
         }
 
-        let repaint_ctx = ctx.clone();
-        let wake: os_api::LocalIpcWake = Arc::new(move || {
-            repaint_ctx.request_repaint();
-        });
         let server = os_api::OS::start_local_ipc_server_with_wake(&self.endpoint, Some(wake))?;
         self.server = Some(server);
         Ok(())
     }
 
+    /// Answers every forwarded request waiting on the server; returns whether there
+    /// were any.
+    fn serve_requests(&self, state: &mut AppState) -> bool {
+        let Some(server) = &self.server else {
+            return false;
+        };
+        let mut served = false;
+        while let Ok(request) = server.try_recv() {
+            let response = local_ipc_response_frame(state, &request.request);
+            let _ = request.response_tx.send(response);
+            served = true;
+        }
+        served
+    }
+
     #[cfg(test)]
     fn server_started(&self) -> bool {
         self.server.is_some()
     }
 }
 
-impl App {
-    #[cfg_attr(all(feature = "windows", not(feature = "linux")), allow(dead_code))]
-    pub fn new_with_startup_intent(
-        cc: &eframe::CreationContext<'_>,
-        startup_intent: StartupIntent,
-    ) -> Self {
-        let mut app = Self::new_without_startup_intent(cc);
-        app.handle_startup_intent_after_forwarding(startup_intent, false);
-        app
+#[cfg(any(test, feature = "windows", feature = "linux"))]
+fn local_ipc_response_frame(state: &mut AppState, request: &[u8]) -> Vec<u8> {
+    let response = match parse_ipc_command_frame(request) {
+        Ok(command) => ipc_command_response(state, command),
+        Err(_) => IpcResponse {
+            code: IpcResponseCode::ProtocolError,
+            detail: None,
+        },
+    };
+
+    serialize_ipc_response_frame(&response)
+        .unwrap_or_else(|_| br#"{"version":1,"code":"protocol_error"}"#.to_vec())
+}
+
+#[cfg(any(test, feature = "windows", feature = "linux"))]
+fn ipc_command_response(state: &mut AppState, command: IpcCommand) -> IpcResponse {
+    match command {
+        IpcCommand::RunRule { group_id, rule_id } => {
+            run_rule_outcome_to_response(state.run_group_program(group_id, rule_id))
+        }
+        IpcCommand::RunGroup { group_name } => {
+            run_rule_outcome_to_response(state.run_group_by_name(&group_name))
+        }
+        IpcCommand::RunApp {
+            group_name,
+            app_name,
+        } => run_rule_outcome_to_response(state.run_group_program_by_name(&group_name, &app_name)),
     }
+}
 
+impl App {
     pub fn new_without_startup_intent(cc: &eframe::CreationContext<'_>) -> Self {
-        #[cfg(debug_assertions)]
-        {
-            println!("========================================================");
-            println!("DEBUG: [Main Thread] App::new started");
-            println!(
-                "DEBUG: [Eframe] Backend: {}",
-                if cc.gl.is_some() {
-                    "Glow (OpenGL)"
-                } else {
-                    "WGPU"
-                }
-            );
-            println!(
-                "DEBUG: [Eframe] Integration Info: {:?}",
-                cc.integration_info
-            );
+        log::trace!("[Main Thread] App::new started");
+        log::trace!(
+            "[Eframe] Backend: {}",
+            if cc.gl.is_some() {
+                "Glow (OpenGL)"
+            } else {
+                "WGPU"
+            }
+        );
+        log::trace!("[Eframe] Integration Info: {:?}", cc.integration_info);
+        if log::log_enabled!(log::Level::Trace) {
             cc.egui_ctx
-                .options(|o| println!("DEBUG: [Egui] Context Options: {:?}", o));
-            println!("========================================================");
+                .options(|o| log::trace!("[Egui] Context Options: {:?}", o));
         }
 
         cc.egui_ctx
@@ -138,17 +223,18 @@ impl App {
                             h.hwnd.get() as *mut core::ffi::c_void
                         ));
                     }
-                    _ => {
-                        #[cfg(debug_assertions)]
-                        println!("DEBUG: Not a Win32 window handle");
-                    }
+                    _ => log::trace!("Not a Win32 window handle"),
                 }
             }
         }
 
         #[cfg(target_os = "windows")]
         let tray_res = if let Some(hwnd_value) = hwnd {
-            init_tray(cc.egui_ctx.clone(), hwnd_value)
+            init_tray(
+                cc.egui_ctx.clone(),
+                hwnd_value,
+                state.is_process_monitoring_enabled(),
+            )
         } else {
             Err("HWND not found".to_string())
         };
@@ -161,20 +247,28 @@ impl App {
                 let tray_rx = Some(handle.rx);
 
                 #[cfg(target_os = "windows")]
-                let tray_icon_guard = Some(handle.tray_icon);
+                let tray_icon = Some(handle.tray_icon);
+                #[cfg(target_os = "windows")]
+                let tray_monitoring_item = Some(handle.monitoring_item);
 
                 Self {
                     state,
                     tray_rx,
                     #[cfg(test)]
                     forwarded_command_rx: None,
-                    #[cfg(all(target_os = "windows", feature = "windows"))]
+                    #[cfg(any(feature = "windows", feature = "linux"))]
                     forwarding_runtime: None,
                     #[cfg(target_os = "windows")]
-                    _tray_icon_guard: tray_icon_guard,
+                    tray_icon,
+                    #[cfg(target_os = "windows")]
+                    tray_monitoring_item,
+                    #[cfg(target_os = "windows")]
+                    tray_tooltip_count: None,
                     #[cfg(target_os = "windows")]
                     hwnd,
                     is_hidden: false,
+                    window_level: None,
+                    window_limits_monitor: None,
                 }
             }
             Err(e) => {
@@ -186,19 +280,25 @@ impl App {
                     tray_rx: None,
                     #[cfg(test)]
                     forwarded_command_rx: None,
-                    #[cfg(all(target_os = "windows", feature = "windows"))]
+                    #[cfg(any(feature = "windows", feature = "linux"))]
                     forwarding_runtime: None,
                     #[cfg(target_os = "windows")]
-                    _tray_icon_guard: None,
+                    tray_icon: None,
+                    #[cfg(target_os = "windows")]
+                    tray_monitoring_item: None,
+                    #[cfg(target_os = "windows")]
+                    tray_tooltip_count: None,
                     #[cfg(target_os = "windows")]
                     hwnd,
                     is_hidden: false,
+                    window_level: None,
+                    window_limits_monitor: None,
                 }
             }
         }
     }
 
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(any(feature = "windows", feature = "linux"))]
     pub fn install_forwarding_runtime(
         &mut self,
         mut forwarding_runtime: Option<AppForwardingRuntime>,
@@ -248,6 +348,7 @@ impl App {
             Arc<TokioRwLock<RunningApps>>,
             Arc<RwLock<InstalledPackageTrackingState>>,
             Arc<RwLock<crate::app::models::AppStateStorage>>,
+            watch::Receiver<bool>,
         ) -> Receiver<ShellEvent>,
     {
         Self::bootstrap_runtime_without_startup(state, spawn_monitors);
@@ -260,6 +361,7 @@ impl App {
             Arc<TokioRwLock<RunningApps>>,
             Arc<RwLock<InstalledPackageTrackingState>>,
             Arc<RwLock<crate::app::models::AppStateStorage>>,
+            watch::Receiver<bool>,
         ) -> Receiver<ShellEvent>,
     {
         diagnostics::log_startup(&mut state.log_manager, &state.persistent_state);
@@ -267,6 +369,7 @@ impl App {
             state.runtime.running_apps_handle(),
             state.runtime.installed_package_tracking_handle(),
             state.persistent_state.clone(),
+            state.runtime.monitor_shutdown_handle(),
         ));
     }
 
@@ -288,7 +391,7 @@ impl App {
 
     fn handle_startup_intent(state: &mut AppState, startup_intent: StartupIntent) {
         match startup_intent {
-            StartupIntent::NormalGui => state.start_app_with_autorun(),
+            StartupIntent::NormalGui | StartupIntent::Service => state.start_app_with_autorun(),
             StartupIntent::RunRule { group_id, rule_id } => {
                 match state.run_group_program(group_id, rule_id) {
                     RunRuleOutcome::Accepted | RunRuleOutcome::LaunchRejected(_) => {}
@@ -300,6 +403,106 @@ impl App {
                     ),
                 }
             }
+            intent @ (StartupIntent::RunGroup { .. } | StartupIntent::RunApp { .. }) => {
+                if let RunRuleOutcome::MissingGroup | RunRuleOutcome::MissingRule =
+                    Self::run_named_intent(state, intent)
+                {
+                    state.log_manager.add_important_sticky_once(
+                        "ERROR: Command-line launch target was not found".to_string(),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Runs a `--run-group`/`--run-app` launch against the saved state without
+    /// creating a window.
+    #[cfg(any(feature = "windows", feature = "linux"))]
+    pub fn run_headless(startup_intent: StartupIntent) -> RunRuleOutcome {
+        let mut state = AppState::new();
+        Self::run_named_intent(&mut state, startup_intent)
+    }
+
+    /// Runs the process monitors against the saved state without a window until
+    /// Ctrl+C, printing the log to stdout. Shortcuts and `--run-app` calls forwarded
+    /// to `forwarding` are launched here. Returns the process exit code.
+    #[cfg(any(feature = "windows", feature = "linux"))]
+    pub fn run_service(mut forwarding: AppForwardingRuntime) -> i32 {
+        let rt = match tokio::runtime::Runtime::new() {
+            Ok(rt) => rt,
+            Err(err) => {
+                eprintln!("Failed to start the service runtime: {err}");
+                return 1;
+            }
+        };
+        let _guard = rt.enter();
+
+        let mut state = AppState::new();
+        Self::bootstrap_runtime_without_startup(&mut state, execution::spawn_monitors);
+        state
+            .log_manager
+            .add_entry("Service mode started; press Ctrl+C to stop".to_string());
+        let request_ready = Arc::new(tokio::sync::Notify::new());
+        let wake_ready = Arc::clone(&request_ready);
+        if let Err(err) = forwarding.start_server_with_wake(Arc::new(move || {
+            wake_ready.notify_one();
+        })) {
+            state.log_manager.add_important_entry(format!(
+                "WARNING: Shortcut forwarding server failed to start: {err}"
+            ));
+        }
+        Self::flush_service_log(&mut state);
+
+        rt.block_on(async {
+            let ctrl_c = tokio::signal::ctrl_c();
+            tokio::pin!(ctrl_c);
+            let mut ticker = tokio::time::interval(SERVICE_POLL_INTERVAL);
+            loop {
+                tokio::select! {
+                    _ = &mut ctrl_c => break,
+                    _ = request_ready.notified() => {
+                        forwarding.serve_requests(&mut state);
+                        Self::flush_service_log(&mut state);
+                    }
+                    _ = ticker.tick() => {
+                        forwarding.serve_requests(&mut state);
+                        state.sync_self_affinity();
+                        state.sync_affinity_audit();
+                        state.poll_autorun();
+                        Self::drain_monitor_events(&mut state);
+                        Self::flush_service_log(&mut state);
+                    }
+                }
+            }
+        });
+
+        state.shutdown_background_tasks();
+        state
+            .log_manager
+            .add_entry("Service mode stopped".to_string());
+        Self::flush_service_log(&mut state);
+        0
+    }
+
+    /// Prints and drops the log entries gathered since the last flush; nothing
+    /// else reads them when there is no window.
+    #[cfg(any(feature = "windows", feature = "linux"))]
+    fn flush_service_log(state: &mut AppState) {
+        for entry in state.log_manager.entries.drain(..) {
+            println!("{}", entry.format());
+        }
+    }
+
+    fn run_named_intent(state: &mut AppState, startup_intent: StartupIntent) -> RunRuleOutcome {
+        match startup_intent {
+            StartupIntent::RunGroup { group_name } => state.run_group_by_name(&group_name),
+            StartupIntent::RunApp {
+                group_name,
+                app_name,
+            } => state.run_group_program_by_name(&group_name, &app_name),
+            StartupIntent::NormalGui | StartupIntent::RunRule { .. } | StartupIntent::Service => {
+                RunRuleOutcome::MissingRule
+            }
         }
     }
 
@@ -310,32 +513,54 @@ impl App {
             tray_rx: None,
             #[cfg(test)]
             forwarded_command_rx: None,
-            #[cfg(all(target_os = "windows", feature = "windows"))]
+            #[cfg(any(feature = "windows", feature = "linux"))]
             forwarding_runtime: None,
             #[cfg(target_os = "windows")]
-            _tray_icon_guard: None,
+            tray_icon: None,
+            #[cfg(target_os = "windows")]
+            tray_monitoring_item: None,
+            #[cfg(target_os = "windows")]
+            tray_tooltip_count: None,
             #[cfg(target_os = "windows")]
             hwnd: None,
             is_hidden: false,
+            window_level: None,
+            window_limits_monitor: None,
         }
     }
 }
 
 impl eframe::App for App {
+    fn on_exit(&mut self, _gl: Option<&eframe::glow::Context>) {
+        self.state.shutdown_background_tasks();
+    }
+
     fn logic(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         self.handle_tray_events(ctx);
         self.handle_monitor_events(ctx);
-        #[cfg(all(target_os = "windows", feature = "windows"))]
+        #[cfg(any(feature = "windows", feature = "linux"))]
         self.handle_local_ipc_requests(ctx);
         #[cfg(test)]
         self.handle_forwarded_commands(ctx);
         self.state.poll_installed_app_picker_refresh();
+        self.state.poll_affinity_probe();
+        self.state.poll_process_list();
+        self.state.poll_folder_scan();
+        self.state.sync_self_affinity();
+        self.state.sync_affinity_audit();
+        if let Some(wait) = self.state.poll_autorun() {
+            ctx.request_repaint_after(wait);
+        }
+        self.fit_window_to_monitor(ctx);
 
-        if !self.should_render(ctx) {
+        let render = self.should_render(ctx);
+        self.sync_window_level(ctx);
+        if !render {
             return;
         }
 
         self.apply_theme(ctx);
+        self.handle_hotkeys(ctx);
         self.handle_file_drops(ctx);
     }
 
@@ -350,27 +575,41 @@ impl eframe::App for App {
 
 #[cfg(test)]
 mod tests {
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(any(
+        all(target_os = "windows", feature = "windows"),
+        all(target_os = "linux", feature = "linux")
+    ))]
     use super::AppForwardingRuntime;
-    use super::{theme_preference_for_index, App};
+    use super::{theme_preference_for_index, window_level_for, window_size_limits, App};
     use crate::app::instance_forwarding::{
         parse_ipc_response_frame, serialize_ipc_command_frame, ForwardedIpcCommand, IpcCommand,
         IpcResponseCode,
     };
-    use crate::app::models::{AppStateStorage, AppToRun, CoreGroup, CpuSchema};
+    use crate::app::models::{
+        AppStateStorage, AppToRun, CoreGroup, CoreSelector, CpuSchema, SelfAffinity,
+    };
     use crate::app::runtime::AppState;
     use crate::app::shell::events::ShellEvent;
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(any(
+        all(target_os = "windows", feature = "windows"),
+        all(target_os = "linux", feature = "linux")
+    ))]
     use crate::app::shell::sessions::ShortcutCreationRole;
     use crate::app::startup::StartupIntent;
     use eframe::egui;
     use os_api::PriorityClass;
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(any(
+        all(target_os = "windows", feature = "windows"),
+        all(target_os = "linux", feature = "linux")
+    ))]
     use os_api::{LocalIpcClientError, LocalIpcEndpoint, OS};
     use std::path::PathBuf;
     use std::sync::mpsc;
     use std::sync::{Arc, RwLock};
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(any(
+        all(target_os = "windows", feature = "windows"),
+        all(target_os = "linux", feature = "linux")
+    ))]
     use std::time::SystemTime;
 
     #[test]
@@ -381,6 +620,28 @@ mod tests {
         assert_eq!(theme_preference_for_index(99), egui::ThemePreference::Dark);
     }
 
+    #[test]
+    fn test_window_level_drops_on_top_while_hidden_in_tray() {
+        assert_eq!(
+            window_level_for(true, false),
+            egui::WindowLevel::AlwaysOnTop
+        );
+        assert_eq!(window_level_for(true, true), egui::WindowLevel::Normal);
+        assert_eq!(window_level_for(false, false), egui::WindowLevel::Normal);
+    }
+
+    #[test]
+    fn test_window_limits_fit_short_high_dpi_screens() {
+        // 1920x1080 at 200% scaling is 960x540 points.
+        let (min, max) = window_size_limits(egui::vec2(960.0, 540.0));
+        assert_eq!(min, egui::vec2(470.0, 540.0));
+        assert_eq!(max, egui::vec2(470.0, 540.0));
+
+        let (min, max) = window_size_limits(egui::vec2(2560.0, 1440.0));
+        assert_eq!(min, egui::vec2(470.0, 600.0));
+        assert_eq!(max, egui::vec2(470.0, 1440.0));
+    }
+
     fn sample_state() -> AppState {
         AppState::new_for_test(
             Arc::new(RwLock::new(AppStateStorage {
@@ -392,7 +653,23 @@ mod tests {
                 },
                 theme_index: 0,
                 process_monitoring_enabled: false,
+                reset_runtime_on_restart: false,
+                hide_group_overlap_warning: false,
+                hide_taskbar_when_tray: true,
+                separate_apps_per_group: false,
+                launch_confirm_secs: 0,
+                launch_confirm_window: false,
                 rule_identities: None,
+                profiles: Vec::new(),
+                active_profile: None,
+                reserved_cores: Vec::new(),
+                self_affinity: SelfAffinity::Off,
+                affinity_audit: false,
+                click_to_launch: false,
+                always_on_top: false,
+                hide_tips: false,
+                run_all_confirm_threshold: 5,
+                favorites: Vec::new(),
                 loaded_version: 5,
                 pending_pre_v6_backup: false,
             })),
@@ -452,7 +729,27 @@ mod tests {
         );
     }
 
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(all(target_os = "linux", feature = "linux"))]
+    fn unique_ipc_endpoint(label: &str) -> LocalIpcEndpoint {
+        let nanos = SystemTime::now()
+            .duration_since(SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_nanos();
+        let name = format!(
+            "cpu-affinity-tool-app-test-{label}-{}-{nanos}",
+            std::process::id()
+        );
+        let dir = std::env::temp_dir();
+        LocalIpcEndpoint {
+            socket_path: dir.join(format!("{name}.sock")),
+            lock_path: dir.join(format!("{name}.lock")),
+        }
+    }
+
+    #[cfg(any(
+        all(target_os = "windows", feature = "windows"),
+        all(target_os = "linux", feature = "linux")
+    ))]
     #[test]
     fn test_pending_forwarding_runtime_starts_server_only_when_installed() {
         let endpoint = unique_ipc_endpoint("pending");
@@ -534,7 +831,16 @@ mod tests {
                     cores: vec![0, 1],
                     programs,
                     is_hidden: false,
+                    is_collapsed: false,
                     run_all_button: true,
+                    single_numa_node: false,
+                    default_priority: PriorityClass::Normal,
+                    core_selector: CoreSelector::Fixed,
+                    exclude_names: Vec::new(),
+                    use_job_object: false,
+                    cpu_rate_percent: None,
+                    enabled: true,
+                    color: None,
                 }],
                 cpu_schema: CpuSchema {
                     model: "Test CPU".to_string(),
@@ -542,7 +848,23 @@ mod tests {
                 },
                 theme_index: 0,
                 process_monitoring_enabled: false,
+                reset_runtime_on_restart: false,
+                hide_group_overlap_warning: false,
+                hide_taskbar_when_tray: true,
+                separate_apps_per_group: false,
+                launch_confirm_secs: 0,
+                launch_confirm_window: false,
                 rule_identities: None,
+                profiles: Vec::new(),
+                active_profile: None,
+                reserved_cores: Vec::new(),
+                self_affinity: SelfAffinity::Off,
+                affinity_audit: false,
+                click_to_launch: false,
+                always_on_top: false,
+                hide_tips: false,
+                run_all_confirm_threshold: 5,
+                favorites: Vec::new(),
                 loaded_version: 5,
                 pending_pre_v6_backup: false,
             })),
@@ -560,7 +882,7 @@ mod tests {
             &mut state,
             &ctx,
             StartupIntent::NormalGui,
-            move |_, _, _| rx,
+            move |_, _, _, _| rx,
         );
 
         assert!(state.runtime.monitor_rx.is_some());
@@ -622,7 +944,7 @@ mod tests {
             &mut state,
             &ctx,
             StartupIntent::NormalGui,
-            move |_, _, _| rx,
+            move |_, _, _, _| rx,
         );
 
         let messages = state
@@ -664,7 +986,7 @@ mod tests {
                 group_id,
                 rule_id: requested_rule_id,
             },
-            move |_, _, _| rx,
+            move |_, _, _, _| rx,
         );
 
         let messages = state
@@ -786,30 +1108,90 @@ mod tests {
 impl App {
     fn handle_tray_events(&mut self, ctx: &egui::Context) {
         let mut show_requested = false;
+        let mut quit_requested = false;
 
         if let Some(rx) = &self.tray_rx {
             while let Ok(cmd) = rx.try_recv() {
                 match cmd {
                     TrayCmd::Show => show_requested = true,
+                    TrayCmd::ToggleMonitoring => self.state.toggle_process_monitoring(),
+                    TrayCmd::Quit => quit_requested = true,
                 }
             }
         }
 
+        if quit_requested {
+            // Closing the viewport ends the app through `on_exit` like the window's close button.
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        }
         if show_requested {
             self.show_from_tray(ctx);
         }
+
+        #[cfg(target_os = "windows")]
+        {
+            self.sync_tray_monitoring_label();
+            self.sync_tray_tooltip();
+        }
+    }
+
+    /// Shows how many apps are tracked in the tray tooltip, updated when the count changes.
+    #[cfg(target_os = "windows")]
+    fn sync_tray_tooltip(&mut self) {
+        let Some(tray_icon) = &self.tray_icon else {
+            return;
+        };
+        let running_apps = self.state.runtime.running_apps_handle();
+        let Ok(count) = running_apps.try_read().map(|apps| apps.apps.len()) else {
+            return;
+        };
+        if self.tray_tooltip_count != Some(count) {
+            let _ = tray_icon.set_tooltip(Some(crate::tray::tray_tooltip(count)));
+            self.tray_tooltip_count = Some(count);
+        }
+    }
+
+    /// Keeps the tray toggle text in step with changes made from the footer or hotkey.
+    #[cfg(target_os = "windows")]
+    fn sync_tray_monitoring_label(&self) {
+        if let Some(item) = &self.tray_monitoring_item {
+            let label =
+                crate::tray::monitoring_menu_label(self.state.is_process_monitoring_enabled());
+            if item.text() != label {
+                item.set_text(label);
+            }
+        }
+    }
+
+    fn handle_hotkeys(&mut self, ctx: &egui::Context) {
+        if ctx.input_mut(|input| input.consume_key(egui::Modifiers::COMMAND, egui::Key::M)) {
+            self.state.toggle_process_monitoring();
+        }
     }
 
     fn handle_monitor_events(&mut self, ctx: &egui::Context) {
+        if Self::drain_monitor_events(&mut self.state) {
+            ctx.request_repaint();
+        }
+    }
+
+    /// Moves pending monitor and launch events into the log; returns whether any
+    /// of them should repaint the window.
+    fn drain_monitor_events(state: &mut AppState) -> bool {
         let mut repaint_requested = false;
 
-        if let Some(rx) = &self.state.runtime.monitor_rx {
+        let runtime = &state.runtime;
+        for rx in runtime
+            .monitor_rx
+            .iter()
+            .chain(std::iter::once(&runtime.launch_event_rx))
+        {
             while let Ok(event) = rx.try_recv() {
                 if let Some((message, sticky)) = event.legacy_log_message() {
                     if sticky {
-                        self.state.log_manager.add_sticky_once(message.to_string());
+                        state.log_manager.add_sticky_once(message.to_string());
                     } else {
-                        self.state.log_manager.add_entry(message.to_string());
+                        state.log_manager.add_entry(message.to_string());
                     }
                 }
 
@@ -817,9 +1199,7 @@ impl App {
             }
         }
 
-        if repaint_requested {
-            ctx.request_repaint();
-        }
+        repaint_requested
     }
 
     #[cfg(test)]
@@ -843,50 +1223,45 @@ impl App {
         ctx.request_repaint();
     }
 
-    #[cfg(all(target_os = "windows", feature = "windows"))]
+    #[cfg(any(feature = "windows", feature = "linux"))]
     fn handle_local_ipc_requests(&mut self, ctx: &egui::Context) {
-        let mut requests = Vec::new();
         if let Some(runtime) = &self.forwarding_runtime {
-            if let Some(server) = &runtime.server {
-                while let Ok(request) = server.try_recv() {
-                    requests.push(request);
-                }
+            if runtime.serve_requests(&mut self.state) {
+                ctx.request_repaint();
             }
         }
+    }
 
-        if requests.is_empty() {
-            return;
-        }
-
-        for request in requests {
-            let response = self.handle_local_ipc_request_frame(&request.request);
-            let _ = request.response_tx.send(response);
-        }
+    #[cfg(test)]
+    fn handle_local_ipc_request_frame(&mut self, request: &[u8]) -> Vec<u8> {
+        local_ipc_response_frame(&mut self.state, request)
+    }
 
-        ctx.request_repaint();
+    #[cfg(test)]
+    fn handle_ipc_command(&mut self, command: IpcCommand) -> IpcResponse {
+        ipc_command_response(&mut self.state, command)
     }
 
-    #[cfg(any(test, all(target_os = "windows", feature = "windows")))]
-    fn handle_local_ipc_request_frame(&mut self, request: &[u8]) -> Vec<u8> {
-        let response = match parse_ipc_command_frame(request) {
-            Ok(command) => self.handle_ipc_command(command),
-            Err(_) => IpcResponse {
-                code: IpcResponseCode::ProtocolError,
-                detail: None,
-            },
+    fn fit_window_to_monitor(&mut self, ctx: &egui::Context) {
+        let Some(monitor_size) = ctx.input(|input| input.viewport().monitor_size) else {
+            return;
         };
-
-        serialize_ipc_response_frame(&response)
-            .unwrap_or_else(|_| br#"{"version":1,"code":"protocol_error"}"#.to_vec())
+        if self.window_limits_monitor == Some(monitor_size) {
+            return;
+        }
+        self.window_limits_monitor = Some(monitor_size);
+        let (min, max) = window_size_limits(monitor_size);
+        ctx.send_viewport_cmd(egui::ViewportCommand::MinInnerSize(min));
+        ctx.send_viewport_cmd(egui::ViewportCommand::MaxInnerSize(max));
     }
 
-    #[cfg(any(test, all(target_os = "windows", feature = "windows")))]
-    fn handle_ipc_command(&mut self, command: IpcCommand) -> IpcResponse {
-        match command {
-            IpcCommand::RunRule { group_id, rule_id } => {
-                run_rule_outcome_to_response(self.state.run_group_program(group_id, rule_id))
-            }
+    fn sync_window_level(&mut self, ctx: &egui::Context) {
+        let level = window_level_for(self.state.is_always_on_top(), self.is_hidden);
+        if self.window_level == Some(level) {
+            return;
         }
+        self.window_level = Some(level);
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(level));
     }
 
     fn should_render(&mut self, ctx: &egui::Context) -> bool {
@@ -910,7 +1285,9 @@ impl App {
 
         #[cfg(target_os = "windows")]
         if let Some(hwnd) = self.hwnd {
-            crate::app::adapters::os::set_taskbar_visible(hwnd, false);
+            if self.state.is_taskbar_hidden_when_tray() {
+                crate::app::adapters::os::set_taskbar_visible(hwnd, false);
+            }
             crate::app::adapters::os::restore_and_focus_window(hwnd);
         }
 
@@ -962,9 +1339,7 @@ impl App {
                 .collect()
         });
 
-        if !files.is_empty() {
-            self.state.ui.dropped_files = Some(files);
-        }
+        self.state.queue_dropped_files(files);
     }
 
     fn render_main_ui(&mut self, ui: &mut egui::Ui) {
@@ -981,6 +1356,8 @@ impl App {
                 GroupRoute::Edit => group_editor::edit_group_window(app_state, ui),
             },
             WindowRoute::Logs => logs::draw_logs_window(app_state, ui),
+            WindowRoute::Processes => processes::draw_processes_window(app_state, ui),
+            WindowRoute::CpuTopology => cpu_topology::draw_cpu_topology_window(app_state, ui),
             WindowRoute::AppRunSettings => run_settings::draw_app_run_settings(app_state, ui),
             WindowRoute::InstalledAppPicker => {
                 installed_app_picker::draw_installed_app_picker(app_state, ui)
@@ -4,9 +4,9 @@ pub mod presenters;
 mod routes;
 pub mod sessions;
 
-pub use app::App;
-#[cfg(all(target_os = "windows", feature = "windows"))]
+#[cfg(any(feature = "windows", feature = "linux"))]
 pub use app::AppForwardingRuntime;
+pub use app::{main_viewport, App};
 pub use routes::{GroupRoute, WindowRoute};
 pub type GroupFormSession = sessions::GroupFormSession;
 pub type UiSession = sessions::UiSession;
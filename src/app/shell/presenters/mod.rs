@@ -1,5 +1,7 @@
 #[path = "../../views/central.rs"]
 pub mod central;
+#[path = "../../views/cpu_topology.rs"]
+pub mod cpu_topology;
 #[path = "../../views/footer.rs"]
 pub mod footer;
 #[path = "../../views/group_editor.rs"]
@@ -10,6 +12,8 @@ pub mod header;
 pub mod installed_app_picker;
 #[path = "../../views/logs.rs"]
 pub mod logs;
+#[path = "../../views/processes.rs"]
+pub mod processes;
 #[path = "../../views/run_settings.rs"]
 pub mod run_settings;
 #[path = "../../views/shared_elements.rs"]
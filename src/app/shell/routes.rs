@@ -9,6 +9,8 @@ pub enum GroupRoute {
 pub enum WindowRoute {
     Groups(GroupRoute),
     Logs,
+    Processes,
+    CpuTopology,
     AppRunSettings,
     InstalledAppPicker,
 }
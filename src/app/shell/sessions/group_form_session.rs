@@ -1,4 +1,8 @@
+use crate::app::features::diagnostics::affinity_probe::AffinityProbeReport;
+use crate::app::models::CoreSelector;
 use crate::app::shared::ids::GroupId;
+use os_api::PriorityClass;
+use std::sync::mpsc::Receiver;
 
 /// Represents the state of the form used for creating or editing a core group.
 /// This structure tracks the form's input fields and editing state.
@@ -13,8 +17,48 @@ pub struct GroupFormSession {
     pub group_name: String,
     /// Whether the "run all" button should be enabled for this group.
     pub run_all_enabled: bool,
+    /// Whether launched apps should prefer a single NUMA node.
+    pub single_numa_node: bool,
+    /// Priority given to apps newly added to the group.
+    pub default_priority: PriorityClass,
+    /// How launches pick their cores.
+    pub core_selector: CoreSelector,
+    /// Comma-separated executable names left at the default affinity.
+    pub exclude_names: String,
+    /// Whether launches run in a Job Object constraining all children.
+    pub use_job_object: bool,
+    /// CPU usage cap in percent, `None` for no limit.
+    pub cpu_rate_percent: Option<u8>,
+    /// Picked accent color, `None` for the one derived from the name.
+    pub color: Option<[u8; 3]>,
     /// Index of the last clicked core for shift+click range selection.
     pub last_clicked_core: Option<usize>,
+    /// Core that had focus when Shift+arrow was pressed, with the pass number; the
+    /// core receiving focus next extends the range from it.
+    pub keyboard_range_from: Option<(usize, u64)>,
+    /// Profile that was active when editing started, if any.
+    pub active_profile: Option<String>,
+    /// Whether the selection is saved as the active profile's override
+    /// instead of the group's own cores.
+    pub save_to_profile: bool,
+    /// The group's own cores, kept intact while editing a profile override.
+    pub base_cores: Vec<usize>,
+    /// CPUs the OS currently offers, refreshed whenever the editor opens.
+    pub available_core_mask: usize,
+    /// NUMA node of each thread, loaded whenever the editor opens or the topology
+    /// is refreshed; `None` where the OS doesn't report one.
+    pub numa_nodes: Vec<Option<u16>>,
+    /// Load of each thread last sampled by monitoring, refreshed every frame the
+    /// editor is shown; `None` while monitoring hasn't sampled any.
+    pub core_usage: Option<Vec<f32>>,
+    /// Threads reserved for the OS, loaded whenever the editor opens.
+    pub reserved_cores: Vec<usize>,
+    /// Lets reserved threads be selected for this group.
+    pub allow_reserved_cores: bool,
+    /// Pending result of the "Test affinity" diagnostic, while it runs.
+    pub affinity_probe_rx: Option<Receiver<Result<AffinityProbeReport, String>>>,
+    /// Outcome of the last affinity test for the current selection.
+    pub affinity_probe_result: Option<Result<AffinityProbeReport, String>>,
 }
 
 impl GroupFormSession {
@@ -23,8 +67,73 @@ impl GroupFormSession {
         self.editing_group_id = None;
         self.editing_selection = None;
         self.run_all_enabled = false;
+        self.single_numa_node = false;
+        self.default_priority = PriorityClass::Normal;
+        self.core_selector = CoreSelector::Fixed;
+        self.exclude_names.clear();
+        self.use_job_object = false;
+        self.cpu_rate_percent = None;
+        self.color = None;
         self.group_name.clear();
         self.core_selection.fill(false);
         self.last_clicked_core = None;
+        self.keyboard_range_from = None;
+        self.active_profile = None;
+        self.save_to_profile = false;
+        self.base_cores.clear();
+        self.allow_reserved_cores = false;
+        self.affinity_probe_rx = None;
+        self.affinity_probe_result = None;
+    }
+
+    /// Whether a core can be selected; cores beyond the mask width are assumed available.
+    pub fn is_core_available(&self, index: usize) -> bool {
+        index >= usize::BITS as usize || self.available_core_mask & (1 << index) != 0
+    }
+
+    pub fn is_core_reserved(&self, index: usize) -> bool {
+        self.reserved_cores.contains(&index)
+    }
+
+    /// Whether a core may be newly selected: available, and not reserved unless overridden.
+    pub fn is_core_selectable(&self, index: usize) -> bool {
+        self.is_core_available(index)
+            && (self.allow_reserved_cores || !self.is_core_reserved(index))
+    }
+
+    /// Fits the selection to `total_threads` threads and drops threads the OS no
+    /// longer offers; returns the selected threads that were lost.
+    pub fn resize_core_selection(&mut self, total_threads: usize) -> Vec<usize> {
+        let mut lost: Vec<usize> = self
+            .core_selection
+            .iter()
+            .enumerate()
+            .skip(total_threads)
+            .filter_map(|(index, &selected)| selected.then_some(index))
+            .collect();
+        self.core_selection.resize(total_threads, false);
+        for index in 0..total_threads {
+            if self.core_selection[index] && !self.is_core_available(index) {
+                self.core_selection[index] = false;
+                lost.push(index);
+            }
+        }
+        lost.sort_unstable();
+        if self
+            .last_clicked_core
+            .is_some_and(|index| index >= total_threads)
+        {
+            self.last_clicked_core = None;
+        }
+        lost
+    }
+
+    pub fn toggle_reserved_core(&mut self, index: usize) {
+        if let Some(position) = self.reserved_cores.iter().position(|&core| core == index) {
+            self.reserved_cores.remove(position);
+        } else {
+            self.reserved_cores.push(index);
+            self.reserved_cores.sort_unstable();
+        }
     }
 }
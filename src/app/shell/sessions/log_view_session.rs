@@ -0,0 +1,95 @@
+/// View state of the Activity window; reset on restart.
+pub struct LogViewSession {
+    /// Case-insensitive substring filter; empty shows every entry.
+    pub filter: String,
+    /// Lists the newest entry first (the default) instead of the oldest.
+    pub newest_first: bool,
+    /// Keeps the newest entry in view as new ones arrive.
+    pub auto_scroll: bool,
+    /// Newest entry seen on the previous frame, to notice arrivals once the log is capped.
+    last_newest: Option<String>,
+}
+
+impl Default for LogViewSession {
+    fn default() -> Self {
+        Self {
+            filter: String::new(),
+            newest_first: true,
+            auto_scroll: true,
+            last_newest: None,
+        }
+    }
+}
+
+impl LogViewSession {
+    /// Applies the filter and sort order to formatted entries given oldest first.
+    pub fn visible_entries(&self, entries: impl DoubleEndedIterator<Item = String>) -> Vec<String> {
+        let needle = self.filter.trim().to_lowercase();
+        let matches = |entry: &String| needle.is_empty() || entry.to_lowercase().contains(&needle);
+        if self.newest_first {
+            entries.rev().filter(matches).collect()
+        } else {
+            entries.filter(matches).collect()
+        }
+    }
+
+    /// Records the newest entry and reports whether it changed since the last call.
+    pub fn note_newest(&mut self, newest: Option<&str>) -> bool {
+        if self.last_newest.as_deref() == newest {
+            return false;
+        }
+        self.last_newest = newest.map(str::to_string);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LogViewSession;
+
+    fn entries() -> impl DoubleEndedIterator<Item = String> {
+        [
+            "[10:00:00] Launched Game",
+            "[10:00:01] ERROR: boom",
+            "[10:00:02] Launched game helper",
+        ]
+        .into_iter()
+        .map(str::to_string)
+    }
+
+    #[test]
+    fn test_visible_entries_filter_case_insensitively_in_either_order() {
+        let mut view = LogViewSession {
+            filter: " game ".into(),
+            ..LogViewSession::default()
+        };
+        assert_eq!(
+            view.visible_entries(entries()),
+            vec![
+                "[10:00:02] Launched game helper",
+                "[10:00:00] Launched Game"
+            ]
+        );
+
+        view.newest_first = false;
+        assert_eq!(
+            view.visible_entries(entries()),
+            vec![
+                "[10:00:00] Launched Game",
+                "[10:00:02] Launched game helper"
+            ]
+        );
+
+        view.filter.clear();
+        assert_eq!(view.visible_entries(entries()).len(), 3);
+    }
+
+    #[test]
+    fn test_note_newest_reports_only_changes() {
+        let mut view = LogViewSession::default();
+        assert!(!view.note_newest(None));
+        assert!(view.note_newest(Some("a")));
+        assert!(!view.note_newest(Some("a")));
+        assert!(view.note_newest(Some("b")));
+    }
+}
@@ -1,10 +1,17 @@
 mod group_form_session;
 mod installed_picker_session;
+mod log_view_session;
+mod process_list_session;
 mod rule_editor_session;
 mod ui_session;
 
 pub use group_form_session::GroupFormSession;
 pub use installed_picker_session::InstalledAppPickerSession;
+pub use log_view_session::LogViewSession;
+pub use process_list_session::ProcessListSession;
 pub(crate) use rule_editor_session::ShortcutCreationRole;
 pub use rule_editor_session::{RuleEditorSession, RuleEditorTarget, RuleShortcutResult};
-pub use ui_session::UiSession;
+pub use ui_session::{
+    DeletedGroup, PendingAppStop, PendingConfigImport, PendingFolderAdd, PendingFolderScan,
+    PendingGroupPriority, PendingRunGroup, RuleSelectMode, UiSession,
+};
@@ -0,0 +1,103 @@
+use crate::app::models::AffinityFavorite;
+use crate::app::shared::ids::GroupId;
+use os_api::ProcessInfo;
+use std::sync::mpsc::Receiver;
+use std::time::SystemTime;
+
+/// State of the Processes view; the list is only re-read when a refresh is requested.
+#[derive(Default)]
+pub struct ProcessListSession {
+    /// Last enumeration result, sorted by name.
+    pub processes: Vec<ProcessInfo>,
+    /// When `processes` was read; `None` until the first refresh finishes.
+    pub refreshed_at: Option<SystemTime>,
+    /// Case-insensitive name or PID filter; empty shows every process.
+    pub filter: String,
+    /// Process the apply action targets.
+    pub selected_pid: Option<u32>,
+    /// Group whose threads and default priority are applied to the selected process.
+    pub target_group_id: Option<GroupId>,
+    /// Favorite mask waiting for the user to pick the process it is applied to.
+    pub pick_favorite: Option<AffinityFavorite>,
+    pub refresh_rx: Option<Receiver<Vec<ProcessInfo>>>,
+}
+
+impl ProcessListSession {
+    pub fn is_refreshing(&self) -> bool {
+        self.refresh_rx.is_some()
+    }
+
+    /// Cached processes matching the filter.
+    pub fn visible_processes(&self) -> impl Iterator<Item = &ProcessInfo> {
+        let needle = self.filter.trim().to_lowercase();
+        self.processes.iter().filter(move |process| {
+            needle.is_empty()
+                || process.name.to_lowercase().contains(&needle)
+                || process.pid.to_string().starts_with(&needle)
+        })
+    }
+
+    /// Replaces the cached list, dropping the selection when that process is gone.
+    pub fn set_processes(&mut self, processes: Vec<ProcessInfo>) {
+        if let Some(pid) = self.selected_pid {
+            if !processes.iter().any(|process| process.pid == pid) {
+                self.selected_pid = None;
+            }
+        }
+        self.processes = processes;
+        self.refreshed_at = Some(SystemTime::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ProcessListSession;
+    use os_api::{PriorityClass, ProcessInfo};
+
+    fn process(pid: u32, name: &str) -> ProcessInfo {
+        ProcessInfo {
+            pid,
+            name: name.to_string(),
+            affinity_mask: 0b11,
+            priority: PriorityClass::Normal,
+        }
+    }
+
+    #[test]
+    fn test_visible_processes_match_name_or_pid_prefix() {
+        let mut session = ProcessListSession::default();
+        session.set_processes(vec![
+            process(120, "Game.exe"),
+            process(4512, "obs64.exe"),
+            process(77, "steam.exe"),
+        ]);
+
+        session.filter = " game ".into();
+        let names: Vec<&str> = session
+            .visible_processes()
+            .map(|process| process.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Game.exe"]);
+
+        session.filter = "45".into();
+        assert_eq!(session.visible_processes().next().unwrap().pid, 4512);
+
+        session.filter.clear();
+        assert_eq!(session.visible_processes().count(), 3);
+    }
+
+    #[test]
+    fn test_set_processes_drops_selection_of_exited_process() {
+        let mut session = ProcessListSession {
+            selected_pid: Some(120),
+            ..ProcessListSession::default()
+        };
+
+        session.set_processes(vec![process(120, "game.exe")]);
+        assert_eq!(session.selected_pid, Some(120));
+        assert!(session.refreshed_at.is_some());
+
+        session.set_processes(vec![process(77, "steam.exe")]);
+        assert_eq!(session.selected_pid, None);
+    }
+}
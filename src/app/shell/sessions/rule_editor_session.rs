@@ -23,6 +23,11 @@ pub struct RuleEditorSession {
     pub target: Option<RuleEditorTarget>,
     /// Transient result from desktop shortcut creation.
     pub shortcut_result: Option<RuleShortcutResult>,
+    /// Arguments of a re-resolved shortcut that differ from the draft's, awaiting
+    /// the user's choice.
+    pub resolved_args: Option<Vec<String>>,
+    /// Whether Realtime priority was picked and awaits confirmation.
+    pub confirm_realtime: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
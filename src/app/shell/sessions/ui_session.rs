@@ -1,16 +1,135 @@
-use crate::app::shared::ids::GroupId;
-use crate::app::shell::sessions::{GroupFormSession, InstalledAppPickerSession, RuleEditorSession};
+use crate::app::adapters::discovery::ExpandedDrop;
+use crate::app::features::diagnostics::cpu_topology::CpuTopologyReport;
+use crate::app::features::preferences::config_import::ConfigDiff;
+use crate::app::features::rules::PersistedGroupIdentity;
+use crate::app::models::cpu_presets::UserPresetLayout;
+use crate::app::models::{AppStateStorage, CoreGroup, CoreSelector};
+use crate::app::shared::ids::{GroupId, RuleId};
+use crate::app::shell::sessions::{
+    GroupFormSession, InstalledAppPickerSession, LogViewSession, ProcessListSession,
+    RuleEditorSession,
+};
 use crate::app::shell::{GroupRoute, WindowRoute};
+use os_api::{PriorityClass, PriorityClassName};
 use std::path::PathBuf;
+use std::sync::mpsc::Receiver;
+use std::time::{Duration, Instant};
+
+/// How long the header offers to undo a group deletion.
+pub const GROUP_DELETE_UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+/// A running app the user asked to stop, awaiting confirmation.
+pub struct PendingAppStop {
+    pub group_id: GroupId,
+    pub rule_id: RuleId,
+    pub app_name: String,
+}
+
+/// Files found in dropped folders, waiting for confirmation because there are many.
+pub struct PendingFolderAdd {
+    pub group_id: GroupId,
+    pub paths: Vec<PathBuf>,
+    pub folder_count: usize,
+    pub found_in_folders: usize,
+    pub truncated: bool,
+}
+
+/// Dropped folders being scanned in the background for the group they were added to.
+pub struct PendingFolderScan {
+    pub group_id: GroupId,
+    pub rx: Receiver<ExpandedDrop>,
+}
+
+/// A config file read for import, waiting for the user to review its changes.
+pub struct PendingConfigImport {
+    pub path: PathBuf,
+    pub config: AppStateStorage,
+    pub diff: ConfigDiff,
+}
+
+/// A "Run all" that would start many apps, waiting for confirmation.
+pub struct PendingRunGroup {
+    pub group_id: GroupId,
+    pub group_name: String,
+    pub force: bool,
+    /// Apps the run would start rather than only reapply settings to.
+    pub app_count: usize,
+    pub dont_ask_again: bool,
+}
+
+/// A priority chosen for every app of a group, waiting for confirmation.
+pub struct PendingGroupPriority {
+    pub group_id: GroupId,
+    pub group_name: String,
+    pub priority: PriorityClassName,
+    pub app_count: usize,
+}
+
+/// How a click on an app's name changes the row selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleSelectMode {
+    /// Plain click: select only this row.
+    Only,
+    /// Ctrl click: add or drop this row.
+    Toggle,
+    /// Shift click: select the rows between the last clicked one and this one.
+    Range,
+}
+
+/// A deleted group kept in memory so the deletion can be undone.
+pub struct DeletedGroup {
+    pub index: usize,
+    pub group: CoreGroup,
+    pub identity: PersistedGroupIdentity,
+    /// Profile overrides removed with the group, keyed by profile name
+    pub profile_cores: Vec<(String, Vec<usize>)>,
+    pub deleted_at: Instant,
+}
+
+impl DeletedGroup {
+    pub fn is_expired(&self) -> bool {
+        self.deleted_at.elapsed() >= GROUP_DELETE_UNDO_WINDOW
+    }
+}
 
 /// Transient UI state owned by the shell layer.
 pub struct UiSession {
     pub current_window: WindowRoute,
     pub group_form: GroupFormSession,
     pub app_edit_state: RuleEditorSession,
+    /// Dropped paths waiting for a group; later drops are added until one takes them.
     pub dropped_files: Option<Vec<PathBuf>>,
     pub file_drop_hover_target: Option<GroupId>,
+    /// Whether files dropped outside every group are waiting for the user to pick one.
+    pub drop_group_picker_open: bool,
     pub installed_app_picker: InstalledAppPickerSession,
+    pub log_view: LogViewSession,
+    pub process_list: ProcessListSession,
+    /// Detected on the first visit to the topology panel.
+    pub cpu_topology: Option<CpuTopologyReport>,
+    /// Layout the user is describing for an unrecognized CPU.
+    pub preset_draft: UserPresetLayout,
+    /// Name typed into the header's new-profile field.
+    pub new_profile_name: String,
+    /// Name and thread list typed into the header's new-favorite form.
+    pub new_favorite_name: String,
+    pub new_favorite_threads: String,
+    /// Footer tip on display and when it was last rotated.
+    pub current_tip_index: usize,
+    pub last_tip_change_time: Instant,
+    pub pending_app_stop: Option<PendingAppStop>,
+    pub pending_folder_scan: Option<PendingFolderScan>,
+    pub pending_folder_add: Option<PendingFolderAdd>,
+    pub pending_run_group: Option<PendingRunGroup>,
+    pub pending_group_priority: Option<PendingGroupPriority>,
+    pub pending_config_import: Option<PendingConfigImport>,
+    /// Whether the group editor is asking to confirm deleting the edited group.
+    pub confirm_group_delete: bool,
+    pub recently_deleted_group: Option<DeletedGroup>,
+    /// App rows highlighted by clicks on their names; bulk actions apply to them.
+    pub selected_rules: Vec<RuleId>,
+    /// Row a Shift click extends the selection from.
+    pub selection_anchor: Option<RuleId>,
 }
 
 impl UiSession {
@@ -23,21 +142,62 @@ impl UiSession {
                 core_selection: vec![false; total_threads],
                 group_name: String::new(),
                 run_all_enabled: false,
+                single_numa_node: false,
+                default_priority: PriorityClass::Normal,
+                core_selector: CoreSelector::Fixed,
+                exclude_names: String::new(),
+                use_job_object: false,
+                cpu_rate_percent: None,
+                color: None,
                 last_clicked_core: None,
+                keyboard_range_from: None,
+                active_profile: None,
+                save_to_profile: false,
+                base_cores: Vec::new(),
+                available_core_mask: usize::MAX,
+                reserved_cores: Vec::new(),
+                allow_reserved_cores: false,
+                affinity_probe_rx: None,
+                affinity_probe_result: None,
+                numa_nodes: Vec::new(),
+                core_usage: None,
             },
             app_edit_state: RuleEditorSession {
                 current_edit: None,
                 target: None,
                 shortcut_result: None,
+                resolved_args: None,
+                confirm_realtime: false,
             },
             dropped_files: None,
             file_drop_hover_target: None,
+            drop_group_picker_open: false,
             installed_app_picker: InstalledAppPickerSession::default(),
+            log_view: LogViewSession::default(),
+            process_list: ProcessListSession::default(),
+            cpu_topology: None,
+            preset_draft: UserPresetLayout::default(),
+            new_profile_name: String::new(),
+            new_favorite_name: String::new(),
+            new_favorite_threads: String::new(),
+            current_tip_index: 0,
+            last_tip_change_time: Instant::now(),
+            pending_app_stop: None,
+            pending_folder_scan: None,
+            pending_folder_add: None,
+            pending_run_group: None,
+            pending_group_priority: None,
+            pending_config_import: None,
+            confirm_group_delete: false,
+            recently_deleted_group: None,
+            selected_rules: Vec::new(),
+            selection_anchor: None,
         }
     }
 
     pub fn reset_group_form(&mut self) {
         self.group_form.reset();
+        self.confirm_group_delete = false;
     }
 
     pub fn set_current_window(&mut self, window: WindowRoute) {
@@ -61,6 +221,10 @@ mod tests {
         assert!(state.app_edit_state.current_edit.is_none());
         assert!(state.dropped_files.is_none());
         assert!(state.file_drop_hover_target.is_none());
+        assert!(!state.drop_group_picker_open);
+        assert!(state.pending_app_stop.is_none());
+        assert!(!state.confirm_group_delete);
+        assert!(state.recently_deleted_group.is_none());
     }
 
     #[test]
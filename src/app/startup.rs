@@ -1,9 +1,29 @@
 use crate::app::shared::ids::{GroupId, RuleId};
+use std::path::PathBuf;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StartupIntent {
     NormalGui,
-    RunRule { group_id: GroupId, rule_id: RuleId },
+    RunRule {
+        group_id: GroupId,
+        rule_id: RuleId,
+    },
+    RunGroup {
+        group_name: String,
+    },
+    RunApp {
+        group_name: String,
+        app_name: String,
+    },
+    /// Monitoring and reapply rules only, without a window, until Ctrl+C.
+    Service,
+}
+
+impl StartupIntent {
+    /// Name-based launches are meant for scripts and never open a window.
+    pub fn runs_headless(&self) -> bool {
+        matches!(self, Self::RunGroup { .. } | Self::RunApp { .. })
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -14,15 +34,59 @@ pub enum StartupIntentError {
     TooManyArguments,
     InvalidGroupId(String),
     InvalidRuleId(String),
+    MissingRunGroupName,
+    MissingRunAppTarget,
+    InvalidRunAppTarget(String),
+    InvalidName(String),
+    MissingStateDir,
 }
 
 const RUN_RULE_FLAG: &str = "--run-rule";
+const RUN_GROUP_FLAG: &str = "--run-group";
+const RUN_APP_FLAG: &str = "--run-app";
+const SERVICE_FLAG: &str = "--service";
+const VERBOSE_FLAG: &str = "--verbose";
+const STATE_DIR_FLAG: &str = "--state-dir";
 const MAX_ID_LEN: usize = 128;
+const MAX_NAME_LEN: usize = 256;
+
+/// Removes every `--verbose` from the arguments, returning whether there was one. It
+/// combines with any intent, so it is taken out before the intent is parsed.
+pub fn take_verbose_flag(args: &mut Vec<String>) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != VERBOSE_FLAG);
+    args.len() != before
+}
+
+/// Removes `--state-dir <dir>` or `--state-dir=<dir>` from the arguments, returning the
+/// directory. Like `--verbose` it combines with any intent.
+pub fn take_state_dir_flag(args: &mut Vec<String>) -> Result<Option<PathBuf>, StartupIntentError> {
+    let Some(index) = args
+        .iter()
+        .position(|arg| arg == STATE_DIR_FLAG || arg.starts_with("--state-dir="))
+    else {
+        return Ok(None);
+    };
+    let flag = args.remove(index);
+    let dir = match flag.strip_prefix("--state-dir=") {
+        Some(dir) => dir.to_string(),
+        None if index < args.len() => args.remove(index),
+        None => return Err(StartupIntentError::MissingStateDir),
+    };
+    if dir.trim().is_empty() {
+        return Err(StartupIntentError::MissingStateDir);
+    }
+    Ok(Some(PathBuf::from(dir)))
+}
 
 pub fn parse_startup_args(args: &[String]) -> Result<StartupIntent, StartupIntentError> {
     match args {
         [] => Ok(StartupIntent::NormalGui),
         [flag, rest @ ..] if flag == RUN_RULE_FLAG => parse_run_rule_args(rest),
+        [flag, rest @ ..] if flag == RUN_GROUP_FLAG => parse_run_group_args(rest),
+        [flag, rest @ ..] if flag == RUN_APP_FLAG => parse_run_app_args(rest),
+        [flag] if flag == SERVICE_FLAG => Ok(StartupIntent::Service),
+        [flag, ..] if flag == SERVICE_FLAG => Err(StartupIntentError::TooManyArguments),
         [arg, ..] => Err(StartupIntentError::UnknownArgument(arg.clone())),
     }
 }
@@ -51,6 +115,49 @@ fn parse_run_rule_args(args: &[String]) -> Result<StartupIntent, StartupIntentEr
     })
 }
 
+fn parse_run_group_args(args: &[String]) -> Result<StartupIntent, StartupIntentError> {
+    let group_name = args
+        .first()
+        .ok_or(StartupIntentError::MissingRunGroupName)?;
+    if args.len() > 1 {
+        return Err(StartupIntentError::TooManyArguments);
+    }
+
+    Ok(StartupIntent::RunGroup {
+        group_name: parse_cli_name(group_name)?,
+    })
+}
+
+fn parse_run_app_args(args: &[String]) -> Result<StartupIntent, StartupIntentError> {
+    let target = args
+        .first()
+        .ok_or(StartupIntentError::MissingRunAppTarget)?;
+    if args.len() > 1 {
+        return Err(StartupIntentError::TooManyArguments);
+    }
+    let (group_name, app_name) = target
+        .split_once('/')
+        .ok_or_else(|| StartupIntentError::InvalidRunAppTarget(target.clone()))?;
+
+    Ok(StartupIntent::RunApp {
+        group_name: parse_cli_name(group_name)?,
+        app_name: parse_cli_name(app_name)?,
+    })
+}
+
+fn parse_cli_name(value: &str) -> Result<String, StartupIntentError> {
+    let name = value.trim();
+    if is_cli_safe_name(name) {
+        Ok(name.to_string())
+    } else {
+        Err(StartupIntentError::InvalidName(value.to_string()))
+    }
+}
+
+pub(crate) fn is_cli_safe_name(value: &str) -> bool {
+    !value.trim().is_empty() && value.len() <= MAX_NAME_LEN && !value.chars().any(char::is_control)
+}
+
 pub(crate) fn is_cli_safe_id(value: &str) -> bool {
     !value.is_empty()
         && value.len() <= MAX_ID_LEN
@@ -147,4 +254,111 @@ mod tests {
             Err(StartupIntentError::InvalidRuleId(oversized_rule_id))
         );
     }
+
+    #[test]
+    fn test_run_group_and_run_app_parse_trimmed_names() {
+        assert_eq!(
+            parse_startup_args(&args(&["--run-group", " Game Group "])),
+            Ok(StartupIntent::RunGroup {
+                group_name: "Game Group".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_startup_args(&args(&["--run-app", "Game Group/Game Launcher"])),
+            Ok(StartupIntent::RunApp {
+                group_name: "Game Group".to_string(),
+                app_name: "Game Launcher".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_run_group_and_run_app_reject_missing_or_malformed_targets() {
+        assert_eq!(
+            parse_startup_args(&args(&["--run-group"])),
+            Err(StartupIntentError::MissingRunGroupName)
+        );
+        assert_eq!(
+            parse_startup_args(&args(&["--run-group", "Games", "extra"])),
+            Err(StartupIntentError::TooManyArguments)
+        );
+        assert_eq!(
+            parse_startup_args(&args(&["--run-app"])),
+            Err(StartupIntentError::MissingRunAppTarget)
+        );
+        assert_eq!(
+            parse_startup_args(&args(&["--run-app", "Games"])),
+            Err(StartupIntentError::InvalidRunAppTarget("Games".to_string()))
+        );
+        assert_eq!(
+            parse_startup_args(&args(&["--run-app", "Games/ "])),
+            Err(StartupIntentError::InvalidName(" ".to_string()))
+        );
+        assert_eq!(
+            parse_startup_args(&args(&["--run-group", "bad\nname"])),
+            Err(StartupIntentError::InvalidName("bad\nname".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_service_flag_takes_no_arguments() {
+        assert_eq!(
+            parse_startup_args(&args(&["--service"])),
+            Ok(StartupIntent::Service)
+        );
+        assert_eq!(
+            parse_startup_args(&args(&["--service", "extra"])),
+            Err(StartupIntentError::TooManyArguments)
+        );
+    }
+
+    #[test]
+    fn test_verbose_flag_is_taken_out_before_parsing() {
+        let mut startup_args = args(&["--verbose", "--run-group", "Games"]);
+        assert!(take_verbose_flag(&mut startup_args));
+        assert_eq!(startup_args, args(&["--run-group", "Games"]));
+        assert!(!take_verbose_flag(&mut startup_args));
+    }
+
+    #[test]
+    fn test_state_dir_flag_is_taken_out_before_parsing() {
+        let mut startup_args = args(&["--run-group", "Games", "--state-dir", "D:\\Portable"]);
+        assert_eq!(
+            take_state_dir_flag(&mut startup_args),
+            Ok(Some(PathBuf::from("D:\\Portable")))
+        );
+        assert_eq!(startup_args, args(&["--run-group", "Games"]));
+        assert_eq!(take_state_dir_flag(&mut startup_args), Ok(None));
+
+        let mut startup_args = args(&["--state-dir=portable"]);
+        assert_eq!(
+            take_state_dir_flag(&mut startup_args),
+            Ok(Some(PathBuf::from("portable")))
+        );
+        assert!(startup_args.is_empty());
+
+        assert_eq!(
+            take_state_dir_flag(&mut args(&["--state-dir"])),
+            Err(StartupIntentError::MissingStateDir)
+        );
+        assert_eq!(
+            take_state_dir_flag(&mut args(&["--state-dir="])),
+            Err(StartupIntentError::MissingStateDir)
+        );
+    }
+
+    #[test]
+    fn test_only_name_based_intents_run_headless() {
+        assert!(!StartupIntent::NormalGui.runs_headless());
+        assert!(!StartupIntent::Service.runs_headless());
+        assert!(!StartupIntent::RunRule {
+            group_id: GroupId("group-1".to_string()),
+            rule_id: RuleId("rule-1".to_string()),
+        }
+        .runs_headless());
+        assert!(StartupIntent::RunGroup {
+            group_name: "Games".to_string(),
+        }
+        .runs_headless());
+    }
 }
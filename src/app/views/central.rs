@@ -1,16 +1,26 @@
 use crate::app::features::execution::AppRowAction;
-use crate::app::models::{AppRuntimeKey, AppStatus};
+use crate::app::features::topology::GroupCoreOverlap;
+use crate::app::models::{format_clock_time, AffinityDrift, AppRuntimeKey, AppStatus};
 use crate::app::runtime::{AppState, CentralPanelSnapshot};
 use crate::app::shared::ids::{GroupId, RuleId};
+use crate::app::shell::presenters::run_settings::REALTIME_WARNING;
 use crate::app::shell::presenters::shared_elements::{
-    drag_grip, ghost_button, group_frame, inset_frame, inter_medium_family, inter_semibold_family,
-    palette, row_fill, success_color, toned_sized_button, warning_color, ToneRole,
-    BUTTON_FONT_SIZE,
+    danger_color, drag_grip, ghost_button, group_frame, inset_frame, inter_medium_family,
+    inter_semibold_family, palette, row_fill, success_color, toned_sized_button, warning_color,
+    ToneRole, BUTTON_FONT_SIZE,
 };
+use crate::app::shell::sessions::{PendingAppStop, RuleSelectMode};
 use eframe::egui::{self, Align, CentralPanel, Color32, Layout, RichText, ScrollArea, Vec2};
+use os_api::{PriorityClass, PriorityClassName};
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
 
 const ICON_EDIT: &str = "\u{2699}";
+const ICON_STOP: &str = "\u{23F9}";
+const ICON_AFFINITY_DRIFT: &str = "\u{26A0}";
+const PRIORITY_BADGE_WIDTH: f32 = 22.0;
+/// Rows of a disabled group are drawn faded; their running apps stay usable.
+const DISABLED_GROUP_ROW_OPACITY: f32 = 0.5;
 
 enum CentralAction {
     MoveGroupToIndex {
@@ -18,10 +28,20 @@ enum CentralAction {
         target_index: usize,
     },
     StartEditGroup(GroupId),
+    ApplyGroupToRunning(GroupId),
     ToggleGroupHidden {
         group_id: GroupId,
         is_hidden: bool,
     },
+    ToggleGroupCollapsed {
+        group_id: GroupId,
+        is_collapsed: bool,
+    },
+    SetAllGroupsCollapsed(bool),
+    SetGroupEnabled {
+        group_id: GroupId,
+        enabled: bool,
+    },
     AddSelectedFiles {
         group_id: GroupId,
         paths: Vec<PathBuf>,
@@ -31,20 +51,47 @@ enum CentralAction {
         group_id: GroupId,
         rule_id: RuleId,
     },
-    RunGroup(GroupId),
+    RunGroup {
+        group_id: GroupId,
+        force: bool,
+    },
     RunGroupProgramAction {
         group_id: GroupId,
         rule_id: RuleId,
         action: AppRowAction,
     },
+    RequestAppStop {
+        group_id: GroupId,
+        rule_id: RuleId,
+        app_name: String,
+    },
     MoveRuleToGroup {
         source_group_id: GroupId,
         rule_id: RuleId,
         target_group_id: GroupId,
         target_rule_index: usize,
     },
+    CloneRuleToGroup {
+        source_group_id: GroupId,
+        rule_id: RuleId,
+        target_group_id: GroupId,
+    },
+    SelectRule {
+        rule_id: RuleId,
+        mode: RuleSelectMode,
+    },
+    ClearRuleSelection,
+    RemoveSelectedRules,
+    SetSelectedRulesPriority(PriorityClassName),
+    RequestGroupPriority {
+        group_id: GroupId,
+        priority: PriorityClassName,
+    },
+    MoveSelectedRulesToGroup(GroupId),
+    RevealProgram(PathBuf),
     LogMessage(String),
     ConsumeDroppedFiles(GroupId),
+    PickDropGroup,
 }
 
 #[derive(Clone)]
@@ -96,6 +143,8 @@ pub fn draw_central_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
     let panel_fill = root_ui.visuals().panel_fill;
     let snapshot = app.build_central_panel_snapshot();
     let group_count = snapshot.groups.len();
+    let all_collapsed = group_count > 0 && snapshot.groups.iter().all(|group| group.is_collapsed);
+    let mut header_actions = Vec::new();
     CentralPanel::default()
         .frame(
             egui::Frame::NONE
@@ -113,6 +162,21 @@ pub fn draw_central_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
                     );
                 });
                 ui.with_layout(Layout::right_to_left(Align::BOTTOM), |ui| {
+                    let (label, hover) = if all_collapsed {
+                        ("Expand all", "Unfold every group")
+                    } else {
+                        ("Collapse all", "Fold every group to its header line")
+                    };
+                    if group_count > 0
+                        && ghost_button(
+                            ui,
+                            egui::Button::new(RichText::new(label).size(BUTTON_FONT_SIZE)),
+                        )
+                        .on_hover_text(hover)
+                        .clicked()
+                    {
+                        header_actions.push(CentralAction::SetAllGroupsCollapsed(!all_collapsed));
+                    }
                     ui.label(
                         RichText::new(format!("{group_count} groups · drag to reorder"))
                             .small()
@@ -120,7 +184,11 @@ pub fn draw_central_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
                     );
                 });
             });
+            execute_actions(app, std::mem::take(&mut header_actions));
             ui.add_space(3.0);
+            render_core_overlap_warning(app, ui, &snapshot.core_overlaps);
+            let bulk_actions = render_bulk_action_bar(app, ui, &snapshot);
+            execute_actions(app, bulk_actions);
             ScrollArea::vertical().show(ui, |ui| {
                 ui.vertical(|ui| {
                     let actions = render_groups(app, ui, &ctx, &snapshot);
@@ -130,6 +198,408 @@ pub fn draw_central_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
         });
     render_rule_drag_preview(&ctx);
     render_group_drag_preview(&ctx);
+    render_app_stop_confirmation(app, &ctx);
+    render_folder_add_confirmation(app, &ctx);
+    render_run_all_confirmation(app, &ctx);
+    render_group_priority_confirmation(app, &ctx);
+    render_drop_group_picker(app, &ctx);
+}
+
+/// Informational banner listing groups that share cores; overlap can be intentional.
+fn render_core_overlap_warning(
+    app: &mut AppState,
+    ui: &mut egui::Ui,
+    overlaps: &[GroupCoreOverlap],
+) {
+    if overlaps.is_empty() {
+        return;
+    }
+
+    inset_frame(ui).show(ui, |ui| {
+        ui.set_width(ui.available_width());
+        ui.horizontal(|ui| {
+            ui.colored_label(
+                warning_color(ui),
+                RichText::new("Groups share CPU threads").strong(),
+            );
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ghost_button(ui, egui::Button::new(RichText::new("Hide").small()))
+                    .on_hover_text(
+                        "Stop warning about overlapping groups; re-enable it from the footer",
+                    )
+                    .clicked()
+                {
+                    app.toggle_group_overlap_warning();
+                }
+            });
+        });
+        for overlap in overlaps {
+            ui.label(
+                RichText::new(core_overlap_line(overlap))
+                    .small()
+                    .color(palette(ui).text_secondary),
+            );
+        }
+    });
+    ui.add_space(3.0);
+}
+
+/// Priorities offered by the bulk menus, highest first.
+const PRIORITY_CHOICES: [(PriorityClassName, &str); 6] = [
+    (PriorityClassName::Realtime, "RealTime"),
+    (PriorityClassName::High, "High"),
+    (PriorityClassName::AboveNormal, "Above Normal"),
+    (PriorityClassName::Normal, "Normal"),
+    (PriorityClassName::BelowNormal, "Below Normal"),
+    (PriorityClassName::Idle, "Low"),
+];
+
+/// Actions for the app rows picked with Ctrl or Shift clicks.
+fn render_bulk_action_bar(
+    app: &AppState,
+    ui: &mut egui::Ui,
+    snapshot: &CentralPanelSnapshot,
+) -> Vec<CentralAction> {
+    let mut actions = Vec::new();
+    let selected = app.ui.selected_rules.len();
+    if selected < 2 {
+        return actions;
+    }
+
+    inset_frame(ui).show(ui, |ui| {
+        ui.set_width(ui.available_width());
+        ui.horizontal(|ui| {
+            ui.label(RichText::new(format!("{selected} apps selected")).strong());
+            ui.menu_button(RichText::new("Set priority").size(BUTTON_FONT_SIZE), |ui| {
+                // Realtime needs the warning first; it is offered per group and per app.
+                for (class, label) in PRIORITY_CHOICES
+                    .into_iter()
+                    .filter(|(class, _)| *class != PriorityClassName::Realtime)
+                {
+                    if ui.button(label).clicked() {
+                        actions.push(CentralAction::SetSelectedRulesPriority(class));
+                        ui.close();
+                    }
+                }
+            });
+            ui.menu_button(RichText::new("Move to").size(BUTTON_FONT_SIZE), |ui| {
+                for group in &snapshot.groups {
+                    if ui.button(&group.name).clicked() {
+                        actions.push(CentralAction::MoveSelectedRulesToGroup(
+                            group.group_id.clone(),
+                        ));
+                        ui.close();
+                    }
+                }
+            });
+            ui.menu_button(
+                RichText::new("Remove")
+                    .size(BUTTON_FONT_SIZE)
+                    .color(danger_color(ui)),
+                |ui| {
+                    if ui
+                        .button(
+                            RichText::new(format!("Remove {selected} apps"))
+                                .color(danger_color(ui)),
+                        )
+                        .clicked()
+                    {
+                        actions.push(CentralAction::RemoveSelectedRules);
+                        ui.close();
+                    }
+                },
+            );
+            ui.with_layout(Layout::right_to_left(Align::Center), |ui| {
+                if ghost_button(ui, egui::Button::new(RichText::new("Clear").small()))
+                    .on_hover_text("Deselect all apps")
+                    .clicked()
+                {
+                    actions.push(CentralAction::ClearRuleSelection);
+                }
+            });
+        });
+        ui.label(
+            RichText::new(
+                "Ctrl+click an app's name to add or drop it, Shift+click to select a range",
+            )
+            .small()
+            .weak(),
+        );
+    });
+    ui.add_space(3.0);
+    actions
+}
+
+fn core_overlap_line(overlap: &GroupCoreOverlap) -> String {
+    let cores = overlap
+        .cores
+        .iter()
+        .map(|core| core.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "\"{}\" and \"{}\" share threads {cores}",
+        overlap.first, overlap.second
+    )
+}
+
+fn render_app_stop_confirmation(app: &mut AppState, ctx: &egui::Context) {
+    let Some(app_name) = app
+        .ui
+        .pending_app_stop
+        .as_ref()
+        .map(|pending| pending.app_name.clone())
+    else {
+        return;
+    };
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let modal = egui::Modal::new(egui::Id::new("central-app-stop-confirmation")).show(ctx, |ui| {
+        ui.set_max_width(320.0);
+        ui.label(RichText::new(format!("Stop {app_name}?")).strong());
+        ui.label(
+            RichText::new(
+                "The app and its child processes are terminated immediately; unsaved work is lost.",
+            )
+            .small()
+            .weak(),
+        );
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            if toned_sized_button(
+                ui,
+                [72.0, 22.0],
+                egui::Button::new(RichText::new("Stop").size(BUTTON_FONT_SIZE)),
+                ToneRole::Warning,
+            )
+            .clicked()
+            {
+                confirmed = true;
+            }
+            if ghost_button(ui, egui::Button::new("Cancel")).clicked() {
+                cancelled = true;
+            }
+        });
+    });
+
+    if confirmed {
+        let _ = app.confirm_pending_app_stop();
+    } else if cancelled || modal.should_close() {
+        app.ui.pending_app_stop = None;
+    }
+}
+
+fn render_folder_add_confirmation(app: &mut AppState, ctx: &egui::Context) {
+    let Some((found, folder_count, truncated)) =
+        app.ui.pending_folder_add.as_ref().map(|pending| {
+            (
+                pending.found_in_folders,
+                pending.folder_count,
+                pending.truncated,
+            )
+        })
+    else {
+        return;
+    };
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let modal = egui::Modal::new(egui::Id::new("central-folder-add-confirmation")).show(ctx, |ui| {
+        ui.set_max_width(320.0);
+        ui.label(RichText::new(format!("Add {found} apps from the dropped folders?")).strong());
+        let detail = if truncated {
+            format!("Scanning {folder_count} folder(s) stopped early because it found too many files. Each file becomes its own app entry.")
+        } else {
+            format!("Found in {folder_count} folder(s). Each file becomes its own app entry.")
+        };
+        ui.label(RichText::new(detail).small().weak());
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            if toned_sized_button(
+                ui,
+                [72.0, 22.0],
+                egui::Button::new(RichText::new("Add all").size(BUTTON_FONT_SIZE)),
+                ToneRole::Primary,
+            )
+            .clicked()
+            {
+                confirmed = true;
+            }
+            if ghost_button(ui, egui::Button::new("Cancel")).clicked() {
+                cancelled = true;
+            }
+        });
+    });
+
+    if confirmed {
+        let _ = app.confirm_pending_folder_add();
+    } else if cancelled || modal.should_close() {
+        app.ui.pending_folder_add = None;
+    }
+}
+
+fn render_run_all_confirmation(app: &mut AppState, ctx: &egui::Context) {
+    let Some(pending) = app.ui.pending_run_group.as_mut() else {
+        return;
+    };
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let modal = egui::Modal::new(egui::Id::new("central-run-all-confirmation")).show(ctx, |ui| {
+        ui.set_max_width(320.0);
+        ui.label(
+            RichText::new(format!(
+                "Start {} apps from {}?",
+                pending.app_count, pending.group_name
+            ))
+            .strong(),
+        );
+        ui.label(
+            RichText::new(
+                "Launching many programs at once can make the system unresponsive for a while.",
+            )
+            .small()
+            .weak(),
+        );
+        ui.checkbox(
+            &mut pending.dont_ask_again,
+            RichText::new("Don't ask again").small(),
+        );
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            if toned_sized_button(
+                ui,
+                [72.0, 22.0],
+                egui::Button::new(RichText::new("Run all").size(BUTTON_FONT_SIZE)),
+                ToneRole::Primary,
+            )
+            .clicked()
+            {
+                confirmed = true;
+            }
+            if ghost_button(ui, egui::Button::new("Cancel")).clicked() {
+                cancelled = true;
+            }
+        });
+    });
+
+    if confirmed {
+        app.confirm_pending_run_group();
+    } else if cancelled || modal.should_close() {
+        app.ui.pending_run_group = None;
+    }
+}
+
+fn render_group_priority_confirmation(app: &mut AppState, ctx: &egui::Context) {
+    let Some(pending) = app.ui.pending_group_priority.as_ref() else {
+        return;
+    };
+    let priority_label = PRIORITY_CHOICES
+        .iter()
+        .find(|(class, _)| *class == pending.priority)
+        .map_or("Normal", |(_, label)| label);
+    let title = format!(
+        "Set {} apps in {} to {priority_label}?",
+        pending.app_count, pending.group_name
+    );
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let modal =
+        egui::Modal::new(egui::Id::new("central-group-priority-confirmation")).show(ctx, |ui| {
+            ui.set_max_width(320.0);
+            ui.label(RichText::new(title).strong());
+            ui.label(
+                RichText::new(
+                    "Each app's own priority is replaced; apps of the group that are running get it right away.",
+                )
+                .small()
+                .weak(),
+            );
+            let realtime = pending.priority == PriorityClassName::Realtime;
+            if realtime {
+                ui.label(RichText::new(REALTIME_WARNING).small().weak());
+            }
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if toned_sized_button(
+                    ui,
+                    [72.0, 22.0],
+                    egui::Button::new(RichText::new("Apply").size(BUTTON_FONT_SIZE)),
+                    if realtime {
+                        ToneRole::Warning
+                    } else {
+                        ToneRole::Primary
+                    },
+                )
+                .clicked()
+                {
+                    confirmed = true;
+                }
+                if ghost_button(ui, egui::Button::new("Cancel")).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirmed {
+        let _ = app.confirm_pending_group_priority();
+    } else if cancelled || modal.should_close() {
+        app.ui.pending_group_priority = None;
+    }
+}
+
+fn render_drop_group_picker(app: &mut AppState, ctx: &egui::Context) {
+    if !app.ui.drop_group_picker_open {
+        return;
+    }
+    let file_count = app.ui.dropped_files.as_ref().map_or(0, Vec::len);
+    let groups = app.group_choices();
+
+    let mut chosen = None;
+    let mut cancelled = false;
+    let modal = egui::Modal::new(egui::Id::new("central-drop-group-picker")).show(ctx, |ui| {
+        ui.set_max_width(320.0);
+        ui.label(
+            RichText::new(format!("Add {file_count} dropped file(s) to which group?")).strong(),
+        );
+        ui.label(
+            RichText::new("The files were dropped outside every group.")
+                .small()
+                .weak(),
+        );
+        ui.add_space(6.0);
+        if groups.is_empty() {
+            ui.label(
+                RichText::new("Create a group first.")
+                    .small()
+                    .weak()
+                    .italics(),
+            );
+        }
+        for (group_id, name) in &groups {
+            if ui
+                .add_sized(
+                    [ui.available_width(), 22.0],
+                    egui::Button::new(RichText::new(name).size(BUTTON_FONT_SIZE)),
+                )
+                .clicked()
+            {
+                chosen = Some(group_id.clone());
+            }
+        }
+        ui.add_space(6.0);
+        if ghost_button(ui, egui::Button::new("Discard files")).clicked() {
+            cancelled = true;
+        }
+    });
+
+    if let Some(group_id) = chosen {
+        consume_dropped_files(app, group_id);
+    } else if cancelled || modal.should_close() {
+        app.discard_dropped_files();
+    }
 }
 
 #[cfg(target_os = "windows")]
@@ -184,6 +654,63 @@ fn format_core_summary(cores: &[usize]) -> String {
     format!("{} threads · {visible}{suffix}", cores.len())
 }
 
+/// Formats a tracked runtime compactly, e.g. `42s`, `5m 07s`, or `2h 03m`.
+fn format_elapsed(elapsed: Duration) -> String {
+    let total_seconds = elapsed.as_secs();
+    let hours = total_seconds / 3600;
+    let minutes = (total_seconds % 3600) / 60;
+    let seconds = total_seconds % 60;
+    if hours > 0 {
+        format!("{hours}h {minutes:02}m")
+    } else if minutes > 0 {
+        format!("{minutes}m {seconds:02}s")
+    } else {
+        format!("{seconds}s")
+    }
+}
+
+/// Short badge text and intensity (0 = Idle .. 5 = Realtime) of a priority.
+fn priority_badge(priority: PriorityClass) -> (&'static str, u8) {
+    match priority.class_name() {
+        PriorityClassName::Idle => ("LOW", 0),
+        PriorityClassName::BelowNormal => ("BN", 1),
+        PriorityClassName::Normal => ("N", 2),
+        PriorityClassName::AboveNormal => ("AN", 3),
+        PriorityClassName::High => ("HI", 4),
+        PriorityClassName::Realtime => ("RT", 5),
+    }
+}
+
+/// Gray for Idle through red for Realtime.
+fn priority_badge_color(level: u8) -> Color32 {
+    const IDLE: [f32; 3] = [132.0, 141.0, 149.0];
+    const REALTIME: [f32; 3] = [220.0, 78.0, 68.0];
+    let t = f32::from(level.min(5)) / 5.0;
+    let channel = |i: usize| (IDLE[i] + (REALTIME[i] - IDLE[i]) * t).round() as u8;
+    Color32::from_rgb(channel(0), channel(1), channel(2))
+}
+
+fn priority_tooltip(priority: PriorityClass) -> String {
+    let mut tooltip = format!("Priority: {:?}", priority.class_name());
+    if priority.eco_qos() {
+        tooltip.push_str(" + Efficiency mode");
+    }
+    tooltip
+}
+
+/// Actual and intended masks of a process found on the wrong cores.
+fn affinity_drift_tooltip(drift: &AffinityDrift, monitoring_enabled: bool) -> String {
+    let remedy = if monitoring_enabled {
+        "Monitoring could not reapply it, e.g. because the process runs elevated"
+    } else {
+        "Monitoring is off; use Fix or turn monitoring on to reapply it"
+    };
+    format!(
+        "Affinity differs from the group (PID {})\nActual: {:#X}\nIntended: {:#X}\n{remedy}",
+        drift.pid, drift.actual_mask, drift.expected_mask
+    )
+}
+
 fn app_status_label(status: AppStatus) -> &'static str {
     match status {
         AppStatus::Running => "Running · protected",
@@ -233,6 +760,30 @@ fn app_action_presentation(status: AppStatus, focus_supported: bool) -> AppActio
     }
 }
 
+fn rule_select_mode(modifiers: egui::Modifiers) -> RuleSelectMode {
+    if modifiers.shift {
+        RuleSelectMode::Range
+    } else if modifiers.command {
+        RuleSelectMode::Toggle
+    } else {
+        RuleSelectMode::Only
+    }
+}
+
+/// Row action a double-click on the app's name triggers: only starting or focusing,
+/// never a fix, and nothing unless the setting is on and the group may run apps.
+fn double_click_action(
+    click_to_launch: bool,
+    group_enabled: bool,
+    action: Option<AppRowAction>,
+) -> Option<AppRowAction> {
+    match action {
+        Some(AppRowAction::Run) if click_to_launch && group_enabled => Some(AppRowAction::Run),
+        Some(AppRowAction::Focus) if click_to_launch => Some(AppRowAction::Focus),
+        _ => None,
+    }
+}
+
 fn platform_supports_app_focus() -> bool {
     cfg!(feature = "windows")
 }
@@ -278,6 +829,9 @@ fn render_groups(
     snapshot: &CentralPanelSnapshot,
 ) -> Vec<CentralAction> {
     let mut actions = Vec::new();
+    let click_to_launch = app.is_click_to_launch();
+    let cluster_names = app.cluster_names();
+    let selected_rules = app.ui.selected_rules.clone();
     let mut hovered_file_drop_group = None;
     let active_group_payload = egui::DragAndDrop::payload::<GroupDragPayload>(ctx);
     let group_drag_pos = if active_group_payload.is_some() {
@@ -306,11 +860,13 @@ fn render_groups(
     };
     let rule_pointer_pos = rule_drop_pos.or(rule_drag_pos);
     let has_hovered_files = ctx.input(|i| !i.raw.hovered_files.is_empty());
-    let has_dropped_files = app
-        .ui
-        .dropped_files
-        .as_ref()
-        .is_some_and(|files| !files.is_empty());
+    // While the picker is open the files wait for it, not for the pointer.
+    let has_dropped_files = !app.ui.drop_group_picker_open
+        && app
+            .ui
+            .dropped_files
+            .as_ref()
+            .is_some_and(|files| !files.is_empty());
     let file_drop_pos = ctx.input(|i| i.pointer.hover_pos().or_else(|| i.pointer.latest_pos()));
     let groups_len = snapshot.groups.len();
 
@@ -349,9 +905,11 @@ fn render_groups(
                     }
                 }
 
+                let [r, g, b] = group.color;
+                let accent = egui::Color32::from_rgb(r, g, b);
                 egui::Frame::NONE
-                    .fill(colors.inset)
-                    .stroke(egui::Stroke::new(1.0, colors.border_strong))
+                    .fill(accent.gamma_multiply(0.25))
+                    .stroke(egui::Stroke::new(1.5, accent))
                     .corner_radius(6.0)
                     .inner_margin(egui::Margin::symmetric(5, 3))
                     .show(ui, |ui| {
@@ -368,11 +926,16 @@ fn render_groups(
                             .family(inter_semibold_family())
                             .strong(),
                     );
-                    ui.label(
-                        RichText::new(format_core_summary(&group.cores))
-                            .size(9.0)
-                            .color(colors.text_muted),
-                    );
+                    let summary = if group.is_collapsed {
+                        format!(
+                            "{} · {} apps",
+                            format_core_summary(&group.cores),
+                            group.programs.len()
+                        )
+                    } else {
+                        format_core_summary(&group.cores)
+                    };
+                    ui.label(RichText::new(summary).size(9.0).color(colors.text_muted));
                 });
                 let title_response = ui
                     .interact(
@@ -381,7 +944,24 @@ fn render_groups(
                         egui::Sense::click_and_drag(),
                     )
                     .on_hover_cursor(egui::CursorIcon::Grab)
-                    .on_hover_text("Drag group to change its position");
+                    .on_hover_text(
+                        "Drag group to change its position; right-click for group actions",
+                    );
+                title_response.context_menu(|ui| {
+                    ui.add_enabled_ui(!group.programs.is_empty(), |ui| {
+                        ui.menu_button("Set priority for all apps", |ui| {
+                            for (class, label) in PRIORITY_CHOICES {
+                                if ui.button(label).clicked() {
+                                    actions.push(CentralAction::RequestGroupPriority {
+                                        group_id: group_id.clone(),
+                                        priority: class,
+                                    });
+                                    ui.close();
+                                }
+                            }
+                        });
+                    });
+                });
                 let group_drag_payload = GroupDragPayload {
                     group_id: group_id.clone(),
                     preview_label: group.name.clone(),
@@ -391,6 +971,23 @@ fn render_groups(
                 title_response.dnd_set_drag_payload(group_drag_payload);
 
                 ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                    let (fold_icon, fold_hover) = if group.is_collapsed {
+                        ("\u{25B8}", "Expand group")
+                    } else {
+                        ("\u{25BE}", "Collapse group to this line")
+                    };
+                    if ghost_button(
+                        ui,
+                        egui::Button::new(RichText::new(fold_icon).size(BUTTON_FONT_SIZE)),
+                    )
+                    .on_hover_text(fold_hover)
+                    .clicked()
+                    {
+                        actions.push(CentralAction::ToggleGroupCollapsed {
+                            group_id: group_id.clone(),
+                            is_collapsed: !group.is_collapsed,
+                        });
+                    }
                     if ghost_button(
                         ui,
                         egui::Button::new(
@@ -402,9 +999,38 @@ fn render_groups(
                     {
                         actions.push(CentralAction::StartEditGroup(group_id.clone()));
                     }
+                    let mut enabled = group.enabled;
+                    let enabled_response = ui
+                        .checkbox(&mut enabled, RichText::new("Enabled").size(BUTTON_FONT_SIZE));
+                    if let Some(progress) = group.launch_progress {
+                        ui.label(
+                            RichText::new(format!(
+                                "Launching {}/{}",
+                                progress.started, progress.total
+                            ))
+                            .size(BUTTON_FONT_SIZE)
+                            .color(colors.text_muted),
+                        )
+                        .on_hover_text("Queued apps start as their delays run out");
+                    }
+                    if enabled_response
+                        .on_hover_text(
+                            "A disabled group keeps its settings but skips autorun and does not launch its apps; apps already running stay monitored",
+                        )
+                        .changed()
+                    {
+                        actions.push(CentralAction::SetGroupEnabled {
+                            group_id: group_id.clone(),
+                            enabled,
+                        });
+                    }
                 });
             });
 
+            if group.is_collapsed {
+                return;
+            }
+
             ui.add_space(2.0);
             ui.separator();
             ui.add_space(2.0);
@@ -412,15 +1038,40 @@ fn render_groups(
             ui.horizontal(|ui| {
                 if group.run_all_button
                     && ui
-                        .button(
-                            RichText::new("\u{25B6} Run all")
-                                .size(BUTTON_FONT_SIZE)
-                                .strong(),
+                        .add_enabled(
+                            group.enabled && !group.programs.is_empty(),
+                            egui::Button::new(
+                                RichText::new("\u{25B6} Run all")
+                                    .size(BUTTON_FONT_SIZE)
+                                    .strong(),
+                            ),
+                        )
+                        .on_hover_text(
+                            "Launch the group's apps that are not running and reapply settings to the rest; hold Shift to launch every app, focusing the ones already open",
                         )
-                        .on_hover_text("Run all apps in group")
+                        .on_disabled_hover_text(if group.enabled {
+                            "Add an app to this group before running it"
+                        } else {
+                            "Enable the group to run its apps"
+                        })
                         .clicked()
                 {
-                    actions.push(CentralAction::RunGroup(group_id.clone()));
+                    actions.push(CentralAction::RunGroup {
+                        group_id: group_id.clone(),
+                        force: ui.input(|input| input.modifiers.shift),
+                    });
+                }
+
+                if ghost_button(
+                    ui,
+                    egui::Button::new(RichText::new("Apply to running").size(BUTTON_FONT_SIZE)),
+                )
+                .on_hover_text(
+                    "Give this group's threads and priorities to its apps that are already running, e.g. started outside the tool, and track them",
+                )
+                .clicked()
+                {
+                    actions.push(CentralAction::ApplyGroupToRunning(group_id.clone()));
                 }
 
                 if crate::app::adapters::discovery::supports_installed_app_picker()
@@ -482,11 +1133,19 @@ fn render_groups(
                 for (program_index, program) in group.programs.iter().enumerate() {
                     let app_status = app.get_app_status_sync(&program.app_key);
 
+                    let row_fill = if selected_rules.contains(&program.rule_id) {
+                        palette(ui).selected.fill
+                    } else {
+                        row_fill(ui)
+                    };
                     let row_response = egui::Frame::NONE
-                        .fill(row_fill(ui))
+                        .fill(row_fill)
                         .corner_radius(5.0)
                         .inner_margin(egui::Margin::symmetric(4, 1))
                         .show(ui, |ui| {
+                            if !group.enabled {
+                                ui.multiply_opacity(DISABLED_GROUP_ROW_OPACITY);
+                            }
                             ui.horizontal(|ui| {
                                 ui.spacing_mut().item_spacing.x = 4.0;
                                 let drag_response = ui
@@ -502,13 +1161,17 @@ fn render_groups(
 
                                 let (rect, status_response) =
                                     ui.allocate_exact_size(Vec2::splat(8.0), egui::Sense::hover());
+                                let launch_failure = (app_status == AppStatus::NotRunning)
+                                    .then(|| app.get_launch_failure(&program.app_key))
+                                    .flatten();
                                 let color = match app_status_tone(app_status) {
+                                    _ if launch_failure.is_some() => danger_color(ui),
                                     AppStatusTone::Neutral => palette(ui).neutral_status,
                                     AppStatusTone::Success => success_color(ui),
                                     AppStatusTone::Warning => warning_color(ui),
                                 };
                                 let full_status = app_status_label(app_status);
-                                let status_tooltip =
+                                let mut status_tooltip =
                                     app.get_running_app_pids(&program.app_key).map_or_else(
                                         || format!("Status: {full_status}"),
                                         |pids| {
@@ -517,9 +1180,37 @@ fn render_groups(
                                             )
                                         },
                                     );
+                                if let Some(failure) = &launch_failure {
+                                    status_tooltip
+                                        .push_str(&format!("\nLast launch failed: {failure}"));
+                                }
                                 status_response.on_hover_text(status_tooltip.clone());
                                 ui.painter().circle_filled(rect.center(), 3.5, color);
 
+                                if let Some(drift) = app.get_affinity_drift(&program.app_key) {
+                                    ui.label(
+                                        RichText::new(ICON_AFFINITY_DRIFT)
+                                            .size(10.0)
+                                            .color(warning_color(ui)),
+                                    )
+                                    .on_hover_text(affinity_drift_tooltip(
+                                        &drift,
+                                        app.is_process_monitoring_enabled(),
+                                    ));
+                                }
+
+                                let (badge_text, badge_level) = priority_badge(program.priority);
+                                ui.add_sized(
+                                    [PRIORITY_BADGE_WIDTH, 21.0],
+                                    egui::Label::new(
+                                        RichText::new(badge_text)
+                                            .size(9.0)
+                                            .family(inter_semibold_family())
+                                            .color(priority_badge_color(badge_level)),
+                                    ),
+                                )
+                                .on_hover_text(priority_tooltip(program.priority));
+
                                 let action_presentation = app_action_presentation(
                                     app_status,
                                     platform_supports_app_focus(),
@@ -531,12 +1222,15 @@ fn render_groups(
                                 };
                                 let action_width = 42.0;
                                 let settings_width = 23.0;
+                                let stop_width = 23.0;
                                 let status_width = (ui.available_width() * 0.28).clamp(88.0, 150.0);
                                 let name_width = (ui.available_width()
                                     - status_width
                                     - action_width
+                                    - stop_width
                                     - settings_width
-                                    - 12.0)
+                                    - PRIORITY_BADGE_WIDTH
+                                    - 20.0)
                                     .max(72.0);
                                 let name_response = ui.add_sized(
                                     [name_width, 21.0],
@@ -546,19 +1240,109 @@ fn render_groups(
                                             .family(inter_medium_family())
                                             .strong(),
                                     )
-                                    .truncate(),
+                                    .truncate()
+                                    .sense(egui::Sense::click()),
                                 );
-                                name_response.on_hover_text(program.launch_target_detail.clone());
+                                if name_response.clicked() {
+                                    let modifiers = ui.input(|input| input.modifiers);
+                                    actions.push(CentralAction::SelectRule {
+                                        rule_id: program.rule_id.clone(),
+                                        mode: rule_select_mode(modifiers),
+                                    });
+                                }
+                                if name_response.double_clicked() {
+                                    if let Some(row_action) = double_click_action(
+                                        click_to_launch,
+                                        group.enabled,
+                                        action_presentation.action,
+                                    ) {
+                                        actions.push(CentralAction::RunGroupProgramAction {
+                                            group_id: group_id.clone(),
+                                            rule_id: program.rule_id.clone(),
+                                            action: row_action,
+                                        });
+                                    }
+                                }
+                                name_response
+                                    .on_hover_text(program.launch_target_detail.clone())
+                                    .context_menu(|ui| {
+                                        ui.menu_button("Copy to group", |ui| {
+                                            let others = snapshot
+                                                .groups
+                                                .iter()
+                                                .filter(|other| other.group_id != group_id);
+                                            let mut any = false;
+                                            for other in others {
+                                                any = true;
+                                                if ui.button(&other.name).clicked() {
+                                                    actions.push(CentralAction::CloneRuleToGroup {
+                                                        source_group_id: group_id.clone(),
+                                                        rule_id: program.rule_id.clone(),
+                                                        target_group_id: other.group_id.clone(),
+                                                    });
+                                                    ui.close();
+                                                }
+                                            }
+                                            if !any {
+                                                ui.label(
+                                                    RichText::new("No other groups").small().weak(),
+                                                );
+                                            }
+                                        });
+                                        if app_status != AppStatus::NotRunning
+                                            && ui
+                                                .button("Release")
+                                                .on_hover_text(
+                                                    "Reset the app to all threads and Normal priority and stop tracking it; it keeps running",
+                                                )
+                                                .clicked()
+                                        {
+                                            actions.push(CentralAction::RunGroupProgramAction {
+                                                group_id: group_id.clone(),
+                                                rule_id: program.rule_id.clone(),
+                                                action: AppRowAction::Release,
+                                            });
+                                            ui.close();
+                                        }
+                                        if let Some(bin_path) = &program.bin_path {
+                                            if ui.button("Open file location").clicked() {
+                                                actions.push(CentralAction::RevealProgram(
+                                                    bin_path.clone(),
+                                                ));
+                                                ui.close();
+                                            }
+                                        }
+                                    });
+
+                                let started_at = (app_status != AppStatus::NotRunning)
+                                    .then(|| app.get_running_app_started_at(&program.app_key))
+                                    .flatten();
+                                let status_text = app_status_display_label(app_status, status_width);
+                                let (status_text, status_tooltip) = match started_at {
+                                    Some(started_at) => {
+                                        ctx.request_repaint_after(Duration::from_secs(1));
+                                        let elapsed = format_elapsed(
+                                            SystemTime::now()
+                                                .duration_since(started_at)
+                                                .unwrap_or_default(),
+                                        );
+                                        (
+                                            format!("{elapsed} · {status_text}"),
+                                            format!(
+                                                "{status_tooltip}\nStarted: {}\nRunning for {elapsed}",
+                                                format_clock_time(started_at)
+                                            ),
+                                        )
+                                    }
+                                    None => (status_text.to_string(), status_tooltip),
+                                };
 
                                 ui.add_sized(
                                     [status_width, 21.0],
                                     egui::Label::new(
-                                        RichText::new(app_status_display_label(
-                                            app_status,
-                                            status_width,
-                                        ))
-                                        .size(10.0)
-                                        .color(status_color),
+                                        RichText::new(status_text)
+                                            .size(10.0)
+                                            .color(status_color),
                                     )
                                     .truncate(),
                                 )
@@ -575,11 +1359,22 @@ fn render_groups(
                                 let button = egui::Button::new(
                                     RichText::new(action_presentation.label).size(BUTTON_FONT_SIZE),
                                 );
-                                let action_response = if action_presentation.action.is_none() {
-                                    ui.add_enabled(
+                                let run_blocked = !group.enabled
+                                    && action_presentation.action == Some(AppRowAction::Run);
+                                let action_response = if action_presentation.action.is_none()
+                                    || run_blocked
+                                {
+                                    let response = ui.add_enabled(
                                         false,
                                         button.min_size(egui::vec2(action_width, 21.0)),
-                                    )
+                                    );
+                                    if run_blocked {
+                                        response.on_disabled_hover_text(
+                                            "Enable the group to run this app",
+                                        )
+                                    } else {
+                                        response
+                                    }
                                 } else if app_status == AppStatus::SettingsMismatch {
                                     toned_sized_button(
                                         ui,
@@ -600,6 +1395,57 @@ fn render_groups(
                                         });
                                     }
                                 }
+                                if action_presentation.action == Some(AppRowAction::Run)
+                                    && !run_blocked
+                                    && !cluster_names.is_empty()
+                                {
+                                    let action_response = action_response
+                                        .on_hover_text("Right-click to run on a single CPU cluster");
+                                    action_response.context_menu(|ui| {
+                                        let mut picked = None;
+                                        if ui.button("Run on this group").clicked() {
+                                            picked = Some(AppRowAction::Run);
+                                        }
+                                        for (cluster, name) in cluster_names.iter().enumerate() {
+                                            if ui
+                                                .button(format!("Run on {name}"))
+                                                .on_hover_text(
+                                                    "Use all of this cluster's threads for this launch; the group is not changed",
+                                                )
+                                                .clicked()
+                                            {
+                                                picked = Some(AppRowAction::RunOnCluster(cluster));
+                                            }
+                                        }
+                                        if let Some(row_action) = picked {
+                                            actions.push(CentralAction::RunGroupProgramAction {
+                                                group_id: group_id.clone(),
+                                                rule_id: program.rule_id.clone(),
+                                                action: row_action,
+                                            });
+                                            ui.close();
+                                        }
+                                    });
+                                }
+
+                                if app_status == AppStatus::NotRunning {
+                                    ui.add_space(stop_width);
+                                } else if ghost_button(
+                                    ui,
+                                    egui::Button::new(
+                                        RichText::new(ICON_STOP).size(BUTTON_FONT_SIZE),
+                                    )
+                                    .min_size(egui::vec2(stop_width, 21.0)),
+                                )
+                                .on_hover_text("Stop the app and its child processes")
+                                .clicked()
+                                {
+                                    actions.push(CentralAction::RequestAppStop {
+                                        group_id: group_id.clone(),
+                                        rule_id: program.rule_id.clone(),
+                                        app_name: program.name.clone(),
+                                    });
+                                }
 
                                 if ghost_button(
                                     ui,
@@ -776,14 +1622,17 @@ fn render_groups(
         }
     }
 
-    if let Some(group_id) = resolve_file_drop_target(
+    match resolve_file_drop_target(
         &mut app.ui.file_drop_hover_target,
         has_hovered_files,
         has_dropped_files,
         file_drop_pos.is_some(),
         hovered_file_drop_group,
     ) {
-        actions.push(CentralAction::ConsumeDroppedFiles(group_id));
+        Some(group_id) => actions.push(CentralAction::ConsumeDroppedFiles(group_id)),
+        // Dropped outside every group: ask instead of losing them.
+        None if has_dropped_files => actions.push(CentralAction::PickDropGroup),
+        None => {}
     }
 
     actions
@@ -979,12 +1828,27 @@ fn execute_actions(app: &mut AppState, actions: Vec<CentralAction>) {
             CentralAction::StartEditGroup(group_id) => {
                 app.start_editing_group(group_id);
             }
+            CentralAction::ApplyGroupToRunning(group_id) => {
+                app.apply_group_to_running(group_id);
+            }
             CentralAction::ToggleGroupHidden {
                 group_id,
                 is_hidden,
             } => {
                 app.set_group_is_hidden(group_id, is_hidden);
             }
+            CentralAction::ToggleGroupCollapsed {
+                group_id,
+                is_collapsed,
+            } => {
+                app.set_group_is_collapsed(group_id, is_collapsed);
+            }
+            CentralAction::SetAllGroupsCollapsed(is_collapsed) => {
+                app.set_all_groups_collapsed(is_collapsed);
+            }
+            CentralAction::SetGroupEnabled { group_id, enabled } => {
+                app.set_group_enabled(group_id, enabled);
+            }
             CentralAction::AddSelectedFiles { group_id, paths } => {
                 app.add_selected_files_to_group(group_id, paths);
             }
@@ -994,8 +1858,8 @@ fn execute_actions(app: &mut AppState, actions: Vec<CentralAction>) {
             CentralAction::OpenAppRunSettings { group_id, rule_id } => {
                 app.open_app_run_settings(group_id, rule_id);
             }
-            CentralAction::RunGroup(group_id) => {
-                app.run_group(group_id);
+            CentralAction::RunGroup { group_id, force } => {
+                app.request_run_group(group_id, force);
             }
             CentralAction::RunGroupProgramAction {
                 group_id,
@@ -1004,6 +1868,17 @@ fn execute_actions(app: &mut AppState, actions: Vec<CentralAction>) {
             } => {
                 app.run_group_program_action(group_id, rule_id, action);
             }
+            CentralAction::RequestAppStop {
+                group_id,
+                rule_id,
+                app_name,
+            } => {
+                app.ui.pending_app_stop = Some(PendingAppStop {
+                    group_id,
+                    rule_id,
+                    app_name,
+                });
+            }
             CentralAction::MoveRuleToGroup {
                 source_group_id,
                 rule_id,
@@ -1017,16 +1892,45 @@ fn execute_actions(app: &mut AppState, actions: Vec<CentralAction>) {
                     target_rule_index,
                 );
             }
+            CentralAction::CloneRuleToGroup {
+                source_group_id,
+                rule_id,
+                target_group_id,
+            } => {
+                let _ = app.clone_rule_to_group(source_group_id, rule_id, target_group_id);
+            }
+            CentralAction::SelectRule { rule_id, mode } => app.select_rule(rule_id, mode),
+            CentralAction::ClearRuleSelection => app.clear_rule_selection(),
+            CentralAction::RemoveSelectedRules => {
+                let _ = app.remove_selected_rules();
+            }
+            CentralAction::SetSelectedRulesPriority(priority) => {
+                let _ = app.set_selected_rules_priority(priority);
+            }
+            CentralAction::RequestGroupPriority { group_id, priority } => {
+                app.request_group_priority(group_id, priority);
+            }
+            CentralAction::MoveSelectedRulesToGroup(group_id) => {
+                let _ = app.move_selected_rules_to_group(group_id);
+            }
+            CentralAction::RevealProgram(bin_path) => {
+                app.reveal_program_location(&bin_path);
+            }
             CentralAction::LogMessage(message) => {
                 app.log_manager.add_entry(message);
             }
-            CentralAction::ConsumeDroppedFiles(group_id) => {
-                let _ = app.consume_dropped_files_into_group(group_id);
-            }
+            CentralAction::ConsumeDroppedFiles(group_id) => consume_dropped_files(app, group_id),
+            CentralAction::PickDropGroup => app.open_drop_group_picker(),
         }
     }
 }
 
+/// Adds the pending dropped files to the group. Added and skipped targets are logged
+/// while adding, and so are files left unused because the group vanished meanwhile.
+fn consume_dropped_files(app: &mut AppState, group_id: GroupId) {
+    app.consume_dropped_files_into_group(group_id);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1050,7 +1954,9 @@ mod tests {
             rule_id: RuleId(rule_id.to_string()),
             name: name.to_string(),
             launch_target_detail: name.to_string(),
+            bin_path: None,
             app_key,
+            priority: PriorityClass::Normal,
         }
     }
 
@@ -1064,7 +1970,11 @@ mod tests {
             name: name.to_string(),
             cores: vec![0],
             is_hidden: false,
+            is_collapsed: false,
+            enabled: true,
+            color: [0, 0, 0],
             run_all_button: true,
+            launch_progress: None,
             programs,
         }
     }
@@ -1079,6 +1989,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_double_click_only_runs_or_focuses_when_enabled() {
+        assert_eq!(
+            double_click_action(true, true, Some(AppRowAction::Run)),
+            Some(AppRowAction::Run)
+        );
+        assert_eq!(
+            double_click_action(true, false, Some(AppRowAction::Focus)),
+            Some(AppRowAction::Focus)
+        );
+        assert_eq!(
+            double_click_action(true, false, Some(AppRowAction::Run)),
+            None
+        );
+        assert_eq!(
+            double_click_action(true, true, Some(AppRowAction::Fix)),
+            None
+        );
+        assert_eq!(
+            double_click_action(false, true, Some(AppRowAction::Run)),
+            None
+        );
+    }
+
     #[test]
     fn test_file_drop_target_uses_current_hovered_group() {
         let mut cached = Some(GroupId("old-group".to_string()));
@@ -1167,6 +2101,7 @@ mod tests {
                     vec![program("rule-b", "Other", other_key)],
                 ),
             ],
+            core_overlaps: Vec::new(),
         };
 
         assert_eq!(
@@ -1196,6 +2131,7 @@ mod tests {
                     vec![program("rule-b", "Sample Copy", sample_key.clone())],
                 ),
             ],
+            core_overlaps: Vec::new(),
         };
 
         assert_eq!(
@@ -1222,6 +2158,7 @@ mod tests {
                     program("rule-b", "Other", other_key),
                 ],
             )],
+            core_overlaps: Vec::new(),
         };
 
         assert_eq!(
@@ -1244,6 +2181,7 @@ mod tests {
                 "Games",
                 vec![program("rule-a", "Sample", sample_key.clone())],
             )],
+            core_overlaps: Vec::new(),
         };
 
         assert_eq!(
@@ -1276,6 +2214,7 @@ mod tests {
                 "Games",
                 vec![program("rule-a", "Sample", sample_key)],
             )],
+            core_overlaps: Vec::new(),
         };
 
         assert_eq!(
@@ -1299,6 +2238,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_priority_badges_run_from_gray_idle_to_red_realtime() {
+        assert_eq!(priority_badge(PriorityClass::Idle), ("LOW", 0));
+        assert_eq!(priority_badge(PriorityClass::High), ("HI", 4));
+        assert_eq!(
+            priority_badge(PriorityClass::from_parts(PriorityClassName::Realtime, true)),
+            ("RT", 5)
+        );
+        assert_eq!(priority_badge_color(0), Color32::from_rgb(132, 141, 149));
+        assert_eq!(priority_badge_color(5), Color32::from_rgb(220, 78, 68));
+        assert!(priority_badge_color(4).r() > priority_badge_color(2).r());
+        assert_eq!(
+            priority_tooltip(PriorityClass::from_parts(PriorityClassName::Idle, true)),
+            "Priority: Idle + Efficiency mode"
+        );
+    }
+
+    #[test]
+    fn test_core_overlap_line_names_both_groups_and_all_shared_threads() {
+        let overlap = GroupCoreOverlap {
+            first: "Games".into(),
+            second: "Stream".into(),
+            cores: vec![2, 4, 6, 8, 10, 12, 14, 16, 18],
+        };
+        assert_eq!(
+            core_overlap_line(&overlap),
+            "\"Games\" and \"Stream\" share threads 2, 4, 6, 8, 10, 12, 14, 16, 18"
+        );
+    }
+
+    #[test]
+    fn test_format_elapsed_scales_units_with_runtime() {
+        assert_eq!(format_elapsed(Duration::from_secs(42)), "42s");
+        assert_eq!(format_elapsed(Duration::from_secs(307)), "5m 07s");
+        assert_eq!(format_elapsed(Duration::from_secs(7380)), "2h 03m");
+    }
+
+    #[test]
+    fn test_affinity_drift_tooltip_shows_actual_and_intended_masks() {
+        let drift = AffinityDrift {
+            pid: 4321,
+            actual_mask: 0xFFFF,
+            expected_mask: 0xF0,
+        };
+        assert_eq!(
+            affinity_drift_tooltip(&drift, false),
+            "Affinity differs from the group (PID 4321)\nActual: 0xFFFF\nIntended: 0xF0\n\
+             Monitoring is off; use Fix or turn monitoring on to reapply it"
+        );
+        assert!(affinity_drift_tooltip(&drift, true).ends_with("runs elevated"));
+    }
+
     #[test]
     fn test_app_status_label_is_explicit() {
         assert_eq!(app_status_label(AppStatus::Running), "Running · protected");
@@ -0,0 +1,240 @@
+use crate::app::features::diagnostics::cpu_topology::CpuTopologyReport;
+use crate::app::models::cpu_presets::{UserPresetLayout, USER_PRESETS_FILE_NAME};
+use crate::app::models::CoreType;
+use crate::app::runtime::AppState;
+use crate::app::shell::presenters::shared_elements::{glass_frame, BUTTON_FONT_SIZE};
+use eframe::egui::{self, CentralPanel, RichText, ScrollArea};
+
+fn core_type_label(core_type: CoreType) -> &'static str {
+    match core_type {
+        CoreType::Performance => "Performance",
+        CoreType::Efficient => "Efficient",
+        CoreType::HyperThreading => "Hyper-thread",
+        CoreType::Other => "Other",
+    }
+}
+
+/// Form for describing the CPU's layout; returns whether saving was requested.
+fn draw_preset_draft(
+    ui: &mut egui::Ui,
+    draft: &mut UserPresetLayout,
+    detected_threads: usize,
+) -> bool {
+    let mut save = false;
+    egui::Grid::new("cpu_preset_draft")
+        .num_columns(2)
+        .show(ui, |ui| {
+            ui.label("Preset name");
+            ui.text_edit_singleline(&mut draft.name);
+            ui.end_row();
+            ui.label("Performance cores");
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(&mut draft.performance_cores).range(0..=256));
+                ui.checkbox(&mut draft.performance_ht, "Two threads each (HT/SMT)");
+            });
+            ui.end_row();
+            ui.label("Efficient cores");
+            ui.add_enabled(
+                draft.ccds == 0,
+                egui::DragValue::new(&mut draft.efficient_cores).range(0..=256),
+            );
+            ui.end_row();
+            ui.label("CCDs");
+            ui.add(egui::DragValue::new(&mut draft.ccds).range(0..=16))
+                .on_hover_text(
+                    "Chiplets the performance cores are split across; 0 for a single die",
+                );
+            ui.end_row();
+        });
+    if draft.ccds > 0 {
+        draft.efficient_cores = 0;
+    }
+
+    let threads = draft.total_threads();
+    let threads_note = format!("{threads} threads described, {detected_threads} detected");
+    if threads == detected_threads {
+        ui.label(RichText::new(threads_note).small().weak());
+    } else {
+        ui.colored_label(
+            ui.visuals().warn_fg_color,
+            RichText::new(format!(
+                "{threads_note}; the preset will not match until they agree"
+            ))
+            .small(),
+        );
+    }
+
+    let json = draft.to_json();
+    let mut preview = match &json {
+        Ok(json) => json.clone(),
+        Err(err) => err.clone(),
+    };
+    ui.add(
+        egui::TextEdit::multiline(&mut preview)
+            .code_editor()
+            .interactive(false)
+            .desired_rows(6)
+            .desired_width(f32::INFINITY),
+    );
+    ui.horizontal(|ui| {
+        let valid = json.is_ok();
+        if ui
+            .add_enabled(valid, egui::Button::new(RichText::new("Save preset").size(BUTTON_FONT_SIZE)))
+            .on_hover_text(format!(
+                "Add this layout to {USER_PRESETS_FILE_NAME} beside the state file; it takes precedence over built-in presets"
+            ))
+            .clicked()
+        {
+            save = true;
+        }
+        if ui
+            .add_enabled(valid, egui::Button::new(RichText::new("Copy JSON").size(BUTTON_FONT_SIZE)))
+            .on_hover_text("Copy the preset entry, e.g. to propose it for cpu_presets.json")
+            .clicked()
+        {
+            ui.ctx().copy_text(preview.clone());
+        }
+    });
+    save
+}
+
+/// View of the detected CPU model and the preset layout groups are built on, with a
+/// form for adding a preset when none matched.
+pub fn draw_cpu_topology_window(app: &mut AppState, root_ui: &mut egui::Ui) {
+    let report = app
+        .ui
+        .cpu_topology
+        .get_or_insert_with(CpuTopologyReport::detect)
+        .clone();
+    let draft = &mut app.ui.preset_draft;
+    if draft.model.is_empty() {
+        *draft = UserPresetLayout {
+            name: report.model.clone(),
+            model: report.model.clone(),
+            performance_cores: report.total_threads,
+            ..UserPresetLayout::default()
+        };
+    }
+    let mut save_preset = false;
+    let mut refresh = false;
+
+    CentralPanel::default()
+        .frame(
+            egui::Frame::NONE
+                .fill(root_ui.visuals().panel_fill)
+                .inner_margin(egui::Margin::symmetric(6, 4)),
+        )
+        .show(root_ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.heading(RichText::new("CPU topology").strong());
+                    ui.label(
+                        RichText::new("How the tool sees your processor's cores")
+                            .small()
+                            .weak(),
+                    );
+                });
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    if ui
+                        .button(RichText::new("Copy details").size(BUTTON_FONT_SIZE))
+                        .on_hover_text("Copy this layout as text, e.g. for a preset request")
+                        .clicked()
+                    {
+                        ui.ctx().copy_text(report.summary());
+                    }
+                    if ui
+                        .button(RichText::new("Refresh CPU").size(BUTTON_FONT_SIZE))
+                        .on_hover_text(
+                            "Re-detect the CPU threads and layout, e.g. after a power mode parked or woke cores",
+                        )
+                        .clicked()
+                    {
+                        refresh = true;
+                    }
+                });
+            });
+
+            ui.add_space(5.0);
+
+            glass_frame(ui).show(ui, |ui| {
+                ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        egui::Grid::new("cpu_topology_summary")
+                            .num_columns(2)
+                            .show(ui, |ui| {
+                                ui.label(RichText::new("Model").strong());
+                                ui.label(&report.model);
+                                ui.end_row();
+                                ui.label(RichText::new("Logical threads").strong());
+                                ui.label(report.total_threads.to_string());
+                                ui.end_row();
+                            });
+
+                        ui.add_space(6.0);
+                        egui::CollapsingHeader::new(RichText::new("Describe this CPU").strong())
+                            .default_open(report.schema.is_none())
+                            .show(ui, |ui| {
+                                save_preset = draw_preset_draft(
+                                    ui,
+                                    &mut app.ui.preset_draft,
+                                    report.total_threads,
+                                );
+                            });
+
+                        let Some(schema) = &report.schema else {
+                            ui.add_space(6.0);
+                            ui.label(
+                                RichText::new(
+                                    "No preset matched this CPU, so groups show plain thread numbers. \
+                                     Describe the layout above, or include the model and thread count when asking for a preset.",
+                                )
+                                .small()
+                                .weak()
+                                .italics(),
+                            );
+                            return;
+                        };
+
+                        for (cluster_index, cluster) in schema.clusters.iter().enumerate() {
+                            ui.add_space(8.0);
+                            ui.label(RichText::new(&cluster.name).strong());
+                            egui::Grid::new(("cpu_topology_cluster", cluster_index))
+                                .striped(true)
+                                .num_columns(3)
+                                .show(ui, |ui| {
+                                    for heading in ["Thread", "Label", "Type"] {
+                                        ui.label(RichText::new(heading).small().strong());
+                                    }
+                                    ui.end_row();
+                                    for core in &cluster.cores {
+                                        ui.label(core.index.to_string());
+                                        ui.label(&core.label);
+                                        ui.label(core_type_label(core.core_type));
+                                        ui.end_row();
+                                    }
+                                });
+                        }
+
+                        let unlisted = report.unlisted_threads();
+                        if !unlisted.is_empty() {
+                            ui.add_space(6.0);
+                            ui.label(
+                                RichText::new(format!(
+                                    "Threads {unlisted:?} are not part of the matched preset"
+                                ))
+                                .small()
+                                .weak(),
+                            );
+                        }
+                    });
+            });
+        });
+
+    if save_preset {
+        app.save_user_cpu_preset();
+    }
+    if refresh {
+        app.refresh_cpu_topology();
+    }
+}
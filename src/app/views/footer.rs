@@ -1,9 +1,28 @@
-use crate::app::models::APP_VERSION;
+use crate::app::features::diagnostics::audit::AUDIT_FILE_NAME;
+use crate::app::features::preferences::{
+    DEFAULT_LAUNCH_CONFIRM_SECS, DEFAULT_RUN_ALL_CONFIRM_THRESHOLD, MAX_LAUNCH_CONFIRM_SECS,
+    MAX_RUN_ALL_CONFIRM_THRESHOLD,
+};
+use crate::app::models::{SelfAffinity, APP_VERSION};
 use crate::app::runtime::AppState;
 use crate::app::shell::presenters::shared_elements::{
-    inter_medium_family, palette, success_color, UiPalette, BUTTON_FONT_SIZE,
+    ghost_button, inter_medium_family, palette, success_color, UiPalette, BUTTON_FONT_SIZE,
 };
+use crate::app::shell::UiSession;
 use eframe::egui::{self, Layout, Margin, Panel, RichText, Stroke, Vec2};
+use std::time::{Duration, Instant};
+
+/// How long each tip stays before the next one is shown.
+const TIP_INTERVAL: Duration = Duration::from_secs(12);
+
+const TIPS: &[&str] = &[
+    "Drop an .exe, shortcut or folder onto a group to add its programs; drop elsewhere to pick the group",
+    "While monitoring is on, apps that reset their affinity or priority are moved back (Ctrl+M pauses it)",
+    "Groups built from a detected CPU preset name their threads by cluster, e.g. P-cores, E-cores or a CCD; see Topology",
+    "Hold Shift on Run all to launch every app of a group, focusing the ones already open",
+    "Right-click a group's title to give all of its apps one priority",
+    "Ctrl+click or Shift+click app names to change priority, move or remove several apps at once",
+];
 
 fn footer_frame(colors: &UiPalette) -> egui::Frame {
     egui::Frame::NONE
@@ -12,6 +31,54 @@ fn footer_frame(colors: &UiPalette) -> egui::Frame {
         .inner_margin(Margin::symmetric(8, 4))
 }
 
+/// Moves on to the next tip once `TIP_INTERVAL` has passed and returns the tip to show.
+fn rotate_tip(session: &mut UiSession, now: Instant) -> &'static str {
+    if now.saturating_duration_since(session.last_tip_change_time) >= TIP_INTERVAL {
+        session.current_tip_index = (session.current_tip_index + 1) % TIPS.len();
+        session.last_tip_change_time = now;
+    }
+    TIPS[session.current_tip_index % TIPS.len()]
+}
+
+/// Rotating usage hint above the status row, until tips are switched off.
+fn tips_banner(app: &mut AppState, ui: &mut egui::Ui, colors: &UiPalette) {
+    if app.are_tips_hidden() {
+        return;
+    }
+    let tip = rotate_tip(&mut app.ui, Instant::now());
+    ui.ctx().request_repaint_after(Duration::from_secs(1));
+    ui.horizontal(|ui| {
+        ui.label(
+            RichText::new("Tip")
+                .size(8.5)
+                .family(inter_medium_family())
+                .color(colors.accent.fg),
+        );
+        ui.label(RichText::new(tip).size(8.5).color(colors.text_secondary));
+        ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+            if ghost_button(ui, egui::Button::new(RichText::new("Hide tips").size(8.5)))
+                .on_hover_text("Stop showing tips; the Show tips checkbox brings them back")
+                .clicked()
+            {
+                app.toggle_hide_tips();
+            }
+            if ghost_button(ui, egui::Button::new(RichText::new("Next").size(8.5))).clicked() {
+                app.ui.current_tip_index = (app.ui.current_tip_index + 1) % TIPS.len();
+                app.ui.last_tip_change_time = Instant::now();
+            }
+        });
+    });
+    ui.separator();
+}
+
+fn self_affinity_label(value: SelfAffinity) -> &'static str {
+    match value {
+        SelfAffinity::Off => "Tool on all threads",
+        SelfAffinity::OutsideGroups => "Tool outside groups",
+        SelfAffinity::Core(_) => "Tool on one thread",
+    }
+}
+
 /// Draws the bottom panel (footer) of the application.
 ///
 /// This panel contains:
@@ -27,6 +94,7 @@ pub fn draw_bottom_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
     Panel::bottom("bottom_panel")
         .frame(footer_frame(&colors))
         .show(root_ui, |ui| {
+        tips_banner(app, ui, &colors);
         let monitoring_enabled = app.is_process_monitoring_enabled();
         ui.horizontal(|ui| {
                     let (label, detail, color) = if monitoring_enabled {
@@ -72,12 +140,208 @@ pub fn draw_bottom_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
                         if ui
                             .button(RichText::new(action_label).size(BUTTON_FONT_SIZE))
                             .on_hover_text(
-                                "Keeps tracked app processes on their assigned CPU cores and restores priority",
+                                "Keeps tracked app processes on their assigned CPU cores and restores priority (Ctrl+M)",
                             )
                             .clicked()
                         {
                             app.toggle_process_monitoring();
                         }
+                        let mut reset_on_restart = app.is_runtime_reset_on_restart();
+                        if ui
+                            .checkbox(
+                                &mut reset_on_restart,
+                                RichText::new("Reset runtime on restart").size(8.5),
+                            )
+                            .on_hover_text(
+                                "When every tracked process exits and new ones take over, start the runtime clock again instead of keeping the earliest start",
+                            )
+                            .changed()
+                        {
+                            app.toggle_runtime_reset_on_restart();
+                        }
+                        let mut overlap_warning = !app.is_group_overlap_warning_hidden();
+                        if ui
+                            .checkbox(
+                                &mut overlap_warning,
+                                RichText::new("Warn on shared threads").size(8.5),
+                            )
+                            .on_hover_text(
+                                "Show a banner when two groups use the same CPU threads",
+                            )
+                            .changed()
+                        {
+                            app.toggle_group_overlap_warning();
+                        }
+                        let mut separate_apps = app.is_separate_apps_per_group();
+                        if ui
+                            .checkbox(
+                                &mut separate_apps,
+                                RichText::new("Separate apps per group").size(8.5),
+                            )
+                            .on_hover_text(
+                                "Launch and track the same app in different groups as separate instances instead of one shared entry",
+                            )
+                            .changed()
+                        {
+                            app.toggle_separate_apps_per_group();
+                        }
+                        let mut confirm_secs = app.launch_confirm_secs();
+                        if confirm_secs > 0 {
+                            if crate::app::adapters::os::supports_window_lookup() {
+                                let mut wait_for_window = app.is_launch_confirm_window();
+                                if ui
+                                    .checkbox(
+                                        &mut wait_for_window,
+                                        RichText::new("Wait for window").size(8.5),
+                                    )
+                                    .on_hover_text(
+                                        "Only confirm a launch once the program shows a window",
+                                    )
+                                    .changed()
+                                {
+                                    app.toggle_launch_confirm_window();
+                                }
+                            }
+                            if ui
+                                .add(
+                                    egui::DragValue::new(&mut confirm_secs)
+                                        .range(1..=MAX_LAUNCH_CONFIRM_SECS)
+                                        .suffix("s"),
+                                )
+                                .on_hover_text("How long a launched program is watched")
+                                .changed()
+                            {
+                                app.set_launch_confirm_secs(confirm_secs);
+                            }
+                        }
+                        let mut confirm_launches = confirm_secs > 0;
+                        if ui
+                            .checkbox(
+                                &mut confirm_launches,
+                                RichText::new("Confirm launches").size(8.5),
+                            )
+                            .on_hover_text(
+                                "Watch each started program briefly and log whether it kept running or exited during startup",
+                            )
+                            .changed()
+                        {
+                            app.set_launch_confirm_secs(if confirm_launches {
+                                DEFAULT_LAUNCH_CONFIRM_SECS
+                            } else {
+                                0
+                            });
+                        }
+                        let mut run_all_threshold = app.run_all_confirm_threshold();
+                        if run_all_threshold > 0
+                            && ui
+                                .add(
+                                    egui::DragValue::new(&mut run_all_threshold)
+                                        .range(1..=MAX_RUN_ALL_CONFIRM_THRESHOLD),
+                                )
+                                .on_hover_text(
+                                    "Run all asks first when it would start more apps than this",
+                                )
+                                .changed()
+                        {
+                            app.set_run_all_confirm_threshold(run_all_threshold);
+                        }
+                        let mut confirm_run_all = run_all_threshold > 0;
+                        if ui
+                            .checkbox(
+                                &mut confirm_run_all,
+                                RichText::new("Confirm Run all").size(8.5),
+                            )
+                            .on_hover_text(
+                                "Ask before Run all starts many apps at once",
+                            )
+                            .changed()
+                        {
+                            app.set_run_all_confirm_threshold(if confirm_run_all {
+                                DEFAULT_RUN_ALL_CONFIRM_THRESHOLD
+                            } else {
+                                0
+                            });
+                        }
+                        let mut click_to_launch = app.is_click_to_launch();
+                        if ui
+                            .checkbox(
+                                &mut click_to_launch,
+                                RichText::new("Double-click to run").size(8.5),
+                            )
+                            .on_hover_text(
+                                "Run or focus an app by double-clicking its name; a single click only selects the row",
+                            )
+                            .changed()
+                        {
+                            app.toggle_click_to_launch();
+                        }
+                        let mut show_tips = !app.are_tips_hidden();
+                        if ui
+                            .checkbox(&mut show_tips, RichText::new("Show tips").size(8.5))
+                            .on_hover_text("Show rotating usage tips above the status line")
+                            .changed()
+                        {
+                            app.toggle_hide_tips();
+                        }
+                        let mut audit = app.is_affinity_audit_enabled();
+                        if ui
+                            .checkbox(&mut audit, RichText::new("Audit changes").size(8.5))
+                            .on_hover_text(format!(
+                                "Append every affinity and priority change to {AUDIT_FILE_NAME} beside the state file"
+                            ))
+                            .changed()
+                        {
+                            app.toggle_affinity_audit();
+                        }
+                        if crate::app::adapters::os::supports_hide_to_tray() {
+                            let mut hide_taskbar = app.is_taskbar_hidden_when_tray();
+                            if ui
+                                .checkbox(
+                                    &mut hide_taskbar,
+                                    RichText::new("Tray only when hidden").size(8.5),
+                                )
+                                .on_hover_text(
+                                    "Remove the taskbar button while the window is hidden to the tray",
+                                )
+                                .changed()
+                            {
+                                app.toggle_hide_taskbar_when_tray();
+                            }
+                        }
+                        let self_affinity = app.self_affinity();
+                        let mut updated = self_affinity;
+                        if let SelfAffinity::Core(core) = &mut updated {
+                            ui.add(egui::DragValue::new(core).range(0..=usize::BITS as usize - 1))
+                                .on_hover_text("Thread the tool keeps to");
+                        }
+                        egui::ComboBox::from_id_salt("self_affinity")
+                            .selected_text(
+                                RichText::new(self_affinity_label(self_affinity)).size(8.5),
+                            )
+                            .show_ui(ui, |ui| {
+                                let core = match self_affinity {
+                                    SelfAffinity::Core(core) => core,
+                                    _ => 0,
+                                };
+                                for choice in [
+                                    SelfAffinity::Off,
+                                    SelfAffinity::OutsideGroups,
+                                    SelfAffinity::Core(core),
+                                ] {
+                                    ui.selectable_value(
+                                        &mut updated,
+                                        choice,
+                                        self_affinity_label(choice),
+                                    );
+                                }
+                            })
+                            .response
+                            .on_hover_text(
+                                "Threads the tool itself runs on, so its monitor stays out of the way of pinned programs",
+                            );
+                        if updated != self_affinity {
+                            app.set_self_affinity(updated);
+                        }
                     });
                 });
         });
@@ -85,8 +349,10 @@ pub fn draw_bottom_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
 
 #[cfg(test)]
 mod tests {
-    use super::footer_frame;
+    use super::{footer_frame, rotate_tip, TIPS, TIP_INTERVAL};
     use crate::app::shell::presenters::shared_elements::palette_for_dark_mode;
+    use crate::app::shell::UiSession;
+    use std::time::Duration;
 
     #[test]
     fn test_footer_frame_fills_the_entire_panel_surface() {
@@ -96,4 +362,21 @@ mod tests {
         assert_eq!(frame.fill, colors.group);
         assert_eq!(frame.inner_margin, eframe::egui::Margin::symmetric(8, 4));
     }
+
+    #[test]
+    fn test_rotate_tip_advances_after_the_interval_and_wraps() {
+        let mut session = UiSession::new(8);
+        let start = session.last_tip_change_time;
+
+        assert_eq!(
+            rotate_tip(&mut session, start + Duration::from_secs(1)),
+            TIPS[0]
+        );
+        assert_eq!(rotate_tip(&mut session, start + TIP_INTERVAL), TIPS[1]);
+        assert_eq!(session.last_tip_change_time, start + TIP_INTERVAL);
+        assert_eq!(rotate_tip(&mut session, start + TIP_INTERVAL), TIPS[1]);
+
+        session.current_tip_index = TIPS.len() - 1;
+        assert_eq!(rotate_tip(&mut session, start + TIP_INTERVAL * 2), TIPS[0]);
+    }
 }
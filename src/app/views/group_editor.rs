@@ -1,16 +1,40 @@
-use crate::app::models::{CoreInfo, CoreType, CpuSchema};
+use crate::app::adapters::os;
+use crate::app::features::topology;
+use crate::app::models::{name_accent_color, CoreInfo, CoreSelector, CoreType, CpuSchema};
 use crate::app::runtime::AppState;
 use crate::app::shell::presenters::shared_elements::{
     ghost_button, glass_frame, inset_frame, inter_semibold_family, paint_focus_ring,
-    paint_selected_tone_feedback, palette, toned_button, ToneRole, ToneTokens, UiPalette,
-    BUTTON_FONT_SIZE,
+    paint_selected_tone_feedback, palette, success_color, toned_button, toned_sized_button,
+    warning_color, ToneRole, ToneTokens, UiPalette, BUTTON_FONT_SIZE,
 };
 use crate::app::shell::GroupFormSession;
 use eframe::egui::{self, CentralPanel, RichText};
+use os_api::{PriorityClass, PriorityClassName};
 
 const CORE_TILE_WIDTH: f32 = 56.0;
+/// Cap offered when "Limit CPU usage" is first ticked.
+const DEFAULT_CPU_RATE_PERCENT: u8 = 50;
+/// Threads one Windows processor group holds, so one affinity mask covers at most this many.
+const PROCESSOR_GROUP_THREADS: usize = 64;
+const REFRESH_CPU_HINT: &str =
+    "Re-detect the CPU threads and layout, e.g. after a power mode parked or woke cores";
+
+/// Warns above the form when its name is already used by another group.
+fn draw_group_name_conflict(ui: &mut egui::Ui, app: &mut AppState) {
+    if let Some(existing) = app.group_form_name_conflict() {
+        ui.colored_label(
+            warning_color(ui),
+            RichText::new(format!(
+                "A group named \"{existing}\" already exists; names must differ so --run-group can find each group"
+            ))
+            .small(),
+        );
+        ui.add_space(4.0);
+    }
+}
 
 /// Form for creating/editing a group: divided into rendering the name and the section with cores and clusters.
+/// Returns true when the affinity test was requested.
 fn draw_group_form_ui(
     ui: &mut egui::Ui,
     groups: &mut GroupFormSession,
@@ -19,7 +43,8 @@ fn draw_group_form_ui(
     on_save: &mut dyn FnMut(),
     on_cancel: &mut dyn FnMut(),
     on_delete: Option<&mut dyn FnMut()>,
-) {
+) -> bool {
+    let mut test_affinity_clicked = false;
     glass_frame(ui).show(ui, |ui| {
         ui.vertical(|ui| {
             ui.label(RichText::new("Group name").strong());
@@ -36,6 +61,128 @@ fn draw_group_form_ui(
             &mut groups.run_all_enabled,
             "Show a Run all action for this group",
         );
+        ui.checkbox(&mut groups.single_numa_node, "Keep on one NUMA node")
+            .on_hover_text(
+                "Launched apps prefer the NUMA node that holds most of the selected threads",
+            );
+        if groups.single_numa_node {
+            let selected: Vec<usize> = groups
+                .core_selection
+                .iter()
+                .enumerate()
+                .filter_map(|(index, selected)| selected.then_some(index))
+                .collect();
+            if let Some(warning) = topology::numa_span_warning(&selected, |core| {
+                groups.numa_nodes.get(core).copied().flatten()
+            }) {
+                ui.colored_label(warning_color(ui), RichText::new(warning).small());
+            }
+        }
+
+        ui.horizontal(|ui| {
+            ui.label("Default priority for new apps:");
+            let mut class = groups.default_priority.class_name();
+            egui::ComboBox::from_id_salt("group_default_priority")
+                .selected_text(format!("{class:?}"))
+                .show_ui(ui, |ui| {
+                    for (option, label) in [
+                        (PriorityClassName::Realtime, "RealTime"),
+                        (PriorityClassName::High, "High"),
+                        (PriorityClassName::AboveNormal, "Above Normal"),
+                        (PriorityClassName::Normal, "Normal"),
+                        (PriorityClassName::BelowNormal, "Below Normal"),
+                        (PriorityClassName::Idle, "Low"),
+                    ] {
+                        ui.selectable_value(&mut class, option, label);
+                    }
+                })
+                .response
+                .on_hover_text("Apps added to this group later start with this priority; apps already in it keep theirs");
+            groups.default_priority =
+                PriorityClass::from_parts(class, groups.default_priority.eco_qos());
+        });
+
+        draw_core_selector_ui(ui, groups, cpu_schema);
+
+        ui.horizontal(|ui| {
+            ui.label("Leave child processes alone:");
+            ui.add(
+                egui::TextEdit::singleline(&mut groups.exclude_names)
+                    .hint_text("e.g. EasyAntiCheat.exe, BEService.exe")
+                    .desired_width(220.0),
+            )
+            .on_hover_text(
+                "Comma-separated executable names. Child processes of this group's apps with these names keep the system's default affinity, e.g. an anti-cheat service",
+            );
+        });
+
+        if os::supports_job_objects() {
+            ui.checkbox(
+                &mut groups.use_job_object,
+                "Use Job Object (constrain all children)",
+            )
+            .on_hover_text(
+                "Start apps in a Windows Job Object so every process they spawn stays on these threads, even launchers that respawn themselves. Excluded child processes cannot leave the job",
+            );
+        }
+
+        ui.horizontal(|ui| {
+            let mut limited = groups.cpu_rate_percent.is_some();
+            if ui
+                .checkbox(&mut limited, "Limit CPU usage")
+                .on_hover_text(
+                    "Hard cap on the share of the whole machine's CPU time the group's apps and their child processes may use, e.g. for a background encoder. On Linux this needs write access to /sys/fs/cgroup",
+                )
+                .changed()
+            {
+                groups.cpu_rate_percent = limited.then_some(DEFAULT_CPU_RATE_PERCENT);
+            }
+            if let Some(percent) = &mut groups.cpu_rate_percent {
+                ui.add(egui::Slider::new(percent, 1..=100).suffix("%"));
+            }
+        });
+
+        ui.horizontal(|ui| {
+            let mut custom = groups.color.is_some();
+            if ui
+                .checkbox(&mut custom, "Custom color")
+                .on_hover_text(
+                    "Mark the group in the list with a color of your choice; otherwise one is picked from its name",
+                )
+                .changed()
+            {
+                groups.color = custom.then(|| name_accent_color(&groups.group_name));
+            }
+            match &mut groups.color {
+                Some(color) => {
+                    ui.color_edit_button_srgb(color);
+                }
+                None => {
+                    let [r, g, b] = name_accent_color(&groups.group_name);
+                    let (rect, _) =
+                        ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                    ui.painter()
+                        .rect_filled(rect, 3.0, egui::Color32::from_rgb(r, g, b));
+                }
+            }
+        });
+
+        if let (true, Some(profile)) = (is_edit, groups.active_profile.clone()) {
+            ui.checkbox(
+                &mut groups.save_to_profile,
+                format!("Save threads to profile \"{profile}\" only"),
+            )
+            .on_hover_text("The group's base threads stay unchanged for other profiles");
+            if groups.save_to_profile {
+                ui.colored_label(
+                    warning_color(ui),
+                    RichText::new(format!(
+                        "Profile \"{profile}\" overrides this group's threads"
+                    ))
+                    .small(),
+                );
+            }
+        }
 
         ui.add_space(5.0);
         ui.separator();
@@ -43,6 +190,9 @@ fn draw_group_form_ui(
 
         draw_cpu_cores_ui(ui, groups, cpu_schema);
 
+        ui.add_space(6.0);
+        test_affinity_clicked = draw_affinity_probe_ui(ui, groups);
+
         ui.add_space(9.0);
         ui.separator();
         ui.add_space(6.0);
@@ -89,6 +239,119 @@ fn draw_group_form_ui(
             });
         });
     });
+    test_affinity_clicked
+}
+
+/// "Cores at launch": the selected threads, or the idlest threads of a cluster.
+fn draw_core_selector_ui(ui: &mut egui::Ui, groups: &mut GroupFormSession, cpu_schema: &CpuSchema) {
+    ui.horizontal(|ui| {
+        ui.label("Cores at launch:");
+        ui.radio_value(
+            &mut groups.core_selector,
+            CoreSelector::Fixed,
+            "Selected threads",
+        );
+        let least_loaded = matches!(groups.core_selector, CoreSelector::LeastLoaded { .. });
+        if ui
+            .radio(least_loaded, "Least-loaded threads of a cluster")
+            .on_hover_text("Picks the idlest threads of the cluster each time an app starts; monitoring keeps the app on them")
+            .clicked()
+            && !least_loaded
+            && !cpu_schema.clusters.is_empty()
+        {
+            groups.core_selector = CoreSelector::LeastLoaded {
+                cluster: 0,
+                count: cpu_schema.clusters[0].cores.len().clamp(1, 2),
+            };
+        }
+    });
+
+    let CoreSelector::LeastLoaded { cluster, count } = &mut groups.core_selector else {
+        return;
+    };
+    if cpu_schema.clusters.is_empty() {
+        ui.colored_label(
+            warning_color(ui),
+            RichText::new("Define a CPU cluster first; launches use the selected threads").small(),
+        );
+        return;
+    }
+    ui.horizontal(|ui| {
+        ui.add(egui::DragValue::new(count).range(1..=usize::BITS as usize));
+        ui.label("threads of");
+        egui::ComboBox::from_id_salt("group_core_selector_cluster")
+            .selected_text(
+                cpu_schema
+                    .clusters
+                    .get(*cluster)
+                    .map_or("(missing cluster)", |found| found.name.as_str()),
+            )
+            .show_ui(ui, |ui| {
+                for (index, found) in cpu_schema.clusters.iter().enumerate() {
+                    ui.selectable_value(cluster, index, &found.name);
+                }
+            });
+    });
+    ui.label(
+        RichText::new("The selected threads below are used if the cluster is removed").small(),
+    );
+}
+
+/// "Test affinity" diagnostic: spins the selected threads and shows which cores carried the load.
+fn draw_affinity_probe_ui(ui: &mut egui::Ui, groups: &GroupFormSession) -> bool {
+    let mut clicked = false;
+    let running = groups.affinity_probe_rx.is_some();
+    let has_selection = groups.core_selection.iter().any(|&selected| selected);
+
+    ui.horizontal(|ui| {
+        if ui
+            .add_enabled(
+                !running && has_selection,
+                egui::Button::new("Test affinity"),
+            )
+            .on_hover_text(
+                "Runs busy loops pinned to the selected threads for about two seconds and reports which cores actually spun",
+            )
+            .on_disabled_hover_text(if running {
+                "The affinity test is running"
+            } else {
+                "Select at least one thread to test"
+            })
+            .clicked()
+        {
+            clicked = true;
+        }
+
+        if running {
+            ui.spinner();
+            ui.label(RichText::new("Spinning the selected threads...").small().weak());
+            ui.ctx().request_repaint_after(std::time::Duration::from_millis(100));
+        }
+    });
+
+    match &groups.affinity_probe_result {
+        Some(Ok(report)) if report.matches_mask() => {
+            ui.colored_label(success_color(ui), RichText::new(report.summary()).small());
+        }
+        Some(Ok(report)) => {
+            ui.colored_label(
+                warning_color(ui),
+                RichText::new(format!(
+                    "Mismatch with the selected threads. {}",
+                    report.summary()
+                ))
+                .small(),
+            );
+        }
+        Some(Err(err)) => {
+            ui.colored_label(
+                warning_color(ui),
+                RichText::new(format!("Affinity test failed: {err}")).small(),
+            );
+        }
+        None => {}
+    }
+    clicked
 }
 
 /// Rendering the CPU cores section: a list of already created clusters and a panel of free cores.
@@ -113,6 +376,56 @@ fn draw_cpu_cores_ui(ui: &mut egui::Ui, groups: &mut GroupFormSession, cpu_schem
         .small()
         .strong(),
     );
+    ui.horizontal(|ui| {
+        let masks = selection_masks(&groups.core_selection);
+        let hex = masks_text(&masks, |mask| format!("{mask:#X}"));
+        ui.label(RichText::new(format!("Mask {hex}")).small().monospace())
+            .on_hover_text(masks_text(&masks, |mask| format!("{mask:#b}")));
+        if ghost_button(
+            ui,
+            egui::Button::new(RichText::new("Copy").size(BUTTON_FONT_SIZE)),
+        )
+        .on_hover_text("Copy the mask, e.g. to compare with Task Manager's affinity dialog")
+        .clicked()
+        {
+            ui.ctx().copy_text(hex);
+        }
+    });
+    if ghost_button(
+        ui,
+        egui::Button::new(RichText::new("Use my current affinity").size(BUTTON_FONT_SIZE)),
+    )
+    .on_hover_text(
+        "Select the threads this tool itself may run on, e.g. after restricting it in Task Manager",
+    )
+    .clicked()
+        && apply_affinity_mask(groups, os::current_process_affinity())
+    {
+        groups.last_clicked_core = None;
+    }
+    let unavailable = (0..groups.core_selection.len())
+        .filter(|&index| !groups.is_core_available(index))
+        .count();
+    if unavailable > 0 {
+        ui.colored_label(
+            warning_color(ui),
+            RichText::new(format!(
+                "{unavailable} threads are disabled, parked or offline and can't be selected"
+            ))
+            .small(),
+        );
+    }
+    if !groups.reserved_cores.is_empty() {
+        ui.checkbox(
+            &mut groups.allow_reserved_cores,
+            RichText::new(format!(
+                "Allow reserved threads {:?} in this group",
+                groups.reserved_cores
+            ))
+            .small(),
+        )
+        .on_hover_text("Right-click a thread to reserve it for the OS or release it");
+    }
     ui.add_space(4.0);
     ui.separator();
 
@@ -125,15 +438,14 @@ fn draw_cpu_cores_ui(ui: &mut egui::Ui, groups: &mut GroupFormSession, cpu_schem
         inset_frame(ui).show(ui, |ui| {
             ui.horizontal(|ui| {
                 ui.label(RichText::new(&cluster.name).strong());
+                draw_core_preset_buttons(ui, groups, &cluster.cores);
             });
-            draw_core_buttons(ui, groups, &mut cluster.cores);
+            draw_core_buttons(ui, groups, &mut cluster.cores, Some(&cluster.name));
         });
     }
 
     if !free_core_indexes.is_empty() {
         inset_frame(ui).show(ui, |ui| {
-            ui.label(RichText::new("Free Cores").strong());
-
             // Temporary CoreInfo for drawing buttons of free cores
             let mut free_cores: Vec<CoreInfo> = free_core_indexes
                 .iter()
@@ -144,11 +456,187 @@ fn draw_cpu_cores_ui(ui: &mut egui::Ui, groups: &mut GroupFormSession, cpu_schem
                 })
                 .collect();
 
-            draw_core_buttons(ui, groups, &mut free_cores);
+            ui.horizontal(|ui| {
+                ui.label(RichText::new("Free Cores").strong());
+                draw_core_preset_buttons(ui, groups, &free_cores);
+            });
+            draw_core_buttons(ui, groups, &mut free_cores, None);
         });
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CorePreset {
+    FirstHalf,
+    SecondHalf,
+    EvenCores,
+    OddCores,
+    PerformanceOnly,
+    NoHyperThreading,
+}
+
+impl CorePreset {
+    const ALL: [CorePreset; 6] = [
+        CorePreset::FirstHalf,
+        CorePreset::SecondHalf,
+        CorePreset::EvenCores,
+        CorePreset::OddCores,
+        CorePreset::PerformanceOnly,
+        CorePreset::NoHyperThreading,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            CorePreset::FirstHalf => "First half",
+            CorePreset::SecondHalf => "Second half",
+            CorePreset::EvenCores => "Even",
+            CorePreset::OddCores => "Odd",
+            CorePreset::PerformanceOnly => "P-cores",
+            CorePreset::NoHyperThreading => "No HT",
+        }
+    }
+
+    fn hover_text(self) -> &'static str {
+        match self {
+            CorePreset::FirstHalf => "Select the first half of the threads in this set",
+            CorePreset::SecondHalf => "Select the second half of the threads in this set",
+            CorePreset::EvenCores => {
+                "Select even-numbered threads, usually one thread per physical core"
+            }
+            CorePreset::OddCores => {
+                "Select odd-numbered threads, usually the hyper-threading siblings"
+            }
+            CorePreset::PerformanceOnly => {
+                "Select only the performance cores detected for this CPU"
+            }
+            CorePreset::NoHyperThreading => {
+                "Select one thread per physical core, skipping hyper-threading siblings"
+            }
+        }
+    }
+
+    /// Thread indexes this preset picks out of `cores`, in render order.
+    ///
+    /// "No HT" follows the detected topology: it keeps every core that is not a
+    /// hyper-threading sibling. Only sets without any known core type (no preset
+    /// matched, or free cores) fall back to treating odd threads as siblings.
+    fn pick(self, cores: &[CoreInfo]) -> Vec<usize> {
+        let half = cores.len().div_ceil(2);
+        let topology_known = cores.iter().any(|core| core.core_type != CoreType::Other);
+        cores
+            .iter()
+            .enumerate()
+            .filter(|(position, core)| match self {
+                CorePreset::FirstHalf => *position < half,
+                CorePreset::SecondHalf => *position >= half,
+                CorePreset::EvenCores => core.index % 2 == 0,
+                CorePreset::OddCores => core.index % 2 == 1,
+                CorePreset::PerformanceOnly => core.core_type == CoreType::Performance,
+                CorePreset::NoHyperThreading if topology_known => {
+                    core.core_type != CoreType::HyperThreading
+                }
+                CorePreset::NoHyperThreading => core.index % 2 == 0,
+            })
+            .map(|(_, core)| core.index)
+            .collect()
+    }
+}
+
+/// Applies `preset` to the threads of one set, leaving threads outside `cores` untouched.
+///
+/// Selecting a preset that is already exactly applied clears it again. Returns whether
+/// any selection bit changed.
+fn apply_core_preset(selection: &mut [bool], cores: &[CoreInfo], preset: CorePreset) -> bool {
+    let picked = preset.pick(cores);
+    let total = selection.len();
+    let in_range = |core: &&CoreInfo| core.index < total;
+    let already_applied = cores
+        .iter()
+        .filter(in_range)
+        .all(|core| selection[core.index] == picked.contains(&core.index));
+
+    let mut changed = false;
+    for core in cores.iter().filter(in_range) {
+        let target = !already_applied && picked.contains(&core.index);
+        if selection[core.index] != target {
+            selection[core.index] = target;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Selected threads as affinity masks, one per 64-thread processor group.
+fn selection_masks(core_selection: &[bool]) -> Vec<u64> {
+    core_selection
+        .chunks(PROCESSOR_GROUP_THREADS)
+        .map(|chunk| {
+            chunk
+                .iter()
+                .enumerate()
+                .filter(|(_, selected)| **selected)
+                .fold(0u64, |mask, (bit, _)| mask | 1 << bit)
+        })
+        .collect()
+}
+
+/// A lone mask as-is; with several processor groups each mask is labelled by group.
+fn masks_text(masks: &[u64], format_mask: impl Fn(u64) -> String) -> String {
+    match masks {
+        [] => format_mask(0),
+        [mask] => format_mask(*mask),
+        _ => masks
+            .iter()
+            .enumerate()
+            .map(|(group, mask)| format!("group {group}: {}", format_mask(*mask)))
+            .collect::<Vec<_>>()
+            .join(", "),
+    }
+}
+
+/// Selects exactly the selectable threads set in `mask`; threads beyond the mask width
+/// are cleared. Returns whether any selection bit changed.
+fn apply_affinity_mask(groups: &mut GroupFormSession, mask: usize) -> bool {
+    let mut changed = false;
+    for index in 0..groups.core_selection.len() {
+        let target = index < usize::BITS as usize
+            && mask & (1 << index) != 0
+            && groups.is_core_selectable(index);
+        if groups.core_selection[index] != target {
+            groups.core_selection[index] = target;
+            changed = true;
+        }
+    }
+    changed
+}
+
+fn draw_core_preset_buttons(ui: &mut egui::Ui, groups: &mut GroupFormSession, cores: &[CoreInfo]) {
+    let cores: Vec<CoreInfo> = cores
+        .iter()
+        .filter(|core| groups.is_core_selectable(core.index))
+        .cloned()
+        .collect();
+    let cores = cores.as_slice();
+    ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+        ui.spacing_mut().item_spacing.x = 3.0;
+        for preset in CorePreset::ALL.into_iter().rev() {
+            if preset.pick(cores).is_empty() {
+                continue;
+            }
+            if ghost_button(
+                ui,
+                egui::Button::new(RichText::new(preset.label()).size(BUTTON_FONT_SIZE)),
+            )
+            .on_hover_text(preset.hover_text())
+            .clicked()
+                && apply_core_preset(&mut groups.core_selection, cores, preset)
+            {
+                groups.last_clicked_core = None;
+            }
+        }
+    });
+}
+
 fn core_tile_tokens(
     _core_type: CoreType,
     is_selected: bool,
@@ -172,6 +660,42 @@ fn core_tile_text(label: &str, thread_index: usize) -> CoreTileText {
     }
 }
 
+/// Tooltip of a core tile: its kind, hyper-thread sibling and cluster, read from the
+/// detected schema, and the thread's current load once monitoring has sampled it.
+/// Threads outside every cluster only show their index.
+fn core_tile_hover_text(
+    core: &CoreInfo,
+    cluster_cores: &[CoreInfo],
+    cluster_name: Option<&str>,
+    usage: Option<f32>,
+) -> String {
+    let load = usage
+        .map(|busy| format!("\nLoad: {:.0}%", busy * 100.0))
+        .unwrap_or_default();
+    let Some(cluster_name) = cluster_name else {
+        return format!("Thread {}{load}", core.index);
+    };
+    // Threads of one physical core share its label.
+    let siblings = cluster_cores
+        .iter()
+        .filter(|other| other.label == core.label && other.index != core.index)
+        .map(|other| other.index.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let kind = match (core.core_type, siblings.is_empty()) {
+        (CoreType::HyperThreading, false) => format!("HT sibling of thread {siblings}"),
+        (CoreType::HyperThreading, true) => "Hyper-thread".to_string(),
+        (CoreType::Performance, false) => format!("Performance, HT sibling on thread {siblings}"),
+        (CoreType::Performance, true) => "Performance".to_string(),
+        (CoreType::Efficient, _) => "Efficient".to_string(),
+        (CoreType::Other, _) => "Other".to_string(),
+    };
+    format!(
+        "{}, thread {} ({kind})\nCluster: {cluster_name}{load}",
+        core.label, core.index
+    )
+}
+
 fn core_tile_widget_info(
     enabled: bool,
     is_selected: bool,
@@ -195,16 +719,62 @@ fn selected_core_tile_fill(tokens: ToneTokens, hovered: bool, pressed: bool) ->
     }
 }
 
+/// Why a core tile may refuse new selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CoreTileLock {
+    Free,
+    Reserved { overridden: bool },
+    Unavailable,
+}
+
+impl CoreTileLock {
+    fn for_core(groups: &GroupFormSession, index: usize) -> Self {
+        if !groups.is_core_available(index) {
+            CoreTileLock::Unavailable
+        } else if groups.is_core_reserved(index) {
+            CoreTileLock::Reserved {
+                overridden: groups.allow_reserved_cores,
+            }
+        } else {
+            CoreTileLock::Free
+        }
+    }
+
+    fn allows_selection(self) -> bool {
+        matches!(
+            self,
+            CoreTileLock::Free | CoreTileLock::Reserved { overridden: true }
+        )
+    }
+}
+
 fn core_tile_button(
     ui: &mut egui::Ui,
     size: egui::Vec2,
     core: &CoreInfo,
+    hover_text: &str,
     is_selected: bool,
+    lock: CoreTileLock,
     tokens: Option<ToneTokens>,
 ) -> egui::Response {
-    let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+    // An unavailable core stays clickable only while selected, so it can be cleared.
+    // Reserved tiles keep sensing clicks for their context menu.
+    let sense = if lock != CoreTileLock::Unavailable || is_selected {
+        egui::Sense::click()
+    } else {
+        egui::Sense::hover()
+    };
+    let (rect, response) = ui.allocate_exact_size(size, sense);
     let text = core_tile_text(&core.label, core.index);
-    let response = response.on_hover_text(&text.accessible);
+    let response = match lock {
+        CoreTileLock::Free => response.on_hover_text(hover_text),
+        CoreTileLock::Reserved { .. } => response.on_hover_text(format!(
+            "{hover_text}\nReserved for the OS; right-click to release"
+        )),
+        CoreTileLock::Unavailable => response.on_hover_text(format!(
+            "{hover_text}\nUnavailable: disabled, parked or offline"
+        )),
+    };
     let (fill, border, foreground) = if let Some(tokens) = tokens {
         let fill = selected_core_tile_fill(
             tokens,
@@ -246,7 +816,31 @@ fn core_tile_button(
             check_stroke,
         );
     }
-    response.widget_info(|| core_tile_widget_info(response.enabled(), is_selected, &text));
+    match lock {
+        CoreTileLock::Free => {}
+        CoreTileLock::Reserved { .. } => {
+            painter.text(
+                egui::pos2(rect.right() - 5.0, rect.top() + 6.0),
+                egui::Align2::CENTER_CENTER,
+                "R",
+                egui::FontId::proportional(7.5),
+                warning_color(ui),
+            );
+        }
+        CoreTileLock::Unavailable => {
+            ui.painter().line_segment(
+                [rect.left_bottom(), rect.right_top()],
+                egui::Stroke::new(1.0, warning_color(ui)),
+            );
+        }
+    }
+    response.widget_info(|| {
+        core_tile_widget_info(
+            response.enabled() && lock.allows_selection(),
+            is_selected,
+            &text,
+        )
+    });
     paint_focus_ring(ui, &response);
     if let Some(tokens) = tokens {
         paint_selected_tone_feedback(ui, &response, tokens);
@@ -254,20 +848,66 @@ fn core_tile_button(
     response
 }
 
-fn draw_core_buttons(ui: &mut egui::Ui, groups: &mut GroupFormSession, cores: &mut [CoreInfo]) {
-    draw_core_buttons_impl(ui, groups, cores, |_| {});
+/// Sets every thread from `anchor` to `to` to the anchor's state, as Shift+click and
+/// Shift+arrow do; locked threads are only ever cleared. Returns whether anything changed.
+fn extend_core_range(groups: &mut GroupFormSession, anchor: usize, to: usize) -> bool {
+    let Some(&target_state) = groups.core_selection.get(anchor) else {
+        return false;
+    };
+    let mut changed = false;
+    for i in anchor.min(to)..=anchor.max(to) {
+        if i < groups.core_selection.len()
+            && groups.core_selection[i] != target_state
+            && (!target_state || groups.is_core_selectable(i))
+        {
+            groups.core_selection[i] = target_state;
+            changed = true;
+        }
+    }
+    changed
+}
+
+/// Range step for a tile that just received focus after Shift+arrow on `from`. Without
+/// an earlier anchor the range starts at `from`, selected.
+fn extend_core_range_from_key(groups: &mut GroupFormSession, from: usize, to: usize) -> bool {
+    let mut changed = false;
+    let anchor = match groups.last_clicked_core {
+        Some(anchor) => anchor,
+        None => {
+            if groups.is_core_selectable(from) && !groups.core_selection[from] {
+                groups.core_selection[from] = true;
+                changed = true;
+            }
+            groups.last_clicked_core = Some(from);
+            from
+        }
+    };
+    extend_core_range(groups, anchor, to) || changed
+}
+
+fn draw_core_buttons(
+    ui: &mut egui::Ui,
+    groups: &mut GroupFormSession,
+    cores: &mut [CoreInfo],
+    cluster_name: Option<&str>,
+) {
+    draw_core_buttons_impl(ui, groups, cores, cluster_name, |_| {});
 }
 
 fn draw_core_buttons_impl(
     ui: &mut egui::Ui,
     groups: &mut GroupFormSession,
     cores: &mut [CoreInfo],
+    cluster_name: Option<&str>,
     mut record_rect: impl FnMut(egui::Rect),
 ) {
     let colors = palette(ui);
     ui.horizontal_wrapped(|ui| {
         ui.spacing_mut().item_spacing.x = 3.0;
-        let all_selected = cores.iter().all(|c| groups.core_selection[c.index]);
+        let all_selected = cores
+            .iter()
+            .filter(|c| groups.is_core_selectable(c.index))
+            .all(|c| groups.core_selection[c.index]);
         let all_tokens = core_tile_tokens(CoreType::Other, all_selected, colors);
         let mut all_label =
             RichText::new(if all_selected { "✓ All" } else { "All" }).size(BUTTON_FONT_SIZE);
@@ -296,7 +936,7 @@ fn draw_core_buttons_impl(
         if all_response.clicked() {
             let mut changed = false;
             for c in cores.iter() {
-                let target = !all_selected;
+                let target = !all_selected && groups.is_core_selectable(c.index);
                 if groups.core_selection[c.index] != target {
                     groups.core_selection[c.index] = target;
                     changed = true;
@@ -315,31 +955,73 @@ fn draw_core_buttons_impl(
                 _ => egui::vec2(CORE_TILE_WIDTH, 30.0),
             };
 
+            let lock = CoreTileLock::for_core(groups, core.index);
             let mut response = core_tile_button(
                 ui,
                 size,
                 core,
+                &core_tile_hover_text(
+                    core,
+                    cores,
+                    cluster_name,
+                    groups
+                        .core_usage
+                        .as_ref()
+                        .and_then(|usage| usage.get(core.index).copied()),
+                ),
                 is_selected,
+                lock,
                 core_tile_tokens(core.core_type, is_selected, colors),
             );
             record_rect(response.rect);
 
-            if response.clicked() {
+            if lock != CoreTileLock::Unavailable {
+                response.context_menu(|ui| {
+                    let label = if groups.is_core_reserved(core.index) {
+                        "Release reservation"
+                    } else {
+                        "Reserve for the OS"
+                    };
+                    if ui.button(label).clicked() {
+                        groups.toggle_reserved_core(core.index);
+                        ui.close();
+                    }
+                });
+            }
+
+            // egui moves focus between tiles with the arrow keys; Shift also extends
+            // the selection to the tile that receives focus on the next pass.
+            let pass = ui.ctx().cumulative_pass_nr();
+            if response.has_focus()
+                && ui.input(|i| {
+                    i.modifiers.shift
+                        && [
+                            egui::Key::ArrowLeft,
+                            egui::Key::ArrowRight,
+                            egui::Key::ArrowUp,
+                            egui::Key::ArrowDown,
+                        ]
+                        .into_iter()
+                        .any(|key| i.key_pressed(key))
+                })
+            {
+                groups.keyboard_range_from = Some((core.index, pass));
+            }
+            if response.gained_focus() {
+                if let Some((from, pressed_at)) = groups.keyboard_range_from.take() {
+                    if from != core.index
+                        && pressed_at + 2 >= pass
+                        && extend_core_range_from_key(groups, from, core.index)
+                    {
+                        response.mark_changed();
+                    }
+                }
+            }
+
+            if response.clicked() && (is_selected || lock.allows_selection()) {
                 let shift = ui.input(|i| i.modifiers.shift);
                 if let (true, Some(last_idx)) = (shift, groups.last_clicked_core) {
-                    let start = last_idx.min(core.index);
-                    let end = last_idx.max(core.index);
-                    let target_state = groups.core_selection[last_idx];
-                    let mut changed = false;
-                    for i in start..=end {
-                        if i < groups.core_selection.len()
-                            && groups.core_selection[i] != target_state
-                        {
-                            groups.core_selection[i] = target_state;
-                            changed = true;
-                        }
-                    }
-                    if changed {
+                    if extend_core_range(groups, last_idx, core.index) {
                         response.mark_changed();
                     }
                 } else {
@@ -359,7 +1041,7 @@ fn draw_core_buttons_for_test(
     cores: &mut [CoreInfo],
 ) -> Vec<egui::Rect> {
     let mut control_rects = Vec::with_capacity(cores.len() + 1);
-    draw_core_buttons_impl(ui, groups, cores, |rect| control_rects.push(rect));
+    draw_core_buttons_impl(ui, groups, cores, None, |rect| control_rects.push(rect));
     control_rects
 }
 
@@ -367,6 +1049,8 @@ fn draw_core_buttons_for_test(
 pub fn create_group_window(app: &mut AppState, root_ui: &mut egui::Ui) {
     let mut create_clicked = false;
     let mut cancel_clicked = false;
+    let mut test_affinity_clicked = false;
+    let mut refresh_cpu_clicked = false;
 
     CentralPanel::default().show(root_ui, |ui| {
         ui.add_space(3.0);
@@ -386,15 +1070,23 @@ pub fn create_group_window(app: &mut AppState, root_ui: &mut egui::Ui) {
                 {
                     cancel_clicked = true;
                 }
+                if ghost_button(ui, egui::Button::new("Refresh CPU"))
+                    .on_hover_text(REFRESH_CPU_HINT)
+                    .clicked()
+                {
+                    refresh_cpu_clicked = true;
+                }
             });
         });
         ui.add_space(6.0);
+        draw_group_name_conflict(ui, app);
 
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
                 let mut schema = app.get_cpu_schema();
-                draw_group_form_ui(
+                app.ui.group_form.core_usage = app.core_usage();
+                test_affinity_clicked = draw_group_form_ui(
                     ui,
                     &mut app.ui.group_form,
                     &mut schema,
@@ -404,6 +1096,13 @@ pub fn create_group_window(app: &mut AppState, root_ui: &mut egui::Ui) {
                     None,
                 );
             });
+        app.sync_reserved_cores_from_form();
+        if test_affinity_clicked {
+            app.request_affinity_probe();
+        }
+        if refresh_cpu_clicked {
+            app.refresh_cpu_topology();
+        }
     });
 
     if create_clicked || cancel_clicked {
@@ -417,10 +1116,13 @@ pub fn create_group_window(app: &mut AppState, root_ui: &mut egui::Ui) {
 
 /// Group editing window.
 pub fn edit_group_window(app: &mut AppState, root_ui: &mut egui::Ui) {
+    let ctx = root_ui.ctx().clone();
     CentralPanel::default().show(root_ui, |ui| {
         let mut save_clicked = false;
         let mut delete_clicked = false;
         let mut cancel_clicked = false;
+        let mut test_affinity_clicked = false;
+        let mut refresh_cpu_clicked = false;
 
         ui.add_space(3.0);
         ui.horizontal(|ui| {
@@ -439,15 +1141,23 @@ pub fn edit_group_window(app: &mut AppState, root_ui: &mut egui::Ui) {
                 {
                     cancel_clicked = true;
                 }
+                if ghost_button(ui, egui::Button::new("Refresh CPU"))
+                    .on_hover_text(REFRESH_CPU_HINT)
+                    .clicked()
+                {
+                    refresh_cpu_clicked = true;
+                }
             });
         });
         ui.add_space(6.0);
+        draw_group_name_conflict(ui, app);
 
         egui::ScrollArea::vertical()
             .auto_shrink([false, false])
             .show(ui, |ui| {
                 let mut schema = app.get_cpu_schema();
-                draw_group_form_ui(
+                app.ui.group_form.core_usage = app.core_usage();
+                test_affinity_clicked = draw_group_form_ui(
                     ui,
                     &mut app.ui.group_form,
                     &mut schema,
@@ -457,45 +1167,131 @@ pub fn edit_group_window(app: &mut AppState, root_ui: &mut egui::Ui) {
                     Some(&mut || delete_clicked = true),
                 );
             });
+        app.sync_reserved_cores_from_form();
+        if test_affinity_clicked {
+            app.request_affinity_probe();
+        }
+        if refresh_cpu_clicked {
+            app.refresh_cpu_topology();
+        }
 
         if save_clicked {
             app.commit_group_form_session();
         }
 
         if delete_clicked {
-            app.delete_current_group_form_target();
+            app.ui.confirm_group_delete = true;
         }
 
         if cancel_clicked {
             app.cancel_group_form_session();
         }
     });
+
+    render_group_delete_confirmation(app, &ctx);
+}
+
+fn render_group_delete_confirmation(app: &mut AppState, ctx: &egui::Context) {
+    if !app.ui.confirm_group_delete {
+        return;
+    }
+    let Some((group_name, program_count)) = app.group_form_delete_summary() else {
+        app.ui.confirm_group_delete = false;
+        return;
+    };
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let modal = egui::Modal::new(egui::Id::new("group-delete-confirmation")).show(ctx, |ui| {
+        ui.set_max_width(320.0);
+        ui.label(RichText::new(format!("Delete group \"{group_name}\"?")).strong());
+        let detail = match program_count {
+            0 => "The group has no programs.".to_string(),
+            1 => "Its 1 configured program is removed with it.".to_string(),
+            count => format!("Its {count} configured programs are removed with it."),
+        };
+        ui.label(
+            RichText::new(format!(
+                "{detail} You can undo this from the header for a few seconds."
+            ))
+            .small()
+            .weak(),
+        );
+        ui.add_space(6.0);
+        ui.horizontal(|ui| {
+            if toned_sized_button(
+                ui,
+                [72.0, 22.0],
+                egui::Button::new(RichText::new("Delete").size(BUTTON_FONT_SIZE)),
+                ToneRole::Danger,
+            )
+            .clicked()
+            {
+                confirmed = true;
+            }
+            if ghost_button(ui, egui::Button::new("Cancel")).clicked() {
+                cancelled = true;
+            }
+        });
+    });
+
+    if confirmed {
+        app.delete_current_group_form_target();
+    } else if cancelled || modal.should_close() {
+        app.ui.confirm_group_delete = false;
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        core_tile_text, core_tile_tokens, core_tile_widget_info, draw_core_buttons_for_test,
-        selected_core_tile_fill, CORE_TILE_WIDTH,
+        apply_affinity_mask, apply_core_preset, core_tile_hover_text, core_tile_text,
+        core_tile_tokens, core_tile_widget_info, draw_core_buttons_for_test, extend_core_range,
+        extend_core_range_from_key, masks_text, selected_core_tile_fill, selection_masks,
+        CorePreset, CORE_TILE_WIDTH,
     };
-    use crate::app::models::{CoreInfo, CoreType};
+    use crate::app::models::cpu_presets::get_preset_for_model;
+    use crate::app::models::{CoreInfo, CoreSelector, CoreType};
     use crate::app::shell::presenters::shared_elements::{
         inter_semibold_family, palette_for_dark_mode, ui_font_definitions, BUTTON_FONT_SIZE,
     };
     use crate::app::shell::GroupFormSession;
     use eframe::egui::{self, Pos2, RawInput, Rect, WidgetType};
+    use os_api::PriorityClass;
 
-    fn render_narrow_core_tiles(is_selected: bool) -> Vec<Rect> {
-        let ctx = egui::Context::default();
-        ctx.set_fonts(ui_font_definitions());
-        let mut groups = GroupFormSession {
+    fn form_session(core_selection: Vec<bool>) -> GroupFormSession {
+        GroupFormSession {
             editing_group_id: None,
             editing_selection: None,
-            core_selection: vec![is_selected; 20],
+            core_selection,
             group_name: String::new(),
             run_all_enabled: false,
+            single_numa_node: false,
+            default_priority: PriorityClass::Normal,
+            core_selector: CoreSelector::Fixed,
+            exclude_names: String::new(),
+            use_job_object: false,
+            cpu_rate_percent: None,
+            color: None,
             last_clicked_core: None,
-        };
+            keyboard_range_from: None,
+            active_profile: None,
+            save_to_profile: false,
+            base_cores: Vec::new(),
+            available_core_mask: usize::MAX,
+            reserved_cores: Vec::new(),
+            allow_reserved_cores: false,
+            affinity_probe_rx: None,
+            affinity_probe_result: None,
+            numa_nodes: Vec::new(),
+            core_usage: None,
+        }
+    }
+
+    fn render_narrow_core_tiles(is_selected: bool) -> Vec<Rect> {
+        let ctx = egui::Context::default();
+        ctx.set_fonts(ui_font_definitions());
+        let mut groups = form_session(vec![is_selected; 20]);
         let mut cores = (0..20)
             .map(|index| CoreInfo {
                 index,
@@ -522,6 +1318,153 @@ mod tests {
         tile_rects
     }
 
+    fn preset_cores() -> Vec<CoreInfo> {
+        (2..8)
+            .map(|index| CoreInfo {
+                index,
+                core_type: if index < 4 {
+                    CoreType::Performance
+                } else {
+                    CoreType::Efficient
+                },
+                label: format!("{index}"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_core_presets_pick_threads_within_the_rendered_set() {
+        let cores = preset_cores();
+        assert_eq!(CorePreset::FirstHalf.pick(&cores), vec![2, 3, 4]);
+        assert_eq!(CorePreset::SecondHalf.pick(&cores), vec![5, 6, 7]);
+        assert_eq!(CorePreset::EvenCores.pick(&cores), vec![2, 4, 6]);
+        assert_eq!(CorePreset::OddCores.pick(&cores), vec![3, 5, 7]);
+        assert_eq!(CorePreset::PerformanceOnly.pick(&cores), vec![2, 3]);
+        assert_eq!(
+            CorePreset::NoHyperThreading.pick(&cores),
+            vec![2, 3, 4, 5, 6, 7]
+        );
+    }
+
+    fn no_ht_pick_for_model(model: &str, total_threads: usize) -> Vec<usize> {
+        let schema = get_preset_for_model(model, total_threads).expect("preset should match");
+        schema
+            .clusters
+            .iter()
+            .flat_map(|cluster| CorePreset::NoHyperThreading.pick(&cluster.cores))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_ht_keeps_primary_p_threads_and_every_e_core_on_i9_13900k() {
+        let picked = no_ht_pick_for_model("13th Gen Intel(R) Core(TM) i9-13900K", 32);
+        let expected: Vec<usize> = (0..16).step_by(2).chain(16..32).collect();
+        assert_eq!(picked, expected);
+    }
+
+    #[test]
+    fn test_no_ht_keeps_one_thread_per_core_on_ryzen_ccds() {
+        let picked = no_ht_pick_for_model("AMD Ryzen 9 7950X3D 16-Core Processor", 32);
+        let expected: Vec<usize> = (0..32).step_by(2).collect();
+        assert_eq!(picked, expected);
+    }
+
+    #[test]
+    fn test_no_ht_falls_back_to_parity_without_a_matched_preset() {
+        let cores: Vec<CoreInfo> = (0..6)
+            .map(|index| CoreInfo {
+                index,
+                core_type: CoreType::Other,
+                label: format!("{index}"),
+            })
+            .collect();
+        assert_eq!(CorePreset::NoHyperThreading.pick(&cores), vec![0, 2, 4]);
+    }
+
+    #[test]
+    fn test_apply_core_preset_leaves_other_sets_alone_and_toggles_off() {
+        let cores = preset_cores();
+        let mut selection = vec![true, true, false, true, false, false, false, false, true];
+
+        assert!(apply_core_preset(
+            &mut selection,
+            &cores,
+            CorePreset::EvenCores
+        ));
+        assert_eq!(
+            selection,
+            vec![true, true, true, false, true, false, true, false, true]
+        );
+
+        assert!(apply_core_preset(
+            &mut selection,
+            &cores,
+            CorePreset::EvenCores
+        ));
+        assert_eq!(
+            selection,
+            vec![true, true, false, false, false, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn test_apply_affinity_mask_selects_only_selectable_threads_in_the_mask() {
+        let mut groups = form_session(vec![true, false, false, false, false, false]);
+        groups.available_core_mask = !0b10_0000;
+        groups.reserved_cores = vec![3];
+
+        assert!(apply_affinity_mask(&mut groups, 0b10_1110));
+        assert_eq!(
+            groups.core_selection,
+            vec![false, true, true, false, false, false]
+        );
+        assert!(!apply_affinity_mask(&mut groups, 0b10_1110));
+
+        groups.allow_reserved_cores = true;
+        assert!(apply_affinity_mask(&mut groups, 0b10_1110));
+        assert!(groups.core_selection[3]);
+    }
+
+    #[test]
+    fn test_selection_masks_split_per_processor_group() {
+        let mut selection = vec![true, false, true, true];
+        let masks = selection_masks(&selection);
+        assert_eq!(masks, vec![0b1101]);
+        assert_eq!(masks_text(&masks, |mask| format!("{mask:#X}")), "0xD");
+
+        selection.resize(66, false);
+        selection[65] = true;
+        let masks = selection_masks(&selection);
+        assert_eq!(masks, vec![0b1101, 0b10]);
+        assert_eq!(
+            masks_text(&masks, |mask| format!("{mask:#b}")),
+            "group 0: 0b1101, group 1: 0b10"
+        );
+    }
+
+    #[test]
+    fn test_keyboard_range_starts_at_focused_core_and_skips_locked_threads() {
+        let mut groups = form_session(vec![false; 6]);
+        groups.available_core_mask = !(1 << 3);
+
+        assert!(extend_core_range_from_key(&mut groups, 1, 2));
+        assert_eq!(groups.last_clicked_core, Some(1));
+        assert!(extend_core_range_from_key(&mut groups, 2, 4));
+        assert_eq!(
+            groups.core_selection,
+            vec![false, true, true, false, true, false]
+        );
+
+        // Anchored on a cleared thread, the range clears instead.
+        groups.last_clicked_core = Some(0);
+        assert!(extend_core_range(&mut groups, 0, 2));
+        assert_eq!(
+            groups.core_selection,
+            vec![false, false, false, false, true, false]
+        );
+        assert!(!extend_core_range(&mut groups, 9, 2));
+    }
+
     #[test]
     fn test_core_tile_text_keeps_full_label_and_thread_on_separate_lines() {
         let text = core_tile_text("P0", 0);
@@ -535,6 +1478,41 @@ mod tests {
         assert_eq!(text.accessible, "E3, thread 15");
     }
 
+    #[test]
+    fn test_core_tile_hover_text_names_sibling_and_cluster() {
+        let core = |index, core_type, label: &str| CoreInfo {
+            index,
+            core_type,
+            label: label.to_string(),
+        };
+        let cores = vec![
+            core(6, CoreType::Performance, "P3"),
+            core(7, CoreType::HyperThreading, "P3"),
+            core(16, CoreType::Efficient, "E0"),
+        ];
+
+        assert_eq!(
+            core_tile_hover_text(&cores[1], &cores, Some("Performance Cores"), None),
+            "P3, thread 7 (HT sibling of thread 6)\nCluster: Performance Cores"
+        );
+        assert_eq!(
+            core_tile_hover_text(&cores[0], &cores, Some("Performance Cores"), Some(0.426)),
+            "P3, thread 6 (Performance, HT sibling on thread 7)\nCluster: Performance Cores\nLoad: 43%"
+        );
+        assert_eq!(
+            core_tile_hover_text(&cores[2], &cores, Some("Efficient Cores"), None),
+            "E0, thread 16 (Efficient)\nCluster: Efficient Cores"
+        );
+        assert_eq!(
+            core_tile_hover_text(&cores[2], &cores, None, None),
+            "Thread 16"
+        );
+        assert_eq!(
+            core_tile_hover_text(&cores[2], &cores, None, Some(1.0)),
+            "Thread 16\nLoad: 100%"
+        );
+    }
+
     #[test]
     fn test_core_tile_metadata_keeps_button_and_selected_semantics() {
         let text = core_tile_text("E3", 15);
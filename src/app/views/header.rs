@@ -5,6 +5,7 @@ use crate::app::shell::presenters::shared_elements::{
 };
 use crate::app::shell::{GroupRoute, WindowRoute};
 use eframe::egui::{self, Color32, Layout, Margin, Panel, RichText, Stroke};
+use std::time::Duration;
 
 const NAVIGATION_SWITCH_WIDTH: f32 = 168.0;
 const THEME_BUTTON_SIZE: egui::Vec2 = egui::vec2(22.0, 20.0);
@@ -168,10 +169,143 @@ fn navigation_switch(app: &mut AppState, ui: &mut egui::Ui) {
                 if navigation_button(ui, activity, activity_selected).clicked() {
                     app.set_current_window(WindowRoute::Logs);
                 }
+
+                let processes_selected = matches!(app.ui.current_window, WindowRoute::Processes);
+                let processes = if processes_selected {
+                    RichText::new("Processes").size(BUTTON_FONT_SIZE).strong()
+                } else {
+                    RichText::new("Processes").size(BUTTON_FONT_SIZE)
+                };
+                if navigation_button(ui, processes, processes_selected).clicked() {
+                    app.set_current_window(WindowRoute::Processes);
+                }
+
+                let topology_selected = matches!(app.ui.current_window, WindowRoute::CpuTopology);
+                let topology = if topology_selected {
+                    RichText::new("Topology").size(BUTTON_FONT_SIZE).strong()
+                } else {
+                    RichText::new("Topology").size(BUTTON_FONT_SIZE)
+                };
+                if navigation_button(ui, topology, topology_selected).clicked() {
+                    app.set_current_window(WindowRoute::CpuTopology);
+                }
             });
         });
 }
 
+const BASE_PROFILE_LABEL: &str = "Base layout";
+
+fn profile_selector(app: &mut AppState, ui: &mut egui::Ui) {
+    let active = app.active_profile_name();
+    let mut selected = active.clone();
+    egui::ComboBox::from_id_salt("core_profile_selector")
+        .selected_text(active.as_deref().unwrap_or(BASE_PROFILE_LABEL))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(&mut selected, None, BASE_PROFILE_LABEL);
+            for name in app.profile_names() {
+                ui.selectable_value(&mut selected, Some(name.clone()), name);
+            }
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut app.ui.new_profile_name)
+                        .hint_text("New profile")
+                        .desired_width(110.0),
+                );
+                if ui.button("Add").clicked()
+                    && app.create_profile(&app.ui.new_profile_name.clone())
+                {
+                    app.ui.new_profile_name.clear();
+                    selected = app.active_profile_name();
+                }
+            });
+            if active.is_some() && ui.button("Delete active profile").clicked() {
+                app.delete_active_profile();
+                selected = None;
+            }
+        })
+        .response
+        .on_hover_text("Core profile applied to group launches");
+
+    if selected != app.active_profile_name() {
+        app.set_active_profile(selected.as_deref());
+    }
+}
+
+/// One-click masks; clicking one opens the Processes view to pick the process it applies to.
+fn favorites_bar(app: &mut AppState, ui: &mut egui::Ui) {
+    let picking = app
+        .ui
+        .process_list
+        .pick_favorite
+        .as_ref()
+        .map(|favorite| favorite.name.clone());
+    let mut pick = None;
+    let mut remove = None;
+    for favorite in app.favorites() {
+        let response = ui
+            .add(
+                egui::Button::new(RichText::new(&favorite.name).size(BUTTON_FONT_SIZE))
+                    .selected(picking.as_deref() == Some(favorite.name.as_str())),
+            )
+            .on_hover_text(format!(
+                "Threads {:?}; click, then pick a process in Processes",
+                favorite.cores
+            ));
+        response.context_menu(|ui| {
+            if ui.button("Remove favorite").clicked() {
+                remove = Some(favorite.name.clone());
+                ui.close();
+            }
+        });
+        if response.clicked() {
+            pick = Some(favorite);
+        }
+    }
+    ui.menu_button(RichText::new("+ Favorite").size(BUTTON_FONT_SIZE), |ui| {
+        ui.add(
+            egui::TextEdit::singleline(&mut app.ui.new_favorite_name)
+                .hint_text("Name, e.g. P-cores")
+                .desired_width(140.0),
+        );
+        ui.add(
+            egui::TextEdit::singleline(&mut app.ui.new_favorite_threads)
+                .hint_text("Threads, e.g. 0-3, 8")
+                .desired_width(140.0),
+        );
+        if ui.button("Add").clicked() && app.add_favorite_from_form() {
+            ui.close();
+        }
+    })
+    .response
+    .on_hover_text("Save a thread mask as a header button for applying to any process");
+
+    if let Some(name) = remove {
+        app.remove_favorite(&name);
+    }
+    if let Some(favorite) = pick {
+        app.start_favorite_pick(favorite);
+    }
+}
+
+fn undo_group_delete_button(app: &mut AppState, ui: &mut egui::Ui) {
+    let Some(group_name) = app.undoable_deleted_group_name() else {
+        return;
+    };
+    // Keep repainting so the button disappears once the undo window closes.
+    ui.ctx().request_repaint_after(Duration::from_secs(1));
+    if toned_button(
+        ui,
+        egui::Button::new(RichText::new("Undo delete").size(BUTTON_FONT_SIZE)),
+        ToneRole::Warning,
+    )
+    .on_hover_text(format!("Restore group \"{group_name}\""))
+    .clicked()
+    {
+        app.undo_group_delete();
+    }
+}
+
 pub fn draw_top_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
     let ctx = root_ui.ctx().clone();
     let cpu_model = app.get_cpu_schema().model;
@@ -202,6 +336,22 @@ pub fn draw_top_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
                             app.toggle_theme();
                             ctx.request_repaint();
                         }
+                        let on_top = app.is_always_on_top();
+                        if ui
+                            .add(
+                                egui::Button::new(RichText::new("On top").size(BUTTON_FONT_SIZE))
+                                    .selected(on_top),
+                            )
+                            .on_hover_text(if on_top {
+                                "Window stays above other windows; click to release it"
+                            } else {
+                                "Keep the window above other windows, e.g. a game in windowed mode"
+                            })
+                            .clicked()
+                        {
+                            app.toggle_always_on_top();
+                            ctx.request_repaint();
+                        }
                         if toned_button(
                             ui,
                             egui::Button::new(
@@ -213,6 +363,12 @@ pub fn draw_top_panel(app: &mut AppState, root_ui: &mut egui::Ui) {
                         {
                             app.start_creating_group();
                         }
+                        profile_selector(app, ui);
+                        undo_group_delete_button(app, ui);
+                        ui.separator();
+                        ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
+                            favorites_bar(app, ui);
+                        });
                     });
                 });
 
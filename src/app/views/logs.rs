@@ -1,12 +1,158 @@
+use crate::app::features::preferences::config_import::ConfigDiff;
 use crate::app::runtime::AppState;
 use crate::app::shell::presenters::shared_elements::{
-    glass_frame, palette, toned_button, ToneRole, BUTTON_FONT_SIZE,
+    danger_color, ghost_button, glass_frame, palette, success_color, toned_button, warning_color,
+    ToneRole, BUTTON_FONT_SIZE,
 };
-use eframe::egui::{self, CentralPanel, RichText, ScrollArea};
+use eframe::egui::{self, CentralPanel, Color32, RichText, ScrollArea};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogLevel {
+    Error,
+    Warning,
+    Ok,
+    Info,
+}
+
+/// Guesses the level of a log line from the upper-case words the app logs with.
+fn infer_log_level(line: &str) -> LogLevel {
+    let has_word = |word: &str| {
+        line.split(|c: char| !c.is_ascii_alphanumeric())
+            .any(|token| token == word)
+    };
+    if has_word("ERROR") {
+        LogLevel::Error
+    } else if has_word("WARNING") {
+        LogLevel::Warning
+    } else if has_word("OK") {
+        LogLevel::Ok
+    } else {
+        LogLevel::Info
+    }
+}
+
+fn log_level_color(ui: &egui::Ui, level: LogLevel) -> Color32 {
+    match level {
+        LogLevel::Error => danger_color(ui),
+        LogLevel::Warning => warning_color(ui),
+        LogLevel::Ok => success_color(ui),
+        LogLevel::Info => palette(ui).text_secondary,
+    }
+}
+
+fn theme_name(theme_index: usize) -> &'static str {
+    match theme_index {
+        0 => "System",
+        1 => "Light",
+        _ => "Dark",
+    }
+}
+
+/// Lines describing an import's changes, one per affected group, app or setting.
+fn config_diff_lines(diff: &ConfigDiff) -> Vec<String> {
+    let mut lines = Vec::new();
+    lines.extend(
+        diff.groups_added
+            .iter()
+            .map(|name| format!("+ Group {name}")),
+    );
+    lines.extend(
+        diff.groups_removed
+            .iter()
+            .map(|name| format!("- Group {name}")),
+    );
+    lines.extend(
+        diff.groups_changed
+            .iter()
+            .map(|name| format!("~ Group {name}")),
+    );
+    lines.extend(
+        diff.programs_added
+            .iter()
+            .map(|(group, app)| format!("+ {app} in {group}")),
+    );
+    lines.extend(
+        diff.programs_removed
+            .iter()
+            .map(|(group, app)| format!("- {app} from {group}")),
+    );
+    lines.extend(
+        diff.autorun_added
+            .iter()
+            .map(|(group, app)| format!("! {app} in {group} starts with the tool")),
+    );
+    lines.extend(
+        diff.post_launch_commands
+            .iter()
+            .map(|(group, app, command)| {
+                format!("! {app} in {group} runs after launch: {command}")
+            }),
+    );
+    if let Some((old, new)) = diff.theme_change {
+        lines.push(format!(
+            "~ Theme {} -> {}",
+            theme_name(old),
+            theme_name(new)
+        ));
+    }
+    lines
+}
+
+fn render_config_import_confirmation(app: &mut AppState, ctx: &egui::Context) {
+    let Some(pending) = app.ui.pending_config_import.as_ref() else {
+        return;
+    };
+    let lines = config_diff_lines(&pending.diff);
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let modal =
+        egui::Modal::new(egui::Id::new("logs-config-import-confirmation")).show(ctx, |ui| {
+            ui.set_max_width(360.0);
+            ui.label(RichText::new("Replace the current config?").strong());
+            ui.label(
+                RichText::new(pending.path.display().to_string())
+                    .small()
+                    .weak(),
+            );
+            ui.add_space(4.0);
+            if pending.diff.is_empty() {
+                ui.label(RichText::new("The file matches the current config.").small());
+            } else {
+                ScrollArea::vertical().max_height(220.0).show(ui, |ui| {
+                    for line in &lines {
+                        ui.label(RichText::new(line).small().monospace());
+                    }
+                });
+            }
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if toned_button(
+                    ui,
+                    egui::Button::new(RichText::new("Import").size(BUTTON_FONT_SIZE)),
+                    ToneRole::Primary,
+                )
+                .clicked()
+                {
+                    confirmed = true;
+                }
+                if ghost_button(ui, egui::Button::new("Cancel")).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirmed {
+        app.confirm_config_import();
+    } else if cancelled || modal.should_close() {
+        app.ui.pending_config_import = None;
+    }
+}
 
 pub fn draw_logs_window(app: &mut AppState, root_ui: &mut egui::Ui) {
     let mut clear_logs = false;
     let mut open_data_folder = false;
+    let mut import_config = false;
     let data_dir = app.active_data_dir();
     let hover = format!(
         "Open {} folder\n{}",
@@ -14,11 +160,8 @@ pub fn draw_logs_window(app: &mut AppState, root_ui: &mut egui::Ui) {
         data_dir.display()
     );
 
-    let entries = app
-        .log_manager
-        .formatted_entries()
-        .rev()
-        .collect::<Vec<_>>();
+    let newest = app.log_manager.formatted_entries().next_back();
+    let newest_arrived = app.ui.log_view.note_newest(newest.as_deref());
 
     CentralPanel::default()
         .frame(
@@ -53,33 +196,83 @@ pub fn draw_logs_window(app: &mut AppState, root_ui: &mut egui::Ui) {
                     {
                         open_data_folder = true;
                     }
+                    if ui
+                        .button(RichText::new("Import config").size(BUTTON_FONT_SIZE))
+                        .on_hover_text(
+                            "Load another copy of this tool's state file, e.g. one shared from another machine; its changes are shown before anything is replaced",
+                        )
+                        .clicked()
+                    {
+                        import_config = true;
+                    }
                 });
             });
 
             ui.add_space(5.0);
 
+            let view = &mut app.ui.log_view;
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut view.filter)
+                        .hint_text("Filter")
+                        .desired_width(160.0),
+                );
+                let order = if view.newest_first {
+                    "Newest first"
+                } else {
+                    "Oldest first"
+                };
+                if ghost_button(
+                    ui,
+                    egui::Button::new(RichText::new(order).size(BUTTON_FONT_SIZE)),
+                )
+                .on_hover_text("Switch the sort order")
+                .clicked()
+                {
+                    view.newest_first = !view.newest_first;
+                }
+                ui.checkbox(
+                    &mut view.auto_scroll,
+                    RichText::new("Auto-scroll").size(BUTTON_FONT_SIZE),
+                )
+                .on_hover_text("Keep the newest entry in view as new ones arrive");
+            });
+            let entries = view.visible_entries(app.log_manager.formatted_entries());
+
+            ui.add_space(5.0);
+
+            let mut scroll_area = ScrollArea::vertical().auto_shrink([false, false]);
+            if view.auto_scroll {
+                if !view.newest_first {
+                    scroll_area = scroll_area.stick_to_bottom(true);
+                } else if newest_arrived {
+                    scroll_area = scroll_area.vertical_scroll_offset(0.0);
+                }
+            }
+            let filtering = !view.filter.trim().is_empty();
+
             glass_frame(ui).show(ui, |ui| {
-                ScrollArea::vertical()
-                    .auto_shrink([false, false])
-                    .show(ui, |ui| {
-                        if entries.is_empty() {
-                            ui.label(RichText::new("No activity yet").small().weak().italics());
-                        }
-                        for (index, log_string) in entries.iter().enumerate() {
-                            egui::Frame::NONE
-                                .inner_margin(egui::Margin::symmetric(5, 3))
-                                .show(ui, |ui| {
-                                    ui.label(
-                                        RichText::new(log_string)
-                                            .size(10.0)
-                                            .color(palette(ui).text_secondary),
-                                    );
-                                });
-                            if index + 1 < entries.len() {
-                                ui.separator();
-                            }
+                scroll_area.show(ui, |ui| {
+                    if entries.is_empty() {
+                        let empty = if filtering {
+                            "No entries match the filter"
+                        } else {
+                            "No activity yet"
+                        };
+                        ui.label(RichText::new(empty).small().weak().italics());
+                    }
+                    for (index, log_string) in entries.iter().enumerate() {
+                        egui::Frame::NONE
+                            .inner_margin(egui::Margin::symmetric(5, 3))
+                            .show(ui, |ui| {
+                                let color = log_level_color(ui, infer_log_level(log_string));
+                                ui.label(RichText::new(log_string).size(10.0).color(color));
+                            });
+                        if index + 1 < entries.len() {
+                            ui.separator();
                         }
-                    });
+                    }
+                });
             });
         });
 
@@ -90,4 +283,37 @@ pub fn draw_logs_window(app: &mut AppState, root_ui: &mut egui::Ui) {
     if open_data_folder {
         app.open_active_data_dir();
     }
+
+    if import_config {
+        if let Some(path) = rfd::FileDialog::new()
+            .add_filter("Config", &["json"])
+            .pick_file()
+        {
+            app.request_config_import(path);
+        }
+    }
+    render_config_import_confirmation(app, &root_ui.ctx().clone());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{infer_log_level, LogLevel};
+
+    #[test]
+    fn test_infer_log_level_matches_whole_upper_case_words() {
+        assert_eq!(
+            infer_log_level("[10:00:00] ERROR: Failed to save state"),
+            LogLevel::Error
+        );
+        assert_eq!(
+            infer_log_level("[10:00:00] WARNING: thread 3 is reserved"),
+            LogLevel::Warning
+        );
+        assert_eq!(infer_log_level("[10:00:00] Affinity test OK"), LogLevel::Ok);
+        assert_eq!(
+            infer_log_level("[10:00:00] Launched Bookkeeper with TOKEN"),
+            LogLevel::Info
+        );
+        assert_eq!(infer_log_level("[10:00:00] no errors"), LogLevel::Info);
+    }
 }
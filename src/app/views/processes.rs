@@ -0,0 +1,217 @@
+use crate::app::runtime::AppState;
+use crate::app::shell::presenters::shared_elements::{
+    glass_frame, toned_button, ToneRole, BUTTON_FONT_SIZE,
+};
+use eframe::egui::{self, CentralPanel, RichText, ScrollArea};
+use std::time::SystemTime;
+
+/// Thread indices set in an affinity mask, for the mask tooltip.
+fn mask_threads(mask: usize) -> Vec<usize> {
+    (0..usize::BITS as usize)
+        .filter(|&bit| mask & (1 << bit) != 0)
+        .collect()
+}
+
+fn refreshed_label(refreshed_at: Option<SystemTime>) -> String {
+    match refreshed_at.and_then(|at| at.elapsed().ok()) {
+        Some(age) if age.as_secs() < 60 => format!("Updated {}s ago", age.as_secs()),
+        Some(age) => format!("Updated {}m ago", age.as_secs() / 60),
+        None => "Not loaded yet".to_string(),
+    }
+}
+
+/// Read-only list of running processes; the selected one can take a group's settings,
+/// or the clicked one a favorite's mask while a favorite waits for its process.
+pub fn draw_processes_window(app: &mut AppState, root_ui: &mut egui::Ui) {
+    if app.ui.process_list.refreshed_at.is_none() {
+        app.request_process_list_refresh();
+    }
+
+    let groups = app.group_choices();
+    let mut refresh = false;
+    let mut apply = None;
+    let mut apply_favorite = None;
+    let mut cancel_pick = false;
+
+    CentralPanel::default()
+        .frame(
+            egui::Frame::NONE
+                .fill(root_ui.visuals().panel_fill)
+                .inner_margin(egui::Margin::symmetric(6, 4)),
+        )
+        .show(root_ui, |ui| {
+            let view = &mut app.ui.process_list;
+            ui.horizontal(|ui| {
+                ui.vertical(|ui| {
+                    ui.heading(RichText::new("Processes").strong());
+                    ui.label(
+                        RichText::new("Current affinity and priority of running processes")
+                            .small()
+                            .weak(),
+                    );
+                });
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let label = if view.is_refreshing() {
+                        "Refreshing…"
+                    } else {
+                        "Refresh"
+                    };
+                    if ui
+                        .add_enabled(
+                            !view.is_refreshing(),
+                            egui::Button::new(RichText::new(label).size(BUTTON_FONT_SIZE)),
+                        )
+                        .clicked()
+                    {
+                        refresh = true;
+                    }
+                    ui.label(
+                        RichText::new(refreshed_label(view.refreshed_at))
+                            .small()
+                            .weak(),
+                    );
+                });
+            });
+
+            ui.add_space(5.0);
+
+            ui.horizontal(|ui| {
+                ui.add(
+                    egui::TextEdit::singleline(&mut view.filter)
+                        .hint_text("Filter by name or PID")
+                        .desired_width(160.0),
+                );
+                ui.separator();
+                ui.label(RichText::new("Apply group").size(BUTTON_FONT_SIZE));
+                if view
+                    .target_group_id
+                    .as_ref()
+                    .is_some_and(|id| !groups.iter().any(|(group_id, _)| group_id == id))
+                {
+                    view.target_group_id = None;
+                }
+                let selected_name = view
+                    .target_group_id
+                    .as_ref()
+                    .and_then(|id| groups.iter().find(|(group_id, _)| group_id == id))
+                    .map_or("Choose a group", |(_, name)| name.as_str());
+                egui::ComboBox::from_id_salt("process_list_target_group")
+                    .selected_text(selected_name)
+                    .show_ui(ui, |ui| {
+                        for (group_id, name) in &groups {
+                            ui.selectable_value(
+                                &mut view.target_group_id,
+                                Some(group_id.clone()),
+                                name,
+                            );
+                        }
+                    });
+                let target = view.target_group_id.clone().zip(view.selected_pid);
+                let clicked = ui
+                    .add_enabled_ui(target.is_some(), |ui| {
+                        toned_button(
+                            ui,
+                            egui::Button::new(RichText::new("Apply").size(BUTTON_FONT_SIZE)),
+                            ToneRole::Primary,
+                        )
+                        .on_hover_text(
+                            "Set the group's threads and default priority on the selected process once; it is not tracked afterwards",
+                        )
+                        .on_disabled_hover_text("Select a process and a group first")
+                        .clicked()
+                    })
+                    .inner;
+                if clicked {
+                    apply = target;
+                }
+            });
+
+            ui.add_space(5.0);
+
+            if let Some(favorite) = &view.pick_favorite {
+                ui.horizontal(|ui| {
+                    ui.label(
+                        RichText::new(format!(
+                            "Click a process to limit it to favorite \"{}\" (threads {:?})",
+                            favorite.name, favorite.cores
+                        ))
+                        .strong(),
+                    );
+                    if ui.button("Cancel").clicked() {
+                        cancel_pick = true;
+                    }
+                });
+                ui.add_space(5.0);
+            }
+
+            let visible: Vec<_> = view.visible_processes().cloned().collect();
+            glass_frame(ui).show(ui, |ui| {
+                ScrollArea::vertical()
+                    .auto_shrink([false, false])
+                    .show(ui, |ui| {
+                        if visible.is_empty() {
+                            let empty = if view.is_refreshing() {
+                                "Reading processes…"
+                            } else {
+                                "No processes match the filter"
+                            };
+                            ui.label(RichText::new(empty).small().weak().italics());
+                            return;
+                        }
+                        egui::Grid::new("process_list_grid")
+                            .striped(true)
+                            .num_columns(4)
+                            .show(ui, |ui| {
+                                for heading in ["PID", "Name", "Affinity", "Priority"] {
+                                    ui.label(RichText::new(heading).small().strong());
+                                }
+                                ui.end_row();
+                                for process in &visible {
+                                    let selected = view.selected_pid == Some(process.pid);
+                                    if ui
+                                        .selectable_label(selected, process.pid.to_string())
+                                        .clicked()
+                                    {
+                                        view.selected_pid = Some(process.pid);
+                                        if view.pick_favorite.is_some() {
+                                            apply_favorite = Some(process.pid);
+                                        }
+                                    }
+                                    ui.label(&process.name);
+                                    ui.label(format!("{:#X}", process.affinity_mask))
+                                        .on_hover_text(format!(
+                                            "Threads {:?}",
+                                            mask_threads(process.affinity_mask)
+                                        ));
+                                    ui.label(format!("{:?}", process.priority.class_name()));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+        });
+
+    if refresh {
+        app.request_process_list_refresh();
+    }
+    if let Some((group_id, pid)) = apply {
+        app.apply_group_to_process(&group_id, pid);
+    }
+    if let Some(pid) = apply_favorite {
+        app.apply_favorite_to_process(pid);
+    }
+    if cancel_pick {
+        app.ui.process_list.pick_favorite = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mask_threads;
+
+    #[test]
+    fn test_mask_threads_lists_set_bits_in_order() {
+        assert_eq!(mask_threads(0b1011), vec![0, 1, 3]);
+        assert!(mask_threads(0).is_empty());
+    }
+}
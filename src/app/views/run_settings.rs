@@ -1,13 +1,24 @@
+use crate::app::features::topology;
+use crate::app::models::{LaunchBarrier, ReadyCondition, SpreadStrategy, ThreadMatch, ThreadRule};
 use crate::app::runtime::AppState;
 use crate::app::shell::presenters::shared_elements::{
-    danger_color, ghost_button, glass_frame, success_color, toned_button, ToneRole,
+    danger_color, ghost_button, glass_frame, success_color, toned_button, toned_sized_button,
+    warning_color, ToneRole, BUTTON_FONT_SIZE,
 };
 use crate::app::shell::sessions::RuleShortcutResult;
 use crate::app::shell::{GroupRoute, WindowRoute};
 use eframe::egui::{self, Align, CentralPanel, ComboBox, Layout, RichText, Vec2};
-use os_api::PriorityClass;
+use os_api::{IoPriority, PriorityClass, PriorityClassName, WindowShow};
 use std::path::PathBuf;
 
+/// Longest wait the editor offers before an autorun app starts, ten minutes.
+const MAX_AUTORUN_DELAY_MS: u32 = 600_000;
+
+#[cfg(target_os = "windows")]
+pub(crate) const REALTIME_WARNING: &str = "A Realtime process runs ahead of input and system tasks; if it busy-loops the whole machine can stop responding. Windows grants it only when this tool runs as administrator, otherwise High is used.";
+#[cfg(not(target_os = "windows"))]
+pub(crate) const REALTIME_WARNING: &str = "A Realtime process (SCHED_FIFO) runs ahead of everything else at its level; if it busy-loops the whole machine can stop responding. It needs root or CAP_SYS_NICE; without it High is tried instead.";
+
 #[cfg(target_os = "windows")]
 fn pick_binary_path() -> Option<PathBuf> {
     rfd::FileDialog::new()
@@ -20,6 +31,125 @@ fn pick_binary_path() -> Option<PathBuf> {
     rfd::FileDialog::new().pick_file()
 }
 
+#[cfg(target_os = "windows")]
+const ECO_QOS_HOVER_TEXT: &str =
+    "Run the app in Windows Efficiency mode: lower clocks and a preference for efficient cores";
+
+#[cfg(not(target_os = "windows"))]
+const ECO_QOS_HOVER_TEXT: &str =
+    "Run the app under SCHED_IDLE so it only gets CPU time the rest of the system leaves free";
+
+#[cfg(target_os = "windows")]
+const LAUNCH_BARRIER_HOVER_TEXT: &str = "Autorun and Run all start the apps after this one only once it shows a window or starts the named child process, e.g. a launcher before its game; after the timeout they start anyway with a warning";
+
+/// Linux has no window check, so only the child process condition is offered.
+#[cfg(not(target_os = "windows"))]
+const LAUNCH_BARRIER_HOVER_TEXT: &str = "Autorun and Run all start the apps after this one only once it starts the named child process, e.g. a launcher before its game; after the timeout they start anyway with a warning";
+
+fn window_show_label(window_show: WindowShow) -> &'static str {
+    match window_show {
+        WindowShow::Normal => "Normal",
+        WindowShow::Minimized => "Minimized",
+        WindowShow::Hidden => "Hidden",
+    }
+}
+
+fn io_priority_label(io_priority: IoPriority) -> &'static str {
+    match io_priority {
+        IoPriority::Normal => "Normal",
+        IoPriority::Low => "Low",
+        IoPriority::VeryLow => "Very low",
+    }
+}
+
+/// Editor rows for per-thread affinity rules; returns whether anything changed.
+fn spread_label(strategy: SpreadStrategy) -> &'static str {
+    match strategy {
+        SpreadStrategy::All => "All group cores",
+        SpreadStrategy::Pack { .. } => "Pack (lowest first)",
+        SpreadStrategy::Spread => "Spread (every other core)",
+    }
+}
+
+fn draw_thread_rules(
+    ui: &mut egui::Ui,
+    rules: &mut Vec<ThreadRule>,
+    group_cores: &[usize],
+) -> bool {
+    let mut changed = false;
+    let mut rule_to_remove = None;
+
+    for (i, rule) in rules.iter_mut().enumerate() {
+        ui.horizontal(|ui| {
+            ui.label(format!("{}:", i + 1));
+            let by_name = matches!(rule.matcher, ThreadMatch::Name(_));
+            ComboBox::from_id_salt(("thread_rule_match", i))
+                .selected_text(if by_name { "Name" } else { "Index" })
+                .width(70.0)
+                .show_ui(ui, |ui| {
+                    if ui.selectable_label(by_name, "Name").clicked() && !by_name {
+                        rule.matcher = ThreadMatch::Name(String::new());
+                        changed = true;
+                    }
+                    if ui.selectable_label(!by_name, "Index").clicked() && by_name {
+                        rule.matcher = ThreadMatch::Index(0);
+                        changed = true;
+                    }
+                });
+
+            match &mut rule.matcher {
+                ThreadMatch::Name(pattern) => {
+                    changed |= ui
+                        .add(
+                            egui::TextEdit::singleline(pattern)
+                                .hint_text("e.g. RenderThread")
+                                .desired_width(140.0),
+                        )
+                        .changed();
+                }
+                ThreadMatch::Index(index) => {
+                    changed |= ui
+                        .add(egui::DragValue::new(index).range(0..=4096))
+                        .changed();
+                }
+            }
+
+            for &core in group_cores {
+                let selected = rule.cores.contains(&core);
+                if ui.selectable_label(selected, core.to_string()).clicked() {
+                    if selected {
+                        rule.cores.retain(|&c| c != core);
+                    } else {
+                        rule.cores.push(core);
+                        rule.cores.sort_unstable();
+                    }
+                    changed = true;
+                }
+            }
+
+            if ui.button("Remove").clicked() {
+                rule_to_remove = Some(i);
+            }
+        });
+    }
+
+    if let Some(idx) = rule_to_remove {
+        rules.remove(idx);
+        changed = true;
+    }
+
+    ui.add_space(3.0);
+    if ui.button("Add Thread Rule").clicked() {
+        rules.push(ThreadRule {
+            matcher: ThreadMatch::Name(String::new()),
+            cores: Vec::new(),
+        });
+        changed = true;
+    }
+
+    changed
+}
+
 #[cfg(target_os = "windows")]
 fn browse_binary_hover_text() -> &'static str {
     "Select executable..."
@@ -46,6 +176,50 @@ fn shortcut_message_for_current_frame(
     }
 }
 
+/// Asks before switching the draft to Realtime priority; returns whether it was switched.
+fn render_realtime_confirmation(app: &mut AppState, ctx: &egui::Context) -> bool {
+    if !app.ui.app_edit_state.confirm_realtime {
+        return false;
+    }
+
+    let mut confirmed = false;
+    let mut cancelled = false;
+    let modal =
+        egui::Modal::new(egui::Id::new("run-settings-realtime-confirmation")).show(ctx, |ui| {
+            ui.set_max_width(320.0);
+            ui.label(RichText::new("Use Realtime priority?").strong());
+            ui.label(RichText::new(REALTIME_WARNING).small().weak());
+            ui.add_space(6.0);
+            ui.horizontal(|ui| {
+                if toned_sized_button(
+                    ui,
+                    [90.0, 22.0],
+                    egui::Button::new(RichText::new("Use Realtime").size(BUTTON_FONT_SIZE)),
+                    ToneRole::Warning,
+                )
+                .clicked()
+                {
+                    confirmed = true;
+                }
+                if ghost_button(ui, egui::Button::new("Cancel")).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+    if confirmed || cancelled || modal.should_close() {
+        app.ui.app_edit_state.confirm_realtime = false;
+    }
+    let Some(selected_app) = app.ui.app_edit_state.current_edit.as_mut() else {
+        return false;
+    };
+    if confirmed {
+        selected_app.priority =
+            PriorityClass::from_parts(PriorityClassName::Realtime, selected_app.priority.eco_qos());
+    }
+    confirmed
+}
+
 pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
     if app.ui.app_edit_state.target.is_none() {
         app.set_current_window(WindowRoute::Groups(GroupRoute::List));
@@ -60,9 +234,14 @@ pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
     let mut save_clicked = false;
     let mut delete_clicked = false;
     let mut create_shortcut_clicked = false;
+    let mut reresolve_clicked = false;
+    let mut resolved_args_choice = None;
     let mut draft_changed = false;
+    let mut realtime_requested = false;
     let shortcut_status = app.current_app_edit_shortcut_status();
     let shortcut_result = app.ui.app_edit_state.shortcut_result.clone();
+    let group_cores = app.current_app_edit_group_cores();
+    let cpu_schema = app.get_cpu_schema();
 
     CentralPanel::default().show(root_ui, |ui| {
         ui.add_space(3.0);
@@ -130,6 +309,21 @@ pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
                                         }
                                     }
                                 }
+
+                                if ui
+                                    .button("Re-resolve")
+                                    .on_hover_text(format!(
+                                        "Read the target of {} again, e.g. after an update moved the executable",
+                                        selected_app
+                                            .dropped_path()
+                                            .map(|path| path.display().to_string())
+                                            .unwrap_or_default()
+                                    ))
+                                    .clicked()
+                                {
+                                    reresolve_clicked = true;
+                                    draft_changed = true;
+                                }
                             });
                             ui.end_row();
                         } else {
@@ -151,52 +345,155 @@ pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
                         }
 
                         ui.label(RichText::new("Priority:").strong());
+                        let mut class = selected_app.priority.class_name();
                         ComboBox::from_id_salt("priority_combo")
-                            .selected_text(format!("{:?}", selected_app.priority))
+                            .selected_text(format!("{class:?}"))
                             .show_ui(ui, |ui| {
-                                draft_changed |= ui
-                                    .selectable_value(
-                                        &mut selected_app.priority,
-                                        PriorityClass::Realtime,
-                                        "RealTime",
-                                    )
-                                    .changed();
-                                draft_changed |= ui
-                                    .selectable_value(
-                                        &mut selected_app.priority,
-                                        PriorityClass::High,
-                                        "High",
-                                    )
-                                    .changed();
-                                draft_changed |= ui
-                                    .selectable_value(
-                                        &mut selected_app.priority,
-                                        PriorityClass::AboveNormal,
-                                        "Above Normal",
-                                    )
-                                    .changed();
-                                draft_changed |= ui
-                                    .selectable_value(
-                                        &mut selected_app.priority,
-                                        PriorityClass::Normal,
-                                        "Normal",
+                                for (option, label) in [
+                                    (PriorityClassName::Realtime, "RealTime"),
+                                    (PriorityClassName::High, "High"),
+                                    (PriorityClassName::AboveNormal, "Above Normal"),
+                                    (PriorityClassName::Normal, "Normal"),
+                                    (PriorityClassName::BelowNormal, "Below Normal"),
+                                    (PriorityClassName::Idle, "Low"),
+                                ] {
+                                    ui.selectable_value(&mut class, option, label);
+                                }
+                            });
+                        if class == PriorityClassName::Realtime
+                            && selected_app.priority.class_name() != PriorityClassName::Realtime
+                        {
+                            realtime_requested = true;
+                            class = selected_app.priority.class_name();
+                        }
+                        ui.end_row();
+
+                        ui.label("");
+                        let mut eco_qos = selected_app.priority.eco_qos();
+                        ui.checkbox(&mut eco_qos, "Efficiency mode (EcoQoS)")
+                            .on_hover_text(ECO_QOS_HOVER_TEXT);
+                        let priority = PriorityClass::from_parts(class, eco_qos);
+                        if priority != selected_app.priority {
+                            selected_app.priority = priority;
+                            draft_changed = true;
+                        }
+                        ui.end_row();
+
+                        ui.label(RichText::new("I/O priority:").strong());
+                        ComboBox::from_id_salt("io_priority_combo")
+                            .selected_text(io_priority_label(selected_app.io_priority))
+                            .show_ui(ui, |ui| {
+                                for option in [IoPriority::Normal, IoPriority::Low, IoPriority::VeryLow] {
+                                    draft_changed |= ui
+                                        .selectable_value(
+                                            &mut selected_app.io_priority,
+                                            option,
+                                            io_priority_label(option),
+                                        )
+                                        .changed();
+                                }
+                            })
+                            .response
+                            .on_hover_text(
+                                "Disk access priority; lower it for background copies, backups and indexers",
+                            );
+                        ui.end_row();
+
+                        if selected_app.bin_path().is_some() {
+                            ui.label(RichText::new("Window:").strong());
+                            // Window placement is only passed to new processes on Windows.
+                            ui.add_enabled_ui(cfg!(target_os = "windows"), |ui| {
+                                ComboBox::from_id_salt("window_show_combo")
+                                    .selected_text(window_show_label(selected_app.window_show))
+                                    .show_ui(ui, |ui| {
+                                        for option in
+                                            [WindowShow::Normal, WindowShow::Minimized, WindowShow::Hidden]
+                                        {
+                                            draft_changed |= ui
+                                                .selectable_value(
+                                                    &mut selected_app.window_show,
+                                                    option,
+                                                    window_show_label(option),
+                                                )
+                                                .changed();
+                                        }
+                                    })
+                                    .response
+                                    .on_hover_text(
+                                        "Minimized and hidden programs start without taking focus",
                                     )
-                                    .changed();
+                                    .on_disabled_hover_text("Only supported on Windows");
+                            });
+                            ui.end_row();
+                        }
+
+                        ui.label(RichText::new("Cores:").strong());
+                        ui.horizontal(|ui| {
+                            let pack_count = match selected_app.spread {
+                                SpreadStrategy::Pack { count } => count,
+                                _ => group_cores.len().div_ceil(2).max(1),
+                            };
+                            ComboBox::from_id_salt("spread_strategy_combo")
+                                .selected_text(spread_label(selected_app.spread))
+                                .show_ui(ui, |ui| {
+                                    for option in [
+                                        SpreadStrategy::All,
+                                        SpreadStrategy::Pack { count: pack_count },
+                                        SpreadStrategy::Spread,
+                                    ] {
+                                        draft_changed |= ui
+                                            .selectable_value(
+                                                &mut selected_app.spread,
+                                                option,
+                                                spread_label(option),
+                                            )
+                                            .changed();
+                                    }
+                                })
+                                .response
+                                .on_hover_text(
+                                    "The group picks the candidate cores; this decides which of them the app runs on. Pack uses the lowest-numbered ones, Spread every other physical core with its threads to spread heat.",
+                                );
+                            if let SpreadStrategy::Pack { count } = &mut selected_app.spread {
                                 draft_changed |= ui
-                                    .selectable_value(
-                                        &mut selected_app.priority,
-                                        PriorityClass::BelowNormal,
-                                        "Below Normal",
+                                    .add(
+                                    egui::DragValue::new(count)
+                                        .range(1..=group_cores.len().max(1))
+                                        .suffix(" threads"),
                                     )
                                     .changed();
-                                draft_changed |= ui
-                                    .selectable_value(
-                                        &mut selected_app.priority,
-                                        PriorityClass::Idle,
-                                        "Low",
+                            }
+                            draft_changed |= ui
+                                .checkbox(&mut selected_app.smt_off, "SMT off")
+                                .on_hover_text(
+                                    "Run on one thread per physical core; hyper-thread siblings are left out",
+                                )
+                                .changed();
+                        });
+                        ui.end_row();
+
+                        ui.label("");
+                        ui.vertical(|ui| {
+                            let shaped =
+                                topology::app_cores(&group_cores, selected_app, &cpu_schema);
+                            ui.label(
+                                RichText::new(format!(
+                                    "Runs on threads {shaped:?} (mask {:#X})",
+                                    topology::app_mask(&group_cores, selected_app, &cpu_schema)
+                                ))
+                                .small()
+                                .weak(),
+                            );
+                            if selected_app.smt_off && cpu_schema.clusters.is_empty() {
+                                ui.colored_label(
+                                    ui.visuals().warn_fg_color,
+                                    RichText::new(
+                                        "No preset matched this CPU; odd threads are assumed to be hyper-thread siblings",
                                     )
-                                    .changed();
-                            });
+                                    .small(),
+                                );
+                            }
+                        });
                         ui.end_row();
                     });
 
@@ -210,14 +507,154 @@ pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
                 {
                     draft_changed = true;
                 }
+                if selected_app.autorun {
+                    ui.horizontal(|ui| {
+                        ui.label("Order");
+                        draft_changed |= ui
+                            .add(egui::DragValue::new(&mut selected_app.autorun_order))
+                            .on_hover_text(
+                                "Autorun apps from every group start from the lowest order up; equal orders keep the group order",
+                            )
+                            .changed();
+                        ui.label("Delay");
+                        let mut delay_ms = selected_app.autorun_delay_ms.unwrap_or(0);
+                        if ui
+                            .add(
+                                egui::DragValue::new(&mut delay_ms)
+                                    .range(0..=MAX_AUTORUN_DELAY_MS)
+                                    .speed(100)
+                                    .suffix(" ms"),
+                            )
+                            .on_hover_text("Wait this long after the previous autorun app starts")
+                            .changed()
+                        {
+                            selected_app.autorun_delay_ms = (delay_ms > 0).then_some(delay_ms);
+                            draft_changed = true;
+                        }
+                    });
+                }
+                let mut has_barrier = selected_app.launch_barrier.is_some();
+                if ui
+                    .checkbox(
+                        &mut has_barrier,
+                        RichText::new("Wait until ready before the next app").strong(),
+                    )
+                    .on_hover_text(LAUNCH_BARRIER_HOVER_TEXT)
+                    .changed()
+                {
+                    selected_app.launch_barrier = has_barrier.then(LaunchBarrier::default);
+                    draft_changed = true;
+                }
+                if let Some(barrier) = selected_app.launch_barrier.as_mut() {
+                    ui.horizontal(|ui| {
+                        let waits_for_child =
+                            matches!(barrier.ready_when, ReadyCondition::Child(_));
+                        let mut wait_for_child = waits_for_child || !cfg!(target_os = "windows");
+                        let (window_picked, child_picked) = if cfg!(target_os = "windows") {
+                            (
+                                ui.radio_value(&mut wait_for_child, false, "Window appears")
+                                    .changed(),
+                                ui.radio_value(&mut wait_for_child, true, "Child process")
+                                    .changed(),
+                            )
+                        } else {
+                            ui.label("Child process");
+                            (false, !waits_for_child)
+                        };
+                        if window_picked || child_picked {
+                            barrier.ready_when = if wait_for_child {
+                                ReadyCondition::Child(String::new())
+                            } else {
+                                ReadyCondition::Window
+                            };
+                            draft_changed = true;
+                        }
+                        if let ReadyCondition::Child(name) = &mut barrier.ready_when {
+                            draft_changed |= ui
+                                .add(
+                                    egui::TextEdit::singleline(name)
+                                        .hint_text("game.exe")
+                                        .desired_width(120.0),
+                                )
+                                .changed();
+                        }
+                        ui.label("Timeout");
+                        draft_changed |= ui
+                            .add(
+                                egui::DragValue::new(&mut barrier.timeout_secs)
+                                    .range(1..=600)
+                                    .suffix(" s"),
+                            )
+                            .changed();
+                    });
+                }
+                draft_changed |= ui
+                    .checkbox(
+                        &mut selected_app.watch_autoapply,
+                        RichText::new("Apply when started elsewhere").strong(),
+                    )
+                    .on_hover_text(
+                        "Whenever this app is found running, e.g. started from a desktop shortcut, give it this group's cores and priority, even while monitoring is off",
+                    )
+                    .changed();
+                draft_changed |= ui
+                    .checkbox(
+                        &mut selected_app.focus_on_relaunch,
+                        RichText::new("Focus its window when started again").strong(),
+                    )
+                    .on_hover_text(
+                        "Launching the app while it runs raises its window; turn off for background apps without one",
+                    )
+                    .changed();
+                if selected_app.bin_path().is_some() {
+                    draft_changed |= ui
+                        .checkbox(
+                            &mut selected_app.inherit_affinity,
+                            RichText::new("Inherit affinity at launch").strong(),
+                        )
+                        .on_hover_text(
+                            "Start the app on the tool's own cores instead of setting its affinity from outside, for anti-cheats that flag such changes; skips the group's Job Object",
+                        )
+                        .changed();
+                    draft_changed |= ui
+                        .checkbox(
+                            &mut selected_app.use_shell_execute,
+                            RichText::new("Open with its default program").strong(),
+                        )
+                        .on_hover_text(
+                            "Open the file the way the file manager would, e.g. a document in its viewer; the process the shell reports gets this group's cores. Add the program under Tracked Process Names to capture every instance of it; shell hosts such as explorer.exe are never matched",
+                        )
+                        .changed();
+                }
                 ui.add_space(6.0);
                 ui.separator();
                 ui.add_space(6.0);
 
                 if selected_app.is_args_editable() {
-                    ui.label(RichText::new("Command Line Arguments:").strong());
+                    ui.label(RichText::new("Command Line Arguments:").strong())
+                        .on_hover_text(
+                            "{group}, {cores} and {mask} are replaced at launch; write {{ and }} for literal braces",
+                        );
                     ui.add_space(3.0);
 
+                    if let Some(resolved_args) = &app.ui.app_edit_state.resolved_args {
+                        ui.horizontal_wrapped(|ui| {
+                            ui.label(
+                                RichText::new(format!(
+                                    "The shortcut now passes {resolved_args:?}"
+                                ))
+                                .small(),
+                            );
+                            if ui.small_button("Use these").clicked() {
+                                resolved_args_choice = Some(true);
+                            }
+                            if ui.small_button("Keep mine").clicked() {
+                                resolved_args_choice = Some(false);
+                            }
+                        });
+                        ui.add_space(3.0);
+                    }
+
                     let mut arg_to_remove = None;
                     if selected_app.args.is_empty() {
                         ui.label(RichText::new("No arguments defined.").weak().italics());
@@ -251,6 +688,44 @@ pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
                     ui.add_space(6.0);
                 }
 
+                let mut run_after_launch = selected_app.post_launch_cmd.is_some();
+                if ui
+                    .checkbox(
+                        &mut run_after_launch,
+                        RichText::new("Run a command after launch").strong(),
+                    )
+                    .on_hover_text(
+                        "Starts a shell command with your user's rights once the app is running, e.g. a macro tool or fan curve script",
+                    )
+                    .changed()
+                {
+                    selected_app.post_launch_cmd = run_after_launch.then(String::new);
+                    draft_changed = true;
+                }
+                if let Some(command) = &mut selected_app.post_launch_cmd {
+                    ui.add_space(3.0);
+                    if ui
+                        .add(
+                            egui::TextEdit::singleline(command)
+                                .hint_text("e.g. fancontrol.exe --profile quiet --pid {pid}")
+                                .desired_width(f32::INFINITY),
+                        )
+                        .changed()
+                    {
+                        draft_changed = true;
+                    }
+                    ui.label(
+                        RichText::new(
+                            "Runs any command as written. {pid}, {group}, {cores} and {mask} are filled in; failures are logged but do not stop the app",
+                        )
+                        .small()
+                        .color(ui.visuals().warn_fg_color),
+                    );
+                }
+                ui.add_space(9.0);
+                ui.separator();
+                ui.add_space(6.0);
+
                 ui.label(RichText::new("Tracked Process Names:").strong());
                 ui.add_space(3.0);
 
@@ -287,6 +762,54 @@ pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
                     draft_changed = true;
                 }
 
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.label("Name pattern:");
+                    let mut pattern = selected_app.match_pattern.clone().unwrap_or_default();
+                    let response = ui
+                        .add(egui::TextEdit::singleline(&mut pattern).hint_text(r"e.g. game_\d+\.exe"))
+                        .on_hover_text(
+                            "Regex matched against the whole process name; any match gets this group's settings. Invalid patterns are matched literally.",
+                        );
+                    if response.changed() {
+                        selected_app.match_pattern =
+                            (!pattern.trim().is_empty()).then_some(pattern);
+                        draft_changed = true;
+                    }
+                });
+                if let Some(err) = selected_app.match_pattern_error() {
+                    ui.colored_label(
+                        warning_color(ui),
+                        RichText::new(format!(
+                            "Invalid pattern, matched literally: {}",
+                            err.lines().last().unwrap_or_default().trim()
+                        ))
+                        .small(),
+                    );
+                }
+
+                ui.add_space(9.0);
+                ui.separator();
+                ui.add_space(6.0);
+
+                egui::CollapsingHeader::new(RichText::new("Thread Affinity (advanced)").strong())
+                    .id_salt("thread_affinity_rules")
+                    .default_open(!selected_app.thread_affinity_rules.is_empty())
+                    .show(ui, |ui| {
+                        ui.label(
+                            RichText::new(
+                                "Pin threads matched by name or index to some of the group's cores; other threads keep the group affinity. Applied while process monitoring is on.",
+                            )
+                            .weak(),
+                        );
+                        ui.add_space(3.0);
+                        draft_changed |= draw_thread_rules(
+                            ui,
+                            &mut selected_app.thread_affinity_rules,
+                            &group_cores,
+                        );
+                    });
+
                 ui.add_space(9.0);
                 ui.separator();
                 ui.add_space(6.0);
@@ -372,6 +895,23 @@ pub fn draw_app_run_settings(app: &mut AppState, root_ui: &mut egui::Ui) {
         });
     });
 
+    if realtime_requested {
+        app.ui.app_edit_state.confirm_realtime = true;
+    }
+    if render_realtime_confirmation(app, root_ui.ctx()) {
+        draft_changed = true;
+    }
+    if reresolve_clicked {
+        app.reresolve_current_edit_target();
+    }
+    match resolved_args_choice {
+        Some(true) => {
+            app.accept_resolved_args();
+            draft_changed = true;
+        }
+        Some(false) => app.discard_resolved_args(),
+        None => {}
+    }
     if draft_changed {
         app.clear_current_app_shortcut_result();
     }
@@ -1,8 +1,16 @@
 mod app;
 mod tray;
 
-use app::shell::App;
-use app::startup::parse_startup_args;
+use app::instance_forwarding::{
+    EntryAction, PreparedStartupForwarding, EXIT_CLI_PARSE_ERROR, EXIT_GUI_STARTUP_ERROR,
+};
+use app::local_forwarding::{
+    prepare_startup_forwarding, run_headless_exit_code, run_service_exit_code,
+};
+use app::models::set_state_dir_override;
+use app::shared::logging;
+use app::shell::{main_viewport, App};
+use app::startup::{parse_startup_args, take_state_dir_flag, take_verbose_flag, StartupIntent};
 use eframe::{run_native, NativeOptions};
 use tokio::runtime::Runtime;
 
@@ -10,14 +18,36 @@ use tokio::runtime::Runtime;
 static GLOBAL: mimalloc::MiMalloc = mimalloc::MiMalloc;
 
 fn main() {
-    let startup_args = std::env::args().skip(1).collect::<Vec<_>>();
+    let mut startup_args = std::env::args().skip(1).collect::<Vec<_>>();
+    logging::init(take_verbose_flag(&mut startup_args));
+    match take_state_dir_flag(&mut startup_args) {
+        Ok(Some(dir)) => set_state_dir_override(dir),
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("Invalid startup arguments: {err:?}");
+            std::process::exit(EXIT_CLI_PARSE_ERROR);
+        }
+    }
     let startup_intent = match parse_startup_args(&startup_args) {
         Ok(intent) => intent,
         Err(err) => {
             eprintln!("Invalid startup arguments: {err:?}");
-            std::process::exit(2);
+            std::process::exit(EXIT_CLI_PARSE_ERROR);
         }
     };
+    if startup_intent == StartupIntent::Service {
+        std::process::exit(run_service_exit_code());
+    }
+    let PreparedStartupForwarding {
+        action,
+        mut forwarding_runtime,
+        forwarding_warning,
+    } = prepare_startup_forwarding(startup_intent);
+    let startup_intent = match action {
+        EntryAction::RunGui(intent) => intent,
+        EntryAction::RunHeadless(intent) => std::process::exit(run_headless_exit_code(intent)),
+        EntryAction::Exit(code) => std::process::exit(code),
+    };
 
     let rt = Runtime::new().expect("failed to create tokio runtime");
     let _guard = rt.enter();
@@ -28,17 +58,29 @@ fn main() {
         "CPU Affinity Tool",
         NativeOptions {
             run_and_return: true,
-            viewport: eframe::egui::ViewportBuilder::default()
-                .with_min_inner_size([470.0, 600.0])
-                .with_max_inner_size([470.0, 1000.0])
-                .with_maximize_button(false),
+            viewport: main_viewport(),
             ..Default::default()
         },
-        Box::new(move |cc| Ok(Box::new(App::new_with_startup_intent(cc, startup_intent)))),
+        Box::new(move |cc| {
+            let startup_requires_forwarding =
+                matches!(&startup_intent, StartupIntent::RunRule { .. })
+                    && forwarding_runtime.is_some();
+            let mut app = App::new_without_startup_intent(cc);
+            let forwarding_ready = app.install_forwarding_runtime(
+                forwarding_runtime.take(),
+                forwarding_warning,
+                &cc.egui_ctx,
+            );
+            app.handle_startup_intent_after_forwarding(
+                startup_intent,
+                startup_requires_forwarding && !forwarding_ready,
+            );
+            Ok(Box::new(app))
+        }),
     );
 
     if let Err(e) = res {
         eprintln!("Application error: {}", e);
-        std::process::exit(1);
+        std::process::exit(EXIT_GUI_STARTUP_ERROR);
     }
 }
@@ -5,11 +5,35 @@ use std::sync::mpsc::Receiver;
 #[derive(Debug, Clone)]
 pub enum TrayCmd {
     Show,
+    ToggleMonitoring,
+    Quit,
+}
+
+const TRAY_TITLE: &str = "CPU Affinity Tool";
+
+/// Tray tooltip with the number of apps currently tracked.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn tray_tooltip(tracked_apps: usize) -> String {
+    match tracked_apps {
+        0 => format!("{TRAY_TITLE} — no apps tracked"),
+        1 => format!("{TRAY_TITLE} — 1 app tracked"),
+        count => format!("{TRAY_TITLE} — {count} apps tracked"),
+    }
+}
+
+/// Tray menu text for the monitoring toggle in its current state.
+#[cfg_attr(not(target_os = "windows"), allow(dead_code))]
+pub fn monitoring_menu_label(monitoring_enabled: bool) -> &'static str {
+    if monitoring_enabled {
+        "Pause monitoring"
+    } else {
+        "Resume monitoring"
+    }
 }
 
 #[cfg(target_os = "windows")]
 mod sys {
-    use super::{Receiver, TrayCmd};
+    use super::{monitoring_menu_label, Receiver, TrayCmd, TRAY_TITLE};
     use std::sync::mpsc;
     use tray_icon::{
         menu::{Menu, MenuEvent, MenuId, MenuItem},
@@ -19,6 +43,7 @@ mod sys {
     pub struct TrayHandle {
         pub tray_icon: TrayIcon,
         pub rx: Receiver<TrayCmd>,
+        pub monitoring_item: MenuItem,
     }
 
     #[derive(Clone, Copy)]
@@ -30,6 +55,7 @@ mod sys {
     pub fn init_tray(
         ctx: eframe::egui::Context,
         hwnd: windows::Win32::Foundation::HWND,
+        monitoring_enabled: bool,
     ) -> Result<TrayHandle, String> {
         // Command channel
         let (tx, rx) = mpsc::channel::<TrayCmd>();
@@ -37,9 +63,16 @@ mod sys {
         // Build menu
         let menu = Menu::new();
         let show = MenuItem::with_id(MenuId::new("1"), "Restore", true, None);
+        let monitoring_item = MenuItem::with_id(
+            MenuId::new("2"),
+            monitoring_menu_label(monitoring_enabled),
+            true,
+            None,
+        );
         let quit = MenuItem::with_id(MenuId::new("3"), "Quit", true, None);
 
         menu.append(&show).map_err(|e| e.to_string())?;
+        menu.append(&monitoring_item).map_err(|e| e.to_string())?;
         menu.append(&quit).map_err(|e| e.to_string())?;
 
         // Icon: load PNG 32x32 RGBA from assets/icon.ico
@@ -51,7 +84,7 @@ mod sys {
 
         // Create tray
         let tray_icon = TrayIconBuilder::new()
-            .with_tooltip("CPU Affinity Tool")
+            .with_tooltip(TRAY_TITLE)
             .with_menu(Box::new(menu))
             .with_icon(icon)
             .with_menu_on_left_click(false)
@@ -73,8 +106,13 @@ mod sys {
                         let _ = tx.send(TrayCmd::Show);
                         ctx.request_repaint();
                     }
+                    "2" => {
+                        let _ = tx.send(TrayCmd::ToggleMonitoring);
+                        ctx.request_repaint();
+                    }
                     "3" => {
-                        std::process::exit(0);
+                        let _ = tx.send(TrayCmd::Quit);
+                        ctx.request_repaint();
                     }
                     _ => {}
                 }
@@ -99,7 +137,11 @@ mod sys {
             }));
         }
 
-        Ok(TrayHandle { tray_icon, rx })
+        Ok(TrayHandle {
+            tray_icon,
+            rx,
+            monitoring_item,
+        })
     }
 
     fn decode_png_rgba(bytes: &[u8]) -> Result<(Vec<u8>, u32, u32), String> {
@@ -126,3 +168,15 @@ mod sys {
 }
 
 pub use sys::init_tray;
+
+#[cfg(test)]
+mod tests {
+    use super::tray_tooltip;
+
+    #[test]
+    fn test_tray_tooltip_counts_tracked_apps() {
+        assert_eq!(tray_tooltip(0), "CPU Affinity Tool — no apps tracked");
+        assert_eq!(tray_tooltip(1), "CPU Affinity Tool — 1 app tracked");
+        assert_eq!(tray_tooltip(3), "CPU Affinity Tool — 3 apps tracked");
+    }
+}